@@ -0,0 +1,73 @@
+//! Brute-force vs. indexed concentration queries.
+//!
+//! Run with `cargo bench --bench concentration`. Each iteration samples a
+//! 50 × 100 grid (one Petri Dish frame) on the default dish and on a large,
+//! crowded dish where most sources are far from any given point.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use std::hint::black_box;
+
+const ROWS: usize = 50;
+const COLS: usize = 100;
+
+#[allow(clippy::cast_precision_loss)]
+fn grid_points(dish: &PetriDish) -> Vec<(f64, f64)> {
+    (0..ROWS)
+        .flat_map(|r| (0..COLS).map(move |c| (r, c)))
+        .map(|(r, c)| {
+            (
+                c as f64 * dish.width / COLS as f64,
+                r as f64 * dish.height / ROWS as f64,
+            )
+        })
+        .collect()
+}
+
+fn bench_dish(c: &mut Criterion, name: &str, dish: &PetriDish) {
+    let points = grid_points(dish);
+    let mut group = c.benchmark_group(name);
+    group.bench_function("brute_force", |b| {
+        b.iter(|| {
+            points
+                .iter()
+                .map(|&(x, y)| dish.get_concentration(black_box(x), black_box(y)))
+                .sum::<f64>()
+        });
+    });
+    group.bench_function("indexed", |b| {
+        b.iter(|| {
+            let index = dish.source_index();
+            points
+                .iter()
+                .map(|&(x, y)| dish.get_concentration_indexed(&index, black_box(x), black_box(y)))
+                .sum::<f64>()
+        });
+    });
+    group.finish();
+}
+
+fn concentration(c: &mut Criterion) {
+    bench_dish(
+        c,
+        "default_dish",
+        &PetriDish::generate(7, &GeneratorConfig::default()),
+    );
+
+    let crowded = GeneratorConfig {
+        dish: DishConfig {
+            width: 1000.0,
+            height: 500.0,
+            source_count_min: 400,
+            source_count_max: 400,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    bench_dish(c, "crowded_dish", &PetriDish::generate(7, &crowded));
+}
+
+criterion_group!(benches, concentration);
+criterion_main!(benches);
@@ -2,4 +2,5 @@
 #![allow(clippy::module_name_repetitions)]
 
 pub mod simulation;
+#[cfg(feature = "tui")]
 pub mod ui;
@@ -1,3 +1,32 @@
+//! Terminal front end of the simulation.
+//!
+//! Without a subcommand the binary runs the live dashboard: each tick every
+//! agent senses and updates, then the dish and panels are drawn. Keys: 'q'
+//! quits, 'l' cycles the dish layer, 'v' toggles the layout, 'r' resets the
+//! occupancy map, 'p' toggles the landmark pin cursor, 'a' and 'd' cycle the
+//! selected agent and the viewed dish, and ':' opens the command line
+//! (`goal`, `clear`, dish events, `set`, `controller`, `name`). Each tick's
+//! update and draw is timed by a `FrameBudget`, which degrades planning and
+//! drawing when frames run late.
+//!
+//! Modes:
+//! - `--seeds K`: the aggregate view of an `Ensemble` of K replicate runs.
+//! - `export DIR`: runs the configured setup headless and writes `run.csv`
+//!   (plus a notebook and knowledge file on request).
+//! - `sweep JOURNAL`: a resumable parameter sweep on a worker pool.
+//! - `evolve`: the evolutionary loop, headless or with `--dashboard`.
+//! - `replay`: records a run headless, then scrubs through it, optionally
+//!   overlaying exported runs (`--compare`).
+//! - `demo`: the built-in narrated tour.
+//! - `doctor`: self-checks of the simulation and the renderer.
+//!
+//! Every option parses in its own `parse_*_arg`, and a run's world and
+//! agents come from `build_run()`: the dishes from `build_world()` (a
+//! layout, preset, chain or generated dish) and the agents from
+//! `populate()`, seeded by `--seed`. Agent options apply to every agent, and
+//! the same parsers configure `replay`. Argument errors are reported before
+//! the terminal is taken over.
+
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::must_use_candidate)]
@@ -11,33 +40,1314 @@ mod simulation;
 mod ui;
 
 use std::io;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use ratatui::{Terminal, backend::CrosstermBackend};
+use rand::Rng;
+use ratatui::{Terminal, backend::CrosstermBackend, style::Color};
 
 use crate::simulation::{
     agent::Protozoa,
+    annotation::ParamCommand,
+    calibration::SensorCalibration,
+    circadian::Circadian,
+    config::DishConfig,
+    controller::{Controller, ControllerKind},
+    demo::{DemoPlayer, DemoTour},
+    doctor::{Check, Diagnosis},
+    ensemble::{Ensemble, Run},
     environment::PetriDish,
-    params::{DISH_HEIGHT, DISH_WIDTH},
+    events::{DishEvent, EVENT_COMMANDS},
+    evolution::{Evolution, EvolutionReport},
+    export::{ExportOptions, FAULT_DUMP, export_run},
+    fitness::FitnessConfig,
+    generator::GeneratorConfig,
+    goal::GoalCommand,
+    hunger::Hunger,
+    identity,
+    imitation::{Demonstration, Imitation},
+    inference::{BiasEstimator, Likelihood, TransitionModel, Volatility},
+    inheritance::BeliefInit,
+    kinematics::Kinematics,
+    layout::Layout,
+    memory::SpatialGrid,
+    numerics::NumericMode,
+    observer::SourceEventLog,
+    occupancy::OccupancyMap,
+    operators::{CrossoverOperator, MutationOperator, StepSize},
+    params::{
+        DEMO_SEED, ENSEMBLE_DEFAULT_RUNS, EVOLUTION_PREVIEW_STEPS, EXPORT_DEFAULT_TICKS,
+        FIT_UNITS_PER_COLUMN, FIT_UNITS_PER_ROW, MAX_PARTICLES, PARTICLE_COUNT,
+        PREDATOR_DEFAULT_COUNT, REPLAY_DEFAULT_TICKS, REPLAY_KEYFRAME_INTERVAL, REPLAY_TRAIL_TICKS,
+        SWEEP_DEFAULT_SEEDS, SWEEP_DEFAULT_TICKS,
+    },
+    pareto::Selection,
+    planning::PlanningBudget,
+    population::Population,
+    predator::Predator,
+    preset::EnvironmentPreset,
+    replay::{Recording, Trajectory},
+    scan::MIN_SCAN_ORIENTATIONS,
+    scenario::Scenario,
+    senescence::Senescence,
+    sensing::SensorSuite,
+    sensors::SensorArray,
+    social::SocialMode,
+    spawn::{SpawnConfig, StartPosition},
+    streams::Stream,
+    stress::StressResponse,
+    sweep::{Sweep, run_sweep},
+    world::World,
 };
 use crate::ui::{
-    DashboardState,
-    field::compute_field_grid,
-    render::{draw_dashboard, petri_dish_grid_size, world_to_grid_coords},
+    CommandLine, DashboardState, DishLayer, PinCursor,
+    aggregate::draw_aggregate_dashboard,
+    banner::{BANNER_HEIGHT, draw_banner},
+    evolution::{EvolutionView, draw_evolution_dashboard},
+    field::{
+        SOURCE_RING_GLYPH, compute_density_grid, compute_field_grid, compute_light_grid,
+        compute_occupancy_grid, compute_ph_grid, compute_pheromone_grid, compute_signal_grid,
+        compute_temperature_grid, overlay_flow_glyphs, overlay_sources,
+    },
+    frame_budget::{FieldCache, FrameBudget},
+    gradient::compute_gradient_grid,
+    layout::{DashboardLayout, LayoutKind},
+    render::{draw_dashboard, draw_dashboard_in, world_to_grid_coords},
+    scrubber::{Scrubber, draw_scrubber},
+    terminal::{RenderConfig, RenderOverrides, TerminalCaps, adapt_buffer, renderer_smoke_test},
 };
 
+/// Parses `--seeds [K]`: run K replicate seeds in the aggregate view.
+fn parse_seed_count(args: &[String]) -> Option<usize> {
+    let pos = args.iter().position(|a| a == "--seeds")?;
+    let count = args
+        .get(pos + 1)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(ENSEMBLE_DEFAULT_RUNS);
+    Some(count.max(1))
+}
+
+/// Parses `--goal X,Y[,PRIORITY]`: start with an external goal installed.
+fn parse_goal_arg(args: &[String]) -> Result<Option<GoalCommand>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--goal") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --goal X,Y[,PRIORITY]")?;
+    GoalCommand::parse(&format!("goal {}", spec.replace(',', " "))).map(Some)
+}
+
+/// Parses `--preset NAME`: start in a canonical benchmark layout.
+fn parse_preset_arg(args: &[String]) -> Result<Option<EnvironmentPreset>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--preset") else {
+        return Ok(None);
+    };
+    let usage = || {
+        let names: Vec<&str> = EnvironmentPreset::ALL.iter().map(|p| p.name()).collect();
+        format!("usage: --preset {}", names.join("|"))
+    };
+    let name = args.get(pos + 1).ok_or_else(usage)?;
+    EnvironmentPreset::parse(name).map(Some).ok_or_else(usage)
+}
+
+/// Parses `--controller NAME`: the policy every agent runs (active inference
+/// by default).
+fn parse_controller_arg(args: &[String]) -> Result<Option<ControllerKind>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--controller") else {
+        return Ok(None);
+    };
+    let usage = || {
+        let names: Vec<&str> = ControllerKind::ALL.iter().map(Controller::name).collect();
+        format!("usage: --controller {}", names.join("|"))
+    };
+    let name = args.get(pos + 1).ok_or_else(usage)?;
+    ControllerKind::parse(name).map(Some).ok_or_else(usage)
+}
+
+/// Parses `--social aggregate|avoid|school`: every agent's preference for
+/// the company of its neighbors.
+fn parse_social_arg(args: &[String]) -> Result<Option<SocialMode>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--social") else {
+        return Ok(None);
+    };
+    let usage = || {
+        let names: Vec<&str> = SocialMode::ALL.iter().map(|mode| mode.name()).collect();
+        format!("usage: --social {}", names.join("|"))
+    };
+    let name = args.get(pos + 1).ok_or_else(usage)?;
+    SocialMode::parse(name).map(Some).ok_or_else(usage)
+}
+
+/// Parses `--imitate RUN.csv[,RUN.csv...] [--imitation-strength S]`: exported
+/// runs every founder bootstraps its spatial and habit priors from.
+fn parse_imitate_arg(args: &[String]) -> Result<Option<Imitation>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--imitate") else {
+        if args.iter().any(|a| a == "--imitation-strength") {
+            return Err("--imitation-strength needs --imitate RUN.csv".into());
+        }
+        return Ok(None);
+    };
+    let paths = args
+        .get(pos + 1)
+        .ok_or("usage: --imitate RUN.csv[,RUN.csv...] [--imitation-strength S]")?;
+    let demonstrations = paths
+        .split(',')
+        .map(Demonstration::load)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut imitation = Imitation::new(demonstrations);
+    if let Some(i) = args.iter().position(|a| a == "--imitation-strength") {
+        let spec = args
+            .get(i + 1)
+            .ok_or("usage: --imitation-strength S (in [0, 1])")?;
+        imitation.strength = spec
+            .parse()
+            .map_err(|_| format!("invalid imitation strength: {spec}"))?;
+    }
+    imitation.validate()?;
+    Ok(Some(imitation))
+}
+
+/// Parses `--sensors N|BEARING:REACH,...`: every agent's chemoreceptor array
+/// (a fan of N receptors, or receptors placed relative to the sensor pair).
+fn parse_sensors_arg(args: &[String]) -> Result<Option<SensorArray>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--sensors") else {
+        return Ok(None);
+    };
+    let spec = args
+        .get(pos + 1)
+        .ok_or("usage: --sensors N|BEARING:REACH,...")?;
+    SensorArray::parse(spec).map(Some)
+}
+
+/// Parses `--suite LIST`: sensors every agent samples besides its
+/// chemoreceptor array (comma-separated names such as `temporal,touch`).
+fn parse_suite_arg(args: &[String]) -> Result<Option<SensorSuite>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--suite") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --suite NAME[,NAME...]")?;
+    SensorSuite::parse(spec).map(Some)
+}
+
+/// Parses `--layout PATH`: load walls and nutrients from an ASCII map or PNG.
+fn parse_layout_arg(args: &[String]) -> Result<Option<Layout>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--layout") else {
+        return Ok(None);
+    };
+    let path = args.get(pos + 1).ok_or("usage: --layout PATH")?;
+    Layout::load(path).map(Some)
+}
+
+/// Parses `--dish-seed N`: generate the dish reproducibly from a seed.
+fn parse_dish_seed_arg(args: &[String]) -> Result<Option<u64>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--dish-seed") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --dish-seed N")?;
+    spec.parse::<u64>()
+        .map(Some)
+        .map_err(|_| format!("invalid dish seed: {spec}"))
+}
+
+/// Parses `--seed S`: master seed of a run's agents (their start, random
+/// streams and planner), predators and, without `--dish-seed`, generated
+/// dishes; drawn at random when not given.
+fn parse_run_seed_arg(args: &[String]) -> Result<u64, String> {
+    let Some(pos) = args.iter().position(|a| a == "--seed") else {
+        return Ok(rand::rng().random());
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --seed S")?;
+    spec.parse::<u64>()
+        .map_err(|_| format!("invalid seed: {spec}"))
+}
+
+/// Parses `--dishes N`: a chain of N generated dishes linked by portals.
+fn parse_dishes_arg(args: &[String]) -> Result<Option<usize>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--dishes") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --dishes N")?;
+    spec.parse::<usize>()
+        .ok()
+        .filter(|&n| n >= 1)
+        .map(Some)
+        .ok_or_else(|| format!("invalid dish count: {spec}"))
+}
+
+/// Parses `--agents N`: N agents sharing the world.
+fn parse_agents_arg(args: &[String]) -> Result<Option<usize>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--agents") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --agents N")?;
+    spec.parse::<usize>()
+        .ok()
+        .filter(|&n| n >= 1)
+        .map(Some)
+        .ok_or_else(|| format!("invalid agent count: {spec}"))
+}
+
+/// Parses `--predators N`: N pursuit predators in the first dish.
+fn parse_predators_arg(args: &[String]) -> Result<usize, String> {
+    let Some(pos) = args.iter().position(|a| a == "--predators") else {
+        return Ok(PREDATOR_DEFAULT_COUNT);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --predators N")?;
+    spec.parse::<usize>()
+        .map_err(|_| format!("invalid predator count: {spec}"))
+}
+
+/// Parses `--render-config PATH`: override the detected glyphs, colors, mouse
+/// or layout. Without it the per-user config is read if it exists; either way
+/// the path is returned for saving the layout.
+fn parse_render_config_arg(args: &[String]) -> Result<(RenderOverrides, Option<PathBuf>), String> {
+    let Some(pos) = args.iter().position(|a| a == "--render-config") else {
+        let path = RenderOverrides::default_path(|name| std::env::var(name).ok());
+        let overrides = match &path {
+            Some(path) if path.exists() => RenderOverrides::load(path)?,
+            _ => RenderOverrides::default(),
+        };
+        return Ok((overrides, path));
+    };
+    let path = args.get(pos + 1).ok_or("usage: --render-config PATH")?;
+    Ok((RenderOverrides::load(path)?, Some(PathBuf::from(path))))
+}
+
+/// Dashboard layout and the render config it is saved to when toggled.
+struct LayoutSetting {
+    kind: LayoutKind,
+    path: Option<PathBuf>,
+}
+
+impl LayoutSetting {
+    /// Switches to the other layout and saves it (kept for this run only
+    /// without a config path).
+    fn toggle(&mut self) -> Result<(), String> {
+        self.kind = self.kind.next();
+        match &self.path {
+            Some(path) => RenderOverrides::save_layout(path, self.kind),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses `--config PATH`: load dish parameters from a TOML file.
+fn parse_config_arg(args: &[String]) -> Result<DishConfig, String> {
+    let Some(pos) = args.iter().position(|a| a == "--config") else {
+        return Ok(DishConfig::default());
+    };
+    let path = args.get(pos + 1).ok_or("usage: --config PATH")?;
+    DishConfig::load(path)
+}
+
+/// Parses `--light S`: light sensitivity (> 0 seeks light, < 0 avoids it).
+fn parse_light_arg(args: &[String]) -> Result<Option<f64>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--light") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --light SENSITIVITY")?;
+    spec.parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .map(Some)
+        .ok_or_else(|| format!("invalid light sensitivity: {spec}"))
+}
+
+/// Parses `FLAG A,B`: two finite numbers named in `names` (`A,B`), accepted
+/// when `valid(a, b)`.
+fn parse_pair_arg(
+    args: &[String],
+    flag: &str,
+    names: &str,
+    valid: fn(f64, f64) -> bool,
+) -> Result<Option<(f64, f64)>, String> {
+    let Some(pos) = args.iter().position(|a| a == flag) else {
+        return Ok(None);
+    };
+    let spec = args
+        .get(pos + 1)
+        .ok_or_else(|| format!("usage: {flag} {names}"))?;
+    let invalid = || {
+        let what = flag.trim_start_matches('-');
+        format!("invalid {what}: {spec} (expected {names})")
+    };
+    let (a, b) = spec.split_once(',').ok_or_else(invalid)?;
+    let number = |text: &str| text.trim().parse::<f64>().ok().filter(|v| v.is_finite());
+    match (number(a), number(b)) {
+        (Some(a), Some(b)) if valid(a, b) => Ok(Some((a, b))),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses `--trail DEPOSIT,SENSITIVITY`: every agent lays a pheromone trail
+/// and follows (sensitivity > 0) or avoids (< 0) the trails it senses.
+fn parse_trail_arg(args: &[String]) -> Result<Option<(f64, f64)>, String> {
+    parse_pair_arg(args, "--trail", "DEPOSIT,SENSITIVITY", |deposit, _| {
+        deposit >= 0.0
+    })
+}
+
+/// Parses `--quorum EMISSION,PRECISION`: every agent broadcasts a quorum
+/// signal while exploiting and weights the signal it senses with `PRECISION`.
+fn parse_quorum_arg(args: &[String]) -> Result<Option<(f64, f64)>, String> {
+    parse_pair_arg(
+        args,
+        "--quorum",
+        "EMISSION,PRECISION",
+        |emission, precision| emission >= 0.0 && precision >= 0.0,
+    )
+}
+
+/// Parses `--crowding STIFFNESS,COST`: agents push apart when closer than
+/// `COLLISION_RADIUS` (stiffness in [0, 1]) and lose `COST` energy per second
+/// for each neighbor within `CROWDING_RADIUS`.
+fn parse_crowding_arg(args: &[String]) -> Result<Option<(f64, f64)>, String> {
+    parse_pair_arg(args, "--crowding", "STIFFNESS,COST", |stiffness, cost| {
+        (0.0..=1.0).contains(&stiffness) && cost >= 0.0
+    })
+}
+
+/// Parses `--beliefs blank|parent[:NOISE]|average|FILE`: what new agents
+/// know at birth (a file applies to the founders as well).
+fn parse_beliefs_arg(args: &[String]) -> Result<Option<BeliefInit>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--beliefs") else {
+        return Ok(None);
+    };
+    let spec = args
+        .get(pos + 1)
+        .ok_or("usage: --beliefs blank|parent[:NOISE]|average|FILE")?;
+    BeliefInit::parse(spec).map(Some)
+}
+
+/// Parses `--calibration SPEC`: miscalibrate the chemoreceptors
+/// (e.g. `gain_l=1.2,offset_r=0.1,swap`).
+fn parse_calibration_arg(args: &[String]) -> Result<Option<SensorCalibration>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--calibration") else {
+        return Ok(None);
+    };
+    let spec = args
+        .get(pos + 1)
+        .ok_or("usage: --calibration gain_l=G,gain_r=G,offset_l=O,offset_r=O,saturation=S,swap")?;
+    SensorCalibration::parse(spec).map(Some)
+}
+
+/// Parses `--learn-bias RATE`: learn and remove a left/right sensor offset.
+fn parse_learn_bias_arg(args: &[String]) -> Result<Option<f64>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--learn-bias") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --learn-bias RATE")?;
+    spec.parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(Some)
+        .ok_or_else(|| format!("invalid bias learning rate: {spec}"))
+}
+
+/// Parses `--dormancy THRESHOLD`: energy in [0, 1] below which agents with
+/// nothing to eat or head for go dormant.
+fn parse_dormancy_arg(args: &[String]) -> Result<Option<f64>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--dormancy") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --dormancy THRESHOLD")?;
+    spec.parse::<f64>()
+        .ok()
+        .filter(|v| (0.0..=1.0).contains(v))
+        .map(Some)
+        .ok_or_else(|| format!("invalid dormancy threshold: {spec}"))
+}
+
+/// Parses `--persistence LENGTH`: distance (at least 0) over which agents
+/// hold their heading while exploring.
+fn parse_persistence_arg(args: &[String]) -> Result<Option<f64>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--persistence") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --persistence LENGTH")?;
+    spec.parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(Some)
+        .ok_or_else(|| format!("invalid persistence length: {spec}"))
+}
+
+/// Parses `--hunger GAIN`: how strongly (at least 0) the energy deficit
+/// modulates the nutrient preference and the planner's drive.
+fn parse_hunger_arg(args: &[String]) -> Result<Option<Hunger>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--hunger") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --hunger GAIN")?;
+    let gain = spec
+        .parse::<f64>()
+        .map_err(|_| format!("invalid hunger gain: {spec}"))?;
+    let hunger = Hunger::new(gain);
+    hunger.validate()?;
+    Ok(Some(hunger))
+}
+
+/// Parses `--volatility COUPLING`: agents estimate how fast their world
+/// changes and scale their learning rates by it.
+fn parse_volatility_arg(args: &[String]) -> Result<Option<Volatility>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--volatility") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --volatility COUPLING")?;
+    let coupling = spec
+        .parse::<f64>()
+        .map_err(|_| format!("invalid volatility coupling: {spec}"))?;
+    let volatility = Volatility::new(coupling);
+    volatility.validate()?;
+    Ok(Some(volatility))
+}
+
+/// Parses `--learn-likelihood RATE`: agents learn the gain and offsets of
+/// their chemoreceptor observation function from prediction errors.
+fn parse_learn_likelihood_arg(args: &[String]) -> Result<Option<Likelihood>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--learn-likelihood") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --learn-likelihood RATE")?;
+    let rate = spec
+        .parse::<f64>()
+        .map_err(|_| format!("invalid likelihood learning rate: {spec}"))?;
+    let likelihood = Likelihood::new(rate);
+    likelihood.validate()?;
+    Ok(Some(likelihood))
+}
+
+/// Parses `--learn-transition RATE`: agents learn how their body carries
+/// out commands (speed gain, turn gain and drift) from their own moves.
+fn parse_learn_transition_arg(args: &[String]) -> Result<Option<TransitionModel>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--learn-transition") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --learn-transition RATE")?;
+    let rate = spec
+        .parse::<f64>()
+        .map_err(|_| format!("invalid transition learning rate: {spec}"))?;
+    let transition = TransitionModel::new(rate);
+    transition.validate()?;
+    Ok(Some(transition))
+}
+
+/// Parses `--particles [N]`: agents track a particle belief over the patch
+/// location, of `N` particles (`PARTICLE_COUNT` if none is given).
+fn parse_particles_arg(args: &[String]) -> Result<Option<usize>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--particles") else {
+        return Ok(None);
+    };
+    let Some(spec) = args.get(pos + 1).filter(|s| !s.starts_with("--")) else {
+        return Ok(Some(PARTICLE_COUNT));
+    };
+    spec.parse::<usize>()
+        .ok()
+        .filter(|n| (1..=MAX_PARTICLES).contains(n))
+        .map(Some)
+        .ok_or_else(|| format!("invalid particle count: {spec} (1 to {MAX_PARTICLES})"))
+}
+
+/// Parses `--senescence ONSET,RATE`: agents start to age after `ONSET`
+/// seconds, their vigor declining by `RATE` per second.
+fn parse_senescence_arg(args: &[String]) -> Result<Option<Senescence>, String> {
+    let pair = parse_pair_arg(args, "--senescence", "ONSET,RATE", |onset, rate| {
+        Senescence::new(onset, rate).validate().is_ok()
+    })?;
+    Ok(pair.map(|(onset, rate)| Senescence::new(onset, rate)))
+}
+
+/// Parses `--inertia MASS,MOMENT`: agents carry momentum, changing speed
+/// and turning rate against drag instead of at once.
+fn parse_inertia_arg(args: &[String]) -> Result<Option<Kinematics>, String> {
+    let pair = parse_pair_arg(args, "--inertia", "MASS,MOMENT", |mass, moment| {
+        Kinematics::new(mass, moment).validate().is_ok()
+    })?;
+    Ok(pair.map(|(mass, moment)| Kinematics::new(mass, moment)))
+}
+
+/// Parses `--circadian PERIOD,DEPTH`: agents keep an internal clock of
+/// `PERIOD` seconds, entrained by daylight, that lowers their metabolism and
+/// exploration by up to `DEPTH` at believed night.
+fn parse_circadian_arg(args: &[String]) -> Result<Option<Circadian>, String> {
+    let pair = parse_pair_arg(args, "--circadian", "PERIOD,DEPTH", |period, depth| {
+        Circadian::new(period, depth).validate().is_ok()
+    })?;
+    Ok(pair.map(|(period, depth)| Circadian::new(period, depth)))
+}
+
+/// Parses `--stress DEPTH,GAIN`: sustained surprise stresses agents, which
+/// cuts their sensory precision by up to `DEPTH`, multiplies their heading
+/// noise by up to `1 + GAIN` and makes them panic.
+fn parse_stress_arg(args: &[String]) -> Result<Option<StressResponse>, String> {
+    let pair = parse_pair_arg(args, "--stress", "DEPTH,GAIN", |depth, gain| {
+        StressResponse::new(depth, gain).validate().is_ok()
+    })?;
+    Ok(pair.map(|(depth, gain)| StressResponse::new(depth, gain)))
+}
+
+/// Parses `--scan K`: orientations a scan sweeps the sensor pair through,
+/// 0 (never scan) or at least `MIN_SCAN_ORIENTATIONS`.
+fn parse_scan_arg(args: &[String]) -> Result<Option<u32>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--scan") else {
+        return Ok(None);
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --scan ORIENTATIONS")?;
+    spec.parse::<u32>()
+        .ok()
+        .filter(|k| *k == 0 || *k >= MIN_SCAN_ORIENTATIONS)
+        .map(Some)
+        .ok_or_else(|| format!("invalid scan orientations: {spec}"))
+}
+
+/// Parses `--names NAME[,NAME...]`: names the first of `agent_count`
+/// founders in order (see `identity::validate_name`).
+fn parse_names_arg(args: &[String], agent_count: usize) -> Result<Vec<String>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--names") else {
+        return Ok(Vec::new());
+    };
+    let spec = args.get(pos + 1).ok_or("usage: --names NAME[,NAME...]")?;
+    let names = spec
+        .split(',')
+        .map(|name| identity::validate_name(name).map(str::to_string))
+        .collect::<Result<Vec<_>, _>>()?;
+    if names.len() > agent_count {
+        return Err(format!("{} names for {agent_count} agents", names.len()));
+    }
+    Ok(names)
+}
+
+/// Parses `export DIR [--ticks N] [--notebook] [--knowledge]`: write a
+/// headless run for offline analysis.
+fn parse_export_arg(args: &[String]) -> Result<Option<(String, ExportOptions)>, String> {
+    let Some(pos) = args.iter().position(|a| a == "export") else {
+        return Ok(None);
+    };
+    let usage = "usage: export DIR [--ticks N] [--notebook] [--knowledge]";
+    let dir = args
+        .get(pos + 1)
+        .filter(|d| !d.starts_with("--"))
+        .ok_or(usage)?;
+    let ticks = match args.iter().position(|a| a == "--ticks") {
+        None => EXPORT_DEFAULT_TICKS,
+        Some(i) => {
+            let spec = args.get(i + 1).ok_or(usage)?;
+            spec.parse()
+                .map_err(|_| format!("invalid tick count: {spec}"))?
+        }
+    };
+    let notebook = args.iter().any(|a| a == "--notebook");
+    let knowledge = args.iter().any(|a| a == "--knowledge");
+    Ok(Some((
+        dir.clone(),
+        ExportOptions {
+            ticks,
+            notebook,
+            knowledge,
+        },
+    )))
+}
+
+/// Parses `sweep JOURNAL --set NAME=V1,V2,... [--seeds K] [--ticks N] [--jobs J]`:
+/// a resumable parameter sweep over seeds 0..K on J worker threads (scored
+/// by `--fitness`, see `parse_fitness_arg`).
+fn parse_sweep_arg(
+    args: &[String],
+    dish_config: &DishConfig,
+) -> Result<Option<(String, Sweep, usize)>, String> {
+    let Some(pos) = args.iter().position(|a| a == "sweep") else {
+        return Ok(None);
+    };
+    let usage = "usage: sweep JOURNAL --set NAME=V1,V2,... [--seeds K] [--ticks N] [--jobs J]";
+    let journal = args
+        .get(pos + 1)
+        .filter(|d| !d.starts_with("--"))
+        .ok_or(usage)?;
+    let value_of = |flag: &str| -> Result<Option<&String>, String> {
+        match args.iter().position(|a| a == flag) {
+            None => Ok(None),
+            Some(i) => args.get(i + 1).map(Some).ok_or_else(|| usage.to_string()),
+        }
+    };
+    let count = |flag: &str, default: usize| -> Result<usize, String> {
+        value_of(flag)?.map_or(Ok(default), |spec| {
+            spec.parse::<usize>()
+                .ok()
+                .filter(|&n| n >= 1)
+                .ok_or_else(|| format!("invalid {flag} count: {spec}"))
+        })
+    };
+    let axis = value_of("--set")?.ok_or(usage)?;
+    let seeds = (0..count("--seeds", SWEEP_DEFAULT_SEEDS)? as u64).collect();
+    let ticks = count("--ticks", SWEEP_DEFAULT_TICKS as usize)? as u64;
+    let cores = std::thread::available_parallelism().map_or(1, std::num::NonZero::get);
+    let jobs = count("--jobs", cores)?;
+    let sweep = Sweep::parse_axis(parse_scenario(args, dish_config)?, axis, seeds, ticks)?;
+    Ok(Some((journal.clone(), sweep, jobs)))
+}
+
+/// Parses `--fitness SPEC`: what `sweep` and `evolve` score, either an
+/// objective name, weights like `lifespan=1,coverage=500` or a TOML file of
+/// weights. Lifespan alone without the flag.
+fn parse_fitness_arg(args: &[String]) -> Result<FitnessConfig, String> {
+    match args.iter().position(|a| a == "--fitness") {
+        None => Ok(FitnessConfig::default()),
+        Some(i) => {
+            let spec = args
+                .get(i + 1)
+                .ok_or("usage: --fitness NAME | NAME=W,... | FILE.toml")?;
+            let toml = std::path::Path::new(spec)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+            if toml {
+                FitnessConfig::load(spec)
+            } else {
+                FitnessConfig::parse(spec)
+            }
+        }
+    }
+}
+
+/// Headless scenario of `sweep` and `evolve`: `--preset NAME`, or a dish
+/// generated from `dish_config` per seed.
+fn parse_scenario(args: &[String], dish_config: &DishConfig) -> Result<Scenario, String> {
+    Ok(match parse_preset_arg(args)? {
+        Some(preset) => Scenario::Preset(preset),
+        None => Scenario::Generated {
+            generator: GeneratorConfig {
+                dish: *dish_config,
+                ..GeneratorConfig::default()
+            },
+            spawn: SpawnConfig::default(),
+        },
+    })
+}
+
+/// Parses `evolve [--generations G] [--population N] [--ticks T] [--seeds K] [--seed S]
+/// [--mutation uniform|gaussian|reset] [--crossover uniform|swap|blend]
+/// [--mutation-rate P] [--mutation-scale S] [--adaptive-steps] [--fitness SPEC]
+/// [--selection scalar|pareto] [--front PATH]`: generational selection on
+/// fitness (survival time by default) or on the Pareto front of its
+/// objectives, each genome evaluated on seeds 0..K; `--front` names the CSV
+/// the final front is written to.
+fn parse_evolve_arg(
+    args: &[String],
+    dish_config: &DishConfig,
+) -> Result<Option<(Evolution, Option<String>, bool)>, String> {
+    if !args.iter().any(|a| a == "evolve") {
+        return Ok(None);
+    }
+    let usage = "usage: evolve [--generations G] [--population N] [--ticks T] [--seeds K] \
+                 [--seed S] [--mutation uniform|gaussian|reset] [--crossover uniform|swap|blend] \
+                 [--mutation-rate P] [--mutation-scale S] [--adaptive-steps] [--fitness SPEC] \
+                 [--selection scalar|pareto] [--front PATH] [--dashboard]";
+    let value = |flag: &str| -> Result<Option<&String>, String> {
+        match args.iter().position(|a| a == flag) {
+            None => Ok(None),
+            Some(i) => args.get(i + 1).map(Some).ok_or_else(|| usage.to_string()),
+        }
+    };
+    // Rates and scales are shares in [0, 1]
+    let share = |flag: &str, default: f64| -> Result<f64, String> {
+        value(flag)?.map_or(Ok(default), |spec| {
+            spec.parse::<f64>()
+                .ok()
+                .filter(|p| (0.0..=1.0).contains(p))
+                .ok_or_else(|| format!("invalid {flag}: {spec}"))
+        })
+    };
+    // Counts start at 1, the seed at 0
+    let number = |flag: &str, default: u64, min: u64| -> Result<u64, String> {
+        match args.iter().position(|a| a == flag) {
+            None => Ok(default),
+            Some(i) => {
+                let spec = args.get(i + 1).ok_or(usage)?;
+                spec.parse::<u64>()
+                    .ok()
+                    .filter(|&n| n >= min)
+                    .ok_or_else(|| format!("invalid {flag}: {spec}"))
+            }
+        }
+    };
+    let mut evolution = Evolution::new(parse_scenario(args, dish_config)?);
+    evolution.generations = number("--generations", evolution.generations as u64, 1)? as usize;
+    evolution.population = number("--population", evolution.population as u64, 1)? as usize;
+    evolution.ticks = number("--ticks", evolution.ticks, 1)?;
+    evolution.seeds = (0..number("--seeds", evolution.seeds.len() as u64, 1)?).collect();
+    evolution.seed = number("--seed", evolution.seed, 0)?;
+    if let Some(name) = value("--mutation")? {
+        evolution.mutation =
+            MutationOperator::parse(name).ok_or_else(|| format!("invalid --mutation: {name}"))?;
+    }
+    if let Some(name) = value("--crossover")? {
+        evolution.crossover =
+            CrossoverOperator::parse(name).ok_or_else(|| format!("invalid --crossover: {name}"))?;
+    }
+    evolution.mutation_rate = share("--mutation-rate", evolution.mutation_rate)?;
+    evolution.mutation_scale = share("--mutation-scale", evolution.mutation_scale)?;
+    if args.iter().any(|a| a == "--adaptive-steps") {
+        evolution.step_size = StepSize::Adaptive;
+    }
+    evolution.fitness = parse_fitness_arg(args)?;
+    if let Some(name) = value("--selection")? {
+        evolution.selection =
+            Selection::parse(name).ok_or_else(|| format!("invalid --selection: {name}"))?;
+    }
+    let front = value("--front")?.cloned();
+    let dashboard = args.iter().any(|a| a == "--dashboard");
+    Ok(Some((evolution, front, dashboard)))
+}
+
+/// Glyph and color of a predator.
+const PREDATOR_GLYPH: (char, Color) = ('V', Color::Red);
+
+/// Colors of the source rings and of the dim and bright source centers on
+/// the sources layer.
+const SOURCE_TINTS: (Color, Color, Color) = (Color::DarkGray, Color::Yellow, Color::LightYellow);
+
+/// Glyphs (position, trail) and color of each run overlaid by `--compare`.
+const COMPARE_GLYPHS: [(char, char, Color); 2] =
+    [('A', 'a', Color::Cyan), ('B', 'b', Color::Magenta)];
+
+/// Parses `--compare RUN.csv [RUN.csv]`: exported runs to overlay in a replay.
+fn parse_compare_arg(args: &[String]) -> Result<Vec<Trajectory>, String> {
+    let Some(pos) = args.iter().position(|a| a == "--compare") else {
+        return Ok(Vec::new());
+    };
+    let paths: Vec<&String> = args[pos + 1..]
+        .iter()
+        .take_while(|a| !a.starts_with("--") && *a != "replay")
+        .take(COMPARE_GLYPHS.len())
+        .collect();
+    if paths.is_empty() {
+        return Err("usage: --compare RUN.csv [RUN.csv]".into());
+    }
+    paths.into_iter().map(Trajectory::load).collect()
+}
+
+/// Parses `replay [--ticks N] [--seed S] [--preset NAME] [--predators N]
+/// [--controller NAME] [--sensors SPEC] [--suite LIST] [--compare RUN.csv [RUN.csv]]`: record a headless run, then scrub through
+/// it with the compared runs overlaid (recorded as long as the longest
+/// without `--ticks`).
+fn parse_replay_arg(
+    args: &[String],
+    dish_config: &DishConfig,
+) -> Result<Option<(Run, u64, Vec<Trajectory>)>, String> {
+    if !args.iter().any(|a| a == "replay") {
+        if args.iter().any(|a| a == "--compare") {
+            return Err("--compare overlays runs in a replay (add `replay`)".into());
+        }
+        return Ok(None);
+    }
+    let compared = parse_compare_arg(args)?;
+    let usage = "usage: replay [--ticks N] [--seed S] [--preset NAME] [--predators N] \
+                 [--controller NAME] [--sensors SPEC] [--suite LIST] \
+                 [--compare RUN.csv [RUN.csv]]";
+    let number = |flag: &str| -> Result<Option<u64>, String> {
+        match args.iter().position(|a| a == flag) {
+            None => Ok(None),
+            Some(i) => {
+                let spec = args.get(i + 1).ok_or(usage)?;
+                spec.parse()
+                    .map(Some)
+                    .map_err(|_| format!("invalid {flag} value: {spec}"))
+            }
+        }
+    };
+    let longest = compared.iter().map(Trajectory::last_tick).max();
+    let ticks = number("--ticks")?.unwrap_or(longest.unwrap_or(REPLAY_DEFAULT_TICKS));
+    let seed = number("--seed")?.unwrap_or_else(|| rand::rng().random());
+    let scenario = match parse_preset_arg(args)? {
+        Some(preset) => Scenario::Preset(preset),
+        None => Scenario::Generated {
+            generator: GeneratorConfig {
+                dish: *dish_config,
+                ..GeneratorConfig::default()
+            },
+            spawn: SpawnConfig::default(),
+        },
+    };
+    let mut run = scenario.build(seed);
+    if let Some(controller) = parse_controller_arg(args)? {
+        run.agent.controller = controller;
+    }
+    if let Some(sensors) = parse_sensors_arg(args)? {
+        run.agent.morphology.sensors = sensors;
+    }
+    if let Some(suite) = parse_suite_arg(args)? {
+        run.agent.sensor_suite = suite;
+    }
+    if let Some(threshold) = parse_dormancy_arg(args)? {
+        run.agent.morphology.dormancy_threshold = threshold;
+    }
+    if let Some(length) = parse_persistence_arg(args)? {
+        run.agent.morphology.persistence_length = length;
+    }
+    if let Some(hunger) = parse_hunger_arg(args)? {
+        run.agent.generative_model.hunger = hunger;
+    }
+    if let Some(volatility) = parse_volatility_arg(args)? {
+        run.agent.generative_model.volatility = volatility;
+    }
+    if let Some(likelihood) = parse_learn_likelihood_arg(args)? {
+        run.agent.generative_model.likelihood = likelihood;
+    }
+    if let Some(transition) = parse_learn_transition_arg(args)? {
+        run.agent.generative_model.transition = transition;
+    }
+    if let Some(count) = parse_particles_arg(args)? {
+        run.agent
+            .track_particles(count, run.dish.width, run.dish.height);
+    }
+    if let Some(senescence) = parse_senescence_arg(args)? {
+        run.agent.morphology.senescence = senescence;
+    }
+    if let Some(kinematics) = parse_inertia_arg(args)? {
+        run.agent.morphology.kinematics = kinematics;
+    }
+    if let Some(circadian) = parse_circadian_arg(args)? {
+        run.agent.morphology.circadian = circadian;
+    }
+    if let Some(stress) = parse_stress_arg(args)? {
+        run.agent.morphology.stress = stress;
+    }
+    if let Some(orientations) = parse_scan_arg(args)? {
+        run.agent.morphology.scan_orientations = orientations;
+    }
+    if let Some(mode) = parse_social_arg(args)? {
+        run.agent.generative_model.social = mode.preference();
+    }
+    if let Some(imitation) = parse_imitate_arg(args)? {
+        imitation.instill(&mut run.agent);
+    }
+    run.dish.predators = Predator::scatter(
+        parse_predators_arg(args)?,
+        &run.dish,
+        &mut Stream::Predators.rng(seed),
+    );
+    Ok(Some((run, ticks, compared)))
+}
+
+/// Parses `demo [--seed S]`: the built-in tour, seeded with `DEMO_SEED`
+/// unless given.
+fn parse_demo_arg(args: &[String]) -> Result<Option<DemoTour>, String> {
+    if !args.iter().any(|a| a == "demo") {
+        return Ok(None);
+    }
+    let seed = match args.iter().position(|a| a == "--seed") {
+        None => DEMO_SEED,
+        Some(i) => {
+            let spec = args.get(i + 1).ok_or("usage: demo [--seed S]")?;
+            spec.parse()
+                .map_err(|_| format!("invalid --seed value: {spec}"))?
+        }
+    };
+    Ok(Some(DemoTour::builtin(seed)))
+}
+
+/// Builds the dishes of a run from the command line (a layout, a preset, a
+/// chain of generated dishes or one dish), with the spawn config of its
+/// agents. Generated dishes come from `--dish-seed`, or the run `seed`
+/// without it.
+fn build_world(
+    args: &[String],
+    dish_config: &DishConfig,
+    agent_count: usize,
+    seed: u64,
+) -> Result<(World, SpawnConfig), String> {
+    let preset = parse_preset_arg(args)?;
+    let layout = parse_layout_arg(args)?;
+    let dish_seed = parse_dish_seed_arg(args)?;
+    let dish_count = parse_dishes_arg(args)?;
+
+    let (width, height) = (dish_config.width, dish_config.height);
+    let config = GeneratorConfig {
+        dish: *dish_config,
+        ..GeneratorConfig::default()
+    };
+    let mut spawn = SpawnConfig::default();
+    if agent_count > 1 {
+        // Spread a population over the dish instead of stacking it at the center
+        spawn.position = StartPosition::Uniform;
+    }
+    Ok(match (layout, preset, dish_count) {
+        (Some(layout), _, _) => (
+            World::single(PetriDish::from_layout(&layout, width, height)),
+            layout.spawn_config(width, height),
+        ),
+        (None, Some(preset), _) => (
+            World::single(PetriDish::from_preset(preset, width, height)),
+            preset.spawn_config(width, height),
+        ),
+        (None, None, Some(count)) => (
+            World::chain(count, &config, dish_seed.unwrap_or(seed)),
+            spawn,
+        ),
+        (None, None, None) => match dish_seed {
+            Some(seed) => (World::single(PetriDish::generate(seed, &config)), spawn),
+            None => (
+                World::single(PetriDish::from_config(dish_config, seed)),
+                spawn,
+            ),
+        },
+    })
+}
+
+/// Scatters `predators` in the world's first dish and spawns `agents` there,
+/// all drawn from the streams of `seed` (a lone agent's streams are seeded
+/// with `seed` itself, as in a `Scenario`).
+fn populate(
+    world: &mut World,
+    spawn: &SpawnConfig,
+    agents: usize,
+    predators: usize,
+    seed: u64,
+) -> Population {
+    let mut rng = Stream::Predators.rng(seed);
+    world.dishes[0].predators = Predator::scatter(predators, &world.dishes[0], &mut rng);
+    let mut rng = Stream::Spawn.rng(seed);
+    if agents > 1 {
+        Population::spawn(agents, world, spawn, &mut rng)
+    } else {
+        let mut agent = spawn.spawn(&world.dishes[0], &mut rng);
+        agent.seed_streams(seed);
+        Population::single(agent)
+    }
+}
+
+/// Builds the world and agents of a single-world run from the command line.
+fn build_run(args: &[String], dish_config: &DishConfig) -> Result<(World, Population), String> {
+    let goal = parse_goal_arg(args)?;
+    let light_sensitivity = parse_light_arg(args)?;
+    let trail = parse_trail_arg(args)?;
+    let quorum = parse_quorum_arg(args)?;
+    let crowding = parse_crowding_arg(args)?;
+    let calibration = parse_calibration_arg(args)?;
+    let bias_rate = parse_learn_bias_arg(args)?;
+    let controller = parse_controller_arg(args)?;
+    let sensors = parse_sensors_arg(args)?;
+    let suite = parse_suite_arg(args)?;
+    let dormancy = parse_dormancy_arg(args)?;
+    let persistence = parse_persistence_arg(args)?;
+    let hunger = parse_hunger_arg(args)?.unwrap_or_default();
+    let volatility = parse_volatility_arg(args)?.unwrap_or_default();
+    let likelihood = parse_learn_likelihood_arg(args)?.unwrap_or_default();
+    let transition = parse_learn_transition_arg(args)?.unwrap_or_default();
+    let senescence = parse_senescence_arg(args)?.unwrap_or_default();
+    let kinematics = parse_inertia_arg(args)?.unwrap_or_default();
+    let circadian = parse_circadian_arg(args)?.unwrap_or_default();
+    let stress = parse_stress_arg(args)?.unwrap_or_default();
+    let scan = parse_scan_arg(args)?;
+    let social = parse_social_arg(args)?.map(SocialMode::preference);
+    let agent_count = parse_agents_arg(args)?.unwrap_or(1);
+    let predator_count = parse_predators_arg(args)?;
+    let strict = args.iter().any(|a| a == "--strict");
+    let names = parse_names_arg(args, agent_count)?;
+    let seed = parse_run_seed_arg(args)?;
+
+    let (mut world, spawn) = build_world(args, dish_config, agent_count, seed)?;
+    let mut population = populate(&mut world, &spawn, agent_count, predator_count, seed);
+    for (agent, name) in population.agents.iter_mut().zip(names) {
+        agent.name = Some(name);
+    }
+    if let Some((stiffness, cost)) = crowding {
+        population.crowding.stiffness = stiffness;
+        population.crowding.cost = cost;
+    }
+    for agent in &mut population.agents {
+        if let Some(sensitivity) = light_sensitivity {
+            agent.morphology.light_sensitivity = sensitivity;
+        }
+        if let Some((deposit, sensitivity)) = trail {
+            agent.morphology.pheromone_deposit = deposit;
+            agent.morphology.pheromone_sensitivity = sensitivity;
+        }
+        if let Some((emission, precision)) = quorum {
+            agent.morphology.signal_emission = emission;
+            agent.generative_model.prior_precision.signal = precision;
+        }
+        agent.sensor_noise = dish_config.sensor_noise;
+        agent.motor_noise = dish_config.motor_noise;
+        agent.inference = dish_config.inference;
+        if strict {
+            agent.numerics = NumericMode::Strict;
+        }
+        if let Some(calibration) = calibration {
+            agent.calibration = calibration;
+        }
+        if let Some(rate) = bias_rate {
+            agent.bias_estimator = BiasEstimator::new(rate);
+        }
+        if let Some(controller) = controller {
+            agent.controller = controller;
+        }
+        if let Some(sensors) = sensors {
+            agent.morphology.sensors = sensors;
+        }
+        if let Some(suite) = &suite {
+            agent.sensor_suite = suite.clone();
+        }
+        if let Some(threshold) = dormancy {
+            agent.morphology.dormancy_threshold = threshold;
+        }
+        if let Some(length) = persistence {
+            agent.morphology.persistence_length = length;
+        }
+        agent.morphology.senescence = senescence;
+        agent.morphology.kinematics = kinematics;
+        agent.morphology.circadian = circadian;
+        agent.morphology.stress = stress;
+        if let Some(orientations) = scan {
+            agent.morphology.scan_orientations = orientations;
+        }
+        agent.generative_model.social = social.unwrap_or_default();
+        agent.generative_model.hunger = hunger;
+        agent.generative_model.volatility = volatility;
+        agent.generative_model.likelihood = likelihood;
+        agent.generative_model.transition = transition;
+        if let Some(goal_command) = goal {
+            agent.apply_goal_command(goal_command);
+        }
+    }
+    instill_knowledge(args, &mut population)?;
+    if let Some(count) = parse_particles_arg(args)? {
+        for agent in &mut population.agents {
+            let dish = &world.dishes[agent.dish_id];
+            agent.track_particles(count, dish.width, dish.height);
+        }
+    }
+    Ok((world, population))
+}
+
+/// Gives every agent of `population` the beliefs (`--beliefs`) and habits
+/// (`--imitate`) asked for on the command line.
+fn instill_knowledge(args: &[String], population: &mut Population) -> Result<(), String> {
+    if let Some(beliefs) = parse_beliefs_arg(args)? {
+        for agent in &mut population.agents {
+            beliefs.instill(agent, None, &[]);
+        }
+        population.belief_init = beliefs;
+    }
+    if let Some(imitation) = parse_imitate_arg(args)? {
+        for agent in &mut population.agents {
+            imitation.instill(agent);
+        }
+    }
+    Ok(())
+}
+
+/// Runs `sweep` headless into `journal`, printing the final energy and the
+/// `fitness` score per value.
+fn run_sweep_journal(
+    sweep: &Sweep,
+    journal: &str,
+    jobs: usize,
+    fitness: &FitnessConfig,
+) -> Result<(), String> {
+    let report = run_sweep(sweep, journal, jobs)?;
+    println!(
+        "{journal}: {} cells resumed, {} run on {jobs} workers",
+        report.resumed, report.ran
+    );
+    let scores = report.fitness_by_value(fitness);
+    for ((value, energy), (_, score)) in report.final_energy_by_value().into_iter().zip(scores) {
+        println!(
+            "{}={value:<8} final energy {:.3} ± {:.3}, fitness {:.3} ± {:.3}",
+            sweep.param.name(),
+            energy.mean,
+            energy.std,
+            score.mean,
+            score.std
+        );
+    }
+    Ok(())
+}
+
+/// Runs `evolve` headless, printing a line per generation and the best
+/// genome, then writes the final Pareto front to `front` if given.
+fn run_evolution(evolution: &Evolution, front: Option<&str>) -> Result<(), String> {
+    println!("fitness: {}", evolution.fitness.describe());
+    let report = evolution.run(|summary| {
+        println!(
+            "gen {:>3}: fitness best {:.1} mean {:.1} ± {:.1}, survival best {:.0}, \
+             front {} (step {:.3})",
+            summary.generation,
+            summary.best.fitness,
+            summary.fitness.mean,
+            summary.fitness.std,
+            summary.best.survival,
+            summary.front.len(),
+            summary.mutation_scale
+        );
+    });
+    finish_evolution(&report, front)
+}
+
+/// Prints the best genome of a finished `evolve` run and writes its final
+/// Pareto front to `front` if given.
+fn finish_evolution(report: &EvolutionReport, front: Option<&str>) -> Result<(), String> {
+    if let Some(best) = report.best() {
+        println!("best genome: {}", best.genome.describe());
+    }
+    if let Some(path) = front {
+        std::fs::write(path, report.front_csv())
+            .map_err(|e| format!("cannot write {path}: {e}"))?;
+        println!("front written to {path}");
+    }
+    Ok(())
+}
+
+/// Runs `doctor [--config PATH] [--render-config PATH]`: the self-checks on
+/// the given (or default) configs, printing the diagnosis; fails if any check
+/// does.
+fn run_doctor(args: &[String]) -> Result<(), String> {
+    let mut diagnosis = Diagnosis::simulation(parse_config_arg(args));
+    diagnosis.push(renderer_smoke_test());
+    diagnosis.push(Check::from_result(
+        "render config",
+        parse_render_config_arg(args).map(|(_, path)| match path {
+            Some(path) if path.exists() => path.display().to_string(),
+            _ => "defaults".to_string(),
+        }),
+    ));
+    println!("terminal: {}", TerminalCaps::detect());
+    println!("{diagnosis}");
+    match diagnosis.failures() {
+        0 => Ok(()),
+        failed => Err(format!(
+            "{failed} of {} checks failed",
+            diagnosis.checks.len()
+        )),
+    }
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Setup Terminal
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|a| a == "doctor") {
+        return Ok(run_doctor(&args)?);
+    }
+    let seed_count = parse_seed_count(&args);
+    let dish_config = parse_config_arg(&args)?;
+    if let Some((journal, sweep, jobs)) = parse_sweep_arg(&args, &dish_config)? {
+        run_sweep_journal(&sweep, &journal, jobs, &parse_fitness_arg(&args)?)?;
+        return Ok(());
+    }
+    let evolve = match parse_evolve_arg(&args, &dish_config)? {
+        Some((evolution, front, false)) => {
+            run_evolution(&evolution, front.as_deref())?;
+            return Ok(());
+        }
+        Some((evolution, front, true)) => Some((evolution, front)),
+        None => None,
+    };
+    // Built before the terminal is taken over, so argument errors print normally
+    let replay = parse_replay_arg(&args, &dish_config)?.map(|(run, ticks, compared)| {
+        (
+            Recording::record(run, ticks, REPLAY_KEYFRAME_INTERVAL),
+            compared,
+        )
+    });
+    let demo = parse_demo_arg(&args)?;
+    // Master seed of the aggregate view's runs
+    let seed = parse_run_seed_arg(&args)?;
+    let run = match (seed_count, &evolve) {
+        (None, None) if demo.is_none() => Some(build_run(&args, &dish_config)?),
+        _ => None,
+    };
+
+    if let Some((dir, options)) = parse_export_arg(&args)? {
+        let (mut world, mut population) = run.ok_or("export runs a single agent (drop --seeds)")?;
+        if population.len() > 1 {
+            return Err("export runs a single agent (drop --agents)".into());
+        }
+        for path in export_run(&dir, &mut world, population.selected_mut(), options)? {
+            println!("wrote {}", path.display());
+        }
+        return Ok(());
+    }
+
+    let caps = TerminalCaps::detect();
+    let (overrides, config_path) = parse_render_config_arg(&args)?;
+    let render = RenderConfig::from_caps(caps).with_overrides(overrides);
+    let mut layout = LayoutSetting {
+        kind: overrides.layout.unwrap_or_default(),
+        path: config_path,
+    };
+    if args.iter().any(|a| a == "--capabilities") {
+        println!("terminal: {caps}");
+        println!("renderer: {render:?}");
+        return Ok(());
+    }
+
+    let mut terminal = enter_terminal(render)?;
+
+    // App State
+    let tick_rate = Duration::from_millis(50);
+
+    let fit = args.iter().any(|a| a == "--fit");
+    let mut evolved = None;
+    let res = if let Some((evolution, _)) = &evolve {
+        run_evolution_app(&mut terminal, evolution, render, tick_rate)
+            .map(|report| evolved = report)
+    } else if let Some((recording, compared)) = &replay {
+        run_replay_app(
+            &mut terminal,
+            recording,
+            compared,
+            render,
+            &mut layout,
+            tick_rate,
+        )
+    } else if let Some(tour) = demo {
+        let mut player = DemoPlayer::new(tour);
+        run_demo_app(&mut terminal, &mut player, render, &mut layout, tick_rate)
+    } else if let Some((mut world, mut population)) = run {
+        run_app(
+            &mut terminal,
+            &mut world,
+            &mut population,
+            render,
+            &mut layout,
+            fit,
+            tick_rate,
+        )
+    } else {
+        let count = seed_count.unwrap_or(ENSEMBLE_DEFAULT_RUNS);
+        let mut ensemble =
+            Ensemble::with_dish_config(count, &SpawnConfig::default(), &dish_config, seed);
+        run_ensemble_app(&mut terminal, &mut ensemble, render, tick_rate)
+    };
+
+    leave_terminal(&mut terminal, render)?;
+    if let Err(err) = res {
+        println!("{err:?}");
+    }
+    if let (Some((_, front)), Some(report)) = (&evolve, &evolved) {
+        finish_evolution(report, front.as_deref())?;
+    }
+
+    Ok(())
+}
+
+/// Takes over the terminal for a dashboard (raw mode, alternate screen,
+/// mouse capture if `render` allows it), warning if it is smaller than
+/// 80x24.
+fn enter_terminal(render: RenderConfig) -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if render.mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let terminal = Terminal::new(backend)?;
 
     // Check terminal size
     let size = terminal.size()?;
@@ -47,81 +1357,508 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             size.width, size.height
         );
     }
+    Ok(terminal)
+}
 
-    // App State
-    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
-    let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
-    let tick_rate = Duration::from_millis(50);
-
-    let res = run_app(&mut terminal, &mut dish, &mut agent, tick_rate);
-
-    // Restore Terminal
+/// Restores the terminal taken over by `enter_terminal`.
+fn leave_terminal(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    render: RenderConfig,
+) -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    if render.mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    terminal.show_cursor()
+}
 
-    if let Err(err) = res {
-        println!("{err:?}");
+/// With `fit` (`--fit`), resizes the dishes to the dish panel of a `size`
+/// terminal in `layout` if the panel changed since `fitted`; returns the panel
+/// size now fitted.
+fn fit_dishes(
+    fit: bool,
+    layout: LayoutKind,
+    size: ratatui::layout::Size,
+    fitted: Option<(usize, usize)>,
+    world: &mut World,
+    population: &mut Population,
+    occupancy: &mut [OccupancyMap],
+) -> Option<(usize, usize)> {
+    let area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
+    let (rows, cols) = DashboardLayout::new(layout, area).field_size();
+    if !fit || fitted == Some((rows, cols)) || rows == 0 || cols == 0 {
+        return fitted;
+    }
+    let width = cols as f64 * FIT_UNITS_PER_COLUMN;
+    let height = rows as f64 * FIT_UNITS_PER_ROW;
+    world.resize(width, height, &mut population.agents);
+    for map in occupancy {
+        map.resize(width, height);
     }
+    Some((rows, cols))
+}
 
-    Ok(())
+/// Stops the live run at the first strict agent that halted, writing its
+/// state dump to `FAULT_DUMP` in the working directory.
+fn check_faults(population: &Population) -> io::Result<()> {
+    let Some(fault) = population.agents.iter().find_map(|a| a.fault.as_deref()) else {
+        return Ok(());
+    };
+    let path = Path::new(FAULT_DUMP);
+    fault.write_dump(path).map_err(io::Error::other)?;
+    Err(io::Error::other(format!(
+        "{fault}; state dumped to {}",
+        path.display()
+    )))
 }
 
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
-    dish: &mut PetriDish,
-    agent: &mut Protozoa,
+    world: &mut World,
+    population: &mut Population,
+    render: RenderConfig,
+    layout: &mut LayoutSetting,
+    fit: bool,
     tick_rate: Duration,
 ) -> io::Result<()> {
     let mut last_tick = Instant::now();
+    // Dish panel size the dishes were last fitted to (`--fit`)
+    let mut fitted: Option<(usize, usize)> = None;
+    let mut occupancy = occupancy_maps(world);
+    // Source events per dish, for the Events panel
+    let mut source_logs = vec![SourceEventLog::default(); world.len()];
+    let mut layer = DishLayer::default();
+    let mut cursor = PinCursor::default();
+    let mut command = CommandLine::default();
+    // Dish shown in the panel; `None` follows the selected agent
+    let mut viewed: Option<usize> = None;
+    let mut frame_budget = FrameBudget::new(tick_rate);
+    let mut field_cache = FieldCache::default();
     loop {
         // 1. Update
-        if last_tick.elapsed() >= tick_rate {
-            dish.update();
-            agent.sense(dish);
-            agent.update_state(dish);
+        let started = Instant::now();
+        let ticked = last_tick.elapsed() >= tick_rate;
+        if ticked {
+            let cap = frame_budget.level.planning_cap();
+            step_live(world, population, &mut source_logs, &mut occupancy, cap);
             last_tick = Instant::now();
+            check_faults(population)?;
         }
+        fitted = fit_dishes(
+            fit,
+            layout.kind,
+            terminal.size()?,
+            fitted,
+            world,
+            population,
+            &mut occupancy,
+        );
+        let agent = population.selected();
+        let shown = viewed.unwrap_or(agent.dish_id);
 
         // 2. Render
         terminal.draw(|f| {
             let area = f.area();
+            let dish = &world.dishes[shown];
 
-            // Use the petri dish panel inner size for field computation
-            let (field_rows, field_cols) = petri_dish_grid_size(area);
+            // Compute background in parallel; a large swarm is drawn as a
+            // density, a lagging dashboard reuses or simplifies the field
+            let field = DashboardLayout::new(layout.kind, area).field_size();
+            let swarm = population.positions_in(shown);
+            let level = frame_budget.level;
+            let drawn = level.layer(layer).for_swarm(swarm.len());
+            let mut grid = field_cache.grid((drawn, shown, field), level.reuses_field(), || {
+                layer_grid(drawn, dish, shown, agent, &occupancy[shown], &swarm, field)
+            });
 
-            // Compute background in parallel
-            let mut grid = compute_field_grid(dish, field_rows, field_cols);
+            // Overlay markers on field
+            overlay_markers(&mut grid, world, shown, population, &cursor, drawn);
+
+            // Create dashboard state
+            let mut dashboard_state = live_state(world, population, shown);
+            dashboard_state.layer = drawn;
+            dashboard_state.layout = layout.kind;
+            dashboard_state.pin_cursor = cursor.position();
+            dashboard_state.command_line = command.display();
+            dashboard_state.source_events = source_logs[shown].iter().copied().collect();
+            dashboard_state.source_counts = source_logs[shown].counts();
+            dashboard_state.glyph_tints = glyph_tints(drawn);
+            dashboard_state.frame_status = frame_budget.status();
 
-            // Overlay Agent on field
-            if field_rows > 0 && field_cols > 0 {
-                let (r, c) = world_to_grid_coords(
-                    agent.x,
-                    agent.y,
-                    dish.width,
-                    dish.height,
-                    field_rows,
-                    field_cols,
-                );
-
-                if r < field_rows && c < field_cols {
-                    if let Some(line) = grid.get_mut(r) {
-                        if c < line.len() {
-                            line.replace_range(c..=c, "O");
+            // Draw the full dashboard
+            draw_dashboard(f, grid, &dashboard_state);
+            adapt_buffer(f.buffer_mut(), render);
+        })?;
+        if ticked {
+            frame_budget.record(started.elapsed());
+        }
+
+        // 3. Input
+        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)? {
+            let agent = population.selected_mut();
+            let dish = &world.dishes[agent.dish_id];
+            match event::read()? {
+                Event::Key(key) => match key.code {
+                    code if command.is_open() => {
+                        if let Some(text) = command.handle_key(code) {
+                            let dish = &mut world.dishes[agent.dish_id];
+                            run_command(&text, agent, dish, &mut command);
                         }
                     }
+                    KeyCode::Char(':') => command.open(),
+                    KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Char('l') => layer = layer.next(),
+                    KeyCode::Char('v') => command.message = layout.toggle().err(),
+                    KeyCode::Char('r') => occupancy[shown].reset(),
+                    KeyCode::Char('d') => {
+                        viewed = next_view(viewed, world.len());
+                        cursor.active = false;
+                    }
+                    // Pins belong to the selected agent, so a new selection drops the cursor
+                    KeyCode::Char('a') => {
+                        population.select_next();
+                        cursor.active = false;
+                    }
+                    // Pins belong to the agent's dish, so only pin while viewing it
+                    KeyCode::Char('p') if shown == agent.dish_id => {
+                        cursor.toggle(agent.x, agent.y);
+                    }
+                    KeyCode::Esc => cursor.active = false,
+                    code if cursor.active => handle_pin_key(code, &mut cursor, agent, dish),
+                    _ => {}
+                },
+                Event::Mouse(mouse)
+                    if cursor.active && mouse.kind == MouseEventKind::Down(MouseButton::Left) =>
+                {
+                    let size = terminal.size()?;
+                    let area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
+                    let dashboard = DashboardLayout::new(layout.kind, area);
+                    handle_pin_click(&dashboard, mouse, &mut cursor, agent, dish);
                 }
+                _ => {}
             }
+        }
+    }
+}
 
-            // Create dashboard state
-            let dashboard_state = DashboardState::from_agent(agent, dish);
+/// An empty occupancy map per dish of `world`.
+fn occupancy_maps(world: &World) -> Vec<OccupancyMap> {
+    world
+        .dishes
+        .iter()
+        .map(|dish| OccupancyMap::new(dish.width, dish.height))
+        .collect()
+}
 
-            // Draw the full dashboard
-            draw_dashboard(f, grid, &dashboard_state);
+/// Dashboard state of `population`'s selected agent with dish `shown` in the
+/// panel, labelled when there are several dishes or agents, with the memory
+/// readout covering the whole population.
+fn live_state(world: &World, population: &Population, shown: usize) -> DashboardState {
+    let agent = population.selected();
+    let mut state = DashboardState::from_agent(agent, &world.dishes[agent.dish_id]);
+    state.dish_label = dish_label(world.len(), shown, agent.dish_id);
+    state.agent_label = agent_label(population);
+    state.memory_bytes = population.memory_usage().total();
+    state
+}
+
+/// One tick of the live dashboard: agents replan within `cap`, the world and
+/// population step and the occupancy maps record where the agents went.
+fn step_live(
+    world: &mut World,
+    population: &mut Population,
+    source_logs: &mut [SourceEventLog],
+    occupancy: &mut [OccupancyMap],
+    cap: Option<PlanningBudget>,
+) {
+    for agent in &mut population.agents {
+        agent.planning_cap = cap;
+    }
+    world.update_observed(source_logs);
+    population.step(world);
+    for agent in &population.agents {
+        occupancy[agent.dish_id].record(agent.x, agent.y);
+    }
+}
+
+/// Background grid of a `field` (rows, columns) dish panel for `layer`,
+/// showing dish `shown` (the gradient layer compares against `agent`'s beliefs
+/// about it, the density layer counts the living agents at `swarm`).
+fn layer_grid(
+    layer: DishLayer,
+    dish: &PetriDish,
+    shown: usize,
+    agent: &Protozoa,
+    occupancy: &OccupancyMap,
+    swarm: &[(f64, f64)],
+    (field_rows, field_cols): (usize, usize),
+) -> Vec<String> {
+    match layer {
+        DishLayer::Nutrient => {
+            let mut grid = compute_field_grid(dish, field_rows, field_cols);
+            overlay_flow_glyphs(&mut grid, dish);
+            grid
+        }
+        DishLayer::Occupancy => compute_occupancy_grid(dish, occupancy, field_rows, field_cols),
+        DishLayer::Gradient => {
+            let fresh = SpatialGrid::new(dish.width, dish.height);
+            let priors = agent.spatial_priors_in(shown).unwrap_or(&fresh);
+            compute_gradient_grid(dish, priors, field_rows, field_cols)
+        }
+        DishLayer::Temperature => compute_temperature_grid(dish, field_rows, field_cols),
+        DishLayer::Light => compute_light_grid(dish, field_rows, field_cols),
+        DishLayer::Ph => compute_ph_grid(dish, field_rows, field_cols),
+        DishLayer::Pheromone => compute_pheromone_grid(dish, field_rows, field_cols),
+        DishLayer::Quorum => compute_signal_grid(dish, field_rows, field_cols),
+        DishLayer::Density => compute_density_grid(dish, swarm, field_rows, field_cols),
+        DishLayer::Sources => {
+            let mut grid = compute_field_grid(dish, field_rows, field_cols);
+            overlay_sources(&mut grid, dish);
+            grid
+        }
+    }
+}
+
+/// Next dish view for the `d` key: follow the agent, then each dish in turn.
+fn next_view(viewed: Option<usize>, dish_count: usize) -> Option<usize> {
+    match viewed {
+        None if dish_count > 1 => Some(0),
+        Some(i) if i + 1 < dish_count => Some(i + 1),
+        _ => None,
+    }
+}
+
+/// Sidebar title tag for the selected agent: its ID and name, and the
+/// survivors once any agent has died (just the name, if any, with a single
+/// agent).
+fn agent_label(population: &Population) -> Option<String> {
+    let agent = population.selected();
+    if population.len() < 2 {
+        return agent.name.clone();
+    }
+    let label = format!(
+        "{} {}/{}",
+        agent.label(),
+        population.selected_index() + 1,
+        population.len()
+    );
+    let alive = population.alive_count();
+    Some(if alive == population.len() {
+        label
+    } else {
+        format!("{label}, {alive} alive")
+    })
+}
+
+/// Panel title tag for the viewed dish (none in a single-dish world).
+fn dish_label(dish_count: usize, shown: usize, agent_dish: usize) -> Option<String> {
+    if dish_count < 2 {
+        return None;
+    }
+    let label = format!("Dish {}/{dish_count}", shown + 1);
+    Some(if shown == agent_dish {
+        label
+    } else {
+        format!("{label}, agent in {}", agent_dish + 1)
+    })
+}
+
+/// Draws predators (`V`), portals (`X`) and every agent (`agent_glyph`)
+/// in the shown dish, then, if the selected agent is there, its pinned
+/// landmarks (`P`), the external goal (`G`), the pin cursor (`+`) and the
+/// selected agent again on top of the field grid. Over the density `layer`
+/// only the selected agent is drawn.
+fn overlay_markers(
+    grid: &mut [String],
+    world: &World,
+    shown: usize,
+    population: &Population,
+    cursor: &PinCursor,
+    layer: DishLayer,
+) {
+    let dish = &world.dishes[shown];
+    put_predators(grid, dish);
+    let mut put = |x: f64, y: f64, marker: &str| put_marker(grid, dish, x, y, marker);
+    for portal in world.portals_in(shown) {
+        put(portal.x, portal.y, "X");
+    }
+    if layer != DishLayer::Density {
+        for other in population.in_dish(shown) {
+            put(other.x, other.y, agent_glyph(other));
+        }
+    }
+    let agent = population.selected();
+    if agent.dish_id != shown {
+        return;
+    }
+    for landmark in agent.episodic_memory.iter().filter(|l| l.is_pinned()) {
+        put(landmark.x, landmark.y, "P");
+    }
+    if let Some(goal) = &agent.goal {
+        put(goal.x, goal.y, "G");
+    }
+    if let Some((x, y)) = cursor.position() {
+        put(x, y, "+");
+    }
+    put(agent.x, agent.y, agent_glyph(agent));
+}
+
+/// Dish glyph of an agent: `O`, `o` while dormant, `x` once dead.
+fn agent_glyph(agent: &Protozoa) -> &'static str {
+    match (agent.is_alive(), agent.is_dormant()) {
+        (false, _) => "x",
+        (true, true) => "o",
+        (true, false) => "O",
+    }
+}
+
+/// Tinted dish glyphs over `layer`: predators, and on the sources layer the
+/// ring and the centers (`1`-`4` dim, `5`-`9` bright).
+fn glyph_tints(layer: DishLayer) -> Vec<(char, Color)> {
+    let mut tints = vec![PREDATOR_GLYPH];
+    if layer == DishLayer::Sources {
+        let (ring, dim, bright) = SOURCE_TINTS;
+        tints.push((SOURCE_RING_GLYPH, ring));
+        tints.extend(('1'..='4').map(|digit| (digit, dim)));
+        tints.extend(('5'..='9').map(|digit| (digit, bright)));
+    }
+    tints
+}
+
+/// Draws the predators of `dish` (`PREDATOR_GLYPH`).
+fn put_predators(grid: &mut [String], dish: &PetriDish) {
+    let glyph = PREDATOR_GLYPH.0.to_string();
+    for predator in &dish.predators {
+        put_marker(grid, dish, predator.x, predator.y, &glyph);
+    }
+}
+
+/// Writes `marker` into the grid cell showing `(x, y)` of `dish`.
+fn put_marker(grid: &mut [String], dish: &PetriDish, x: f64, y: f64, marker: &str) {
+    let rows = grid.len();
+    let cols = grid.first().map_or(0, String::len);
+    if rows == 0 || cols == 0 {
+        return;
+    }
+    let (r, c) = world_to_grid_coords(x, y, dish.width, dish.height, rows, cols);
+    if let Some(line) = grid.get_mut(r) {
+        if c < line.len() {
+            line.replace_range(c..=c, marker);
+        }
+    }
+}
+
+/// Executes a submitted command line (`goal X Y [PRIORITY]`, `clear`,
+/// `set NAME VALUE`, `controller NAME`, `name NAME`, or a `DishEvent`
+/// injected into the agent's dish).
+fn run_command(text: &str, agent: &mut Protozoa, dish: &mut PetriDish, command: &mut CommandLine) {
+    let first = text.split_whitespace().next().unwrap_or_default();
+    if EVENT_COMMANDS.contains(&first) {
+        if let Err(err) = DishEvent::parse(text).and_then(|event| dish.inject(event)) {
+            command.message = Some(err);
+        }
+        return;
+    }
+    if first == "set" {
+        command.message = Some(match ParamCommand::parse(text) {
+            Ok(change) => change.apply(&mut agent.morphology),
+            Err(err) => err,
+        });
+        return;
+    }
+    if first == "name" {
+        let name = text.split_whitespace().nth(1).unwrap_or_default();
+        if let Err(err) = agent.rename(name) {
+            command.message = Some(err);
+        }
+        return;
+    }
+    if first == "controller" {
+        let name = text.split_whitespace().nth(1).unwrap_or_default();
+        if let Some(controller) = ControllerKind::parse(name) {
+            agent.controller = controller;
+        } else {
+            let names: Vec<&str> = ControllerKind::ALL.iter().map(Controller::name).collect();
+            command.message = Some(format!("usage: controller {}", names.join("|")));
+        }
+        return;
+    }
+    match GoalCommand::parse(text) {
+        Ok(goal_command) => agent.apply_goal_command(goal_command),
+        Err(err) => command.message = Some(err),
+    }
+}
+
+/// Handles a cursor-mode click: pins the point under it on the dish panel of
+/// `dashboard` and moves the cursor there.
+fn handle_pin_click(
+    dashboard: &DashboardLayout,
+    mouse: MouseEvent,
+    cursor: &mut PinCursor,
+    agent: &mut Protozoa,
+    dish: &PetriDish,
+) {
+    if let Some((x, y)) =
+        dashboard.screen_to_world(mouse.column, mouse.row, dish.width, dish.height)
+    {
+        cursor.x = x;
+        cursor.y = y;
+        agent.episodic_memory.pin(x, y, agent.tick_count);
+    }
+}
+
+/// Handles cursor-mode keys: arrows move, Enter pins, Delete/Backspace unpins.
+fn handle_pin_key(code: KeyCode, cursor: &mut PinCursor, agent: &mut Protozoa, dish: &PetriDish) {
+    let (steps_x, steps_y) = match code {
+        KeyCode::Left => (-1.0, 0.0),
+        KeyCode::Right => (1.0, 0.0),
+        KeyCode::Up => (0.0, -1.0),
+        KeyCode::Down => (0.0, 1.0),
+        KeyCode::Enter => {
+            agent
+                .episodic_memory
+                .pin(cursor.x, cursor.y, agent.tick_count);
+            return;
+        }
+        KeyCode::Delete | KeyCode::Backspace => {
+            agent.episodic_memory.unpin(cursor.x, cursor.y);
+            return;
+        }
+        _ => return,
+    };
+    cursor.nudge(steps_x, steps_y, dish.width, dish.height);
+}
+
+fn run_ensemble_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    ensemble: &mut Ensemble,
+    render: RenderConfig,
+    tick_rate: Duration,
+) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+    let mut command = CommandLine::default();
+    loop {
+        // 1. Update (all runs in parallel)
+        if last_tick.elapsed() >= tick_rate {
+            ensemble.step();
+            last_tick = Instant::now();
+        }
+
+        // 2. Render
+        let history: Vec<_> = ensemble.history.iter().copied().collect();
+        let markers: Vec<_> = ensemble.markers.iter().cloned().collect();
+        let command_line = command.display();
+        terminal.draw(|f| {
+            draw_aggregate_dashboard(
+                f,
+                &history,
+                ensemble.len(),
+                &markers,
+                command_line.as_deref(),
+            );
+            adapt_buffer(f.buffer_mut(), render);
         })?;
 
         // 3. Input
@@ -129,12 +1866,311 @@ fn run_app<B: ratatui::backend::Backend>(
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    code if command.is_open() => {
+                        // Changes apply to every run and are marked on the charts
+                        if let Some(text) = command.handle_key(code) {
+                            command.message = Some(match ParamCommand::parse(&text) {
+                                Ok(change) => ensemble.apply_param(change),
+                                Err(err) => err,
+                            });
+                        }
+                    }
+                    KeyCode::Char(':') => command.open(),
+                    KeyCode::Char('q') => return Ok(()),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Runs `evolve --dashboard`: the evolution on a background thread, its
+/// generations and champion shown live until 'q'. Returns the report if the
+/// run finished before the dashboard closed.
+fn run_evolution_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    evolution: &Evolution,
+    render: RenderConfig,
+    tick_rate: Duration,
+) -> io::Result<Option<EvolutionReport>> {
+    let (sender, generations) = std::sync::mpsc::channel();
+    let background = evolution.clone();
+    let worker = std::thread::spawn(move || {
+        background.run(|summary| {
+            // The dashboard may have closed; the run still finishes
+            let _ = sender.send(summary.clone());
+        })
+    });
+    let mut view = EvolutionView::new(evolution);
+    let mut last_tick = Instant::now();
+    loop {
+        // 1. Update: new generations, then the champion preview
+        for summary in generations.try_iter() {
+            view.push(summary, evolution);
+        }
+        if last_tick.elapsed() >= tick_rate {
+            view.step_preview(evolution, EVOLUTION_PREVIEW_STEPS);
+            last_tick = Instant::now();
+        }
+
+        // 2. Render
+        terminal.draw(|f| {
+            draw_evolution_dashboard(f, &view);
+            adapt_buffer(f.buffer_mut(), render);
+        })?;
+
+        // 3. Input
+        let timeout = tick_rate
+            .checked_sub(last_tick.elapsed())
+            .unwrap_or_else(|| Duration::from_secs(0));
         if event::poll(timeout)? {
             if let Event::Key(key) = event::read()? {
                 if key.code == KeyCode::Char('q') {
-                    return Ok(());
+                    break;
                 }
             }
         }
     }
+    Ok(worker.is_finished().then(|| worker.join().ok()).flatten())
+}
+
+/// Splits the replay screen into the dashboard and the scrubber below it.
+fn replay_layout(area: ratatui::layout::Rect) -> [ratatui::layout::Rect; 2] {
+    use ratatui::layout::{Constraint, Layout as Split};
+    Split::vertical([Constraint::Min(0), Constraint::Length(4)]).areas(area)
+}
+
+fn run_replay_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    recording: &Recording,
+    compared: &[Trajectory],
+    render: RenderConfig,
+    layout: &mut LayoutSetting,
+    tick_rate: Duration,
+) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+    let mut scrubber = Scrubber::new(recording.len());
+    let mut run = recording.frame(0);
+    let event_ticks: Vec<u64> = recording.events().iter().map(|e| e.tick).collect();
+    // Occupancy is not recorded, so that layer stays blank in replays
+    let mut occupancy = OccupancyMap::new(run.dish.width, run.dish.height);
+    let mut layer = DishLayer::default();
+    loop {
+        // 1. Update
+        let at = scrubber.tick;
+        if last_tick.elapsed() >= tick_rate {
+            scrubber.advance();
+            last_tick = Instant::now();
+        }
+        if scrubber.tick != at {
+            recording.seek(&mut run, at, scrubber.tick);
+        }
+
+        // 2. Render
+        terminal.draw(|f| {
+            let [dashboard, timeline] = replay_layout(f.area());
+            let field = DashboardLayout::new(layout.kind, dashboard).field_size();
+            // A dead agent's cell is covered by its `x` marker
+            let swarm = [(run.agent.x, run.agent.y)];
+            let mut grid = layer_grid(layer, &run.dish, 0, &run.agent, &occupancy, &swarm, field);
+            overlay_compared(&mut grid, &run.dish, compared, scrubber.tick);
+            put_predators(&mut grid, &run.dish);
+            if let Some(goal) = &run.agent.goal {
+                put_marker(&mut grid, &run.dish, goal.x, goal.y, "G");
+            }
+            let marker = agent_glyph(&run.agent);
+            put_marker(&mut grid, &run.dish, run.agent.x, run.agent.y, marker);
+
+            let mut dashboard_state = DashboardState::from_agent(&run.agent, &run.dish);
+            dashboard_state.layer = layer;
+            dashboard_state.layout = layout.kind;
+            dashboard_state.glyph_tints = COMPARE_GLYPHS
+                .iter()
+                .take(compared.len())
+                .flat_map(|&(head, trail, color)| [(head, color), (trail, color)])
+                .chain(glyph_tints(layer))
+                .collect();
+            draw_dashboard_in(f, dashboard, grid, &dashboard_state);
+            let next = recording
+                .next_event(scrubber.tick)
+                .map(|e| format!("next: {} @{}", e.label(), e.tick));
+            let note = [next, compare_legend(compared, scrubber.tick)]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("  ");
+            draw_scrubber(f, timeline, &scrubber, &event_ticks, Some(&note));
+            adapt_buffer(f.buffer_mut(), render);
+        })?;
+
+        // 3. Input
+        let timeout = tick_rate
+            .checked_sub(last_tick.elapsed())
+            .unwrap_or_else(|| Duration::from_secs(0));
+
+        if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key) => match key.code {
+                    KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Char(' ') => scrubber.toggle_play(),
+                    KeyCode::Left => scrubber.step_by(-1),
+                    KeyCode::Right => scrubber.step_by(1),
+                    KeyCode::PageUp => scrubber.step_by(-(REPLAY_KEYFRAME_INTERVAL as i64)),
+                    KeyCode::PageDown => scrubber.step_by(REPLAY_KEYFRAME_INTERVAL as i64),
+                    KeyCode::Home => scrubber.seek(0),
+                    KeyCode::End => scrubber.seek(recording.len()),
+                    KeyCode::Char('n') => {
+                        if let Some(event) = recording.next_event(scrubber.tick) {
+                            scrubber.seek(event.tick);
+                        }
+                    }
+                    KeyCode::Char('b') => {
+                        if let Some(event) = recording.previous_event(scrubber.tick) {
+                            scrubber.seek(event.tick);
+                        }
+                    }
+                    KeyCode::Char('+' | '=') => scrubber.faster(),
+                    KeyCode::Char('-') => scrubber.slower(),
+                    KeyCode::Char('[') => scrubber.set_loop_start(),
+                    KeyCode::Char(']') => scrubber.set_loop_end(),
+                    KeyCode::Char('\\') => scrubber.clear_loop(),
+                    KeyCode::Char('l') => layer = layer.next(),
+                    // The replay has no message line; a failed save keeps the toggle
+                    KeyCode::Char('v') => _ = layout.toggle(),
+                    KeyCode::Char('r') => occupancy.reset(),
+                    _ => {}
+                },
+                // Clicking the timeline seeks to the tick under the pointer
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                    let size = terminal.size()?;
+                    let area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
+                    let [_, timeline] = replay_layout(area);
+                    let bar_row = timeline.y + 1;
+                    let (left, width) = (timeline.x + 1, timeline.width.saturating_sub(2));
+                    if mouse.row == bar_row && (left..left + width).contains(&mouse.column) {
+                        scrubber.seek(scrubber.tick_at_column(mouse.column - left, width));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn demo_layout(area: ratatui::layout::Rect) -> [ratatui::layout::Rect; 2] {
+    use ratatui::layout::{Constraint, Layout as Split};
+    Split::vertical([Constraint::Length(BANNER_HEIGHT), Constraint::Min(0)]).areas(area)
+}
+
+/// Plays a demo tour under its caption banner: space pauses, `n` cuts to
+/// the next segment, `b` starts the tour over.
+fn run_demo_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    player: &mut DemoPlayer,
+    render: RenderConfig,
+    layout: &mut LayoutSetting,
+    tick_rate: Duration,
+) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+    let mut playing = true;
+    let mut occupancy = OccupancyMap::new(player.run().dish.width, player.run().dish.height);
+    let mut layer = DishLayer::default();
+    loop {
+        // 1. Update
+        if playing && last_tick.elapsed() >= tick_rate {
+            player.step();
+            // A new segment starts from a blank heatmap
+            if player.tick() == 0 {
+                occupancy.reset();
+            }
+            occupancy.record(player.run().agent.x, player.run().agent.y);
+            last_tick = Instant::now();
+        }
+
+        // 2. Render
+        terminal.draw(|f| {
+            let [banner, dashboard] = demo_layout(f.area());
+            let Run { dish, agent } = player.run();
+            let field = DashboardLayout::new(layout.kind, dashboard).field_size();
+            let swarm = [(agent.x, agent.y)];
+            let mut grid = layer_grid(layer, dish, 0, agent, &occupancy, &swarm, field);
+            put_predators(&mut grid, dish);
+            for landmark in agent.episodic_memory.iter().filter(|l| l.is_pinned()) {
+                put_marker(&mut grid, dish, landmark.x, landmark.y, "P");
+            }
+            put_marker(&mut grid, dish, agent.x, agent.y, agent_glyph(agent));
+
+            let mut dashboard_state = DashboardState::from_agent(agent, dish);
+            dashboard_state.layer = layer;
+            dashboard_state.layout = layout.kind;
+            dashboard_state.glyph_tints = glyph_tints(layer);
+            draw_dashboard_in(f, dashboard, grid, &dashboard_state);
+            let caption = if player.is_finished() {
+                "That was the tour: b plays it again, q quits."
+            } else {
+                &player.segment().caption
+            };
+            draw_banner(f, banner, &player.heading(), caption, player.note());
+            adapt_buffer(f.buffer_mut(), render);
+        })?;
+
+        // 3. Input
+        let timeout = tick_rate
+            .checked_sub(last_tick.elapsed())
+            .unwrap_or_else(|| Duration::from_secs(0));
+
+        if event::poll(timeout)?
+            && let Event::Key(key) = event::read()?
+        {
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char(' ') => playing = !playing,
+                KeyCode::Char('n') => {
+                    player.skip();
+                    occupancy.reset();
+                }
+                KeyCode::Char('b') => {
+                    player.restart();
+                    occupancy.reset();
+                }
+                KeyCode::Char('l') => layer = layer.next(),
+                KeyCode::Char('v') => _ = layout.toggle(),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Draws each compared run's last `REPLAY_TRAIL_TICKS` ticks as a trail and
+/// its position at `tick` (glyphs from `COMPARE_GLYPHS`).
+fn overlay_compared(grid: &mut [String], dish: &PetriDish, compared: &[Trajectory], tick: u64) {
+    for (trajectory, &(head, trail, _)) in compared.iter().zip(&COMPARE_GLYPHS) {
+        let (head, trail) = (head.to_string(), trail.to_string());
+        for point in trajectory.window(tick.saturating_sub(REPLAY_TRAIL_TICKS), tick) {
+            put_marker(grid, dish, point.x, point.y, &trail);
+        }
+        if let Some(point) = trajectory.at(tick) {
+            put_marker(grid, dish, point.x, point.y, &head);
+        }
+    }
+}
+
+/// Scrubber legend of the compared runs at `tick` (`A run.csv E 0.62`; `-`
+/// before a run's first tick), none without any.
+fn compare_legend(compared: &[Trajectory], tick: u64) -> Option<String> {
+    if compared.is_empty() {
+        return None;
+    }
+    let entries: Vec<String> = compared
+        .iter()
+        .zip(&COMPARE_GLYPHS)
+        .map(|(trajectory, (head, _, _))| match trajectory.at(tick) {
+            Some(point) => format!("{head} {} E {:.2}", trajectory.label, point.energy),
+            None => format!("{head} {} -", trajectory.label),
+        })
+        .collect();
+    Some(entries.join("  "))
 }
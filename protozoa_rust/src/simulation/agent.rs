@@ -2,22 +2,99 @@
 //!
 //! The agent minimizes Variational Free Energy through gradient descent on beliefs,
 //! and selects actions by minimizing Expected Free Energy over predicted futures.
-
-use crate::simulation::environment::PetriDish;
+//!
+//! `update_state()` runs the agent's `pipeline` (see `pipeline.rs`), by
+//! default `infer()`, `learn_precision()`, `plan()`, `act()`, `remember()`,
+//! `metabolize()`, `locomote()` and `regulate()` in turn. The `controller`
+//! decides the turn and speed; the default `active_inference_decision()`
+//! blends the EFE heading action with the reactive and taxis steering terms
+//! and sets `cruise_speed` from the plan step's speed action.
+//!
+//! Sensing is intermittent. After each sample the agent picks the ticks to
+//! the next one from `SENSING_INTERVALS` by lowest `sensing_efe()`; until
+//! then `sense()` holds every reading, beliefs skip the VFE step and only
+//! grow uncertain, and precision, bias and spatial-prior learning pause.
+//! Each sample costs `morphology.sensing_cost · sensor_dist`. A sample may
+//! be widened into a saccade when `saccade_efe()` < 0, or into a scan at
+//! several orientations when `scan_efe()` < 0 (see `scan.rs`), each at an
+//! extra cost (`SACCADE_COST`, `SCAN_COST`).
+//!
+//! The agent's life:
+//! - Death: after `DEATH_STARVATION_TICKS` at zero energy, or when `health`
+//!   reaches zero (see `health.rs`), the agent stops for good
+//!   (`AgentMode::Dead`) and `sense()`/`update_state()` do nothing.
+//! - Dormancy: below `morphology.dormancy_threshold`, with nothing sensed
+//!   and no navigation target, `dormant_step()` replaces the tick: a sample
+//!   every `DORMANT_SAMPLE_INTERVAL` ticks and `DORMANT_METABOLIC_FACTOR` of
+//!   the base cost, until a sample reads at least `WAKE_CONCENTRATION`.
+//! - Aging: `age` grows every tick, and `vigor()` (see `senescence.rs`) caps
+//!   the speed, scales the belief learning rate and shortens the reach of
+//!   every receptor.
+//!
+//! Optional drives, all off by default: the circadian clock (`keep_time()`,
+//! see `circadian.rs`) scales metabolism and exploration by the believed
+//! time of day; the stress response (see `stress.rs`) lowers the learned
+//! precisions, amplifies the heading noise and triggers panic; threat and
+//! social observations add `threat_risk()` and `social_risk()` to the VFE,
+//! and a threat above `THREAT_HAZARD_LEVEL` interrupts the plan.
+//!
+//! Critical calculations pass through `assert_finite()`, which catches NaN
+//! propagation early.
+
+use crate::simulation::arrival::{ArrivalEvent, ArrivalTarget, ArrivalTracker, EventLog};
+use crate::simulation::calibration::SensorCalibration;
+use crate::simulation::circadian::Circadian;
+use crate::simulation::clock::SimClock;
+use crate::simulation::controller::{Controller, ControllerKind, Decision};
+use crate::simulation::environment::{Environment, PetriDish};
+use crate::simulation::footprint::{MemoryLimits, MemoryUsage};
+use crate::simulation::goal::{Goal, GoalCommand};
+use crate::simulation::health::{self, Injury};
+use crate::simulation::identity;
+use crate::simulation::imitation::HabitMap;
 use crate::simulation::inference::{
-    BeliefState, GenerativeModel, PrecisionEstimator, expected_free_energy, prediction_errors,
-    variational_free_energy, vfe_gradient,
+    BeliefState, BiasEstimator, GenerativeModel, InferenceScheme, MotionSample, ParticleBelief,
+    PointReading, PrecisionEstimator, ReceptorObservation, array_free_energy,
+    circadian_free_energy, circadian_gradient, interoceptive_efe, interoceptive_free_energy,
+    ph_risk, prediction_errors, saccade_efe, scan_efe, sensing_efe, signal_risk, social_risk,
+    species_risk, thermal_free_energy, thermal_prediction_errors, threat_risk,
+};
+use crate::simulation::kinematics::{Kinematics, Motion};
+use crate::simulation::memory::{
+    EpisodicMemory, Landmark, SensorHistory, SensorSnapshot, SpatialGrid, TravelModel,
 };
-use crate::simulation::memory::{EpisodicMemory, SensorHistory, SensorSnapshot, SpatialGrid};
+use crate::simulation::motor_noise::MotorNoise;
+use crate::simulation::numerics::{NonFinite, NumericMode, NumericalFault, finite_all};
 use crate::simulation::params::{
-    BASE_METABOLIC_COST, BELIEF_LEARNING_RATE, DISH_HEIGHT, DISH_WIDTH, EXHAUSTION_SPEED_FACTOR,
-    EXHAUSTION_THRESHOLD, EXPLORATION_SCALE, FRUSTRATION_THRESHOLD, INTAKE_RATE, LANDMARK_ATTRACTION_SCALE,
-    LANDMARK_THRESHOLD, LANDMARK_VISIT_RADIUS, MAX_PRECISION, MAX_SPEED, MAX_VFE,
-    MCTS_REPLAN_INTERVAL, MCTS_URGENT_ENERGY, MIN_PRECISION, NOISE_SCALE, PANIC_THRESHOLD,
-    PANIC_TURN_RANGE, SENSOR_ANGLE, SENSOR_DIST, SPEED_METABOLIC_COST, SURPRISE_THRESHOLD, TARGET_CONCENTRATION,
-    UNCERTAINTY_GROWTH, UNCERTAINTY_REDUCTION,
+    BASE_METABOLIC_COST, BELIEF_LEARNING_RATE, CIRCADIAN_PHASE_DIFFUSION, DEATH_STARVATION_TICKS,
+    DISH_HEIGHT, DISH_WIDTH, DORMANCY_THRESHOLD, DORMANT_METABOLIC_FACTOR, DORMANT_SAMPLE_INTERVAL,
+    EXHAUSTION_SPEED_FACTOR, EXHAUSTION_THRESHOLD, EXPLORATION_SCALE, GOAL_ATTRACTION_SCALE,
+    GOAL_PRIOR_PRECISION, HABIT_GAIN, INITIAL_CRUISE_SPEED, INTAKE_RATE, LANDMARK_ATTRACTION_SCALE,
+    LANDMARK_THRESHOLD, LANDMARK_VISIT_RADIUS, LIGHT_SENSITIVITY, MAX_PRECISION, MAX_SPEED,
+    MAX_VFE, MIN_PRECISION, NOISE_SCALE, PANIC_THRESHOLD, PANIC_TURN_RANGE, PERSISTENCE_LENGTH,
+    PH_STEERING_GAIN, PHEROMONE_DEPOSIT, PHEROMONE_GAIN, PHEROMONE_SENSITIVITY, PHOTOTAXIS_GAIN,
+    PLAN_STEP_WEIGHT, PREFERRED_PH, SACCADE_COST, SACCADE_SWEEP, SCAN_COST, SCAN_ORIENTATIONS,
+    SCAN_TURN_GAIN, SENSING_COST, SENSING_INTERVALS, SENSOR_ANGLE, SENSOR_DIST, SIGNAL_EMISSION,
+    SIGNAL_STEERING_GAIN, SOCIAL_ALIGNMENT_GAIN, SOCIAL_STEERING_GAIN, SPECIES_STEERING_GAIN,
+    SPEED_METABOLIC_COST, STRESS_PANIC_LEVEL, TARGET_CONCENTRATION, THERMAL_STEERING_GAIN,
+    THREAT_HAZARD_LEVEL, WAKE_CONCENTRATION,
 };
-use crate::simulation::planning::{Action, AgentState, MCTSPlanner};
+use crate::simulation::persistence::{correlated_turn, heading_correlation, turning_cost_term};
+use crate::simulation::pipeline::{Phase, TickContext, TickPipeline};
+use crate::simulation::planning::{
+    Action, AgentState, MCTSPlanner, PlanExecutor, PlanSignals, PlanningBudget, planning_pressure,
+};
+use crate::simulation::provenance::{Provenance, Term};
+use crate::simulation::reserve::EnergyReserve;
+use crate::simulation::scan::{BearingBelief, MIN_SCAN_ORIENTATIONS, scan_bearings};
+use crate::simulation::senescence::Senescence;
+use crate::simulation::sensing::{Pose, Sensor, SensorSuite};
+use crate::simulation::sensor_noise::SensorNoise;
+use crate::simulation::sensors::{SensorArray, side_means};
+use crate::simulation::social::SocialObservation;
+use crate::simulation::starvation::StarvationForecast;
+use crate::simulation::streams::{AgentStreams, Stream};
+use crate::simulation::stress::StressResponse;
 use rand::Rng;
 use std::f64::consts::PI;
 
@@ -33,12 +110,17 @@ pub enum AgentMode {
     Panicking,
     /// Energy below exhaustion threshold
     Exhausted,
-    /// Actively navigating toward a landmark
+    /// Actively navigating toward a landmark or an external goal
     GoalNav,
+    /// Starved: energy at zero for `DEATH_STARVATION_TICKS` ticks
+    Dead,
+    /// Spore-like: near-zero metabolism and sensing until food returns
+    Dormant,
 }
 
 /// Validates that a value is finite (not NaN or infinite).
 /// Returns a safe fallback (0.0) in release mode if the value is non-finite.
+/// Strict agents go through `Protozoa::finite_or_zero` instead.
 #[inline]
 fn assert_finite(value: f64, context: &str) -> f64 {
     debug_assert!(value.is_finite(), "Non-finite value in {context}: {value}");
@@ -52,8 +134,87 @@ pub struct Morphology {
     pub sensor_dist: f64,
     /// Sensor stereo spread in radians.
     pub sensor_angle: f64,
+    /// Chemoreceptors, placed relative to `sensor_angle` and `sensor_dist`
+    pub sensors: SensorArray,
     /// Learning rate for belief updates via VFE gradient descent.
     pub belief_learning_rate: f64,
+    /// Response to light: > 0 turns toward brighter light, < 0 away, 0 ignores it.
+    pub light_sensitivity: f64,
+    /// Homeostatic pH set-point (normalized, 0.5 = neutral).
+    pub preferred_ph: f64,
+    /// Energy per second of sampling every tick, per unit of `sensor_dist`.
+    pub sensing_cost: f64,
+    /// Extra sensor spread in radians of a saccade (0 = never sweeps).
+    pub saccade_sweep: f64,
+    /// Orientations a scan sweeps the sensor pair through (0 = never scans).
+    pub scan_orientations: u32,
+    /// Pheromone trail laid per second (0 = lays none).
+    pub pheromone_deposit: f64,
+    /// Response to trails: > 0 follows the stronger trail, < 0 avoids it, 0 ignores it.
+    pub pheromone_sensitivity: f64,
+    /// Quorum signal broadcast per second while exploiting (0 = silent).
+    pub signal_emission: f64,
+    /// Energy below which the agent goes dormant when it has nothing to
+    /// eat or head for (0 = never).
+    pub dormancy_threshold: f64,
+    /// Distance over which the heading stays correlated while exploring
+    /// (0 = uncorrelated, see `persistence.rs`)
+    pub persistence_length: f64,
+    /// Decline of speed, sensor reach and learning rate with age
+    pub senescence: Senescence,
+    /// Internal clock modulating metabolism and exploration by time of day
+    pub circadian: Circadian,
+    /// How sustained surprise builds stress and what stress does
+    pub stress: StressResponse,
+    /// Mass and moment of inertia of the body (none by default)
+    pub kinematics: Kinematics,
+}
+
+impl Morphology {
+    /// Checks every continuous parameter (strict numerical mode).
+    ///
+    /// # Errors
+    /// The first one that is NaN or infinite.
+    pub fn check_finite(&self) -> Result<(), NonFinite> {
+        finite_all([
+            ("morphology.sensor_dist", self.sensor_dist),
+            ("morphology.sensor_angle", self.sensor_angle),
+            ("morphology.belief_learning_rate", self.belief_learning_rate),
+            ("morphology.light_sensitivity", self.light_sensitivity),
+            ("morphology.preferred_ph", self.preferred_ph),
+            ("morphology.sensing_cost", self.sensing_cost),
+            ("morphology.saccade_sweep", self.saccade_sweep),
+            ("morphology.pheromone_deposit", self.pheromone_deposit),
+            (
+                "morphology.pheromone_sensitivity",
+                self.pheromone_sensitivity,
+            ),
+            ("morphology.signal_emission", self.signal_emission),
+            ("morphology.dormancy_threshold", self.dormancy_threshold),
+            ("morphology.persistence_length", self.persistence_length),
+            ("morphology.senescence.rate", self.senescence.rate),
+            ("morphology.kinematics.mass", self.kinematics.mass),
+            ("morphology.kinematics.moment", self.kinematics.moment),
+        ])
+    }
+
+    /// Energy per sensor sample: `sensing_cost` per unit of reach, the pair
+    /// reaching `sensor_dist` and larger arrays their total reach over 2.
+    #[must_use]
+    pub fn sample_cost(&self) -> f64 {
+        self.sensing_cost * self.sensor_dist * self.sensors.cost_factor()
+    }
+}
+
+/// Maps the agent keeps for a dish it is not currently in.
+///
+/// Spatial priors and landmarks are in the coordinates of one dish, so in a
+/// multi-dish `World` each dish gets its own set, tagged with its index.
+#[derive(Debug, Clone)]
+pub struct DishMemory {
+    pub dish_id: usize,
+    pub spatial_priors: SpatialGrid<20, 10>,
+    pub episodic_memory: EpisodicMemory,
 }
 
 /// Represents the single-cell organism (Agent) using Continuous Active Inference.
@@ -72,19 +233,119 @@ pub struct Morphology {
 /// - **Episodic memory**: Landmarks for goal-directed navigation
 /// - **Morphogenesis**: System 2 regulator that adapts morphology based on stress
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // Independent per-sample sensing flags
 pub struct Protozoa {
     // === Position and Movement ===
     pub x: f64,
     pub y: f64,
     pub angle: f64,
     pub speed: f64,
+    /// Angular velocity in radians per second (carried between ticks only
+    /// under inertia, see `kinematics.rs`)
+    pub spin: f64,
+    /// Speed the active inference policy commands, changed by speed actions
+    pub cruise_speed: f64,
+    /// Dish of the `World` the agent is in (0 in a single-dish run)
+    pub dish_id: usize,
 
     // === Internal State ===
     pub energy: f64,
+    /// Slow energy compartment buffering `energy`
+    pub reserve: EnergyReserve,
+    /// Smoothed energy rates behind the time-to-starvation estimate
+    pub starvation: StarvationForecast,
+    /// Structural integrity in [0, 1], lost to toxins, collisions and strain
+    pub health: f64,
+    /// Consecutive ticks spent at zero energy
+    pub starving_ticks: u64,
+    /// Tick (the agent's `tick_count`) it starved or succumbed to damage at;
+    /// the dead neither sense nor act
+    pub died_at: Option<u64>,
+    /// Tick it went dormant at, while dormant: it holds still, samples every
+    /// `DORMANT_SAMPLE_INTERVAL` ticks and pays `DORMANT_METABOLIC_FACTOR`
+    /// of the base metabolism
+    pub dormant_since: Option<u64>,
+    /// Simulated seconds lived, dormant or not (see `senescence.rs`)
+    pub age: f64,
+    /// Stable ID: the agent's ID in its population's `Lineage`, unique in
+    /// the population and kept for life (0 outside a population)
+    pub lineage_id: u64,
+    /// Optional name (see `identity::validate_name`)
+    pub name: Option<String>,
+    /// Population tick the agent was created at (0 for founders)
+    pub created_at: u64,
     pub last_mean_sense: f64,
     pub temp_gradient: f64,
     pub val_l: f64,
     pub val_r: f64,
+    /// Last chemoreceptor array sample, one reading per receptor (`val_l`
+    /// and `val_r` are its side means)
+    pub readings: Vec<ReceptorObservation>,
+    /// Sensors sampled besides the chemoreceptor array, with their last
+    /// readings (empty by default, see `sensing.rs`)
+    pub sensor_suite: SensorSuite,
+    /// Per-species left sensor readings (indexed by species)
+    pub species_l: Vec<f64>,
+    /// Per-species right sensor readings (indexed by species)
+    pub species_r: Vec<f64>,
+    /// Left thermoreceptor reading
+    pub thermo_l: f64,
+    /// Right thermoreceptor reading
+    pub thermo_r: f64,
+    /// Left photoreceptor reading
+    pub light_l: f64,
+    /// Right photoreceptor reading
+    pub light_r: f64,
+    /// Left pH sensor reading
+    pub ph_l: f64,
+    /// Right pH sensor reading
+    pub ph_r: f64,
+    /// Left pheromone receptor reading
+    pub pheromone_l: f64,
+    /// Right pheromone receptor reading
+    pub pheromone_r: f64,
+    /// Left quorum signal receptor reading
+    pub signal_l: f64,
+    /// Right quorum signal receptor reading
+    pub signal_r: f64,
+    /// Left threat (predator scent) reading
+    pub threat_l: f64,
+    /// Right threat (predator scent) reading
+    pub threat_r: f64,
+    /// Daylight level read at the last sample (entrains the circadian clock)
+    pub daylight: f64,
+    /// Slow stress level in [0, 1) driven by sustained VFE (see `stress.rs`)
+    pub stress: f64,
+    /// Neighbors sensed this population step (see `social.rs`)
+    pub social: SocialObservation,
+    /// How raw chemoreceptor reads map to `val_l`/`val_r` (identity = matched sensors)
+    pub calibration: SensorCalibration,
+    /// Learned left/right chemoreceptor offset removed from `val_l`/`val_r`
+    pub bias_estimator: BiasEstimator,
+    /// Read noise of the chemoreceptor channels, applied before calibration
+    pub sensor_noise: SensorNoise,
+    /// Current drift offsets of the left and right channels
+    pub noise_offsets: (f64, f64),
+    /// Execution noise on the commanded turn and speed
+    pub motor_noise: MotorNoise,
+    /// Ticks between sensor samples, chosen by EFE after each sample
+    pub sensing_interval: u32,
+    /// Ticks left before the next sample
+    pub sample_countdown: u32,
+    /// Whether the last `sense()` took a fresh sample
+    pub sampled: bool,
+    /// Sweep the sensors wide at the next sample, chosen by EFE
+    pub saccade_planned: bool,
+    /// Whether the last sample was a saccade (sensors swept wide)
+    pub saccade: bool,
+    /// Scan the gradient's bearing at the next sample, chosen by EFE
+    pub scan_planned: bool,
+    /// Whether the last sample included a scan
+    pub scan: bool,
+    /// `(bearing, reading)` pairs of the last scan (empty without one)
+    pub scan_samples: Vec<(f64, f64)>,
+    /// Belief about the nutrient gradient's bearing, sharpened by scans
+    pub bearing: BearingBelief,
 
     // === Active Inference Components ===
     /// Gaussian beliefs about hidden states: q(s) = N(μ, Σ)
@@ -93,26 +354,70 @@ pub struct Protozoa {
     pub generative_model: GenerativeModel,
     /// Online precision estimator from prediction errors
     pub precision_estimator: PrecisionEstimator,
+    /// Online precision estimator for the thermoreceptors
+    pub thermal_precision_estimator: PrecisionEstimator,
     /// Current Variational Free Energy (for monitoring/visualization)
     pub current_vfe: f64,
 
     // === Memory Systems ===
     /// Spatial prior grid: learned expectations about nutrient concentration
     pub spatial_priors: SpatialGrid<20, 10>,
+    /// Learned map of sensed threat in the current dish, avoided by planning
+    pub threat_priors: SpatialGrid<20, 10>,
+    /// Demonstrated headings in the current dish (see `imitation.rs`)
+    pub habits: HabitMap,
     /// Short-term memory: recent sensor experiences
     pub sensor_history: SensorHistory,
     /// Episodic memory: remembered high-nutrient landmarks
     pub episodic_memory: EpisodicMemory,
+    /// Spatial priors and landmarks of the other dishes visited
+    pub dish_memories: Vec<DishMemory>,
+    /// Exogenous goal (installed via `set_goal`), with progress tracking
+    pub goal: Option<Goal>,
+    /// Arrival detection for the current goal or landmark
+    pub arrivals: ArrivalTracker,
+    /// Recent arrival events with running travel statistics
+    pub events: EventLog,
     /// Current simulation tick
     pub tick_count: u64,
+    /// Master seed of `streams` and the planner's rollouts: two agents with
+    /// the same seed draw the same numbers (see `seed_streams`)
+    pub seed: u64,
+    /// Random streams for exploration, noise and panic turns
+    pub streams: AgentStreams,
+    /// Last exploration draw (from [−1, 1] unless carried over by
+    /// directional persistence)
+    pub explore_turn: f64,
+    /// Turn the last active inference decision commanded
+    pub last_turn: f64,
 
     // === Planning System ===
+    /// Policy deciding each tick's turn and speed (active inference by default)
+    pub controller: ControllerKind,
+    /// Phases `update_state` runs (the full tick by default)
+    pub pipeline: TickPipeline,
     /// MCTS planner for trajectory optimization
     pub planner: MCTSPlanner,
+    /// Largest budget a replan may use (set by the dashboard's frame-budget
+    /// guard on slow machines; `None` = uncapped)
+    pub planning_cap: Option<PlanningBudget>,
     /// Tick when last planning occurred
     pub last_plan_tick: u64,
-    /// Best action from last planning cycle
+    /// Plan step executed this tick
     pub planned_action: Action,
+    /// Commitment to the current plan and its interruption policy
+    pub plan_executor: PlanExecutor,
+    /// What produced the last executed decision
+    pub provenance: Provenance,
+    /// Whether non-finite values are zeroed or halt the agent
+    pub numerics: NumericMode,
+    /// How beliefs are updated on a sample (VFE gradient descent by default)
+    pub inference: InferenceScheme,
+    /// Particle belief over the patch location, tracked next to `beliefs`
+    /// (off by default)
+    pub particles: Option<ParticleBelief>,
+    /// First non-finite value a strict agent met (it has halted since)
+    pub fault: Option<Box<NumericalFault>>,
 
     // === Morphogenesis (System 2) ===
     /// Dynamic morphological parameters
@@ -120,10 +425,13 @@ pub struct Protozoa {
     /// Accumulated surprise (integral of VFE) for morphogenesis regulation
     pub cumulative_surprise: f64,
     /// Accumulated frustration (integral of EFE) for morphogenesis regulation
+    #[allow(dead_code)] // Reserved for the morphogenesis regulator
     pub cumulative_frustration: f64,
     /// Current structural complexity metric
+    #[allow(dead_code)] // Reserved for the morphogenesis regulator
     pub current_complexity: f64,
     /// History of complexity values for tracking evolution
+    #[allow(dead_code)] // Reserved for the morphogenesis regulator
     pub complexity_history: Vec<f64>,
 }
 
@@ -132,133 +440,804 @@ impl Protozoa {
     ///
     /// Initializes Active Inference components with neutral priors.
     #[must_use]
+    #[allow(dead_code)] // Used by tests; the binary spawns via `SpawnConfig`
     pub fn new(x: f64, y: f64) -> Self {
         let mut rng = rand::rng();
-        let initial_angle = rng.random_range(0.0..2.0 * PI);
+        Self::with_heading(x, y, rng.random_range(0.0..2.0 * PI))
+    }
 
-        Self {
+    /// Creates a new Protozoa agent at the given position and heading, its
+    /// random streams seeded from a freshly drawn `seed`.
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub fn with_heading(x: f64, y: f64, initial_angle: f64) -> Self {
+        let seed = rand::rng().random();
+        let mut agent = Self {
             x,
             y,
             angle: initial_angle,
             speed: 0.0,
+            spin: 0.0,
+            cruise_speed: INITIAL_CRUISE_SPEED,
+            dish_id: 0,
             energy: 1.0,
+            reserve: EnergyReserve::default(),
+            starvation: StarvationForecast::default(),
+            health: 1.0,
+            starving_ticks: 0,
+            died_at: None,
+            dormant_since: None,
+            age: 0.0,
+            lineage_id: 0,
+            name: None,
+            created_at: 0,
             last_mean_sense: 0.0,
             temp_gradient: 0.0,
             val_l: 0.0,
             val_r: 0.0,
+            readings: Vec::new(),
+            sensor_suite: SensorSuite::default(),
+            species_l: Vec::new(),
+            species_r: Vec::new(),
+            thermo_l: 0.0,
+            thermo_r: 0.0,
+            light_l: 0.0,
+            light_r: 0.0,
+            ph_l: 0.0,
+            ph_r: 0.0,
+            pheromone_l: 0.0,
+            pheromone_r: 0.0,
+            signal_l: 0.0,
+            signal_r: 0.0,
+            threat_l: 0.0,
+            threat_r: 0.0,
+            daylight: 1.0,
+            stress: 0.0,
+            social: SocialObservation::default(),
+            calibration: SensorCalibration::default(),
+            bias_estimator: BiasEstimator::default(),
+            sensor_noise: SensorNoise::default(),
+            noise_offsets: (0.0, 0.0),
+            motor_noise: MotorNoise::default(),
+            sensing_interval: 1,
+            sample_countdown: 0,
+            sampled: true,
+            saccade_planned: false,
+            saccade: false,
+            scan_planned: false,
+            scan: false,
+            scan_samples: Vec::new(),
+            bearing: BearingBelief::default(),
             // Active Inference components
             beliefs: BeliefState::new(x, y, initial_angle),
             generative_model: GenerativeModel::new(),
             precision_estimator: PrecisionEstimator::new(),
+            thermal_precision_estimator: PrecisionEstimator::new(),
             current_vfe: 0.0,
             // Memory systems
             spatial_priors: SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT),
+            threat_priors: SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT),
+            habits: HabitMap::default(),
             sensor_history: SensorHistory::new(),
             episodic_memory: EpisodicMemory::new(),
+            dish_memories: Vec::new(),
+            goal: None,
+            arrivals: ArrivalTracker::new(),
+            events: EventLog::new(),
             tick_count: 0,
+            seed,
+            streams: AgentStreams::from_seed(seed),
+            explore_turn: 0.0,
+            last_turn: 0.0,
             // Planning
+            controller: ControllerKind::default(),
+            pipeline: TickPipeline::default(),
             planner: MCTSPlanner::new(),
+            planning_cap: None,
             last_plan_tick: 0,
             planned_action: Action::Straight,
+            plan_executor: PlanExecutor::new(),
+            provenance: Provenance::default(),
+            numerics: NumericMode::default(),
+            inference: InferenceScheme::default(),
+            particles: None,
+            fault: None,
             // Morphogenesis (System 2)
             morphology: Morphology {
                 sensor_dist: SENSOR_DIST,
                 sensor_angle: SENSOR_ANGLE,
+                sensors: SensorArray::pair(),
                 belief_learning_rate: BELIEF_LEARNING_RATE,
+                light_sensitivity: LIGHT_SENSITIVITY,
+                preferred_ph: PREFERRED_PH,
+                sensing_cost: SENSING_COST,
+                saccade_sweep: SACCADE_SWEEP,
+                scan_orientations: SCAN_ORIENTATIONS,
+                pheromone_deposit: PHEROMONE_DEPOSIT,
+                pheromone_sensitivity: PHEROMONE_SENSITIVITY,
+                signal_emission: SIGNAL_EMISSION,
+                dormancy_threshold: DORMANCY_THRESHOLD,
+                persistence_length: PERSISTENCE_LENGTH,
+                senescence: Senescence::default(),
+                circadian: Circadian::default(),
+                stress: StressResponse::default(),
+                kinematics: Kinematics::default(),
             },
             cumulative_surprise: 0.0,
             cumulative_frustration: 0.0,
             current_complexity: 0.0,
             complexity_history: Vec::new(),
-        }
+        };
+        agent.planner.seed(Stream::Rollouts.seed(seed));
+        agent
+    }
+
+    /// Reseeds every behavioral stream (exploration, noise, panic, rollouts,
+    /// inheritance, sensing, motor, particles) from `master`, so a seeded run
+    /// is reproducible and each subsystem's draws are independent of the
+    /// others.
+    pub fn seed_streams(&mut self, master: u64) {
+        self.seed = master;
+        self.streams = AgentStreams::from_seed(master);
+        self.planner.seed(Stream::Rollouts.seed(master));
     }
 
     /// Updates the agent's sensory inputs based on the current environment.
     ///
-    /// Detects concentration at two points (left and right sensors).
-    pub fn sense(&mut self, dish: &PetriDish) {
+    /// Detects concentration at two points (left and right sensors),
+    /// both in total and per chemical species, plus temperature
+    /// (thermoreceptors), light (photoreceptors), pH, predator threat and
+    /// pheromone trails at the same points.
+    /// The total reading carries the dish's sensory turbulence and goes
+    /// through the agent's `calibration`, then its learned bias correction.
+    /// The agent samples every `sensing_interval` ticks; in between the
+    /// readings are held and `sampled` is false. A planned saccade widens
+    /// the sensor spread by `saccade_sweep` for that one sample, and a
+    /// planned scan also reads the total concentration with the pair turned
+    /// through `scan_orientations` orientations around the body.
+    pub fn sense(&mut self, dish: &(impl Environment + ?Sized)) {
+        if !self.is_alive() {
+            return;
+        }
+        if !self.sensor_noise.is_off() {
+            self.noise_offsets = self.sensor_noise.drift(
+                self.noise_offsets,
+                &dish.clock(),
+                &mut self.streams.sensing,
+            );
+        }
+        // Between samples every receptor holds its last reading
+        if self.sample_countdown > 0 {
+            self.sample_countdown -= 1;
+            self.sampled = false;
+            self.saccade = false;
+            self.scan = false;
+            self.scan_samples.clear();
+            return;
+        }
+        self.saccade = std::mem::take(&mut self.saccade_planned);
+        self.scan = std::mem::take(&mut self.scan_planned);
+        let reach = self.sensor_reach();
+        let pose = self.pose();
+
         // Left Sensor
-        let theta_l = self.angle + self.morphology.sensor_angle;
-        let x_l = self.x + self.morphology.sensor_dist * theta_l.cos();
-        let y_l = self.y + self.morphology.sensor_dist * theta_l.sin();
-        self.val_l = dish.get_concentration(x_l, y_l);
+        let (x_l, y_l) = pose.point(1.0, 1.0);
+        self.species_l = dish.species_concentrations(x_l, y_l);
+        self.thermo_l = dish.get_temperature(x_l, y_l);
+        self.light_l = dish.get_light(x_l, y_l);
+        self.ph_l = dish.get_ph(x_l, y_l);
+        self.threat_l = dish.get_threat(x_l, y_l);
+        self.pheromone_l = dish.get_pheromone(x_l, y_l);
+        self.signal_l = dish.get_signal(x_l, y_l);
 
         // Right Sensor
-        let theta_r = self.angle - self.morphology.sensor_angle;
-        let x_r = self.x + self.morphology.sensor_dist * theta_r.cos();
-        let y_r = self.y + self.morphology.sensor_dist * theta_r.sin();
-        self.val_r = dish.get_concentration(x_r, y_r);
+        let (x_r, y_r) = pose.point(-1.0, 1.0);
+        self.species_r = dish.species_concentrations(x_r, y_r);
+        self.thermo_r = dish.get_temperature(x_r, y_r);
+        self.light_r = dish.get_light(x_r, y_r);
+        self.ph_r = dish.get_ph(x_r, y_r);
+        self.threat_r = dish.get_threat(x_r, y_r);
+        self.pheromone_r = dish.get_pheromone(x_r, y_r);
+        self.signal_r = dish.get_signal(x_r, y_r);
+        self.daylight = dish.daylight();
+        self.sampled = true;
+
+        // Chemoreceptor array: each receptor reports through the channel
+        // of its side (crossed wiring mirrors the sides), and each channel
+        // reads the mean of its receptors
+        let mut raws = Vec::with_capacity(self.morphology.sensors.channels());
+        self.morphology.sensors.sample(&pose, dish, &mut raws);
+        self.readings.clear();
+        for (receptor, raw) in self.morphology.sensors.receptors().iter().zip(raws) {
+            let (raw_l, raw_r) =
+                self.sensor_noise
+                    .read(raw, self.noise_offsets, &mut self.streams.sensing);
+            let (obs_l, obs_r) = self.calibration.apply(raw_l, raw_r);
+            let (obs_l, obs_r) = self.bias_estimator.correct(obs_l, obs_r);
+            let bearing = if self.calibration.swapped {
+                -receptor.bearing
+            } else {
+                receptor.bearing
+            };
+            let value = if bearing > 0.0 {
+                obs_l
+            } else if bearing < 0.0 {
+                obs_r
+            } else {
+                f64::midpoint(obs_l, obs_r)
+            };
+            self.readings.push(ReceptorObservation { value, bearing });
+        }
+        (self.val_l, self.val_r) = side_means(&self.readings);
+        if !self.sensor_suite.is_empty() {
+            self.sensor_suite.sense(&pose, dish);
+        }
+
+        // Scan: the pair swept around the body samples the gradient's bearing
+        self.scan_samples.clear();
+        if self.scan {
+            for bearing in scan_bearings(
+                self.angle,
+                self.morphology.sensor_angle,
+                self.morphology.scan_orientations,
+            ) {
+                let value = dish.sensed_concentration(
+                    self.x + reach * bearing.cos(),
+                    self.y + reach * bearing.sin(),
+                );
+                self.scan_samples.push((bearing, value));
+            }
+        }
+    }
+
+    /// Where the sensors stand this tick: the sensor pair spread wider on a
+    /// saccade.
+    #[must_use]
+    pub fn pose(&self) -> Pose {
+        let spread = if self.saccade {
+            self.morphology.sensor_angle + self.morphology.saccade_sweep
+        } else {
+            self.morphology.sensor_angle
+        };
+        Pose {
+            x: self.x,
+            y: self.y,
+            angle: self.angle,
+            spread,
+            reach: self.sensor_reach(),
+            tick: self.tick_count,
+        }
     }
 
-    /// Updates the agent's internal state using Active Inference.
+    /// Starts tracking a particle belief of `count` particles spread over a
+    /// `width` × `height` dish, drawn from the agent's particle stream.
+    pub fn track_particles(&mut self, count: usize, width: f64, height: f64) {
+        self.particles = Some(ParticleBelief::uniform(
+            count,
+            width,
+            height,
+            &mut self.streams.particles,
+        ));
+    }
+
+    /// Chemoreceptor observations for inference: the last array sample, or
+    /// the `val_l`/`val_r` pair if the agent has not sensed yet.
+    #[must_use]
+    pub fn chemoreceptor_observations(&self) -> Vec<ReceptorObservation> {
+        if self.readings.is_empty() {
+            ReceptorObservation::pair((self.val_l, self.val_r)).to_vec()
+        } else {
+            self.readings.clone()
+        }
+    }
+
+    /// Updates the agent's internal state using Active Inference: one run
+    /// of its `pipeline`, by default every phase of `Phase::ALL`.
     ///
     /// # Active Inference Loop
     /// 1. **Infer**: Update beliefs via gradient descent on Variational Free Energy
     /// 2. **Learn**: Update precision estimates from prediction errors
-    /// 3. **Plan**: Select action minimizing Expected Free Energy
-    /// 4. **Act**: Execute action and update position
-    #[allow(clippy::too_many_lines)]
-    pub fn update_state(&mut self, dish: &PetriDish) {
-        let mut rng = rand::rng();
+    /// 3. **Plan**: The agent's `controller` picks a turn and speed (by
+    ///    default the action minimizing Expected Free Energy, see
+    ///    `active_inference_decision`)
+    /// 4. **Act**: Execute the decision, remember, metabolize and move
+    ///
+    /// An agent at zero energy for `DEATH_STARVATION_TICKS` ticks in a row
+    /// dies: it stops, and from then on this does nothing. One below its
+    /// `dormancy_threshold` with no landmark to head for and little to eat
+    /// goes dormant instead (see `dormant_step`).
+    pub fn update_state(&mut self, dish: &(impl Environment + ?Sized)) {
+        // Taken out for the tick so the phases can borrow the agent
+        let pipeline = std::mem::replace(&mut self.pipeline, TickPipeline::new(Vec::new()));
+        pipeline.run(self, dish);
+        self.pipeline = pipeline;
+    }
+
+    /// Context for a tick starting now: the held readings, and no decision
+    /// yet (straight on at cruise speed, the current speed, no drag).
+    #[must_use]
+    pub fn tick_context(&self) -> TickContext {
+        TickContext {
+            observations: (self.val_l, self.val_r),
+            chemoreceptors: self.chemoreceptor_observations(),
+            thermal: (self.thermo_l, self.thermo_r),
+            mean_sense: self.finite_or_zero(f64::midpoint(self.val_l, self.val_r), "mean_sense"),
+            decision: Decision {
+                turn: 0.0,
+                speed: self.cruise_speed,
+            },
+            commanded_speed: self.speed,
+            viscosity: 1.0,
+            impact: None,
+            heading: self.angle,
+        }
+    }
 
-        // Get observations
-        let observations = (self.val_l, self.val_r);
-        let mean_sense = assert_finite(f64::midpoint(self.val_l, self.val_r), "mean_sense");
+    /// Runs one `phase` of the tick described by `tick`.
+    pub fn run_phase(
+        &mut self,
+        phase: Phase,
+        dish: &(impl Environment + ?Sized),
+        tick: &mut TickContext,
+    ) {
+        match phase {
+            Phase::Infer => self.infer(dish, tick),
+            Phase::LearnPrecision => self.learn_precision(tick),
+            Phase::Plan => self.plan(tick),
+            Phase::Act => self.act(dish, tick),
+            Phase::Remember => self.remember(dish, tick),
+            Phase::Metabolize => self.metabolize(dish, tick),
+            Phase::Locomote => self.locomote(dish, tick),
+            Phase::Regulate => self.regulate(dish, tick),
+        }
+    }
 
-        // === PHASE 1: INFERENCE (Minimize VFE) ===
+    /// Weighs the particle belief by the fresh left and right readings,
+    /// taken where the sensor pair stands.
+    fn observe_particles(&mut self) {
+        let pose = self.pose();
+        let readings = [(1.0, self.val_l), (-1.0, self.val_r)].map(|(bearing, value)| {
+            let (x, y) = pose.point(bearing, 1.0);
+            PointReading { x, y, value }
+        });
+        if let Some(particles) = &mut self.particles {
+            particles.observe(&readings, &mut self.streams.particles);
+        }
+    }
 
+    /// Inference phase (minimize VFE): syncs the position beliefs, descends
+    /// the VFE on a fresh sample (beliefs only grow less certain between
+    /// samples), keeps circadian time, blurs or sharpens the gradient's
+    /// bearing, and updates the VFE, accumulated surprise and stress.
+    pub fn infer(&mut self, dish: &(impl Environment + ?Sized), tick: &TickContext) {
         // Synchronize position beliefs with actual position (proprioception)
         self.beliefs.sync_position(self.x, self.y, self.angle);
+        // Interoception sets how precisely food is preferred
+        self.generative_model.observe_energy(self.energy);
+
+        if self.sampled {
+            // Update beliefs on the fresh sample (chemical and thermal
+            // modalities) by the agent's inference scheme, at a rate that
+            // grows with the estimated volatility: a volatile world is
+            // learned (and forgotten) faster
+            self.generative_model.observe_volatility(tick.mean_sense);
+            let learning_rate = self.morphology.belief_learning_rate
+                * self.vigor()
+                * self.generative_model.learning_rate_scale();
+            self.inference.update(
+                &mut self.beliefs,
+                &tick.chemoreceptors,
+                tick.thermal,
+                &self.generative_model,
+                learning_rate,
+            );
+            if self.particles.is_some() {
+                self.observe_particles();
+            }
+        } else {
+            // No new reading: the held one carries no information, so
+            // beliefs only grow less certain
+            self.beliefs
+                .increase_uncertainty(self.generative_model.uncertainty_growth());
+        }
 
-        // Compute VFE gradient and update beliefs
-        let gradient = vfe_gradient(observations, &self.beliefs, &self.generative_model);
-        self.beliefs.update(&gradient, self.morphology.belief_learning_rate);
+        // The circadian clock free-runs and is entrained by the daylight read
+        self.keep_time(&dish.clock());
 
-        // Reduce uncertainty after incorporating observation
-        self.beliefs.decrease_uncertainty(UNCERTAINTY_REDUCTION);
+        // The gradient's bearing blurs as the agent moves; a scan sharpens it
+        self.bearing.drift(&dish.clock());
+        if self.scan {
+            let precision = &self.generative_model.sensory_precision;
+            let noise_var = 1.0 / f64::midpoint(precision.left, precision.right).max(f64::EPSILON);
+            self.bearing.observe(&self.scan_samples, noise_var);
+        }
 
         // Compute and store current VFE for monitoring
+        let circadian_vfe = if self.morphology.circadian.is_off() {
+            0.0
+        } else {
+            circadian_free_energy(self.daylight, &self.beliefs, &self.generative_model)
+        };
         self.current_vfe =
-            variational_free_energy(observations, &self.beliefs, &self.generative_model);
+            array_free_energy(&tick.chemoreceptors, &self.beliefs, &self.generative_model)
+                + thermal_free_energy(tick.thermal, &self.beliefs, &self.generative_model)
+                + species_risk(&self.species_l, &self.species_r, &self.generative_model)
+                + ph_risk(
+                    (self.ph_l, self.ph_r),
+                    self.morphology.preferred_ph,
+                    &self.generative_model,
+                )
+                + threat_risk((self.threat_l, self.threat_r), &self.generative_model)
+                + signal_risk((self.signal_l, self.signal_r), &self.generative_model)
+                + social_risk(&self.social, self.angle, &self.generative_model)
+                + circadian_vfe
+                + interoceptive_free_energy(self.energy, &self.generative_model);
 
         // Accumulate surprise for morphogenesis regulation
         self.cumulative_surprise += self.current_vfe;
 
-        // === PHASE 2: PRECISION LEARNING ===
+        // Sustained surprise builds stress, which recovers once it passes
+        if !self.morphology.stress.is_off() {
+            self.stress = self
+                .morphology
+                .stress
+                .step(self.stress, self.current_vfe, &dish.clock());
+        }
+    }
 
+    /// Precision learning phase: on a fresh sample, updates the chemical
+    /// and thermal precision estimates and the bias estimate from the
+    /// prediction errors, and installs the precisions (scaled down by
+    /// stress) in the generative model.
+    pub fn learn_precision(&mut self, tick: &TickContext) {
+        if !self.sampled {
+            return;
+        }
         // Update precision estimates from prediction errors
-        let (err_l, err_r) = prediction_errors(observations, &self.beliefs, &self.generative_model);
-        self.precision_estimator.update(err_l, err_r);
-
-        // Update generative model with learned precisions
+        let (err_l, err_r) =
+            prediction_errors(tick.observations, &self.beliefs, &self.generative_model);
+        let rate_scale = self.generative_model.learning_rate_scale();
+        self.precision_estimator
+            .update_scaled(err_l, err_r, rate_scale);
+        self.bias_estimator.update(err_l, err_r);
+        self.generative_model
+            .learn_likelihood((err_l, err_r), self.beliefs.mean.angle);
+
+        // Update generative model with learned precisions, which stress
+        // scales down globally
+        let stress = self.stress_precision();
         self.generative_model.update_sensory_precision(
-            self.precision_estimator.precision_left(),
-            self.precision_estimator.precision_right(),
+            self.precision_estimator.precision_left() * stress,
+            self.precision_estimator.precision_right() * stress,
         );
 
-        // === PHASE 3: PLANNING (Minimize EFE) ===
+        // Thermoreceptor precision is learned independently (multimodal weighting)
+        let (thermal_err_l, thermal_err_r) = thermal_prediction_errors(tick.thermal, &self.beliefs);
+        self.thermal_precision_estimator
+            .update_scaled(thermal_err_l, thermal_err_r, rate_scale);
+        self.generative_model.update_thermal_precision(
+            self.thermal_precision_estimator.precision_left() * stress,
+            self.thermal_precision_estimator.precision_right() * stress,
+        );
+    }
 
+    /// Decision phase (minimize EFE, unless a baseline controller runs):
+    /// updates the temporal gradient and asks the `controller` for the
+    /// tick's decision.
+    pub fn plan(&mut self, tick: &mut TickContext) {
         // Compute temporal gradient (for panic detection)
-        self.temp_gradient = mean_sense - self.last_mean_sense;
-        self.last_mean_sense = mean_sense;
+        self.temp_gradient = tick.mean_sense - self.last_mean_sense;
+        self.last_mean_sense = tick.mean_sense;
+
+        let controller = self.controller;
+        self.provenance = Provenance::new(controller);
+        tick.decision = controller.decide(self);
+    }
+
+    /// Action phase: executes the decision with the motor noise as a new
+    /// heading and a speed, capped by vigor and divided by the local drag.
+    pub fn act(&mut self, dish: &(impl Environment + ?Sized), tick: &mut TickContext) {
+        let decision = tick.decision;
+        // The body executes the command with its motor noise
+        let (turn, speed) = if self.motor_noise.is_off() {
+            (decision.turn, decision.speed)
+        } else {
+            self.motor_noise
+                .execute(decision.turn, decision.speed, &mut self.streams.motor)
+        };
+        // An aged agent cannot reach its young top speed
+        tick.commanded_speed = speed.min(MAX_SPEED * self.vigor());
+
+        // Viscous drag: thick medium divides the speed actually achieved,
+        // reached at once or, under inertia, over time
+        tick.viscosity = dish.get_viscosity(self.x, self.y);
+        let (motion, turn) = self.morphology.kinematics.advance(
+            self.motion(),
+            turn,
+            tick.commanded_speed,
+            self.morphology.sensor_dist,
+            tick.viscosity,
+            dish.clock().dt(),
+        );
+        self.angle += self.finite_or_zero(turn, "d_theta");
+        self.angle = self.angle.rem_euclid(2.0 * PI);
+        self.speed = motion.speed;
+        self.spin = motion.spin;
+        self.provenance.settle(decision, self.speed);
+    }
+
+    /// Memory phase: learns the spatial and threat priors from a fresh
+    /// sample, records the tick in short-term memory, advances the tick
+    /// count and age, and stores, decays and revisits landmarks.
+    pub fn remember(&mut self, dish: &(impl Environment + ?Sized), tick: &TickContext) {
+        let mean_sense = tick.mean_sense;
+        // Update spatial prior with observation (world model learning)
+        if self.sampled {
+            self.spatial_priors.update(self.x, self.y, mean_sense);
+            self.threat_priors
+                .update(self.x, self.y, f64::midpoint(self.threat_l, self.threat_r));
+        }
+
+        // Record experience in short-term memory
+        self.sensor_history.push(SensorSnapshot {
+            val_l: self.val_l,
+            val_r: self.val_r,
+            x: self.x,
+            y: self.y,
+            energy: self.energy,
+            tick: self.tick_count,
+        });
+        self.tick_count += 1;
+        self.age += dish.clock().dt();
+
+        // Episodic memory: landmark detection and maintenance
+        self.episodic_memory.decay_all();
+
+        if mean_sense > LANDMARK_THRESHOLD {
+            self.episodic_memory
+                .maybe_store(self.x, self.y, mean_sense, self.tick_count);
+        }
+
+        self.episodic_memory
+            .update_on_visit(self.x, self.y, mean_sense, self.tick_count);
+    }
+
+    /// Metabolism phase: pays the base, swimming, sensing, saccade and scan
+    /// costs, eats in proportion to the mean reading, settles the energy
+    /// (possibly starving to death), and goes dormant or slows down when
+    /// the energy runs low.
+    pub fn metabolize(&mut self, dish: &(impl Environment + ?Sized), tick: &TickContext) {
+        // Rates are per second; swimming effort is paid for the commanded
+        // speed, scaled by the drag
+        let clock = dish.clock();
+        let metabolic_cost = clock.rate(
+            BASE_METABOLIC_COST * self.circadian_metabolism()
+                + (SPEED_METABOLIC_COST * (tick.commanded_speed / MAX_SPEED) * tick.viscosity),
+        );
+        let intake = clock.rate(INTAKE_RATE * tick.mean_sense);
+        // Each sample costs in proportion to how far the sensors reach
+        let sensing_cost = if self.sampled {
+            clock.rate(self.morphology.sample_cost())
+        } else {
+            0.0
+        };
+        let saccade_cost = if self.saccade {
+            clock.rate(SACCADE_COST)
+        } else {
+            0.0
+        };
+
+        let scan_cost = if self.scan {
+            clock.rate(SCAN_COST)
+        } else {
+            0.0
+        };
+
+        let cost = metabolic_cost + sensing_cost + saccade_cost + scan_cost;
+        let energy = self.finite_or_zero(self.energy - cost + intake, "energy");
+        if self.settle_energy(energy, cost, &clock) {
+            return;
+        }
+
+        // Dormancy: nothing to eat here and no landmark to head for
+        if self.energy < self.morphology.dormancy_threshold
+            && tick.mean_sense < WAKE_CONCENTRATION
+            && self.navigation_target().is_none()
+        {
+            self.dormant_since = Some(self.tick_count);
+            self.speed = 0.0;
+            self.spin = 0.0;
+        }
+
+        // Exhaustion check
+        if self.energy <= EXHAUSTION_THRESHOLD {
+            self.speed *= EXHAUSTION_SPEED_FACTOR;
+        }
+    }
+
+    /// Locomotion phase: swims along the heading, drifts with the flow,
+    /// stays in the dish and is stopped by obstacles (the impact speed goes
+    /// to `tick.impact`). A free move teaches the transition model.
+    pub fn locomote(&mut self, dish: &(impl Environment + ?Sized), tick: &mut TickContext) {
+        let clock = dish.clock();
+        let (prev_x, prev_y) = (self.x, self.y);
+        self.x += clock.rate(self.speed * self.angle.cos());
+        self.y += clock.rate(self.speed * self.angle.sin());
+
+        // Passive drift with the fluid currents
+        let (flow_x, flow_y) = dish.flow_at(self.x, self.y);
+        self.x += clock.rate(flow_x);
+        self.y += clock.rate(flow_y);
+
+        // Boundary Check
+        let (width, height) = dish.bounds();
+        let unbounded = (self.x, self.y);
+        self.x = self.x.clamp(0.0, width);
+        self.y = self.y.clamp(0.0, height);
+
+        // Obstacles are solid: a move that would end inside one is undone
+        tick.impact = dish.is_blocked(self.x, self.y).then_some(self.speed);
+        if tick.impact.is_some() {
+            self.x = prev_x;
+            self.y = prev_y;
+            // The obstacle absorbs any momentum
+            if !self.morphology.kinematics.is_off() {
+                self.speed = 0.0;
+            }
+        } else if unbounded == (self.x, self.y) {
+            self.learn_transition((prev_x, prev_y), tick);
+        }
+    }
+
+    /// Compares the move from `from` to the proprioceived position with
+    /// the one the transition model predicts for the tick's command.
+    fn learn_transition(&mut self, from: (f64, f64), tick: &TickContext) {
+        if self.generative_model.transition.is_off() {
+            return;
+        }
+        self.generative_model.transition.learn(&MotionSample {
+            turn: tick.decision.turn,
+            speed: tick.decision.speed,
+            heading: tick.heading,
+            new_heading: self.angle,
+            dx: self.x - from.0,
+            dy: self.y - from.1,
+        });
+    }
+
+    /// Regulation phase: settles health against toxins, the tick's impact
+    /// and strain (possibly succumbing), then tracks goal progress and
+    /// detects arrivals.
+    pub fn regulate(&mut self, dish: &(impl Environment + ?Sized), tick: &TickContext) {
+        // Damage from toxins, collisions and strain; regeneration when fed
+        let clock = dish.clock();
+        let injury = Injury::assess(
+            (self.val_l, self.val_r),
+            tick.impact,
+            self.morphology.sensor_dist,
+            &clock,
+        );
+        self.health = health::settle(self.health, &injury, self.energy, &clock);
+        if self.health <= 0.0 {
+            self.died_at = Some(self.tick_count);
+            self.speed = 0.0;
+            return;
+        }
+
+        // Goal progress metrics
+        if let Some(goal) = &mut self.goal {
+            goal.track(self.x, self.y);
+        }
+
+        // Arrival detection; a reached external goal is completed and removed
+        let target = self.navigation_target();
+        if let Some(event) = self
+            .arrivals
+            .update(target, self.x, self.y, self.tick_count)
+        {
+            self.record_arrival(event);
+        }
+    }
+
+    /// Settles the tick's `energy` with the reserve, feeds the starvation
+    /// forecast the change in stored energy and the tick's `cost`, and counts
+    /// starvation; returns true if the agent has just starved to death.
+    fn settle_energy(&mut self, energy: f64, cost: f64, clock: &SimClock) -> bool {
+        // The reserve banks overflow and surplus, and covers shortfalls
+        let stored = self.stored_energy();
+        self.energy = self.reserve.exchange(energy, clock);
+        self.starvation.observe(self.stored_energy() - stored, cost);
+
+        // Death: starved for too long
+        if self.energy > 0.0 {
+            self.starving_ticks = 0;
+        } else {
+            self.starving_ticks += 1;
+        }
+        if self.starving_ticks >= DEATH_STARVATION_TICKS {
+            self.died_at = Some(self.tick_count);
+            self.speed = 0.0;
+            return true;
+        }
+        false
+    }
+
+    /// One tick of dormancy: no inference, decision or movement. The agent
+    /// samples every `DORMANT_SAMPLE_INTERVAL` ticks and wakes once its mean
+    /// reading reaches `WAKE_CONCENTRATION`; meanwhile it pays only
+    /// `DORMANT_METABOLIC_FACTOR` of the base metabolism (plus its samples)
+    /// and eats nothing, so it can still starve.
+    pub fn dormant_step(&mut self, dish: &(impl Environment + ?Sized)) {
+        self.tick_count += 1;
+        self.speed = 0.0;
+        let clock = dish.clock();
+        self.age += clock.dt();
+        self.keep_time(&clock);
+        let mut cost = clock
+            .rate(BASE_METABOLIC_COST * DORMANT_METABOLIC_FACTOR * self.circadian_metabolism());
+        if self.sampled {
+            cost += clock.rate(self.morphology.sample_cost());
+            let mean_sense = f64::midpoint(self.val_l, self.val_r);
+            if mean_sense >= WAKE_CONCENTRATION {
+                self.dormant_since = None;
+                self.last_mean_sense = mean_sense;
+                self.sample_countdown = 0;
+            } else {
+                self.sample_countdown = DORMANT_SAMPLE_INTERVAL - 1;
+            }
+        }
+        self.settle_energy(
+            self.finite_or_zero(self.energy - cost, "energy"),
+            cost,
+            &clock,
+        );
+    }
+
+    /// The active inference decision (`ActiveInferenceController`): picks the
+    /// next sensing interval and saccade by EFE, follows or replans the
+    /// committed MCTS plan, and blends the EFE action and plan step with the
+    /// reactive, homeostatic, taxis, exploration and goal steering terms.
+    /// The plan step's speed action sets the cruise speed.
+    #[allow(clippy::too_many_lines)]
+    pub fn active_inference_decision(&mut self) -> Decision {
+        let mean_sense = f64::midpoint(self.val_l, self.val_r);
+
+        // Pick the next sampling interval (EFE of each sensing rate)
+        if self.sampled {
+            self.sensing_interval = self.select_sensing_interval();
+            self.sample_countdown = self.sensing_interval - 1;
+        }
+        // Sweep the sensors at the next sample if the information is worth it
+        self.saccade_planned = self.sample_countdown == 0 && self.select_saccade();
+        // Scan the gradient's bearing at the next sample while it is vague
+        self.scan_planned = self.sample_countdown == 0 && self.select_scan();
 
         // Select action using EFE-based planning
         let efe_action = self.select_action_efe();
 
-        // MCTS Planning: replan periodically or when urgent
-        let should_replan = self.tick_count == 0
-            || self.tick_count.saturating_sub(self.last_plan_tick) >= MCTS_REPLAN_INTERVAL
-            || self.energy < MCTS_URGENT_ENERGY;
+        // MCTS Planning: follow the committed plan until it runs out or an
+        // interruption (surprise spike, hazard, arrival) invalidates it
+        // Hazard: a sensor reads the toxic void (off the dish or inside an
+        // obstacle), or a predator is close
+        let hazard = self.val_l < 0.0
+            || self.val_r < 0.0
+            || f64::midpoint(self.threat_l, self.threat_r) > THREAT_HAZARD_LEVEL;
+        self.plan_executor.check(&PlanSignals {
+            vfe: self.current_vfe,
+            hazard,
+            arrivals: self.events.count(),
+        });
 
-        if should_replan {
-            let state = AgentState::new(self.x, self.y, self.angle, self.speed, self.energy);
-            self.planned_action = self.planner.plan(&state, &self.spatial_priors);
-            self.last_plan_tick = self.tick_count;
+        let replanned = !self.plan_executor.is_committed();
+        if replanned {
+            self.replan();
         }
+        self.planned_action = self
+            .plan_executor
+            .next_action()
+            .unwrap_or_else(|| self.planner.best_action());
 
-        // === PHASE 4: ACTION EXECUTION ===
-
-        // Blend EFE-selected action with MCTS and reactive components
+        // Blend EFE-selected action with the committed plan step and reactive components
         let efe_delta = efe_action.angle_delta();
         let mcts_delta = self.planned_action.angle_delta();
 
@@ -269,36 +1248,64 @@ impl Protozoa {
         let gradient = self.val_l - self.val_r;
         let reactive_d_theta = -0.1 * homeostatic_error * spatial_precision * gradient;
 
-        // Exploration bonus for uncertain regions
-        let exploration_bonus = EXPLORATION_SCALE / spatial_precision;
-        let explore_direction = rng.random_range(-1.0..1.0) * exploration_bonus;
+        // Per-species homeostatic steering (zero without species preferences)
+        let species_d_theta = self.species_steering();
+
+        // Thermotaxis toward the preferred temperature
+        let thermal_d_theta = self.thermal_steering();
+
+        // pH homeostasis: a second set-point that can pull against the nutrient one
+        let ph_d_theta = self.ph_steering();
+
+        // Phototaxis: sign and strength set by the light-sensitivity morphology
+        let light_d_theta = self.phototaxis_steering();
 
-        // Noise proportional to VFE (high uncertainty = more exploration)
-        let noise = rng.random_range(-NOISE_SCALE..NOISE_SCALE)
-            * (self.current_vfe / MAX_VFE).clamp(0.0, 1.0);
+        // Trail following (stigmergy): sign and strength set by the morphology
+        let trail_d_theta = self.trail_steering();
 
-        // Panic Turn (if conditions worsening rapidly)
+        // Recruitment: turn toward the quorum signal as much as the model weights it
+        let signal_d_theta = self.recruitment_steering();
+
+        // Social preference: toward or away from company, along with neighbors
+        let social_d_theta = self.social_steering();
+
+        // Turn toward the gradient's bearing as far as scans have settled it
+        let scan_d_theta = self.scan_steering(mean_sense);
+
+        // Exploration bonus for uncertain regions, wandering off in a
+        // correlated random walk under directional persistence
+        let exploration_bonus =
+            EXPLORATION_SCALE / spatial_precision * self.circadian_exploration();
+        self.explore_turn = correlated_turn(
+            self.explore_turn,
+            self.streams.exploration.random_range(-1.0..1.0),
+            self.heading_persistence(),
+        );
+        let explore_direction = self.explore_turn * exploration_bonus;
+
+        // Noise proportional to VFE (high uncertainty = more exploration),
+        // amplified by stress
+        let noise = self.streams.noise.random_range(-NOISE_SCALE..NOISE_SCALE)
+            * (self.current_vfe / MAX_VFE).clamp(0.0, 1.0)
+            * self.stress_noise();
+
+        // Panic Turn (if conditions worsening rapidly, or stress is high)
         let mut panic_turn = 0.0;
-        if self.temp_gradient < PANIC_THRESHOLD {
-            panic_turn = rng.random_range(-PANIC_TURN_RANGE..PANIC_TURN_RANGE);
-        }
-
-        // Goal-directed navigation toward remembered landmarks when energy is low
-        let goal_attraction = if self.energy < MCTS_URGENT_ENERGY {
-            if let Some(landmark) =
-                self.episodic_memory
-                    .best_distant_landmark(self.x, self.y, LANDMARK_VISIT_RADIUS)
-            {
-                let dx = landmark.x - self.x;
-                let dy = landmark.y - self.y;
-                let target_angle = dy.atan2(dx);
-                let angle_diff = (target_angle - self.angle).rem_euclid(2.0 * PI);
-                let normalized_diff = if angle_diff > PI {
-                    angle_diff - 2.0 * PI
-                } else {
-                    angle_diff
-                };
-                LANDMARK_ATTRACTION_SCALE * normalized_diff * landmark.reliability
+        if self.is_panicking() {
+            panic_turn = self
+                .streams
+                .panic
+                .random_range(-PANIC_TURN_RANGE..PANIC_TURN_RANGE);
+        }
+
+        // Goal-directed navigation toward remembered landmarks, as strongly
+        // as hunger urges it (below `MCTS_URGENT_ENERGY` when unmodulated)
+        let urge = self.generative_model.landmark_urge(self.energy);
+        let goal_attraction = if urge > 0.0 {
+            if let Some(landmark) = self.navigation_landmark() {
+                urge * LANDMARK_ATTRACTION_SCALE
+                    * self.heading_error(landmark.x, landmark.y)
+                    * landmark.reliability
             } else {
                 0.0
             }
@@ -306,88 +1313,638 @@ impl Protozoa {
             0.0
         };
 
-        // Blend all heading contributions
+        // Exogenous goal: turn toward the preferred position, weighted by
+        // priority / (1 + priority) so homeostasis still matters
+        let external_goal = self.goal.map_or(0.0, |goal| {
+            GOAL_ATTRACTION_SCALE
+                * self.heading_error(goal.x, goal.y)
+                * (goal.priority / (1.0 + goal.priority))
+        });
+
+        // Habit: turn the way demonstrators swam here (zero without a bootstrap)
+        let habit_d_theta = self.habit_steering();
+
+        // Blend all heading contributions, recording each one
         // EFE action gets highest weight as it's the principled Active Inference component
-        let d_theta = assert_finite(
-            0.4 * efe_delta
-                + 0.2 * mcts_delta
-                + 0.2 * reactive_d_theta
-                + explore_direction
-                + noise
-                + panic_turn
-                + goal_attraction,
-            "d_theta",
-        );
+        let mut turn = 0.0;
+        for (term, value) in [
+            (Term::Efe, 0.4 * efe_delta),
+            (Term::Plan, PLAN_STEP_WEIGHT * mcts_delta),
+            (Term::Reactive, 0.2 * reactive_d_theta),
+            (Term::Species, species_d_theta),
+            (Term::Thermal, thermal_d_theta),
+            (Term::Ph, ph_d_theta),
+            (Term::Light, light_d_theta),
+            (Term::Trail, trail_d_theta),
+            (Term::Signal, signal_d_theta),
+            (Term::Social, social_d_theta),
+            (Term::Scan, scan_d_theta),
+            (Term::Exploration, explore_direction),
+            (Term::Noise, noise),
+            (Term::Panic, panic_turn),
+            (Term::Landmark, goal_attraction),
+            (Term::Goal, external_goal),
+            (Term::Habit, habit_d_theta),
+        ] {
+            self.provenance.terms[term as usize] = value;
+            turn += value;
+        }
+        // Directional persistence: the turning cost holds on to the last turn
+        let persistence = turning_cost_term(turn, self.last_turn, self.heading_persistence());
+        self.provenance.terms[Term::Persistence as usize] = persistence;
+        turn += persistence;
+        self.last_turn = turn;
+        self.provenance.efe_action = Some(efe_action);
+        self.provenance.planned_action = Some(self.planned_action);
+        self.provenance.replanned = replanned;
+
+        // Speed follows the plan: its rollouts trade the cost of moving
+        // against the information and nutrients it reaches
+        self.cruise_speed = self.planned_action.next_speed(self.cruise_speed);
+
+        Decision {
+            turn: self.finite_or_zero(turn, "d_theta"),
+            speed: self.finite_or_zero(self.cruise_speed, "cruise_speed"),
+        }
+    }
 
-        self.angle += d_theta;
-        self.angle = self.angle.rem_euclid(2.0 * PI);
+    /// Plans from the current state with a budget sized by hunger,
+    /// uncertainty (`planning_pressure`) and the starvation forecast's
+    /// urgency (within `planning_cap`), and commits to the result.
+    fn replan(&mut self) {
+        let pressure = planning_pressure(self.energy, self.current_vfe)
+            .max(self.starvation.urgency(self.stored_energy()));
+        self.planner
+            .set_budget(PlanningBudget::for_pressure(pressure).capped(self.planning_cap));
+        self.planner.set_drive(self.generative_model.drive());
+        let state = self.rollout_state();
+        self.planner
+            .plan_avoiding(&state, &self.spatial_priors, Some(&self.threat_priors));
+        self.plan_executor
+            .commit(self.planner.best_plan(), self.events.count());
+        self.last_plan_tick = self.tick_count;
+    }
 
-        // Speed Update: Move to reduce VFE (proportional to free energy)
-        // Higher VFE = more "anxious" = move faster to find preferred states
-        self.speed = MAX_SPEED * (self.current_vfe / MAX_VFE).clamp(0.1, 1.0);
+    /// The agent as the planner's rollouts start from: its pose, commanded
+    /// speed, energy and body in its current motion.
+    #[must_use]
+    pub fn rollout_state(&self) -> AgentState {
+        AgentState::new(self.x, self.y, self.angle, self.cruise_speed, self.energy)
+            .with_body(
+                self.morphology.kinematics,
+                self.morphology.sensor_dist,
+                self.motion(),
+            )
+            .with_transition(self.generative_model.transition)
+    }
 
-        // === PHASE 5: MEMORY & LEARNING ===
+    /// Speed and angular velocity the body carries.
+    #[must_use]
+    pub fn motion(&self) -> Motion {
+        Motion {
+            speed: self.speed,
+            spin: self.spin,
+        }
+    }
 
-        // Update spatial prior with observation (world model learning)
-        self.spatial_priors.update(self.x, self.y, mean_sense);
+    /// What the agent expects a trip to cost: its stored energy, and the
+    /// metabolism's rate per second (base, swimming at the cruise speed, or
+    /// `INITIAL_CRUISE_SPEED` if slower, and sampling at the current
+    /// interval) per unit of distance covered.
+    #[must_use]
+    pub fn travel_model(&self) -> TravelModel {
+        let speed = self.cruise_speed.max(INITIAL_CRUISE_SPEED);
+        let per_second = BASE_METABOLIC_COST * self.circadian_metabolism()
+            + SPEED_METABOLIC_COST * speed / MAX_SPEED
+            + self.morphology.sample_cost() / f64::from(self.sensing_interval.max(1));
+        TravelModel {
+            stored: self.stored_energy(),
+            cost_per_distance: per_second / speed,
+        }
+    }
 
-        // Record experience in short-term memory
-        self.sensor_history.push(SensorSnapshot {
-            val_l: self.val_l,
-            val_r: self.val_r,
-            x: self.x,
-            y: self.y,
-            energy: self.energy,
-            tick: self.tick_count,
+    /// Landmark goal navigation heads for: the best distant one the agent
+    /// can expect to reach, weighed by `travel_model()`.
+    #[must_use]
+    pub fn navigation_landmark(&self) -> Option<&Landmark> {
+        self.episodic_memory.best_reachable_landmark(
+            self.x,
+            self.y,
+            LANDMARK_VISIT_RADIUS,
+            &self.travel_model(),
+        )
+    }
+
+    /// Target the agent is currently navigating toward: the external goal if
+    /// set, else the `navigation_landmark()` when hunger urges it.
+    #[must_use]
+    pub fn navigation_target(&self) -> Option<(ArrivalTarget, f64, f64)> {
+        if let Some(goal) = &self.goal {
+            return Some((ArrivalTarget::Goal, goal.x, goal.y));
+        }
+        if self.generative_model.landmark_urge(self.energy) <= 0.0 {
+            return None;
+        }
+        self.navigation_landmark()
+            .map(|landmark| (ArrivalTarget::Landmark, landmark.x, landmark.y))
+    }
+
+    /// Logs an arrival event, completing the external goal if it was reached.
+    fn record_arrival(&mut self, event: ArrivalEvent) {
+        if event.target == ArrivalTarget::Goal {
+            self.clear_goal();
+        }
+        self.events.push(event);
+    }
+
+    /// Signed angle in (-π, π] from the current heading to the point (x, y).
+    fn heading_error(&self, x: f64, y: f64) -> f64 {
+        let target_angle = (y - self.y).atan2(x - self.x);
+        let angle_diff = (target_angle - self.angle).rem_euclid(2.0 * PI);
+        if angle_diff > PI {
+            angle_diff - 2.0 * PI
+        } else {
+            angle_diff
+        }
+    }
+
+    /// Starts the agent's maps (spatial and threat priors, habits) afresh,
+    /// sized to a `width` × `height` dish.
+    pub fn size_maps(&mut self, width: f64, height: f64) {
+        self.spatial_priors = SpatialGrid::new(width, height);
+        self.threat_priors = SpatialGrid::new(width, height);
+        self.habits = HabitMap::new(width, height);
+    }
+
+    /// Spatial priors the agent holds for `dish_id`, if it has been there.
+    #[must_use]
+    pub fn spatial_priors_in(&self, dish_id: usize) -> Option<&SpatialGrid<20, 10>> {
+        if dish_id == self.dish_id {
+            return Some(&self.spatial_priors);
+        }
+        self.dish_memories
+            .iter()
+            .find(|m| m.dish_id == dish_id)
+            .map(|m| &m.spatial_priors)
+    }
+
+    /// Moves the agent to (x, y) in dish `to`, a `width` × `height` dish,
+    /// keeping its heading.
+    ///
+    /// The spatial priors and landmarks of the dish being left are stashed in
+    /// `dish_memories` and those of `to` restored (fresh ones covering the
+    /// dish on a first visit). The threat map starts afresh, as each dish has
+    /// its own predators. Position beliefs are resynced, and the committed plan, the
+    /// arrival leg and any goal are dropped since they refer to the old dish.
+    pub fn migrate(&mut self, to: usize, x: f64, y: f64, width: f64, height: f64) {
+        if to != self.dish_id {
+            let restored = match self.dish_memories.iter().position(|m| m.dish_id == to) {
+                Some(i) => self.dish_memories.swap_remove(i),
+                None => DishMemory {
+                    dish_id: to,
+                    spatial_priors: SpatialGrid::new(width, height),
+                    episodic_memory: EpisodicMemory::new(),
+                },
+            };
+            self.dish_memories.push(DishMemory {
+                dish_id: self.dish_id,
+                spatial_priors: std::mem::replace(
+                    &mut self.spatial_priors,
+                    restored.spatial_priors,
+                ),
+                episodic_memory: std::mem::replace(
+                    &mut self.episodic_memory,
+                    restored.episodic_memory,
+                ),
+            });
+            self.dish_id = to;
+            self.threat_priors = SpatialGrid::new(width, height);
+            self.habits = HabitMap::new(width, height);
+        }
+        self.x = x;
+        self.y = y;
+        self.beliefs.sync_position(x, y, self.angle);
+        self.plan_executor.abandon();
+        self.arrivals = ArrivalTracker::new();
+        self.clear_goal();
+    }
+
+    /// Follows a resize of dish `dish_id` by (sx, sy) (see `PetriDish::resize`).
+    ///
+    /// The spatial priors and landmarks kept for that dish are stretched with
+    /// it. If the agent is in the dish, its position, particle cloud and any
+    /// goal move too, position beliefs are resynced, and the committed plan and arrival leg
+    /// are dropped since their targets were in the old coordinates.
+    pub fn rescale_dish(&mut self, dish_id: usize, sx: f64, sy: f64) {
+        let stretch = |priors: &mut SpatialGrid<20, 10>, landmarks: &mut EpisodicMemory| {
+            let (width, height) = priors.world_dimensions();
+            priors.resize(width * sx, height * sy);
+            landmarks.rescale(sx, sy);
+        };
+        if dish_id != self.dish_id {
+            if let Some(memory) = self.dish_memories.iter_mut().find(|m| m.dish_id == dish_id) {
+                stretch(&mut memory.spatial_priors, &mut memory.episodic_memory);
+            }
+            return;
+        }
+        stretch(&mut self.spatial_priors, &mut self.episodic_memory);
+        let (width, height) = self.threat_priors.world_dimensions();
+        self.threat_priors.resize(width * sx, height * sy);
+        self.habits.resize(width * sx, height * sy);
+        self.x *= sx;
+        self.y *= sy;
+        self.beliefs.sync_position(self.x, self.y, self.angle);
+        if let Some(particles) = &mut self.particles {
+            particles.rescale(sx, sy);
+        }
+        self.plan_executor.abandon();
+        self.arrivals = ArrivalTracker::new();
+        if let Some(goal) = self.goal {
+            self.set_goal(goal.x * sx, goal.y * sy, goal.priority);
+        }
+    }
+
+    /// Installs an exogenous goal at (x, y).
+    ///
+    /// The goal becomes a prior preference over position in the generative
+    /// model with precision `GOAL_PRIOR_PRECISION × priority`, replacing any
+    /// previous goal. Progress is tracked in `self.goal`.
+    pub fn set_goal(&mut self, x: f64, y: f64, priority: f64) {
+        let priority = priority.max(0.0);
+        self.generative_model
+            .set_position_preference(x, y, GOAL_PRIOR_PRECISION * priority);
+        self.goal = Some(Goal::new(x, y, priority, (self.x, self.y), self.tick_count));
+    }
+
+    /// Removes the exogenous goal and restores the default position prior.
+    pub fn clear_goal(&mut self) {
+        self.generative_model.clear_position_preference();
+        self.goal = None;
+    }
+
+    /// Applies a parsed goal command.
+    pub fn apply_goal_command(&mut self, command: GoalCommand) {
+        match command {
+            GoalCommand::Set { x, y, priority } => self.set_goal(x, y, priority),
+            GoalCommand::Clear => self.clear_goal(),
+        }
+    }
+
+    /// Reactive heading change from per-species preferences.
+    ///
+    /// Mirrors the single-nutrient reactive term for each species k:
+    /// `Δθ = -g Σₖ πₖ (ōₖ - ηₖ)(Lₖ - Rₖ)`, turning toward species below their
+    /// target and away from species above it.
+    fn species_steering(&self) -> f64 {
+        self.generative_model
+            .species_preferences
+            .iter()
+            .zip(self.species_l.iter().zip(&self.species_r))
+            .map(|(pref, (l, r))| {
+                let error = f64::midpoint(*l, *r) - pref.target;
+                -SPECIES_STEERING_GAIN * pref.precision * error * (l - r)
+            })
+            .sum()
+    }
+
+    /// Reactive heading change from the thermal preference.
+    ///
+    /// Uses the precision-weighted temperature belief `μ_T` rather than the raw
+    /// readings: `Δθ = -g πη,T (μ_T - η_T)(T_L - T_R)`.
+    fn thermal_steering(&self) -> f64 {
+        let error = self.beliefs.mean.temperature - self.generative_model.prior_mean.temperature;
+        -THERMAL_STEERING_GAIN
+            * self.generative_model.prior_precision.temperature
+            * error
+            * (self.thermo_l - self.thermo_r)
+    }
+
+    /// Reactive heading change from the pH set-point.
+    ///
+    /// `Δθ = -g π_pH (ō - η_pH)(pH_L - pH_R)`: turns toward more alkaline water
+    /// when too acidic and toward more acidic water when too alkaline.
+    fn ph_steering(&self) -> f64 {
+        let error = f64::midpoint(self.ph_l, self.ph_r) - self.morphology.preferred_ph;
+        -PH_STEERING_GAIN
+            * self.generative_model.prior_precision.ph
+            * error
+            * (self.ph_l - self.ph_r)
+    }
+
+    /// Reactive heading change from the light-sensitivity morphology.
+    ///
+    /// `Δθ = g s (I_L - I_R)`: positive sensitivity turns toward the brighter
+    /// side, negative sensitivity away from it.
+    #[must_use]
+    pub fn phototaxis_steering(&self) -> f64 {
+        PHOTOTAXIS_GAIN * self.morphology.light_sensitivity * (self.light_l - self.light_r)
+    }
+
+    /// Reactive heading change toward the stronger pheromone trail.
+    ///
+    /// `Δθ = g s (P_L - P_R)`: positive sensitivity turns toward the side
+    /// with more trail, negative sensitivity away from it.
+    #[must_use]
+    pub fn trail_steering(&self) -> f64 {
+        PHEROMONE_GAIN
+            * self.morphology.pheromone_sensitivity
+            * (self.pheromone_l - self.pheromone_r)
+    }
+
+    /// Heading change toward the demonstrated heading where the agent is.
+    ///
+    /// `Δθ = g w sin(φ_h - θ)` for the habit heading `φ_h` and weight `w` of
+    /// its cell: zero without a habit prior.
+    #[must_use]
+    pub fn habit_steering(&self) -> f64 {
+        let habit = self.habits.at(self.x, self.y);
+        HABIT_GAIN * habit.weight * (habit.heading - self.angle).sin()
+    }
+
+    /// Lays this tick's trail (`pheromone_deposit` per second) where the
+    /// agent is; the dead lay none.
+    pub fn lay_pheromone(&self, dish: &mut PetriDish) {
+        if self.is_alive() {
+            let amount = dish.clock.rate(self.morphology.pheromone_deposit);
+            dish.pheromone.deposit(self.x, self.y, amount);
+        }
+    }
+
+    /// Reactive heading change toward the stronger quorum signal.
+    ///
+    /// `Δθ = g π_signal (s_L - s_R)`: the descent direction of `signal_risk`,
+    /// zero when the model gives the signal no precision.
+    #[must_use]
+    pub fn recruitment_steering(&self) -> f64 {
+        SIGNAL_STEERING_GAIN
+            * self.generative_model.prior_precision.signal
+            * (self.signal_l - self.signal_r)
+    }
+
+    /// Reactive heading change from the social preference.
+    ///
+    /// `Δθ = -g π_social (ρ - ρ*)(ρ_L - ρ_R) + g_a a sin(φ̄ - θ)`: the descent
+    /// direction of `social_risk`, toward the more crowded side while below
+    /// the preferred density and away from it above, and toward the
+    /// neighbors' mean heading as much as alignment is preferred.
+    #[must_use]
+    pub fn social_steering(&self) -> f64 {
+        let preference = &self.generative_model.social;
+        if preference.is_off() {
+            return 0.0;
+        }
+        let error = self.social.density() - preference.density;
+        let density = -SOCIAL_STEERING_GAIN
+            * preference.precision
+            * error
+            * (self.social.left - self.social.right);
+        let alignment = self.social.heading.map_or(0.0, |heading| {
+            SOCIAL_ALIGNMENT_GAIN * preference.alignment * (heading - self.angle).sin()
         });
-        self.tick_count += 1;
+        density + alignment
+    }
 
-        // Episodic memory: landmark detection and maintenance
-        self.episodic_memory.decay_all();
+    /// Heading change toward the believed gradient bearing.
+    ///
+    /// `Δθ = g c (β − θ)`, up the gradient while `mean_sense` is below the
+    /// nutrient target and down it above, with c the bearing belief's
+    /// `confidence()`: zero until a scan has settled it.
+    #[must_use]
+    pub fn scan_steering(&self, mean_sense: f64) -> f64 {
+        let error = self.bearing.heading_error(self.angle);
+        let toward = if mean_sense < TARGET_CONCENTRATION {
+            error
+        } else {
+            error - PI.copysign(error)
+        };
+        SCAN_TURN_GAIN * self.bearing.confidence() * toward
+    }
 
-        if mean_sense > LANDMARK_THRESHOLD {
-            self.episodic_memory
-                .maybe_store(self.x, self.y, mean_sense, self.tick_count);
+    /// Broadcasts this tick's quorum signal (`signal_emission` per second)
+    /// where the agent is, if it is exploiting a patch.
+    pub fn broadcast(&self, dish: &mut PetriDish) {
+        if self.morphology.signal_emission > 0.0 && self.current_mode(dish) == AgentMode::Exploiting
+        {
+            let amount = dish.clock.rate(self.morphology.signal_emission);
+            dish.signal.emit(self.x, self.y, amount);
         }
+    }
 
-        self.episodic_memory
-            .update_on_visit(self.x, self.y, mean_sense, self.tick_count);
+    /// Whether the agent has not starved yet.
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        self.died_at.is_none()
+    }
 
-        // === PHASE 6: METABOLISM ===
+    /// The agent's ID and name as shown in the dashboard (`#3 alice`).
+    #[must_use]
+    pub fn label(&self) -> String {
+        identity::label(self.lineage_id, self.name.as_deref())
+    }
 
-        let metabolic_cost =
-            BASE_METABOLIC_COST + (SPEED_METABOLIC_COST * (self.speed / MAX_SPEED));
-        let intake = INTAKE_RATE * mean_sense;
+    /// Names the agent.
+    ///
+    /// # Errors
+    /// If the name is not valid (`identity::validate_name`); the old name
+    /// stays.
+    pub fn rename(&mut self, name: &str) -> Result<(), String> {
+        self.name = Some(identity::validate_name(name)?.to_string());
+        Ok(())
+    }
 
-        self.energy = assert_finite(self.energy - metabolic_cost + intake, "energy");
-        self.energy = self.energy.clamp(0.0, 1.0);
+    /// Whether a strict agent has halted on a non-finite value.
+    #[must_use]
+    pub fn is_halted(&self) -> bool {
+        self.fault.is_some()
+    }
 
-        // Exhaustion check
-        if self.energy <= EXHAUSTION_THRESHOLD {
-            self.speed *= EXHAUSTION_SPEED_FACTOR;
+    /// `value` as `assert_finite` passes it; a strict agent keeps it even if
+    /// it is not finite, for `check_finite` to catch.
+    fn finite_or_zero(&self, value: f64, context: &str) -> f64 {
+        if self.numerics.is_strict() {
+            value
+        } else {
+            assert_finite(value, context)
         }
+    }
 
-        // === PHASE 7: POSITION UPDATE ===
+    /// Checks the agent's numerical state after a phase of `tick`: its pose,
+    /// energy and readings, then inference (beliefs, sensory precisions),
+    /// planning (EFE estimates, the rollout start) and morphology.
+    ///
+    /// # Errors
+    /// The first value that is NaN or infinite.
+    pub fn check_finite(&self, tick: &TickContext) -> Result<(), NonFinite> {
+        finite_all([
+            ("x", self.x),
+            ("y", self.y),
+            ("angle", self.angle),
+            ("speed", self.speed),
+            ("spin", self.spin),
+            ("cruise_speed", self.cruise_speed),
+            ("energy", self.energy),
+            ("reserve.level", self.reserve.level),
+            ("health", self.health),
+            ("age", self.age),
+            ("val_l", self.val_l),
+            ("val_r", self.val_r),
+            ("vfe", self.current_vfe),
+            ("stress", self.stress),
+            ("tick.mean_sense", tick.mean_sense),
+            ("tick.decision.turn", tick.decision.turn),
+            ("tick.decision.speed", tick.decision.speed),
+            ("tick.commanded_speed", tick.commanded_speed),
+        ])?;
+        self.beliefs.check_finite()?;
+        self.generative_model.check_finite()?;
+        self.planner.check_finite()?;
+        self.sensor_suite.check_finite()?;
+        if let Some(particles) = &self.particles {
+            particles.check_finite()?;
+        }
+        self.rollout_state().check_finite()?;
+        self.morphology.check_finite()
+    }
 
-        self.x += self.speed * self.angle.cos();
-        self.y += self.speed * self.angle.sin();
+    /// In strict mode, checks the state after `stage` of `tick` and halts
+    /// the agent with a `NumericalFault` on the first non-finite value.
+    /// Returns whether it halted (never in lenient mode).
+    pub fn halt_on_fault(&mut self, stage: &'static str, tick: &TickContext) -> bool {
+        if !self.numerics.is_strict() {
+            return false;
+        }
+        if let Err(error) = self.check_finite(tick) {
+            self.fault = Some(Box::new(NumericalFault {
+                error,
+                stage,
+                tick: self.tick_count,
+                dump: format!("{self:#?}"),
+            }));
+        }
+        self.is_halted()
+    }
 
-        // Boundary Check
-        self.x = self.x.clamp(0.0, dish.width);
-        self.y = self.y.clamp(0.0, dish.height);
+    /// Whether the agent is dormant (see `dormant_step`).
+    #[must_use]
+    pub fn is_dormant(&self) -> bool {
+        self.dormant_since.is_some()
+    }
+
+    /// Share of its young limits the agent keeps at its age (1 until
+    /// senescence sets in).
+    #[must_use]
+    pub fn vigor(&self) -> f64 {
+        self.morphology.senescence.vigor(self.age)
+    }
+
+    /// One tick of the circadian clock (see `circadian.rs`): the phase
+    /// belief free-runs, then a fresh daylight reading corrects it by a
+    /// Gauss-Newton step. An agent without a clock keeps no time.
+    fn keep_time(&mut self, clock: &SimClock) {
+        let circadian = self.morphology.circadian;
+        if circadian.is_off() {
+            return;
+        }
+        self.beliefs.advance_phase(
+            circadian.phase_step(clock),
+            CIRCADIAN_PHASE_DIFFUSION * clock.dt(),
+        );
+        if self.sampled {
+            let model = &self.generative_model;
+            let gradient = circadian_gradient(self.daylight, &self.beliefs, model);
+            let information = model.sensory_precision.daylight
+                * model.daylight_slope(self.beliefs.mean.phase).powi(2);
+            self.beliefs.entrain_phase(gradient, information);
+        }
+    }
+
+    /// Factor on the base metabolism at the believed time of day (1 without
+    /// a circadian clock).
+    #[must_use]
+    pub fn circadian_metabolism(&self) -> f64 {
+        self.morphology
+            .circadian
+            .metabolic_factor(self.beliefs.mean.phase)
+    }
+
+    /// Factor on the exploration bonus at the believed time of day (1
+    /// without a circadian clock).
+    #[must_use]
+    pub fn circadian_exploration(&self) -> f64 {
+        self.morphology
+            .circadian
+            .exploration_factor(self.beliefs.mean.phase)
+    }
+
+    /// Factor on the learned sensory precisions at the current stress (1
+    /// without a stress response).
+    #[must_use]
+    pub fn stress_precision(&self) -> f64 {
+        self.morphology.stress.precision_factor(self.stress)
+    }
+
+    /// Correlation of the heading over the distance swum in a second at the
+    /// current speed (0 without directional persistence).
+    #[must_use]
+    pub fn heading_persistence(&self) -> f64 {
+        heading_correlation(self.speed, self.morphology.persistence_length)
+    }
+
+    /// Factor on the VFE-driven heading noise at the current stress (1
+    /// without a stress response).
+    #[must_use]
+    pub fn stress_noise(&self) -> f64 {
+        self.morphology.stress.noise_factor(self.stress)
+    }
+
+    /// Whether the agent panics: with a stress response, while stress is at
+    /// least `STRESS_PANIC_LEVEL`; without one, when the mean reading falls
+    /// faster than `PANIC_THRESHOLD` per tick.
+    #[must_use]
+    pub fn is_panicking(&self) -> bool {
+        if self.morphology.stress.is_off() {
+            self.temp_gradient < PANIC_THRESHOLD
+        } else {
+            self.stress >= STRESS_PANIC_LEVEL
+        }
+    }
+
+    /// How far the sensors reach now: `sensor_dist`, shortened by age.
+    #[must_use]
+    pub fn sensor_reach(&self) -> f64 {
+        self.morphology.sensor_dist * self.vigor()
+    }
+
+    /// Energy held in both compartments, fast and reserve.
+    #[must_use]
+    pub fn stored_energy(&self) -> f64 {
+        self.energy + self.reserve.level
+    }
+
+    /// Forecast ticks until starvation at the current net energy rate, and
+    /// assuming no further intake (see `StarvationForecast`).
+    #[must_use]
+    pub fn starvation_forecast(&self) -> (Option<f64>, Option<f64>) {
+        let stored = self.stored_energy();
+        (
+            self.starvation.ticks_left(stored),
+            self.starvation.ticks_left_fasting(stored),
+        )
     }
 
     /// Select action by minimizing Expected Free Energy.
     ///
-    /// Evaluates each candidate action and returns the one with lowest EFE.
+    /// Evaluates each heading action and returns the one with lowest EFE,
+    /// the nutrient risk weighted by the observed energy (`interoceptive_efe`).
+    /// Speed is left to the planner, whose rollouts see what moving buys.
     fn select_action_efe(&self) -> Action {
         let mut best_action = Action::Straight;
         let mut best_efe = f64::INFINITY;
 
-        for action in Action::all() {
+        for action in Action::TURNS {
             // Predict beliefs after taking this action
             let predicted = self.predict_beliefs_after_action(action);
-            let efe = expected_free_energy(&predicted, &self.generative_model);
+            let efe = interoceptive_efe(&predicted, &self.generative_model, self.energy);
 
             if efe < best_efe {
                 best_efe = efe;
@@ -398,24 +1955,83 @@ impl Protozoa {
         best_action
     }
 
+    /// Sampling interval from `SENSING_INTERVALS` with the lowest
+    /// `sensing_efe`: sparser sampling saves sensing energy but leaves the
+    /// nutrient belief to drift. Ties go to the shorter interval.
+    fn select_sensing_interval(&self) -> u32 {
+        let cost = self.morphology.sample_cost();
+        let mut best = SENSING_INTERVALS[0];
+        let mut best_efe = f64::INFINITY;
+        for interval in SENSING_INTERVALS {
+            let efe = sensing_efe(&self.beliefs, &self.generative_model, interval, cost);
+            if efe < best_efe {
+                best_efe = efe;
+                best = interval;
+            }
+        }
+        best
+    }
+
+    /// Whether a saccade at the next sample has negative `saccade_efe`:
+    /// the information gained about the lateral nutrient slope outweighs
+    /// `SACCADE_COST`. Never with a zero `saccade_sweep`.
+    fn select_saccade(&self) -> bool {
+        self.morphology.saccade_sweep > 0.0
+            && saccade_efe(
+                &self.beliefs,
+                &self.generative_model,
+                self.morphology.sensor_angle,
+                self.morphology.saccade_sweep,
+                SACCADE_COST,
+            ) < 0.0
+    }
+
+    /// Whether a scan at the next sample has negative `scan_efe`: the
+    /// bearing belief is vague enough that pinning it down outweighs
+    /// `SCAN_COST`. Never with too few `scan_orientations`.
+    fn select_scan(&self) -> bool {
+        let orientations = self.morphology.scan_orientations;
+        orientations >= MIN_SCAN_ORIENTATIONS
+            && scan_efe(
+                self.bearing.var,
+                self.bearing.amplitude,
+                2 * orientations,
+                &self.generative_model,
+                SCAN_COST,
+            ) < 0.0
+    }
+
     /// Predict beliefs after taking an action.
     ///
     /// Uses the generative model's transition dynamics to predict future beliefs.
     fn predict_beliefs_after_action(&self, action: Action) -> BeliefState {
         let mut predicted = self.beliefs.clone();
 
-        // Predict state change from action
-        predicted.mean.angle += action.angle_delta();
-        predicted.mean.angle = predicted.mean.angle.rem_euclid(2.0 * PI);
-
-        // Predict position change (assuming current speed)
-        let speed_estimate = self.speed.max(0.5); // Minimum expected speed
-        predicted.mean.x += speed_estimate * predicted.mean.angle.cos();
-        predicted.mean.y += speed_estimate * predicted.mean.angle.sin();
+        if self.morphology.kinematics.is_off() {
+            // Predict state change from action, as the body carries it out
+            let transition = &self.generative_model.transition;
+            predicted.mean.angle += transition.turn(action.angle_delta());
+            predicted.mean.angle = predicted.mean.angle.rem_euclid(2.0 * PI);
+
+            // Predict position change (assuming current speed)
+            let speed_estimate = self.speed.max(0.5); // Minimum expected speed
+            let (dx, dy) = transition.displacement(speed_estimate, predicted.mean.angle);
+            predicted.mean.x += dx;
+            predicted.mean.y += dy;
+        } else {
+            // Under inertia the turn and speed lag the command: predict the
+            // move the planner's rollouts would make
+            let moved = self.rollout_state().step(action, &self.spatial_priors);
+            let turned = moved.angle - self.angle;
+            predicted.mean.angle = (predicted.mean.angle + turned).rem_euclid(2.0 * PI);
+            predicted.mean.x += moved.motion.speed * predicted.mean.angle.cos();
+            predicted.mean.y += moved.motion.speed * predicted.mean.angle.sin();
+        }
 
-        // Clamp predicted position to dish
-        predicted.mean.x = predicted.mean.x.clamp(0.0, DISH_WIDTH);
-        predicted.mean.y = predicted.mean.y.clamp(0.0, DISH_HEIGHT);
+        // Clamp predicted position to the dish the priors cover
+        let (width, height) = self.spatial_priors.world_dimensions();
+        predicted.mean.x = predicted.mean.x.clamp(0.0, width);
+        predicted.mean.y = predicted.mean.y.clamp(0.0, height);
 
         // Predict nutrient belief from spatial priors
         let expected_nutrient = self
@@ -426,7 +2042,7 @@ impl Protozoa {
             0.5 * predicted.mean.nutrient + 0.5 * expected_nutrient.mean.clamp(0.0, 1.0);
 
         // Uncertainty increases with prediction (future is uncertain)
-        predicted.increase_uncertainty(UNCERTAINTY_GROWTH);
+        predicted.increase_uncertainty(self.generative_model.uncertainty_growth());
 
         predicted
     }
@@ -434,24 +2050,26 @@ impl Protozoa {
     /// Returns the current behavioral mode derived from internal state.
     #[must_use]
     #[allow(dead_code)] // Used by tests and future UI components
-    pub fn current_mode(&self, _dish: &PetriDish) -> AgentMode {
+    pub fn current_mode(&self, _dish: &(impl Environment + ?Sized)) -> AgentMode {
+        if !self.is_alive() {
+            return AgentMode::Dead;
+        }
+        if self.is_dormant() {
+            return AgentMode::Dormant;
+        }
+
         // Check exhausted first (most critical)
         if self.energy <= EXHAUSTION_THRESHOLD {
             return AgentMode::Exhausted;
         }
 
-        // Check if panicking (temporal gradient)
-        if self.temp_gradient < PANIC_THRESHOLD {
+        // Check if panicking (temporal gradient, or stress)
+        if self.is_panicking() {
             return AgentMode::Panicking;
         }
 
-        // Check goal navigation (low energy, has landmark)
-        if self.energy < MCTS_URGENT_ENERGY
-            && self
-                .episodic_memory
-                .best_distant_landmark(self.x, self.y, LANDMARK_VISIT_RADIUS)
-                .is_some()
-        {
+        // Check goal navigation (external goal, or low energy with a landmark)
+        if self.navigation_target().is_some() {
             return AgentMode::GoalNav;
         }
 
@@ -465,12 +2083,31 @@ impl Protozoa {
         AgentMode::Exploring
     }
 
-    /// Returns ticks until next MCTS replan.
+    /// Returns ticks until next MCTS replan (committed plan steps left,
+    /// unless an interruption ends the plan sooner).
     #[must_use]
     #[allow(dead_code)] // Used by tests and future UI components
     pub fn ticks_until_replan(&self) -> u64 {
-        let elapsed = self.tick_count.saturating_sub(self.last_plan_tick);
-        MCTS_REPLAN_INTERVAL.saturating_sub(elapsed)
+        self.plan_executor.remaining() as u64
+    }
+
+    /// Applies new capacities to the agent's growing structures, compacting
+    /// any that are already over them.
+    #[allow(dead_code)] // Used by tests and long-run experiments
+    pub fn set_memory_limits(&mut self, limits: &MemoryLimits) {
+        self.events.set_capacity(limits.event_log);
+        self.planner.cache_mut().set_capacity(limits.rollout_cache);
+    }
+
+    /// Approximate memory held by the agent.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            fixed: std::mem::size_of::<Self>(),
+            event_log: self.events.heap_bytes(),
+            rollout_cache: self.planner.cache().heap_bytes(),
+            ..MemoryUsage::default()
+        }
     }
 
     /// Returns the current Variational Free Energy.
@@ -0,0 +1,235 @@
+//! Change markers for runtime parameter edits.
+//!
+//! Whenever a parameter changes mid-run (today: `set NAME VALUE` typed on the
+//! command line), the change is recorded as a `Marker` at the current tick and
+//! sim time. The ensemble charts draw every marker as a vertical line, so a
+//! shift in the curves can be told apart from the agent adapting on its own.
+//! Anything else that changes parameters at runtime should record a marker
+//! through `MarkerLog::record` too.
+
+use crate::simulation::agent::Morphology;
+use crate::simulation::clock::SimClock;
+use crate::simulation::params::MARKER_LOG_CAPACITY;
+use std::collections::VecDeque;
+
+/// A labeled point in sim time where a parameter changed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Marker {
+    pub tick: u64,
+    /// Sim-seconds at the change (`SimClock::seconds`)
+    pub time: f64,
+    /// What changed, e.g. `light 0.00 -> 2.00`
+    pub label: String,
+}
+
+/// Bounded log of recent markers, oldest first.
+#[derive(Clone, Debug)]
+pub struct MarkerLog {
+    recent: VecDeque<Marker>,
+    capacity: usize,
+    total: usize,
+}
+
+impl Default for MarkerLog {
+    fn default() -> Self {
+        Self::with_capacity(MARKER_LOG_CAPACITY)
+    }
+}
+
+impl MarkerLog {
+    /// Creates a log keeping at most `capacity` recent markers.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            recent: VecDeque::with_capacity(capacity),
+            capacity,
+            total: 0,
+        }
+    }
+
+    /// Records a change at the clock's current tick, evicting the oldest
+    /// marker beyond the capacity.
+    pub fn record(&mut self, clock: SimClock, label: impl Into<String>) {
+        self.total += 1;
+        if self.capacity == 0 {
+            return;
+        }
+        if self.recent.len() >= self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(Marker {
+            tick: clock.tick,
+            time: clock.seconds(),
+            label: label.into(),
+        });
+    }
+
+    /// Recent markers, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Marker> {
+        self.recent.iter()
+    }
+
+    /// Markers recorded since the start of the run (including evicted ones).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn count(&self) -> usize {
+        self.total
+    }
+}
+
+/// Morphology parameter adjustable at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MorphologyParam {
+    /// `sensor_dist`
+    SensorDist,
+    /// `sensor_angle` (radians)
+    SensorAngle,
+    /// `belief_learning_rate`
+    LearningRate,
+    /// `light_sensitivity`
+    Light,
+    /// `preferred_ph`
+    Ph,
+    /// `sensing_cost`
+    SensingCost,
+    /// `saccade_sweep` (radians)
+    Saccade,
+    /// `pheromone_deposit`
+    Deposit,
+    /// `pheromone_sensitivity`
+    Trail,
+    /// `senescence.rate`
+    Aging,
+    /// `persistence_length`
+    Persistence,
+}
+
+impl MorphologyParam {
+    /// Every parameter, in command-line order.
+    pub const ALL: [Self; 11] = [
+        Self::SensorDist,
+        Self::SensorAngle,
+        Self::LearningRate,
+        Self::Light,
+        Self::Ph,
+        Self::SensingCost,
+        Self::Saccade,
+        Self::Deposit,
+        Self::Trail,
+        Self::Aging,
+        Self::Persistence,
+    ];
+
+    /// Name used on the command line.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SensorDist => "sensor_dist",
+            Self::SensorAngle => "sensor_angle",
+            Self::LearningRate => "learning_rate",
+            Self::Light => "light",
+            Self::Ph => "ph",
+            Self::SensingCost => "sensing_cost",
+            Self::Saccade => "saccade",
+            Self::Deposit => "deposit",
+            Self::Trail => "trail",
+            Self::Aging => "aging",
+            Self::Persistence => "persistence",
+        }
+    }
+
+    /// Current value in `morphology`.
+    #[must_use]
+    pub fn get(self, morphology: &Morphology) -> f64 {
+        match self {
+            Self::SensorDist => morphology.sensor_dist,
+            Self::SensorAngle => morphology.sensor_angle,
+            Self::LearningRate => morphology.belief_learning_rate,
+            Self::Light => morphology.light_sensitivity,
+            Self::Ph => morphology.preferred_ph,
+            Self::SensingCost => morphology.sensing_cost,
+            Self::Saccade => morphology.saccade_sweep,
+            Self::Deposit => morphology.pheromone_deposit,
+            Self::Trail => morphology.pheromone_sensitivity,
+            Self::Aging => morphology.senescence.rate,
+            Self::Persistence => morphology.persistence_length,
+        }
+    }
+
+    fn slot(self, morphology: &mut Morphology) -> &mut f64 {
+        match self {
+            Self::SensorDist => &mut morphology.sensor_dist,
+            Self::SensorAngle => &mut morphology.sensor_angle,
+            Self::LearningRate => &mut morphology.belief_learning_rate,
+            Self::Light => &mut morphology.light_sensitivity,
+            Self::Ph => &mut morphology.preferred_ph,
+            Self::SensingCost => &mut morphology.sensing_cost,
+            Self::Saccade => &mut morphology.saccade_sweep,
+            Self::Deposit => &mut morphology.pheromone_deposit,
+            Self::Trail => &mut morphology.pheromone_sensitivity,
+            Self::Aging => &mut morphology.senescence.rate,
+            Self::Persistence => &mut morphology.persistence_length,
+        }
+    }
+}
+
+/// A textual parameter change, `set NAME VALUE`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamCommand {
+    pub param: MorphologyParam,
+    pub value: f64,
+}
+
+impl ParamCommand {
+    /// Parses `set NAME VALUE` (names from `MorphologyParam::name`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable message for other commands, unknown names,
+    /// non-numeric values and values outside the parameter's range
+    /// (distances and rates must be positive, pH within [0, 1], the sensing
+    /// cost, saccade sweep, pheromone deposit, aging rate and persistence
+    /// length not negative).
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let usage = || {
+            let names: Vec<&str> = MorphologyParam::ALL.iter().map(|p| p.name()).collect();
+            format!("usage: set {} VALUE", names.join("|"))
+        };
+        let mut words = input.split_whitespace();
+        if words.next() != Some("set") {
+            return Err(usage());
+        }
+        let name = words.next().ok_or_else(usage)?;
+        let param = MorphologyParam::ALL
+            .into_iter()
+            .find(|p| p.name() == name)
+            .ok_or_else(|| format!("unknown parameter: {name}"))?;
+        let text = words.next().ok_or_else(usage)?;
+        let value = text
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| format!("invalid value: {text}"))?;
+        let valid = match param {
+            MorphologyParam::SensorDist | MorphologyParam::LearningRate => value > 0.0,
+            MorphologyParam::Ph => (0.0..=1.0).contains(&value),
+            MorphologyParam::SensingCost
+            | MorphologyParam::Saccade
+            | MorphologyParam::Deposit
+            | MorphologyParam::Aging
+            | MorphologyParam::Persistence => value >= 0.0,
+            MorphologyParam::SensorAngle | MorphologyParam::Light | MorphologyParam::Trail => true,
+        };
+        if !valid {
+            return Err(format!("{name} out of range: {value}"));
+        }
+        Ok(Self { param, value })
+    }
+
+    /// Applies the change and returns the marker label (`name old -> new`).
+    pub fn apply(self, morphology: &mut Morphology) -> String {
+        let old = self.param.get(morphology);
+        *self.param.slot(morphology) = self.value;
+        format!("{} {old:.2} -> {:.2}", self.param.name(), self.value)
+    }
+}
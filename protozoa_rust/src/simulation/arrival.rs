@@ -0,0 +1,269 @@
+//! Arrival detection for goal-directed navigation.
+//!
+//! A navigation *leg* starts when the agent commits to a target (an external
+//! goal or a remembered landmark). The leg completes once the agent has stayed
+//! within `ARRIVAL_RADIUS` of the target for `ARRIVAL_DWELL_TICKS` consecutive
+//! ticks, emitting an `ArrivalEvent` with the travel time and path length.
+
+use crate::simulation::params::{ARRIVAL_DWELL_TICKS, ARRIVAL_RADIUS, EVENT_LOG_CAPACITY};
+use std::collections::VecDeque;
+
+/// What the agent was navigating toward.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArrivalTarget {
+    /// An external goal (see `Protozoa::set_goal`)
+    Goal,
+    /// A landmark from episodic memory
+    Landmark,
+}
+
+/// Completion of a navigation leg.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArrivalEvent {
+    pub target: ArrivalTarget,
+    /// Target position
+    pub x: f64,
+    pub y: f64,
+    /// Tick at which the arrival was confirmed (after dwelling)
+    pub tick: u64,
+    /// Ticks from the start of the leg until first entering the arrival radius
+    pub travel_ticks: u64,
+    /// Distance actually travelled until first entering the arrival radius
+    pub path_length: f64,
+    /// Straight-line distance to the target at the start of the leg
+    pub straight_distance: f64,
+}
+
+impl ArrivalEvent {
+    /// Straight-line distance over path length in [0, 1] (1 = direct route).
+    #[must_use]
+    pub fn path_efficiency(&self) -> f64 {
+        if self.path_length <= f64::EPSILON {
+            return 1.0;
+        }
+        (self.straight_distance / self.path_length).clamp(0.0, 1.0)
+    }
+}
+
+/// The navigation leg currently being tracked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Leg {
+    target: ArrivalTarget,
+    x: f64,
+    y: f64,
+    start_tick: u64,
+    straight_distance: f64,
+    path_length: f64,
+    /// Travel ticks and path length when the radius was first entered
+    entered: Option<(u64, f64)>,
+    dwell: u64,
+    completed: bool,
+}
+
+/// Detects arrivals at navigation targets.
+#[derive(Clone, Debug, Default)]
+pub struct ArrivalTracker {
+    leg: Option<Leg>,
+    last_position: Option<(f64, f64)>,
+}
+
+impl ArrivalTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current navigation target, if a leg is in progress.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn current_target(&self) -> Option<(ArrivalTarget, f64, f64)> {
+        self.leg
+            .filter(|leg| !leg.completed)
+            .map(|leg| (leg.target, leg.x, leg.y))
+    }
+
+    /// Advances the tracker by one tick.
+    ///
+    /// `candidate` is the target the agent is steering toward this tick. A new
+    /// candidate starts a new leg, except inside the radius of the current
+    /// landmark: landmark navigation ignores landmarks the agent is already
+    /// at, and eating there ends the low-energy drive, so the leg is kept
+    /// while the agent dwells. External goals always take over immediately.
+    pub fn update(
+        &mut self,
+        candidate: Option<(ArrivalTarget, f64, f64)>,
+        x: f64,
+        y: f64,
+        tick: u64,
+    ) -> Option<ArrivalEvent> {
+        let step = self
+            .last_position
+            .map_or(0.0, |(px, py)| (x - px).hypot(y - py));
+        self.last_position = Some((x, y));
+
+        let dwelling_at_landmark = self.leg.is_some_and(|leg| {
+            leg.target == ArrivalTarget::Landmark
+                && !leg.completed
+                && (leg.x - x).hypot(leg.y - y) <= ARRIVAL_RADIUS
+        });
+        let keep = dwelling_at_landmark && !matches!(candidate, Some((ArrivalTarget::Goal, _, _)));
+
+        if !keep
+            && candidate.map(|(t, cx, cy)| (t, cx.to_bits(), cy.to_bits()))
+                != self
+                    .leg
+                    .map(|leg| (leg.target, leg.x.to_bits(), leg.y.to_bits()))
+        {
+            self.leg = candidate.map(|(target, cx, cy)| Leg {
+                target,
+                x: cx,
+                y: cy,
+                start_tick: tick,
+                straight_distance: (cx - x).hypot(cy - y),
+                path_length: 0.0,
+                entered: None,
+                dwell: 0,
+                completed: false,
+            });
+            // The first tick of a leg only records the start position
+            if self.leg.is_some() {
+                return None;
+            }
+        }
+
+        let leg = self.leg.as_mut().filter(|leg| !leg.completed)?;
+        leg.path_length += step;
+
+        if (leg.x - x).hypot(leg.y - y) > ARRIVAL_RADIUS {
+            leg.entered = None;
+            leg.dwell = 0;
+            return None;
+        }
+
+        let (travel_ticks, path_length) = *leg
+            .entered
+            .get_or_insert((tick.saturating_sub(leg.start_tick), leg.path_length));
+        leg.dwell += 1;
+        if leg.dwell < ARRIVAL_DWELL_TICKS {
+            return None;
+        }
+
+        leg.completed = true;
+        Some(ArrivalEvent {
+            target: leg.target,
+            x: leg.x,
+            y: leg.y,
+            tick,
+            travel_ticks,
+            path_length,
+            straight_distance: leg.straight_distance,
+        })
+    }
+}
+
+/// Bounded log of recent arrival events with running totals.
+#[derive(Clone, Debug)]
+pub struct EventLog {
+    recent: VecDeque<ArrivalEvent>,
+    /// Maximum number of recent events kept
+    capacity: usize,
+    total: usize,
+    total_travel_ticks: u64,
+    total_path_length: f64,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::with_capacity(EVENT_LOG_CAPACITY)
+    }
+}
+
+impl EventLog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a log keeping at most `capacity` recent events.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            recent: VecDeque::with_capacity(capacity),
+            capacity,
+            total: 0,
+            total_travel_ticks: 0,
+            total_path_length: 0.0,
+        }
+    }
+
+    /// Records an event, evicting the oldest beyond the capacity.
+    ///
+    /// Running totals include evicted events.
+    pub fn push(&mut self, event: ArrivalEvent) {
+        self.total += 1;
+        self.total_travel_ticks += event.travel_ticks;
+        self.total_path_length += event.path_length;
+        if self.capacity == 0 {
+            return;
+        }
+        if self.recent.len() >= self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(event);
+    }
+
+    /// Maximum number of recent events kept.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and long-run experiments
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity, dropping the oldest events that no longer fit
+    /// and releasing their storage.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.recent.len().saturating_sub(capacity);
+        self.recent.drain(..excess);
+        self.recent.shrink_to(capacity);
+    }
+
+    /// Bytes of storage held for recent events.
+    #[must_use]
+    pub fn heap_bytes(&self) -> usize {
+        self.recent.capacity() * std::mem::size_of::<ArrivalEvent>()
+    }
+
+    /// Recent events, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &ArrivalEvent> {
+        self.recent.iter()
+    }
+
+    /// Most recent event.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and navigation benchmarks
+    pub fn last(&self) -> Option<&ArrivalEvent> {
+        self.recent.back()
+    }
+
+    /// Number of arrivals since the start of the run (including evicted ones).
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.total
+    }
+
+    /// Mean travel time over all arrivals.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Tick counts are far below 2^52
+    pub fn mean_travel_ticks(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.total_travel_ticks as f64 / self.total as f64)
+    }
+
+    /// Mean path length over all arrivals.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and navigation benchmarks
+    #[allow(clippy::cast_precision_loss)] // Arrival counts are small
+    pub fn mean_path_length(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.total_path_length / self.total as f64)
+    }
+}
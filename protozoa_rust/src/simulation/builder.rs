@@ -0,0 +1,156 @@
+//! Builder for agents with a chosen internal state.
+//!
+//! `Protozoa::builder()` starts from the agent `with_heading` would make at
+//! the dish center, with maps sized to the dish (`dish`, the default size
+//! unless set), and lets tests and experiments set its pose, energy,
+//! body plan, random streams, maps and policy before it exists, instead of
+//! constructing a default agent and editing its public fields afterwards:
+//!
+//! ```text
+//! let agent = Protozoa::builder().position(20.0, 10.0).heading(0.0).seed(7).build();
+//! ```
+
+use crate::simulation::agent::{DishMemory, Morphology, Protozoa};
+use crate::simulation::controller::ControllerKind;
+use crate::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use crate::simulation::streams::Stream;
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Settings of an agent under construction (`Protozoa::builder`).
+#[derive(Clone, Debug)]
+pub struct ProtozoaBuilder {
+    /// Size of the dish the agent starts in
+    width: f64,
+    height: f64,
+    /// Start position; the dish center when unset
+    position: Option<(f64, f64)>,
+    /// Start heading; drawn when unset (from the seed's spawn stream if seeded)
+    heading: Option<f64>,
+    energy: f64,
+    morphology: Option<Morphology>,
+    /// Master seed of the agent's random streams
+    seed: Option<u64>,
+    /// Maps to start with, and the dish they belong to
+    memory: Option<DishMemory>,
+    controller: ControllerKind,
+}
+
+impl Default for ProtozoaBuilder {
+    /// Dish center, drawn heading, full energy and default everything else.
+    fn default() -> Self {
+        Self {
+            width: DISH_WIDTH,
+            height: DISH_HEIGHT,
+            position: None,
+            heading: None,
+            energy: 1.0,
+            morphology: None,
+            seed: None,
+            memory: None,
+            controller: ControllerKind::default(),
+        }
+    }
+}
+
+impl Protozoa {
+    /// Starts building an agent (see `ProtozoaBuilder`).
+    #[must_use]
+    pub fn builder() -> ProtozoaBuilder {
+        ProtozoaBuilder::default()
+    }
+}
+
+impl ProtozoaBuilder {
+    /// Size of the dish the agent starts in; its spatial and threat priors
+    /// and habits cover that dish.
+    #[must_use]
+    pub fn dish(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Start position (beliefs start there too).
+    #[must_use]
+    pub fn position(mut self, x: f64, y: f64) -> Self {
+        self.position = Some((x, y));
+        self
+    }
+
+    /// Start heading in radians.
+    #[must_use]
+    pub fn heading(mut self, angle: f64) -> Self {
+        self.heading = Some(angle);
+        self
+    }
+
+    /// Start energy.
+    #[must_use]
+    pub fn energy(mut self, energy: f64) -> Self {
+        self.energy = energy;
+        self
+    }
+
+    /// Body plan in place of the default one.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn morphology(mut self, morphology: &Morphology) -> Self {
+        self.morphology = Some(*morphology);
+        self
+    }
+
+    /// Seeds every random stream (`Protozoa::seed_streams`) and, unless a
+    /// heading is given, the heading draw.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Spatial priors and landmarks to start with; the agent starts in the
+    /// dish they belong to.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn memory(mut self, memory: DishMemory) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Policy driving the agent.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn controller(mut self, controller: ControllerKind) -> Self {
+        self.controller = controller;
+        self
+    }
+
+    /// Builds the agent.
+    #[must_use]
+    pub fn build(self) -> Protozoa {
+        let heading = self.heading.unwrap_or_else(|| match self.seed {
+            Some(seed) => Stream::Spawn.rng(seed).random_range(0.0..2.0 * PI),
+            None => rand::rng().random_range(0.0..2.0 * PI),
+        });
+        let (x, y) = self
+            .position
+            .unwrap_or((self.width / 2.0, self.height / 2.0));
+        let mut agent = Protozoa::with_heading(x, y, heading);
+        agent.size_maps(self.width, self.height);
+        agent.energy = self.energy;
+        agent.controller = self.controller;
+        if let Some(seed) = self.seed {
+            agent.seed_streams(seed);
+        }
+        if let Some(morphology) = self.morphology {
+            agent.morphology = morphology;
+        }
+        if let Some(memory) = self.memory {
+            agent.dish_id = memory.dish_id;
+            agent.spatial_priors = memory.spatial_priors;
+            agent.episodic_memory = memory.episodic_memory;
+        }
+        agent
+    }
+}
@@ -0,0 +1,127 @@
+//! Sensor calibration: how raw chemoreceptor reads become observations.
+//!
+//! Real receptor pairs are rarely matched. `SensorCalibration` maps the two
+//! raw nutrient readings to what the agent observes: an optional left/right
+//! swap (crossed wiring, flipped handedness), then a gain and an offset per
+//! channel, then a saturation ceiling. The default is the identity, so a
+//! calibrated agent reads the field as it is. Toxic-void readings (-1) are
+//! only swapped, so hazards stay visible through any miscalibration.
+//!
+//! The agent is not told its calibration; a `BiasEstimator` can learn the
+//! resulting left/right offset from its prediction errors.
+
+/// Gain and offset of one receptor channel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChannelCalibration {
+    pub gain: f64,
+    pub offset: f64,
+}
+
+impl Default for ChannelCalibration {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            offset: 0.0,
+        }
+    }
+}
+
+impl ChannelCalibration {
+    /// Maps a raw reading through gain and offset.
+    #[must_use]
+    pub fn apply(&self, raw: f64) -> f64 {
+        self.gain * raw + self.offset
+    }
+}
+
+/// Mapping from raw chemoreceptor reads to the agent's observations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SensorCalibration {
+    pub left: ChannelCalibration,
+    pub right: ChannelCalibration,
+    /// Left and right receptors are wired to the opposite channels
+    pub swapped: bool,
+    /// Highest level a channel can report
+    pub saturation: f64,
+}
+
+impl Default for SensorCalibration {
+    fn default() -> Self {
+        Self {
+            left: ChannelCalibration::default(),
+            right: ChannelCalibration::default(),
+            swapped: false,
+            saturation: 1.0,
+        }
+    }
+}
+
+impl SensorCalibration {
+    /// Returns true if observations equal the raw reads.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Observations `(left, right)` for raw reads `(raw_l, raw_r)`.
+    #[must_use]
+    pub fn apply(&self, raw_l: f64, raw_r: f64) -> (f64, f64) {
+        let (raw_l, raw_r) = if self.swapped {
+            (raw_r, raw_l)
+        } else {
+            (raw_l, raw_r)
+        };
+        let channel = |calibration: &ChannelCalibration, raw: f64| {
+            if raw < 0.0 {
+                return raw;
+            }
+            calibration.apply(raw).clamp(0.0, self.saturation)
+        };
+        (channel(&self.left, raw_l), channel(&self.right, raw_r))
+    }
+
+    /// Parses a comma-separated spec such as `gain_l=1.2,offset_r=0.1,swap`.
+    ///
+    /// Keys are `gain_l`, `gain_r`, `offset_l`, `offset_r`, `saturation` and
+    /// the flag `swap`; unset keys keep the identity.
+    ///
+    /// # Errors
+    /// Returns a message for an unknown key, a malformed number, a negative
+    /// gain or a saturation that is not positive.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut calibration = Self::default();
+        for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            if item == "swap" {
+                calibration.swapped = true;
+                continue;
+            }
+            let (key, value) = item
+                .split_once('=')
+                .ok_or_else(|| format!("expected KEY=VALUE or swap: {item}"))?;
+            let value = value
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("invalid {key}: {value}"))?;
+            match key {
+                "gain_l" => calibration.left.gain = value,
+                "gain_r" => calibration.right.gain = value,
+                "offset_l" => calibration.left.offset = value,
+                "offset_r" => calibration.right.offset = value,
+                "saturation" => calibration.saturation = value,
+                _ => return Err(format!("unknown calibration key: {key}")),
+            }
+        }
+        if calibration.left.gain < 0.0 || calibration.right.gain < 0.0 {
+            return Err("gains must not be negative".to_string());
+        }
+        if calibration.saturation <= 0.0 {
+            return Err(format!(
+                "saturation must be positive: {}",
+                calibration.saturation
+            ));
+        }
+        Ok(calibration)
+    }
+}
@@ -0,0 +1,105 @@
+//! Circadian clock: an internal oscillator entrained by daylight.
+//!
+//! The clock is the agent's belief about the time of day, a hidden state
+//! `BeliefMean::phase` (0 = noon, π = midnight, as in `DayNightCycle`).
+//! Between samples it free-runs, advancing 2π per `period` seconds while
+//! its variance grows by `CIRCADIAN_PHASE_DIFFUSION` per second; at each
+//! sample the daylight the photoreceptors read corrects it by a
+//! Gauss-Newton step on the circadian free energy (`circadian_gradient`),
+//! so an agent whose period or phase is off locks onto the dish's day.
+//! With φ ~ `N(μ_φ, σ²_φ)`, period T, daylight D and the predicted daylight
+//! g(φ) = 1 − Â (1 − cos φ)/2:
+//!
+//! ```text
+//! μ_φ ← μ_φ + 2π Δt / T        σ²_φ ← min(σ²_φ + D_φ Δt, σ²_max)   free-run
+//! F_D = ½ π_D (D − g)²
+//! σ⁻²_φ ← σ⁻²_φ + π_D g'²      μ_φ ← μ_φ + σ²_φ π_D g' (D − g)     at a sample
+//! ```
+//!
+//! The believed time of day modulates the agent: at believed night its base
+//! metabolism drops by up to `metabolic_depth` (rest) and its exploration
+//! bonus by up to `exploration_depth`. With both depths 0 (the default)
+//! the agent keeps no time at all.
+
+use crate::simulation::clock::SimClock;
+use crate::simulation::params::{CIRCADIAN_DEPTH, CIRCADIAN_PERIOD};
+use std::f64::consts::PI;
+
+/// Believed share of daylight at circadian `phase`: 1 at noon, 0 at midnight.
+#[must_use]
+pub fn daytime(phase: f64) -> f64 {
+    0.5 * (1.0 + phase.cos())
+}
+
+/// An agent's circadian clock and how much it matters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circadian {
+    /// Free-running period in seconds
+    pub period: f64,
+    /// Share of the base metabolism saved at believed midnight, in [0, 1]
+    pub metabolic_depth: f64,
+    /// Share of the exploration bonus dropped at believed midnight, in [0, 1]
+    pub exploration_depth: f64,
+}
+
+impl Default for Circadian {
+    fn default() -> Self {
+        Self::new(CIRCADIAN_PERIOD, CIRCADIAN_DEPTH)
+    }
+}
+
+impl Circadian {
+    /// A clock of `period` seconds modulating metabolism and exploration by
+    /// the same `depth`.
+    #[must_use]
+    pub fn new(period: f64, depth: f64) -> Self {
+        Self {
+            period,
+            metabolic_depth: depth,
+            exploration_depth: depth,
+        }
+    }
+
+    /// Returns true if the clock modulates nothing (and is not kept).
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.metabolic_depth <= 0.0 && self.exploration_depth <= 0.0
+    }
+
+    /// Phase the clock advances in one tick of `clock`.
+    #[must_use]
+    pub fn phase_step(&self, clock: &SimClock) -> f64 {
+        2.0 * PI * clock.dt() / self.period
+    }
+
+    /// Factor on the base metabolism at believed `phase`:
+    /// `1 - d_m (1 - D(φ))`.
+    #[must_use]
+    pub fn metabolic_factor(&self, phase: f64) -> f64 {
+        1.0 - self.metabolic_depth * (1.0 - daytime(phase))
+    }
+
+    /// Factor on the exploration bonus at believed `phase`:
+    /// `1 - d_e (1 - D(φ))`.
+    #[must_use]
+    pub fn exploration_factor(&self, phase: f64) -> f64 {
+        1.0 - self.exploration_depth * (1.0 - daytime(phase))
+    }
+
+    /// Checks that the period is finite and positive and both depths lie
+    /// in [0, 1].
+    ///
+    /// # Errors
+    /// Names the first offending value.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.period.is_finite() && self.period > 0.0) {
+            return Err(format!("circadian period out of range: {}", self.period));
+        }
+        for depth in [self.metabolic_depth, self.exploration_depth] {
+            if !(0.0..=1.0).contains(&depth) {
+                return Err(format!("circadian depth out of range: {depth}"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+//! Simulation clock with sim-time semantics.
+//!
+//! Biological rates in `params.rs` (metabolism, intake, source decay, drift,
+//! movement) are expressed per simulated second. `SimClock` turns them into
+//! per-tick amounts for the configured `seconds_per_tick`, so changing the
+//! tick rate changes the time resolution, not the biology.
+
+use crate::simulation::params::SECONDS_PER_TICK;
+
+/// Tick counter plus the sim-time length of one tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimClock {
+    /// Ticks elapsed
+    pub tick: u64,
+    /// Simulated seconds per tick (`dt`)
+    pub seconds_per_tick: f64,
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new(SECONDS_PER_TICK)
+    }
+}
+
+impl SimClock {
+    /// Creates a clock at tick 0.
+    #[must_use]
+    pub fn new(seconds_per_tick: f64) -> Self {
+        Self {
+            tick: 0,
+            seconds_per_tick,
+        }
+    }
+
+    /// Advances the clock by one tick.
+    pub fn advance(&mut self) {
+        self.tick += 1;
+    }
+
+    /// Simulated seconds per tick.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and sim-time experiments
+    pub fn dt(&self) -> f64 {
+        self.seconds_per_tick
+    }
+
+    /// Simulated seconds elapsed.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Tick counts are far below 2^52
+    pub fn seconds(&self) -> f64 {
+        self.tick as f64 * self.seconds_per_tick
+    }
+
+    /// Per-tick amount of a quantity accumulating at `per_second`.
+    #[must_use]
+    pub fn rate(&self, per_second: f64) -> f64 {
+        per_second * self.seconds_per_tick
+    }
+
+    /// Per-tick multiplier of a quantity shrinking by `per_second` each second.
+    #[must_use]
+    pub fn factor(&self, per_second: f64) -> f64 {
+        per_second.powf(self.seconds_per_tick)
+    }
+
+    /// Per-tick chance of an event that happens with `per_second` chance each second.
+    #[must_use]
+    pub fn probability(&self, per_second: f64) -> f64 {
+        1.0 - (1.0 - per_second.clamp(0.0, 1.0)).powf(self.seconds_per_tick)
+    }
+
+    /// Per-tick step of a random walk with `per_second` spread after one second.
+    #[must_use]
+    pub fn diffusion(&self, per_second: f64) -> f64 {
+        per_second * self.seconds_per_tick.sqrt()
+    }
+
+    /// Whole ticks covering `seconds` of sim time (at least one for positive durations).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and for scheduling events in sim time
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn ticks_for(&self, seconds: f64) -> u64 {
+        if seconds <= 0.0 {
+            return 0;
+        }
+        ((seconds / self.seconds_per_tick).round() as u64).max(1)
+    }
+}
@@ -0,0 +1,299 @@
+//! Dish configuration loaded at startup.
+//!
+//! `DishConfig` mirrors the environment constants in `params.rs` (dish size,
+//! source ranges, decay rates, brownian step) and can be read from a TOML
+//! file, so environments can be tuned without recompiling. Missing keys fall
+//! back to the `params.rs` defaults; unknown keys are rejected to catch typos.
+//!
+//! ```toml
+//! width = 120.0
+//! source_count_min = 3
+//! source_count_max = 6
+//! decay_min = 0.995
+//! brownian_step = 0.2
+//! viscosity_zones = 2
+//! ph_spots = 2
+//! season_length = 5000
+//! season_depth = 0.6
+//! kernel = "exponential"
+//! turbulence_amplitude = 0.05
+//! inference = "ekf"
+//!
+//! [sensor_noise.left]
+//! std = 0.2
+//! drift = 0.01
+//!
+//! [motor_noise]
+//! turn_std = 0.1
+//! speed_std = 0.2
+//! ```
+
+use crate::simulation::cycle::{DayNightCycle, SeasonCycle};
+use crate::simulation::inference::InferenceScheme;
+use crate::simulation::kernel::Kernel;
+use crate::simulation::motor_noise::MotorNoise;
+use crate::simulation::params::{
+    BROWNIAN_STEP, DAY_NIGHT_AMPLITUDE, DAY_NIGHT_PERIOD, DISH_HEIGHT, DISH_WIDTH, PH_SPOT_COUNT,
+    RESPAWN_THRESHOLD, SEASON_DEPTH, SEASON_LENGTH, SECONDS_PER_TICK, SOURCE_COUNT_MAX,
+    SOURCE_COUNT_MIN, SOURCE_DECAY_MAX, SOURCE_DECAY_MIN, SOURCE_INTENSITY_MAX,
+    SOURCE_INTENSITY_MIN, SOURCE_MARGIN, SOURCE_RADIUS_MAX, SOURCE_RADIUS_MIN,
+    TURBULENCE_AMPLITUDE, TURBULENCE_PERIOD, TURBULENCE_SCALE, VISCOSITY_ZONE_COUNT,
+};
+use crate::simulation::sensor_noise::SensorNoise;
+use crate::simulation::turbulence::Turbulence;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Runtime environment parameters (defaults from `params.rs`).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DishConfig {
+    pub width: f64,
+    pub height: f64,
+    /// Margin from dish edges for source placement
+    pub source_margin: f64,
+    /// Minimum sources per species (inclusive)
+    pub source_count_min: usize,
+    /// Maximum sources per species (inclusive)
+    pub source_count_max: usize,
+    pub source_radius_min: f64,
+    pub source_radius_max: f64,
+    pub source_intensity_min: f64,
+    pub source_intensity_max: f64,
+    /// Slowest per-tick intensity multiplier (fastest decay)
+    pub decay_min: f64,
+    /// Fastest per-tick intensity multiplier (slowest decay)
+    pub decay_max: f64,
+    /// Maximum per-tick source drift along each axis
+    pub brownian_step: f64,
+    /// Intensity below which a source respawns elsewhere
+    pub respawn_threshold: f64,
+    /// Simulated seconds per day/night cycle
+    pub day_night_period: u64,
+    /// Night-time reduction of nutrient supply (0 = no cycle)
+    pub day_night_amplitude: f64,
+    /// Simulated seconds per rich or poor season
+    pub season_length: u64,
+    /// Poor-season reduction of nutrient supply (0 = no seasons)
+    pub season_depth: f64,
+    /// Number of viscous zones slowing the agent
+    pub viscosity_zones: usize,
+    /// Number of acidic/alkaline spots
+    pub ph_spots: usize,
+    /// Simulated seconds per tick (rates are per second)
+    pub seconds_per_tick: f64,
+    /// Radial profile of random sources (`gaussian`, `top_hat`, `exponential`, `annulus`)
+    pub kernel: Kernel,
+    /// Largest turbulent offset on sensed concentration (0 = off)
+    pub turbulence_amplitude: f64,
+    /// Turbulent eddy size in world units
+    pub turbulence_scale: f64,
+    /// Sim-seconds for the turbulence pattern to decorrelate
+    pub turbulence_period: f64,
+    /// Noise on the agents' chemoreceptor readings (noiseless by default)
+    pub sensor_noise: SensorNoise,
+    /// Noise on the agents' executed turns and speed (noiseless by default)
+    pub motor_noise: MotorNoise,
+    /// How the agents update their beliefs (VFE gradient descent by default)
+    pub inference: InferenceScheme,
+}
+
+impl Default for DishConfig {
+    fn default() -> Self {
+        Self {
+            width: DISH_WIDTH,
+            height: DISH_HEIGHT,
+            source_margin: SOURCE_MARGIN,
+            source_count_min: SOURCE_COUNT_MIN,
+            source_count_max: SOURCE_COUNT_MAX,
+            source_radius_min: SOURCE_RADIUS_MIN,
+            source_radius_max: SOURCE_RADIUS_MAX,
+            source_intensity_min: SOURCE_INTENSITY_MIN,
+            source_intensity_max: SOURCE_INTENSITY_MAX,
+            decay_min: SOURCE_DECAY_MIN,
+            decay_max: SOURCE_DECAY_MAX,
+            brownian_step: BROWNIAN_STEP,
+            respawn_threshold: RESPAWN_THRESHOLD,
+            day_night_period: DAY_NIGHT_PERIOD,
+            day_night_amplitude: DAY_NIGHT_AMPLITUDE,
+            season_length: SEASON_LENGTH,
+            season_depth: SEASON_DEPTH,
+            viscosity_zones: VISCOSITY_ZONE_COUNT,
+            ph_spots: PH_SPOT_COUNT,
+            seconds_per_tick: SECONDS_PER_TICK,
+            kernel: Kernel::Gaussian,
+            turbulence_amplitude: TURBULENCE_AMPLITUDE,
+            turbulence_scale: TURBULENCE_SCALE,
+            turbulence_period: TURBULENCE_PERIOD,
+            sensor_noise: SensorNoise::default(),
+            motor_noise: MotorNoise::default(),
+            inference: InferenceScheme::default(),
+        }
+    }
+}
+
+impl DishConfig {
+    /// Default configuration for a dish of the given size.
+    #[must_use]
+    pub fn sized(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            ..Self::default()
+        }
+    }
+
+    /// Parses and validates a TOML document.
+    ///
+    /// # Errors
+    /// Returns a message for malformed TOML, unknown keys or invalid values.
+    pub fn from_toml_str(text: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(text).map_err(|e| format!("invalid dish config: {e}"))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads a TOML file (see `from_toml_str`).
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be read or is not a valid config.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Serializes the configuration as TOML (a starting point for a config file).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and for writing template configs
+    pub fn to_toml_string(self) -> String {
+        toml::to_string(&self).unwrap_or_default()
+    }
+
+    /// Checks that every range is ordered and every value is usable.
+    ///
+    /// # Errors
+    /// Names the first offending key.
+    #[allow(clippy::cast_precision_loss)] // Source counts are tiny
+    pub fn validate(&self) -> Result<(), String> {
+        let finite = [
+            ("width", self.width),
+            ("height", self.height),
+            ("source_margin", self.source_margin),
+            ("source_radius_min", self.source_radius_min),
+            ("source_radius_max", self.source_radius_max),
+            ("source_intensity_min", self.source_intensity_min),
+            ("source_intensity_max", self.source_intensity_max),
+            ("decay_min", self.decay_min),
+            ("decay_max", self.decay_max),
+            ("brownian_step", self.brownian_step),
+            ("respawn_threshold", self.respawn_threshold),
+            ("day_night_amplitude", self.day_night_amplitude),
+            ("season_depth", self.season_depth),
+            ("seconds_per_tick", self.seconds_per_tick),
+            ("turbulence_amplitude", self.turbulence_amplitude),
+            ("turbulence_scale", self.turbulence_scale),
+            ("turbulence_period", self.turbulence_period),
+        ];
+        if let Some((key, _)) = finite.iter().find(|(_, v)| !v.is_finite()) {
+            return Err(format!("{key} must be finite"));
+        }
+        if self.width <= 0.0 || self.height <= 0.0 {
+            return Err("width and height must be positive".into());
+        }
+        if self.source_margin < 0.0 || 2.0 * self.source_margin >= self.width.min(self.height) {
+            return Err("source_margin must leave room for sources inside the dish".into());
+        }
+        let ranges = [
+            (
+                "source_count",
+                self.source_count_min as f64,
+                self.source_count_max as f64,
+            ),
+            (
+                "source_radius",
+                self.source_radius_min,
+                self.source_radius_max,
+            ),
+            (
+                "source_intensity",
+                self.source_intensity_min,
+                self.source_intensity_max,
+            ),
+            ("decay", self.decay_min, self.decay_max),
+        ];
+        if let Some((key, ..)) = ranges.iter().find(|(_, lo, hi)| lo > hi) {
+            return Err(format!("{key}_min must not exceed {key}_max"));
+        }
+        if self.source_radius_min <= 0.0 {
+            return Err("source_radius_min must be positive".into());
+        }
+        if self.decay_min <= 0.0 || self.decay_max > 1.0 {
+            return Err("decay rates must lie in (0, 1]".into());
+        }
+        if self.brownian_step < 0.0 || self.respawn_threshold < 0.0 {
+            return Err("brownian_step and respawn_threshold must not be negative".into());
+        }
+        if !(0.0..=1.0).contains(&self.day_night_amplitude) {
+            return Err("day_night_amplitude must lie in [0, 1]".into());
+        }
+        if self.day_night_period == 0 {
+            return Err("day_night_period must be positive".into());
+        }
+        if !(0.0..=1.0).contains(&self.season_depth) {
+            return Err("season_depth must lie in [0, 1]".into());
+        }
+        if self.season_length == 0 {
+            return Err("season_length must be positive".into());
+        }
+        if self.seconds_per_tick <= 0.0 {
+            return Err("seconds_per_tick must be positive".into());
+        }
+        if self.turbulence_amplitude < 0.0 {
+            return Err("turbulence_amplitude must not be negative".into());
+        }
+        if self.turbulence_scale <= 0.0 || self.turbulence_period <= 0.0 {
+            return Err("turbulence_scale and turbulence_period must be positive".into());
+        }
+        self.sensor_noise.validate()?;
+        self.motor_noise.validate()
+    }
+
+    /// The day/night cycle described by this configuration.
+    #[must_use]
+    pub fn day_night(&self) -> DayNightCycle {
+        DayNightCycle {
+            period: self.day_night_period,
+            amplitude: self.day_night_amplitude,
+        }
+    }
+
+    /// The seasonal cycle described by this configuration.
+    #[must_use]
+    pub fn seasons(&self) -> SeasonCycle {
+        SeasonCycle {
+            length: self.season_length,
+            depth: self.season_depth,
+        }
+    }
+
+    /// The sensory turbulence described by this configuration, with the
+    /// noise pattern chosen by `seed`.
+    #[must_use]
+    pub fn turbulence(&self, seed: u64) -> Turbulence {
+        Turbulence {
+            amplitude: self.turbulence_amplitude,
+            scale: self.turbulence_scale,
+            period: self.turbulence_period,
+            seed,
+        }
+    }
+
+    /// Per-species source count range, ordered and non-empty.
+    #[must_use]
+    pub fn source_count_range(&self) -> (usize, usize) {
+        let lo = self.source_count_min.min(self.source_count_max);
+        (lo, self.source_count_min.max(self.source_count_max))
+    }
+}
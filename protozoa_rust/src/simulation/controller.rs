@@ -0,0 +1,204 @@
+//! Pluggable decision policies.
+//!
+//! `Protozoa::update_state` infers, learns, remembers, pays for its
+//! metabolism and moves the same way whatever drives it; only the decision
+//! (how far to turn and how fast to swim this tick) is delegated to the
+//! agent's `controller`. Active inference is the default; the baselines read
+//! the same sensors, so they can be compared in the same dish and dashboard.
+//!
+//! The baselines are a random walk, a pure gradient follower, Braitenberg's
+//! vehicle 2b and bacterial run-and-tumble, whose tumble probability falls
+//! with the temporal gradient. None of them plans. `ControllerKind` is the
+//! agent's `controller` field; `--controller NAME` and the console's
+//! `controller NAME` set it.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::params::{
+    BASELINE_SPEED_FRACTION, BRAITENBERG_GAIN, GRADIENT_FOLLOWER_GAIN, MAX_SPEED, RANDOM_WALK_TURN,
+    TUMBLE_BASE_RATE, TUMBLE_GRADIENT_GAIN,
+};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// What a controller chose for this tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decision {
+    /// Heading change (radians, positive toward the left sensor)
+    pub turn: f64,
+    /// Commanded swimming speed, before viscous drag
+    pub speed: f64,
+}
+
+/// A policy turning the agent's current readings and beliefs into a decision.
+pub trait Controller {
+    /// Lowercase name used on the command line and in the dashboard.
+    fn name(&self) -> &'static str;
+
+    /// Decides this tick's turn and speed. Beliefs, precisions and
+    /// `temp_gradient` are already updated from the latest reading.
+    fn decide(&self, agent: &mut Protozoa) -> Decision;
+}
+
+/// The agent's own policy: EFE action selection blended with the committed
+/// MCTS plan and the reactive steering terms (`Protozoa::active_inference_decision`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActiveInferenceController;
+
+impl Controller for ActiveInferenceController {
+    fn name(&self) -> &'static str {
+        "active"
+    }
+
+    fn decide(&self, agent: &mut Protozoa) -> Decision {
+        agent.active_inference_decision()
+    }
+}
+
+/// Uniform random turns of at most `RANDOM_WALK_TURN` at a constant speed;
+/// ignores every reading.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RandomWalk;
+
+impl Controller for RandomWalk {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn decide(&self, agent: &mut Protozoa) -> Decision {
+        Decision {
+            turn: agent
+                .streams
+                .exploration
+                .random_range(-RANDOM_WALK_TURN..RANDOM_WALK_TURN),
+            speed: BASELINE_SPEED_FRACTION * MAX_SPEED,
+        }
+    }
+}
+
+/// Turns toward the stronger chemoreceptor at a constant speed, always
+/// climbing the nutrient gradient (no set-point, memory or planning).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PureGradientFollower;
+
+impl Controller for PureGradientFollower {
+    fn name(&self) -> &'static str {
+        "gradient"
+    }
+
+    fn decide(&self, agent: &mut Protozoa) -> Decision {
+        Decision {
+            turn: GRADIENT_FOLLOWER_GAIN * (agent.val_l - agent.val_r),
+            speed: BASELINE_SPEED_FRACTION * MAX_SPEED,
+        }
+    }
+}
+
+/// Braitenberg vehicle 2b ("aggression"): each chemoreceptor excites the
+/// opposite motor, so the agent turns toward the source and speeds up as
+/// the readings grow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Braitenberg;
+
+impl Controller for Braitenberg {
+    fn name(&self) -> &'static str {
+        "braitenberg"
+    }
+
+    fn decide(&self, agent: &mut Protozoa) -> Decision {
+        // Motor speeds: left = k·r, right = k·l; turn by their difference,
+        // swim at their mean
+        Decision {
+            turn: BRAITENBERG_GAIN * (agent.val_l - agent.val_r),
+            speed: MAX_SPEED * f64::midpoint(agent.val_l, agent.val_r).clamp(0.1, 1.0),
+        }
+    }
+}
+
+/// Bacterial run-and-tumble chemotaxis: swims straight at a constant speed
+/// and tumbles to a uniformly random heading with probability
+/// `TUMBLE_BASE_RATE · exp(−TUMBLE_GRADIENT_GAIN · temp_gradient)` per tick,
+/// so runs up the gradient last longer. Reads only the temporal change of
+/// the mean reading, never the left-right difference.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunAndTumble;
+
+impl RunAndTumble {
+    /// Probability of tumbling this tick for a temporal gradient.
+    #[must_use]
+    pub fn tumble_probability(temp_gradient: f64) -> f64 {
+        let rate = TUMBLE_BASE_RATE * (-TUMBLE_GRADIENT_GAIN * temp_gradient).exp();
+        if rate.is_nan() { 1.0 } else { rate.min(1.0) }
+    }
+}
+
+impl Controller for RunAndTumble {
+    fn name(&self) -> &'static str {
+        "tumble"
+    }
+
+    fn decide(&self, agent: &mut Protozoa) -> Decision {
+        let p = Self::tumble_probability(agent.temp_gradient);
+        let tumbles = agent.streams.exploration.random_bool(p);
+        Decision {
+            turn: if tumbles {
+                agent.streams.exploration.random_range(-PI..PI)
+            } else {
+                0.0
+            },
+            speed: BASELINE_SPEED_FRACTION * MAX_SPEED,
+        }
+    }
+}
+
+/// Controller an agent runs, selected with `--controller NAME`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ControllerKind {
+    #[default]
+    ActiveInference,
+    RandomWalk,
+    GradientFollower,
+    Braitenberg,
+    RunAndTumble,
+}
+
+impl ControllerKind {
+    /// All controllers, in display order.
+    pub const ALL: [Self; 5] = [
+        Self::ActiveInference,
+        Self::RandomWalk,
+        Self::GradientFollower,
+        Self::Braitenberg,
+        Self::RunAndTumble,
+    ];
+
+    /// Parses a controller name (`active`, `random`, `gradient`,
+    /// `braitenberg`, `tumble`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl Controller for ControllerKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::ActiveInference => ActiveInferenceController.name(),
+            Self::RandomWalk => RandomWalk.name(),
+            Self::GradientFollower => PureGradientFollower.name(),
+            Self::Braitenberg => Braitenberg.name(),
+            Self::RunAndTumble => RunAndTumble.name(),
+        }
+    }
+
+    fn decide(&self, agent: &mut Protozoa) -> Decision {
+        match self {
+            Self::ActiveInference => ActiveInferenceController.decide(agent),
+            Self::RandomWalk => RandomWalk.decide(agent),
+            Self::GradientFollower => PureGradientFollower.decide(agent),
+            Self::Braitenberg => Braitenberg.decide(agent),
+            Self::RunAndTumble => RunAndTumble.decide(agent),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Soft collisions and crowding costs between agents sharing a dish.
+//!
+//! Agents otherwise ignore each other, so a population can pile up on one
+//! patch. `Crowding::apply` runs once per population step in each dish, on a
+//! `NeighborIndex` (a uniform grid over the agents' positions) built from the
+//! positions after everyone moved. Two agents closer than `collision_radius`
+//! are pushed apart by `stiffness` times their overlap, and every agent pays
+//! `cost` energy per second for each neighbor within `neighbor_radius`, so
+//! dense clusters thin out on their own. Both are off by default.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::environment::PetriDish;
+use crate::simulation::params::{
+    COLLISION_RADIUS, COLLISION_STIFFNESS, CROWDING_COST, CROWDING_RADIUS,
+};
+
+/// Uniform-grid bucketing of agent positions for radius queries.
+#[derive(Clone, Debug, Default)]
+pub struct NeighborIndex {
+    cell: f64,
+    cols: usize,
+    rows: usize,
+    /// Indices into `positions` per cell, row-major
+    cells: Vec<Vec<usize>>,
+    positions: Vec<(f64, f64)>,
+}
+
+impl NeighborIndex {
+    /// Buckets `positions` over a `width` × `height` dish in cells of `cell`
+    /// world units (queries are cheapest with the largest query radius).
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // Cell counts are small and non-negative
+    #[allow(clippy::cast_sign_loss)]
+    pub fn build(positions: &[(f64, f64)], width: f64, height: f64, cell: f64) -> Self {
+        let cell = cell.max(f64::EPSILON);
+        let cols = (width / cell).ceil().max(1.0) as usize;
+        let rows = (height / cell).ceil().max(1.0) as usize;
+        let mut index = Self {
+            cell,
+            cols,
+            rows,
+            cells: vec![Vec::new(); cols * rows],
+            positions: positions.to_vec(),
+        };
+        for (i, &(x, y)) in positions.iter().enumerate() {
+            let (c, r) = index.cell_of(x, y);
+            index.cells[r * cols + c].push(i);
+        }
+        index
+    }
+
+    /// Indices of the positions within `radius` of position `i`, itself excluded.
+    pub fn neighbors(&self, i: usize, radius: f64) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = self.positions[i];
+        let (col, row) = self.cell_of(x, y);
+        let reach = self.reach(radius);
+        let (c0, c1) = (col.saturating_sub(reach), (col + reach).min(self.cols - 1));
+        let (r0, r1) = (row.saturating_sub(reach), (row + reach).min(self.rows - 1));
+        (r0..=r1)
+            .flat_map(move |row| (c0..=c1).map(move |col| row * self.cols + col))
+            .flat_map(move |cell| self.cells[cell].iter().copied())
+            .filter(move |&j| {
+                let (nx, ny) = self.positions[j];
+                j != i && (nx - x).powi(2) + (ny - y).powi(2) <= radius * radius
+            })
+    }
+
+    /// Cells to scan on each side to cover `radius`.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn reach(&self, radius: f64) -> usize {
+        ((radius.max(0.0) / self.cell).ceil() as usize).min(self.cols.max(self.rows))
+    }
+
+    /// Cell (column, row) containing (x, y), clamped to the grid.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn cell_of(&self, x: f64, y: f64) -> (usize, usize) {
+        let clamp = |v: f64, n: usize| ((v / self.cell).floor().max(0.0) as usize).min(n - 1);
+        (clamp(x, self.cols), clamp(y, self.rows))
+    }
+}
+
+/// Soft-collision and crowding-cost settings of a population.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crowding {
+    /// Distance below which agents push each other apart
+    pub collision_radius: f64,
+    /// Share of the overlap removed per tick (0 = no collisions)
+    pub stiffness: f64,
+    /// Distance within which another agent counts as a neighbor
+    pub neighbor_radius: f64,
+    /// Energy per second lost per neighbor (0 = no penalty)
+    pub cost: f64,
+}
+
+impl Default for Crowding {
+    fn default() -> Self {
+        Self {
+            collision_radius: COLLISION_RADIUS,
+            stiffness: COLLISION_STIFFNESS,
+            neighbor_radius: CROWDING_RADIUS,
+            cost: CROWDING_COST,
+        }
+    }
+}
+
+impl Crowding {
+    /// Whether neither collisions nor crowding costs are on.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.stiffness <= 0.0 && self.cost <= 0.0
+    }
+
+    /// One tick of crowding among the living `agents` in `dish`: pushes
+    /// overlapping pairs apart (held inside the dish and out of obstacles)
+    /// and charges each agent for its neighbors. Displacements and costs are
+    /// computed from the positions on entry, so the agents' order does not
+    /// matter.
+    pub fn apply<'a>(&self, dish: &PetriDish, agents: impl IntoIterator<Item = &'a mut Protozoa>) {
+        if self.is_off() {
+            return;
+        }
+        let mut agents: Vec<&mut Protozoa> = agents.into_iter().filter(|a| a.is_alive()).collect();
+        let positions: Vec<(f64, f64)> = agents.iter().map(|a| (a.x, a.y)).collect();
+        let index = NeighborIndex::build(
+            &positions,
+            dish.width,
+            dish.height,
+            self.collision_radius.max(self.neighbor_radius),
+        );
+        let radius = self.collision_radius;
+        for (i, agent) in agents.iter_mut().enumerate() {
+            let (x, y) = positions[i];
+            if self.cost > 0.0 {
+                #[allow(clippy::cast_precision_loss)] // Neighbor counts are small
+                let crowd = index.neighbors(i, self.neighbor_radius).count() as f64;
+                agent.energy = (agent.energy - dish.clock.rate(self.cost * crowd)).max(0.0);
+            }
+            if self.stiffness <= 0.0 {
+                continue;
+            }
+            let (mut dx, mut dy) = (0.0, 0.0);
+            for j in index.neighbors(i, radius) {
+                let (ox, oy) = (x - positions[j].0, y - positions[j].1);
+                let distance = ox.hypot(oy);
+                // Coincident agents split along x, the lower index to the left
+                let (ux, uy) = if distance > f64::EPSILON {
+                    (ox / distance, oy / distance)
+                } else if i < j {
+                    (-1.0, 0.0)
+                } else {
+                    (1.0, 0.0)
+                };
+                // Each agent takes half of the correction
+                let push = 0.5 * self.stiffness.min(1.0) * (radius - distance);
+                dx += push * ux;
+                dy += push * uy;
+            }
+            let (nx, ny) = (
+                (x + dx).clamp(0.0, dish.width),
+                (y + dy).clamp(0.0, dish.height),
+            );
+            if !dish.is_blocked(nx, ny) {
+                (agent.x, agent.y) = (nx, ny);
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Day/night and seasonal nutrient regeneration cycles.
+//!
+//! A global daylight level oscillates sinusoidally between 1 (noon) and
+//! `1 - amplitude` (midnight). It scales every source's contribution to the
+//! concentration and the per-tick probability that a depleted source respawns,
+//! so food becomes predictably scarce at night.
+//!
+//! Seasons are a slower, discrete regime on top: rich and poor seasons of
+//! `length` seconds alternate, the poor one scaling the supply by
+//! `1 - depth`. Unlike daylight the regime switches abruptly, so it is a
+//! hidden state an agent could infer from a run of poor harvests;
+//! `PetriDish::season` exposes the true regime and its boundaries are
+//! reported as `SourceEvent::SeasonChanged`.
+
+use std::f64::consts::PI;
+
+/// Sinusoidal daylight modulation of the nutrient supply.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DayNightCycle {
+    /// Simulated seconds per full day
+    pub period: u64,
+    /// Depth of the night trough in [0, 1] (0 disables the cycle)
+    pub amplitude: f64,
+}
+
+impl DayNightCycle {
+    /// Daylight level at sim time `t` (seconds): `L(t) = 1 - A · (1 - cos(2πt / P)) / 2`.
+    ///
+    /// Time starts at noon (`L = 1`) and reaches midnight (`L = 1 - A`) after
+    /// half a period.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Periods are far below 2^52
+    pub fn level(&self, seconds: f64) -> f64 {
+        if self.amplitude <= 0.0 || self.period == 0 {
+            return 1.0;
+        }
+        let period = self.period as f64;
+        let phase = seconds.rem_euclid(period) / period;
+        let amplitude = self.amplitude.min(1.0);
+        1.0 - amplitude * 0.5 * (1.0 - (2.0 * PI * phase).cos())
+    }
+
+    /// True during the darker half of the cycle.
+    #[must_use]
+    pub fn is_night(&self, seconds: f64) -> bool {
+        self.amplitude > 0.0 && self.level(seconds) < 1.0 - 0.5 * self.amplitude.min(1.0)
+    }
+}
+
+/// Nutrient regime of a `SeasonCycle`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Season {
+    /// Full supply (the first season)
+    #[default]
+    Rich,
+    /// Supply reduced by the cycle's depth
+    Poor,
+}
+
+impl Season {
+    /// Display name (`rich`, `poor`).
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rich => "rich",
+            Self::Poor => "poor",
+        }
+    }
+
+    /// Numeric code for exports (0 rich, 1 poor).
+    #[must_use]
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Rich => 0,
+            Self::Poor => 1,
+        }
+    }
+}
+
+/// Alternating rich and poor seasons scaling the nutrient supply.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeasonCycle {
+    /// Simulated seconds per season (a full year is two seasons)
+    pub length: u64,
+    /// Poor-season reduction of the supply in [0, 1] (0 disables the cycle)
+    pub depth: f64,
+}
+
+impl SeasonCycle {
+    /// Whether seasons leave the supply untouched.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.depth <= 0.0 || self.length == 0
+    }
+
+    /// Season at sim time `t` (seconds): rich during even-numbered seasons
+    /// `[2k·length, (2k + 1)·length)`, poor during the others.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Lengths are far below 2^52
+    pub fn season(&self, seconds: f64) -> Season {
+        if self.is_off() {
+            return Season::Rich;
+        }
+        let year = 2.0 * self.length as f64;
+        if seconds.rem_euclid(year) < self.length as f64 {
+            Season::Rich
+        } else {
+            Season::Poor
+        }
+    }
+
+    /// Supply multiplier at sim time `t`: 1 in the rich season, `1 - depth`
+    /// in the poor one.
+    #[must_use]
+    pub fn level(&self, seconds: f64) -> f64 {
+        match self.season(seconds) {
+            Season::Rich => 1.0,
+            Season::Poor => 1.0 - self.depth.min(1.0),
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Built-in demo tours: scripted, annotated runs for presentations.
+//!
+//! A `DemoTour` is a sequence of `DemoSegment`s, each a seeded scenario run
+//! for a fixed number of ticks with a title and a caption for the banner.
+//! A segment's `DemoCue`s script what happens in it: dish perturbations go
+//! through the dish's `EventSchedule` like any other scheduled event, while
+//! changes to the agent (a pinned landmark, a drained battery, a rewritten
+//! body plan) are applied by the `DemoPlayer` as their tick comes up. Every
+//! cue carries a short note the banner shows once it has fired, so the
+//! audience is told what just happened as it happens.
+//!
+//! `DemoTour::builtin` is the tour behind `demo`: exploration, landmark
+//! navigation, panic and morphogenesis, in that order.
+
+use crate::simulation::annotation::{MorphologyParam, ParamCommand};
+use crate::simulation::ensemble::Run;
+use crate::simulation::events::DishEvent;
+use crate::simulation::generator::GeneratorConfig;
+use crate::simulation::preset::EnvironmentPreset;
+use crate::simulation::scenario::Scenario;
+use crate::simulation::spawn::SpawnConfig;
+
+/// A scripted change made during a segment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DemoAction {
+    /// Perturbs the dish (scheduled with the dish's events)
+    Event(DishEvent),
+    /// Changes a morphology parameter, as `set NAME VALUE` would
+    Set(ParamCommand),
+    /// Pins a landmark at (x, y) in the agent's episodic memory
+    Pin { x: f64, y: f64 },
+    /// Sets the agent's energy
+    Energy(f64),
+}
+
+/// An action, the segment tick it happens at and what the banner says then.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DemoCue {
+    /// Tick of the segment's dish clock it happens at: dish events fire
+    /// during the step reaching it and agent changes follow that step (the
+    /// first step is tick 1)
+    pub tick: u64,
+    pub action: DemoAction,
+    pub note: String,
+}
+
+impl DemoCue {
+    #[must_use]
+    pub fn new(tick: u64, action: DemoAction, note: &str) -> Self {
+        Self {
+            tick,
+            action,
+            note: note.to_string(),
+        }
+    }
+}
+
+/// One annotated scene of a tour.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DemoSegment {
+    pub title: String,
+    /// What to look for, shown under the title
+    pub caption: String,
+    pub scenario: Scenario,
+    /// Length in ticks
+    pub ticks: u64,
+    /// Cues in tick order
+    pub cues: Vec<DemoCue>,
+}
+
+impl DemoSegment {
+    /// The segment's run for `seed`, with its dish events scheduled.
+    #[must_use]
+    pub fn build(&self, seed: u64) -> Run {
+        let mut run = self.scenario.build(seed);
+        for cue in &self.cues {
+            if let DemoAction::Event(event) = cue.action {
+                run.dish.events.schedule(cue.tick, event);
+            }
+        }
+        run
+    }
+}
+
+/// A sequence of segments played in order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DemoTour {
+    pub segments: Vec<DemoSegment>,
+    /// Seed every segment's run is built from
+    pub seed: u64,
+}
+
+impl DemoTour {
+    /// The built-in tour: exploration, landmark navigation, panic and
+    /// morphogenesis.
+    #[must_use]
+    pub fn builtin(seed: u64) -> Self {
+        Self {
+            segments: vec![exploration(), navigation(), panic(), morphogenesis()],
+            seed,
+        }
+    }
+}
+
+fn exploration() -> DemoSegment {
+    DemoSegment {
+        title: "Exploration".to_string(),
+        caption: "Nothing smells of food yet, so the agent swims where it expects to learn \
+                  the most, filling in its map of the dish."
+            .to_string(),
+        scenario: Scenario::Generated {
+            generator: GeneratorConfig::default(),
+            spawn: SpawnConfig::default(),
+        },
+        ticks: 400,
+        cues: Vec::new(),
+    }
+}
+
+fn navigation() -> DemoSegment {
+    let (x, y) = (85.0, 10.0);
+    DemoSegment {
+        title: "Landmark navigation".to_string(),
+        caption: "A remembered landmark is a goal: once hungry, the agent heads for the best \
+                  place it knows instead of following the local gradient."
+            .to_string(),
+        scenario: Scenario::Preset(EnvironmentPreset::Ring),
+        ticks: 400,
+        cues: vec![
+            DemoCue::new(
+                1,
+                DemoAction::Event(DishEvent::Extinction { fraction: 1.0 }),
+                "the ring of food is cleared away",
+            ),
+            DemoCue::new(
+                1,
+                DemoAction::Event(DishEvent::source(x, y)),
+                "a rich source appears in the far corner",
+            ),
+            DemoCue::new(1, DemoAction::Pin { x, y }, "it is pinned as a landmark"),
+            DemoCue::new(
+                40,
+                DemoAction::Energy(0.25),
+                "energy drops: the agent sets off for the landmark",
+            ),
+        ],
+    }
+}
+
+fn panic() -> DemoSegment {
+    DemoSegment {
+        title: "Panic".to_string(),
+        caption: "When readings crash the agent panics, turning hard to get out of a place \
+                  that has stopped feeding it."
+            .to_string(),
+        scenario: Scenario::Preset(EnvironmentPreset::Gradient),
+        ticks: 500,
+        cues: vec![
+            DemoCue::new(
+                250,
+                DemoAction::Event(DishEvent::Extinction { fraction: 1.0 }),
+                "the source is wiped out",
+            ),
+            DemoCue::new(
+                380,
+                DemoAction::Event(DishEvent::pulse(50.0, 25.0)),
+                "a fresh pulse lands mid-dish",
+            ),
+        ],
+    }
+}
+
+fn morphogenesis() -> DemoSegment {
+    let set = |param, value| DemoAction::Set(ParamCommand { param, value });
+    DemoSegment {
+        title: "Morphogenesis".to_string(),
+        caption: "The body plan is rewritten mid-run and behavior follows: the same agent \
+                  senses, and so swims, differently."
+            .to_string(),
+        scenario: Scenario::Preset(EnvironmentPreset::Gradient),
+        ticks: 500,
+        cues: vec![
+            DemoCue::new(
+                150,
+                set(MorphologyParam::SensorDist, 6.0),
+                "sensors grow: the gradient is read further out",
+            ),
+            DemoCue::new(
+                300,
+                set(MorphologyParam::SensorAngle, 1.2),
+                "sensors splay: wider, sharper turns",
+            ),
+        ],
+    }
+}
+
+/// Playback of a tour: the current segment, its run and the cues fired.
+#[derive(Clone)]
+pub struct DemoPlayer {
+    tour: DemoTour,
+    /// Index of the segment playing (the number of segments once finished)
+    segment: usize,
+    /// Cues of the segment already fired
+    fired: usize,
+    run: Run,
+}
+
+impl DemoPlayer {
+    /// Starts `tour` at its first segment.
+    ///
+    /// # Panics
+    /// If the tour has no segments.
+    #[must_use]
+    pub fn new(tour: DemoTour) -> Self {
+        let run = tour.segments[0].build(tour.seed);
+        Self {
+            tour,
+            segment: 0,
+            fired: 0,
+            run,
+        }
+    }
+
+    /// The run on screen.
+    #[must_use]
+    pub fn run(&self) -> &Run {
+        &self.run
+    }
+
+    /// The segment playing, or the last one once the tour is over.
+    #[must_use]
+    pub fn segment(&self) -> &DemoSegment {
+        let last = self.tour.segments.len() - 1;
+        &self.tour.segments[self.segment.min(last)]
+    }
+
+    /// Returns true once every segment has played.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.segment >= self.tour.segments.len()
+    }
+
+    /// Ticks into the current segment.
+    #[must_use]
+    pub fn tick(&self) -> u64 {
+        self.run.dish.clock.tick
+    }
+
+    /// Note of the last cue fired in this segment.
+    #[must_use]
+    pub fn note(&self) -> Option<&str> {
+        let fired = self.fired.checked_sub(1)?;
+        Some(self.segment().cues[fired].note.as_str())
+    }
+
+    /// Banner heading: `Demo 2/4 · Landmark navigation`.
+    #[must_use]
+    pub fn heading(&self) -> String {
+        let count = self.tour.segments.len();
+        if self.is_finished() {
+            format!("Demo {count}/{count} · End of the tour")
+        } else {
+            format!(
+                "Demo {}/{count} · {}",
+                self.segment + 1,
+                self.segment().title
+            )
+        }
+    }
+
+    /// Advances the tour by one tick, moving on to the next segment once the
+    /// current one has run its length; does nothing when finished.
+    pub fn step(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        if self.tick() >= self.segment().ticks {
+            self.skip();
+            return;
+        }
+        self.run.step();
+        self.apply_due();
+    }
+
+    /// Cuts to the start of the next segment (or ends the tour).
+    pub fn skip(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        self.segment += 1;
+        self.fired = 0;
+        if !self.is_finished() {
+            self.run = self.segment().build(self.tour.seed);
+        }
+    }
+
+    /// Starts the tour over.
+    pub fn restart(&mut self) {
+        *self = Self::new(self.tour.clone());
+    }
+
+    /// Applies the agent changes of the cues due by now; dish events have
+    /// been injected by the dish itself and are only marked as fired.
+    fn apply_due(&mut self) {
+        while let Some(cue) = self.segment().cues.get(self.fired)
+            && cue.tick <= self.tick()
+        {
+            let action = cue.action;
+            let agent = &mut self.run.agent;
+            match action {
+                DemoAction::Event(_) => {}
+                DemoAction::Set(change) => _ = change.apply(&mut agent.morphology),
+                DemoAction::Pin { x, y } => _ = agent.episodic_memory.pin(x, y, agent.tick_count),
+                DemoAction::Energy(energy) => agent.energy = energy,
+            }
+            self.fired += 1;
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Fast self-checks behind the `doctor` subcommand.
+//!
+//! Each check returns a `Check` (pass/fail with a one-line detail) instead of
+//! panicking, so one broken subsystem does not hide the others. `Diagnosis`
+//! collects them and prints a report to paste into a bug report: the
+//! `platform()` line, one line per check and a summary. The simulation checks
+//! live here; the binary adds the renderer smoke test and the config files.
+//!
+//! The determinism check compares run digests (an FNV-1a hash of the agent's
+//! pose and energy every tick) across serial and parallel passes. The NaN
+//! stress check runs extreme genomes, corner starts, coincident sources and
+//! every controller, requiring finite state throughout.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::config::DishConfig;
+use crate::simulation::controller::ControllerKind;
+use crate::simulation::ensemble::Run;
+use crate::simulation::evolution::{Gene, Genome};
+use crate::simulation::generator::GeneratorConfig;
+use crate::simulation::params::{DOCTOR_SEEDS, DOCTOR_STRESS_TICKS, DOCTOR_TICKS};
+use crate::simulation::preset::EnvironmentPreset;
+use crate::simulation::scenario::Scenario;
+use crate::simulation::spawn::SpawnConfig;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::f64::consts::PI;
+use std::fmt;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Outcome of one self-check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub passed: bool,
+    /// What was checked, or what went wrong
+    pub detail: String,
+}
+
+impl Check {
+    /// A passed check.
+    #[must_use]
+    pub fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: true,
+            detail: detail.into(),
+        }
+    }
+
+    /// A failed check.
+    #[must_use]
+    pub fn fail(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: false,
+            detail: detail.into(),
+        }
+    }
+
+    /// Passes with the `Ok` detail, fails with the `Err` one.
+    #[must_use]
+    pub fn from_result(name: &'static str, result: Result<String, String>) -> Self {
+        match result {
+            Ok(detail) => Self::pass(name, detail),
+            Err(detail) => Self::fail(name, detail),
+        }
+    }
+
+    /// Runs `check`, turning a panic into a failure with its message.
+    pub fn guarded(name: &'static str, check: impl FnOnce() -> Result<String, String>) -> Self {
+        match catch_unwind(AssertUnwindSafe(check)) {
+            Ok(result) => Self::from_result(name, result),
+            Err(payload) => Self::fail(name, format!("panicked: {}", panic_message(&*payload))),
+        }
+    }
+}
+
+/// The checks of one `doctor` run, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diagnosis {
+    pub checks: Vec<Check>,
+}
+
+impl Diagnosis {
+    /// The simulation checks: determinism and NaN stress on dishes from the
+    /// loaded config (the defaults if it failed to load), then the config
+    /// itself.
+    #[must_use]
+    pub fn simulation(loaded: Result<DishConfig, String>) -> Self {
+        let config = loaded.clone().unwrap_or_default();
+        Self {
+            checks: vec![
+                check_determinism(&config),
+                check_nan_stress(&config),
+                check_dish_config(loaded),
+            ],
+        }
+    }
+
+    /// Appends a check.
+    pub fn push(&mut self, check: Check) {
+        self.checks.push(check);
+    }
+
+    /// Number of failed checks.
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.checks.iter().filter(|check| !check.passed).count()
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", platform())?;
+        let width = self.checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        for check in &self.checks {
+            let status = if check.passed { "ok" } else { "FAIL" };
+            writeln!(f, "{status:<4}  {:<width$}  {}", check.name, check.detail)?;
+        }
+        write!(
+            f,
+            "{}/{} checks passed",
+            self.checks.len() - self.failures(),
+            self.checks.len()
+        )
+    }
+}
+
+/// Version, OS, architecture and threading of this build.
+#[must_use]
+pub fn platform() -> String {
+    #[cfg(feature = "parallel")]
+    let threads = format!("parallel, {} threads", rayon::current_num_threads());
+    #[cfg(not(feature = "parallel"))]
+    let threads = "serial".to_string();
+    format!(
+        "protozoa_rust {} on {}/{} ({threads})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+/// FNV-1a digest of the agent's position, heading and energy after each of
+/// `ticks` ticks of a generated dish from `config` seeded with `seed`.
+#[must_use]
+pub fn run_digest(config: &DishConfig, seed: u64, ticks: u64) -> u64 {
+    let mut run = generated(config).build(seed);
+    let mut digest = Fnv::default();
+    for _ in 0..ticks {
+        run.step();
+        let agent = &run.agent;
+        for value in [agent.x, agent.y, agent.angle, agent.energy] {
+            digest.write(value.to_bits());
+        }
+    }
+    digest.0
+}
+
+/// The same seeds give the same digest twice in a row and, with the
+/// `parallel` feature, when run on worker threads.
+#[must_use]
+pub fn check_determinism(config: &DishConfig) -> Check {
+    Check::guarded("determinism", || {
+        let seeds: Vec<u64> = (0..DOCTOR_SEEDS).collect();
+        let serial = || -> Vec<u64> {
+            seeds
+                .iter()
+                .map(|&seed| run_digest(config, seed, DOCTOR_TICKS))
+                .collect()
+        };
+        let first = serial();
+        if serial() != first {
+            return Err("two serial runs of the same seeds differ".into());
+        }
+        #[cfg(feature = "parallel")]
+        {
+            let parallel: Vec<u64> = seeds
+                .par_iter()
+                .map(|&seed| run_digest(config, seed, DOCTOR_TICKS))
+                .collect();
+            if parallel != first {
+                return Err("runs on worker threads differ from serial runs".into());
+            }
+        }
+        let mut digest = Fnv::default();
+        for &d in &first {
+            digest.write(d);
+        }
+        Ok(format!(
+            "digest {:016x} ({} seeds x {DOCTOR_TICKS} ticks)",
+            digest.0,
+            seeds.len()
+        ))
+    })
+}
+
+/// Hostile but legal setups (gene extremes, a corner start heading out, a
+/// pile of coincident sources, each controller) run `DOCTOR_STRESS_TICKS`
+/// ticks with every agent quantity staying finite.
+#[must_use]
+pub fn check_nan_stress(config: &DishConfig) -> Check {
+    Check::guarded("nan stress", || {
+        let scenarios = stress_scenarios(config);
+        let count = scenarios.len();
+        for (name, mut run) in scenarios {
+            for tick in 0..DOCTOR_STRESS_TICKS {
+                run.step();
+                if !finite_state(&run.agent) {
+                    return Err(format!("{name}: non-finite agent state at tick {tick}"));
+                }
+            }
+        }
+        Ok(format!(
+            "{count} scenarios x {DOCTOR_STRESS_TICKS} ticks stayed finite"
+        ))
+    })
+}
+
+/// The dish config loaded and passes `DishConfig::validate`.
+#[must_use]
+pub fn check_dish_config(loaded: Result<DishConfig, String>) -> Check {
+    let result = loaded.and_then(|config| {
+        config.validate()?;
+        Ok(if config == DishConfig::default() {
+            "defaults".to_string()
+        } else {
+            format!("{} x {} dish", config.width, config.height)
+        })
+    });
+    Check::from_result("dish config", result)
+}
+
+/// Named runs for `check_nan_stress`.
+fn stress_scenarios(config: &DishConfig) -> Vec<(String, Run)> {
+    let scenario = generated(config);
+    let mut scenarios = Vec::new();
+    for (label, pick) in [("gene maxima", 1), ("gene minima", 0)] {
+        let mut run = scenario.build(1);
+        let genome = Genome {
+            genes: Gene::ALL.map(|gene| {
+                let (lo, hi) = gene.bounds();
+                if pick == 1 { hi } else { lo }
+            }),
+        };
+        genome.express(&mut run.agent);
+        scenarios.push((label.to_string(), run));
+    }
+
+    let mut corner = Scenario::Preset(EnvironmentPreset::Maze).build(2);
+    corner.agent = Protozoa::with_heading(0.0, 0.0, 1.25 * PI);
+    corner.agent.seed_streams(2);
+    scenarios.push(("corner start".to_string(), corner));
+
+    let mut pile = scenario.build(3);
+    if let Some(source) = pile.dish.sources.first().cloned() {
+        let (x, y) = (pile.agent.x, pile.agent.y);
+        pile.dish.sources.extend((0..64).map(|_| {
+            let mut copy = source.clone();
+            (copy.x, copy.y, copy.intensity) = (x, y, 1e3);
+            copy
+        }));
+    }
+    scenarios.push(("source pile".to_string(), pile));
+
+    for controller in ControllerKind::ALL {
+        let mut run = scenario.build(4);
+        run.agent.controller = controller;
+        scenarios.push((format!("{controller:?} controller"), run));
+    }
+    scenarios
+}
+
+/// Generated dishes from `config` with the default spawn.
+fn generated(config: &DishConfig) -> Scenario {
+    Scenario::Generated {
+        generator: GeneratorConfig {
+            dish: *config,
+            ..GeneratorConfig::default()
+        },
+        spawn: SpawnConfig::default(),
+    }
+}
+
+/// Whether every quantity the dashboard and the dynamics read is finite.
+fn finite_state(agent: &Protozoa) -> bool {
+    [
+        agent.x,
+        agent.y,
+        agent.angle,
+        agent.speed,
+        agent.energy,
+        agent.current_vfe,
+        agent.free_energy(),
+        agent.believed_nutrient(),
+        agent.belief_uncertainty(),
+    ]
+    .iter()
+    .all(|v| v.is_finite())
+}
+
+/// Text of a panic payload (`&str` or `String`).
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// 64-bit FNV-1a over 64-bit words.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv {
+    fn write(&mut self, word: u64) {
+        for byte in word.to_le_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
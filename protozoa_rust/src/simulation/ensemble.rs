@@ -0,0 +1,255 @@
+//! Concurrent replicate runs of one configuration.
+//!
+//! An `Ensemble` steps K independent dish/agent pairs in parallel (`rayon`,
+//! with the `parallel` feature) and summarizes them into mean ± spread
+//! statistics, so behavior is judged on aggregate evidence rather than a
+//! single run.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::annotation::{MarkerLog, ParamCommand};
+use crate::simulation::clock::SimClock;
+use crate::simulation::config::DishConfig;
+use crate::simulation::environment::PetriDish;
+use crate::simulation::footprint::{MemoryLimits, MemoryUsage};
+use crate::simulation::observer::DishObserver;
+use crate::simulation::params::ENSEMBLE_HISTORY_LEN;
+use crate::simulation::predator::hunt;
+use crate::simulation::spawn::SpawnConfig;
+use crate::simulation::streams::Stream;
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+/// One headless replicate: its own dish and agent.
+#[derive(Clone)]
+pub struct Run {
+    pub dish: PetriDish,
+    pub agent: Protozoa,
+}
+
+impl Run {
+    /// Advances this run by one tick (environment, sensing, inference, the
+    /// agent's pheromone trail and quorum signal, then any predators in the
+    /// dish hunt).
+    pub fn step(&mut self) {
+        self.dish.update();
+        self.agent.sense(&self.dish);
+        self.agent.update_state(&self.dish);
+        self.agent.lay_pheromone(&mut self.dish);
+        self.agent.broadcast(&mut self.dish);
+        hunt(&mut self.dish, [&mut self.agent]);
+    }
+
+    /// `step`, reporting the dish's source events to `observer`.
+    pub fn step_observed(&mut self, observer: &mut impl DishObserver) {
+        self.dish.update_observed(observer);
+        self.agent.sense(&self.dish);
+        self.agent.update_state(&self.dish);
+        self.agent.lay_pheromone(&mut self.dish);
+        self.agent.broadcast(&mut self.dish);
+        hunt(&mut self.dish, [&mut self.agent]);
+    }
+}
+
+/// Summary statistics of one metric across runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stat {
+    pub mean: f64,
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stat {
+    /// Computes mean, population standard deviation, min and max.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Run counts are small
+    pub fn from_values(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        Self {
+            mean,
+            std: var.sqrt(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// Lower edge of the mean ± std band.
+    #[must_use]
+    pub fn lower(&self) -> f64 {
+        self.mean - self.std
+    }
+
+    /// Upper edge of the mean ± std band.
+    #[must_use]
+    pub fn upper(&self) -> f64 {
+        self.mean + self.std
+    }
+}
+
+/// Aggregate metrics across all runs at one tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EnsembleSample {
+    pub tick: u64,
+    /// Sim-seconds elapsed at the sample (`SimClock::seconds`)
+    pub time: f64,
+    /// Agent energy
+    pub energy: Stat,
+    /// Variational Free Energy
+    pub vfe: Stat,
+    /// Fraction of spatial grid cells visited
+    pub coverage: Stat,
+}
+
+/// K replicate runs of the same configuration, stepped concurrently.
+pub struct Ensemble {
+    pub runs: Vec<Run>,
+    /// Rolling window of aggregate samples (oldest first)
+    pub history: VecDeque<EnsembleSample>,
+    /// Maximum samples kept in `history`
+    history_capacity: usize,
+    pub tick: u64,
+    /// Parameter changes applied mid-run, drawn on the charts
+    pub markers: MarkerLog,
+}
+
+impl Ensemble {
+    /// Creates `count` independent runs, each with a fresh dish and a spawned agent.
+    ///
+    /// Unseeded: a fresh random master seed is drawn for the ensemble.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests; the binary passes its loaded config
+    pub fn new(count: usize, spawn: &SpawnConfig) -> Self {
+        Self::with_dish_config(count, spawn, &DishConfig::default(), rand::rng().random())
+    }
+
+    /// Creates `count` independent runs whose dishes follow `config`.
+    ///
+    /// Run `i` is seeded with `seed + i`: its dish is generated from it, its
+    /// agent starts from its `Stream::Spawn` stream and draws from its
+    /// streams (as in a `Scenario`), so the same seed gives the same ensemble.
+    #[must_use]
+    pub fn with_dish_config(
+        count: usize,
+        spawn: &SpawnConfig,
+        config: &DishConfig,
+        seed: u64,
+    ) -> Self {
+        let runs = (0..count as u64)
+            .map(|i| {
+                let run_seed = seed.wrapping_add(i);
+                let dish = PetriDish::from_config(config, run_seed);
+                let mut agent = spawn.spawn(&dish, &mut Stream::Spawn.rng(run_seed));
+                agent.seed_streams(run_seed);
+                Run { dish, agent }
+            })
+            .collect();
+
+        Self {
+            runs,
+            history: VecDeque::with_capacity(ENSEMBLE_HISTORY_LEN),
+            history_capacity: ENSEMBLE_HISTORY_LEN,
+            tick: 0,
+            markers: MarkerLog::default(),
+        }
+    }
+
+    /// Number of runs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Returns true if the ensemble has no runs.
+    #[must_use]
+    #[allow(dead_code)] // API completeness alongside `len`
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Advances every run by one tick in parallel and records an aggregate sample.
+    pub fn step(&mut self) {
+        #[cfg(feature = "parallel")]
+        self.runs.par_iter_mut().for_each(Run::step);
+        #[cfg(not(feature = "parallel"))]
+        self.runs.iter_mut().for_each(Run::step);
+        self.tick += 1;
+
+        if self.history_capacity == 0 {
+            return;
+        }
+        if self.history.len() >= self.history_capacity {
+            self.history.pop_front();
+        }
+        let sample = self.sample();
+        self.history.push_back(sample);
+    }
+
+    /// Applies a parameter change to every run's agent and records it as a
+    /// marker at the current tick. Returns the marker label.
+    pub fn apply_param(&mut self, command: ParamCommand) -> String {
+        let mut label = String::new();
+        for run in &mut self.runs {
+            label = command.apply(&mut run.agent.morphology);
+        }
+        let clock = self
+            .runs
+            .first()
+            .map_or_else(SimClock::default, |r| r.dish.clock);
+        self.markers.record(clock, label.clone());
+        label
+    }
+
+    /// Applies new capacities to the sample history and to every run's agent.
+    #[allow(dead_code)] // Used by tests and long-run experiments
+    pub fn set_memory_limits(&mut self, limits: &MemoryLimits) {
+        self.history_capacity = limits.ensemble_history;
+        let excess = self.history.len().saturating_sub(limits.ensemble_history);
+        self.history.drain(..excess);
+        self.history.shrink_to(limits.ensemble_history);
+        for run in &mut self.runs {
+            run.agent.set_memory_limits(limits);
+        }
+    }
+
+    /// Approximate memory held by all runs and the sample history.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and long-run experiments
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let history = MemoryUsage {
+            history: self.history.capacity() * std::mem::size_of::<EnsembleSample>(),
+            ..MemoryUsage::default()
+        };
+        self.runs
+            .iter()
+            .map(|r| r.agent.memory_usage())
+            .sum::<MemoryUsage>()
+            + history
+    }
+
+    /// Computes aggregate statistics over the current state of all runs.
+    #[must_use]
+    pub fn sample(&self) -> EnsembleSample {
+        let energy: Vec<f64> = self.runs.iter().map(|r| r.agent.energy).collect();
+        let vfe: Vec<f64> = self.runs.iter().map(|r| r.agent.current_vfe).collect();
+        let coverage: Vec<f64> = self
+            .runs
+            .iter()
+            .map(|r| r.agent.spatial_priors.visited_fraction())
+            .collect();
+
+        EnsembleSample {
+            tick: self.tick,
+            time: self.runs.first().map_or(0.0, |r| r.dish.clock.seconds()),
+            energy: Stat::from_values(&energy),
+            vfe: Stat::from_values(&vfe),
+            coverage: Stat::from_values(&coverage),
+        }
+    }
+}
@@ -1,108 +1,1045 @@
+//! Petri dish environment and the `Environment` trait.
+//!
+//! `Environment` is the agent's view of a world: concentration, gradient,
+//! update and bounds are required, and the other senses and physics
+//! (temperature, pH, light, viscosity, flow, walls, clock) default to a plain
+//! medium. `Protozoa::sense`/`update_state` and the planner's rollouts take
+//! any `impl Environment + ?Sized`; `SpatialGrid` implements it as the
+//! learned world model the rollouts step through.
+//!
+//! `PetriDish` sums the kernels of its nutrient sources (Gaussian by default,
+//! see `kernel.rs`), each belonging to a chemical species (`with_species`,
+//! mixed or segregated into vertical bands). Sources decay, drift via
+//! Brownian motion and respawn when depleted. `gradient()` is the exact
+//! gradient of `get_concentration()` (sources plus the bilinear raster,
+//! times daylight; zero outside, in obstacles and where the level
+//! saturates), the ground truth for inferred gradients;
+//! `concentration_gradient()` keeps the central-difference version.
+//! `resize()` stretches a dish mid-run, keeping radii and relative positions,
+//! and returns the scale factors for agent-side state.
+
+use crate::simulation::clock::SimClock;
+use crate::simulation::config::DishConfig;
+use crate::simulation::cycle::Season;
+use crate::simulation::events::{DishEvent, EventSchedule};
+use crate::simulation::flow::FlowField;
+use crate::simulation::generator::GeneratorConfig;
+use crate::simulation::kernel::Kernel;
+use crate::simulation::layout::{Layout, NutrientMap};
+use crate::simulation::light::LightField;
+use crate::simulation::observer::{DishObserver, SourceEvent};
 use crate::simulation::params::{
-    BROWNIAN_STEP, RESPAWN_THRESHOLD, SOURCE_COUNT_MAX, SOURCE_COUNT_MIN, SOURCE_DECAY_MAX,
-    SOURCE_DECAY_MIN, SOURCE_INTENSITY_MAX, SOURCE_INTENSITY_MIN, SOURCE_MARGIN, SOURCE_RADIUS_MAX,
-    SOURCE_RADIUS_MIN,
+    AMBIENT_TEMPERATURE, LIGHT_AMBIENT, NEUTRAL_PH, NUTRIENT_SPECIES, PHEROMONE_DECAY, SIGNAL_DECAY,
 };
-use rand::Rng;
+use crate::simulation::ph::PhField;
+use crate::simulation::pheromone::PheromoneField;
+use crate::simulation::predator::{Predator, threat_at};
+use crate::simulation::preset::EnvironmentPreset;
+use crate::simulation::signal::SignalField;
+use crate::simulation::source_index::SourceIndex;
+use crate::simulation::streams::Stream;
+use crate::simulation::thermal::ThermalField;
+use crate::simulation::turbulence::Turbulence;
+use crate::simulation::viscosity::ViscosityField;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A world an agent can sense, move through and plan in.
+///
+/// `Protozoa::sense`, `Protozoa::update_state` and the planner's
+/// `AgentState::step` only see their surroundings through this trait, so a
+/// diffusion grid, an imported map or the agent's own `SpatialGrid` can stand
+/// in for a `PetriDish`. Only the nutrient field and the bounds are required;
+/// the other modalities default to a plain medium (ambient temperature,
+/// neutral pH, no flow, no walls) so a minimal world reads as neutral on them.
+pub trait Environment {
+    /// Nutrient concentration at (x, y) in [0, 1]; -1.0 outside the world or
+    /// inside walls (Toxic Void).
+    fn get_concentration(&self, x: f64, y: f64) -> f64;
+
+    /// Concentration gradient (∂C/∂x, ∂C/∂y) at (x, y).
+    #[allow(dead_code)] // Used by tests and library worlds
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64);
+
+    /// Advances the world by one tick.
+    #[allow(dead_code)] // Used by tests and library worlds
+    fn update(&mut self);
+
+    /// World extent (width, height); positions lie in [0, width] × [0, height].
+    fn bounds(&self) -> (f64, f64);
+
+    /// Returns true if (x, y) lies within `bounds`.
+    fn contains(&self, x: f64, y: f64) -> bool {
+        let (width, height) = self.bounds();
+        (0.0..=width).contains(&x) && (0.0..=height).contains(&y)
+    }
+
+    /// Concentration as a chemoreceptor reads it (the exact level by default).
+    fn sensed_concentration(&self, x: f64, y: f64) -> f64 {
+        self.get_concentration(x, y)
+    }
+
+    /// Per-species concentrations at (x, y) (a single species by default).
+    fn species_concentrations(&self, x: f64, y: f64) -> Vec<f64> {
+        vec![self.get_concentration(x, y)]
+    }
+
+    /// Temperature at (x, y) in [0, 1]; -1.0 outside the world.
+    fn get_temperature(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            AMBIENT_TEMPERATURE
+        } else {
+            -1.0
+        }
+    }
+
+    /// Normalized pH at (x, y) in [0, 1]; -1.0 outside the world.
+    fn get_ph(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            NEUTRAL_PH
+        } else {
+            -1.0
+        }
+    }
+
+    /// Light intensity at (x, y) in [0, 1]; 0.0 (dark) outside the world.
+    fn get_light(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            LIGHT_AMBIENT
+        } else {
+            0.0
+        }
+    }
+
+    /// Daylight level of the day/night cycle in [0, 1]; always noon by default.
+    fn daylight(&self) -> f64 {
+        1.0
+    }
+
+    /// Threat scent of predators at (x, y) in [0, 1]; none by default.
+    fn get_threat(&self, _x: f64, _y: f64) -> f64 {
+        0.0
+    }
+
+    /// Pheromone trail level at (x, y) in [0, 1]; none by default.
+    fn get_pheromone(&self, _x: f64, _y: f64) -> f64 {
+        0.0
+    }
+
+    /// Quorum signal concentration at (x, y) in [0, 1]; none by default.
+    fn get_signal(&self, _x: f64, _y: f64) -> f64 {
+        0.0
+    }
+
+    /// Viscosity of the medium at (x, y); 1.0 is plain medium.
+    fn get_viscosity(&self, _x: f64, _y: f64) -> f64 {
+        1.0
+    }
+
+    /// Fluid velocity `(vx, vy)` at (x, y).
+    fn flow_at(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    /// Returns true if (x, y) is inside a wall.
+    fn is_blocked(&self, _x: f64, _y: f64) -> bool {
+        false
+    }
+
+    /// Tick length and elapsed time, for per-second rates.
+    fn clock(&self) -> SimClock {
+        SimClock::default()
+    }
+}
 
 /// Represents a single Gaussian source of nutrients in the petri dish.
 ///
 /// The source has a position, radius (spread), and intensity (concentration).
 /// It decays over time and moves slightly via Brownian motion.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NutrientSource {
     pub x: f64,
     pub y: f64,
     pub radius: f64,
     pub intensity: f64,
     pub decay_rate: f64,
+    /// Chemical species index (0-based)
+    pub species: usize,
+    /// Injected by a pulse event: removed instead of respawned once depleted
+    pub transient: bool,
+    /// Radial profile of the source (Gaussian unless configured)
+    pub kernel: Kernel,
 }
 
 impl NutrientSource {
-    /// Creates a new random nutrient source within the given bounds.
-    fn random(width: f64, height: f64) -> Self {
-        let mut rng = rand::rng();
+    /// Creates a new random nutrient source of the given species within an x-band of the dish.
+    fn random(
+        rng: &mut impl Rng,
+        config: &DishConfig,
+        x_min: f64,
+        x_max: f64,
+        species: usize,
+    ) -> Self {
+        let margin_x = config.source_margin.min((x_max - x_min) / 4.0);
+        let margin_y = config.source_margin;
         Self {
-            x: rng.random_range(SOURCE_MARGIN..width - SOURCE_MARGIN),
-            y: rng.random_range(SOURCE_MARGIN..height - SOURCE_MARGIN),
-            radius: rng.random_range(SOURCE_RADIUS_MIN..SOURCE_RADIUS_MAX),
-            intensity: rng.random_range(SOURCE_INTENSITY_MIN..SOURCE_INTENSITY_MAX),
-            decay_rate: rng.random_range(SOURCE_DECAY_MIN..SOURCE_DECAY_MAX),
+            x: rng.random_range(x_min + margin_x..=x_max - margin_x),
+            y: rng.random_range(margin_y..=config.height - margin_y),
+            radius: rng.random_range(config.source_radius_min..=config.source_radius_max),
+            intensity: rng.random_range(config.source_intensity_min..=config.source_intensity_max),
+            decay_rate: rng.random_range(config.decay_min..=config.decay_max),
+            species,
+            transient: false,
+            kernel: config.kernel,
         }
     }
+
+    /// Contribution of this source at (x, y): intensity times the kernel
+    /// profile (Gaussian: I * exp(-dist^2 / (2*sigma^2))).
+    #[must_use]
+    pub fn contribution(&self, x: f64, y: f64) -> f64 {
+        self.intensity * self.kernel.profile(x - self.x, y - self.y, self.radius)
+    }
+
+    /// Gradient of `contribution` at (x, y) (Gaussian: -(p - s) / σ² times the
+    /// contribution).
+    #[must_use]
+    pub fn contribution_gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        let (g_x, g_y) = self.kernel.gradient(x - self.x, y - self.y, self.radius);
+        (self.intensity * g_x, self.intensity * g_y)
+    }
+
+    /// Distance beyond which the contribution is negligible (`Kernel::reach`).
+    #[must_use]
+    pub fn reach(&self) -> f64 {
+        self.kernel.reach(self.radius)
+    }
+}
+
+/// Axis-aligned solid wall the agent cannot enter.
+///
+/// Sensors read inside an obstacle as the toxic void (-1.0), like the dish edge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Obstacle {
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+}
+
+impl Obstacle {
+    /// Returns true if (x, y) lies inside the obstacle (edges included).
+    #[must_use]
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        (self.x_min..=self.x_max).contains(&x) && (self.y_min..=self.y_max).contains(&y)
+    }
+}
+
+/// Spatial arrangement of chemical species in the dish.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(dead_code)] // `Segregated` is selected by experiments and tests
+pub enum SpeciesLayout {
+    /// Every species may appear anywhere
+    #[default]
+    Mixed,
+    /// Species `k` is confined to the `k`-th vertical band (anti-correlated supplies)
+    Segregated,
 }
 
 /// Represents the simulation environment (the "dish").
 ///
 /// Contains multiple `NutrientSource`s and handles their dynamics (decay, movement, respawn).
 /// It calculates the aggregate nutrient concentration at any point.
+#[derive(Clone, Debug)]
 pub struct PetriDish {
     pub width: f64,
     pub height: f64,
     pub sources: Vec<NutrientSource>,
+    /// Fluid currents advecting sources and pushing the agent
+    pub flow: FlowField,
+    /// Number of distinct chemical species
+    pub species_count: usize,
+    /// How species are arranged (also used on respawn)
+    pub species_layout: SpeciesLayout,
+    /// Static temperature landscape (second sensory modality)
+    pub thermal: ThermalField,
+    /// Directional light gradient (for phototaxis)
+    pub light: LightField,
+    /// Solid walls (empty except in maze-like presets and loaded layouts)
+    pub obstacles: Vec<Obstacle>,
+    /// Static nutrient raster from a loaded layout (empty otherwise)
+    pub nutrient_map: NutrientMap,
+    /// Thick-medium zones that slow the agent down
+    pub viscosity: ViscosityField,
+    /// Static pH landscape (second homeostatic variable)
+    pub ph: PhField,
+    /// Sources neither decay, move nor respawn (benchmark presets)
+    pub static_sources: bool,
+    /// Source ranges and dynamics (from `params.rs` or a TOML file)
+    pub config: DishConfig,
+    /// Ticks and sim time elapsed (drives the day/night cycle, rates and scheduled events)
+    pub clock: SimClock,
+    /// Scripted perturbations fired by `update`
+    pub events: EventSchedule,
+    /// Structured noise on what chemoreceptors read (`sensed_concentration`)
+    pub turbulence: Turbulence,
+    /// Predators hunting in the dish (moved by `predator::hunt`)
+    pub predators: Vec<Predator>,
+    /// Trails laid by the agents (`Protozoa::lay_pheromone`)
+    pub pheromone: PheromoneField,
+    /// Quorum signals broadcast by the agents (`Protozoa::broadcast`)
+    pub signal: SignalField,
+    /// Random stream for source dynamics: drift, respawns and events (`Stream::Drift`)
+    rng: StdRng,
 }
 
 impl PetriDish {
     /// Creates a new Petri dish with the specified dimensions and random nutrient sources.
+    ///
+    /// Unseeded: a fresh random seed is drawn for every dish.
     #[must_use]
+    #[allow(dead_code)] // Used by tests; the binary builds dishes from `DishConfig`
     pub fn new(width: f64, height: f64) -> Self {
-        let mut rng = rand::rng();
-        let num_sources = rng.random_range(SOURCE_COUNT_MIN..=SOURCE_COUNT_MAX);
-        let sources = (0..num_sources)
-            .map(|_| NutrientSource::random(width, height))
-            .collect();
+        Self::with_species(
+            width,
+            height,
+            NUTRIENT_SPECIES,
+            SpeciesLayout::Mixed,
+            rand::rng().random(),
+        )
+    }
 
+    /// Creates a dish with `species_count` chemical species arranged by
+    /// `layout`, generated from `seed`.
+    ///
+    /// Each species receives between `SOURCE_COUNT_MIN` and `SOURCE_COUNT_MAX` sources.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests; the binary builds dishes from `DishConfig`
+    pub fn with_species(
+        width: f64,
+        height: f64,
+        species_count: usize,
+        layout: SpeciesLayout,
+        seed: u64,
+    ) -> Self {
+        Self::generate(
+            seed,
+            &GeneratorConfig {
+                dish: DishConfig::sized(width, height),
+                species_count,
+                layout,
+                obstacle_count: 0,
+                ..GeneratorConfig::default()
+            },
+        )
+    }
+
+    /// Procedurally generates a dish from `seed`.
+    ///
+    /// Source positions, radii, initial intensities and decay rates, the
+    /// thermal landscape, obstacles, viscosity zones and pH spots are drawn
+    /// from a stream seeded with `seed`. The dish then switches to the
+    /// `Stream::Drift` stream for its own dynamics, so layout options that draw
+    /// more numbers do not change how sources drift and respawn. The same seed
+    /// and config give the same dish, tick for tick.
+    #[must_use]
+    pub fn generate(seed: u64, config: &GeneratorConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (width, height) = (config.dish.width, config.dish.height);
+        let thermal = ThermalField::random(&mut rng, width, height);
+        let mut dish = Self {
+            width,
+            height,
+            sources: Vec::new(),
+            flow: FlowField::new(width, height),
+            species_count: config.species_count.max(1),
+            species_layout: config.layout,
+            thermal,
+            light: LightField::new(width, height),
+            obstacles: Vec::new(),
+            nutrient_map: NutrientMap::default(),
+            viscosity: ViscosityField::uniform(),
+            ph: PhField::uniform(),
+            static_sources: false,
+            config: config.dish,
+            clock: SimClock::new(config.dish.seconds_per_tick),
+            events: EventSchedule::new(),
+            turbulence: config.dish.turbulence(Stream::Turbulence.seed(seed)),
+            predators: Vec::new(),
+            pheromone: PheromoneField::new(width, height),
+            signal: SignalField::default(),
+            rng,
+        };
+
+        for species in 0..dish.species_count {
+            let (lo, hi) = config.dish.source_count_range();
+            let num_sources = dish.rng.random_range(lo..=hi);
+            for _ in 0..num_sources {
+                let source = dish.random_source(species);
+                dish.sources.push(source);
+            }
+        }
+
+        dish.obstacles = config.place_obstacles(&mut dish.rng, &dish.sources);
+        dish.viscosity =
+            ViscosityField::random(&mut dish.rng, config.dish.viscosity_zones, width, height);
+        dish.ph = PhField::random(&mut dish.rng, config.dish.ph_spots, width, height);
+        dish.rng = Stream::Drift.rng(seed);
+        dish
+    }
+
+    /// Creates a single-species dish without obstacles from a runtime
+    /// configuration, generated from `seed`.
+    #[must_use]
+    pub fn from_config(config: &DishConfig, seed: u64) -> Self {
+        Self::generate(
+            seed,
+            &GeneratorConfig {
+                dish: *config,
+                obstacle_count: 0,
+                ..GeneratorConfig::default()
+            },
+        )
+    }
+
+    /// Creates a `width` × `height` dish without sources or obstacles,
+    /// generated from `seed`: an open field for experiments that place
+    /// their own food.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn empty(width: f64, height: f64, seed: u64) -> Self {
+        let mut dish = Self::from_config(&DishConfig::sized(width, height), seed);
+        dish.sources.clear();
+        dish
+    }
+
+    /// Creates a canonical benchmark layout.
+    ///
+    /// Preset dishes are fully deterministic: static sources and obstacles
+    /// from the preset, uniform ambient temperature and neutral pH, one nutrient species.
+    #[must_use]
+    pub fn from_preset(preset: EnvironmentPreset, width: f64, height: f64) -> Self {
         Self {
             width,
             height,
-            sources,
+            sources: preset.sources(width, height),
+            flow: FlowField::new(width, height),
+            species_count: 1,
+            species_layout: SpeciesLayout::Mixed,
+            thermal: ThermalField::uniform(),
+            light: LightField::new(width, height),
+            obstacles: preset.obstacles(width, height),
+            nutrient_map: NutrientMap::default(),
+            viscosity: ViscosityField::uniform(),
+            ph: PhField::uniform(),
+            static_sources: true,
+            config: DishConfig::sized(width, height),
+            clock: SimClock::default(),
+            events: EventSchedule::new(),
+            turbulence: Turbulence::default(),
+            predators: Vec::new(),
+            pheromone: PheromoneField::new(width, height),
+            signal: SignalField::default(),
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    /// Creates a dish from a loaded layout stretched over `width` × `height`.
+    ///
+    /// Like a preset the dish is fully deterministic: no Gaussian sources, the
+    /// layout's nutrient raster and walls, uniform temperature and neutral pH.
+    #[must_use]
+    pub fn from_layout(layout: &Layout, width: f64, height: f64) -> Self {
+        let mut dish = Self::from_preset(EnvironmentPreset::Gradient, width, height);
+        dish.sources.clear();
+        dish.obstacles = layout.obstacles(width, height);
+        dish.nutrient_map = layout.nutrient_map(width, height);
+        dish
+    }
+
+    /// Resizes the dish to `width` × `height` mid-run.
+    ///
+    /// Everything placed in the dish is stretched with it: sources, obstacles,
+    /// thermal sources, viscosity zones, pH spots, predators, pending pulses,
+    /// quorum signals, the flow vortex and the light gradient keep their
+    /// relative positions, and the nutrient raster and pheromone trails cover
+    /// the new extent. Radii are kept, so patches stay the same size in a
+    /// larger dish. Returns the scale factors (sx, sy) for
+    /// anything outside the dish that holds coordinates in it (the agent,
+    /// occupancy maps, portals). Non-positive sizes leave the dish unchanged.
+    pub fn resize(&mut self, width: f64, height: f64) -> (f64, f64) {
+        if !(width > 0.0 && height > 0.0 && width.is_finite() && height.is_finite()) {
+            return (1.0, 1.0);
+        }
+        let (sx, sy) = (width / self.width, height / self.height);
+        for source in &mut self.sources {
+            source.x *= sx;
+            source.y *= sy;
+        }
+        for obstacle in &mut self.obstacles {
+            obstacle.x_min *= sx;
+            obstacle.x_max *= sx;
+            obstacle.y_min *= sy;
+            obstacle.y_max *= sy;
+        }
+        for spot in &mut self.thermal.sources {
+            spot.x *= sx;
+            spot.y *= sy;
+        }
+        for zone in &mut self.viscosity.zones {
+            zone.x *= sx;
+            zone.y *= sy;
+        }
+        for spot in &mut self.ph.spots {
+            spot.x *= sx;
+            spot.y *= sy;
+        }
+        for predator in &mut self.predators {
+            predator.x *= sx;
+            predator.y *= sy;
+        }
+        self.events.rescale(sx, sy);
+        self.flow.center_x *= sx;
+        self.flow.center_y *= sy;
+        self.pheromone.resize(width, height);
+        self.signal.rescale(sx, sy);
+        let light = self.light;
+        self.light = LightField::directional(
+            width,
+            height,
+            light.direction,
+            light.ambient,
+            light.contrast,
+        );
+        if !self.nutrient_map.is_empty() {
+            self.nutrient_map.width = width;
+            self.nutrient_map.height = height;
         }
+        self.width = width;
+        self.height = height;
+        self.config.width = width;
+        self.config.height = height;
+        (sx, sy)
+    }
+
+    /// Returns true if (x, y) is inside any obstacle.
+    #[must_use]
+    pub fn is_blocked(&self, x: f64, y: f64) -> bool {
+        self.obstacles.iter().any(|o| o.contains(x, y))
+    }
+
+    /// Creates a random source of `species` respecting the dish's species layout.
+    #[allow(clippy::cast_precision_loss)] // Species counts are tiny
+    fn random_source(&mut self, species: usize) -> NutrientSource {
+        let (x_min, x_max) = match self.species_layout {
+            SpeciesLayout::Mixed => (0.0, self.width),
+            SpeciesLayout::Segregated => {
+                let band = self.width / self.species_count as f64;
+                (band * species as f64, band * (species + 1) as f64)
+            }
+        };
+        NutrientSource::random(&mut self.rng, &self.config, x_min, x_max, species)
     }
 
     /// Calculates the nutrient concentration at a specific coordinate (x, y).
     ///
-    /// Returns the sum of Gaussian contributions from all sources.
-    /// If the coordinate is outside the bounds, returns -1.0 (Toxic Void).
+    /// Returns the sum of Gaussian contributions from all sources and the
+    /// layout's nutrient raster, scaled by the current supply level. If the coordinate is outside the bounds or inside an obstacle, returns -1.0 (Toxic Void).
     #[must_use]
     pub fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        self.concentration_from(self.sources.iter(), x, y)
+    }
+
+    /// Concentration as a chemoreceptor at (x, y) reads it now: the exact
+    /// level plus the dish's `turbulence`, clamped to [0, 1]. Outside the dish
+    /// and in obstacles it is -1.0 like `get_concentration`.
+    #[must_use]
+    pub fn sensed_concentration(&self, x: f64, y: f64) -> f64 {
+        let level = self.get_concentration(x, y);
+        if level < 0.0 || self.turbulence.is_off() {
+            return level;
+        }
+        let offset = self.turbulence.sample(x, y, self.clock.seconds());
+        (level + offset).clamp(0.0, 1.0)
+    }
+
+    /// `get_concentration` evaluating only the sources near (x, y).
+    ///
+    /// `index` must come from `source_index()` on the current sources. Each
+    /// source is cut off beyond `SOURCE_INDEX_CUTOFF` radii, so the result can
+    /// fall short of the exact sum by a few millionths per source.
+    #[must_use]
+    pub fn get_concentration_indexed(&self, index: &SourceIndex, x: f64, y: f64) -> f64 {
+        self.concentration_from(index.near(&self.sources, x, y), x, y)
+    }
+
+    /// Spatial hash over the current sources, for batches of
+    /// `get_concentration_indexed` queries (rebuild after the sources change).
+    #[must_use]
+    pub fn source_index(&self) -> SourceIndex {
+        SourceIndex::build(&self.sources, self.width, self.height)
+    }
+
+    /// Concentration at (x, y) from `sources` plus the nutrient raster.
+    fn concentration_from<'a>(
+        &self,
+        sources: impl Iterator<Item = &'a NutrientSource>,
+        x: f64,
+        y: f64,
+    ) -> f64 {
+        if x < 0.0 || x > self.width || y < 0.0 || y > self.height || self.is_blocked(x, y) {
+            return -1.0;
+        }
+
+        let concentration: f64 =
+            sources.map(|s| s.contribution(x, y)).sum::<f64>() + self.nutrient_map.sample(x, y);
+        (concentration * self.supply()).clamp(0.0, 1.0)
+    }
+
+    /// Exact concentration gradient (∂C/∂x, ∂C/∂y) at (x, y), from the source
+    /// kernels and the nutrient raster, scaled by the current supply level.
+    ///
+    /// Ground truth for `get_concentration`: (0, 0) outside the dish, inside
+    /// obstacles and where the concentration saturates at 0 or 1.
+    #[must_use]
+    pub fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        if x < 0.0 || x > self.width || y < 0.0 || y > self.height || self.is_blocked(x, y) {
+            return (0.0, 0.0);
+        }
+        let supply = self.supply();
+        let level = (self
+            .sources
+            .iter()
+            .map(|s| s.contribution(x, y))
+            .sum::<f64>()
+            + self.nutrient_map.sample(x, y))
+            * supply;
+        if level <= 0.0 || level >= 1.0 {
+            return (0.0, 0.0);
+        }
+        let (map_x, map_y) = self.nutrient_map.gradient(x, y);
+        let (g_x, g_y) = self
+            .sources
+            .iter()
+            .map(|s| s.contribution_gradient(x, y))
+            .fold((map_x, map_y), |(ax, ay), (sx, sy)| (ax + sx, ay + sy));
+        (g_x * supply, g_y * supply)
+    }
+
+    /// Concentration gradient (∂C/∂x, ∂C/∂y) at (x, y) by central differences.
+    ///
+    /// Sample points are clamped to the dish so the gradient stays defined at
+    /// the walls. Next to obstacles it picks up the toxic-void step; `gradient`
+    /// is exact.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn concentration_gradient(&self, x: f64, y: f64, step: f64) -> (f64, f64) {
+        let sample = |sx: f64, sy: f64| {
+            self.get_concentration(sx.clamp(0.0, self.width), sy.clamp(0.0, self.height))
+        };
+        let step = step.max(f64::EPSILON);
+        (
+            (sample(x + step, y) - sample(x - step, y)) / (2.0 * step),
+            (sample(x, y + step) - sample(x, y - step)) / (2.0 * step),
+        )
+    }
+
+    /// Calculates the concentration of a single chemical species at (x, y).
+    ///
+    /// Same semantics as `get_concentration`: clamped to [0, 1], -1.0 outside the dish.
+    #[must_use]
+    pub fn get_species_concentration(&self, species: usize, x: f64, y: f64) -> f64 {
+        if x < 0.0 || x > self.width || y < 0.0 || y > self.height || self.is_blocked(x, y) {
+            return -1.0;
+        }
+
+        let concentration: f64 = self
+            .sources
+            .iter()
+            .filter(|s| s.species == species)
+            .map(|s| s.contribution(x, y))
+            .sum::<f64>()
+            // The layout raster is species 0
+            + if species == 0 { self.nutrient_map.sample(x, y) } else { 0.0 };
+        (concentration * self.supply()).clamp(0.0, 1.0)
+    }
+
+    /// Current nutrient supply level: the daylight level times the season's
+    /// (see `SeasonCycle`).
+    #[must_use]
+    pub fn supply(&self) -> f64 {
+        self.daylight() * self.config.seasons().level(self.clock.seconds())
+    }
+
+    /// Current seasonal regime (always `Rich` without a season cycle).
+    #[must_use]
+    pub fn season(&self) -> Season {
+        self.config.seasons().season(self.clock.seconds())
+    }
+
+    /// Current daylight level in [1 - amplitude, 1] (see `DayNightCycle`).
+    #[must_use]
+    pub fn daylight(&self) -> f64 {
+        self.config.day_night().level(self.clock.seconds())
+    }
+
+    /// True during the darker half of the day/night cycle.
+    #[must_use]
+    pub fn is_night(&self) -> bool {
+        self.config.day_night().is_night(self.clock.seconds())
+    }
+
+    /// Per-species concentrations at (x, y), indexed by species.
+    #[must_use]
+    pub fn species_concentrations(&self, x: f64, y: f64) -> Vec<f64> {
+        (0..self.species_count)
+            .map(|k| self.get_species_concentration(k, x, y))
+            .collect()
+    }
+
+    /// Temperature at (x, y) in [0, 1]; -1.0 outside the dish like concentration.
+    #[must_use]
+    pub fn get_temperature(&self, x: f64, y: f64) -> f64 {
         if x < 0.0 || x > self.width || y < 0.0 || y > self.height {
             return -1.0;
         }
+        self.thermal.temperature_at(x, y)
+    }
+
+    /// Normalized pH at (x, y) in [0, 1]; -1.0 outside the dish like concentration.
+    #[must_use]
+    pub fn get_ph(&self, x: f64, y: f64) -> f64 {
+        if x < 0.0 || x > self.width || y < 0.0 || y > self.height {
+            return -1.0;
+        }
+        self.ph.ph_at(x, y)
+    }
+
+    /// Viscosity of the medium at (x, y); 1.0 is plain medium.
+    #[must_use]
+    pub fn get_viscosity(&self, x: f64, y: f64) -> f64 {
+        self.viscosity.viscosity_at(x, y)
+    }
 
-        let mut concentration = 0.0;
-        for source in &self.sources {
-            let d_x = x - source.x;
-            let d_y = y - source.y;
-            let dist_sq = d_x.powi(2) + d_y.powi(2);
-            let sigma_sq = source.radius.powi(2).max(f64::EPSILON);
+    /// Pheromone trail level at (x, y) in [0, 1]; 0.0 outside the dish.
+    #[must_use]
+    pub fn get_pheromone(&self, x: f64, y: f64) -> f64 {
+        self.pheromone.level_at(x, y)
+    }
 
-            // Gaussian: I * exp(-dist^2 / (2*sigma^2))
-            concentration += source.intensity * (-dist_sq / (2.0 * sigma_sq)).exp();
+    /// Quorum signal concentration at (x, y) in [0, 1]; 0.0 outside the dish.
+    #[must_use]
+    pub fn get_signal(&self, x: f64, y: f64) -> f64 {
+        if x < 0.0 || x > self.width || y < 0.0 || y > self.height {
+            return 0.0;
         }
+        self.signal.level_at(x, y)
+    }
 
-        concentration.clamp(0.0, 1.0)
+    /// Light intensity at (x, y) in [0, 1]; 0.0 (dark) outside the dish.
+    #[must_use]
+    pub fn get_light(&self, x: f64, y: f64) -> f64 {
+        if x < 0.0 || x > self.width || y < 0.0 || y > self.height {
+            return 0.0;
+        }
+        self.light.intensity_at(x, y)
+    }
+
+    /// Returns the fluid velocity `(vx, vy)` at (x, y).
+    #[must_use]
+    pub fn flow_at(&self, x: f64, y: f64) -> (f64, f64) {
+        self.flow.velocity_at(x, y)
     }
 
-    /// Updates the state of the environment (nutrient decay, brownian motion, regrowth).
+    /// Updates the state of the environment (nutrient decay, advection, brownian motion, regrowth).
+    ///
+    /// Advances the clock by one tick of `clock.dt()` sim-seconds; decay,
+    /// drift and respawn rates are per second. Scheduled events due at the
+    /// new tick fire first. Depleted sources respawn with probability equal
+    /// to the supply level (per second), so at night and in the poor season
+    /// they stay exhausted longer; depleted pulses are removed.
     pub fn update(&mut self) {
-        let mut rng = rand::rng();
+        self.update_observed(&mut |_, _| {});
+    }
+
+    /// `update`, reporting source depletions, respawns, edge crossings and
+    /// season changes to `observer` as they happen.
+    pub fn update_observed(&mut self, observer: &mut impl DishObserver) {
+        let season = self.season();
+        self.clock.advance();
+        if self.season() != season {
+            observer.on_source_event(
+                self.clock.tick,
+                SourceEvent::SeasonChanged {
+                    season: self.season(),
+                },
+            );
+        }
+        for event in self.events.take_due(self.clock.tick) {
+            // A scheduled edit of a source that is gone by now has nothing to do
+            let _ = self.inject(event);
+        }
+        let clock = self.clock;
+        self.pheromone.decay(clock.factor(PHEROMONE_DECAY));
+        self.signal.decay(clock.factor(SIGNAL_DECAY));
+        if self.static_sources {
+            // Preset sources are frozen, but pulses still fade
+            for source in self.sources.iter_mut().filter(|s| s.transient) {
+                source.intensity *= clock.factor(source.decay_rate);
+            }
+            self.remove_depleted_pulses();
+            return;
+        }
+        let supply = self.supply();
+        let respawn_chance = clock.probability(supply);
+        let step = clock.diffusion(self.config.brownian_step);
 
+        let threshold = self.config.respawn_threshold;
         for i in 0..self.sources.len() {
             // Entropy
-            self.sources[i].intensity *= self.sources[i].decay_rate;
+            let before = self.sources[i].intensity;
+            self.sources[i].intensity *= clock.factor(self.sources[i].decay_rate);
+            if before >= threshold
+                && self.sources[i].intensity < threshold
+                && !self.sources[i].transient
+            {
+                observer.on_source_event(
+                    clock.tick,
+                    SourceEvent::Depleted {
+                        index: i,
+                        species: self.sources[i].species,
+                    },
+                );
+            }
+
+            // Advection
+            let (v_x, v_y) = self.flow.velocity_at(self.sources[i].x, self.sources[i].y);
+            self.sources[i].x += clock.rate(v_x);
+            self.sources[i].y += clock.rate(v_y);
 
             // Brownian Motion
-            self.sources[i].x += rng.random_range(-BROWNIAN_STEP..BROWNIAN_STEP);
-            self.sources[i].y += rng.random_range(-BROWNIAN_STEP..BROWNIAN_STEP);
+            self.sources[i].x += self.rng.random_range(-step..=step);
+            self.sources[i].y += self.rng.random_range(-step..=step);
 
             // Clamp
-            self.sources[i].x = self.sources[i].x.clamp(0.0, self.width);
-            self.sources[i].y = self.sources[i].y.clamp(0.0, self.height);
+            let (x, y) = (self.sources[i].x, self.sources[i].y);
+            self.sources[i].x = x.clamp(0.0, self.width);
+            self.sources[i].y = y.clamp(0.0, self.height);
+            if (self.sources[i].x, self.sources[i].y) != (x, y) {
+                observer.on_source_event(
+                    clock.tick,
+                    SourceEvent::CrossedEdge {
+                        index: i,
+                        x: self.sources[i].x,
+                        y: self.sources[i].y,
+                    },
+                );
+            }
 
-            // Regrowth
-            if self.sources[i].intensity < RESPAWN_THRESHOLD {
-                self.sources[i] = NutrientSource::random(self.width, self.height);
+            // Regrowth (always at full supply; with probability `supply` per second otherwise)
+            if self.sources[i].intensity < threshold
+                && !self.sources[i].transient
+                && (supply >= 1.0 || self.rng.random::<f64>() < respawn_chance)
+            {
+                self.sources[i] = self.random_source(self.sources[i].species);
+                observer.on_source_event(
+                    clock.tick,
+                    SourceEvent::Respawned {
+                        index: i,
+                        species: self.sources[i].species,
+                        x: self.sources[i].x,
+                        y: self.sources[i].y,
+                    },
+                );
+            }
+        }
+        self.remove_depleted_pulses();
+    }
+
+    /// Applies one perturbation immediately; the entry point for every
+    /// change to the dish's sources from outside its own dynamics.
+    ///
+    /// Positions are clamped to the dish and species to the dish's species.
+    /// Extinction and relocation draw from the dish's random stream, so seeded
+    /// dishes stay reproducible.
+    ///
+    /// # Errors
+    ///
+    /// Leaves the dish unchanged and returns a message if the event names a
+    /// source index the dish does not have, or gives a non-positive radius,
+    /// a negative intensity or a decay rate outside (0, 1].
+    #[allow(clippy::cast_precision_loss)] // Source counts are tiny
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn inject(&mut self, event: DishEvent) -> Result<(), String> {
+        match event {
+            DishEvent::SpawnSource { radius, .. } | DishEvent::Pulse { radius, .. }
+                if radius <= 0.0 || !radius.is_finite() =>
+            {
+                return Err(format!("radius must be positive: {radius}"));
+            }
+            DishEvent::SpawnSource { intensity, .. } | DishEvent::Pulse { intensity, .. }
+                if intensity < 0.0 || !intensity.is_finite() =>
+            {
+                return Err(format!("intensity must not be negative: {intensity}"));
+            }
+            DishEvent::SpawnSource { decay_rate, .. }
+            | DishEvent::Pulse { decay_rate, .. }
+            | DishEvent::SetDecay { decay_rate, .. }
+                if !(decay_rate > 0.0 && decay_rate <= 1.0) =>
+            {
+                return Err(format!("decay rate must be in (0, 1]: {decay_rate}"));
+            }
+            DishEvent::RemoveSource { index }
+            | DishEvent::SetDecay { index, .. }
+            | DishEvent::MoveSource { index, .. }
+                if index >= self.sources.len() =>
+            {
+                return Err(format!(
+                    "no source {index} (the dish has {})",
+                    self.sources.len()
+                ));
+            }
+            _ => {}
+        }
+        match event {
+            DishEvent::SpawnSource {
+                x,
+                y,
+                radius,
+                intensity,
+                decay_rate,
+                species,
+            } => self.sources.push(NutrientSource {
+                x: x.clamp(0.0, self.width),
+                y: y.clamp(0.0, self.height),
+                radius,
+                intensity,
+                decay_rate,
+                species: species.min(self.species_count - 1),
+                transient: false,
+                kernel: self.config.kernel,
+            }),
+            DishEvent::RemoveSource { index } => {
+                self.sources.remove(index);
+            }
+            DishEvent::SetDecay { index, decay_rate } => {
+                self.sources[index].decay_rate = decay_rate;
+            }
+            DishEvent::MoveSource { index, x, y } => {
+                self.sources[index].x = x.clamp(0.0, self.width);
+                self.sources[index].y = y.clamp(0.0, self.height);
+            }
+            DishEvent::Pulse {
+                x,
+                y,
+                radius,
+                intensity,
+                decay_rate,
+                species,
+            } => self.sources.push(NutrientSource {
+                x: x.clamp(0.0, self.width),
+                y: y.clamp(0.0, self.height),
+                radius,
+                intensity,
+                decay_rate,
+                species: species.min(self.species_count - 1),
+                transient: true,
+                kernel: Kernel::Gaussian,
+            }),
+            DishEvent::Extinction { fraction } => {
+                let doomed =
+                    (fraction.clamp(0.0, 1.0) * self.sources.len() as f64).round() as usize;
+                for _ in 0..doomed {
+                    let index = self.rng.random_range(0..self.sources.len());
+                    self.sources.swap_remove(index);
+                }
+            }
+            DishEvent::Relocation => {
+                for i in 0..self.sources.len() {
+                    let moved = self.random_source(self.sources[i].species);
+                    self.sources[i].x = moved.x;
+                    self.sources[i].y = moved.y;
+                }
             }
         }
+        Ok(())
+    }
+
+    /// Drops pulse sources that have decayed below the respawn threshold.
+    fn remove_depleted_pulses(&mut self) {
+        let threshold = self.config.respawn_threshold;
+        self.sources
+            .retain(|s| !s.transient || s.intensity >= threshold);
+    }
+}
+
+impl Environment for PetriDish {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        PetriDish::get_concentration(self, x, y)
+    }
+
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        PetriDish::gradient(self, x, y)
+    }
+
+    fn update(&mut self) {
+        PetriDish::update(self);
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    fn sensed_concentration(&self, x: f64, y: f64) -> f64 {
+        PetriDish::sensed_concentration(self, x, y)
+    }
+
+    fn species_concentrations(&self, x: f64, y: f64) -> Vec<f64> {
+        PetriDish::species_concentrations(self, x, y)
+    }
+
+    fn get_temperature(&self, x: f64, y: f64) -> f64 {
+        PetriDish::get_temperature(self, x, y)
+    }
+
+    fn get_ph(&self, x: f64, y: f64) -> f64 {
+        PetriDish::get_ph(self, x, y)
+    }
+
+    fn get_light(&self, x: f64, y: f64) -> f64 {
+        PetriDish::get_light(self, x, y)
+    }
+
+    fn daylight(&self) -> f64 {
+        PetriDish::daylight(self)
+    }
+
+    fn get_viscosity(&self, x: f64, y: f64) -> f64 {
+        PetriDish::get_viscosity(self, x, y)
+    }
+
+    fn get_threat(&self, x: f64, y: f64) -> f64 {
+        threat_at(&self.predators, x, y)
+    }
+
+    fn get_pheromone(&self, x: f64, y: f64) -> f64 {
+        PetriDish::get_pheromone(self, x, y)
+    }
+
+    fn get_signal(&self, x: f64, y: f64) -> f64 {
+        PetriDish::get_signal(self, x, y)
+    }
+
+    fn flow_at(&self, x: f64, y: f64) -> (f64, f64) {
+        PetriDish::flow_at(self, x, y)
+    }
+
+    fn is_blocked(&self, x: f64, y: f64) -> bool {
+        PetriDish::is_blocked(self, x, y)
+    }
+
+    fn clock(&self) -> SimClock {
+        self.clock
     }
 }
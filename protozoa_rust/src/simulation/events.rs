@@ -0,0 +1,222 @@
+//! Scripted environmental perturbations.
+//!
+//! A `DishEvent` is a one-off change to the dish, and `PetriDish::inject` is
+//! the single entry point that applies one: scenarios, the command line
+//! (`DishEvent::parse`), corpses and tests all perturb the dish through it
+//! rather than editing its sources directly. An `EventSchedule` holds events
+//! keyed by tick; `PetriDish::update` injects every event that has come due
+//! before running its usual dynamics, so perturbation experiments (a sudden
+//! food pulse, a crash of the supply, a reshuffled landscape) can probe how
+//! the agent regulates itself.
+
+// Library API for perturbation experiments; the TUI binary schedules none
+#![allow(dead_code)]
+
+use crate::simulation::params::{
+    CORPSE_DECAY, CORPSE_INTENSITY, CORPSE_RADIUS, PULSE_DECAY, PULSE_INTENSITY, PULSE_RADIUS,
+    SOURCE_DECAY_MAX, SOURCE_INTENSITY_MAX, SOURCE_RADIUS_MAX,
+};
+
+/// First words of the command-line forms of `DishEvent`s (`DishEvent::parse`).
+pub const EVENT_COMMANDS: [&str; 5] = ["pulse", "spawn", "remove", "decay", "move"];
+
+/// A one-off change to the dish.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DishEvent {
+    /// Adds a permanent source (it respawns like the dish's own when depleted)
+    SpawnSource {
+        x: f64,
+        y: f64,
+        radius: f64,
+        intensity: f64,
+        /// Per-second intensity multiplier
+        decay_rate: f64,
+        species: usize,
+    },
+    /// Removes the source at `index` (later sources move down one place)
+    RemoveSource { index: usize },
+    /// Changes the per-second intensity multiplier of the source at `index`
+    SetDecay { index: usize, decay_rate: f64 },
+    /// Moves the source at `index` to (x, y)
+    MoveSource { index: usize, x: f64, y: f64 },
+    /// Injects a strong transient source that decays away without respawning
+    Pulse {
+        x: f64,
+        y: f64,
+        radius: f64,
+        intensity: f64,
+        /// Per-tick intensity multiplier
+        decay_rate: f64,
+        species: usize,
+    },
+    /// Removes a random `fraction` in [0, 1] of the sources for good
+    Extinction { fraction: f64 },
+    /// Moves every source to a new random position (intensities are kept)
+    Relocation,
+}
+
+impl DishEvent {
+    /// A permanent species-0 source at (x, y) as large, strong and lasting
+    /// as generated sources get.
+    #[must_use]
+    pub fn source(x: f64, y: f64) -> Self {
+        Self::SpawnSource {
+            x,
+            y,
+            radius: SOURCE_RADIUS_MAX,
+            intensity: SOURCE_INTENSITY_MAX,
+            decay_rate: SOURCE_DECAY_MAX,
+            species: 0,
+        }
+    }
+
+    /// A species-0 pulse at (x, y) with the default pulse parameters.
+    #[must_use]
+    pub fn pulse(x: f64, y: f64) -> Self {
+        Self::Pulse {
+            x,
+            y,
+            radius: PULSE_RADIUS,
+            intensity: PULSE_INTENSITY,
+            decay_rate: PULSE_DECAY,
+            species: 0,
+        }
+    }
+
+    /// The small species-0 deposit a dead agent leaves at (x, y).
+    #[must_use]
+    pub fn corpse(x: f64, y: f64) -> Self {
+        Self::Pulse {
+            x,
+            y,
+            radius: CORPSE_RADIUS,
+            intensity: CORPSE_INTENSITY,
+            decay_rate: CORPSE_DECAY,
+            species: 0,
+        }
+    }
+
+    /// Parses a command line: `pulse X Y`, `spawn X Y`, `remove I`,
+    /// `decay I RATE` or `move I X Y`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable message for unknown commands and missing or
+    /// invalid arguments. Whether a source index exists is checked by
+    /// `PetriDish::inject`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut words = input.split_whitespace();
+        let command = words.next().ok_or("empty command")?;
+        let usage = match command {
+            "pulse" => "usage: pulse X Y",
+            "spawn" => "usage: spawn X Y",
+            "remove" => "usage: remove INDEX",
+            "decay" => "usage: decay INDEX RATE",
+            "move" => "usage: move INDEX X Y",
+            other => return Err(format!("unknown command: {other}")),
+        };
+        let args: Vec<&str> = words.collect();
+        let word = |i: usize| args.get(i).copied().ok_or(usage);
+        let number = |i: usize, name: &str| -> Result<f64, String> {
+            let word = word(i)?;
+            word.parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("invalid {name}: {word}"))
+        };
+        let index = || -> Result<usize, String> {
+            let word = word(0)?;
+            word.parse::<usize>()
+                .map_err(|_| format!("invalid source index: {word}"))
+        };
+        let event = match command {
+            "pulse" => Self::pulse(number(0, "x")?, number(1, "y")?),
+            "spawn" => Self::source(number(0, "x")?, number(1, "y")?),
+            "remove" => Self::RemoveSource { index: index()? },
+            "decay" => Self::SetDecay {
+                index: index()?,
+                decay_rate: number(1, "decay rate")?,
+            },
+            _ => Self::MoveSource {
+                index: index()?,
+                x: number(1, "x")?,
+                y: number(2, "y")?,
+            },
+        };
+        Ok(event)
+    }
+}
+
+/// An event and the tick at which it fires.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScheduledEvent {
+    pub tick: u64,
+    pub event: DishEvent,
+}
+
+/// Pending events ordered by tick.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventSchedule {
+    /// Not yet fired, sorted by tick (ties keep insertion order)
+    pending: Vec<ScheduledEvent>,
+    /// Number of events fired so far
+    fired: usize,
+}
+
+impl EventSchedule {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedules `event` to fire when the dish reaches `tick`.
+    pub fn schedule(&mut self, tick: u64, event: DishEvent) {
+        let index = self.pending.partition_point(|e| e.tick <= tick);
+        self.pending.insert(index, ScheduledEvent { tick, event });
+    }
+
+    /// Removes and returns every event due at or before `tick`, in order.
+    pub fn take_due(&mut self, tick: u64) -> Vec<DishEvent> {
+        let due = self.pending.partition_point(|e| e.tick <= tick);
+        self.fired += due;
+        self.pending.drain(..due).map(|e| e.event).collect()
+    }
+
+    /// Scales the positions of pending pulses, spawns and moves by (sx, sy),
+    /// after the dish was resized.
+    pub fn rescale(&mut self, sx: f64, sy: f64) {
+        for scheduled in &mut self.pending {
+            if let DishEvent::Pulse { x, y, .. }
+            | DishEvent::SpawnSource { x, y, .. }
+            | DishEvent::MoveSource { x, y, .. } = &mut scheduled.event
+            {
+                *x *= sx;
+                *y *= sy;
+            }
+        }
+    }
+
+    /// Events still waiting to fire.
+    #[must_use]
+    pub fn pending(&self) -> &[ScheduledEvent] {
+        &self.pending
+    }
+
+    /// Tick of the next pending event.
+    #[must_use]
+    pub fn next_tick(&self) -> Option<u64> {
+        self.pending.first().map(|e| e.tick)
+    }
+
+    /// Number of events fired so far.
+    #[must_use]
+    pub fn fired(&self) -> usize {
+        self.fired
+    }
+
+    /// Returns true if nothing is pending.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
@@ -0,0 +1,589 @@
+//! Genomes and a generational evolutionary loop.
+//!
+//! A `Genome` encodes the agent's morphology and its key inference
+//! parameters as one value per `Gene`, each bounded by its physiological
+//! range. `Evolution::run` evaluates every genome of a generation headless
+//! on the same `Scenario` seeds, ranks them by their mean `fitness` (by
+//! default survival time: ticks alive, capped at `ticks`; mean final energy
+//! breaks ties, see `fitness.rs` for other objectives), keeps the
+//! `elite` unchanged and fills the rest of the next generation with
+//! offspring of tournament-selected parents (crossover, then mutation, by
+//! the operators chosen in `operators.rs`). With `Selection::Pareto` it
+//! ranks by Pareto front and crowding distance instead (NSGA-II, see
+//! `pareto.rs`), parents competing with their offspring. Every generation
+//! records its Pareto front. Variation draws from
+//! `Stream::Mutation` of the evolution's seed and evaluations are
+//! deterministic, so a run replays exactly.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::ensemble::{Run, Stat};
+use crate::simulation::fitness::{Episode, Fitness, FitnessConfig, Objective};
+use crate::simulation::operators::{CrossoverOperator, MutationOperator, StepSize};
+use crate::simulation::params::{
+    BELIEF_LEARNING_RATE, EVOLUTION_ELITE, EVOLUTION_GENERATIONS, EVOLUTION_POPULATION,
+    EVOLUTION_SEEDS, EVOLUTION_TICKS, EVOLUTION_TOURNAMENT, INITIAL_SENSORY_PRECISION,
+    MAX_LEARNING_RATE, MAX_PRIOR_PRECISION, MAX_SENSOR_ANGLE, MAX_SENSOR_DIST,
+    MAX_SENSORY_PRECISION, MIN_LEARNING_RATE, MIN_PRIOR_PRECISION, MIN_SENSOR_ANGLE,
+    MIN_SENSOR_DIST, MIN_SENSORY_PRECISION, MUTATION_RATE, MUTATION_SCALE,
+    NUTRIENT_PRIOR_PRECISION, SACCADE_SWEEP, SENSOR_ANGLE, SENSOR_DIST,
+};
+use crate::simulation::pareto::{Selection, crowded_order, non_dominated_sort};
+use crate::simulation::scenario::Scenario;
+use crate::simulation::streams::Stream;
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt::Write as _;
+
+/// One evolvable parameter of an agent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gene {
+    /// `Morphology::sensor_dist`
+    SensorDist,
+    /// `Morphology::sensor_angle` (radians)
+    SensorAngle,
+    /// `Morphology::belief_learning_rate`
+    LearningRate,
+    /// `Morphology::saccade_sweep` (radians)
+    Saccade,
+    /// Prior precision on nutrient (strength of the homeostatic preference)
+    NutrientPrecision,
+    /// Initial precision of both chemoreceptors (learned on from there)
+    SensoryPrecision,
+}
+
+/// Number of genes in a genome.
+pub const GENE_COUNT: usize = Gene::ALL.len();
+
+impl Gene {
+    /// Every gene, in genome order.
+    pub const ALL: [Self; 6] = [
+        Self::SensorDist,
+        Self::SensorAngle,
+        Self::LearningRate,
+        Self::Saccade,
+        Self::NutrientPrecision,
+        Self::SensoryPrecision,
+    ];
+
+    /// Name used in reports.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SensorDist => "sensor_dist",
+            Self::SensorAngle => "sensor_angle",
+            Self::LearningRate => "learning_rate",
+            Self::Saccade => "saccade",
+            Self::NutrientPrecision => "nutrient_precision",
+            Self::SensoryPrecision => "sensory_precision",
+        }
+    }
+
+    /// Inclusive range mutation keeps the gene in.
+    #[must_use]
+    pub fn bounds(self) -> (f64, f64) {
+        match self {
+            Self::SensorDist => (MIN_SENSOR_DIST, MAX_SENSOR_DIST),
+            Self::SensorAngle => (MIN_SENSOR_ANGLE, MAX_SENSOR_ANGLE),
+            Self::LearningRate => (MIN_LEARNING_RATE, MAX_LEARNING_RATE),
+            Self::Saccade => (0.0, MAX_SENSOR_ANGLE),
+            Self::NutrientPrecision => (MIN_PRIOR_PRECISION, MAX_PRIOR_PRECISION),
+            Self::SensoryPrecision => (MIN_SENSORY_PRECISION, MAX_SENSORY_PRECISION),
+        }
+    }
+
+    /// Value the default agent is born with.
+    #[must_use]
+    pub fn default_value(self) -> f64 {
+        match self {
+            Self::SensorDist => SENSOR_DIST,
+            Self::SensorAngle => SENSOR_ANGLE,
+            Self::LearningRate => BELIEF_LEARNING_RATE,
+            Self::Saccade => SACCADE_SWEEP,
+            Self::NutrientPrecision => NUTRIENT_PRIOR_PRECISION,
+            Self::SensoryPrecision => INITIAL_SENSORY_PRECISION,
+        }
+    }
+
+    /// Current value in `agent`.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and evolution analyses
+    pub fn get(self, agent: &Protozoa) -> f64 {
+        match self {
+            Self::SensorDist => agent.morphology.sensor_dist,
+            Self::SensorAngle => agent.morphology.sensor_angle,
+            Self::LearningRate => agent.morphology.belief_learning_rate,
+            Self::Saccade => agent.morphology.saccade_sweep,
+            Self::NutrientPrecision => agent.generative_model.prior_precision.nutrient,
+            Self::SensoryPrecision => agent.generative_model.sensory_precision.left,
+        }
+    }
+
+    fn set(self, agent: &mut Protozoa, value: f64) {
+        let model = &mut agent.generative_model;
+        match self {
+            Self::SensorDist => agent.morphology.sensor_dist = value,
+            Self::SensorAngle => agent.morphology.sensor_angle = value,
+            Self::LearningRate => agent.morphology.belief_learning_rate = value,
+            Self::Saccade => agent.morphology.saccade_sweep = value,
+            Self::NutrientPrecision => model.prior_precision.nutrient = value,
+            Self::SensoryPrecision => {
+                model.sensory_precision.left = value;
+                model.sensory_precision.right = value;
+            }
+        }
+    }
+}
+
+/// One value per gene, in `Gene::ALL` order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Genome {
+    pub genes: [f64; GENE_COUNT],
+}
+
+impl Default for Genome {
+    /// The genome of the default agent.
+    fn default() -> Self {
+        Self {
+            genes: Gene::ALL.map(Gene::default_value),
+        }
+    }
+}
+
+impl Genome {
+    /// Reads the genome of `agent`.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and evolution analyses
+    pub fn of(agent: &Protozoa) -> Self {
+        Self {
+            genes: Gene::ALL.map(|gene| gene.get(agent)),
+        }
+    }
+
+    /// Value of `gene`.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and evolution analyses
+    pub fn get(&self, gene: Gene) -> f64 {
+        self.genes[gene as usize]
+    }
+
+    /// Writes every gene into `agent` (before its first tick).
+    pub fn express(&self, agent: &mut Protozoa) {
+        for (gene, &value) in Gene::ALL.iter().zip(&self.genes) {
+            gene.set(agent, value);
+        }
+    }
+
+    /// Shifts each gene with probability `rate` by up to `scale` times its
+    /// range (uniformly either way), clamped to its bounds.
+    pub fn mutate(&mut self, rate: f64, scale: f64, rng: &mut impl Rng) {
+        for (gene, value) in Gene::ALL.iter().zip(&mut self.genes) {
+            if rng.random::<f64>() < rate {
+                let (lo, hi) = gene.bounds();
+                let step = rng.random_range(-1.0..=1.0) * scale * (hi - lo);
+                *value = (*value + step).clamp(lo, hi);
+            }
+        }
+    }
+
+    /// Uniform crossover: each gene from `self` or `other` with equal odds.
+    #[must_use]
+    pub fn crossover(&self, other: &Self, rng: &mut impl Rng) -> Self {
+        let mut child = *self;
+        for (value, &theirs) in child.genes.iter_mut().zip(&other.genes) {
+            if rng.random_bool(0.5) {
+                *value = theirs;
+            }
+        }
+        child
+    }
+
+    /// `name=value` pairs, space separated.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        Gene::ALL
+            .iter()
+            .zip(&self.genes)
+            .map(|(gene, value)| format!("{}={value:.3}", gene.name()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A genome with its fitness.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Individual {
+    pub genome: Genome,
+    /// Mean ticks survived over the evaluation seeds
+    pub survival: f64,
+    /// Mean final energy over the evaluation seeds
+    pub final_energy: f64,
+    /// Mean fitness score over the evaluation seeds
+    pub fitness: f64,
+    /// Mean of each fitness objective over the evaluation seeds
+    pub objectives: Vec<f64>,
+}
+
+/// Fitness summary of one generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenerationSummary {
+    pub generation: usize,
+    /// Fittest individual
+    pub best: Individual,
+    /// Survival statistics across the generation
+    pub survival: Stat,
+    /// Fitness statistics across the generation
+    pub fitness: Stat,
+    /// Fitness of every individual, in rank order
+    pub fitnesses: Vec<f64>,
+    /// Individuals no other individual of the generation dominates (each
+    /// genome once)
+    pub front: Vec<Individual>,
+    /// Mutation scale the generation's offspring were bred with
+    pub mutation_scale: f64,
+}
+
+/// Every generation of an `Evolution::run`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EvolutionReport {
+    pub generations: Vec<GenerationSummary>,
+}
+
+impl EvolutionReport {
+    /// Fittest individual of the last generation.
+    #[must_use]
+    pub fn best(&self) -> Option<&Individual> {
+        self.generations.last().map(|g| &g.best)
+    }
+
+    /// One row per generation: survival statistics, the best genome and
+    /// its fitness.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and evolution analyses
+    pub fn to_csv(&self) -> String {
+        let names: Vec<&str> = Gene::ALL.iter().map(|g| g.name()).collect();
+        let mut csv = format!(
+            "generation,best_survival,mean_survival,std_survival,{},best_fitness\n",
+            names.join(",")
+        );
+        for summary in &self.generations {
+            let genes: Vec<String> = summary
+                .best
+                .genome
+                .genes
+                .iter()
+                .map(f64::to_string)
+                .collect();
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                summary.generation,
+                summary.best.survival,
+                summary.survival.mean,
+                summary.survival.std,
+                genes.join(","),
+                summary.best.fitness
+            );
+        }
+        csv
+    }
+
+    /// The last generation's Pareto front, one row per individual: its
+    /// weighted objectives (in `Objective::ALL` order), survival, fitness
+    /// and genes.
+    #[must_use]
+    pub fn front_csv(&self) -> String {
+        let objectives: Vec<&str> = Objective::ALL.iter().map(|o| o.name()).collect();
+        let genes: Vec<&str> = Gene::ALL.iter().map(|g| g.name()).collect();
+        let mut csv = format!(
+            "generation,{},survival,fitness,{}\n",
+            objectives.join(","),
+            genes.join(",")
+        );
+        let Some(summary) = self.generations.last() else {
+            return csv;
+        };
+        for individual in &summary.front {
+            let values: Vec<String> = individual
+                .objectives
+                .iter()
+                .chain(&individual.genome.genes)
+                .map(f64::to_string)
+                .collect();
+            let (objectives, genes) = values.split_at(individual.objectives.len());
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                summary.generation,
+                objectives.join(","),
+                individual.survival,
+                individual.fitness,
+                genes.join(",")
+            );
+        }
+        csv
+    }
+}
+
+/// Settings of a generational run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evolution {
+    pub scenario: Scenario,
+    /// Genomes per generation
+    pub population: usize,
+    pub generations: usize,
+    /// Lifetime cap of one evaluation
+    pub ticks: u64,
+    /// Scenario seeds every genome is evaluated on
+    pub seeds: Vec<u64>,
+    /// Genomes copied unchanged into the next generation
+    pub elite: usize,
+    pub mutation_rate: f64,
+    /// Mutation scale of the first generation's offspring
+    pub mutation_scale: f64,
+    pub mutation: MutationOperator,
+    pub crossover: CrossoverOperator,
+    /// Whether `mutation_scale` adapts between generations
+    pub step_size: StepSize,
+    /// What the ranking maximizes
+    pub fitness: FitnessConfig,
+    /// Ranking by scalar fitness or by Pareto front
+    pub selection: Selection,
+    /// Master seed of the mutation stream
+    pub seed: u64,
+}
+
+impl Evolution {
+    /// Defaults from `params.rs` for `scenario`.
+    #[must_use]
+    #[allow(clippy::large_types_passed_by_value)] // Moved into the evolution
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario,
+            population: EVOLUTION_POPULATION,
+            generations: EVOLUTION_GENERATIONS,
+            ticks: EVOLUTION_TICKS,
+            seeds: (0..EVOLUTION_SEEDS as u64).collect(),
+            elite: EVOLUTION_ELITE,
+            mutation_rate: MUTATION_RATE,
+            mutation_scale: MUTATION_SCALE,
+            mutation: MutationOperator::default(),
+            crossover: CrossoverOperator::default(),
+            step_size: StepSize::default(),
+            fitness: FitnessConfig::default(),
+            selection: Selection::default(),
+            seed: 0,
+        }
+    }
+
+    /// The scenario run of `genome` under `seed`, before its first tick.
+    #[must_use]
+    pub fn build(&self, genome: &Genome, seed: u64) -> Run {
+        let mut run = self.scenario.build(seed);
+        genome.express(&mut run.agent);
+        run
+    }
+
+    /// Episode of `genome` in the scenario under `seed`, capped at `ticks`.
+    #[must_use]
+    pub fn episode(&self, genome: &Genome, seed: u64) -> Episode {
+        Episode::record(&mut self.build(genome, seed), self.ticks)
+    }
+
+    /// Ticks `genome` survives in the scenario under `seed` (`ticks` if it
+    /// outlives the cap) and its final energy.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn lifetime(&self, genome: &Genome, seed: u64) -> (u64, f64) {
+        let episode = self.episode(genome, seed);
+        (episode.lifespan, episode.final_energy)
+    }
+
+    /// Fitness of `genome` under the evolution's `fitness`, averaged over
+    /// the evaluation seeds.
+    #[must_use]
+    pub fn evaluate(&self, genome: &Genome) -> Individual {
+        self.evaluate_by(genome, &self.fitness)
+    }
+
+    /// Fitness of `genome` under `fitness`, averaged over the evaluation seeds.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Tick and seed counts are small
+    pub fn evaluate_by(&self, genome: &Genome, fitness: &impl Fitness) -> Individual {
+        let episodes: Vec<Episode> = self
+            .seeds
+            .iter()
+            .map(|&s| self.episode(genome, s))
+            .collect();
+        let n = episodes.len().max(1) as f64;
+        let mut objectives: Vec<f64> = Vec::new();
+        for values in episodes.iter().map(|e| fitness.objectives(e)) {
+            objectives.resize(values.len(), 0.0);
+            for (total, value) in objectives.iter_mut().zip(values) {
+                *total += value / n;
+            }
+        }
+        Individual {
+            genome: *genome,
+            survival: episodes.iter().map(|e| e.lifespan as f64).sum::<f64>() / n,
+            final_energy: episodes.iter().map(|e| e.final_energy).sum::<f64>() / n,
+            fitness: episodes.iter().map(|e| fitness.score(e)).sum::<f64>() / n,
+            objectives,
+        }
+    }
+
+    /// Evaluates `genomes` in order (in parallel with the `parallel` feature).
+    #[must_use]
+    pub fn evaluate_all(&self, genomes: &[Genome]) -> Vec<Individual> {
+        #[cfg(feature = "parallel")]
+        let evaluated = genomes.par_iter().map(|g| self.evaluate(g)).collect();
+        #[cfg(not(feature = "parallel"))]
+        let evaluated = genomes.iter().map(|g| self.evaluate(g)).collect();
+        evaluated
+    }
+
+    /// Evaluates `genomes`, fittest first.
+    #[must_use]
+    pub fn rank(&self, genomes: &[Genome]) -> Vec<Individual> {
+        let mut ranked = self.evaluate_all(genomes);
+        ranked.sort_by(fitter);
+        ranked
+    }
+
+    /// `pool` in NSGA-II order (front rank, then crowding distance), cut to
+    /// the population size.
+    #[must_use]
+    pub fn pareto_rank(&self, pool: Vec<Individual>) -> Vec<Individual> {
+        let points: Vec<Vec<f64>> = pool.iter().map(|i| i.objectives.clone()).collect();
+        let mut slots: Vec<Option<Individual>> = pool.into_iter().map(Some).collect();
+        crowded_order(&points)
+            .into_iter()
+            .take(self.population)
+            .filter_map(|(i, _)| slots[i].take())
+            .collect()
+    }
+
+    /// Next generation from `ranked`: the elite, then offspring of
+    /// tournament-selected parents mutated at `scale`.
+    #[must_use]
+    pub fn breed(&self, ranked: &[Individual], scale: f64, rng: &mut impl Rng) -> Vec<Genome> {
+        let next: Vec<Genome> = ranked
+            .iter()
+            .take(self.elite.min(self.population))
+            .map(|i| i.genome)
+            .collect();
+        self.fill(next, ranked, scale, rng)
+    }
+
+    /// `next` filled up to the population size with offspring of
+    /// tournament-selected parents mutated at `scale`.
+    fn fill(
+        &self,
+        mut next: Vec<Genome>,
+        ranked: &[Individual],
+        scale: f64,
+        rng: &mut impl Rng,
+    ) -> Vec<Genome> {
+        if ranked.is_empty() {
+            return next;
+        }
+        while next.len() < self.population {
+            let (a, b) = (tournament(ranked, rng), tournament(ranked, rng));
+            let mut child = self.crossover.apply(&a, &b, rng);
+            self.mutation
+                .apply(&mut child, self.mutation_rate, scale, rng);
+            next.push(child);
+        }
+        next
+    }
+
+    /// Runs every generation from mutated copies of the default genome (the
+    /// first founder unchanged), calling `on_generation` after each. With
+    /// `StepSize::Adaptive` the mutation scale follows the share of each
+    /// generation whose fitness beats the previous best. Under
+    /// `Selection::Pareto` each generation is the best of the previous one
+    /// and its offspring, and all of it is offspring of crowded tournaments.
+    #[allow(clippy::cast_precision_loss)] // Population sizes are small
+    pub fn run(&self, mut on_generation: impl FnMut(&GenerationSummary)) -> EvolutionReport {
+        let mut rng = Stream::Mutation.rng(self.seed);
+        let mut genomes: Vec<Genome> = (0..self.population)
+            .map(|i| {
+                let mut genome = Genome::default();
+                if i > 0 {
+                    genome.mutate(1.0, self.mutation_scale, &mut rng);
+                }
+                genome
+            })
+            .collect();
+        let mut report = EvolutionReport::default();
+        let mut scale = self.mutation_scale;
+        let mut parents: Vec<Individual> = Vec::new();
+        for generation in 0..self.generations {
+            let ranked = match self.selection {
+                Selection::Scalar => self.rank(&genomes),
+                Selection::Pareto => {
+                    let mut pool = std::mem::take(&mut parents);
+                    pool.extend(self.evaluate_all(&genomes));
+                    self.pareto_rank(pool)
+                }
+            };
+            let Some(best) = ranked.iter().min_by(|a, b| fitter(a, b)).cloned() else {
+                break;
+            };
+            let points: Vec<Vec<f64>> = ranked.iter().map(|i| i.objectives.clone()).collect();
+            // The front once per distinct genome
+            let mut front: Vec<Individual> = Vec::new();
+            for &i in non_dominated_sort(&points).first().into_iter().flatten() {
+                if front.iter().all(|member| member.genome != ranked[i].genome) {
+                    front.push(ranked[i].clone());
+                }
+            }
+            let survivals: Vec<f64> = ranked.iter().map(|i| i.survival).collect();
+            let fitnesses: Vec<f64> = ranked.iter().map(|i| i.fitness).collect();
+            if let Some(previous) = report.best() {
+                let beaten = fitnesses.iter().filter(|&&f| f > previous.fitness).count();
+                scale = self
+                    .step_size
+                    .adapt(scale, beaten as f64 / fitnesses.len() as f64);
+            }
+            let summary = GenerationSummary {
+                generation,
+                best,
+                survival: Stat::from_values(&survivals),
+                fitness: Stat::from_values(&fitnesses),
+                fitnesses,
+                front,
+                mutation_scale: scale,
+            };
+            on_generation(&summary);
+            report.generations.push(summary);
+            genomes = match self.selection {
+                Selection::Scalar => self.breed(&ranked, scale, &mut rng),
+                Selection::Pareto => {
+                    let offspring = self.fill(Vec::new(), &ranked, scale, &mut rng);
+                    parents = ranked;
+                    offspring
+                }
+            };
+        }
+        report
+    }
+}
+
+/// Scalar ranking: higher fitness first, then higher final energy.
+fn fitter(a: &Individual, b: &Individual) -> std::cmp::Ordering {
+    b.fitness
+        .total_cmp(&a.fitness)
+        .then(b.final_energy.total_cmp(&a.final_energy))
+}
+
+/// Genome of the fittest of `EVOLUTION_TOURNAMENT` random picks from
+/// `ranked` (fittest first, so the lowest index drawn wins; in NSGA-II order
+/// this is the crowded-comparison tournament).
+fn tournament(ranked: &[Individual], rng: &mut impl Rng) -> Genome {
+    let winner = (0..EVOLUTION_TOURNAMENT)
+        .map(|_| rng.random_range(0..ranked.len()))
+        .min()
+        .unwrap_or(0);
+    ranked[winner].genome
+}
@@ -0,0 +1,222 @@
+//! Headless run export for offline analysis.
+//!
+//! `export_run` steps a `World` for a number of ticks and writes one CSV row
+//! per tick (`run.csv`, columns `CSV_COLUMNS`). With `notebook` set it also
+//! writes `analysis.ipynb`, a ready-to-run Jupyter notebook (pandas +
+//! matplotlib) that loads the CSV next to it and reproduces the standard
+//! plots: energy and VFE over sim time and the trajectory heatmap. The
+//! notebook refers to the CSV by file name, so the export directory can be
+//! handed to collaborators as is. With `knowledge` set it writes what the
+//! agent learned by the end of the run to `knowledge.json`, which
+//! `--beliefs knowledge.json` gives to the agents of a later run.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::inheritance::Knowledge;
+use crate::simulation::predator::hunt;
+use crate::simulation::world::World;
+use serde_json::{Value, json};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Columns of the exported CSV, in order (`season` is 0 rich, 1 poor;
+/// `turn` is the executed heading change and `driver` the index in
+/// `Term::ALL` of its largest term, −1 if nothing turned the agent).
+pub const CSV_COLUMNS: [&str; 13] = [
+    "tick", "time", "dish", "x", "y", "angle", "speed", "energy", "vfe", "sense", "season", "turn",
+    "driver",
+];
+
+/// File name of the per-tick CSV.
+pub const RUN_CSV: &str = "run.csv";
+
+/// File name of the analysis notebook.
+pub const NOTEBOOK: &str = "analysis.ipynb";
+
+/// File name of the learned knowledge.
+pub const KNOWLEDGE: &str = "knowledge.json";
+
+/// File name of a strict agent's state dump, written when it halts.
+pub const FAULT_DUMP: &str = "numerical_fault.txt";
+
+/// What `export_run` writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Ticks to simulate (one CSV row each)
+    pub ticks: u64,
+    /// Also write the analysis notebook
+    pub notebook: bool,
+    /// Also write the agent's final knowledge
+    pub knowledge: bool,
+}
+
+/// Appends the CSV row for the agent's current state.
+fn push_row(csv: &mut String, world: &World, agent: &Protozoa) {
+    let dish = &world.dishes[agent.dish_id];
+    let clock = dish.clock;
+    let _ = writeln!(
+        csv,
+        "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        clock.tick,
+        clock.seconds(),
+        agent.dish_id,
+        agent.x,
+        agent.y,
+        agent.angle,
+        agent.speed,
+        agent.energy,
+        agent.current_vfe,
+        agent.last_mean_sense,
+        dish.season().code(),
+        agent.provenance.turn,
+        agent
+            .provenance
+            .dominant()
+            .map_or(-1, |(term, _)| term as i32),
+    );
+}
+
+/// Runs `ticks` ticks (the agent's dish's predators hunting it) and returns
+/// the CSV text (header included), stopping early if a strict agent halts.
+#[must_use]
+pub fn record_csv(world: &mut World, agent: &mut Protozoa, ticks: u64) -> String {
+    let mut csv = CSV_COLUMNS.join(",");
+    csv.push('\n');
+    for _ in 0..ticks {
+        // A strict agent that halted has nothing more to record
+        if agent.is_halted() {
+            break;
+        }
+        world.update();
+        world.step_agent(agent);
+        agent.lay_pheromone(&mut world.dishes[agent.dish_id]);
+        agent.broadcast(&mut world.dishes[agent.dish_id]);
+        hunt(&mut world.dishes[agent.dish_id], [&mut *agent]);
+        push_row(&mut csv, world, agent);
+    }
+    csv
+}
+
+/// Jupyter notebook (nbformat 4) analyzing `csv_name` from a dish of the
+/// given size.
+#[must_use]
+pub fn notebook(csv_name: &str, width: f64, height: f64, ticks: u64) -> Value {
+    let markdown = |text: &str| {
+        json!({
+            "cell_type": "markdown",
+            "metadata": {},
+            "source": source_lines(text),
+        })
+    };
+    let code = |text: &str| {
+        json!({
+            "cell_type": "code",
+            "execution_count": null,
+            "metadata": {},
+            "outputs": [],
+            "source": source_lines(text),
+        })
+    };
+    json!({
+        "cells": [
+            markdown(&format!(
+                "# Protozoa run analysis\n\n\
+                 Exported by `protozoa_rust export`: {ticks} ticks in a \
+                 {width} × {height} dish. One row per tick in `{csv_name}`."
+            )),
+            code(&format!(
+                "import matplotlib.pyplot as plt\n\
+                 import pandas as pd\n\n\
+                 RUN_CSV = {csv_name:?}\n\
+                 DISH_WIDTH = {width:?}\n\
+                 DISH_HEIGHT = {height:?}\n\n\
+                 run = pd.read_csv(RUN_CSV)\n\
+                 run.describe()"
+            )),
+            markdown("## Energy"),
+            code(
+                "fig, ax = plt.subplots(figsize=(10, 3))\n\
+                 ax.plot(run[\"time\"], run[\"energy\"])\n\
+                 ax.set_xlabel(\"time (s)\")\n\
+                 ax.set_ylabel(\"energy\")\n\
+                 ax.set_ylim(0, 1)\n\
+                 plt.show()"
+            ),
+            markdown("## Variational free energy"),
+            code(
+                "fig, ax = plt.subplots(figsize=(10, 3))\n\
+                 ax.plot(run[\"time\"], run[\"vfe\"], color=\"tab:red\")\n\
+                 ax.set_xlabel(\"time (s)\")\n\
+                 ax.set_ylabel(\"VFE\")\n\
+                 plt.show()"
+            ),
+            markdown("## Trajectory heatmap\n\nTime spent per dish region (y grows downward, as on screen)."),
+            code(
+                "fig, ax = plt.subplots(figsize=(10, 5))\n\
+                 counts, _, _, image = ax.hist2d(\n    \
+                     run[\"x\"], run[\"y\"], bins=[50, 25],\n    \
+                     range=[[0, DISH_WIDTH], [0, DISH_HEIGHT]], cmap=\"magma\",\n\
+                 )\n\
+                 ax.invert_yaxis()\n\
+                 ax.set_aspect(\"equal\")\n\
+                 fig.colorbar(image, ax=ax, label=\"ticks\")\n\
+                 plt.show()"
+            ),
+        ],
+        "metadata": {
+            "kernelspec": {
+                "display_name": "Python 3",
+                "language": "python",
+                "name": "python3",
+            },
+            "language_info": { "name": "python" },
+        },
+        "nbformat": 4,
+        "nbformat_minor": 4,
+    })
+}
+
+/// Notebook cell source: one string per line, newlines kept.
+fn source_lines(text: &str) -> Vec<String> {
+    text.split_inclusive('\n').map(str::to_string).collect()
+}
+
+/// Simulates `options.ticks` ticks and writes `run.csv` (and, if asked,
+/// `analysis.ipynb` and `knowledge.json`) into `dir`, creating it if needed.
+/// Returns the paths written.
+///
+/// # Errors
+/// Returns a message if the directory or a file cannot be written, or if a
+/// strict agent halted: then `run.csv` ends at the fault and the state is
+/// dumped to `numerical_fault.txt`.
+pub fn export_run(
+    dir: impl AsRef<Path>,
+    world: &mut World,
+    agent: &mut Protozoa,
+    options: ExportOptions,
+) -> Result<Vec<PathBuf>, String> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+    let write = |name: &str, text: &str| {
+        let path = dir.join(name);
+        std::fs::write(&path, text)
+            .map(|()| path.clone())
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))
+    };
+
+    let mut written = vec![write(RUN_CSV, &record_csv(world, agent, options.ticks))?];
+    if let Some(fault) = &agent.fault {
+        let path = dir.join(FAULT_DUMP);
+        fault.write_dump(&path)?;
+        return Err(format!("{fault}; state dumped to {}", path.display()));
+    }
+    if options.notebook {
+        let dish = &world.dishes[0];
+        let book = notebook(RUN_CSV, dish.width, dish.height, options.ticks);
+        let text = serde_json::to_string_pretty(&book).unwrap_or_default();
+        written.push(write(NOTEBOOK, &text)?);
+    }
+    if options.knowledge {
+        written.push(write(KNOWLEDGE, &Knowledge::of(agent).to_json())?);
+    }
+    Ok(written)
+}
@@ -0,0 +1,288 @@
+//! Pluggable fitness for evolution and parameter sweeps.
+//!
+//! What counts as fit depends on the experiment: living long, staying well
+//! fed, exploring the dish or leaving offspring. An `Episode` records what
+//! one agent's run leaves behind, and a `Fitness` turns it into objective
+//! values (a vector, for multi-objective comparisons) and a scalar score
+//! (their sum unless the fitness says otherwise). Every `Objective` is a
+//! built-in fitness on its own; a `FitnessConfig` is a weighted combination,
+//! given on the command line as `lifespan=1,coverage=500` or as a TOML file
+//! with one weight per objective. `Evolution` ranks genomes by its `fitness`
+//! and `SweepReport::fitness_by_value` scores sweep cells with the same
+//! definitions. The default configuration (lifespan alone) is the fitness
+//! evolution has always used.
+
+use crate::simulation::ensemble::Run;
+use crate::simulation::scenario::ScenarioOutcome;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What one agent's run leaves behind for scoring.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Episode {
+    /// Ticks the agent was alive (the tick it died counts)
+    pub lifespan: u64,
+    /// Energy held over the ticks alive, integrated over simulated seconds
+    pub energy_integral: f64,
+    /// Energy at the end of the run
+    pub final_energy: f64,
+    /// Share of spatial-prior cells the agent visited
+    pub coverage: f64,
+    /// Agents registered as its offspring (0 for a single-agent run)
+    pub offspring: usize,
+}
+
+impl Episode {
+    /// Steps `run` until its agent dies or `ticks` ticks pass, recording
+    /// the episode.
+    pub fn record(run: &mut Run, ticks: u64) -> Self {
+        let mut episode = Self::default();
+        while episode.lifespan < ticks && run.agent.is_alive() {
+            run.step();
+            episode.lifespan += 1;
+            episode.energy_integral += run.dish.clock.rate(run.agent.energy);
+        }
+        episode.final_energy = run.agent.energy;
+        episode.coverage = run.agent.spatial_priors.visited_fraction();
+        episode
+    }
+
+    /// The episode of a scenario run: its trace up to the agent's death,
+    /// or the whole trace if it lived.
+    #[must_use]
+    pub fn of_outcome(outcome: &ScenarioOutcome) -> Self {
+        let ticks = outcome.trace.last().map_or(0, |point| point.tick);
+        let lifespan = outcome
+            .run
+            .agent
+            .died_at
+            .map_or(ticks, |died| died.min(ticks));
+        let clock = outcome.run.dish.clock;
+        Self {
+            lifespan,
+            energy_integral: outcome
+                .trace
+                .iter()
+                .filter(|point| (1..=lifespan).contains(&point.tick))
+                .map(|point| clock.rate(point.energy))
+                .sum(),
+            final_energy: outcome.metrics.final_energy,
+            coverage: outcome.metrics.coverage,
+            offspring: 0,
+        }
+    }
+}
+
+/// Turns an episode into objective values and a score (larger is fitter).
+pub trait Fitness {
+    /// Objective values of `episode`.
+    fn objectives(&self, episode: &Episode) -> Vec<f64>;
+
+    /// Scalar fitness of `episode`: the sum of its objectives by default.
+    fn score(&self, episode: &Episode) -> f64 {
+        self.objectives(episode).iter().sum()
+    }
+}
+
+/// A built-in fitness measure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    /// Ticks alive
+    Lifespan,
+    /// Energy integrated over the lifetime
+    Energy,
+    /// Share of the dish explored
+    Coverage,
+    /// Offspring left
+    Offspring,
+}
+
+impl Objective {
+    /// Every objective, in `FitnessConfig` order.
+    pub const ALL: [Self; 4] = [
+        Self::Lifespan,
+        Self::Energy,
+        Self::Coverage,
+        Self::Offspring,
+    ];
+
+    /// Parses an objective name (`lifespan`, `energy`, `coverage`, `offspring`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|objective| objective.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Lowercase name used on the command line and in config files.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Lifespan => "lifespan",
+            Self::Energy => "energy",
+            Self::Coverage => "coverage",
+            Self::Offspring => "offspring",
+        }
+    }
+
+    /// Unweighted value of this objective for `episode`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Tick and offspring counts are small
+    pub fn value(self, episode: &Episode) -> f64 {
+        match self {
+            Self::Lifespan => episode.lifespan as f64,
+            Self::Energy => episode.energy_integral,
+            Self::Coverage => episode.coverage,
+            Self::Offspring => episode.offspring as f64,
+        }
+    }
+}
+
+impl Fitness for Objective {
+    fn objectives(&self, episode: &Episode) -> Vec<f64> {
+        vec![self.value(episode)]
+    }
+}
+
+/// A weighted combination of the built-in objectives. Weights a config
+/// file leaves out are 0.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default = "FitnessConfig::zero", deny_unknown_fields)]
+pub struct FitnessConfig {
+    pub lifespan: f64,
+    pub energy: f64,
+    pub coverage: f64,
+    pub offspring: f64,
+}
+
+impl Default for FitnessConfig {
+    /// Lifespan alone.
+    fn default() -> Self {
+        Self::only(Objective::Lifespan)
+    }
+}
+
+impl FitnessConfig {
+    /// Weight 1 on `objective` and 0 on the rest.
+    #[must_use]
+    pub fn only(objective: Objective) -> Self {
+        let mut config = Self::zero();
+        *config.slot(objective) = 1.0;
+        config
+    }
+
+    /// Every weight 0 (not a valid fitness on its own).
+    const fn zero() -> Self {
+        Self {
+            lifespan: 0.0,
+            energy: 0.0,
+            coverage: 0.0,
+            offspring: 0.0,
+        }
+    }
+
+    /// Weight of `objective`.
+    #[must_use]
+    pub fn weight(&self, objective: Objective) -> f64 {
+        match objective {
+            Objective::Lifespan => self.lifespan,
+            Objective::Energy => self.energy,
+            Objective::Coverage => self.coverage,
+            Objective::Offspring => self.offspring,
+        }
+    }
+
+    fn slot(&mut self, objective: Objective) -> &mut f64 {
+        match objective {
+            Objective::Lifespan => &mut self.lifespan,
+            Objective::Energy => &mut self.energy,
+            Objective::Coverage => &mut self.coverage,
+            Objective::Offspring => &mut self.offspring,
+        }
+    }
+
+    /// Parses `NAME` (that objective alone) or `NAME=W,NAME=W,...` (the
+    /// named weights, the rest 0).
+    ///
+    /// # Errors
+    /// Returns a message for an unknown name, a bad weight or all-zero weights.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        if let Some(objective) = Objective::parse(spec) {
+            return Ok(Self::only(objective));
+        }
+        let mut config = Self::zero();
+        for term in spec.split(',') {
+            let (name, weight) = term
+                .split_once('=')
+                .ok_or("usage: --fitness NAME | NAME=W,... | FILE.toml")?;
+            let objective = Objective::parse(name.trim())
+                .ok_or_else(|| format!("unknown fitness objective: {name}"))?;
+            *config.slot(objective) = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid fitness weight: {weight}"))?;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parses and validates a TOML document of weights.
+    ///
+    /// # Errors
+    /// Returns a message for malformed TOML, unknown keys or invalid weights.
+    pub fn from_toml_str(text: &str) -> Result<Self, String> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| format!("invalid fitness config: {e}"))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads a TOML file (see `from_toml_str`).
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be read or is not a valid config.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Checks that every weight is finite and at least one is not zero.
+    ///
+    /// # Errors
+    /// Names the first offending objective.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(objective) = Objective::ALL
+            .into_iter()
+            .find(|&o| !self.weight(o).is_finite())
+        {
+            return Err(format!("{} weight must be finite", objective.name()));
+        }
+        if Objective::ALL.iter().all(|&o| self.weight(o) == 0.0) {
+            return Err("fitness needs a non-zero weight".to_string());
+        }
+        Ok(())
+    }
+
+    /// The non-zero weights as `name=weight` pairs.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        Objective::ALL
+            .iter()
+            .filter(|&&o| self.weight(o) != 0.0)
+            .map(|&o| format!("{}={}", o.name(), self.weight(o)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Fitness for FitnessConfig {
+    /// Weighted objective values, in `Objective::ALL` order.
+    fn objectives(&self, episode: &Episode) -> Vec<f64> {
+        Objective::ALL
+            .iter()
+            .map(|&o| self.weight(o) * o.value(episode))
+            .collect()
+    }
+}
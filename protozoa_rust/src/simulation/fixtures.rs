@@ -0,0 +1,267 @@
+//! Small deterministic environments with analytic answers.
+//!
+//! Random dishes exercise everything at once. Each fixture here isolates one
+//! feature of a field, so the inference and planning modules can be checked
+//! against ground truth:
+//!
+//! - `StaticSource`: one Gaussian source that never decays or drifts. The
+//!   field peaks at its center and its gradient points straight at it.
+//! - `LinearGradient`: `c = base + slope·x` across the dish, with gradient
+//!   `(slope, 0)` everywhere.
+//! - `StepChange`: a uniform field that jumps from `before` to `after` at a
+//!   given tick.
+//!
+//! All three are plain `Environment`s built in code. `run_fixture` steps an
+//! agent through one (sense, update, advance the field) and records a
+//! `FixtureTrace` of what it did, believed and should have sensed, which the
+//! expectations (`first_tick_within`, `mean_belief`, ...) are checked on.
+//! With a seeded agent, a fixture replays exactly.
+
+// Test-support API; the TUI binary does not use it
+#![allow(dead_code)]
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::environment::Environment;
+use std::ops::Range;
+
+/// One Gaussian source, fixed forever.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StaticSource {
+    pub x: f64,
+    pub y: f64,
+    /// Concentration at the center
+    pub intensity: f64,
+    /// Spread (standard deviation) of the Gaussian
+    pub radius: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl StaticSource {
+    /// A full-strength source of radius 12 at the center of a dish.
+    #[must_use]
+    pub fn centered(width: f64, height: f64) -> Self {
+        Self {
+            x: width / 2.0,
+            y: height / 2.0,
+            intensity: 1.0,
+            radius: 12.0,
+            width,
+            height,
+        }
+    }
+
+    /// Distance from (x, y) to the source's center.
+    #[must_use]
+    pub fn distance(&self, x: f64, y: f64) -> f64 {
+        (x - self.x).hypot(y - self.y)
+    }
+
+    fn field(&self, x: f64, y: f64) -> f64 {
+        let d = self.distance(x, y);
+        self.intensity * (-d * d / (2.0 * self.radius * self.radius)).exp()
+    }
+}
+
+impl Environment for StaticSource {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            self.field(x, y)
+        } else {
+            -1.0
+        }
+    }
+
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        let scale = -self.field(x, y) / (self.radius * self.radius);
+        (scale * (x - self.x), scale * (y - self.y))
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+}
+
+/// A field rising linearly along x. Keep `base` and `base + slope·width`
+/// within [0, 1] for the concentration to stay a valid reading.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearGradient {
+    /// Concentration at the left wall
+    pub base: f64,
+    /// Rise per unit of x
+    pub slope: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl LinearGradient {
+    /// Rising from 0 at the left wall to 1 at the right one.
+    #[must_use]
+    pub fn across(width: f64, height: f64) -> Self {
+        Self {
+            base: 0.0,
+            slope: 1.0 / width,
+            width,
+            height,
+        }
+    }
+}
+
+impl Environment for LinearGradient {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            self.base + self.slope * x
+        } else {
+            -1.0
+        }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (self.slope, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+}
+
+/// A uniform field stepping from one level to another.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepChange {
+    /// Level before the step
+    pub before: f64,
+    /// Level from the step on
+    pub after: f64,
+    /// Tick the step happens at
+    pub at: u64,
+    pub width: f64,
+    pub height: f64,
+    tick: u64,
+}
+
+impl StepChange {
+    /// A field at `before` until tick `at`, then at `after`.
+    #[must_use]
+    pub fn new(before: f64, after: f64, at: u64, width: f64, height: f64) -> Self {
+        Self {
+            before,
+            after,
+            at,
+            width,
+            height,
+            tick: 0,
+        }
+    }
+
+    /// Ticks the field has been advanced.
+    #[must_use]
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// The uniform level at `tick`.
+    #[must_use]
+    pub fn level_at(&self, tick: u64) -> f64 {
+        if tick < self.at {
+            self.before
+        } else {
+            self.after
+        }
+    }
+}
+
+impl Environment for StepChange {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            self.level_at(self.tick)
+        } else {
+            -1.0
+        }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    fn update(&mut self) {
+        self.tick += 1;
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+}
+
+/// One tick of an agent in a fixture.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixtureSample {
+    /// Tick of the fixture the agent sensed in
+    pub tick: u64,
+    /// Position the agent sensed from
+    pub sensed_at: (f64, f64),
+    /// True concentration there
+    pub truth: f64,
+    /// Believed nutrient concentration after the update
+    pub belief: f64,
+    /// Variational free energy after the update
+    pub vfe: f64,
+    /// Pose after the update
+    pub x: f64,
+    pub y: f64,
+    pub angle: f64,
+    pub energy: f64,
+}
+
+/// Every tick of an agent's run through a fixture, in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FixtureTrace {
+    pub samples: Vec<FixtureSample>,
+}
+
+impl FixtureTrace {
+    /// First tick (index into `samples`) that ended within `distance` of
+    /// (x, y), if any.
+    #[must_use]
+    pub fn first_tick_within(&self, x: f64, y: f64, distance: f64) -> Option<usize> {
+        self.samples
+            .iter()
+            .position(|s| (s.x - x).hypot(s.y - y) <= distance)
+    }
+
+    /// Mean belief over the ticks in `range`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_belief(&self, range: Range<usize>) -> f64 {
+        let window = &self.samples[range];
+        window.iter().map(|s| s.belief).sum::<f64>() / window.len().max(1) as f64
+    }
+}
+
+/// Steps `agent` through `env` for `ticks` ticks (sense, update the agent,
+/// advance the field), recording each tick.
+pub fn run_fixture(env: &mut impl Environment, agent: &mut Protozoa, ticks: usize) -> FixtureTrace {
+    let mut trace = FixtureTrace::default();
+    for tick in 0..ticks as u64 {
+        let sensed_at = (agent.x, agent.y);
+        let truth = env.get_concentration(agent.x, agent.y);
+        agent.sense(env);
+        agent.update_state(env);
+        trace.samples.push(FixtureSample {
+            tick,
+            sensed_at,
+            truth,
+            belief: agent.beliefs.mean.nutrient,
+            vfe: agent.current_vfe,
+            x: agent.x,
+            y: agent.y,
+            angle: agent.angle,
+            energy: agent.energy,
+        });
+        env.update();
+    }
+    trace
+}
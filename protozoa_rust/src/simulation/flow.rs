@@ -0,0 +1,64 @@
+//! Fluid flow (advection currents) in the dish.
+//!
+//! The velocity field is the sum of a uniform current and a vortex around the
+//! dish center. It carries nutrient sources along and pushes the agent each tick.
+
+use crate::simulation::params::{FLOW_DIRECTION, FLOW_STRENGTH, FLOW_VORTICITY};
+
+/// Steady velocity field over the dish, in world units per tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlowField {
+    /// Speed of the uniform current
+    pub strength: f64,
+    /// Heading of the uniform current in radians (0 = +x)
+    pub direction: f64,
+    /// Angular velocity of the vortex around the dish center (radians per tick)
+    pub vorticity: f64,
+    /// Vortex center x coordinate
+    pub center_x: f64,
+    /// Vortex center y coordinate
+    pub center_y: f64,
+}
+
+impl FlowField {
+    /// Creates the flow field configured in `params.rs` for a dish of the given size.
+    #[must_use]
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            strength: FLOW_STRENGTH,
+            direction: FLOW_DIRECTION,
+            vorticity: FLOW_VORTICITY,
+            center_x: width / 2.0,
+            center_y: height / 2.0,
+        }
+    }
+
+    /// Creates a still field (no currents).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and scripted scenarios
+    pub fn still(width: f64, height: f64) -> Self {
+        Self {
+            strength: 0.0,
+            vorticity: 0.0,
+            ..Self::new(width, height)
+        }
+    }
+
+    /// Returns true if the field has no currents anywhere.
+    #[must_use]
+    pub fn is_still(&self) -> bool {
+        self.strength == 0.0 && self.vorticity == 0.0
+    }
+
+    /// Velocity `(vx, vy)` at world position (x, y).
+    ///
+    /// `v = s·(cos φ, sin φ) + ω·(-(y - cy), x - cx)`
+    #[must_use]
+    pub fn velocity_at(&self, x: f64, y: f64) -> (f64, f64) {
+        let uniform_x = self.strength * self.direction.cos();
+        let uniform_y = self.strength * self.direction.sin();
+        let vortex_x = -self.vorticity * (y - self.center_y);
+        let vortex_y = self.vorticity * (x - self.center_x);
+        (uniform_x + vortex_x, uniform_y + vortex_y)
+    }
+}
@@ -0,0 +1,82 @@
+//! Long-run memory footprint controls.
+//!
+//! Every structure that grows as a run goes on (event logs, caches, sample
+//! histories, lineage records and the dead of a population) has a capacity taken from `MemoryLimits`. Once a structure is
+//! full it evicts or compacts instead of growing, so day-long runs use a
+//! constant amount of memory. `MemoryUsage` reports the current footprint for
+//! the diagnostics readout.
+
+use crate::simulation::params::{
+    DEAD_AGENT_CAPACITY, ENSEMBLE_HISTORY_LEN, EVENT_LOG_CAPACITY, LINEAGE_CAPACITY,
+    MCTS_CACHE_CAPACITY,
+};
+
+/// Capacities of the growing structures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryLimits {
+    /// Recent arrival events kept per agent
+    pub event_log: usize,
+    /// Rollout cache entries per agent
+    pub rollout_cache: usize,
+    /// Aggregate samples kept per ensemble
+    pub ensemble_history: usize,
+    /// Lineage records kept per population (only the dead are compacted)
+    pub lineage: usize,
+    /// Dead agents kept per population
+    pub dead_agents: usize,
+}
+
+impl Default for MemoryLimits {
+    fn default() -> Self {
+        Self {
+            event_log: EVENT_LOG_CAPACITY,
+            rollout_cache: MCTS_CACHE_CAPACITY,
+            ensemble_history: ENSEMBLE_HISTORY_LEN,
+            lineage: LINEAGE_CAPACITY,
+            dead_agents: DEAD_AGENT_CAPACITY,
+        }
+    }
+}
+
+/// Approximate heap and inline bytes held by a run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Fixed-size state (agent struct, grids, ring buffers)
+    pub fixed: usize,
+    /// Arrival event log
+    pub event_log: usize,
+    /// MCTS rollout cache
+    pub rollout_cache: usize,
+    /// Aggregate sample history (ensembles only)
+    pub history: usize,
+    /// Lineage records (populations only)
+    pub lineage: usize,
+}
+
+impl MemoryUsage {
+    /// Sum of all parts.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.fixed + self.event_log + self.rollout_cache + self.history + self.lineage
+    }
+}
+
+impl std::ops::Add for MemoryUsage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            fixed: self.fixed + other.fixed,
+            event_log: self.event_log + other.event_log,
+            rollout_cache: self.rollout_cache + other.rollout_cache,
+            history: self.history + other.history,
+            lineage: self.lineage + other.lineage,
+        }
+    }
+}
+
+impl std::iter::Sum for MemoryUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
+    }
+}
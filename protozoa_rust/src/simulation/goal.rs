@@ -0,0 +1,111 @@
+//! Exogenous goals installed from outside the agent.
+//!
+//! A goal is expressed the Active Inference way: as a temporary prior
+//! preference over position in the generative model. Its precision is the
+//! goal's priority, so stronger goals weigh more against homeostasis.
+//! Progress toward the goal is tracked for benchmarking.
+//!
+//! Goals arrive through `Protozoa::set_goal`, the dashboard's `:` console
+//! (`GoalCommand`) or `--goal`. There is no WebSocket command channel: the
+//! crate has no networking layer, and a remote controller can drive
+//! `set_goal` from its own process.
+
+/// An externally set target position with progress tracking.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Goal {
+    /// Target x position
+    pub x: f64,
+    /// Target y position
+    pub y: f64,
+    /// Priority (scales the precision of the position prior)
+    pub priority: f64,
+    /// Tick at which the goal was installed
+    pub set_tick: u64,
+    /// Distance to the target when the goal was installed
+    pub initial_distance: f64,
+    /// Current distance to the target
+    pub distance: f64,
+}
+
+impl Goal {
+    /// Creates a goal as seen from the agent's current position.
+    #[must_use]
+    pub fn new(x: f64, y: f64, priority: f64, from: (f64, f64), tick: u64) -> Self {
+        let distance = (x - from.0).hypot(y - from.1);
+        Self {
+            x,
+            y,
+            priority,
+            set_tick: tick,
+            initial_distance: distance,
+            distance,
+        }
+    }
+
+    /// Updates the current distance from the agent's position.
+    pub fn track(&mut self, agent_x: f64, agent_y: f64) {
+        self.distance = (self.x - agent_x).hypot(self.y - agent_y);
+    }
+
+    /// Fraction of the initial distance covered, in [0, 1].
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        if self.initial_distance <= f64::EPSILON {
+            return 1.0;
+        }
+        (1.0 - self.distance / self.initial_distance).clamp(0.0, 1.0)
+    }
+
+    /// Ticks elapsed since the goal was installed.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and goal-reaching benchmarks
+    pub fn elapsed(&self, tick: u64) -> u64 {
+        tick.saturating_sub(self.set_tick)
+    }
+}
+
+/// A textual goal command, e.g. typed on the dashboard command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GoalCommand {
+    /// `goal X Y [PRIORITY]`
+    Set { x: f64, y: f64, priority: f64 },
+    /// `clear`
+    Clear,
+}
+
+impl GoalCommand {
+    /// Parses `goal X Y [PRIORITY]` (priority defaults to 1) or `clear`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable message for unknown commands, missing or
+    /// non-numeric arguments, and non-positive priorities.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut words = input.split_whitespace();
+        match words.next() {
+            Some("clear") => Ok(Self::Clear),
+            Some("goal") => {
+                let mut number = |name: &str| -> Result<Option<f64>, String> {
+                    words
+                        .next()
+                        .map(|w| {
+                            w.parse::<f64>()
+                                .ok()
+                                .filter(|v| v.is_finite())
+                                .ok_or_else(|| format!("invalid {name}: {w}"))
+                        })
+                        .transpose()
+                };
+                let x = number("x")?.ok_or("usage: goal X Y [PRIORITY]")?;
+                let y = number("y")?.ok_or("usage: goal X Y [PRIORITY]")?;
+                let priority = number("priority")?.unwrap_or(1.0);
+                if priority <= 0.0 {
+                    return Err(format!("priority must be positive: {priority}"));
+                }
+                Ok(Self::Set { x, y, priority })
+            }
+            Some(other) => Err(format!("unknown command: {other}")),
+            None => Err("empty command".to_string()),
+        }
+    }
+}
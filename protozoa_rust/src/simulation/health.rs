@@ -0,0 +1,67 @@
+//! Structural integrity, a second vital sign beside energy.
+//!
+//! Energy tracks the food budget; health tracks damage to the body. An agent
+//! loses health while its sensors read the toxic void (off the dish or inside
+//! an obstacle), on every collision with an obstacle (in proportion to its
+//! speed), and while its sensors reach further than `STRAIN_SENSOR_DIST` (a
+//! body stretched by extreme morphogenesis). It regenerates at
+//! `HEALTH_REGEN_RATE` per second while energy is at least
+//! `HEALTH_REGEN_ENERGY`, and the agent dies when health reaches zero, however
+//! well fed it is.
+
+use crate::simulation::clock::SimClock;
+use crate::simulation::params::{
+    COLLISION_DAMAGE, HEALTH_REGEN_ENERGY, HEALTH_REGEN_RATE, MAX_SENSOR_DIST, MAX_SPEED,
+    STRAIN_DAMAGE_RATE, STRAIN_SENSOR_DIST, TOXIN_DAMAGE_RATE,
+};
+
+/// Damage taken in one tick, by cause.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Injury {
+    /// From sensors reading the toxic void
+    pub toxin: f64,
+    /// From running into an obstacle
+    pub collision: f64,
+    /// From sensors stretched past `STRAIN_SENSOR_DIST`
+    pub strain: f64,
+}
+
+impl Injury {
+    /// Assesses a tick: the two sensor readings, the speed of a collision
+    /// (`None` without one) and the sensor reach.
+    #[must_use]
+    pub fn assess(
+        readings: (f64, f64),
+        impact_speed: Option<f64>,
+        sensor_dist: f64,
+        clock: &SimClock,
+    ) -> Self {
+        let toxic = f64::from(u8::from(readings.0 < 0.0) + u8::from(readings.1 < 0.0)) / 2.0;
+        let excess = ((sensor_dist - STRAIN_SENSOR_DIST) / (MAX_SENSOR_DIST - STRAIN_SENSOR_DIST))
+            .clamp(0.0, 1.0);
+        Self {
+            toxin: clock.rate(TOXIN_DAMAGE_RATE * toxic),
+            collision: impact_speed.map_or(0.0, |speed| {
+                COLLISION_DAMAGE * (speed.abs() / MAX_SPEED).min(1.0)
+            }),
+            strain: clock.rate(STRAIN_DAMAGE_RATE * excess),
+        }
+    }
+
+    /// All damage of the tick.
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        self.toxin + self.collision + self.strain
+    }
+}
+
+/// Health after a tick's `injury`, regenerating if `energy` allows, in [0, 1].
+#[must_use]
+pub fn settle(health: f64, injury: &Injury, energy: f64, clock: &SimClock) -> f64 {
+    let regen = if energy >= HEALTH_REGEN_ENERGY {
+        clock.rate(HEALTH_REGEN_RATE)
+    } else {
+        0.0
+    };
+    (health - injury.total() + regen).clamp(0.0, 1.0)
+}
@@ -0,0 +1,142 @@
+//! Hunger-dependent preference modulation.
+//!
+//! How much an agent cares about food should depend on how much it needs
+//! it. Without modulation (the default) the nutrient preference has a fixed
+//! precision and a few decisions switch at hard energy thresholds: the pull
+//! of a remembered landmark and landmark navigation only start below
+//! `MCTS_URGENT_ENERGY`. With a gain `g > 0` (`Hunger::gain`, `--hunger`)
+//! everything follows the energy deficit below the interoceptive set-point,
+//! `h = clamp((η_E − E) / η_E, 0, 1)`, continuously:
+//!
+//! ```text
+//! d(h) = e^(g (h − h₀))        h₀ = HUNGER_PIVOT
+//! π_η(h) = π_η · d(h)          nutrient prior precision
+//! w_prag = d(h),  w_epi = 1 / d(h)   in the planner's value
+//! u(E) = min(1, h / h_u)       landmark urge, h_u the deficit at MCTS_URGENT_ENERGY
+//! ```
+//!
+//! A hungry agent (`h > h₀`) holds its food preference more precisely and
+//! values expected food above information; a sated one relaxes the
+//! preference and explores. The landmark pull grows smoothly from the
+//! set-point and reaches full strength where the threshold used to be.
+
+use crate::simulation::params::{HUNGER_GAIN, HUNGER_PIVOT, MCTS_URGENT_ENERGY};
+
+/// Weights of the two parts of a plan's value (`MCTSPlanner::drive`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Drive {
+    /// Weight of the expected food (pragmatic value)
+    pub pragmatic: f64,
+    /// Weight of the expected information (epistemic value)
+    pub epistemic: f64,
+}
+
+impl Default for Drive {
+    /// Both at their base weight.
+    fn default() -> Self {
+        Self {
+            pragmatic: 1.0,
+            epistemic: 1.0,
+        }
+    }
+}
+
+/// Modulation of preferences by the energy deficit
+/// (`GenerativeModel::hunger`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hunger {
+    /// Gain `g` of the modulation (0 = off)
+    pub gain: f64,
+    /// Current energy deficit `h` in [0, 1] (`observe`)
+    pub level: f64,
+}
+
+impl Default for Hunger {
+    fn default() -> Self {
+        Self::new(HUNGER_GAIN)
+    }
+}
+
+impl Hunger {
+    /// Modulation with gain `gain`, sated.
+    #[must_use]
+    pub const fn new(gain: f64) -> Self {
+        Self { gain, level: 0.0 }
+    }
+
+    /// Returns true if preferences are not modulated.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.gain <= 0.0
+    }
+
+    /// Energy deficit in [0, 1] of `energy` below `setpoint`.
+    #[must_use]
+    pub fn deficit(energy: f64, setpoint: f64) -> f64 {
+        if setpoint > 0.0 {
+            ((setpoint - energy) / setpoint).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Records the deficit of `energy` below `setpoint`.
+    pub fn observe(&mut self, energy: f64, setpoint: f64) {
+        self.level = Self::deficit(energy, setpoint);
+    }
+
+    /// Factor `d(h)` on the nutrient preference (1 when off or at the
+    /// pivot).
+    #[must_use]
+    pub fn factor(&self) -> f64 {
+        if self.is_off() {
+            1.0
+        } else {
+            (self.gain * (self.level - HUNGER_PIVOT)).exp()
+        }
+    }
+
+    /// Weights of food and information at the current deficit.
+    #[must_use]
+    pub fn drive(&self) -> Drive {
+        if self.is_off() {
+            return Drive::default();
+        }
+        let factor = self.factor();
+        Drive {
+            pragmatic: factor,
+            epistemic: factor.recip(),
+        }
+    }
+
+    /// Strength in [0, 1] of the pull toward a remembered food patch at
+    /// `energy`. Off, it is 1 below `MCTS_URGENT_ENERGY` and 0 from there.
+    #[must_use]
+    pub fn urge(&self, energy: f64, setpoint: f64) -> f64 {
+        if self.is_off() {
+            return if energy < MCTS_URGENT_ENERGY {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        let urgent = Self::deficit(MCTS_URGENT_ENERGY, setpoint);
+        if urgent > 0.0 {
+            (Self::deficit(energy, setpoint) / urgent).min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Checks that the gain is finite and not negative.
+    ///
+    /// # Errors
+    /// Names the offending gain.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.gain.is_finite() && self.gain >= 0.0 {
+            Ok(())
+        } else {
+            Err(format!("hunger gain out of range: {}", self.gain))
+        }
+    }
+}
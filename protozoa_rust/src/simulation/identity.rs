@@ -0,0 +1,43 @@
+//! Stable identities of agents.
+//!
+//! Indices into `Population::agents` say nothing to someone reading a
+//! telemetry file or a lineage tree, and a name given on the command line
+//! should follow its agent through the run. Every agent carries its
+//! lineage ID (`Protozoa::lineage_id`, unique within its population and
+//! kept for life, dead or alive), an optional name (`Protozoa::name`) and
+//! the population tick it was created at (`Protozoa::created_at`). Names
+//! are short tokens so they survive CSV columns and the command line
+//! unquoted; `label` renders an identity as the dashboard and logs show it.
+
+use crate::simulation::params::AGENT_NAME_MAX_LEN;
+
+/// `name` if it is a usable agent name: 1 to `AGENT_NAME_MAX_LEN` ASCII
+/// letters, digits, `-` or `_`.
+///
+/// # Errors
+/// Says what is wrong with the name.
+pub fn validate_name(name: &str) -> Result<&str, String> {
+    if name.is_empty() || name.len() > AGENT_NAME_MAX_LEN {
+        return Err(format!(
+            "agent name must be 1 to {AGENT_NAME_MAX_LEN} characters: {name:?}"
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "agent name may only use letters, digits, - and _: {name:?}"
+        ));
+    }
+    Ok(name)
+}
+
+/// `#ID`, followed by the name if there is one (`#3 alice`).
+#[must_use]
+pub fn label(id: u64, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("#{id} {name}"),
+        None => format!("#{id}"),
+    }
+}
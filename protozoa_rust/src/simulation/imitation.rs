@@ -0,0 +1,323 @@
+//! Imitation bootstrap: warm starts from recorded runs.
+//!
+//! A `Demonstration` is a run exported with `export` (a `run.csv`), whether
+//! a champion agent's or a human's at the controls: where it went, which way
+//! it swam and what it sensed there, tick by tick. Before an episode starts,
+//! `Imitation::instill` gives an agent two priors taken from one or more
+//! demonstrations:
+//!
+//! - spatial priors: every demonstrated reading is folded into a map as if
+//!   the agent had sensed it there, the map's visit counts are scaled by the
+//!   bootstrap `strength` and the result is pooled with what the agent
+//!   already knows, so a weak bootstrap is soon outweighed by experience;
+//! - habit priors: a `HabitMap` of the demonstrators' mean heading in each
+//!   cell, weighted by how consistently they swam that way and by
+//!   `strength`, which `Protozoa::habit_steering` turns toward.
+//!
+//! For strength s in [0, 1], a cell with n demonstrated visits, mean μ and
+//! sum of squares M₂, and a cell with demonstrated headings θₖ:
+//!
+//! ```text
+//! n' = round(s n)      M₂' = M₂ n' / n          then pooled with the agent's cell
+//! φ_h = atan2(Σₖ sin θₖ, Σₖ cos θₖ)      w = s R̄      R̄ the mean resultant length
+//! Δθ = g_h w sin(φ_h − θ)                          added to the heading blend
+//! ```
+//!
+//! Demonstrations are in the coordinates of the dish they were recorded in
+//! (only rows in dish 0 are kept), so they belong to the same layout: the
+//! same `--preset`, `--layout` or `--dish-seed`.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::memory::{CellPrior, SpatialGrid};
+use crate::simulation::params::{DISH_HEIGHT, DISH_WIDTH, IMITATION_STRENGTH};
+use std::path::Path;
+
+/// Columns of a `HabitMap` (as in the spatial priors).
+const HABIT_COLUMNS: usize = 20;
+/// Rows of a `HabitMap`.
+const HABIT_ROWS: usize = 10;
+
+/// One demonstrated tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DemonstrationStep {
+    pub x: f64,
+    pub y: f64,
+    /// Heading in radians
+    pub angle: f64,
+    /// Mean chemoreceptor reading
+    pub sense: f64,
+}
+
+/// A recorded run to learn from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Demonstration {
+    /// Where it came from (file name)
+    pub label: String,
+    pub steps: Vec<DemonstrationStep>,
+}
+
+impl Demonstration {
+    /// Parses an `export` CSV; columns are found by header name (`x`, `y`,
+    /// `angle` and `sense` are required, others are ignored). With a `dish`
+    /// column only rows in dish 0 are kept.
+    ///
+    /// # Errors
+    /// Returns a message naming the missing column or the bad line.
+    pub fn from_csv(label: &str, text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+        let find = |name: &str| header.iter().position(|h| h.trim() == name);
+        let column = |name: &str| find(name).ok_or_else(|| format!("{label}: no `{name}` column"));
+        let (x, y, angle, sense) = (
+            column("x")?,
+            column("y")?,
+            column("angle")?,
+            column("sense")?,
+        );
+        let dish = find("dish");
+        let mut steps = Vec::new();
+        for (number, line) in lines.enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let fields: Vec<&str> = line.split(',').collect();
+            let bad = || format!("{label}: bad row on line {}", number + 2);
+            let value = |i: usize| {
+                fields
+                    .get(i)
+                    .and_then(|v| v.trim().parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+            };
+            if let Some(dish) = dish
+                && value(dish).ok_or_else(bad)? != 0.0
+            {
+                continue;
+            }
+            steps.push(DemonstrationStep {
+                x: value(x).ok_or_else(bad)?,
+                y: value(y).ok_or_else(bad)?,
+                angle: value(angle).ok_or_else(bad)?,
+                sense: value(sense).ok_or_else(bad)?,
+            });
+        }
+        Ok(Self {
+            label: label.to_string(),
+            steps,
+        })
+    }
+
+    /// Reads an exported `run.csv`, labelled with its path.
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_csv(&path.display().to_string(), &text)
+    }
+}
+
+/// Demonstrated heading in one cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Habit {
+    /// Mean demonstrated heading in radians
+    pub heading: f64,
+    /// Strength of the habit: bootstrap strength times the mean resultant
+    /// length of the demonstrated headings (0 = no habit)
+    pub weight: f64,
+}
+
+/// Habit priors over the dish: a demonstrated heading per cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HabitMap {
+    cells: Vec<Habit>,
+    world_width: f64,
+    world_height: f64,
+}
+
+impl Default for HabitMap {
+    fn default() -> Self {
+        Self::new(DISH_WIDTH, DISH_HEIGHT)
+    }
+}
+
+impl HabitMap {
+    /// An empty map covering a dish of the given size.
+    #[must_use]
+    pub fn new(world_width: f64, world_height: f64) -> Self {
+        Self {
+            cells: vec![Habit::default(); HABIT_COLUMNS * HABIT_ROWS],
+            world_width,
+            world_height,
+        }
+    }
+
+    /// Index of the cell holding (x, y), clamped to the map.
+    #[allow(
+        clippy::cast_precision_loss,  // Grid dimensions are small
+        clippy::cast_possible_truncation,  // Values are clamped to valid range
+        clippy::cast_sign_loss  // Values are clamped to non-negative
+    )]
+    fn index(&self, x: f64, y: f64) -> usize {
+        let col = ((x / self.world_width) * HABIT_COLUMNS as f64)
+            .floor()
+            .clamp(0.0, (HABIT_COLUMNS - 1) as f64) as usize;
+        let row = ((y / self.world_height) * HABIT_ROWS as f64)
+            .floor()
+            .clamp(0.0, (HABIT_ROWS - 1) as f64) as usize;
+        row * HABIT_COLUMNS + col
+    }
+
+    /// The habit at (x, y).
+    #[must_use]
+    pub fn at(&self, x: f64, y: f64) -> Habit {
+        self.cells[self.index(x, y)]
+    }
+
+    /// Returns true if no cell holds a habit.
+    #[allow(dead_code)] // Used by tests
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(|h| h.weight <= 0.0)
+    }
+
+    /// Iterates over the cells row by row.
+    #[allow(dead_code)] // Used by tests
+    pub fn cells(&self) -> impl Iterator<Item = &Habit> {
+        self.cells.iter()
+    }
+
+    /// Stretches the map over a resized dish, like `SpatialGrid::resize`.
+    pub fn resize(&mut self, world_width: f64, world_height: f64) {
+        self.world_width = world_width;
+        self.world_height = world_height;
+    }
+
+    /// Returns the dish dimensions the map covers.
+    #[allow(dead_code)] // Used by tests
+    #[must_use]
+    pub fn world_dimensions(&self) -> (f64, f64) {
+        (self.world_width, self.world_height)
+    }
+}
+
+/// A bootstrap from demonstrations, selected with `--imitate`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Imitation {
+    pub demonstrations: Vec<Demonstration>,
+    /// How much the demonstrations count, in [0, 1]: the share of their
+    /// visits the spatial priors keep and the top weight of a habit
+    pub strength: f64,
+}
+
+impl Imitation {
+    /// A bootstrap from `demonstrations` at the default strength.
+    #[must_use]
+    pub fn new(demonstrations: Vec<Demonstration>) -> Self {
+        Self {
+            demonstrations,
+            strength: IMITATION_STRENGTH,
+        }
+    }
+
+    /// Checks that the strength lies in [0, 1].
+    ///
+    /// # Errors
+    /// Names the offending strength.
+    pub fn validate(&self) -> Result<(), String> {
+        if (0.0..=1.0).contains(&self.strength) {
+            Ok(())
+        } else {
+            Err(format!(
+                "imitation strength out of range: {}",
+                self.strength
+            ))
+        }
+    }
+
+    fn steps(&self) -> impl Iterator<Item = &DemonstrationStep> {
+        self.demonstrations.iter().flat_map(|d| d.steps.iter())
+    }
+
+    /// Spatial priors over a dish of the given size from every demonstrated
+    /// reading, with visit counts (and the spread with them) scaled by the
+    /// strength; cells left with no visits stay uninformed.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,  // Rounded and clamped to u32
+        clippy::cast_sign_loss  // Visits and strength are non-negative
+    )]
+    pub fn spatial_priors(&self, width: f64, height: f64) -> SpatialGrid<20, 10> {
+        let mut priors = SpatialGrid::new(width, height);
+        for step in self.steps() {
+            priors.update(step.x, step.y, step.sense);
+        }
+        for cell in priors.cells_mut().filter(|c| c.visits > 0) {
+            let visits = (f64::from(cell.visits) * self.strength).round() as u32;
+            *cell = if visits == 0 {
+                CellPrior::default()
+            } else {
+                CellPrior {
+                    mean: cell.mean,
+                    m2: cell.m2 * f64::from(visits) / f64::from(cell.visits),
+                    visits,
+                }
+            };
+        }
+        priors
+    }
+
+    /// Habit priors over a dish of the given size: per cell, the circular
+    /// mean of the demonstrated headings, weighted by the strength times
+    /// their mean resultant length.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Step counts are far below 2^52
+    pub fn habits(&self, width: f64, height: f64) -> HabitMap {
+        let mut map = HabitMap::new(width, height);
+        let mut sums = vec![(0.0, 0.0, 0_usize); map.cells.len()];
+        for step in self.steps() {
+            let sum = &mut sums[map.index(step.x, step.y)];
+            sum.0 += step.angle.sin();
+            sum.1 += step.angle.cos();
+            sum.2 += 1;
+        }
+        for (habit, (sin, cos, count)) in map.cells.iter_mut().zip(sums) {
+            if count > 0 {
+                *habit = Habit {
+                    heading: sin.atan2(cos),
+                    weight: self.strength * sin.hypot(cos) / count as f64,
+                };
+            }
+        }
+        map
+    }
+
+    /// Gives `agent` the demonstrations' priors for the dish its map covers:
+    /// its spatial priors pooled with the bootstrapped ones and its habit
+    /// map replaced.
+    pub fn instill(&self, agent: &mut Protozoa) {
+        let (width, height) = agent.spatial_priors.world_dimensions();
+        let demonstrated = self.spatial_priors(width, height);
+        for (own, shown) in agent.spatial_priors.cells_mut().zip(demonstrated.cells()) {
+            *own = combine(*own, *shown);
+        }
+        agent.habits = self.habits(width, height);
+    }
+}
+
+/// Both cells' observations taken together (Chan et al.'s parallel
+/// Welford update).
+fn combine(a: CellPrior, b: CellPrior) -> CellPrior {
+    if b.visits == 0 {
+        return a;
+    }
+    if a.visits == 0 {
+        return b;
+    }
+    let (na, nb) = (f64::from(a.visits), f64::from(b.visits));
+    let n = na + nb;
+    let delta = b.mean - a.mean;
+    CellPrior {
+        mean: a.mean + delta * nb / n,
+        m2: a.m2 + b.m2 + delta * delta * na * nb / n,
+        visits: a.visits.saturating_add(b.visits),
+    }
+}
@@ -1,7 +1,12 @@
 //! Gaussian belief state over hidden variables.
 //!
 //! Represents the agent's approximate posterior q(s) = N(μ, Σ) over hidden states.
+//! The circadian phase is wrapped to [0, 2π) and has its own variance (1 by
+//! default, so it adds nothing to `log_det()`), advanced and entrained apart
+//! from the gradient-descent update.
 
+use crate::simulation::numerics::{NonFinite, finite_all};
+use crate::simulation::params::MAX_PHASE_VARIANCE;
 use std::f64::consts::PI;
 
 /// Represents Gaussian beliefs: q(s) = N(μ, Σ)
@@ -24,6 +29,10 @@ pub struct BeliefMean {
     pub y: f64,
     /// Believed heading angle (radians)
     pub angle: f64,
+    /// Believed temperature at current location
+    pub temperature: f64,
+    /// Believed circadian phase of the day (0 = noon, π = midnight)
+    pub phase: f64,
 }
 
 /// Diagonal covariance matrix (assumes independence for computational efficiency).
@@ -38,6 +47,10 @@ pub struct BeliefCovariance {
     pub y_var: f64,
     /// Variance in angle belief
     pub angle_var: f64,
+    /// Variance in temperature belief
+    pub temperature_var: f64,
+    /// Variance in circadian phase belief
+    pub phase_var: f64,
 }
 
 impl Default for BeliefCovariance {
@@ -47,6 +60,8 @@ impl Default for BeliefCovariance {
             x_var: 1.0,
             y_var: 1.0,
             angle_var: 0.5,
+            temperature_var: 0.25,
+            phase_var: 1.0,
         }
     }
 }
@@ -61,6 +76,8 @@ impl BeliefState {
                 x,
                 y,
                 angle,
+                temperature: 0.5,
+                phase: 0.0,
             },
             covariance: BeliefCovariance::default(),
         }
@@ -74,10 +91,33 @@ impl BeliefState {
         self.mean.x += learning_rate * gradient.x;
         self.mean.y += learning_rate * gradient.y;
         self.mean.angle += learning_rate * gradient.angle;
+        self.mean.temperature += learning_rate * gradient.temperature;
+        self.mean.phase += learning_rate * gradient.phase;
 
         // Clamp to valid ranges
         self.mean.nutrient = self.mean.nutrient.clamp(0.0, 1.0);
+        self.mean.temperature = self.mean.temperature.clamp(0.0, 1.0);
         self.mean.angle = self.mean.angle.rem_euclid(2.0 * PI);
+        self.mean.phase = self.mean.phase.rem_euclid(2.0 * PI);
+    }
+
+    /// Free-runs the circadian phase by `step` radians, its variance
+    /// growing by `diffusion` up to `MAX_PHASE_VARIANCE`.
+    pub fn advance_phase(&mut self, step: f64, diffusion: f64) {
+        self.mean.phase = (self.mean.phase + step).rem_euclid(2.0 * PI);
+        self.covariance.phase_var = (self.covariance.phase_var + diffusion).min(MAX_PHASE_VARIANCE);
+    }
+
+    /// Corrects the circadian phase with a Gauss-Newton step.
+    ///
+    /// `gradient` is the descent direction of the circadian free energy and
+    /// `information` its curvature `π g'(φ)²`; the posterior precision is
+    /// `1/σ²_φ + information`, which scales the step and replaces the
+    /// variance.
+    pub fn entrain_phase(&mut self, gradient: f64, information: f64) {
+        let precision = 1.0 / self.covariance.phase_var.max(f64::EPSILON) + information.max(0.0);
+        self.mean.phase = (self.mean.phase + gradient / precision).rem_euclid(2.0 * PI);
+        self.covariance.phase_var = 1.0 / precision;
     }
 
     /// Synchronize position beliefs with actual position (proprioception).
@@ -93,6 +133,28 @@ impl BeliefState {
         self.covariance.angle_var = 0.01;
     }
 
+    /// Checks every mean and variance (strict numerical mode).
+    ///
+    /// # Errors
+    /// The first one that is NaN or infinite.
+    pub fn check_finite(&self) -> Result<(), NonFinite> {
+        let (mean, var) = (&self.mean, &self.covariance);
+        finite_all([
+            ("beliefs.mean.nutrient", mean.nutrient),
+            ("beliefs.mean.x", mean.x),
+            ("beliefs.mean.y", mean.y),
+            ("beliefs.mean.angle", mean.angle),
+            ("beliefs.mean.temperature", mean.temperature),
+            ("beliefs.mean.phase", mean.phase),
+            ("beliefs.covariance.nutrient_var", var.nutrient_var),
+            ("beliefs.covariance.x_var", var.x_var),
+            ("beliefs.covariance.y_var", var.y_var),
+            ("beliefs.covariance.angle_var", var.angle_var),
+            ("beliefs.covariance.temperature_var", var.temperature_var),
+            ("beliefs.covariance.phase_var", var.phase_var),
+        ])
+    }
+
     /// Total uncertainty (trace of covariance matrix).
     #[must_use]
     pub fn total_uncertainty(&self) -> f64 {
@@ -100,6 +162,8 @@ impl BeliefState {
             + self.covariance.x_var
             + self.covariance.y_var
             + self.covariance.angle_var
+            + self.covariance.temperature_var
+            + self.covariance.phase_var
     }
 
     /// Log determinant of covariance (for entropy computation).
@@ -112,8 +176,15 @@ impl BeliefState {
         let safe_x = self.covariance.x_var.max(1e-10);
         let safe_y = self.covariance.y_var.max(1e-10);
         let safe_angle = self.covariance.angle_var.max(1e-10);
+        let safe_temperature = self.covariance.temperature_var.max(1e-10);
+        let safe_phase = self.covariance.phase_var.max(1e-10);
 
-        safe_nutrient.ln() + safe_x.ln() + safe_y.ln() + safe_angle.ln()
+        safe_nutrient.ln()
+            + safe_x.ln()
+            + safe_y.ln()
+            + safe_angle.ln()
+            + safe_temperature.ln()
+            + safe_phase.ln()
     }
 
     /// Increase uncertainty (used for prediction into the future).
@@ -122,19 +193,23 @@ impl BeliefState {
         self.covariance.x_var *= factor;
         self.covariance.y_var *= factor;
         self.covariance.angle_var *= factor;
+        self.covariance.temperature_var *= factor;
 
         // Cap maximum uncertainty
         self.covariance.nutrient_var = self.covariance.nutrient_var.min(1.0);
         self.covariance.x_var = self.covariance.x_var.min(10.0);
         self.covariance.y_var = self.covariance.y_var.min(10.0);
         self.covariance.angle_var = self.covariance.angle_var.min(1.0);
+        self.covariance.temperature_var = self.covariance.temperature_var.min(1.0);
     }
 
     /// Decrease uncertainty after observation (used after belief update).
     pub fn decrease_uncertainty(&mut self, factor: f64) {
         self.covariance.nutrient_var *= factor;
+        self.covariance.temperature_var *= factor;
         // Keep minimum uncertainty
         self.covariance.nutrient_var = self.covariance.nutrient_var.max(0.001);
+        self.covariance.temperature_var = self.covariance.temperature_var.max(0.001);
     }
 }
 
@@ -148,6 +223,8 @@ impl BeliefMean {
             x: 0.0,
             y: 0.0,
             angle: 0.0,
+            temperature: 0.0,
+            phase: 0.0,
         }
     }
 }
@@ -174,6 +251,8 @@ mod tests {
             x: 0.0,
             y: 0.0,
             angle: 0.0,
+            temperature: 0.0,
+            phase: 0.0,
         };
         beliefs.update(&gradient, 0.1);
 
@@ -190,6 +269,8 @@ mod tests {
             x: 0.0,
             y: 0.0,
             angle: 100.0,
+            temperature: 0.0,
+            phase: 0.0,
         };
         beliefs.update(&gradient, 0.1);
 
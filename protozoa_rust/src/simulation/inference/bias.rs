@@ -0,0 +1,82 @@
+//! Online estimation of a left/right sensor bias from prediction errors.
+//!
+//! A receptor that reads systematically high on one side looks like a
+//! permanent gradient and makes the agent circle. The estimator integrates
+//! the antisymmetric part of the prediction errors (half the difference of
+//! left and right) into a bias per channel and subtracts it from later
+//! readings. A common offset of
+//! both channels cannot be told apart from a change in the field, so it is
+//! left to the beliefs.
+
+use crate::simulation::params::{SENSOR_BIAS_LEARNING_RATE, SENSOR_BIAS_MAX};
+
+/// Learns and removes a left/right offset between the chemoreceptors.
+#[derive(Clone, Debug)]
+pub struct BiasEstimator {
+    /// Estimated offset of the left channel
+    bias_l: f64,
+    /// Estimated offset of the right channel
+    bias_r: f64,
+    /// Integration step per update (0 = learning off)
+    rate: f64,
+}
+
+impl Default for BiasEstimator {
+    fn default() -> Self {
+        Self::new(SENSOR_BIAS_LEARNING_RATE)
+    }
+}
+
+impl BiasEstimator {
+    /// Creates an estimator with no bias learned yet.
+    #[must_use]
+    pub fn new(rate: f64) -> Self {
+        Self {
+            bias_l: 0.0,
+            bias_r: 0.0,
+            rate: rate.max(0.0),
+        }
+    }
+
+    /// Integrates the left/right mismatch of one pair of prediction errors
+    /// (errors of already corrected readings).
+    ///
+    /// ```text
+    /// b_L ← b_L + α·½(e_L − e_R)
+    /// b_R ← b_R − α·½(e_L − e_R)
+    /// ```
+    pub fn update(&mut self, error_l: f64, error_r: f64) {
+        let mismatch = 0.5 * (error_l - error_r);
+        self.bias_l = (self.bias_l + self.rate * mismatch).clamp(-SENSOR_BIAS_MAX, SENSOR_BIAS_MAX);
+        self.bias_r = (self.bias_r - self.rate * mismatch).clamp(-SENSOR_BIAS_MAX, SENSOR_BIAS_MAX);
+    }
+
+    /// Readings with the learned bias removed, clamped to [0, 1].
+    ///
+    /// Toxic-void readings (-1) pass through unchanged.
+    #[must_use]
+    pub fn correct(&self, obs_l: f64, obs_r: f64) -> (f64, f64) {
+        let correct = |obs: f64, bias: f64| {
+            if obs < 0.0 {
+                obs
+            } else {
+                (obs - bias).clamp(0.0, 1.0)
+            }
+        };
+        (correct(obs_l, self.bias_l), correct(obs_r, self.bias_r))
+    }
+
+    /// Learned offsets `(left, right)`.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and diagnostics
+    pub const fn bias(&self) -> (f64, f64) {
+        (self.bias_l, self.bias_r)
+    }
+
+    /// Forgets the learned bias.
+    #[allow(dead_code)] // Used by tests
+    pub fn reset(&mut self) {
+        self.bias_l = 0.0;
+        self.bias_r = 0.0;
+    }
+}
@@ -0,0 +1,162 @@
+//! Extended Kalman filter belief update.
+//!
+//! The default scheme (`InferenceScheme::Gradient`) moves the belief mean a
+//! step down the VFE gradient on each sample and shrinks the variances by a
+//! fixed factor. The EKF instead treats the belief `N(μ, Σ)` as the
+//! predicted state and updates it with the receptor readings through the
+//! same observation function and Jacobian (`GenerativeModel::receptor_prediction`,
+//! `receptor_jacobian`), the noise of receptor i the inverse of its
+//! precision. In information form, over s = (nutrient, angle):
+//!
+//! ```text
+//! Σ⁻ = γ Σ                                   predict (γ = UNCERTAINTY_GROWTH)
+//! Λ  = (Σ⁻)⁻¹ + Σᵢ πᵢ Hᵢᵀ Hᵢ                 Hᵢ = ∂gᵢ/∂s at μ
+//! μ' = μ + Λ⁻¹ Σᵢ πᵢ Hᵢᵀ (oᵢ − gᵢ(μ))
+//! Σ' = diag(Λ⁻¹)                             floored at EKF_VARIANCE_FLOOR
+//! ```
+//!
+//! and the temperature likewise with H = 1 and the thermoreceptor
+//! precisions. The step size and the variances come from the precisions
+//! rather than a learning rate, and the preferences stay out of perception
+//! (they enter only through EFE). Only the diagonal of Σ is kept, as in
+//! `BeliefCovariance`.
+//!
+//! The scheme is chosen per dish (`DishConfig::inference`, `inference =
+//! "ekf"` in TOML) and installed on each agent (`Protozoa::inference`).
+//! `InferenceScheme::update` runs either on one sample, so both can be fed
+//! the same recorded observations.
+
+use super::beliefs::BeliefState;
+use super::free_energy::{array_vfe_gradient, thermal_gradient};
+use super::generative_model::{GenerativeModel, ReceptorObservation};
+use crate::simulation::params::{EKF_VARIANCE_FLOOR, UNCERTAINTY_REDUCTION};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// How beliefs are updated on a sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InferenceScheme {
+    /// Gradient descent on VFE with a learning rate
+    #[default]
+    Gradient,
+    /// Extended Kalman filter
+    Ekf,
+}
+
+impl InferenceScheme {
+    pub const ALL: [Self; 2] = [Self::Gradient, Self::Ekf];
+
+    /// Lowercase name used in configs and the dashboard.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Gradient => "gradient",
+            Self::Ekf => "ekf",
+        }
+    }
+
+    /// The scheme named `name` (case-insensitive).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Updates `beliefs` on one sample: the receptor-array readings and the
+    /// thermoreceptor pair. `learning_rate` scales the gradient step (the
+    /// EKF's step comes from the precisions).
+    pub fn update(
+        self,
+        beliefs: &mut BeliefState,
+        observations: &[ReceptorObservation],
+        thermal: (f64, f64),
+        model: &GenerativeModel,
+        learning_rate: f64,
+    ) {
+        match self {
+            Self::Gradient => gradient_update(beliefs, observations, thermal, model, learning_rate),
+            Self::Ekf => {
+                beliefs.increase_uncertainty(model.uncertainty_growth());
+                ekf_update(beliefs, observations, model);
+                ekf_thermal_update(beliefs, thermal, model);
+            }
+        }
+    }
+}
+
+/// One step down the VFE gradient (chemical and thermal modalities), then
+/// a fixed reduction of the nutrient and temperature variances.
+fn gradient_update(
+    beliefs: &mut BeliefState,
+    observations: &[ReceptorObservation],
+    thermal: (f64, f64),
+    model: &GenerativeModel,
+    learning_rate: f64,
+) {
+    let mut gradient = array_vfe_gradient(observations, beliefs, model);
+    // Thermal step is scaled by the curvature Σπ (Gauss-Newton), so the belief
+    // moves toward the precision-weighted mean of readings and preference
+    // without overshooting when learned precisions grow large
+    let thermal_curvature = model.sensory_precision.thermal_left
+        + model.sensory_precision.thermal_right
+        + model.prior_precision.temperature;
+    gradient.temperature =
+        thermal_gradient(thermal, beliefs, model) / thermal_curvature.max(f64::EPSILON);
+    beliefs.update(&gradient, learning_rate);
+
+    // Reduce uncertainty after incorporating observation
+    beliefs.decrease_uncertainty(UNCERTAINTY_REDUCTION);
+}
+
+/// EKF measurement update of the nutrient and angle beliefs with the
+/// receptor readings.
+pub fn ekf_update(
+    beliefs: &mut BeliefState,
+    observations: &[ReceptorObservation],
+    model: &GenerativeModel,
+) {
+    let var = &beliefs.covariance;
+    // Information matrix Λ = [[a, b], [b, c]] and information vector r
+    let mut a = 1.0 / var.nutrient_var.max(EKF_VARIANCE_FLOOR);
+    let mut b = 0.0;
+    let mut c = 1.0 / var.angle_var.max(EKF_VARIANCE_FLOOR);
+    let (mut r_nutrient, mut r_angle) = (0.0, 0.0);
+    for obs in observations {
+        let precision = model.sensory_precision.chemoreceptor(obs.bearing);
+        let error = obs.value - model.receptor_prediction(&beliefs.mean, obs.bearing);
+        let (h_nutrient, h_angle) = model.receptor_jacobian(&beliefs.mean, obs.bearing);
+        a += precision * h_nutrient * h_nutrient;
+        b += precision * h_nutrient * h_angle;
+        c += precision * h_angle * h_angle;
+        r_nutrient += precision * h_nutrient * error;
+        r_angle += precision * h_angle * error;
+    }
+    let det = a * c - b * b;
+    if det <= 0.0 || !det.is_finite() {
+        return;
+    }
+    beliefs.mean.nutrient =
+        (beliefs.mean.nutrient + (c * r_nutrient - b * r_angle) / det).clamp(0.0, 1.0);
+    beliefs.mean.angle =
+        (beliefs.mean.angle + (a * r_angle - b * r_nutrient) / det).rem_euclid(2.0 * PI);
+    beliefs.covariance.nutrient_var = (c / det).max(EKF_VARIANCE_FLOOR);
+    beliefs.covariance.angle_var = (a / det).max(EKF_VARIANCE_FLOOR);
+}
+
+/// EKF measurement update of the temperature belief with the
+/// thermoreceptor pair (each reading the temperature itself).
+pub fn ekf_thermal_update(beliefs: &mut BeliefState, thermal: (f64, f64), model: &GenerativeModel) {
+    let (pi_l, pi_r) = (
+        model.sensory_precision.thermal_left,
+        model.sensory_precision.thermal_right,
+    );
+    let mean = beliefs.mean.temperature;
+    let information =
+        1.0 / beliefs.covariance.temperature_var.max(EKF_VARIANCE_FLOOR) + pi_l + pi_r;
+    let step = (pi_l * (thermal.0 - mean) + pi_r * (thermal.1 - mean)) / information;
+    beliefs.mean.temperature = (mean + step).clamp(0.0, 1.0);
+    beliefs.covariance.temperature_var = (1.0 / information).max(EKF_VARIANCE_FLOOR);
+}
@@ -13,9 +13,20 @@
 //! ```text
 //! G(π) = Risk + Ambiguity - Epistemic
 //! ```
+//!
+//! # Extra terms
+//!
+//! The optional modalities add their own surprise: sensed threat scent,
+//! quorum silence, social density and alignment, the circadian daylight
+//! prediction and interoceptive energy. The sensing, saccade and scan EFEs
+//! price a sample, a wider sweep or a scan against the energy it costs.
+//! Each function documents its formula.
 
 use super::beliefs::{BeliefMean, BeliefState};
-use super::generative_model::GenerativeModel;
+use super::generative_model::{GenerativeModel, ReceptorObservation};
+use crate::simulation::params::INTAKE_RATE;
+use crate::simulation::social::SocialObservation;
+use std::f64::consts::FRAC_PI_2;
 
 /// Compute Variational Free Energy (for perception).
 ///
@@ -23,25 +34,40 @@ use super::generative_model::GenerativeModel;
 ///
 /// Lower VFE means beliefs are more consistent with observations and priors.
 #[must_use]
+#[allow(dead_code)] // Pair form of the array function; used by tests
 pub fn variational_free_energy(
     observations: (f64, f64),
     beliefs: &BeliefState,
     model: &GenerativeModel,
 ) -> f64 {
-    let (obs_l, obs_r) = observations;
-    let (pred_l, pred_r) = model.observation_function(&beliefs.mean);
+    array_free_energy(&ReceptorObservation::pair(observations), beliefs, model)
+}
 
+/// Variational Free Energy of the readings of a chemoreceptor array.
+///
+/// `F = Σᵢ ½ πᵢ (oᵢ − gᵢ(μ))² + prior terms`, each receptor predicted at its
+/// bearing (`GenerativeModel::receptor_prediction`) and weighted by its
+/// channel's precision (`SensoryPrecision::chemoreceptor`).
+#[must_use]
+pub fn array_free_energy(
+    observations: &[ReceptorObservation],
+    beliefs: &BeliefState,
+    model: &GenerativeModel,
+) -> f64 {
     // Sensory prediction error (precision-weighted squared error)
     // (1/2) × Πₒ × (o - g(μ))²
-    let sensory_error_l = obs_l - pred_l;
-    let sensory_error_r = obs_r - pred_r;
-    let sensory_term = 0.5 * model.sensory_precision.left * sensory_error_l.powi(2)
-        + 0.5 * model.sensory_precision.right * sensory_error_r.powi(2);
+    let sensory_term: f64 = observations
+        .iter()
+        .map(|obs| {
+            let error = obs.value - model.receptor_prediction(&beliefs.mean, obs.bearing);
+            0.5 * model.sensory_precision.chemoreceptor(obs.bearing) * error.powi(2)
+        })
+        .sum();
 
     // Prior prediction error (precision-weighted squared error)
     // (1/2) × Πη × (μ - η)²
     let prior_error_nutrient = beliefs.mean.nutrient - model.prior_mean.nutrient;
-    let prior_term = 0.5 * model.prior_precision.nutrient * prior_error_nutrient.powi(2);
+    let prior_term = 0.5 * model.nutrient_precision() * prior_error_nutrient.powi(2);
 
     // Position prior errors (weak priors, so small contribution)
     let prior_x = 0.5 * model.prior_precision.x * (beliefs.mean.x - model.prior_mean.x).powi(2);
@@ -56,31 +82,42 @@ pub fn variational_free_energy(
 ///
 /// The negative gradient points toward lower free energy.
 #[must_use]
+#[allow(dead_code)] // Pair form of the array function; used by tests
 pub fn vfe_gradient(
     observations: (f64, f64),
     beliefs: &BeliefState,
     model: &GenerativeModel,
 ) -> BeliefMean {
-    let (obs_l, obs_r) = observations;
-    let (pred_l, pred_r) = model.observation_function(&beliefs.mean);
-    let jacobian = model.observation_jacobian(&beliefs.mean);
-
-    // Sensory prediction errors
-    let error_l = obs_l - pred_l;
-    let error_r = obs_r - pred_r;
+    array_vfe_gradient(&ReceptorObservation::pair(observations), beliefs, model)
+}
 
-    // Gradient w.r.t. nutrient belief
-    // ∂F/∂μ_nutrient = -Πₒ,L × (∂g_L/∂μ_nutrient) × error_L
-    //                 - Πₒ,R × (∂g_R/∂μ_nutrient) × error_R
+/// Gradient of `array_free_energy` w.r.t. beliefs (descent direction).
+///
+/// ∂F/∂μ = -Σᵢ πᵢ × ∂gᵢ/∂μ × (oᵢ - gᵢ(μ)) + Πη × (μ - η)
+#[must_use]
+pub fn array_vfe_gradient(
+    observations: &[ReceptorObservation],
+    beliefs: &BeliefState,
+    model: &GenerativeModel,
+) -> BeliefMean {
+    // Gradient w.r.t. nutrient and angle beliefs, one Jacobian row per receptor
+    // ∂F/∂μ_nutrient = -Σᵢ Πₒ,ᵢ × (∂gᵢ/∂μ_nutrient) × errorᵢ
     //                 + Πη,nutrient × (μ_nutrient - η_nutrient)
-    let d_nutrient_sensory = model.sensory_precision.left * jacobian.d_obs_d_nutrient.0 * error_l
-        + model.sensory_precision.right * jacobian.d_obs_d_nutrient.1 * error_r;
+    let (d_nutrient_sensory, d_angle_sensory) =
+        observations
+            .iter()
+            .fold((0.0, 0.0), |(nutrient, angle), obs| {
+                let error = obs.value - model.receptor_prediction(&beliefs.mean, obs.bearing);
+                let (d_obs_d_nutrient, d_obs_d_angle) =
+                    model.receptor_jacobian(&beliefs.mean, obs.bearing);
+                let precision = model.sensory_precision.chemoreceptor(obs.bearing);
+                (
+                    nutrient + precision * d_obs_d_nutrient * error,
+                    angle + precision * d_obs_d_angle * error,
+                )
+            });
     let d_nutrient_prior =
-        model.prior_precision.nutrient * (beliefs.mean.nutrient - model.prior_mean.nutrient);
-
-    // Gradient w.r.t. angle belief
-    let d_angle_sensory = model.sensory_precision.left * jacobian.d_obs_d_angle.0 * error_l
-        + model.sensory_precision.right * jacobian.d_obs_d_angle.1 * error_r;
+        model.nutrient_precision() * (beliefs.mean.nutrient - model.prior_mean.nutrient);
 
     // Return negative gradient (descent direction)
     BeliefMean {
@@ -88,6 +125,8 @@ pub fn vfe_gradient(
         x: 0.0, // Position updated from proprioception
         y: 0.0,
         angle: d_angle_sensory,
+        temperature: 0.0, // Thermal modality handled by `thermal_gradient`
+        phase: 0.0,       // Circadian phase handled by `circadian_gradient`
     }
 }
 
@@ -104,9 +143,7 @@ pub fn vfe_gradient(
 pub fn expected_free_energy(predicted_beliefs: &BeliefState, model: &GenerativeModel) -> f64 {
     // Risk: squared distance from preferred nutrient (scaled by prior precision)
     // This encodes "pragmatic value" - prefer states where I expect to be satisfied
-    let risk = 0.5
-        * model.prior_precision.nutrient
-        * (predicted_beliefs.mean.nutrient - model.prior_mean.nutrient).powi(2);
+    let risk = nutrient_risk(predicted_beliefs, model);
 
     // Ambiguity: expected sensory prediction error variance
     // Higher nutrient variance → more uncertain about what I'll observe
@@ -124,6 +161,264 @@ pub fn expected_free_energy(predicted_beliefs: &BeliefState, model: &GenerativeM
     risk + ambiguity - epistemic
 }
 
+/// Expected Free Energy with the interoceptive channel observing `energy`.
+///
+/// ```text
+/// G_E(π) = G(π) + (w(E) − 1) · Risk
+/// ```
+///
+/// The nutrient risk is weighted by `GenerativeModel::pragmatic_weight`, so
+/// a hungry agent trades ambiguity and epistemic value for food more readily
+/// than a sated one, without a threshold.
+#[must_use]
+pub fn interoceptive_efe(
+    predicted_beliefs: &BeliefState,
+    model: &GenerativeModel,
+    energy: f64,
+) -> f64 {
+    expected_free_energy(predicted_beliefs, model)
+        + (model.pragmatic_weight(energy) - 1.0) * nutrient_risk(predicted_beliefs, model)
+}
+
+/// Risk of the predicted nutrient belief: `½ π_η (μ_n − η_n)²`.
+fn nutrient_risk(predicted_beliefs: &BeliefState, model: &GenerativeModel) -> f64 {
+    0.5 * model.nutrient_precision()
+        * (predicted_beliefs.mean.nutrient - model.prior_mean.nutrient).powi(2)
+}
+
+/// Compute the interoceptive channel's contribution to Variational Free Energy.
+///
+/// `F_E = ½ π_E (E − η_E)²`: the agent observes its own energy E directly,
+/// so the channel carries no belief to update, only surprise at being far
+/// from the set-point.
+#[must_use]
+pub fn interoceptive_free_energy(energy: f64, model: &GenerativeModel) -> f64 {
+    0.5 * model.sensory_precision.interoceptive * (energy - model.prior_mean.energy).powi(2)
+}
+
+/// Expected Free Energy of sampling the chemoreceptors every `interval` ticks.
+///
+/// ```text
+/// G(K) = ½(π_L + π_R)·σ²·γ^(K−1) + π_η·c / (K·r)
+/// ```
+///
+/// The first term is the ambiguity of a nutrient belief left unobserved for
+/// K − 1 ticks (variance σ² growing by γ = `GenerativeModel::uncertainty_growth`
+/// per tick, `UNCERTAINTY_GROWTH` unless volatility scales it). The
+/// second is the sensing cost per tick, `cost_per_sample` c spread over K
+/// ticks, priced as the concentration the agent would have to eat to pay
+/// for it (c over the intake rate r) weighted by the homeostatic prior
+/// precision πη. With free sensing, sampling every tick is always best.
+#[must_use]
+pub fn sensing_efe(
+    beliefs: &BeliefState,
+    model: &GenerativeModel,
+    interval: u32,
+    cost_per_sample: f64,
+) -> f64 {
+    let interval = interval.max(1);
+    let drifted_var = beliefs.covariance.nutrient_var
+        * model
+            .uncertainty_growth()
+            .powi(i32::try_from(interval - 1).unwrap_or(i32::MAX));
+    let ambiguity =
+        0.5 * (model.sensory_precision.left + model.sensory_precision.right) * drifted_var;
+    let energy = model.nutrient_precision() * cost_per_sample / (f64::from(interval) * INTAKE_RATE);
+    ambiguity + energy
+}
+
+/// Expected Free Energy of sweeping the sensors `sweep` radians wider for
+/// one sample, relative to sampling at `sensor_angle`.
+///
+/// ```text
+/// IG(θ) = ½ ln(1 + 4σ²·sin²θ·π_L π_R / (π_L + π_R))
+/// G     = −[IG(θ + Δ) − IG(θ)] + π_η·c / r
+/// ```
+///
+/// IG is the information a sample carries about the lateral nutrient slope:
+/// the left/right difference reads the slope across the sensor baseline
+/// 2d·sin θ, and the slope's prior spread is the nutrient belief's σ over
+/// the sensor distance d, so d cancels. Spreads are capped at π/2 (sensors
+/// side by side). The sweep cost c is priced as in `sensing_efe`; a
+/// negative G means the extra information is worth the energy.
+#[must_use]
+pub fn saccade_efe(
+    beliefs: &BeliefState,
+    model: &GenerativeModel,
+    sensor_angle: f64,
+    sweep: f64,
+    cost: f64,
+) -> f64 {
+    let (pi_l, pi_r) = (model.sensory_precision.left, model.sensory_precision.right);
+    let noise_precision = pi_l * pi_r / (pi_l + pi_r).max(f64::EPSILON);
+    let info_gain = |angle: f64| {
+        let spread = angle.clamp(0.0, FRAC_PI_2).sin();
+        0.5 * (4.0 * beliefs.covariance.nutrient_var * spread.powi(2) * noise_precision).ln_1p()
+    };
+    let epistemic = info_gain(sensor_angle + sweep) - info_gain(sensor_angle);
+    -epistemic + model.nutrient_precision() * cost / INTAKE_RATE
+}
+
+/// Expected Free Energy of a scan: sampling `samples` sensor readings at
+/// orientations evenly around the body, relative to not scanning.
+///
+/// ```text
+/// IG = ½ ln(1 + σ²_β · n A² π̄ / 2)
+/// G  = −IG + π_η·c / r
+/// ```
+///
+/// A first-harmonic fit of n readings with noise precision π̄ (the mean of
+/// the chemoreceptors') over a gradient of amplitude A pins its bearing to a
+/// variance of 2 / (n A² π̄), so the information gained grows with the
+/// variance of the bearing belief. The scan cost c is priced as in
+/// `sensing_efe`; a negative G means the scan is worth its energy.
+#[must_use]
+pub fn scan_efe(
+    bearing_var: f64,
+    amplitude: f64,
+    samples: u32,
+    model: &GenerativeModel,
+    cost: f64,
+) -> f64 {
+    let noise_precision =
+        f64::midpoint(model.sensory_precision.left, model.sensory_precision.right);
+    let information = bearing_var * f64::from(samples) * amplitude.powi(2) * noise_precision / 2.0;
+    -0.5 * information.ln_1p() + model.nutrient_precision() * cost / INTAKE_RATE
+}
+
+/// Compute the risk of the current per-species readings under species preferences.
+///
+/// `R = Σₖ ½ πₖ (ōₖ - ηₖ)²` where ōₖ is the mean of the left/right readings of
+/// species k. Species without a reading or preference contribute nothing, so a
+/// model with no species preferences returns 0.
+#[must_use]
+pub fn species_risk(species_l: &[f64], species_r: &[f64], model: &GenerativeModel) -> f64 {
+    model
+        .species_preferences
+        .iter()
+        .zip(species_l.iter().zip(species_r))
+        .map(|(pref, (l, r))| 0.5 * pref.precision * (f64::midpoint(*l, *r) - pref.target).powi(2))
+        .sum()
+}
+
+/// Compute the risk of the current pH readings under the pH set-point.
+///
+/// `R_pH = ½ π_pH (ō - η_pH)²` where ō is the mean of the left/right pH
+/// readings. The set-point `η_pH` comes from the agent's morphology, so it can
+/// be shifted (allostasis) independently of the nutrient preference.
+#[must_use]
+pub fn ph_risk(ph_obs: (f64, f64), setpoint: f64, model: &GenerativeModel) -> f64 {
+    let error = f64::midpoint(ph_obs.0, ph_obs.1) - setpoint;
+    0.5 * model.prior_precision.ph * error.powi(2)
+}
+
+/// Compute the risk of the current threat readings (predator scent).
+///
+/// `R_threat = ½ π_threat (t_L² + t_R²)`: the preferred threat is zero, so
+/// any scent is surprising, most of all on both sides at once.
+#[must_use]
+pub fn threat_risk(threat_obs: (f64, f64), model: &GenerativeModel) -> f64 {
+    0.5 * model.prior_precision.threat * (threat_obs.0.powi(2) + threat_obs.1.powi(2))
+}
+
+/// Compute the risk of the current quorum signal readings.
+///
+/// `R_signal = ½ π_signal ((1 - s_L)² + (1 - s_R)²)`: the preferred signal
+/// is saturated (agents exploiting nearby), so silence is surprising in
+/// proportion to how much the model weights the signal.
+#[must_use]
+pub fn signal_risk(signal_obs: (f64, f64), model: &GenerativeModel) -> f64 {
+    0.5 * model.prior_precision.signal
+        * ((1.0 - signal_obs.0).powi(2) + (1.0 - signal_obs.1).powi(2))
+}
+
+/// Compute the risk of the current social observation for an agent heading
+/// `angle`.
+///
+/// `R_social = ½ π_social (ρ - ρ*)² + a (1 - cos(φ̄ - θ))`: the density ρ of
+/// neighbors against the preferred density ρ*, plus, with an alignment
+/// preference a, the misalignment with the neighbors' mean heading φ̄
+/// (zero without neighbors).
+#[must_use]
+pub fn social_risk(social: &SocialObservation, angle: f64, model: &GenerativeModel) -> f64 {
+    let preference = &model.social;
+    let density = 0.5 * preference.precision * (social.density() - preference.density).powi(2);
+    let alignment = social.heading.map_or(0.0, |heading| {
+        preference.alignment * (1.0 - (heading - angle).cos())
+    });
+    density + alignment
+}
+
+/// Compute the circadian clock's contribution to Variational Free Energy.
+///
+/// `F_φ = ½ π_D (D - g(μ_φ))²`: the daylight reading D against the daylight
+/// the believed phase predicts (`GenerativeModel::daylight_prediction`).
+#[must_use]
+pub fn circadian_free_energy(daylight: f64, beliefs: &BeliefState, model: &GenerativeModel) -> f64 {
+    let error = daylight - model.daylight_prediction(beliefs.mean.phase);
+    0.5 * model.sensory_precision.daylight * error.powi(2)
+}
+
+/// Descent direction of `circadian_free_energy` w.r.t. the phase belief.
+///
+/// `-∂F_φ/∂μ_φ = π_D g'(μ_φ) (D - g(μ_φ))`; its curvature (Gauss-Newton) is
+/// `π_D g'(μ_φ)²`.
+#[must_use]
+pub fn circadian_gradient(daylight: f64, beliefs: &BeliefState, model: &GenerativeModel) -> f64 {
+    let phase = beliefs.mean.phase;
+    let error = daylight - model.daylight_prediction(phase);
+    model.sensory_precision.daylight * model.daylight_slope(phase) * error
+}
+
+/// Compute the thermal modality's contribution to Variational Free Energy.
+///
+/// Both thermoreceptors directly observe the believed temperature `μ_T`:
+/// ```text
+/// F_T = ½ π_T,L (o_L - μ_T)² + ½ π_T,R (o_R - μ_T)² + ½ π_η,T (μ_T - η_T)²
+/// ```
+#[must_use]
+pub fn thermal_free_energy(
+    thermal_obs: (f64, f64),
+    beliefs: &BeliefState,
+    model: &GenerativeModel,
+) -> f64 {
+    let (error_l, error_r) = thermal_prediction_errors(thermal_obs, beliefs);
+    let prior_error = beliefs.mean.temperature - model.prior_mean.temperature;
+
+    0.5 * model.sensory_precision.thermal_left * error_l.powi(2)
+        + 0.5 * model.sensory_precision.thermal_right * error_r.powi(2)
+        + 0.5 * model.prior_precision.temperature * prior_error.powi(2)
+}
+
+/// Negative gradient of `thermal_free_energy` w.r.t. the temperature belief.
+///
+/// `-∂F_T/∂μ_T = π_T,L (o_L - μ_T) + π_T,R (o_R - μ_T) - π_η,T (μ_T - η_T)`
+///
+/// The learned thermoreceptor precisions decide how far the belief follows
+/// the thermal readings versus the preferred temperature.
+#[must_use]
+pub fn thermal_gradient(
+    thermal_obs: (f64, f64),
+    beliefs: &BeliefState,
+    model: &GenerativeModel,
+) -> f64 {
+    let (error_l, error_r) = thermal_prediction_errors(thermal_obs, beliefs);
+    let prior_error = beliefs.mean.temperature - model.prior_mean.temperature;
+
+    model.sensory_precision.thermal_left * error_l + model.sensory_precision.thermal_right * error_r
+        - model.prior_precision.temperature * prior_error
+}
+
+/// Thermal prediction errors `(o_L - μ_T, o_R - μ_T)` for precision learning.
+#[must_use]
+pub fn thermal_prediction_errors(thermal_obs: (f64, f64), beliefs: &BeliefState) -> (f64, f64) {
+    let (obs_l, obs_r) = thermal_obs;
+    (
+        obs_l - beliefs.mean.temperature,
+        obs_r - beliefs.mean.temperature,
+    )
+}
+
 /// Compute prediction errors for precision learning.
 ///
 /// Returns `(error_left, error_right)`.
@@ -187,9 +482,7 @@ mod tests {
 
         assert!(
             final_vfe < initial_vfe,
-            "VFE should decrease after gradient step: {} -> {}",
-            initial_vfe,
-            final_vfe
+            "VFE should decrease after gradient step: {initial_vfe} -> {final_vfe}"
         );
     }
 
@@ -1,11 +1,27 @@
 //! Generative model: p(o, s) = p(o|s) × p(s)
 //!
 //! Defines the agent's internal model of how observations arise from hidden states.
+//!
+//! A receptor at bearing b predicts the nutrient belief plus a lateral term
+//! k·sin(b·`SENSOR_ANGLE`)·sin(angle) and an offset, with the gain k = 0.2
+//! and no offset unless `likelihood` learns them; the pair forms are b = ±1.
+//! Interoception treats the agent's energy as one more observed channel with
+//! its own set-point and precision; `pragmatic_weight(energy)` grows with the
+//! deficit below the set-point and is 1 when sated.
 
 use super::beliefs::BeliefMean;
+use super::likelihood::Likelihood;
+use super::transition::TransitionModel;
+use super::volatility::Volatility;
+use crate::simulation::hunger::{Drive, Hunger};
+use crate::simulation::numerics::{NonFinite, finite_all};
 use crate::simulation::params::{
-    INITIAL_SENSORY_PRECISION, NUTRIENT_PRIOR_PRECISION, SENSOR_ANGLE, TARGET_CONCENTRATION,
+    CIRCADIAN_AMPLITUDE, DAYLIGHT_PRECISION, INITIAL_SENSORY_PRECISION, INTEROCEPTIVE_PRECISION,
+    INTEROCEPTIVE_SETPOINT, NUTRIENT_PRIOR_PRECISION, PH_PRIOR_PRECISION, PREFERRED_TEMPERATURE,
+    SIGNAL_PRIOR_PRECISION, TARGET_CONCENTRATION, TEMPERATURE_PRIOR_PRECISION,
+    THREAT_PRIOR_PRECISION, UNCERTAINTY_GROWTH,
 };
+use crate::simulation::social::SocialPreference;
 
 /// The agent's generative model of the world.
 ///
@@ -19,6 +35,34 @@ pub struct GenerativeModel {
     pub prior_precision: PriorPrecision,
     /// Sensory precision (inverse observation noise)
     pub sensory_precision: SensoryPrecision,
+    /// Per-species preferences (empty = single-nutrient model)
+    pub species_preferences: Vec<SpeciesPreference>,
+    /// Preferred company of other agents (off by default)
+    pub social: SocialPreference,
+    /// Modulation of the nutrient preference by the energy deficit (off by
+    /// default, see `hunger.rs`)
+    pub hunger: Hunger,
+    /// Estimate of how fast the world changes, scaling learning rates and
+    /// uncertainty growth (off by default, see `volatility.rs`)
+    pub volatility: Volatility,
+    /// Gain and offsets of the chemoreceptor likelihood, learned from
+    /// prediction errors (fixed by default, see `likelihood.rs`)
+    pub likelihood: Likelihood,
+    /// Gains and drift of the agent's own motion, learned from its moves
+    /// (the commanded motion by default, see `transition.rs`)
+    pub transition: TransitionModel,
+}
+
+/// Preference over one chemical species.
+///
+/// Like the nutrient prior, a preference is a prior: the target is the
+/// preferred concentration and the precision is how strongly it is held.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpeciesPreference {
+    /// Preferred concentration of this species
+    pub target: f64,
+    /// Strength of the preference (inverse variance)
+    pub precision: f64,
 }
 
 /// Prior means over hidden states.
@@ -36,6 +80,10 @@ pub struct PriorMean {
     /// Prior mean for heading (no preferred direction)
     #[allow(dead_code)] // Reserved for future heading preference
     pub angle: f64,
+    /// Preferred temperature (thermal preference!)
+    pub temperature: f64,
+    /// Preferred energy level (interoceptive set-point)
+    pub energy: f64,
 }
 
 /// Prior precision (inverse variance) for each hidden state.
@@ -52,6 +100,14 @@ pub struct PriorPrecision {
     /// Precision on heading (weak = any direction OK)
     #[allow(dead_code)] // Reserved for future heading precision
     pub angle: f64,
+    /// How strongly to prefer the target temperature
+    pub temperature: f64,
+    /// How strongly to hold the pH set-point (`Morphology::preferred_ph`)
+    pub ph: f64,
+    /// How strongly to prefer no threat (predator scent)
+    pub threat: f64,
+    /// How strongly to prefer sensing the quorum signal (0 = ignores it)
+    pub signal: f64,
 }
 
 /// Sensory precision (inverse observation variance).
@@ -64,6 +120,56 @@ pub struct SensoryPrecision {
     pub left: f64,
     /// Precision of right chemoreceptor
     pub right: f64,
+    /// Precision of left thermoreceptor
+    pub thermal_left: f64,
+    /// Precision of right thermoreceptor
+    pub thermal_right: f64,
+    /// Precision of the interoceptive channel (the agent's own energy)
+    pub interoceptive: f64,
+    /// Precision of the daylight reading that entrains the circadian clock
+    pub daylight: f64,
+}
+
+impl SensoryPrecision {
+    /// Precision of a chemoreceptor at `bearing`: the left channel's for
+    /// positive bearings, the right's for negative ones, their mean at 0.
+    #[must_use]
+    pub fn chemoreceptor(&self, bearing: f64) -> f64 {
+        if bearing > 0.0 {
+            self.left
+        } else if bearing < 0.0 {
+            self.right
+        } else {
+            f64::midpoint(self.left, self.right)
+        }
+    }
+}
+
+/// One chemoreceptor reading as the model sees it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReceptorObservation {
+    /// Reading after calibration and bias correction
+    pub value: f64,
+    /// Bearing the agent believes the reading comes from, as a share of
+    /// `SENSOR_ANGLE` (+1 = left channel, −1 = right)
+    pub bearing: f64,
+}
+
+impl ReceptorObservation {
+    /// The left/right pair `(obs_l, obs_r)` at bearings +1 and −1.
+    #[must_use]
+    pub const fn pair(observations: (f64, f64)) -> [Self; 2] {
+        [
+            Self {
+                value: observations.0,
+                bearing: 1.0,
+            },
+            Self {
+                value: observations.1,
+                bearing: -1.0,
+            },
+        ]
+    }
 }
 
 impl Default for GenerativeModel {
@@ -82,43 +188,63 @@ impl GenerativeModel {
                 x: 50.0,                        // Center of dish
                 y: 25.0,
                 angle: 0.0,
+                temperature: PREFERRED_TEMPERATURE,
+                energy: INTEROCEPTIVE_SETPOINT,
             },
             prior_precision: PriorPrecision {
                 nutrient: NUTRIENT_PRIOR_PRECISION, // Strong preference for target
                 x: 0.001,                           // Very weak position prior (free to roam)
                 y: 0.001,
                 angle: 0.001,
+                temperature: TEMPERATURE_PRIOR_PRECISION,
+                ph: PH_PRIOR_PRECISION,
+                threat: THREAT_PRIOR_PRECISION,
+                signal: SIGNAL_PRIOR_PRECISION,
             },
             sensory_precision: SensoryPrecision {
                 left: INITIAL_SENSORY_PRECISION,
                 right: INITIAL_SENSORY_PRECISION,
+                thermal_left: INITIAL_SENSORY_PRECISION,
+                thermal_right: INITIAL_SENSORY_PRECISION,
+                interoceptive: INTEROCEPTIVE_PRECISION,
+                daylight: DAYLIGHT_PRECISION,
             },
+            species_preferences: Vec::new(),
+            social: SocialPreference::default(),
+            hunger: Hunger::default(),
+            volatility: Volatility::default(),
+            likelihood: Likelihood::default(),
+            transition: TransitionModel::default(),
+        }
+    }
+
+    /// Create a model with one preference per species (index = species).
+    ///
+    /// Every preference uses `NUTRIENT_PRIOR_PRECISION`.
+    #[must_use]
+    #[allow(dead_code)] // Used by multi-species experiments and tests
+    pub fn with_species_targets(targets: &[f64]) -> Self {
+        Self {
+            species_preferences: targets
+                .iter()
+                .map(|&target| SpeciesPreference {
+                    target,
+                    precision: NUTRIENT_PRIOR_PRECISION,
+                })
+                .collect(),
+            ..Self::new()
         }
     }
 
     /// Observation function: g(s) - predicts observations from hidden states.
     ///
-    /// Returns `(predicted_left, predicted_right)` sensor readings.
+    /// Returns `(predicted_left, predicted_right)` sensor readings: the
+    /// receptor predictions at bearings +1 and −1.
     #[must_use]
-    #[allow(clippy::unused_self)] // Self reserved for future model parameters
     pub fn observation_function(&self, beliefs: &BeliefMean) -> (f64, f64) {
-        // Base prediction is believed nutrient concentration
-        let base = beliefs.nutrient;
-
-        // Sensor angle offset creates differential between left/right
-        // This models how sensors at different angles sample different parts
-        // of the gradient field
-        let gradient_factor = SENSOR_ANGLE.sin() * 0.2;
-
-        // Left sensor is offset by +SENSOR_ANGLE from heading
-        // Right sensor is offset by -SENSOR_ANGLE from heading
-        // In a gradient field, this creates a differential
-        let predicted_left = base + gradient_factor * beliefs.angle.sin();
-        let predicted_right = base - gradient_factor * beliefs.angle.sin();
-
         (
-            predicted_left.clamp(0.0, 1.0),
-            predicted_right.clamp(0.0, 1.0),
+            self.receptor_prediction(beliefs, 1.0),
+            self.receptor_prediction(beliefs, -1.0),
         )
     }
 
@@ -126,33 +252,195 @@ impl GenerativeModel {
     ///
     /// Used for computing gradients of free energy w.r.t. beliefs.
     #[must_use]
-    #[allow(clippy::unused_self)] // Self reserved for future model parameters
+    #[allow(dead_code)] // Pair form of `receptor_jacobian`; used by tests
     pub fn observation_jacobian(&self, beliefs: &BeliefMean) -> ObservationJacobian {
-        let gradient_factor = SENSOR_ANGLE.sin() * 0.2;
-
+        let (d_nutrient_l, d_angle_l) = self.receptor_jacobian(beliefs, 1.0);
+        let (d_nutrient_r, d_angle_r) = self.receptor_jacobian(beliefs, -1.0);
         ObservationJacobian {
-            // ∂g_L/∂nutrient = 1, ∂g_R/∂nutrient = 1
-            d_obs_d_nutrient: (1.0, 1.0),
-            // ∂g_L/∂angle = gradient_factor × cos(angle)
-            // ∂g_R/∂angle = -gradient_factor × cos(angle)
-            d_obs_d_angle: (
-                gradient_factor * beliefs.angle.cos(),
-                -gradient_factor * beliefs.angle.cos(),
-            ),
+            d_obs_d_nutrient: (d_nutrient_l, d_nutrient_r),
+            d_obs_d_angle: (d_angle_l, d_angle_r),
         }
     }
 
+    /// Observation function of one chemoreceptor at `bearing` (a share of
+    /// `SENSOR_ANGLE`, +1 = left, −1 = right):
+    ///
+    /// ```text
+    /// gᵢ(s) = nutrient + k·sin(bᵢ·SENSOR_ANGLE)·sin(angle) + βᵢ
+    /// ```
+    ///
+    /// Receptors at different bearings sample different parts of the
+    /// gradient field, which creates the differential between them. The
+    /// gain k (0.2) and offsets βᵢ (0) are `likelihood`, learned if enabled.
+    #[must_use]
+    pub fn receptor_prediction(&self, beliefs: &BeliefMean, bearing: f64) -> f64 {
+        let gradient_factor = self.likelihood.gradient_factor(bearing);
+        (beliefs.nutrient + gradient_factor * beliefs.angle.sin() + self.likelihood.bias(bearing))
+            .clamp(0.0, 1.0)
+    }
+
+    /// Row of the Jacobian for one chemoreceptor at `bearing`:
+    /// `(∂gᵢ/∂nutrient, ∂gᵢ/∂angle)`.
+    #[must_use]
+    pub fn receptor_jacobian(&self, beliefs: &BeliefMean, bearing: f64) -> (f64, f64) {
+        let gradient_factor = self.likelihood.gradient_factor(bearing);
+        (1.0, gradient_factor * beliefs.angle.cos())
+    }
+
+    /// Daylight predicted at circadian `phase`:
+    ///
+    /// ```text
+    /// g(φ) = 1 - Â (1 - cos φ) / 2
+    /// ```
+    ///
+    /// with Â = `CIRCADIAN_AMPLITUDE` the night dimming the model expects.
+    #[must_use]
+    #[allow(clippy::unused_self)] // Self reserved for a learned amplitude
+    pub fn daylight_prediction(&self, phase: f64) -> f64 {
+        1.0 - CIRCADIAN_AMPLITUDE * 0.5 * (1.0 - phase.cos())
+    }
+
+    /// Slope of `daylight_prediction`: `g'(φ) = -Â sin φ / 2`.
+    #[must_use]
+    #[allow(clippy::unused_self)] // Self reserved for a learned amplitude
+    pub fn daylight_slope(&self, phase: f64) -> f64 {
+        -CIRCADIAN_AMPLITUDE * 0.5 * phase.sin()
+    }
+
+    /// Checks the learned sensory precisions, the hunger level and the
+    /// learned state of the volatility, likelihood and transition model
+    /// (strict numerical mode).
+    ///
+    /// # Errors
+    /// The first one that is NaN or infinite.
+    pub fn check_finite(&self) -> Result<(), NonFinite> {
+        let precision = &self.sensory_precision;
+        finite_all([
+            ("sensory_precision.left", precision.left),
+            ("sensory_precision.right", precision.right),
+            ("sensory_precision.thermal_left", precision.thermal_left),
+            ("sensory_precision.thermal_right", precision.thermal_right),
+            ("hunger.level", self.hunger.level),
+            ("volatility.level_mean", self.volatility.level_mean),
+            ("volatility.log_volatility", self.volatility.log_volatility),
+            ("likelihood.gain", self.likelihood.gain),
+            ("likelihood.bias_left", self.likelihood.bias_left),
+            ("likelihood.bias_right", self.likelihood.bias_right),
+            ("transition.speed_gain", self.transition.speed_gain),
+            ("transition.turn_gain", self.transition.turn_gain),
+            ("transition.drift_x", self.transition.drift_x),
+            ("transition.drift_y", self.transition.drift_y),
+        ])
+    }
+
     /// Update sensory precision based on learned estimates.
     pub fn update_sensory_precision(&mut self, left: f64, right: f64) {
         self.sensory_precision.left = left;
         self.sensory_precision.right = right;
     }
+
+    /// Records the interoceptive reading `energy` as the hunger level the
+    /// nutrient preference is modulated by.
+    pub fn observe_energy(&mut self, energy: f64) {
+        self.hunger.observe(energy, self.prior_mean.energy);
+    }
+
+    /// Records the mean chemoreceptor reading `reading` in the volatility
+    /// estimate.
+    pub fn observe_volatility(&mut self, reading: f64) {
+        self.volatility.observe(reading);
+    }
+
+    /// One step of the likelihood parameters on the chemoreceptor prediction
+    /// errors `errors` at believed heading `angle`, weighted by the current
+    /// sensory precisions.
+    pub fn learn_likelihood(&mut self, errors: (f64, f64), angle: f64) {
+        let precisions = (self.sensory_precision.left, self.sensory_precision.right);
+        self.likelihood.learn(errors, precisions, angle);
+    }
+
+    /// Factor on the belief and precision learning rates at the current
+    /// volatility (1 in a stationary world).
+    #[must_use]
+    pub fn learning_rate_scale(&self) -> f64 {
+        self.volatility.factor()
+    }
+
+    /// Per-tick growth of belief variance without a sample:
+    /// `UNCERTAINTY_GROWTH`, its excess scaled by the volatility.
+    #[must_use]
+    pub fn uncertainty_growth(&self) -> f64 {
+        self.volatility.growth(UNCERTAINTY_GROWTH)
+    }
+
+    /// Precision of the nutrient preference at the current hunger level:
+    /// `prior_precision.nutrient` scaled by `Hunger::factor`.
+    #[must_use]
+    pub fn nutrient_precision(&self) -> f64 {
+        if self.hunger.is_off() {
+            self.prior_precision.nutrient
+        } else {
+            self.prior_precision.nutrient * self.hunger.factor()
+        }
+    }
+
+    /// Weights of food and information in the planner's value.
+    #[must_use]
+    pub fn drive(&self) -> Drive {
+        self.hunger.drive()
+    }
+
+    /// Pull of a remembered food patch at `energy` (see `Hunger::urge`).
+    #[must_use]
+    pub fn landmark_urge(&self, energy: f64) -> f64 {
+        self.hunger.urge(energy, self.prior_mean.energy)
+    }
+
+    /// Weight of the nutrient risk in EFE given the observed `energy`.
+    ///
+    /// ```text
+    /// w(E) = 1 + π_E · max(0, η_E − E)
+    /// ```
+    ///
+    /// Eating moves energy toward the set-point, so the expected
+    /// interoceptive surprise falls fastest where the nutrient preference is
+    /// met; linearized, the channel's error times its precision adds to the
+    /// weight of the nutrient risk. A sated agent weighs it as before, and
+    /// the weight grows smoothly with hunger.
+    #[must_use]
+    pub fn pragmatic_weight(&self, energy: f64) -> f64 {
+        1.0 + self.sensory_precision.interoceptive * (self.prior_mean.energy - energy).max(0.0)
+    }
+
+    /// Install a prior preference over position (an exogenous goal).
+    pub fn set_position_preference(&mut self, x: f64, y: f64, precision: f64) {
+        self.prior_mean.x = x;
+        self.prior_mean.y = y;
+        self.prior_precision.x = precision;
+        self.prior_precision.y = precision;
+    }
+
+    /// Restore the default weak position prior at the dish center.
+    pub fn clear_position_preference(&mut self) {
+        let defaults = Self::new();
+        self.prior_mean.x = defaults.prior_mean.x;
+        self.prior_mean.y = defaults.prior_mean.y;
+        self.prior_precision.x = defaults.prior_precision.x;
+        self.prior_precision.y = defaults.prior_precision.y;
+    }
+
+    /// Update thermoreceptor precision based on learned estimates.
+    pub fn update_thermal_precision(&mut self, left: f64, right: f64) {
+        self.sensory_precision.thermal_left = left;
+        self.sensory_precision.thermal_right = right;
+    }
 }
 
 /// Jacobian of the observation function.
 ///
 /// Contains partial derivatives of each observation w.r.t. each belief.
 #[derive(Clone, Copy, Debug)]
+#[allow(dead_code)] // Returned by `observation_jacobian`
 pub struct ObservationJacobian {
     /// `(∂g_L/∂nutrient, ∂g_R/∂nutrient)`
     pub d_obs_d_nutrient: (f64, f64),
@@ -178,12 +466,14 @@ mod tests {
             x: 50.0,
             y: 25.0,
             angle: 1.0,
+            temperature: 0.5,
+            phase: 0.0,
         };
 
         let (pred_l, pred_r) = model.observation_function(&beliefs);
 
-        assert!(pred_l >= 0.0 && pred_l <= 1.0);
-        assert!(pred_r >= 0.0 && pred_r <= 1.0);
+        assert!((0.0..=1.0).contains(&pred_l));
+        assert!((0.0..=1.0).contains(&pred_r));
     }
 
     #[test]
@@ -194,6 +484,8 @@ mod tests {
             x: 50.0,
             y: 25.0,
             angle: 0.0,
+            temperature: 0.5,
+            phase: 0.0,
         };
 
         let (pred_l, pred_r) = model.observation_function(&beliefs);
@@ -210,6 +502,8 @@ mod tests {
             x: 50.0,
             y: 25.0,
             angle: 0.5,
+            temperature: 0.5,
+            phase: 0.0,
         };
 
         let jacobian = model.observation_jacobian(&beliefs);
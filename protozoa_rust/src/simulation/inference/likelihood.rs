@@ -0,0 +1,124 @@
+//! Learned parameters of the chemoreceptor likelihood p(o|s).
+//!
+//! The observation function predicts a receptor at bearing bᵢ (a share of
+//! `SENSOR_ANGLE`) to read the believed nutrient plus a lateral term whose
+//! slope, the gradient factor, was a fixed 0.2·sin(bᵢ·`SENSOR_ANGLE`):
+//!
+//! ```text
+//! gᵢ(s) = nutrient + k·sin(bᵢ·SENSOR_ANGLE)·sin(angle) + βᵢ
+//! ```
+//!
+//! `Likelihood` makes the gain k and the offsets βᵢ (one per side) model
+//! parameters, learned by gradient descent on the free energy of the
+//! sampled prediction errors eᵢ = oᵢ − gᵢ, each weighted by its precision:
+//!
+//! ```text
+//! k  ← k + η Σᵢ πᵢ eᵢ sin(bᵢ·SENSOR_ANGLE)·sin(angle)
+//! Δ  = η (π_L e_L − π_R e_R) / 2,   β_L ← β_L + Δ,   β_R ← β_R − Δ
+//! ```
+//!
+//! Only the difference between the offsets is learned: a shared offset
+//! would explain the same errors as the nutrient belief and drift with it.
+//! The gain stays within [0, `LIKELIHOOD_MAX_GAIN`] and each offset within
+//! ±`LIKELIHOOD_MAX_BIAS`.
+//!
+//! The rate η is `GenerativeModel::likelihood` (`--learn-likelihood`); at
+//! 0 (the default) nothing is learned and g is the fixed function.
+
+use crate::simulation::params::{
+    LIKELIHOOD_GAIN, LIKELIHOOD_LEARNING_RATE, LIKELIHOOD_MAX_BIAS, LIKELIHOOD_MAX_GAIN,
+    SENSOR_ANGLE,
+};
+
+/// Learnable gain and offsets of the chemoreceptor observation function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Likelihood {
+    /// Learning rate η (0 = fixed parameters)
+    pub rate: f64,
+    /// Gain k of the lateral term
+    pub gain: f64,
+    /// Offsets β of the left and right channels
+    pub bias_left: f64,
+    pub bias_right: f64,
+    /// Learning steps taken
+    pub updates: u64,
+}
+
+impl Default for Likelihood {
+    fn default() -> Self {
+        Self::new(LIKELIHOOD_LEARNING_RATE)
+    }
+}
+
+impl Likelihood {
+    /// Parameters learned at rate `rate`, starting from the fixed model.
+    #[must_use]
+    pub const fn new(rate: f64) -> Self {
+        Self {
+            rate,
+            gain: LIKELIHOOD_GAIN,
+            bias_left: 0.0,
+            bias_right: 0.0,
+            updates: 0,
+        }
+    }
+
+    /// Returns true if the parameters are not learned.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.rate <= 0.0
+    }
+
+    /// Checks the rate is finite and not negative.
+    ///
+    /// # Errors
+    /// A message naming the bad rate.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rate.is_finite() && self.rate >= 0.0 {
+            Ok(())
+        } else {
+            Err(format!(
+                "likelihood learning rate must be finite and >= 0: {}",
+                self.rate
+            ))
+        }
+    }
+
+    /// Slope of the lateral term for a receptor at `bearing`:
+    /// k·sin(bᵢ·`SENSOR_ANGLE`).
+    #[must_use]
+    pub fn gradient_factor(&self, bearing: f64) -> f64 {
+        (bearing * SENSOR_ANGLE).sin() * self.gain
+    }
+
+    /// Offset of a receptor at `bearing`: the left channel's for positive
+    /// bearings, the right's for negative ones, their mean at 0.
+    #[must_use]
+    pub fn bias(&self, bearing: f64) -> f64 {
+        if bearing > 0.0 {
+            self.bias_left
+        } else if bearing < 0.0 {
+            self.bias_right
+        } else {
+            f64::midpoint(self.bias_left, self.bias_right)
+        }
+    }
+
+    /// One learning step on the prediction errors `(e_l, e_r)` of the
+    /// left and right channels, weighted by their precisions `(π_l, π_r)`,
+    /// with the believed heading `angle`.
+    pub fn learn(&mut self, errors: (f64, f64), precisions: (f64, f64), angle: f64) {
+        if self.is_off() {
+            return;
+        }
+        let (weighted_l, weighted_r) = (precisions.0 * errors.0, precisions.1 * errors.1);
+        let lateral = SENSOR_ANGLE.sin() * angle.sin();
+        self.gain = (self.gain + self.rate * (weighted_l - weighted_r) * lateral)
+            .clamp(0.0, LIKELIHOOD_MAX_GAIN);
+        let shift = self.rate * (weighted_l - weighted_r) * 0.5;
+        self.bias_left = (self.bias_left + shift).clamp(-LIKELIHOOD_MAX_BIAS, LIKELIHOOD_MAX_BIAS);
+        self.bias_right =
+            (self.bias_right - shift).clamp(-LIKELIHOOD_MAX_BIAS, LIKELIHOOD_MAX_BIAS);
+        self.updates += 1;
+    }
+}
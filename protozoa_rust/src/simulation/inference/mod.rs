@@ -15,21 +15,46 @@
 //! dμ/dt = -∂F/∂μ
 //! ```
 //!
+//! or, per `InferenceScheme`, an extended Kalman filter on the same
+//! observation model (see `ekf.rs`).
+//!
 //! Expected Free Energy for planning:
 //! ```text
 //! G(π) = Risk + Ambiguity - Epistemic
 //! ```
 
 mod beliefs;
+mod bias;
+mod ekf;
 mod free_energy;
 mod generative_model;
+mod likelihood;
+mod particles;
 mod precision;
+mod transition;
+mod volatility;
 
 #[allow(unused_imports)] // Types exported for future use and API completeness
 pub use beliefs::{BeliefCovariance, BeliefMean, BeliefState};
+pub use bias::BiasEstimator;
+#[allow(unused_imports)] // Measurement updates used by tests
+pub use ekf::{InferenceScheme, ekf_thermal_update, ekf_update};
+#[allow(unused_imports)] // Pair forms used by tests
 pub use free_energy::{
-    expected_free_energy, prediction_errors, variational_free_energy, vfe_gradient,
+    array_free_energy, array_vfe_gradient, circadian_free_energy, circadian_gradient,
+    expected_free_energy, interoceptive_efe, interoceptive_free_energy, ph_risk, prediction_errors,
+    saccade_efe, scan_efe, sensing_efe, signal_risk, social_risk, species_risk,
+    thermal_free_energy, thermal_gradient, thermal_prediction_errors, threat_risk,
+    variational_free_energy, vfe_gradient,
 };
 #[allow(unused_imports)] // Types exported for future use and API completeness
-pub use generative_model::{GenerativeModel, ObservationJacobian, PriorMean, SensoryPrecision};
+pub use generative_model::{
+    GenerativeModel, ObservationJacobian, PriorMean, ReceptorObservation, SensoryPrecision,
+    SpeciesPreference,
+};
+pub use likelihood::Likelihood;
+#[allow(unused_imports)] // Particle type used by tests
+pub use particles::{Particle, ParticleBelief, PointReading};
 pub use precision::PrecisionEstimator;
+pub use transition::{MotionSample, TransitionModel};
+pub use volatility::Volatility;
@@ -0,0 +1,284 @@
+//! Particle belief over the location of a nutrient patch.
+//!
+//! `BeliefState` is a single Gaussian, so it cannot hold two places the food
+//! might be at once: a symmetric set of readings pulls its mean to the
+//! midpoint, where nothing is. A `ParticleBelief` keeps a weighted cloud of
+//! hypotheses instead, each particle a patch center (x, y) predicting the
+//! reading at p as a Gaussian profile of width r = `PARTICLE_PATCH_RADIUS`:
+//!
+//! ```text
+//! g(p | x) = exp(−‖p − x‖² / 2r²)
+//! wᵢ ← wᵢ Πₖ exp(−(oₖ − g(pₖ | xᵢ))² / 2σ²)     σ = PARTICLE_OBSERVATION_NOISE
+//! ESS = 1 / Σ wᵢ²                                (weights normalized)
+//! ```
+//!
+//! Once the effective sample size drops below `PARTICLE_RESAMPLE_THRESHOLD`
+//! of the particle count, the cloud is resampled systematically and each
+//! copy jittered by `PARTICLE_JITTER`, so it keeps covering its modes
+//! instead of collapsing onto a few particles.
+//!
+//! The agent tracks one with `--particles N` (`Protozoa::particles`),
+//! updating it on each sample next to its Gaussian beliefs, which it leaves
+//! untouched; the dashboard draws the cloud in its own panel.
+
+use crate::simulation::numerics::{NonFinite, finite};
+use crate::simulation::params::{
+    MAX_PARTICLES, PARTICLE_JITTER, PARTICLE_OBSERVATION_NOISE, PARTICLE_PATCH_RADIUS,
+    PARTICLE_RESAMPLE_THRESHOLD,
+};
+use crate::simulation::streams::standard_normal;
+use rand::Rng;
+
+/// One hypothesized patch center and its weight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Particle {
+    pub x: f64,
+    pub y: f64,
+    pub weight: f64,
+}
+
+/// A reading taken at a point of the dish.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointReading {
+    pub x: f64,
+    pub y: f64,
+    pub value: f64,
+}
+
+/// Weighted particle cloud over the patch location.
+#[derive(Clone, Debug)]
+pub struct ParticleBelief {
+    particles: Vec<Particle>,
+    /// Dish extent the particles stay within
+    width: f64,
+    height: f64,
+    /// Width of the patch each particle predicts
+    pub patch_radius: f64,
+    /// Standard deviation of a reading about its prediction
+    pub noise: f64,
+    /// Resampling steps so far
+    resamples: u64,
+}
+
+impl ParticleBelief {
+    /// `count` particles (1 to `MAX_PARTICLES`) spread uniformly over a
+    /// `width` × `height` dish, equally weighted.
+    pub fn uniform(count: usize, width: f64, height: f64, rng: &mut impl Rng) -> Self {
+        let count = count.clamp(1, MAX_PARTICLES);
+        let particles = (0..count)
+            .map(|_| Particle {
+                x: rng.random::<f64>() * width,
+                y: rng.random::<f64>() * height,
+                weight: 0.0,
+            })
+            .collect();
+        Self::from_particles(particles, width, height)
+    }
+
+    /// A belief holding `particles` (at least one), their weights
+    /// normalized (uniform if they sum to nothing).
+    ///
+    /// # Panics
+    /// If `particles` is empty.
+    #[must_use]
+    pub fn from_particles(particles: Vec<Particle>, width: f64, height: f64) -> Self {
+        assert!(!particles.is_empty(), "a particle belief needs particles");
+        let mut belief = Self {
+            particles,
+            width,
+            height,
+            patch_radius: PARTICLE_PATCH_RADIUS,
+            noise: PARTICLE_OBSERVATION_NOISE,
+            resamples: 0,
+        };
+        belief.normalize();
+        belief
+    }
+
+    #[must_use]
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Always false: a belief holds at least one particle.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Dish extent `(width, height)` the particles stay within.
+    #[must_use]
+    pub const fn bounds(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    /// Resampling steps so far.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub const fn resamples(&self) -> u64 {
+        self.resamples
+    }
+
+    /// Reading at (x, y) if the patch were centered on `particle`.
+    #[must_use]
+    pub fn predicted(&self, particle: &Particle, x: f64, y: f64) -> f64 {
+        let d2 = (x - particle.x).powi(2) + (y - particle.y).powi(2);
+        (-d2 / (2.0 * self.patch_radius * self.patch_radius)).exp()
+    }
+
+    /// Likelihood of `readings` under `particle`. Readings outside the dish
+    /// or inside walls (negative) say nothing about the patch.
+    #[must_use]
+    pub fn likelihood(&self, particle: &Particle, readings: &[PointReading]) -> f64 {
+        let error: f64 = readings
+            .iter()
+            .filter(|r| r.value >= 0.0)
+            .map(|r| (r.value - self.predicted(particle, r.x, r.y)).powi(2))
+            .sum();
+        (-error / (2.0 * self.noise * self.noise)).exp()
+    }
+
+    /// Weighs every particle by the likelihood of `readings`. If no particle
+    /// explains them at all, the weights start over uniform rather than
+    /// vanish.
+    pub fn reweight(&mut self, readings: &[PointReading]) {
+        let likelihoods: Vec<f64> = self
+            .particles
+            .iter()
+            .map(|p| self.likelihood(p, readings))
+            .collect();
+        for (particle, likelihood) in self.particles.iter_mut().zip(likelihoods) {
+            particle.weight *= likelihood;
+        }
+        self.normalize();
+    }
+
+    /// Reweights on `readings` and resamples if the effective sample size
+    /// has dropped too far. Returns whether it resampled.
+    pub fn observe(&mut self, readings: &[PointReading], rng: &mut impl Rng) -> bool {
+        self.reweight(readings);
+        #[allow(clippy::cast_precision_loss)]
+        let threshold = PARTICLE_RESAMPLE_THRESHOLD * self.len() as f64;
+        let degenerate = self.effective_sample_size() < threshold;
+        if degenerate {
+            self.resample(rng);
+        }
+        degenerate
+    }
+
+    /// Effective sample size 1 / Σ wᵢ²: the particle count when the weights
+    /// are even, 1 when one particle holds them all.
+    #[must_use]
+    pub fn effective_sample_size(&self) -> f64 {
+        1.0 / self
+            .particles
+            .iter()
+            .map(|p| p.weight * p.weight)
+            .sum::<f64>()
+    }
+
+    /// Stretches the cloud with its dish by (sx, sy): particle positions and
+    /// the bounds they stay within scale together.
+    pub fn rescale(&mut self, sx: f64, sy: f64) {
+        for particle in &mut self.particles {
+            particle.x *= sx;
+            particle.y *= sy;
+        }
+        self.width *= sx;
+        self.height *= sy;
+    }
+
+    /// Systematic resampling: N copies drawn at evenly spaced points of the
+    /// cumulative weights from one random offset, each jittered by
+    /// `PARTICLE_JITTER` within the dish and weighted 1/N.
+    pub fn resample(&mut self, rng: &mut impl Rng) {
+        let n = self.len();
+        #[allow(clippy::cast_precision_loss)]
+        let step = 1.0 / n as f64;
+        let mut target = rng.random::<f64>() * step;
+        let mut cumulative = self.particles[0].weight;
+        let mut source = 0;
+        let mut resampled = Vec::with_capacity(n);
+        for _ in 0..n {
+            while target > cumulative && source + 1 < n {
+                source += 1;
+                cumulative += self.particles[source].weight;
+            }
+            let parent = self.particles[source];
+            resampled.push(Particle {
+                x: (parent.x + PARTICLE_JITTER * standard_normal(rng)).clamp(0.0, self.width),
+                y: (parent.y + PARTICLE_JITTER * standard_normal(rng)).clamp(0.0, self.height),
+                weight: step,
+            });
+            target += step;
+        }
+        self.particles = resampled;
+        self.resamples += 1;
+    }
+
+    /// Weighted mean patch location. With several modes it lies between
+    /// them, which is what `weight_within` is for.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn mean(&self) -> (f64, f64) {
+        self.particles.iter().fold((0.0, 0.0), |(x, y), p| {
+            (x + p.weight * p.x, y + p.weight * p.y)
+        })
+    }
+
+    /// Root mean square distance of the particles from their mean.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn spread(&self) -> f64 {
+        let (mx, my) = self.mean();
+        self.particles
+            .iter()
+            .map(|p| p.weight * ((p.x - mx).powi(2) + (p.y - my).powi(2)))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Weight of the particles within `radius` of (x, y).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn weight_within(&self, x: f64, y: f64, radius: f64) -> f64 {
+        self.particles
+            .iter()
+            .filter(|p| (p.x - x).hypot(p.y - y) <= radius)
+            .map(|p| p.weight)
+            .sum()
+    }
+
+    /// The first non-finite particle coordinate or weight.
+    ///
+    /// # Errors
+    /// `NonFinite` naming the offending field.
+    pub fn check_finite(&self) -> Result<(), NonFinite> {
+        self.particles.iter().try_for_each(|p| {
+            finite(p.x, "particles.x")?;
+            finite(p.y, "particles.y")?;
+            finite(p.weight, "particles.weight").map(|_| ())
+        })
+    }
+
+    /// Scales the weights to sum to 1, or evens them out if they sum to
+    /// nothing (or to something non-finite).
+    fn normalize(&mut self) {
+        let total: f64 = self.particles.iter().map(|p| p.weight).sum();
+        #[allow(clippy::cast_precision_loss)]
+        let even = 1.0 / self.len() as f64;
+        for particle in &mut self.particles {
+            particle.weight = if total > 0.0 && total.is_finite() {
+                particle.weight / total
+            } else {
+                even
+            };
+        }
+    }
+}
@@ -41,12 +41,20 @@ impl PrecisionEstimator {
     /// Update variance estimates with new prediction errors.
     ///
     /// Uses exponential moving average: σ² ← (1-α)σ² + α×e²
+    #[allow(dead_code)] // Used by tests
     pub fn update(&mut self, error_l: f64, error_r: f64) {
+        self.update_scaled(error_l, error_r, 1.0);
+    }
+
+    /// Like `update`, with the rate α scaled by `scale` (capped at 1): a
+    /// volatile world is tracked faster (see `Volatility`).
+    pub fn update_scaled(&mut self, error_l: f64, error_r: f64, scale: f64) {
         self.count = self.count.saturating_add(1);
+        let alpha = (self.alpha * scale).min(1.0);
 
         // Exponential moving average of squared errors
-        self.variance_l = (1.0 - self.alpha) * self.variance_l + self.alpha * error_l.powi(2);
-        self.variance_r = (1.0 - self.alpha) * self.variance_r + self.alpha * error_r.powi(2);
+        self.variance_l = (1.0 - alpha) * self.variance_l + alpha * error_l.powi(2);
+        self.variance_r = (1.0 - alpha) * self.variance_r + alpha * error_r.powi(2);
 
         // Ensure minimum variance (numerical stability)
         self.variance_l = self.variance_l.max(0.001);
@@ -111,9 +119,7 @@ mod tests {
         let final_precision = estimator.precision_left();
         assert!(
             final_precision > initial_precision,
-            "Precision should increase with low errors: {} -> {}",
-            initial_precision,
-            final_precision
+            "Precision should increase with low errors: {initial_precision} -> {final_precision}"
         );
     }
 
@@ -0,0 +1,150 @@
+//! Learned transition model: how the agent's own body moves.
+//!
+//! The planner predicts a command to turn the heading by exactly the turn
+//! commanded and move the body the commanded speed along it. Drag, motor
+//! bias, the vigor cap and currents make the body do otherwise.
+//! `TransitionModel` learns the effective motion from the discrepancy
+//! between that prediction and the pose the agent proprioceives after
+//! moving:
+//!
+//! ```text
+//! Δθ̂ = g·u                      turn gain g on the commanded turn u
+//! Δp̂ = s·v·(cos θ', sin θ') + d  speed gain s on the commanded speed v,
+//!                                drift d per tick, θ' the new heading
+//! ```
+//!
+//! Each tick takes a normalized least-mean-squares step on the errors
+//! `e_θ` = Δθ − Δθ̂ (wrapped) and `e_p` = Δp − Δp̂, with ε =
+//! `TRANSITION_REGULARIZER`:
+//!
+//! ```text
+//! g ← g + η e_θ u / (u² + ε)
+//! s ← s + η (e_p · (cos θ', sin θ')) v / (v² + ε)
+//! d ← d + η e_p
+//! ```
+//!
+//! The gains stay within [0, `TRANSITION_MAX_GAIN`] and the drift within
+//! `TRANSITION_MAX_DRIFT` per tick. The model predicts the body's move in
+//! `predict_beliefs_after_action` and in the MCTS rollouts (`AgentState`).
+//!
+//! The rate η is `GenerativeModel::transition` (`--learn-transition`); at
+//! 0 (the default) nothing is learned and the prediction is the command.
+
+use crate::simulation::params::{
+    TRANSITION_LEARNING_RATE, TRANSITION_MAX_DRIFT, TRANSITION_MAX_GAIN, TRANSITION_REGULARIZER,
+};
+use std::f64::consts::PI;
+
+/// A move the body made, as the agent proprioceives it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MotionSample {
+    /// Commanded turn (radians) and speed
+    pub turn: f64,
+    pub speed: f64,
+    /// Heading before and after the move
+    pub heading: f64,
+    pub new_heading: f64,
+    /// Displacement of the body over the tick
+    pub dx: f64,
+    pub dy: f64,
+}
+
+/// Learnable gains and drift of the agent's motion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransitionModel {
+    /// Learning rate η (0 = the command is the motion)
+    pub rate: f64,
+    /// Gain s on the commanded speed
+    pub speed_gain: f64,
+    /// Gain g on the commanded turn
+    pub turn_gain: f64,
+    /// Drift d per tick
+    pub drift_x: f64,
+    pub drift_y: f64,
+    /// Learning steps taken
+    pub updates: u64,
+}
+
+impl Default for TransitionModel {
+    fn default() -> Self {
+        Self::new(TRANSITION_LEARNING_RATE)
+    }
+}
+
+impl TransitionModel {
+    /// Model learned at rate `rate`, starting from the commanded motion.
+    #[must_use]
+    pub const fn new(rate: f64) -> Self {
+        Self {
+            rate,
+            speed_gain: 1.0,
+            turn_gain: 1.0,
+            drift_x: 0.0,
+            drift_y: 0.0,
+            updates: 0,
+        }
+    }
+
+    /// Returns true if the model is not learned.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.rate <= 0.0
+    }
+
+    /// Checks the rate is finite and not negative.
+    ///
+    /// # Errors
+    /// A message naming the bad rate.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rate.is_finite() && self.rate >= 0.0 {
+            Ok(())
+        } else {
+            Err(format!(
+                "transition learning rate must be finite and >= 0: {}",
+                self.rate
+            ))
+        }
+    }
+
+    /// Heading change expected from the commanded turn `turn`.
+    #[must_use]
+    pub fn turn(&self, turn: f64) -> f64 {
+        self.turn_gain * turn
+    }
+
+    /// Displacement expected at commanded speed `speed` along `heading`.
+    #[must_use]
+    pub fn displacement(&self, speed: f64, heading: f64) -> (f64, f64) {
+        let speed = self.speed_gain * speed;
+        (
+            speed * heading.cos() + self.drift_x,
+            speed * heading.sin() + self.drift_y,
+        )
+    }
+
+    /// One learning step on the move `sample`.
+    pub fn learn(&mut self, sample: &MotionSample) {
+        if self.is_off() {
+            return;
+        }
+        let turned = (sample.new_heading - sample.heading + PI).rem_euclid(2.0 * PI) - PI;
+        let turn_error = turned - self.turn(sample.turn);
+        let (px, py) = self.displacement(sample.speed, sample.new_heading);
+        let (ex, ey) = (sample.dx - px, sample.dy - py);
+        let along = ex * sample.new_heading.cos() + ey * sample.new_heading.sin();
+
+        self.turn_gain = (self.turn_gain
+            + self.rate * turn_error * sample.turn
+                / (sample.turn * sample.turn + TRANSITION_REGULARIZER))
+            .clamp(0.0, TRANSITION_MAX_GAIN);
+        self.speed_gain = (self.speed_gain
+            + self.rate * along * sample.speed
+                / (sample.speed * sample.speed + TRANSITION_REGULARIZER))
+            .clamp(0.0, TRANSITION_MAX_GAIN);
+        let (drift_x, drift_y) = (self.drift_x + self.rate * ex, self.drift_y + self.rate * ey);
+        let scale = (TRANSITION_MAX_DRIFT / drift_x.hypot(drift_y)).min(1.0);
+        self.drift_x = drift_x * scale;
+        self.drift_y = drift_y * scale;
+        self.updates += 1;
+    }
+}
@@ -0,0 +1,157 @@
+//! Volatility estimation: a third level over the precision estimates.
+//!
+//! `PrecisionEstimator` learns how noisy the senses are but assumes the
+//! world itself holds still, so the agent learns and forgets at the same
+//! rate whether its sources sit still or drift and decay. `Volatility`
+//! tracks how fast the world is changing, in the manner of a three-level
+//! hierarchical Gaussian filter (Mathys et al. 2014) over the mean reading
+//! u: level 2 follows the reading as a random walk whose step variance is
+//! set by level 3, the log-volatility μ₃:
+//!
+//! ```text
+//! σ̂₂ = σ₂ + e^(κμ₃ + ω)                    predicted variance of the reading
+//! μ₂ ← μ₂ + πᵤ/(π̂₂ + πᵤ) · (u − μ₂)         π̂₂ = 1/σ̂₂, σ₂ = 1/(π̂₂ + πᵤ)
+//! δ₂ = (σ₂ + (Δμ₂)²) π̂₂ − 1                 volatility prediction error
+//! w₂ = e^(κμ₃ + ω) π̂₂
+//! π₃ = 1/(σ₃ + ϑ) + κ²/2 · w₂ (w₂ + (2w₂ − 1) δ₂)
+//! μ₃ ← μ₃ + κ/2 · w₂ δ₂ / π₃
+//! ```
+//!
+//! with κ = `coupling`, ω = `VOLATILITY_TONIC`, ϑ = `VOLATILITY_META` and
+//! πᵤ = `VOLATILITY_INPUT_PRECISION`. Readings that keep moving more than
+//! expected raise μ₃; a calm world lowers it. `factor()` = e^(κμ₃), within
+//! [1/`VOLATILITY_MAX_FACTOR`, `VOLATILITY_MAX_FACTOR`], scales the belief
+//! learning rate, the rate of the precision estimates and the growth of
+//! belief variance between samples (`growth`). Moving through a gradient
+//! changes the readings as much as a drifting source does; the agent reads
+//! both as volatility, and either way its beliefs go stale faster.
+//!
+//! The coupling is `GenerativeModel::volatility` (`--volatility`); at 0
+//! (the default) nothing is tracked and every rate is the fixed one.
+
+use crate::simulation::params::{
+    VOLATILITY_COUPLING, VOLATILITY_INPUT_PRECISION, VOLATILITY_MAX_FACTOR, VOLATILITY_META,
+    VOLATILITY_TONIC,
+};
+
+/// Estimate of how fast the sensed world is changing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::struct_field_names)]
+pub struct Volatility {
+    /// Coupling κ of the reading's step variance to the log-volatility (0 = off)
+    pub coupling: f64,
+    /// Level 2: believed mean reading and its variance
+    pub level_mean: f64,
+    pub level_var: f64,
+    /// Level 3: log-volatility μ₃ and its variance
+    pub log_volatility: f64,
+    pub log_volatility_var: f64,
+    /// Samples observed
+    pub samples: u64,
+}
+
+impl Default for Volatility {
+    fn default() -> Self {
+        Self::new(VOLATILITY_COUPLING)
+    }
+}
+
+impl Volatility {
+    /// Estimate with coupling `coupling`, at the tonic volatility.
+    #[must_use]
+    pub const fn new(coupling: f64) -> Self {
+        Self {
+            coupling,
+            level_mean: 0.0,
+            level_var: 1.0,
+            log_volatility: 0.0,
+            log_volatility_var: 1.0,
+            samples: 0,
+        }
+    }
+
+    /// Returns true if volatility is not tracked.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.coupling <= 0.0
+    }
+
+    /// Checks the coupling is finite and not negative.
+    ///
+    /// # Errors
+    /// A message naming the bad coupling.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.coupling.is_finite() && self.coupling >= 0.0 {
+            Ok(())
+        } else {
+            Err(format!(
+                "volatility coupling must be finite and >= 0: {}",
+                self.coupling
+            ))
+        }
+    }
+
+    /// Step variance of the reading expected at the current log-volatility.
+    #[must_use]
+    pub fn step_variance(&self) -> f64 {
+        (self.coupling * self.log_volatility + VOLATILITY_TONIC).exp()
+    }
+
+    /// Updates both levels on the mean reading `reading`. The first sample
+    /// only sets the level.
+    pub fn observe(&mut self, reading: f64) {
+        if self.is_off() {
+            return;
+        }
+        self.samples += 1;
+        if self.samples == 1 {
+            self.level_mean = reading;
+            self.level_var = VOLATILITY_INPUT_PRECISION.recip();
+            return;
+        }
+        let kappa = self.coupling;
+        let step = self.step_variance();
+        let predicted_precision = 1.0 / (self.level_var + step);
+
+        // Level 2: the reading
+        let precision = predicted_precision + VOLATILITY_INPUT_PRECISION;
+        let change = VOLATILITY_INPUT_PRECISION / precision * (reading - self.level_mean);
+        self.level_mean += change;
+        self.level_var = precision.recip();
+
+        // Level 3: how much more (or less) it moved than expected
+        let error = (self.level_var + change * change) * predicted_precision - 1.0;
+        let weight = step * predicted_precision;
+        let prior_precision = 1.0 / (self.log_volatility_var + VOLATILITY_META);
+        let mut volatility_precision = prior_precision
+            + 0.5 * kappa * kappa * weight * (weight + (2.0 * weight - 1.0) * error);
+        if volatility_precision <= 0.0 {
+            volatility_precision = prior_precision;
+        }
+        self.log_volatility += 0.5 * kappa * weight * error / volatility_precision;
+        self.log_volatility_var = volatility_precision.recip();
+    }
+
+    /// Factor on learning rates: e^(κμ₃) within the bounds, 1 when off.
+    #[must_use]
+    pub fn factor(&self) -> f64 {
+        if self.is_off() {
+            1.0
+        } else {
+            (self.coupling * self.log_volatility)
+                .exp()
+                .clamp(VOLATILITY_MAX_FACTOR.recip(), VOLATILITY_MAX_FACTOR)
+        }
+    }
+
+    /// Per-tick variance growth `growth` (> 1) with its excess scaled by
+    /// the factor; `growth` itself when off.
+    #[must_use]
+    pub fn growth(&self, growth: f64) -> f64 {
+        if self.is_off() {
+            growth
+        } else {
+            1.0 + (growth - 1.0) * self.factor()
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! Knowledge new agents are born with.
+//!
+//! The genome passes on how an agent is built; `Knowledge` is what it has
+//! learned: its nutrient and temperature beliefs, its spatial priors and its
+//! landmarks. A population's `BeliefInit` sets what a newborn starts with,
+//! so inherited knowledge can be varied independently of morphology:
+//! nothing (`blank`, the default), its parent's knowledge with uniform noise
+//! of up to `noise` on every learned nutrient expectation (`parent[:NOISE]`),
+//! the pooled knowledge of the living agents in its dish (`average`), or a
+//! file saved from an earlier run (`export --knowledge` writes one). Maps are
+//! in dish coordinates, so they are only taken from agents in the newborn's
+//! dish and stretched to its size. Noise draws from the newborn's own
+//! inheritance stream.
+//!
+//! The parent's learned expectations become μ + U(−ε, ε) on visited cells.
+//! The population pool combines each cell's counts nₖ, means μₖ and sums
+//! of squares M₂,ₖ over the donors k, then rescales to the mean count n̄:
+//!
+//! ```text
+//! n = Σₖ nₖ      μ̄ = Σₖ nₖ μₖ / n      M₂ = Σₖ [M₂,ₖ + nₖ (μₖ − μ̄)²]
+//! M₂' = M₂ n̄ / n
+//! ```
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::memory::{
+    CellPrior, EpisodicMemory, Landmark, LandmarkProvenance, SpatialGrid,
+};
+use crate::simulation::params::{INHERITANCE_NOISE, LANDMARK_VISIT_RADIUS, MAX_LANDMARKS};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What an agent has learned, detached from the agent.
+#[derive(Clone, Debug)]
+pub struct Knowledge {
+    /// Believed nutrient concentration and its variance
+    pub nutrient: f64,
+    pub nutrient_var: f64,
+    /// Believed temperature and its variance
+    pub temperature: f64,
+    pub temperature_var: f64,
+    /// Learned nutrient map of the dish
+    pub spatial_priors: SpatialGrid<20, 10>,
+    /// Remembered landmarks
+    pub episodic_memory: EpisodicMemory,
+}
+
+impl Knowledge {
+    /// Knowledge `agent` holds about its current dish.
+    #[must_use]
+    pub fn of(agent: &Protozoa) -> Self {
+        let beliefs = &agent.beliefs;
+        Self {
+            nutrient: beliefs.mean.nutrient,
+            nutrient_var: beliefs.covariance.nutrient_var,
+            temperature: beliefs.mean.temperature,
+            temperature_var: beliefs.covariance.temperature_var,
+            spatial_priors: agent.spatial_priors.clone(),
+            episodic_memory: agent.episodic_memory.clone(),
+        }
+    }
+
+    /// Pooled knowledge of `agents` (`None` if there are none): mean
+    /// beliefs, every map cell combined over all visits and then scaled back
+    /// to the mean visit count, and the most valuable distinct landmarks
+    /// (pins first).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Agent counts are small
+    pub fn average<'a>(agents: impl IntoIterator<Item = &'a Protozoa>) -> Option<Self> {
+        let agents: Vec<&Protozoa> = agents.into_iter().collect();
+        let first = agents.first()?;
+        let mean_of = |value: fn(&Protozoa) -> f64| {
+            agents.iter().map(|a| value(a)).sum::<f64>() / agents.len() as f64
+        };
+
+        let grids: Vec<Vec<CellPrior>> = agents
+            .iter()
+            .map(|a| a.spatial_priors.cells().copied().collect())
+            .collect();
+        let mut spatial_priors = first.spatial_priors.clone();
+        for (i, cell) in spatial_priors.cells_mut().enumerate() {
+            *cell = pool(grids.iter().map(|grid| grid[i]), agents.len());
+        }
+
+        let mut landmarks: Vec<Landmark> = agents
+            .iter()
+            .flat_map(|a| a.episodic_memory.iter().copied())
+            .collect();
+        landmarks.sort_by(|a, b| {
+            b.is_pinned()
+                .cmp(&a.is_pinned())
+                .then(b.value().total_cmp(&a.value()))
+        });
+        let mut episodic_memory = EpisodicMemory::new();
+        for landmark in landmarks {
+            let known = episodic_memory
+                .iter()
+                .any(|l| l.distance_to(landmark.x, landmark.y) < LANDMARK_VISIT_RADIUS);
+            if !known && !episodic_memory.insert(landmark) {
+                break;
+            }
+        }
+
+        Some(Self {
+            nutrient: mean_of(|a| a.beliefs.mean.nutrient),
+            nutrient_var: mean_of(|a| a.beliefs.covariance.nutrient_var),
+            temperature: mean_of(|a| a.beliefs.mean.temperature),
+            temperature_var: mean_of(|a| a.beliefs.covariance.temperature_var),
+            spatial_priors,
+            episodic_memory,
+        })
+    }
+
+    /// Adds uniform noise in [−`noise`, `noise`] to the believed nutrient,
+    /// the mean of every visited map cell and every landmark's peak.
+    pub fn perturb(&mut self, noise: f64, rng: &mut impl Rng) {
+        if !noise.is_finite() || noise <= 0.0 {
+            return;
+        }
+        let mut jitter = |value: f64| value + rng.random_range(-noise..=noise);
+        self.nutrient = jitter(self.nutrient).clamp(0.0, 1.0);
+        for cell in self.spatial_priors.cells_mut().filter(|c| c.visits > 0) {
+            cell.mean = jitter(cell.mean).clamp(-0.5, 1.5);
+        }
+        let landmarks: Vec<Landmark> = self.episodic_memory.iter().copied().collect();
+        self.episodic_memory.clear();
+        for mut landmark in landmarks {
+            landmark.peak_nutrient = jitter(landmark.peak_nutrient).clamp(0.0, 1.0);
+            self.episodic_memory.insert(landmark);
+        }
+    }
+
+    /// Gives `agent` this knowledge, stretched to the dish its map covers.
+    /// Its position, heading and every other state are left as they are.
+    pub fn instill(&self, agent: &mut Protozoa) {
+        let (width, height) = agent.spatial_priors.world_dimensions();
+        let (from_width, from_height) = self.spatial_priors.world_dimensions();
+        let beliefs = &mut agent.beliefs;
+        beliefs.mean.nutrient = self.nutrient;
+        beliefs.covariance.nutrient_var = self.nutrient_var;
+        beliefs.mean.temperature = self.temperature;
+        beliefs.covariance.temperature_var = self.temperature_var;
+        agent.spatial_priors = self.spatial_priors.clone();
+        agent.spatial_priors.resize(width, height);
+        agent.episodic_memory = self.episodic_memory.clone();
+        agent
+            .episodic_memory
+            .rescale(width / from_width, height / from_height);
+    }
+
+    /// Serializes the knowledge as JSON (the format `load` reads).
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let (width, height) = self.spatial_priors.world_dimensions();
+        let file = KnowledgeFile {
+            width,
+            height,
+            nutrient: self.nutrient,
+            nutrient_var: self.nutrient_var,
+            temperature: self.temperature,
+            temperature_var: self.temperature_var,
+            cells: self
+                .spatial_priors
+                .cells()
+                .map(|c| CellRecord {
+                    mean: c.mean,
+                    m2: c.m2,
+                    visits: c.visits,
+                })
+                .collect(),
+            landmarks: self
+                .episodic_memory
+                .iter()
+                .map(|l| LandmarkRecord {
+                    x: l.x,
+                    y: l.y,
+                    peak_nutrient: l.peak_nutrient,
+                    reliability: l.reliability,
+                    pinned: l.is_pinned(),
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&file).unwrap_or_default()
+    }
+
+    /// Parses knowledge written by `to_json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message for malformed JSON, a map of the wrong size,
+    /// non-finite values, a non-positive dish size or too many landmarks.
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let file: KnowledgeFile =
+            serde_json::from_str(text).map_err(|e| format!("invalid knowledge file: {e}"))?;
+        let mut spatial_priors = SpatialGrid::<20, 10>::new(file.width, file.height);
+        let (columns, rows) = spatial_priors.dimensions();
+        if file.cells.len() != columns * rows {
+            return Err(format!(
+                "knowledge map has {} cells, expected {}",
+                file.cells.len(),
+                columns * rows
+            ));
+        }
+        let values = [
+            file.nutrient,
+            file.nutrient_var,
+            file.temperature,
+            file.temperature_var,
+        ];
+        let cells_finite = file
+            .cells
+            .iter()
+            .all(|c| c.mean.is_finite() && c.m2.is_finite());
+        let landmarks_finite = file.landmarks.iter().all(|l| {
+            [l.x, l.y, l.peak_nutrient, l.reliability]
+                .iter()
+                .all(|v| v.is_finite())
+        });
+        if !(values.iter().all(|v| v.is_finite()) && cells_finite && landmarks_finite) {
+            return Err("knowledge file holds non-finite values".to_string());
+        }
+        if !(file.width > 0.0 && file.height > 0.0) {
+            return Err(format!(
+                "invalid knowledge dish size: {}x{}",
+                file.width, file.height
+            ));
+        }
+
+        for (cell, record) in spatial_priors.cells_mut().zip(&file.cells) {
+            *cell = CellPrior {
+                mean: record.mean,
+                m2: record.m2.max(0.0),
+                visits: record.visits,
+            };
+        }
+        let mut episodic_memory = EpisodicMemory::new();
+        for record in &file.landmarks {
+            let mut landmark = Landmark::new(record.x, record.y, record.peak_nutrient, 0);
+            landmark.reliability = record.reliability;
+            if record.pinned {
+                landmark.provenance = LandmarkProvenance::User;
+            }
+            if !episodic_memory.insert(landmark) {
+                return Err(format!(
+                    "knowledge file holds more than {MAX_LANDMARKS} landmarks"
+                ));
+            }
+        }
+        Ok(Self {
+            nutrient: file.nutrient,
+            nutrient_var: file.nutrient_var,
+            temperature: file.temperature,
+            temperature_var: file.temperature_var,
+            spatial_priors,
+            episodic_memory,
+        })
+    }
+
+    /// Reads a knowledge file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_json(&text)
+    }
+}
+
+/// Visit-weighted combination of the same cell in `count` maps, scaled back
+/// to the mean visit count (rounded, at least one) so a newborn is as sure
+/// as a typical member, not as all of them together.
+#[allow(clippy::cast_precision_loss)] // Visit counts are far below 2^52
+fn pool(cells: impl Iterator<Item = CellPrior> + Clone, count: usize) -> CellPrior {
+    let visits: u64 = cells.clone().map(|c| u64::from(c.visits)).sum();
+    if visits == 0 {
+        return CellPrior::default();
+    }
+    let total = visits as f64;
+    let mean = cells
+        .clone()
+        .map(|c| f64::from(c.visits) * c.mean)
+        .sum::<f64>()
+        / total;
+    let m2: f64 = cells
+        .map(|c| c.m2 + f64::from(c.visits) * (c.mean - mean).powi(2))
+        .sum();
+    let count = count.max(1) as u64;
+    let typical = ((visits + count / 2) / count).max(1);
+    CellPrior {
+        mean,
+        m2: m2 * typical as f64 / total,
+        visits: u32::try_from(typical).unwrap_or(u32::MAX),
+    }
+}
+
+/// Where a newborn's knowledge comes from, selected with `--beliefs SPEC`.
+#[derive(Clone, Debug, Default)]
+pub enum BeliefInit {
+    /// Uninformed beliefs, an empty map and no landmarks
+    #[default]
+    Blank,
+    /// The parent's knowledge with uniform noise of up to `noise`
+    Parent { noise: f64 },
+    /// The pooled knowledge of the living agents in the newborn's dish
+    Average,
+    /// Knowledge loaded from a file
+    Saved(Box<Knowledge>),
+}
+
+impl BeliefInit {
+    /// Parses `blank`, `parent`, `parent:NOISE`, `average` or the path of
+    /// a knowledge file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message for a negative or non-numeric noise and for a file
+    /// that cannot be loaded.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        match spec {
+            "blank" => Ok(Self::Blank),
+            "average" => Ok(Self::Average),
+            "parent" => Ok(Self::Parent {
+                noise: INHERITANCE_NOISE,
+            }),
+            _ => match spec.strip_prefix("parent:") {
+                Some(noise) => noise
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|v| v.is_finite() && *v >= 0.0)
+                    .map(|noise| Self::Parent { noise })
+                    .ok_or_else(|| format!("invalid inheritance noise: {noise}")),
+                None => Knowledge::load(spec).map(|k| Self::Saved(Box::new(k))),
+            },
+        }
+    }
+
+    /// Gives the newborn `agent` its knowledge. `parent` is its parent
+    /// (`None` for a founder) and `population` the agents already living;
+    /// without a parent or living agents in its dish to learn from it stays
+    /// blank.
+    pub fn instill(
+        &self,
+        agent: &mut Protozoa,
+        parent: Option<&Protozoa>,
+        population: &[Protozoa],
+    ) {
+        let knowledge = match self {
+            Self::Blank => return,
+            Self::Parent { noise } => {
+                let Some(parent) = parent.filter(|p| p.dish_id == agent.dish_id) else {
+                    return;
+                };
+                let mut knowledge = Knowledge::of(parent);
+                knowledge.perturb(*noise, &mut agent.streams.inheritance);
+                knowledge
+            }
+            Self::Average => {
+                let living = population
+                    .iter()
+                    .filter(|a| a.is_alive() && a.dish_id == agent.dish_id);
+                let Some(knowledge) = Knowledge::average(living) else {
+                    return;
+                };
+                knowledge
+            }
+            Self::Saved(knowledge) => (**knowledge).clone(),
+        };
+        knowledge.instill(agent);
+    }
+}
+
+/// On-disk form of `Knowledge`; cells in row-major order.
+#[derive(Serialize, Deserialize)]
+struct KnowledgeFile {
+    width: f64,
+    height: f64,
+    nutrient: f64,
+    nutrient_var: f64,
+    temperature: f64,
+    temperature_var: f64,
+    cells: Vec<CellRecord>,
+    landmarks: Vec<LandmarkRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CellRecord {
+    mean: f64,
+    m2: f64,
+    visits: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LandmarkRecord {
+    x: f64,
+    y: f64,
+    peak_nutrient: f64,
+    reliability: f64,
+    pinned: bool,
+}
@@ -0,0 +1,95 @@
+//! Radial profiles of nutrient sources.
+//!
+//! Every `NutrientSource` spreads its intensity over the dish with a `Kernel`
+//! of its `radius`. The shapes give gradients of very different curvature:
+//!
+//! | Kernel        | Profile at distance d  | Gradient                   |
+//! |---------------|------------------------|----------------------------|
+//! | `gaussian`    | exp(-d² / 2r²)         | smooth, vanishes at peak   |
+//! | `top_hat`     | 1 within r, 0 beyond   | zero (a cliff at r)        |
+//! | `exponential` | exp(-d / r)            | cusp at the center         |
+//! | `annulus`     | exp(-(d - r)² / 2w²)   | ridge at r, dip inside     |
+//!
+//! The annulus ring width is w = `ANNULUS_WIDTH` · r.
+//!
+//! Dishes pick the kernel of their random sources with `DishConfig::kernel`
+//! (TOML key `kernel`); presets and pulses stay Gaussian.
+
+use crate::simulation::params::{ANNULUS_WIDTH, SOURCE_INDEX_CUTOFF};
+use serde::{Deserialize, Serialize};
+
+/// Radial profile of a nutrient source.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kernel {
+    #[default]
+    Gaussian,
+    TopHat,
+    Exponential,
+    Annulus,
+}
+
+impl Kernel {
+    /// Profile in [0, 1] at offset (dx, dy) from a source of `radius`.
+    #[must_use]
+    pub fn profile(self, dx: f64, dy: f64, radius: f64) -> f64 {
+        let dist_sq = dx.powi(2) + dy.powi(2);
+        let sigma_sq = radius.powi(2).max(f64::EPSILON);
+        let radius = radius.max(f64::EPSILON);
+        match self {
+            Self::Gaussian => (-dist_sq / (2.0 * sigma_sq)).exp(),
+            Self::TopHat => {
+                if dist_sq <= radius * radius {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Exponential => (-dist_sq.sqrt() / radius).exp(),
+            Self::Annulus => {
+                let width = ANNULUS_WIDTH * radius;
+                (-(dist_sq.sqrt() - radius).powi(2) / (2.0 * width * width)).exp()
+            }
+        }
+    }
+
+    /// Gradient of `profile` with respect to the query point.
+    ///
+    /// Zero where the profile is not differentiable (the top-hat edge, the
+    /// center of the exponential and annulus).
+    #[must_use]
+    pub fn gradient(self, dx: f64, dy: f64, radius: f64) -> (f64, f64) {
+        let dist = dx.hypot(dy);
+        // d(profile)/d(dist); the gradient is that along the unit offset
+        let slope = match self {
+            Self::Gaussian => {
+                // Written without dividing by `dist`, so the peak is exact
+                let sigma_sq = radius.powi(2).max(f64::EPSILON);
+                let scale = -self.profile(dx, dy, radius) / sigma_sq;
+                return (scale * dx, scale * dy);
+            }
+            Self::TopHat => return (0.0, 0.0),
+            Self::Exponential => -self.profile(dx, dy, radius) / radius.max(f64::EPSILON),
+            Self::Annulus => {
+                let width = ANNULUS_WIDTH * radius.max(f64::EPSILON);
+                -(dist - radius) / (width * width) * self.profile(dx, dy, radius)
+            }
+        };
+        if dist <= f64::EPSILON {
+            return (0.0, 0.0);
+        }
+        (slope * dx / dist, slope * dy / dist)
+    }
+
+    /// Distance beyond which the profile is below exp(-`SOURCE_INDEX_CUTOFF`²/2)
+    /// (zero for the top-hat), used by `SourceIndex`.
+    #[must_use]
+    pub fn reach(self, radius: f64) -> f64 {
+        match self {
+            Self::Gaussian => SOURCE_INDEX_CUTOFF * radius,
+            Self::TopHat => radius,
+            Self::Exponential => SOURCE_INDEX_CUTOFF * SOURCE_INDEX_CUTOFF / 2.0 * radius,
+            Self::Annulus => radius + SOURCE_INDEX_CUTOFF * ANNULUS_WIDTH * radius,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Inertial kinematics: momentum and drag.
+//!
+//! The controller commands a turn per tick and a speed. A body without
+//! inertia (the default) executes both at once. With `Kinematics` it has
+//! momentum instead. Thrust pushes the speed `v` toward the terminal speed
+//! `u/η` of the command `u` in a medium of viscosity `η`, against linear
+//! drag:
+//!
+//! ```text
+//! m dv/dt = k (u − η v)            v(t + dt) = u/η + (v − u/η) e^(−k η dt / m)
+//! I dω/dt = k_r (ω* − ω)           ω(t + dt) = ω* + (ω − ω*) e^(−k_r dt / I)
+//! ```
+//!
+//! where `ω* = turn / dt` is the commanded angular velocity and the heading
+//! advances by `ω dt`. The drag coefficients are tied to the body plan:
+//! longer sensors make a bigger body to push through the medium,
+//! `k = LINEAR_DRAG · s` and `k_r = ROTATIONAL_DRAG · s²` with
+//! `s = sensor_dist / SENSOR_DIST`. With zero mass (or moment) the matching
+//! update is instantaneous, exactly as without inertia.
+//!
+//! The planner's rollouts and the agent's predicted beliefs step the same
+//! dynamics (`AgentState::with_body`), so plans account for the time a turn
+//! or a change of speed takes.
+
+use crate::simulation::params::{
+    KINEMATIC_MASS, KINEMATIC_MOMENT, LINEAR_DRAG, ROTATIONAL_DRAG, SENSOR_DIST,
+};
+
+/// Speed and angular velocity a body carries from one tick to the next.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Motion {
+    /// Speed along the heading
+    pub speed: f64,
+    /// Angular velocity (radians per second, positive to the left)
+    pub spin: f64,
+}
+
+/// Inertia of a body plan (`Morphology::kinematics`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Kinematics {
+    /// Mass resisting changes of speed (0 = instantaneous)
+    pub mass: f64,
+    /// Moment of inertia resisting changes of turning (0 = instantaneous)
+    pub moment: f64,
+}
+
+impl Default for Kinematics {
+    fn default() -> Self {
+        Self::new(KINEMATIC_MASS, KINEMATIC_MOMENT)
+    }
+}
+
+impl Kinematics {
+    /// A body of the given mass and moment of inertia.
+    #[must_use]
+    pub const fn new(mass: f64, moment: f64) -> Self {
+        Self { mass, moment }
+    }
+
+    /// Returns true if commands take effect at once.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.mass <= 0.0 && self.moment <= 0.0
+    }
+
+    /// Linear drag coefficient of a body with sensors `sensor_dist` long.
+    #[must_use]
+    pub fn drag(sensor_dist: f64) -> f64 {
+        LINEAR_DRAG * sensor_dist / SENSOR_DIST
+    }
+
+    /// Rotational drag coefficient of a body with sensors `sensor_dist` long.
+    #[must_use]
+    pub fn rotational_drag(sensor_dist: f64) -> f64 {
+        ROTATIONAL_DRAG * (sensor_dist / SENSOR_DIST).powi(2)
+    }
+
+    /// Motion after one tick of `dt` seconds commanding `turn` radians and
+    /// `speed`, in a medium of `viscosity`, for a body with sensors
+    /// `sensor_dist` long; returned with the turn actually made.
+    #[must_use]
+    pub fn advance(
+        &self,
+        motion: Motion,
+        turn: f64,
+        speed: f64,
+        sensor_dist: f64,
+        viscosity: f64,
+        dt: f64,
+    ) -> (Motion, f64) {
+        let terminal = speed / viscosity;
+        let speed = if self.mass > 0.0 {
+            let decay = (-Self::drag(sensor_dist) * viscosity * dt / self.mass).exp();
+            terminal + (motion.speed - terminal) * decay
+        } else {
+            terminal
+        };
+        let commanded_spin = turn / dt;
+        if self.moment > 0.0 {
+            let decay = (-Self::rotational_drag(sensor_dist) * dt / self.moment).exp();
+            let spin = commanded_spin + (motion.spin - commanded_spin) * decay;
+            (Motion { speed, spin }, spin * dt)
+        } else {
+            (
+                Motion {
+                    speed,
+                    spin: commanded_spin,
+                },
+                turn,
+            )
+        }
+    }
+
+    /// Checks that mass and moment are finite and not negative.
+    ///
+    /// # Errors
+    /// Names the first offending value.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.mass.is_finite() && self.mass >= 0.0) {
+            return Err(format!("kinematic mass out of range: {}", self.mass));
+        }
+        if !(self.moment.is_finite() && self.moment >= 0.0) {
+            return Err(format!("kinematic moment out of range: {}", self.moment));
+        }
+        Ok(())
+    }
+}
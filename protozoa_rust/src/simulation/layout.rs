@@ -0,0 +1,311 @@
+//! Environment layouts loaded from files.
+//!
+//! A layout is a grid of cells, each either a wall or open medium with a
+//! nutrient level in [0, 1]. It is stretched over the dish: walls become
+//! rectangular `Obstacle`s (runs of wall cells are merged) and nutrient levels
+//! become a static `NutrientMap` that is sampled bilinearly, so the agent
+//! still sees smooth gradients between cells.
+//!
+//! Two formats are read:
+//! - **ASCII** (any extension but `.png`): one line per row; `#` is a wall,
+//!   `.` or space is empty medium, `1`–`9` is nutrient at n/9, `*` is full
+//!   nutrient and `S` marks the agent's start. Short lines are padded with
+//!   empty medium.
+//! - **Grayscale PNG** (`png` feature): black is empty medium, gray levels
+//!   are nutrient (brightness / 254), pure white (255) is a wall and fully
+//!   transparent pixels are empty. Color images are read by luminance.
+
+use crate::simulation::environment::Obstacle;
+use crate::simulation::spawn::{SpawnConfig, StartPosition};
+use std::path::Path;
+
+/// Static raster of nutrient levels stretched over the dish.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NutrientMap {
+    pub cols: usize,
+    pub rows: usize,
+    /// Row-major nutrient levels in [0, 1]
+    pub values: Vec<f64>,
+    /// World width covered by the map
+    pub width: f64,
+    /// World height covered by the map
+    pub height: f64,
+}
+
+impl NutrientMap {
+    /// True for the empty map (no raster nutrient anywhere).
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Nutrient level at (x, y), bilinearly interpolated between cell centers.
+    #[must_use]
+    pub fn sample(&self, x: f64, y: f64) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let ([v00, v10, v01, v11], tx, ty) = self.corners(x, y);
+        let top = v00 + (v10 - v00) * tx;
+        let bottom = v01 + (v11 - v01) * tx;
+        top + (bottom - top) * ty
+    }
+
+    /// Exact gradient of `sample` at (x, y); zero along an axis beyond the
+    /// outermost cell centers, where `sample` is flat.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Grid sizes are far below 2^52
+    pub fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        if self.is_empty() {
+            return (0.0, 0.0);
+        }
+        let ([v00, v10, v01, v11], tx, ty) = self.corners(x, y);
+        let (per_x, per_y) = (
+            self.cols as f64 / self.width,
+            self.rows as f64 / self.height,
+        );
+        let inside = |g: f64, n: usize| g > 0.0 && g < (n - 1) as f64;
+        let d_x = if inside(x * per_x - 0.5, self.cols) {
+            ((v10 - v00) * (1.0 - ty) + (v11 - v01) * ty) * per_x
+        } else {
+            0.0
+        };
+        let d_y = if inside(y * per_y - 0.5, self.rows) {
+            ((v01 - v00) * (1.0 - tx) + (v11 - v10) * tx) * per_y
+        } else {
+            0.0
+        };
+        (d_x, d_y)
+    }
+
+    /// Levels at the four cell centers around (x, y) (top-left, top-right,
+    /// bottom-left, bottom-right) and the offsets between them.
+    #[allow(clippy::cast_precision_loss)] // Grid sizes are far below 2^52
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn corners(&self, x: f64, y: f64) -> ([f64; 4], f64, f64) {
+        let fx = (x / self.width * self.cols as f64 - 0.5).clamp(0.0, (self.cols - 1) as f64);
+        let fy = (y / self.height * self.rows as f64 - 0.5).clamp(0.0, (self.rows - 1) as f64);
+        let (c0, r0) = (fx.floor() as usize, fy.floor() as usize);
+        let (c1, r1) = ((c0 + 1).min(self.cols - 1), (r0 + 1).min(self.rows - 1));
+        let at = |c: usize, r: usize| self.values[r * self.cols + c];
+        (
+            [at(c0, r0), at(c1, r0), at(c0, r1), at(c1, r1)],
+            fx - c0 as f64,
+            fy - r0 as f64,
+        )
+    }
+}
+
+/// Cell grid read from an ASCII map or a grayscale image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    pub cols: usize,
+    pub rows: usize,
+    /// Row-major nutrient levels in [0, 1] (0 on walls)
+    pub nutrient: Vec<f64>,
+    /// Row-major wall mask
+    pub walls: Vec<bool>,
+    /// Start cell `(col, row)` marked with `S` (ASCII maps only)
+    pub start: Option<(usize, usize)>,
+}
+
+impl Layout {
+    /// Parses an ASCII map (see the module docs for the legend).
+    ///
+    /// # Errors
+    /// Returns a message for an empty map or an unknown character.
+    pub fn from_ascii(text: &str) -> Result<Self, String> {
+        let lines: Vec<&str> = text.lines().map(|l| l.trim_end_matches('\r')).collect();
+        let rows = lines.len();
+        let cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        if rows == 0 || cols == 0 {
+            return Err("layout map is empty".into());
+        }
+        let mut layout = Self::open(cols, rows);
+        for (r, line) in lines.iter().enumerate() {
+            for (c, ch) in line.chars().enumerate() {
+                let i = r * cols + c;
+                match ch {
+                    '#' => layout.walls[i] = true,
+                    '.' | ' ' => {}
+                    'S' => layout.start = Some((c, r)),
+                    '*' => layout.nutrient[i] = 1.0,
+                    '1'..='9' => layout.nutrient[i] = f64::from(ch as u8 - b'0') / 9.0,
+                    _ => {
+                        return Err(format!(
+                            "unknown layout character {ch:?} at line {}, column {}",
+                            r + 1,
+                            c + 1
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Decodes a PNG image (see the module docs for the gray-level legend).
+    ///
+    /// # Errors
+    /// Returns a message if the bytes are not a decodable PNG.
+    #[cfg(feature = "png")]
+    pub fn from_png(bytes: &[u8]) -> Result<Self, String> {
+        let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder
+            .read_info()
+            .map_err(|e| format!("invalid layout image: {e}"))?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buf)
+            .map_err(|e| format!("invalid layout image: {e}"))?;
+        let (cols, rows) = (info.width as usize, info.height as usize);
+        let channels = info.color_type.samples();
+        if cols == 0 || rows == 0 {
+            return Err("layout image is empty".into());
+        }
+
+        let mut layout = Self::open(cols, rows);
+        for (i, pixel) in buf[..info.buffer_size()]
+            .chunks_exact(channels)
+            .take(cols * rows)
+            .enumerate()
+        {
+            let (level, alpha) = match *pixel {
+                [v] => (v, u8::MAX),
+                [v, a] => (v, a),
+                [r, g, b] => (luma(r, g, b), u8::MAX),
+                [r, g, b, a, ..] => (luma(r, g, b), a),
+                [] => (0, 0),
+            };
+            if alpha == 0 {
+                continue;
+            }
+            if level == u8::MAX {
+                layout.walls[i] = true;
+            } else {
+                layout.nutrient[i] = f64::from(level) / f64::from(u8::MAX - 1);
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Reads a layout file, choosing the format by extension (`.png` or ASCII).
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be read or parsed, or if it is a
+    /// PNG and the `png` feature is disabled.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let is_png = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+        if is_png {
+            #[cfg(feature = "png")]
+            {
+                let bytes = std::fs::read(path)
+                    .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+                return Self::from_png(&bytes);
+            }
+            #[cfg(not(feature = "png"))]
+            return Err(format!(
+                "cannot read {}: built without the `png` feature",
+                path.display()
+            ));
+        }
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_ascii(&text)
+    }
+
+    /// Empty medium everywhere.
+    fn open(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            nutrient: vec![0.0; cols * rows],
+            walls: vec![false; cols * rows],
+            start: None,
+        }
+    }
+
+    /// Nutrient raster stretched over a dish of the given size.
+    #[must_use]
+    pub fn nutrient_map(&self, width: f64, height: f64) -> NutrientMap {
+        NutrientMap {
+            cols: self.cols,
+            rows: self.rows,
+            values: self.nutrient.clone(),
+            width,
+            height,
+        }
+    }
+
+    /// Start at the center of the `S` cell, or the dish center without one
+    /// (random heading, full energy).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Grid sizes are far below 2^52
+    pub fn spawn_config(&self, width: f64, height: f64) -> SpawnConfig {
+        let position = self
+            .start
+            .map_or(StartPosition::Center, |(c, r)| StartPosition::Fixed {
+                x: (c as f64 + 0.5) * width / self.cols as f64,
+                y: (r as f64 + 0.5) * height / self.rows as f64,
+            });
+        SpawnConfig {
+            position,
+            ..SpawnConfig::default()
+        }
+    }
+
+    /// Wall cells as obstacles for a dish of the given size.
+    ///
+    /// Horizontal runs of wall cells become one rectangle, and identical runs
+    /// on consecutive rows are merged, so block-shaped walls stay cheap to test.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Grid sizes are far below 2^52
+    pub fn obstacles(&self, width: f64, height: f64) -> Vec<Obstacle> {
+        let (cell_w, cell_h) = (width / self.cols as f64, height / self.rows as f64);
+        let mut obstacles: Vec<Obstacle> = Vec::new();
+        // Runs (start, end) still open from the previous row, with their obstacle index
+        let mut open: Vec<(usize, usize, usize)> = Vec::new();
+        for r in 0..self.rows {
+            let mut next_open = Vec::new();
+            let mut c = 0;
+            while c < self.cols {
+                if !self.walls[r * self.cols + c] {
+                    c += 1;
+                    continue;
+                }
+                let start = c;
+                while c < self.cols && self.walls[r * self.cols + c] {
+                    c += 1;
+                }
+                let y_max = (r + 1) as f64 * cell_h;
+                if let Some(&(_, _, idx)) = open.iter().find(|(s, e, _)| *s == start && *e == c) {
+                    obstacles[idx].y_max = y_max;
+                    next_open.push((start, c, idx));
+                } else {
+                    obstacles.push(Obstacle {
+                        x_min: start as f64 * cell_w,
+                        y_min: r as f64 * cell_h,
+                        x_max: c as f64 * cell_w,
+                        y_max,
+                    });
+                    next_open.push((start, c, obstacles.len() - 1));
+                }
+            }
+            open = next_open;
+        }
+        obstacles
+    }
+}
+
+/// Rec. 601 luminance of an RGB pixel.
+#[cfg(feature = "png")]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn luma(r: u8, g: u8, b: u8) -> u8 {
+    (0.299 * f64::from(r) + 0.587 * f64::from(g) + 0.114 * f64::from(b)).round() as u8
+}
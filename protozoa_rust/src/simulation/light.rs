@@ -0,0 +1,70 @@
+//! Directional light field for phototaxis.
+//!
+//! Light falls off linearly across the dish: brightest at the edge the light
+//! direction points to, darkest at the opposite edge. The agent's
+//! `light_sensitivity` decides whether it seeks (positive) or avoids
+//! (negative) the light.
+
+use crate::simulation::params::{LIGHT_AMBIENT, LIGHT_CONTRAST, LIGHT_DIRECTION};
+
+/// Linear light gradient over the dish.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LightField {
+    /// Direction of increasing brightness in radians (0 = +x)
+    pub direction: f64,
+    /// Brightness at the dish center
+    pub ambient: f64,
+    /// Brightness difference between the brightest and darkest edge
+    pub contrast: f64,
+    /// Dish center x coordinate
+    pub center_x: f64,
+    /// Dish center y coordinate
+    pub center_y: f64,
+    /// Half the dish extent along the light direction
+    half_extent: f64,
+}
+
+impl LightField {
+    /// Creates the light field configured in `params.rs` for a dish of the given size.
+    #[must_use]
+    pub fn new(width: f64, height: f64) -> Self {
+        Self::directional(
+            width,
+            height,
+            LIGHT_DIRECTION,
+            LIGHT_AMBIENT,
+            LIGHT_CONTRAST,
+        )
+    }
+
+    /// Creates a light field brightening toward `direction`.
+    #[must_use]
+    pub fn directional(
+        width: f64,
+        height: f64,
+        direction: f64,
+        ambient: f64,
+        contrast: f64,
+    ) -> Self {
+        let half_extent = 0.5 * (width * direction.cos().abs() + height * direction.sin().abs());
+        Self {
+            direction,
+            ambient,
+            contrast,
+            center_x: width / 2.0,
+            center_y: height / 2.0,
+            half_extent: half_extent.max(f64::EPSILON),
+        }
+    }
+
+    /// Brightness at (x, y), clamped to [0, 1].
+    ///
+    /// `I = A + ½ c · ((x - cx) cos φ + (y - cy) sin φ) / e`, where `e` is half
+    /// the dish extent along φ.
+    #[must_use]
+    pub fn intensity_at(&self, x: f64, y: f64) -> f64 {
+        let along =
+            (x - self.center_x) * self.direction.cos() + (y - self.center_y) * self.direction.sin();
+        (self.ambient + 0.5 * self.contrast * along / self.half_extent).clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,199 @@
+//! Family records of the agents in a population.
+//!
+//! Every agent born into a `Population` gets a unique lineage ID, its
+//! parent's ID (none for founders) and a generation number. The `Lineage`
+//! registry keeps one record per agent born, including the dead, so
+//! multi-generation experiments can rebuild family trees after the fact.
+//! Past its capacity (`MemoryLimits::lineage`) the oldest records of dead
+//! agents are compacted away; the living are always kept, so a newborn's
+//! parent is always found. IDs keep counting, and a chain back through a
+//! compacted ancestor ends there.
+
+use crate::simulation::params::LINEAGE_CAPACITY;
+use std::fmt::Write as _;
+
+/// Birth and death of one agent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineageRecord {
+    pub id: u64,
+    /// Parent's ID (`None` for a founder)
+    pub parent: Option<u64>,
+    /// 0 for founders, parent's generation + 1 otherwise
+    pub generation: u32,
+    /// Population tick of birth
+    pub born: u64,
+    /// Population tick of death, if dead
+    pub died: Option<u64>,
+}
+
+/// Registry of the agents born in a population, indexed by ID.
+#[derive(Clone, Debug)]
+pub struct Lineage {
+    /// Records in birth order (ascending IDs)
+    records: Vec<LineageRecord>,
+    /// ID of the next agent born
+    next_id: u64,
+    /// Records kept before the oldest dead are compacted away
+    capacity: usize,
+}
+
+impl Default for Lineage {
+    fn default() -> Self {
+        Self::with_capacity(LINEAGE_CAPACITY)
+    }
+}
+
+impl Lineage {
+    /// Empty registry keeping at most `capacity` records once the surplus
+    /// are dead.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: Vec::new(),
+            next_id: 0,
+            capacity,
+        }
+    }
+
+    /// Registers an agent born at `tick` to `parent` (a known ID, or `None`
+    /// for a founder) and returns its record.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` is not a registered ID.
+    pub fn register(&mut self, parent: Option<u64>, tick: u64) -> LineageRecord {
+        let generation = parent.map_or(0, |id| {
+            self.get(id)
+                .expect("parent must be registered before its offspring")
+                .generation
+                + 1
+        });
+        let record = LineageRecord {
+            id: self.next_id,
+            parent,
+            generation,
+            born: tick,
+            died: None,
+        };
+        self.next_id += 1;
+        self.records.push(record);
+        self.compact();
+        record
+    }
+
+    /// Marks agent `id` dead at `tick` (the first death recorded stands).
+    pub fn record_death(&mut self, id: u64, tick: u64) {
+        if let Ok(i) = self.records.binary_search_by_key(&id, |r| r.id) {
+            self.records[i].died.get_or_insert(tick);
+            self.compact();
+        }
+    }
+
+    /// Record of agent `id` (`None` if unknown or compacted away).
+    #[must_use]
+    pub fn get(&self, id: u64) -> Option<&LineageRecord> {
+        let i = self.records.binary_search_by_key(&id, |r| r.id).ok()?;
+        self.records.get(i)
+    }
+
+    /// Number of records kept.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and lineage analyses
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Number of agents ever born, compacted ones included.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and lineage analyses
+    pub fn born(&self) -> u64 {
+        self.next_id
+    }
+
+    /// Records kept before the oldest dead are compacted away.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and long-run experiments
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the capacity, compacting at once if over it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.compact();
+    }
+
+    /// Bytes of storage held for records.
+    #[must_use]
+    pub fn heap_bytes(&self) -> usize {
+        self.records.capacity() * std::mem::size_of::<LineageRecord>()
+    }
+
+    /// Drops the oldest records of dead agents until at most `capacity` are
+    /// kept (or only the living are left).
+    fn compact(&mut self) {
+        let mut excess = self.records.len().saturating_sub(self.capacity);
+        if excess == 0 {
+            return;
+        }
+        self.records.retain(|r| {
+            let drop = excess > 0 && r.died.is_some();
+            excess -= usize::from(drop);
+            !drop
+        });
+    }
+
+    /// Whether no record is kept.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and lineage analyses
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Every record, in birth order.
+    #[allow(dead_code)] // Used by tests and lineage analyses
+    pub fn iter(&self) -> impl Iterator<Item = &LineageRecord> {
+        self.records.iter()
+    }
+
+    /// Direct offspring of `id`, in birth order.
+    #[allow(dead_code)] // Used by tests and lineage analyses
+    pub fn children(&self, id: u64) -> impl Iterator<Item = &LineageRecord> {
+        self.records.iter().filter(move |r| r.parent == Some(id))
+    }
+
+    /// IDs from `id` back to its founder: `[id, parent, grandparent, ..]`
+    /// (empty for an unknown ID).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and lineage analyses
+    pub fn ancestry(&self, id: u64) -> Vec<u64> {
+        let mut chain = Vec::new();
+        let mut next = self.get(id).map(|r| r.id);
+        while let Some(current) = next {
+            chain.push(current);
+            next = self.get(current).and_then(|r| r.parent);
+        }
+        chain
+    }
+
+    /// The registry as CSV (`id,parent,generation,born,died`, blanks for
+    /// none), one row per kept record in birth order.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and lineage analyses
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("id,parent,generation,born,died\n");
+        let blank = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_default();
+        for r in &self.records {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                r.id,
+                blank(r.parent),
+                r.generation,
+                r.born,
+                blank(r.died)
+            );
+        }
+        csv
+    }
+}
@@ -1,9 +1,29 @@
 //! Episodic memory for landmark storage and recall.
 //!
 //! The agent remembers high-nutrient locations (landmarks) and can
-//! navigate back to them when energy is low.
-
-use crate::simulation::params::{LANDMARK_DECAY, LANDMARK_VISIT_RADIUS, MAX_LANDMARKS};
+//! navigate back to them when energy is low. Users may also pin landmarks
+//! by hand; pinned landmarks never decay, are never evicted by discovered
+//! ones and take priority during goal-directed navigation.
+//!
+//! A rich landmark is worth nothing if the agent starves on the way. Goal
+//! navigation therefore weighs each landmark's value against a `TravelModel`
+//! of the trip: its expected energy cost and the probability of arriving
+//! before the stored energy runs out. Landmarks the agent is unlikely to
+//! reach are not chosen at all.
+
+use crate::simulation::params::{
+    LANDMARK_DECAY, LANDMARK_MIN_ARRIVAL, LANDMARK_VISIT_RADIUS, MAX_LANDMARKS, TRAVEL_COST_SPREAD,
+};
+
+/// Where a landmark came from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LandmarkProvenance {
+    /// Discovered by the agent from high nutrient readings
+    #[default]
+    Observed,
+    /// Pinned manually by the user
+    User,
+}
 
 /// A remembered high-nutrient location.
 #[derive(Clone, Copy, Debug)]
@@ -20,6 +40,8 @@ pub struct Landmark {
     pub visit_count: u64,
     /// Reliability score (decays over time when not visited)
     pub reliability: f64,
+    /// Whether the agent discovered this landmark or the user pinned it
+    pub provenance: LandmarkProvenance,
 }
 
 impl Landmark {
@@ -33,9 +55,27 @@ impl Landmark {
             last_visit_tick: tick,
             visit_count: 1,
             reliability: 1.0,
+            provenance: LandmarkProvenance::Observed,
         }
     }
 
+    /// Creates a user-pinned landmark with maximal value.
+    #[must_use]
+    pub fn pinned(x: f64, y: f64, tick: u64) -> Self {
+        Self {
+            peak_nutrient: 1.0,
+            visit_count: 0,
+            provenance: LandmarkProvenance::User,
+            ..Self::new(x, y, 1.0, tick)
+        }
+    }
+
+    /// Returns true if the user pinned this landmark.
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        self.provenance == LandmarkProvenance::User
+    }
+
     /// Returns the distance from this landmark to a given position.
     #[must_use]
     pub fn distance_to(&self, x: f64, y: f64) -> f64 {
@@ -50,9 +90,11 @@ impl Landmark {
         self.peak_nutrient * self.reliability
     }
 
-    /// Decays the reliability of this landmark.
+    /// Decays the reliability of this landmark. Pinned landmarks do not decay.
     pub fn decay(&mut self) {
-        self.reliability *= LANDMARK_DECAY;
+        if !self.is_pinned() {
+            self.reliability *= LANDMARK_DECAY;
+        }
     }
 
     /// Refreshes the landmark on revisit.
@@ -64,6 +106,41 @@ impl Landmark {
     }
 }
 
+/// What the agent expects swimming somewhere to cost it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TravelModel {
+    /// Energy available for the trip (fast compartment plus reserve)
+    pub stored: f64,
+    /// Energy spent per unit of distance swum
+    pub cost_per_distance: f64,
+}
+
+impl TravelModel {
+    /// Expected energy cost of swimming `distance`.
+    #[must_use]
+    pub fn cost(&self, distance: f64) -> f64 {
+        distance * self.cost_per_distance
+    }
+
+    /// Probability of covering `distance` before the stored energy runs out:
+    /// logistic in the margin `stored - cost`, on the scale of
+    /// `TRAVEL_COST_SPREAD` times the cost.
+    #[must_use]
+    pub fn arrival_probability(&self, distance: f64) -> f64 {
+        let cost = self.cost(distance);
+        let scale = (TRAVEL_COST_SPREAD * cost).max(f64::EPSILON);
+        1.0 / (1.0 + (-(self.stored - cost) / scale).exp())
+    }
+
+    /// Expected worth of heading for `landmark` from (x, y): its value times
+    /// the probability of arriving, less the energy the trip costs.
+    #[must_use]
+    pub fn utility(&self, landmark: &Landmark, x: f64, y: f64) -> f64 {
+        let distance = landmark.distance_to(x, y);
+        self.arrival_probability(distance) * landmark.value() - self.cost(distance)
+    }
+}
+
 /// Episodic memory storing remembered landmarks.
 #[derive(Clone, Debug)]
 pub struct EpisodicMemory {
@@ -85,6 +162,14 @@ impl EpisodicMemory {
         }
     }
 
+    /// Scales every landmark position by (sx, sy), after the dish was resized.
+    pub fn rescale(&mut self, sx: f64, sy: f64) {
+        for landmark in self.landmarks.iter_mut().flatten() {
+            landmark.x *= sx;
+            landmark.y *= sy;
+        }
+    }
+
     /// Returns the number of stored landmarks.
     #[must_use]
     pub fn count(&self) -> usize {
@@ -93,7 +178,7 @@ impl EpisodicMemory {
 
     /// Attempts to store a new landmark if it's valuable enough.
     ///
-    /// If memory is full, replaces the least valuable landmark.
+    /// If memory is full, replaces the least valuable discovered landmark.
     /// If the position is near an existing landmark, updates that one instead.
     pub fn maybe_store(&mut self, x: f64, y: f64, nutrient: f64, tick: u64) {
         // Check if near an existing landmark
@@ -115,6 +200,7 @@ impl EpisodicMemory {
                     target_index = Some(i);
                     break; // Empty slot found, use it
                 }
+                Some(landmark) if landmark.is_pinned() => {}
                 Some(landmark) => {
                     let value = landmark.value();
                     if value < min_value {
@@ -134,6 +220,53 @@ impl EpisodicMemory {
         }
     }
 
+    /// Pins a user landmark at (x, y).
+    ///
+    /// A landmark already within the visit radius is promoted to a pin.
+    /// Otherwise an empty slot or the least valuable discovered landmark is
+    /// used. Returns false if every slot already holds a pin.
+    pub fn pin(&mut self, x: f64, y: f64, tick: u64) -> bool {
+        if let Some(landmark) = self
+            .landmarks
+            .iter_mut()
+            .flatten()
+            .find(|l| l.distance_to(x, y) < LANDMARK_VISIT_RADIUS)
+        {
+            landmark.provenance = LandmarkProvenance::User;
+            landmark.reliability = 1.0;
+            return true;
+        }
+
+        let slot = self.landmarks.iter().position(Option::is_none).or_else(|| {
+            self.landmarks
+                .iter()
+                .enumerate()
+                .filter_map(|(i, slot)| slot.map(|l| (i, l)))
+                .filter(|(_, l)| !l.is_pinned())
+                .min_by(|a, b| a.1.value().total_cmp(&b.1.value()))
+                .map(|(i, _)| i)
+        });
+
+        match slot {
+            Some(i) => {
+                self.landmarks[i] = Some(Landmark::pinned(x, y, tick));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the pinned landmark within the visit radius of (x, y), if any.
+    pub fn unpin(&mut self, x: f64, y: f64) -> bool {
+        for slot in &mut self.landmarks {
+            if slot.is_some_and(|l| l.is_pinned() && l.distance_to(x, y) < LANDMARK_VISIT_RADIUS) {
+                *slot = None;
+                return true;
+            }
+        }
+        false
+    }
+
     /// Decays the reliability of all landmarks.
     pub fn decay_all(&mut self) {
         for slot in &mut self.landmarks {
@@ -170,13 +303,61 @@ impl EpisodicMemory {
     /// Returns the best landmark excluding a given radius from current position.
     ///
     /// Useful for finding a landmark to navigate TO (not the one we're at).
+    /// User-pinned landmarks take priority over discovered ones. Distance
+    /// plays no part; see `best_reachable_landmark` for the trip's cost.
     #[must_use]
+    #[allow(dead_code)] // Used by tests
     pub fn best_distant_landmark(&self, x: f64, y: f64, min_distance: f64) -> Option<&Landmark> {
         self.landmarks
             .iter()
             .filter_map(|slot| slot.as_ref())
             .filter(|l| l.distance_to(x, y) >= min_distance)
-            .max_by(|a, b| a.value().total_cmp(&b.value()))
+            .max_by(|a, b| {
+                a.is_pinned()
+                    .cmp(&b.is_pinned())
+                    .then(a.value().total_cmp(&b.value()))
+            })
+    }
+
+    /// Returns the landmark to navigate toward from (x, y) given the trip's
+    /// `travel` model, excluding those within `min_distance`.
+    ///
+    /// Landmarks reached with probability below `LANDMARK_MIN_ARRIVAL` are
+    /// left out; of the rest, pinned landmarks take priority, then the
+    /// highest `TravelModel::utility`.
+    #[must_use]
+    pub fn best_reachable_landmark(
+        &self,
+        x: f64,
+        y: f64,
+        min_distance: f64,
+        travel: &TravelModel,
+    ) -> Option<&Landmark> {
+        self.landmarks
+            .iter()
+            .filter_map(|slot| slot.as_ref())
+            .filter(|l| {
+                let distance = l.distance_to(x, y);
+                distance >= min_distance
+                    && travel.arrival_probability(distance) >= LANDMARK_MIN_ARRIVAL
+            })
+            .max_by(|a, b| {
+                a.is_pinned()
+                    .cmp(&b.is_pinned())
+                    .then(travel.utility(a, x, y).total_cmp(&travel.utility(b, x, y)))
+            })
+    }
+
+    /// Stores `landmark` as is in the first empty slot; returns false if
+    /// memory is full.
+    pub fn insert(&mut self, landmark: Landmark) -> bool {
+        match self.landmarks.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(landmark);
+                true
+            }
+            None => false,
+        }
     }
 
     /// Returns an iterator over all stored landmarks.
@@ -191,6 +372,7 @@ impl EpisodicMemory {
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
 
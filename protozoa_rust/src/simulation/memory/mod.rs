@@ -12,7 +12,7 @@ pub mod episodic;
 mod ring_buffer;
 pub mod spatial_grid;
 
-pub use episodic::{EpisodicMemory, Landmark};
+pub use episodic::{EpisodicMemory, Landmark, LandmarkProvenance, TravelModel};
 pub use ring_buffer::RingBuffer;
 pub use spatial_grid::{CellPrior, SpatialGrid};
 
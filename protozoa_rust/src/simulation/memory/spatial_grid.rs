@@ -3,6 +3,7 @@
 //! Implements a discretized map of learned nutrient expectations using
 //! Welford's online algorithm for numerically stable variance computation.
 
+use crate::simulation::environment::Environment;
 use crate::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
 
 /// Prior beliefs about nutrient concentration at a grid cell.
@@ -173,6 +174,16 @@ impl<const W: usize, const H: usize> SpatialGrid<W, H> {
         self.get_cell(x, y).mean
     }
 
+    /// Iterates over the cells row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &CellPrior> {
+        self.cells.iter().flatten()
+    }
+
+    /// Iterates mutably over the cells row by row.
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut CellPrior> {
+        self.cells.iter_mut().flatten()
+    }
+
     /// Returns grid dimensions.
     #[must_use]
     #[allow(clippy::unused_self)] // Self needed for consistent API
@@ -180,6 +191,24 @@ impl<const W: usize, const H: usize> SpatialGrid<W, H> {
         (W, H)
     }
 
+    /// Stretches the grid over a resized world.
+    ///
+    /// Every cell keeps its prior and covers the same fraction of the world
+    /// as before, so what was learned about a region stays with that region.
+    #[allow(clippy::cast_precision_loss)] // Grid dimensions are small
+    pub fn resize(&mut self, world_width: f64, world_height: f64) {
+        self.world_width = world_width;
+        self.world_height = world_height;
+        self.cell_width = world_width / W as f64;
+        self.cell_height = world_height / H as f64;
+    }
+
+    /// Returns the world dimensions the grid covers.
+    #[must_use]
+    pub fn world_dimensions(&self) -> (f64, f64) {
+        (self.world_width, self.world_height)
+    }
+
     /// Returns cell dimensions in world units.
     #[must_use]
     pub fn cell_dimensions(&self) -> (f64, f64) {
@@ -196,6 +225,54 @@ impl<const W: usize, const H: usize> SpatialGrid<W, H> {
             .sum()
     }
 
+    /// Returns the fraction of cells visited at least once (spatial coverage).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Grid dimensions are small
+    pub fn visited_fraction(&self) -> f64 {
+        let visited = self
+            .cells
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|cell| cell.visits > 0)
+            .count();
+        visited as f64 / (W * H) as f64
+    }
+
+    /// Returns the believed concentration gradient (∂μ/∂x, ∂μ/∂y) at a position.
+    ///
+    /// Uses finite differences between the learned means of visited cells only:
+    /// central differences when both neighbours along an axis were visited,
+    /// one-sided differences against the cell itself otherwise. Returns `None`
+    /// when no axis has enough visited cells to form a difference.
+    #[must_use]
+    pub fn mean_gradient(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let (row, col) = self.world_to_grid(x, y);
+        let visited = |r: usize, c: usize| {
+            let cell = &self.cells[r][c];
+            (cell.visits > 0).then_some(cell.mean)
+        };
+        let center = visited(row, col);
+
+        let axis = |before: Option<f64>, after: Option<f64>, step: f64| match (before, after) {
+            (Some(b), Some(a)) => Some((a - b) / (2.0 * step)),
+            (Some(b), None) => center.map(|m| (m - b) / step),
+            (None, Some(a)) => center.map(|m| (a - m) / step),
+            (None, None) => None,
+        };
+
+        let left = col.checked_sub(1).and_then(|c| visited(row, c));
+        let right = (col + 1 < W).then(|| visited(row, col + 1)).flatten();
+        let up = row.checked_sub(1).and_then(|r| visited(r, col));
+        let down = (row + 1 < H).then(|| visited(row + 1, col)).flatten();
+
+        let d_x = axis(left, right, self.cell_width);
+        let d_y = axis(up, down, self.cell_height);
+        if d_x.is_none() && d_y.is_none() {
+            return None;
+        }
+        Some((d_x.unwrap_or(0.0), d_y.unwrap_or(0.0)))
+    }
+
     /// Resets all cells to default priors.
     pub fn reset(&mut self) {
         for row in &mut self.cells {
@@ -206,7 +283,28 @@ impl<const W: usize, const H: usize> SpatialGrid<W, H> {
     }
 }
 
+/// The learned map as a world model: concentration is the cell mean and the
+/// gradient the finite difference of visited cells ((0, 0) where none is
+/// known). It only changes through observations (`SpatialGrid::update`), so
+/// a tick leaves it as is.
+impl<const W: usize, const H: usize> Environment for SpatialGrid<W, H> {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        self.expected(x, y)
+    }
+
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        self.mean_gradient(x, y).unwrap_or((0.0, 0.0))
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        self.world_dimensions()
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
 
@@ -1,9 +1,76 @@
 pub mod agent;
+pub mod annotation;
+pub mod arrival;
+pub mod builder;
+pub mod calibration;
+pub mod circadian;
+pub mod clock;
+pub mod config;
+pub mod controller;
+pub mod crowding;
+pub mod cycle;
+pub mod demo;
+pub mod doctor;
+pub mod ensemble;
 pub mod environment;
+pub mod events;
+pub mod evolution;
+pub mod export;
+pub mod fitness;
+pub mod fixtures;
+pub mod flow;
+pub mod footprint;
+pub mod generator;
+pub mod goal;
+pub mod health;
+pub mod hunger;
+pub mod identity;
+pub mod imitation;
 pub mod inference;
+pub mod inheritance;
+pub mod kernel;
+pub mod kinematics;
+pub mod layout;
+pub mod light;
+pub mod lineage;
 pub mod memory;
+pub mod motor_noise;
+pub mod numerics;
+pub mod observer;
+pub mod occupancy;
+pub mod operators;
 pub mod params;
+pub mod pareto;
+pub mod persistence;
+pub mod ph;
+pub mod pheromone;
+pub mod pipeline;
 pub mod planning;
+pub mod population;
+pub mod predator;
+pub mod preset;
+pub mod provenance;
+pub mod replay;
+pub mod reserve;
+pub mod scan;
+pub mod scenario;
+pub mod senescence;
+pub mod sensing;
+pub mod sensor_noise;
+pub mod sensors;
+pub mod signal;
+pub mod social;
+pub mod source_index;
+pub mod spawn;
+pub mod starvation;
+pub mod streams;
+pub mod stress;
+pub mod sweep;
+pub mod thermal;
+pub mod turbulence;
+pub mod vec_env;
+pub mod viscosity;
+pub mod world;
 
 #[allow(unused_imports)] // Used by tests and future UI components
 pub use agent::AgentMode;
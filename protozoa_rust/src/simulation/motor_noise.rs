@@ -0,0 +1,74 @@
+//! Motor noise: what makes a movement differ from its command.
+//!
+//! The controller commands a turn and a speed; the body executes them with
+//! errors. The executed turn adds a constant `turn_bias` (a body that veers
+//! to one side) and white Gaussian noise with standard deviation `turn_std`
+//! radians. The executed speed is the commanded one times `1 + speed_std·z`
+//! for a standard normal `z`, so a resting agent stays at rest, and never
+//! drops below 0.
+//!
+//! The default is noiseless. The planner's rollouts step the noiseless
+//! model, so any motor noise is a mismatch between the plant and the model
+//! the plans are made in. Noise draws come from the agent's own
+//! `Stream::Motor`.
+
+use crate::simulation::streams::standard_normal;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Execution noise on the commanded turn and speed (`[motor_noise]` in a
+/// dish config).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MotorNoise {
+    /// Standard deviation of the white noise on each turn (radians)
+    pub turn_std: f64,
+    /// Constant offset on every turn (radians)
+    pub turn_bias: f64,
+    /// Standard deviation of the relative error on each speed
+    pub speed_std: f64,
+}
+
+impl MotorNoise {
+    /// Returns true if movements execute exactly as commanded.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The `(turn, speed)` actually executed for a commanded `turn` and
+    /// `speed`.
+    pub fn execute(&self, turn: f64, speed: f64, rng: &mut impl Rng) -> (f64, f64) {
+        let turn_noise = if self.turn_std > 0.0 {
+            self.turn_std * standard_normal(rng)
+        } else {
+            0.0
+        };
+        let speed_factor = if self.speed_std > 0.0 {
+            (1.0 + self.speed_std * standard_normal(rng)).max(0.0)
+        } else {
+            1.0
+        };
+        (turn + self.turn_bias + turn_noise, speed * speed_factor)
+    }
+
+    /// Checks that every value is finite and no spread is negative.
+    ///
+    /// # Errors
+    /// Names the first offending key.
+    pub fn validate(&self) -> Result<(), String> {
+        for (key, value) in [
+            ("turn_std", self.turn_std),
+            ("turn_bias", self.turn_bias),
+            ("speed_std", self.speed_std),
+        ] {
+            if !value.is_finite() {
+                return Err(format!("motor_noise.{key} must be finite"));
+            }
+        }
+        if self.turn_std < 0.0 || self.speed_std < 0.0 {
+            return Err("motor_noise.turn_std and speed_std must not be negative".into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+//! Strict numerical mode for CI and debugging sessions.
+//!
+//! By default (`NumericMode::Lenient`) a non-finite value in the agent's
+//! update trips a `debug_assert!` in debug builds and is silently replaced
+//! by 0 in release builds, so a long run keeps going. That hides the bug it
+//! comes from. In `NumericMode::Strict` (`Protozoa::numerics`, `--strict`)
+//! nothing is zeroed: after every phase of the tick the agent checks its
+//! numerical state through the `check_finite` helpers of each subsystem
+//! (`BeliefState`, `GenerativeModel` and the VFE for inference,
+//! `MCTSPlanner` and the rollout `AgentState` for planning, `Morphology`),
+//! each returning the first `NonFinite` value as an error. The first one
+//! halts the agent with a `NumericalFault`: what went wrong, in which phase
+//! and tick, and a full dump of the agent's state at that point. The
+//! runner then stops and writes the dump out.
+
+use std::fmt;
+use std::path::Path;
+
+/// How the agent treats non-finite values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumericMode {
+    /// Zero them and carry on (asserting in debug builds)
+    #[default]
+    Lenient,
+    /// Halt with a state dump at the first one
+    Strict,
+}
+
+impl NumericMode {
+    /// Whether faults halt the agent.
+    #[must_use]
+    pub fn is_strict(self) -> bool {
+        self == Self::Strict
+    }
+}
+
+/// A NaN or infinite value, named by where it was found.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NonFinite {
+    /// Quantity that went non-finite (`"energy"`, `"beliefs.mean.x"`, ...)
+    pub context: &'static str,
+    pub value: f64,
+}
+
+impl fmt::Display for NonFinite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "non-finite {}: {}", self.context, self.value)
+    }
+}
+
+impl std::error::Error for NonFinite {}
+
+/// `value` if it is finite.
+///
+/// # Errors
+/// `NonFinite` naming `context` if it is NaN or infinite.
+pub fn finite(value: f64, context: &'static str) -> Result<f64, NonFinite> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(NonFinite { context, value })
+    }
+}
+
+/// Checks named values in order.
+///
+/// # Errors
+/// The first value that is NaN or infinite.
+pub fn finite_all<const N: usize>(values: [(&'static str, f64); N]) -> Result<(), NonFinite> {
+    values
+        .into_iter()
+        .try_for_each(|(context, value)| finite(value, context).map(|_| ()))
+}
+
+/// The first non-finite value a strict agent met, and its state then.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumericalFault {
+    pub error: NonFinite,
+    /// Phase after which it was found (`Phase::name`, `"sense"` before the
+    /// first phase or `"dormant_step"`)
+    pub stage: &'static str,
+    /// The agent's tick count
+    pub tick: u64,
+    /// Pretty-printed state of the agent
+    pub dump: String,
+}
+
+impl NumericalFault {
+    /// Writes the headline and the state dump to `path`.
+    ///
+    /// # Errors
+    /// If the file cannot be written.
+    pub fn write_dump(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, format!("{self}\n\n{}\n", self.dump))
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+}
+
+impl fmt::Display for NumericalFault {
+    /// `non-finite energy: NaN after metabolize at tick 120`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} after {} at tick {}",
+            self.error, self.stage, self.tick
+        )
+    }
+}
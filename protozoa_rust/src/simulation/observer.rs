@@ -0,0 +1,115 @@
+//! Observer hooks on the dish's source dynamics.
+//!
+//! `PetriDish::update_observed` reports what happened to its sources during
+//! the tick (and when the seasonal regime switched) as `SourceEvent`s, so logs and UI panels can follow the dish
+//! without diffing its internal state. Any `FnMut(u64, SourceEvent)` closure
+//! is a `DishObserver`; `SourceEventLog` keeps the most recent events with
+//! running counts for the dashboard. Plain `update()` reports to nobody.
+
+use crate::simulation::cycle::Season;
+use crate::simulation::params::EVENT_LOG_CAPACITY;
+use std::collections::VecDeque;
+
+/// Something that happened to the source at `index` of `PetriDish::sources`.
+///
+/// Indices refer to the source list during the tick; depleted pulses are
+/// removed after the events fire.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SourceEvent {
+    /// A regular (non-pulse) source decayed below `respawn_threshold`
+    /// (fires once per crossing)
+    Depleted { index: usize, species: usize },
+    /// A depleted source was replaced by a fresh one at (x, y)
+    Respawned {
+        index: usize,
+        species: usize,
+        x: f64,
+        y: f64,
+    },
+    /// Drift carried the source past the dish edge; it was held at (x, y)
+    CrossedEdge { index: usize, x: f64, y: f64 },
+    /// The dish entered `season` (a regime boundary of the `SeasonCycle`)
+    SeasonChanged { season: Season },
+}
+
+/// Receives source events as `PetriDish::update_observed` produces them.
+pub trait DishObserver {
+    /// Called once per event with the dish tick it happened on.
+    fn on_source_event(&mut self, tick: u64, event: SourceEvent);
+}
+
+impl<F: FnMut(u64, SourceEvent)> DishObserver for F {
+    fn on_source_event(&mut self, tick: u64, event: SourceEvent) {
+        self(tick, event);
+    }
+}
+
+/// Bounded log of recent source events with running counts per kind.
+#[derive(Clone, Debug)]
+pub struct SourceEventLog {
+    /// `(tick, event)`, oldest first
+    recent: VecDeque<(u64, SourceEvent)>,
+    capacity: usize,
+    depleted: usize,
+    respawned: usize,
+    crossed_edge: usize,
+    season_changes: usize,
+}
+
+impl Default for SourceEventLog {
+    fn default() -> Self {
+        Self::with_capacity(EVENT_LOG_CAPACITY)
+    }
+}
+
+impl SourceEventLog {
+    /// Creates a log keeping at most `capacity` recent events.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            recent: VecDeque::with_capacity(capacity),
+            capacity,
+            depleted: 0,
+            respawned: 0,
+            crossed_edge: 0,
+            season_changes: 0,
+        }
+    }
+
+    /// Recent events with their ticks, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &(u64, SourceEvent)> {
+        self.recent.iter()
+    }
+
+    /// Events seen so far as (depleted, respawned, crossed edge), including
+    /// evicted ones.
+    #[must_use]
+    pub fn counts(&self) -> (usize, usize, usize) {
+        (self.depleted, self.respawned, self.crossed_edge)
+    }
+
+    /// Season boundaries seen so far, including evicted ones.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn season_changes(&self) -> usize {
+        self.season_changes
+    }
+}
+
+impl DishObserver for SourceEventLog {
+    fn on_source_event(&mut self, tick: u64, event: SourceEvent) {
+        match event {
+            SourceEvent::Depleted { .. } => self.depleted += 1,
+            SourceEvent::Respawned { .. } => self.respawned += 1,
+            SourceEvent::CrossedEdge { .. } => self.crossed_edge += 1,
+            SourceEvent::SeasonChanged { .. } => self.season_changes += 1,
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.recent.len() >= self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((tick, event));
+    }
+}
@@ -0,0 +1,110 @@
+//! Time-spent (occupancy) heatmap in world coordinates.
+//!
+//! Counts how many ticks the agent spends in each cell of a fine world grid.
+//! Unlike the agent's spatial priors this is an observer-side measurement:
+//! it never feeds back into behavior.
+
+use crate::simulation::params::{OCCUPANCY_COLS, OCCUPANCY_ROWS};
+
+/// Visitation counts over a `rows × cols` grid covering the dish.
+#[derive(Clone, Debug)]
+pub struct OccupancyMap {
+    counts: Vec<u32>,
+    rows: usize,
+    cols: usize,
+    world_width: f64,
+    world_height: f64,
+    total: u64,
+}
+
+impl OccupancyMap {
+    /// Creates an empty map at the default resolution for a dish of the given size.
+    #[must_use]
+    pub fn new(world_width: f64, world_height: f64) -> Self {
+        Self::with_resolution(world_width, world_height, OCCUPANCY_ROWS, OCCUPANCY_COLS)
+    }
+
+    /// Creates an empty map with an explicit grid resolution.
+    #[must_use]
+    pub fn with_resolution(world_width: f64, world_height: f64, rows: usize, cols: usize) -> Self {
+        let rows = rows.max(1);
+        let cols = cols.max(1);
+        Self {
+            counts: vec![0; rows * cols],
+            rows,
+            cols,
+            world_width,
+            world_height,
+            total: 0,
+        }
+    }
+
+    /// Stretches the map over a resized dish, keeping the counts per cell.
+    pub fn resize(&mut self, world_width: f64, world_height: f64) {
+        self.world_width = world_width;
+        self.world_height = world_height;
+    }
+
+    /// Converts world coordinates to a (row, col) cell, clamped to the grid.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn cell_of(&self, x: f64, y: f64) -> (usize, usize) {
+        let col = ((x / self.world_width) * self.cols as f64)
+            .floor()
+            .clamp(0.0, (self.cols - 1) as f64) as usize;
+        let row = ((y / self.world_height) * self.rows as f64)
+            .floor()
+            .clamp(0.0, (self.rows - 1) as f64) as usize;
+        (row, col)
+    }
+
+    /// Records one tick spent at (x, y). Non-finite positions are ignored.
+    pub fn record(&mut self, x: f64, y: f64) {
+        if !x.is_finite() || !y.is_finite() {
+            return;
+        }
+        let (row, col) = self.cell_of(x, y);
+        let cell = &mut self.counts[row * self.cols + col];
+        *cell = cell.saturating_add(1);
+        self.total = self.total.saturating_add(1);
+    }
+
+    /// Number of ticks recorded in the cell containing (x, y).
+    #[must_use]
+    pub fn count_at(&self, x: f64, y: f64) -> u32 {
+        let (row, col) = self.cell_of(x, y);
+        self.counts[row * self.cols + col]
+    }
+
+    /// Total ticks recorded since the last reset.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and analysis tooling
+    pub const fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Highest single-cell count.
+    #[must_use]
+    pub fn max_count(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Log-scaled occupancy at (x, y) in [0, 1]: `ln(1 + n) / ln(1 + n_max)`.
+    #[must_use]
+    pub fn log_intensity(&self, x: f64, y: f64) -> f64 {
+        let max = self.max_count();
+        if max == 0 {
+            return 0.0;
+        }
+        f64::from(self.count_at(x, y)).ln_1p() / f64::from(max).ln_1p()
+    }
+
+    /// Clears all counts.
+    pub fn reset(&mut self) {
+        self.counts.fill(0);
+        self.total = 0;
+    }
+}
@@ -0,0 +1,163 @@
+//! Variation operators of the evolutionary loop.
+//!
+//! How offspring differ from their parents decides much of what evolution
+//! finds, so `Evolution` picks its operators instead of hard-coding them.
+//! A `MutationOperator` perturbs a genome gene by gene, a
+//! `CrossoverOperator` combines two parents, and a `StepSize` policy keeps
+//! the mutation scale fixed or adapts it between generations by the 1/5
+//! success rule: when more than `MUTATION_SUCCESS_TARGET` of a generation
+//! beats the previous best the steps grow by `MUTATION_STEP_FACTOR`,
+//! otherwise they shrink by it, within `MUTATION_SCALE_MIN`..`MUTATION_SCALE_MAX`.
+//! For gene i in [lᵢ, hᵢ] of n genes, parents a and b and scale s:
+//!
+//! ```text
+//! gᵢ ← clamp(gᵢ + δ, lᵢ, hᵢ),  δ ~ N(0, (s (hᵢ − lᵢ))²)     Gaussian mutation
+//! gᵢ = bᵢ for i ≥ c,  c ~ U{1, …, n − 1}                   swap crossover
+//! gᵢ = aᵢ + u (bᵢ − aᵢ),  u ~ U[0, 1]                      blend crossover
+//! ```
+//!
+//! The defaults (uniform mutation, uniform crossover, fixed steps) are
+//! `Genome::mutate` and `Genome::crossover`, so default runs are unchanged.
+
+use crate::simulation::evolution::{GENE_COUNT, Gene, Genome};
+use crate::simulation::params::{
+    MUTATION_SCALE_MAX, MUTATION_SCALE_MIN, MUTATION_STEP_FACTOR, MUTATION_SUCCESS_TARGET,
+};
+use crate::simulation::streams::standard_normal;
+use rand::Rng;
+
+/// How a gene of an offspring is mutated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MutationOperator {
+    /// Shift by up to `scale` times the gene's range, uniformly either way
+    #[default]
+    Uniform,
+    /// Gaussian shift with a standard deviation of `scale` times the range
+    Gaussian,
+    /// Back to the default agent's value
+    Reset,
+}
+
+impl MutationOperator {
+    /// All mutation operators, in display order.
+    pub const ALL: [Self; 3] = [Self::Uniform, Self::Gaussian, Self::Reset];
+
+    /// Parses an operator name (`uniform`, `gaussian`, `reset`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|operator| operator.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Lowercase name used on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Uniform => "uniform",
+            Self::Gaussian => "gaussian",
+            Self::Reset => "reset",
+        }
+    }
+
+    /// Mutates each gene of `genome` with probability `rate`, clamped to
+    /// its bounds.
+    pub fn apply(self, genome: &mut Genome, rate: f64, scale: f64, rng: &mut impl Rng) {
+        if self == Self::Uniform {
+            genome.mutate(rate, scale, rng);
+            return;
+        }
+        for (gene, value) in Gene::ALL.iter().zip(&mut genome.genes) {
+            if rng.random::<f64>() < rate {
+                let (lo, hi) = gene.bounds();
+                *value = match self {
+                    Self::Gaussian => {
+                        (*value + standard_normal(rng) * scale * (hi - lo)).clamp(lo, hi)
+                    }
+                    Self::Uniform | Self::Reset => gene.default_value(),
+                };
+            }
+        }
+    }
+}
+
+/// How two parents combine into an offspring.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrossoverOperator {
+    /// Each gene from either parent with equal odds
+    #[default]
+    Uniform,
+    /// Parameter swap: the genes after a random cut come from the second parent
+    Swap,
+    /// Each gene at a random point between the parents' values
+    Blend,
+}
+
+impl CrossoverOperator {
+    /// All crossover operators, in display order.
+    pub const ALL: [Self; 3] = [Self::Uniform, Self::Swap, Self::Blend];
+
+    /// Parses an operator name (`uniform`, `swap`, `blend`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|operator| operator.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Lowercase name used on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Uniform => "uniform",
+            Self::Swap => "swap",
+            Self::Blend => "blend",
+        }
+    }
+
+    /// Offspring of `a` and `b`.
+    #[must_use]
+    pub fn apply(self, a: &Genome, b: &Genome, rng: &mut impl Rng) -> Genome {
+        match self {
+            Self::Uniform => a.crossover(b, rng),
+            Self::Swap => {
+                let cut = rng.random_range(1..GENE_COUNT);
+                let mut child = *a;
+                child.genes[cut..].copy_from_slice(&b.genes[cut..]);
+                child
+            }
+            Self::Blend => {
+                let mut child = *a;
+                for (value, &theirs) in child.genes.iter_mut().zip(&b.genes) {
+                    *value += rng.random::<f64>() * (theirs - *value);
+                }
+                child
+            }
+        }
+    }
+}
+
+/// Whether the mutation scale changes between generations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepSize {
+    /// Always the configured scale
+    #[default]
+    Fixed,
+    /// Adapted by the 1/5 success rule
+    Adaptive,
+}
+
+impl StepSize {
+    /// Scale for the next generation after one in which `success` of the
+    /// individuals beat the previous generation's best.
+    #[must_use]
+    pub fn adapt(self, scale: f64, success: f64) -> f64 {
+        match self {
+            Self::Fixed => scale,
+            Self::Adaptive if success > MUTATION_SUCCESS_TARGET => {
+                (scale * MUTATION_STEP_FACTOR).min(MUTATION_SCALE_MAX)
+            }
+            Self::Adaptive => (scale / MUTATION_STEP_FACTOR).max(MUTATION_SCALE_MIN),
+        }
+    }
+}
@@ -8,7 +8,10 @@ pub const TARGET_CONCENTRATION: f64 = 0.8;
 pub const SENSOR_DIST: f64 = 2.0;
 /// Sensor stereo spread in radians (~28.6 degrees)
 pub const SENSOR_ANGLE: f64 = 0.5;
+/// Most chemoreceptors a `SensorArray` can hold
+pub const MAX_CHEMORECEPTORS: usize = 8;
 pub const LEARNING_RATE: f64 = 0.15;
+/// Maximum movement speed (world units per second)
 pub const MAX_SPEED: f64 = 1.5;
 
 // === Agent Behavior Parameters ===
@@ -23,14 +26,38 @@ pub const EXHAUSTION_THRESHOLD: f64 = 0.01;
 /// Speed multiplier applied when agent is exhausted
 pub const EXHAUSTION_SPEED_FACTOR: f64 = 0.5;
 
+// === Simulation Clock Parameters ===
+/// Simulated seconds per tick; biological rates below are per second
+pub const SECONDS_PER_TICK: f64 = 1.0;
+
 // === Agent Metabolism Parameters ===
-/// Base metabolic energy cost per tick (independent of movement)
+/// Base metabolic energy cost per second (independent of movement)
 pub const BASE_METABOLIC_COST: f64 = 0.0005;
-/// Additional metabolic cost per unit of normalized speed
+/// Additional metabolic cost per second per unit of normalized speed
 pub const SPEED_METABOLIC_COST: f64 = 0.0025;
-/// Energy intake rate per unit of sensed concentration
+/// Energy intake per second per unit of sensed concentration
 pub const INTAKE_RATE: f64 = 0.03;
 
+// === Energy Reserve Parameters ===
+/// Most energy the slow reserve compartment holds
+pub const RESERVE_CAPACITY: f64 = 0.5;
+/// Energy above which the surplus is moved into the reserve
+pub const RESERVE_STORE_LEVEL: f64 = 0.8;
+/// Largest transfer per second from energy into the reserve
+pub const RESERVE_STORE_RATE: f64 = 0.002;
+/// Energy below which the reserve refills it
+pub const RESERVE_RELEASE_LEVEL: f64 = 0.3;
+/// Largest transfer per second from the reserve back into energy
+pub const RESERVE_RELEASE_RATE: f64 = 0.005;
+
+// === Starvation Forecast Parameters ===
+/// Weight of the latest tick in the smoothed energy rates
+pub const STARVATION_SMOOTHING: f64 = 0.05;
+/// Forecast ticks left at and below which planning pressure is full
+pub const STARVATION_URGENT_TICKS: f64 = 50.0;
+/// Forecast ticks left from which the forecast adds no planning pressure
+pub const STARVATION_RELAXED_TICKS: f64 = 300.0;
+
 // === Environment Parameters ===
 pub const DISH_WIDTH: f64 = 100.0;
 /// Adjusted for terminal aspect ratio
@@ -45,11 +72,11 @@ pub const SOURCE_RADIUS_MAX: f64 = 8.0;
 pub const SOURCE_INTENSITY_MIN: f64 = 0.5;
 /// Maximum initial intensity for nutrient sources
 pub const SOURCE_INTENSITY_MAX: f64 = 1.0;
-/// Minimum decay rate for nutrient sources (per tick multiplier)
+/// Minimum decay rate for nutrient sources (per second multiplier)
 pub const SOURCE_DECAY_MIN: f64 = 0.990;
-/// Maximum decay rate for nutrient sources (per tick multiplier)
+/// Maximum decay rate for nutrient sources (per second multiplier)
 pub const SOURCE_DECAY_MAX: f64 = 0.998;
-/// Brownian motion step size for source drift
+/// Brownian source drift per axis over one second (per-tick steps scale with √dt)
 pub const BROWNIAN_STEP: f64 = 0.5;
 /// Intensity threshold below which a source respawns
 pub const RESPAWN_THRESHOLD: f64 = 0.05;
@@ -57,6 +84,20 @@ pub const RESPAWN_THRESHOLD: f64 = 0.05;
 pub const SOURCE_COUNT_MIN: usize = 5;
 /// Maximum number of nutrient sources in dish
 pub const SOURCE_COUNT_MAX: usize = 10;
+/// Simulated seconds per day/night nutrient cycle
+pub const DAY_NIGHT_PERIOD: u64 = 1000;
+/// Night-time reduction of nutrient supply in [0, 1] (0 = no cycle)
+pub const DAY_NIGHT_AMPLITUDE: f64 = 0.0;
+/// Simulated seconds per rich or poor season
+pub const SEASON_LENGTH: u64 = 5000;
+/// Poor-season reduction of nutrient supply in [0, 1] (0 = no seasons)
+pub const SEASON_DEPTH: f64 = 0.0;
+/// Radius of a scheduled nutrient pulse
+pub const PULSE_RADIUS: f64 = 10.0;
+/// Initial intensity of a scheduled nutrient pulse
+pub const PULSE_INTENSITY: f64 = 2.0;
+/// Decay rate of a scheduled nutrient pulse (per second multiplier)
+pub const PULSE_DECAY: f64 = 0.99;
 
 // === Memory Parameters ===
 /// Size of sensor history ring buffer
@@ -87,28 +128,91 @@ pub const LANDMARK_DECAY: f64 = 0.995;
 pub const LANDMARK_ATTRACTION_SCALE: f64 = 0.5;
 /// Distance threshold for considering a landmark "visited"
 pub const LANDMARK_VISIT_RADIUS: f64 = 5.0;
+/// Spread of a trip's actual energy cost around its estimate, as a share of
+/// the estimate (paths detour, sampling costs vary)
+pub const TRAVEL_COST_SPREAD: f64 = 0.5;
+/// Least probability of arriving for a landmark to be navigated toward
+pub const LANDMARK_MIN_ARRIVAL: f64 = 0.5;
+/// World units the landmark pinning cursor moves per arrow key press
+pub const PIN_CURSOR_STEP: f64 = 1.0;
+
+// === External Goal Parameters ===
+/// Position prior precision per unit of goal priority
+pub const GOAL_PRIOR_PRECISION: f64 = 0.01;
+/// Scale factor for heading attraction toward an external goal
+pub const GOAL_ATTRACTION_SCALE: f64 = 0.5;
+
+// === Arrival Detection Parameters ===
+/// Distance within which a goal or landmark counts as reached
+pub const ARRIVAL_RADIUS: f64 = LANDMARK_VISIT_RADIUS;
+/// Consecutive ticks inside the arrival radius needed to confirm arrival
+pub const ARRIVAL_DWELL_TICKS: u64 = 5;
+/// Number of recent arrival events kept for the dashboard
+pub const EVENT_LOG_CAPACITY: usize = 8;
+/// Number of recent parameter-change markers kept
+pub const MARKER_LOG_CAPACITY: usize = 32;
 
 // === Planning Parameters ===
-/// Number of MCTS rollouts per planning step
+/// Number of MCTS rollouts per planning step (fixed budget)
 pub const MCTS_ROLLOUTS: usize = 50;
-/// Maximum depth for MCTS trajectory simulation
+/// Maximum depth for MCTS trajectory simulation (fixed budget)
 pub const MCTS_DEPTH: usize = 10;
-/// Ticks between replanning (unless urgent)
-pub const MCTS_REPLAN_INTERVAL: u64 = 20;
-/// Energy threshold below which replanning becomes urgent
+/// Depth of the adaptive budget when coasting (well-known cell, energy to spare)
+pub const MCTS_MIN_DEPTH: usize = 6;
+/// Depth of the adaptive budget in a crisis (starving or unexplored)
+pub const MCTS_MAX_DEPTH: usize = 12;
+/// Rollouts of the adaptive budget when coasting (at least `MCTS_CACHE_MIN_ROLLOUTS`)
+pub const MCTS_MIN_ROLLOUTS: usize = 20;
+/// Rollouts of the adaptive budget in a crisis
+pub const MCTS_MAX_ROLLOUTS: usize = 60;
+/// Energy threshold below which the agent navigates to remembered landmarks
 pub const MCTS_URGENT_ENERGY: f64 = 0.3;
+/// Energy from which hunger no longer adds to the planning budget
+pub const MCTS_RELAXED_ENERGY: f64 = 0.8;
+/// Per-replan decay of cached rollout estimates (priors drift between replans)
+pub const MCTS_CACHE_DECAY: f64 = 0.8;
+/// Cached estimates below this effective rollout count are evicted
+pub const MCTS_CACHE_MIN_WEIGHT: f64 = 1.0;
+/// Fresh rollouts per action even when the cache is warm
+pub const MCTS_CACHE_MIN_ROLLOUTS: usize = 10;
+/// Energy bins of the rollout cache key
+pub const MCTS_CACHE_ENERGY_BUCKETS: usize = 5;
+/// Maximum rollout cache entries before the weakest are compacted away
+pub const MCTS_CACHE_CAPACITY: usize = 1024;
+/// Fraction of the capacity kept when the rollout cache is compacted
+pub const MCTS_CACHE_COMPACT_RATIO: f64 = 0.75;
 /// Weight for blending planned action with reactive control
 pub const PLANNING_WEIGHT: f64 = 0.3;
 
+// === Plan Commitment Parameters ===
+/// Ticks a plan is followed before replanning (at most; shallow plans run out sooner)
+pub const PLAN_COMMIT_HORIZON: usize = MCTS_DEPTH;
+/// VFE above this multiple of its running average interrupts the plan
+pub const PLAN_SURPRISE_RATIO: f64 = 2.0;
+/// ...and must also exceed the running average by this much (ignores jitter near 0)
+pub const PLAN_SURPRISE_MARGIN: f64 = 0.5;
+/// Smoothing rate of the running VFE average
+pub const PLAN_SURPRISE_SMOOTHING: f64 = 0.1;
+/// Heading weight of the committed plan step
+pub const PLAN_STEP_WEIGHT: f64 = 0.2;
+/// Speed change of the accelerate and decelerate actions
+pub const SPEED_STEP: f64 = 0.3;
+/// Cruise speed of a new active inference agent, before any speed action
+pub const INITIAL_CRUISE_SPEED: f64 = 0.75;
+
 // === Active Inference Parameters ===
 /// Learning rate for belief updates via VFE gradient descent
 pub const BELIEF_LEARNING_RATE: f64 = 0.15;
-/// Maximum VFE value for speed scaling normalization
+/// Maximum VFE value for noise and planning-pressure normalization
 pub const MAX_VFE: f64 = 5.0;
 /// Initial sensory precision (inverse observation variance)
 pub const INITIAL_SENSORY_PRECISION: f64 = 5.0;
 /// Prior precision on nutrient belief (strength of homeostatic preference)
 pub const NUTRIENT_PRIOR_PRECISION: f64 = 2.0;
+/// Preferred energy level (prior mean of the interoceptive channel)
+pub const INTEROCEPTIVE_SETPOINT: f64 = 1.0;
+/// Precision of the interoceptive (energy) channel
+pub const INTEROCEPTIVE_PRECISION: f64 = 2.0;
 /// Minimum sensory precision (prevents over-trust of noisy sensors)
 pub const MIN_SENSORY_PRECISION: f64 = 0.5;
 /// Maximum sensory precision (prevents over-confidence)
@@ -117,15 +221,74 @@ pub const MAX_SENSORY_PRECISION: f64 = 20.0;
 pub const UNCERTAINTY_GROWTH: f64 = 1.1;
 /// Uncertainty reduction factor after observation
 pub const UNCERTAINTY_REDUCTION: f64 = 0.95;
+/// Smallest belief variance the EKF inference scheme reaches
+pub const EKF_VARIANCE_FLOOR: f64 = 0.001;
+
+// === Volatility Parameters ===
+/// Coupling κ of the expected change of the readings to the estimated
+/// log-volatility (0 = a stationary world, fixed learning rates)
+pub const VOLATILITY_COUPLING: f64 = 0.0;
+/// Tonic log step variance ω of the mean reading per sample
+pub const VOLATILITY_TONIC: f64 = -6.0;
+/// Meta-volatility ϑ: how fast the log-volatility itself may drift
+pub const VOLATILITY_META: f64 = 0.05;
+/// Precision of a mean reading about the level it tracks
+pub const VOLATILITY_INPUT_PRECISION: f64 = 400.0;
+/// Largest factor (and smallest, inverted) volatility scales learning rates by
+pub const VOLATILITY_MAX_FACTOR: f64 = 2.0;
+
+// === Likelihood Learning Parameters ===
+/// Rate at which the observation function's gain and offsets follow the
+/// prediction errors (0 = the fixed observation function)
+pub const LIKELIHOOD_LEARNING_RATE: f64 = 0.0;
+/// Initial gain of the lateral term of the chemoreceptor prediction
+pub const LIKELIHOOD_GAIN: f64 = 0.2;
+/// Largest learned gain
+pub const LIKELIHOOD_MAX_GAIN: f64 = 1.0;
+/// Largest learned offset of either chemoreceptor channel
+pub const LIKELIHOOD_MAX_BIAS: f64 = 0.2;
+
+// === Transition Learning Parameters ===
+/// Rate at which the speed gain, turn gain and drift of the agent's motion
+/// model follow its proprioceived moves (0 = the command is the motion)
+pub const TRANSITION_LEARNING_RATE: f64 = 0.0;
+/// Largest learned speed or turn gain
+pub const TRANSITION_MAX_GAIN: f64 = 2.0;
+/// Largest learned drift per tick
+pub const TRANSITION_MAX_DRIFT: f64 = 0.5;
+/// Regularizer ε of the normalized gain updates (keeps small commands from
+/// making large steps)
+pub const TRANSITION_REGULARIZER: f64 = 0.01;
+
+// === Particle Filter Parameters ===
+/// Particles tracked by `--particles` without a count
+pub const PARTICLE_COUNT: usize = 256;
+/// Most particles an agent tracks
+pub const MAX_PARTICLES: usize = 4096;
+/// Width (σ) of the Gaussian patch each particle hypothesizes
+pub const PARTICLE_PATCH_RADIUS: f64 = 5.0;
+/// Standard deviation of a reading about the patch a particle predicts
+pub const PARTICLE_OBSERVATION_NOISE: f64 = 0.1;
+/// Resample once the effective sample size drops below this share of the particles
+pub const PARTICLE_RESAMPLE_THRESHOLD: f64 = 0.5;
+/// Standard deviation of the jitter added to resampled particles
+pub const PARTICLE_JITTER: f64 = 1.0;
+
+// === Hunger Parameters ===
+/// Gain of the modulation of preferences by the energy deficit (0 = fixed
+/// nutrient precision and hard energy thresholds)
+pub const HUNGER_GAIN: f64 = 0.0;
+/// Energy deficit at which food and information keep their base weights
+pub const HUNGER_PIVOT: f64 = 0.5;
 
 // === Morphogenesis Parameters ===
 pub const SURPRISE_THRESHOLD: f64 = 2.0;
 pub const FRUSTRATION_THRESHOLD: f64 = 5.0;
-/// Energy cost per unit change in sensor_dist (proportional to change magnitude)
+/// Energy cost per unit change in `sensor_dist` (proportional to change magnitude)
 pub const SENSOR_DIST_ENERGY_COST: f64 = 0.1;
-/// Energy cost per unit change in sensor_angle (proportional to change magnitude)
+/// Energy cost per unit change in `sensor_angle` (proportional to change magnitude)
 pub const SENSOR_ANGLE_ENERGY_COST: f64 = 0.05;
-/// Energy cost per unit change in belief_learning_rate (proportional to change magnitude)
+/// Energy cost per unit change in `belief_learning_rate` (proportional to change magnitude)
 pub const LEARNING_RATE_ENERGY_COST: f64 = 0.02;
 /// Maximum sensor distance (physiological limit)
 pub const MAX_SENSOR_DIST: f64 = 50.0;
@@ -159,7 +322,492 @@ pub const FRACTAL_DIMENSION_FACTOR: f64 = 0.2;
 /// === Complexity-Based Physiological Limits ===
 /// Maximum allowed structural complexity (soft limit, can be exceeded but with penalties)
 pub const MAX_COMPLEXITY: f64 = 10.0;
-/// Multiplier for energy costs when complexity exceeds MAX_COMPLEXITY
+/// Multiplier for energy costs when complexity exceeds `MAX_COMPLEXITY`
 pub const COMPLEXITY_ENERGY_COST_MULTIPLIER: f64 = 2.0;
 /// Complexity history buffer size for tracking evolution
 pub const COMPLEXITY_HISTORY_SIZE: usize = 100;
+
+// === Spawn Parameters ===
+/// Margin from dish edges for uniformly sampled start positions
+pub const SPAWN_MARGIN: f64 = 5.0;
+/// Rejection-sampling attempts for constrained start positions
+pub const SPAWN_MAX_ATTEMPTS: usize = 200;
+
+// === Flow Parameters ===
+/// Speed of the uniform current (world units per second); 0 disables it
+pub const FLOW_STRENGTH: f64 = 0.0;
+/// Direction of the uniform current in radians (0 = +x)
+pub const FLOW_DIRECTION: f64 = 0.0;
+/// Angular velocity of the central vortex (radians per second); 0 disables it
+pub const FLOW_VORTICITY: f64 = 0.0;
+/// Spacing in field cells between rendered flow glyphs
+pub const FLOW_GLYPH_SPACING: usize = 6;
+/// Minimum flow speed at which a glyph is drawn
+pub const FLOW_GLYPH_MIN_SPEED: f64 = 0.01;
+
+// === Ensemble Parameters ===
+/// Default number of concurrent runs in the aggregate view
+pub const ENSEMBLE_DEFAULT_RUNS: usize = 8;
+/// Number of aggregate samples kept for live curves
+pub const ENSEMBLE_HISTORY_LEN: usize = 200;
+
+// === Occupancy Parameters ===
+/// Columns of the time-spent heatmap grid (one per world unit by default)
+pub const OCCUPANCY_COLS: usize = 100;
+/// Rows of the time-spent heatmap grid
+pub const OCCUPANCY_ROWS: usize = 50;
+
+// === Thermal Parameters ===
+/// Number of hot/cold spots in the default dish
+pub const THERMAL_SOURCE_COUNT: usize = 2;
+/// Baseline temperature of the dish
+pub const AMBIENT_TEMPERATURE: f64 = 0.5;
+/// Minimum radius of a thermal spot
+pub const THERMAL_RADIUS_MIN: f64 = 10.0;
+/// Maximum radius of a thermal spot
+pub const THERMAL_RADIUS_MAX: f64 = 20.0;
+/// Largest temperature offset at a spot center
+pub const THERMAL_INTENSITY_MAX: f64 = 0.4;
+/// Preferred temperature (thermal homeostatic target)
+pub const PREFERRED_TEMPERATURE: f64 = 0.5;
+/// Prior precision on temperature belief (strength of thermal preference)
+pub const TEMPERATURE_PRIOR_PRECISION: f64 = 1.0;
+/// Gain of the reactive thermotaxis steering term
+pub const THERMAL_STEERING_GAIN: f64 = 0.1;
+
+// === Light Parameters ===
+/// Direction of increasing brightness in radians (0 = +x)
+pub const LIGHT_DIRECTION: f64 = 0.0;
+/// Brightness at the dish center
+pub const LIGHT_AMBIENT: f64 = 0.5;
+/// Brightness difference between the brightest and darkest edge
+pub const LIGHT_CONTRAST: f64 = 1.0;
+/// Default light sensitivity (> 0 phototaxis, < 0 photophobia, 0 blind)
+pub const LIGHT_SENSITIVITY: f64 = 0.0;
+/// Gain of the phototaxis steering term
+pub const PHOTOTAXIS_GAIN: f64 = 1.0;
+
+// === Viscosity Parameters ===
+/// Viscous zones per generated dish (0 = plain medium everywhere)
+pub const VISCOSITY_ZONE_COUNT: usize = 0;
+/// Smallest viscous zone radius
+pub const VISCOSITY_RADIUS_MIN: f64 = 6.0;
+/// Largest viscous zone radius
+pub const VISCOSITY_RADIUS_MAX: f64 = 14.0;
+/// Smallest extra viscosity at a zone center
+pub const VISCOSITY_EXCESS_MIN: f64 = 1.0;
+/// Largest extra viscosity at a zone center (3.0 = 4× drag)
+pub const VISCOSITY_EXCESS_MAX: f64 = 3.0;
+/// Viscosity above which empty dish cells show the background texture
+pub const VISCOSITY_TEXTURE_THRESHOLD: f64 = 1.25;
+
+// === pH Parameters ===
+/// Baseline (neutral) pH, normalized so 0 is acidic and 1 alkaline
+pub const NEUTRAL_PH: f64 = 0.5;
+/// Acidic/alkaline spots per generated dish (0 = neutral everywhere)
+pub const PH_SPOT_COUNT: usize = 0;
+/// Smallest pH spot radius
+pub const PH_RADIUS_MIN: f64 = 8.0;
+/// Largest pH spot radius
+pub const PH_RADIUS_MAX: f64 = 16.0;
+/// Largest pH offset at a spot center
+pub const PH_SHIFT_MAX: f64 = 0.4;
+/// Preferred pH (default `Morphology::preferred_ph` set-point)
+pub const PREFERRED_PH: f64 = 0.5;
+/// Prior precision on pH (strength of the pH preference)
+pub const PH_PRIOR_PRECISION: f64 = 1.0;
+/// Gain of the reactive pH steering term
+pub const PH_STEERING_GAIN: f64 = 0.1;
+
+// === Procedural Generator Parameters ===
+/// Obstacles placed by `GeneratorConfig::default()` (0 = open dish like `PetriDish::new`)
+pub const GENERATOR_OBSTACLE_COUNT: usize = 0;
+/// Smallest generated obstacle side (above two steps at `MAX_SPEED`, so walls cannot be skipped)
+pub const GENERATOR_OBSTACLE_SIZE_MIN: f64 = 4.0;
+/// Largest generated obstacle side
+pub const GENERATOR_OBSTACLE_SIZE_MAX: f64 = 12.0;
+/// Draws per obstacle before giving up on placing it
+pub const GENERATOR_MAX_ATTEMPTS: usize = 100;
+
+// === Environment Preset Parameters ===
+/// Radius of the point-like sources in the ring, maze and corridor presets
+pub const PRESET_SOURCE_RADIUS: f64 = 4.0;
+/// Peak intensity of preset sources
+pub const PRESET_SOURCE_INTENSITY: f64 = 1.0;
+/// Number of sources in the ring preset
+pub const PRESET_RING_SOURCES: usize = 8;
+/// Maze wall thickness (at least two steps at `MAX_SPEED`, so walls cannot be skipped)
+pub const MAZE_WALL_THICKNESS: f64 = 3.0;
+
+// === Gradient Layer Parameters ===
+/// Minimum gradient magnitude (concentration per world unit) drawn as an arrow
+pub const GRADIENT_GLYPH_MIN: f64 = 1e-3;
+
+// === Swarm View Parameters ===
+/// Living agents in the shown dish above which the nutrient view switches to
+/// the agent density layer
+pub const SWARM_VIEW_THRESHOLD: usize = 50;
+
+// === Frame Budget Parameters ===
+/// Weight of the newest frame in the smoothed frame time
+pub const FRAME_BUDGET_SMOOTHING: f64 = 0.2;
+/// Consecutive frames over budget before the dashboard degrades one level
+pub const FRAME_BUDGET_DEGRADE_FRAMES: u32 = 10;
+/// Consecutive frames with headroom before it restores one level
+pub const FRAME_BUDGET_RESTORE_FRAMES: u32 = 60;
+/// Share of the budget the smoothed frame time must stay under to count as
+/// headroom
+pub const FRAME_BUDGET_HEADROOM: f64 = 0.5;
+/// While the field is reused, it is still recomputed every this many frames
+pub const FRAME_FIELD_REFRESH: u64 = 5;
+
+// === Multi-Species Parameters ===
+/// Number of nutrient species in the default dish
+pub const NUTRIENT_SPECIES: usize = 1;
+/// Gain of the per-species reactive steering term
+pub const SPECIES_STEERING_GAIN: f64 = 0.1;
+
+// === Agent Identity Parameters ===
+/// Longest agent name (`identity::validate_name`)
+pub const AGENT_NAME_MAX_LEN: usize = 24;
+/// Lineage records kept before the oldest dead ones are compacted away
+pub const LINEAGE_CAPACITY: usize = 10_000;
+/// Dead agents a population keeps before dropping the earliest added
+pub const DEAD_AGENT_CAPACITY: usize = 64;
+
+// === Multi-Dish World Parameters ===
+/// Radius of a portal opening
+pub const PORTAL_RADIUS: f64 = 3.0;
+/// Distance of chained portals from the dish edge
+pub const PORTAL_MARGIN: f64 = 6.0;
+
+// === Source Index Parameters ===
+/// Cell side of the `SourceIndex` spatial hash (world units)
+pub const SOURCE_INDEX_CELL: f64 = 10.0;
+/// Radii beyond which an indexed query ignores a Gaussian source (other
+/// kernels are cut off at the same profile level, see `Kernel::reach`)
+pub const SOURCE_INDEX_CUTOFF: f64 = 5.0;
+
+// === Source Kernels ===
+/// Ring width of the annulus kernel, in source radii
+pub const ANNULUS_WIDTH: f64 = 0.5;
+
+// === Export Parameters ===
+/// Ticks simulated by `export` without `--ticks`
+pub const EXPORT_DEFAULT_TICKS: u64 = 2000;
+
+// === Sweep Parameters ===
+/// Seeds per value run by `sweep` without `--seeds`
+pub const SWEEP_DEFAULT_SEEDS: usize = 8;
+/// Ticks per cell run by `sweep` without `--ticks`
+pub const SWEEP_DEFAULT_TICKS: u64 = 1000;
+
+// === Replay Parameters ===
+/// Ticks recorded by `replay` without `--ticks`
+pub const REPLAY_DEFAULT_TICKS: u64 = 2000;
+/// Ticks between stored keyframes (seeks step forward at most this far)
+pub const REPLAY_KEYFRAME_INTERVAL: u64 = 50;
+/// Playback speeds in ticks per frame, slowest first
+pub const REPLAY_SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+/// Ticks of trail drawn behind each compared run (`replay --compare`)
+pub const REPLAY_TRAIL_TICKS: u64 = 150;
+
+// === Dish Fitting Parameters ===
+/// World units per terminal column of the dish panel under `--fit`
+pub const FIT_UNITS_PER_COLUMN: f64 = 1.0;
+/// World units per terminal row under `--fit` (cells are about twice as tall as wide)
+pub const FIT_UNITS_PER_ROW: f64 = 2.0;
+
+// === Sensory Turbulence Parameters ===
+/// Largest turbulent offset on sensed concentration (0 = off)
+pub const TURBULENCE_AMPLITUDE: f64 = 0.0;
+/// Eddy size in world units
+pub const TURBULENCE_SCALE: f64 = 8.0;
+/// Sim-seconds for the turbulence pattern to decorrelate
+pub const TURBULENCE_PERIOD: f64 = 4.0;
+
+// === Sensor Calibration Parameters ===
+/// Step size of the learned left/right sensor bias correction (0 = off)
+pub const SENSOR_BIAS_LEARNING_RATE: f64 = 0.0;
+/// Largest learned bias correction per sensor
+pub const SENSOR_BIAS_MAX: f64 = 0.5;
+
+// === Sensor Suite Parameters ===
+/// Most sensors an agent's suite holds besides its chemoreceptor array
+pub const MAX_SUITE_SENSORS: usize = 8;
+/// Length of the touch feelers as a share of the sensor reach
+pub const TOUCH_REACH: f64 = 0.5;
+
+// === Sensing Cost Parameters ===
+/// Energy per second of sampling every tick, per unit of sensor distance (0 = free sensing)
+pub const SENSING_COST: f64 = 0.0;
+/// Sampling intervals in ticks the agent chooses between (shortest first)
+pub const SENSING_INTERVALS: [u32; 3] = [1, 2, 4];
+
+// === Saccade Parameters ===
+/// Extra sensor spread in radians of a saccade, a one-tick sweep (0 = no saccades)
+pub const SACCADE_SWEEP: f64 = 0.0;
+/// Energy per second of a saccade (paid on the tick of the sweep)
+pub const SACCADE_COST: f64 = 0.002;
+
+// === Active Sensing Scan Parameters ===
+/// Orientations a scan sweeps the sensor pair through, evenly around the body
+/// (0 = no scans; at least 3 to fit a bearing)
+pub const SCAN_ORIENTATIONS: u32 = 0;
+/// Energy per second of a scan (paid on the tick of the sweep)
+pub const SCAN_COST: f64 = 0.002;
+/// Growth per second of the gradient-bearing variance (radians²) between scans
+pub const BEARING_DIFFUSION: f64 = 0.01;
+/// Variance of a bearing known nothing about (uniform on the circle, π²/3)
+pub const BEARING_MAX_VAR: f64 = std::f64::consts::PI * std::f64::consts::PI / 3.0;
+/// Harmonic amplitude of the readings expected before the first scan
+pub const SCAN_PRIOR_AMPLITUDE: f64 = 0.1;
+/// Turn toward the believed gradient bearing at full confidence
+pub const SCAN_TURN_GAIN: f64 = 0.3;
+
+// === Death Parameters ===
+/// Consecutive ticks at zero energy before an agent dies
+pub const DEATH_STARVATION_TICKS: u64 = 100;
+/// Radius of the nutrient deposit left at a corpse
+pub const CORPSE_RADIUS: f64 = 4.0;
+/// Initial intensity of a corpse deposit
+pub const CORPSE_INTENSITY: f64 = 0.5;
+/// Decay rate of a corpse deposit (per second multiplier)
+pub const CORPSE_DECAY: f64 = 0.995;
+
+// === Health Parameters ===
+/// Health lost per second with both sensors in the toxic void
+pub const TOXIN_DAMAGE_RATE: f64 = 0.002;
+/// Health lost on a collision with an obstacle at `MAX_SPEED`
+pub const COLLISION_DAMAGE: f64 = 0.02;
+/// Sensor reach beyond which the body is strained
+pub const STRAIN_SENSOR_DIST: f64 = 20.0;
+/// Health lost per second with the sensors at `MAX_SENSOR_DIST`
+pub const STRAIN_DAMAGE_RATE: f64 = 0.002;
+/// Health regained per second while energy is at least `HEALTH_REGEN_ENERGY`
+pub const HEALTH_REGEN_RATE: f64 = 0.001;
+/// Energy from which health regenerates
+pub const HEALTH_REGEN_ENERGY: f64 = 0.5;
+
+// === Dormancy Parameters ===
+/// Energy below which an agent with no landmark to head for and nothing to
+/// eat goes dormant (0 = never)
+pub const DORMANCY_THRESHOLD: f64 = 0.0;
+/// Share of `BASE_METABOLIC_COST` a dormant agent pays
+pub const DORMANT_METABOLIC_FACTOR: f64 = 0.05;
+/// Ticks between the samples of a dormant agent
+pub const DORMANT_SAMPLE_INTERVAL: u32 = 10;
+/// Mean reading at which a dormant agent wakes, and below which it may go dormant
+pub const WAKE_CONCENTRATION: f64 = 0.3;
+
+// === Senescence Parameters ===
+/// Age in seconds at which an aging agent starts to decline
+pub const SENESCENCE_ONSET: f64 = 1000.0;
+/// Exponential decline of vigor per second past the onset (0 = agents never age)
+pub const SENESCENCE_RATE: f64 = 0.0;
+/// Vigor an aging agent levels off at (share of its young limits)
+pub const SENESCENCE_FLOOR: f64 = 0.25;
+
+// === Persistence Parameters ===
+/// Persistence length of the heading during exploration (0 = uncorrelated
+/// exploration, free reversals)
+pub const PERSISTENCE_LENGTH: f64 = 0.0;
+/// Weight of the quadratic cost of turning against the last turn, at full
+/// heading correlation
+pub const TURNING_COST: f64 = 2.0;
+
+// === Kinematics Parameters ===
+/// Mass of the body, resisting changes of speed (0 = commands take effect at once)
+pub const KINEMATIC_MASS: f64 = 0.0;
+/// Moment of inertia, resisting changes of turning (0 = turns take effect at once)
+pub const KINEMATIC_MOMENT: f64 = 0.0;
+/// Linear drag coefficient of a body with the default sensor length
+pub const LINEAR_DRAG: f64 = 1.0;
+/// Rotational drag coefficient of a body with the default sensor length
+pub const ROTATIONAL_DRAG: f64 = 1.0;
+
+// === Circadian Parameters ===
+/// Free-running period of the circadian clock in seconds (one default day)
+pub const CIRCADIAN_PERIOD: f64 = 1000.0;
+/// Share of metabolism and exploration dropped at believed midnight (0 = no clock)
+pub const CIRCADIAN_DEPTH: f64 = 0.0;
+/// Night dimming of daylight the generative model expects
+pub const CIRCADIAN_AMPLITUDE: f64 = 0.5;
+/// Sensory precision of the daylight reading the clock is entrained by
+pub const DAYLIGHT_PRECISION: f64 = 20.0;
+/// Growth of the phase belief's variance per second of free-running
+pub const CIRCADIAN_PHASE_DIFFUSION: f64 = 1e-3;
+/// Largest phase variance (about that of a uniform phase, π²/3)
+pub const MAX_PHASE_VARIANCE: f64 = 3.0;
+
+// === Stress Parameters ===
+/// Share of sensory precision lost at full stress (0 = no stress response)
+pub const STRESS_PRECISION_DEPTH: f64 = 0.0;
+/// Extra heading noise at full stress (0 = no stress response)
+pub const STRESS_NOISE_GAIN: f64 = 0.0;
+/// VFE at which stress is driven halfway to its maximum
+pub const STRESS_HALF_VFE: f64 = 3.0;
+/// Time constant of rising stress in seconds
+pub const STRESS_RISE_TIME: f64 = 20.0;
+/// Time constant of recovering stress in seconds
+pub const STRESS_RECOVERY_TIME: f64 = 100.0;
+/// Stress at and above which an agent with a stress response panics
+pub const STRESS_PANIC_LEVEL: f64 = 0.5;
+
+// === Predator Parameters ===
+/// Predators spawned per run without `--predators`
+pub const PREDATOR_DEFAULT_COUNT: usize = 0;
+/// Predator swimming speed (a little below the prey's top speed)
+pub const PREDATOR_SPEED: f64 = 1.2;
+/// Largest predator heading change per tick in radians
+pub const PREDATOR_TURN_RATE: f64 = 0.35;
+/// Distance within which a predator notices prey
+pub const PREDATOR_SENSE_RADIUS: f64 = 40.0;
+/// Distance within which a predator drains its prey
+pub const PREDATOR_CONTACT_RADIUS: f64 = 2.5;
+/// Energy per second a predator in contact drains from its prey
+pub const PREDATOR_DRAIN: f64 = 0.05;
+/// Width (σ) of the threat scent a predator gives off
+pub const THREAT_SCENT_RADIUS: f64 = 10.0;
+/// Prior precision on threat (preferred threat is zero)
+pub const THREAT_PRIOR_PRECISION: f64 = 4.0;
+/// Weight of the learned threat map in planning rollouts
+pub const THREAT_RISK_WEIGHT: f64 = 2.0;
+/// Sensed threat that interrupts a committed plan
+pub const THREAT_HAZARD_LEVEL: f64 = 0.5;
+
+// === Pheromone Parameters ===
+/// Columns of the pheromone raster covering the dish
+pub const PHEROMONE_COLS: usize = 100;
+/// Rows of the pheromone raster covering the dish
+pub const PHEROMONE_ROWS: usize = 50;
+/// Decay rate of trail levels (per second multiplier)
+pub const PHEROMONE_DECAY: f64 = 0.98;
+/// Trail level below which a cell is cleared
+pub const PHEROMONE_FLOOR: f64 = 1e-3;
+/// Default trail deposited per second (0 = lays no trail)
+pub const PHEROMONE_DEPOSIT: f64 = 0.0;
+/// Default trail sensitivity (> 0 follows trails, < 0 avoids them, 0 ignores them)
+pub const PHEROMONE_SENSITIVITY: f64 = 0.0;
+/// Gain of the trail-following steering term
+pub const PHEROMONE_GAIN: f64 = 1.0;
+
+// === Quorum-Sensing Parameters ===
+/// Spread (standard deviation) of a broadcast signal in world units
+pub const SIGNAL_RADIUS: f64 = 15.0;
+/// Decay rate of broadcast signals (per second multiplier)
+pub const SIGNAL_DECAY: f64 = 0.5;
+/// Broadcast level below which a signal is dropped
+pub const SIGNAL_FLOOR: f64 = 0.01;
+/// Default signal emitted per second while exploiting (0 = silent)
+pub const SIGNAL_EMISSION: f64 = 0.0;
+/// Default precision of the preference for sensing signal (0 = ignores it)
+pub const SIGNAL_PRIOR_PRECISION: f64 = 0.0;
+/// Gain of the recruitment steering term
+pub const SIGNAL_STEERING_GAIN: f64 = 0.5;
+
+// === Crowding Parameters ===
+/// Distance below which two agents push each other apart
+pub const COLLISION_RADIUS: f64 = 2.0;
+/// Share of the overlap removed per tick by the soft collision (0 = agents pass through)
+pub const COLLISION_STIFFNESS: f64 = 0.0;
+/// Distance within which another agent counts as a crowding neighbor
+pub const CROWDING_RADIUS: f64 = 8.0;
+/// Energy per second lost for each crowding neighbor (0 = no penalty)
+pub const CROWDING_COST: f64 = 0.0;
+
+// === Social Sensing Parameters ===
+/// Distance within which agents observe each other
+pub const SOCIAL_RADIUS: f64 = 15.0;
+/// Preferred neighbor density of aggregating and schooling agents
+pub const SOCIAL_TARGET_DENSITY: f64 = 2.0;
+/// Precision of the density preference installed by a social mode
+pub const SOCIAL_PRIOR_PRECISION: f64 = 1.0;
+/// Alignment preference of schooling agents
+pub const SOCIAL_ALIGNMENT: f64 = 1.0;
+/// Gain of the density steering term
+pub const SOCIAL_STEERING_GAIN: f64 = 0.1;
+/// Gain of the alignment steering term
+pub const SOCIAL_ALIGNMENT_GAIN: f64 = 0.2;
+
+// === Dashboard Layout Parameters ===
+/// Terminal width (columns) from which the dashboard adds a detail column
+pub const WIDE_TERMINAL_COLUMNS: u16 = 200;
+
+// === Evolution Parameters ===
+/// Genomes per generation
+pub const EVOLUTION_POPULATION: usize = 16;
+/// Generations of a default `evolve` run
+pub const EVOLUTION_GENERATIONS: usize = 10;
+/// Lifetime cap in ticks of one evaluation (survivors score this)
+pub const EVOLUTION_TICKS: u64 = 5000;
+/// Evaluation seeds per genome (its fitness is the mean over them)
+pub const EVOLUTION_SEEDS: usize = 3;
+/// Best genomes copied unchanged into the next generation
+pub const EVOLUTION_ELITE: usize = 2;
+/// Genomes drawn per tournament when picking a parent
+pub const EVOLUTION_TOURNAMENT: usize = 3;
+/// Chance that a gene mutates in an offspring
+pub const MUTATION_RATE: f64 = 0.2;
+/// Largest mutation step, as a share of the gene's range
+pub const MUTATION_SCALE: f64 = 0.1;
+/// Share of a generation beating the previous best above which adaptive
+/// mutation steps grow (the 1/5 success rule)
+pub const MUTATION_SUCCESS_TARGET: f64 = 0.2;
+/// Factor adaptive mutation steps grow or shrink by per generation
+pub const MUTATION_STEP_FACTOR: f64 = 1.22;
+/// Smallest adaptive mutation scale
+pub const MUTATION_SCALE_MIN: f64 = 0.01;
+/// Largest adaptive mutation scale
+pub const MUTATION_SCALE_MAX: f64 = 0.5;
+/// Lowest evolvable prior precision on nutrient
+pub const MIN_PRIOR_PRECISION: f64 = 0.1;
+/// Highest evolvable prior precision on nutrient
+pub const MAX_PRIOR_PRECISION: f64 = 10.0;
+/// Recent generations drawn as box plots on the evolution dashboard
+pub const EVOLUTION_BOX_PLOTS: usize = 8;
+/// Bins of the evolution dashboard's fitness histogram
+pub const EVOLUTION_HISTOGRAM_BINS: usize = 12;
+/// Ticks the champion preview advances per dashboard frame
+pub const EVOLUTION_PREVIEW_STEPS: u64 = 4;
+
+// === Baseline Controller Parameters ===
+/// Largest heading change per tick of the random walk (radians)
+pub const RANDOM_WALK_TURN: f64 = 0.5;
+/// Heading change per unit of left-right reading difference (gradient follower)
+pub const GRADIENT_FOLLOWER_GAIN: f64 = 5.0;
+/// Crossed sensor-to-motor gain of the Braitenberg vehicle
+pub const BRAITENBERG_GAIN: f64 = 5.0;
+/// Speed of the random walk, the gradient follower and run-and-tumble, as a
+/// share of `MAX_SPEED`
+pub const BASELINE_SPEED_FRACTION: f64 = 0.5;
+/// Tumble probability per tick of the run-and-tumble controller when the
+/// reading is steady
+pub const TUMBLE_BASE_RATE: f64 = 0.1;
+/// Suppression of tumbling per unit rise of the mean reading per tick
+/// (run-and-tumble)
+pub const TUMBLE_GRADIENT_GAIN: f64 = 100.0;
+
+// === Inheritance Parameters ===
+/// Largest noise on inherited nutrient expectations (`--beliefs parent`)
+pub const INHERITANCE_NOISE: f64 = 0.05;
+
+// === Demo Parameters ===
+/// Seed of the built-in `demo` tour without `--seed` (fixed, so every
+/// showing plays out the same)
+pub const DEMO_SEED: u64 = 7;
+
+// === Imitation Parameters ===
+/// Default bootstrap strength of `--imitate`: the share of demonstrated
+/// visits kept in the spatial priors and the top weight of a habit
+pub const IMITATION_STRENGTH: f64 = 0.5;
+/// Gain of the turn toward the demonstrated heading at full habit weight
+pub const HABIT_GAIN: f64 = 0.3;
+
+// === Doctor Parameters ===
+/// Ticks per seeded run in the `doctor` determinism digest
+pub const DOCTOR_TICKS: u64 = 200;
+/// Seeds in the `doctor` determinism digest
+pub const DOCTOR_SEEDS: u64 = 4;
+/// Ticks per scenario in the `doctor` NaN stress test
+pub const DOCTOR_STRESS_TICKS: u64 = 300;
@@ -0,0 +1,132 @@
+//! Pareto ranking for multi-objective evolution (NSGA-II).
+//!
+//! A scalar fitness has to decide up front how much lifespan is worth in
+//! coverage or energy, which hides the trade-offs between them. With
+//! `Selection::Pareto`, `Evolution` instead keeps each individual's
+//! objective vector (from `Fitness::objectives`) and ranks a generation the
+//! NSGA-II way: `non_dominated_sort` peels the population into fronts (the
+//! first is the Pareto front: nobody in it is beaten on every objective),
+//! and `crowding_distance` prefers individuals in sparse stretches of their
+//! front so the front stays spread out. Parents and offspring compete
+//! together for the next generation, so the front is never lost.
+//!
+//! All objectives are maximized.
+
+/// How `Evolution` ranks a generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Selection {
+    /// By scalar fitness, with elitism
+    #[default]
+    Scalar,
+    /// By Pareto front, then crowding distance (NSGA-II)
+    Pareto,
+}
+
+impl Selection {
+    /// Both selection modes, in display order.
+    pub const ALL: [Self; 2] = [Self::Scalar, Self::Pareto];
+
+    /// Parses a selection name (`scalar`, `pareto`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|selection| selection.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Lowercase name used on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Scalar => "scalar",
+            Self::Pareto => "pareto",
+        }
+    }
+}
+
+/// Returns true if `a` is at least as good as `b` on every objective and
+/// better on one.
+#[must_use]
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+}
+
+/// Indices of `points` grouped into fronts, the Pareto front first. Within
+/// a front indices keep their input order.
+#[must_use]
+pub fn non_dominated_sort(points: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    let n = points.len();
+    let mut beaten_by = vec![0_usize; n];
+    let mut beats: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for i in 0..n {
+        for j in i + 1..n {
+            if dominates(&points[i], &points[j]) {
+                beats[i].push(j);
+                beaten_by[j] += 1;
+            } else if dominates(&points[j], &points[i]) {
+                beats[j].push(i);
+                beaten_by[i] += 1;
+            }
+        }
+    }
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..n).filter(|&i| beaten_by[i] == 0).collect();
+    while !front.is_empty() {
+        let mut next = Vec::new();
+        for &i in &front {
+            for &j in &beats[i] {
+                beaten_by[j] -= 1;
+                if beaten_by[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        next.sort_unstable();
+        fronts.push(front);
+        front = next;
+    }
+    fronts
+}
+
+/// Crowding distance of each member of `front` (indices into `points`), in
+/// `front` order: the normalized side lengths of the box spanned by its
+/// neighbors on each objective. The extremes of every objective get
+/// infinity so the front keeps its ends.
+#[must_use]
+pub fn crowding_distance(points: &[Vec<f64>], front: &[usize]) -> Vec<f64> {
+    let mut distance = vec![0.0; front.len()];
+    let objectives = front.first().map_or(0, |&i| points[i].len());
+    let mut order: Vec<usize> = (0..front.len()).collect();
+    let columns =
+        (0..objectives).map(|k| front.iter().map(|&i| points[i][k]).collect::<Vec<f64>>());
+    for values in columns {
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+        let (Some(&low), Some(&high)) = (order.first(), order.last()) else {
+            break;
+        };
+        let span = values[high] - values[low];
+        distance[low] = f64::INFINITY;
+        distance[high] = f64::INFINITY;
+        if span <= 0.0 {
+            continue;
+        }
+        for window in order.windows(3) {
+            distance[window[1]] += (values[window[2]] - values[window[0]]) / span;
+        }
+    }
+    distance
+}
+
+/// Order of `points` by NSGA-II's crowded comparison: front rank first,
+/// then larger crowding distance. Returns `(index, front rank)` pairs.
+#[must_use]
+pub fn crowded_order(points: &[Vec<f64>]) -> Vec<(usize, usize)> {
+    let mut order = Vec::with_capacity(points.len());
+    for (rank, front) in non_dominated_sort(points).iter().enumerate() {
+        let distance = crowding_distance(points, front);
+        let mut members: Vec<(usize, f64)> = front.iter().copied().zip(distance).collect();
+        members.sort_by(|a, b| b.1.total_cmp(&a.1));
+        order.extend(members.into_iter().map(|(i, _)| (i, rank)));
+    }
+    order
+}
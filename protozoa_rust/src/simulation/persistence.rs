@@ -0,0 +1,57 @@
+//! Directional persistence: correlated random walks instead of jitter.
+//!
+//! Fresh exploration noise every tick makes an agent wiggle on the spot.
+//! Real swimmers hold a heading for a while: the correlation of their
+//! heading decays along the path as `e^(−s / L)`, where `L` is the
+//! persistence length (`Morphology::persistence_length`). Two terms follow
+//! from it, both scaled by the correlation `ρ = e^(−v / L)` over the
+//! distance `v` the agent swims in a second at its current speed:
+//!
+//! - the exploration turn is an AR(1) process,
+//!   `u' = ρ u + √(1 − ρ²) ξ`, so it keeps its variance but wanders slowly;
+//! - turning against the last turn costs `w ρ θ²` on top of the blend's
+//!   squared error `(θ − θ*)²` (`w` = `TURNING_COST`); the minimum
+//!   `θ = θ* / (1 + w ρ)` adds the `Term::Persistence`
+//!   `−w ρ θ* / (1 + w ρ)` to a blended turn `θ*` that reverses the last
+//!   one, so the agent does not flip left and right for nothing. Turns the
+//!   same way as before are free.
+//!
+//! With `L` 0 (the default) `ρ` is 0: exploration is white and turning is
+//! free, exactly as without persistence.
+
+use crate::simulation::params::TURNING_COST;
+
+/// Heading correlation over a path of `step` for persistence length
+/// `length` (0 when `length` is not positive).
+#[must_use]
+pub fn heading_correlation(step: f64, length: f64) -> f64 {
+    if length > 0.0 {
+        (-step.abs() / length).exp()
+    } else {
+        0.0
+    }
+}
+
+/// Next exploration turn from the previous one and a fresh draw from
+/// [−1, 1], at correlation `rho`. It keeps the variance of the draws
+/// (1/3), though it can stray past ±1.
+#[must_use]
+pub fn correlated_turn(previous: f64, fresh: f64, rho: f64) -> f64 {
+    if rho > 0.0 {
+        rho * previous + (1.0 - rho * rho).sqrt() * fresh
+    } else {
+        fresh
+    }
+}
+
+/// Turning-cost term added to the blended turn `desired` after turning by
+/// `previous`, at correlation `rho`: damps a reversal, 0 otherwise.
+#[must_use]
+pub fn turning_cost_term(desired: f64, previous: f64, rho: f64) -> f64 {
+    if desired * previous < 0.0 {
+        let weight = TURNING_COST * rho;
+        -weight * desired / (1.0 + weight)
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,86 @@
+//! pH field: a second homeostatic variable.
+//!
+//! pH is normalized to [0, 1] (0 acidic, 0.5 neutral, 1 alkaline) and is a
+//! neutral baseline plus signed Gaussian acidic and alkaline spots. The agent
+//! holds its own pH set-point (`Morphology::preferred_ph`), so a patch that is
+//! rich in food but too acidic pits the nutrient prior against the pH prior.
+
+use crate::simulation::params::{
+    NEUTRAL_PH, PH_RADIUS_MAX, PH_RADIUS_MIN, PH_SHIFT_MAX, SOURCE_MARGIN,
+};
+use rand::Rng;
+
+/// An acidic (negative shift) or alkaline (positive shift) spot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhSpot {
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+    /// Signed pH offset at the center
+    pub shift: f64,
+}
+
+impl PhSpot {
+    /// Gaussian pH offset of this spot at (x, y).
+    #[must_use]
+    pub fn contribution(&self, x: f64, y: f64) -> f64 {
+        let dist_sq = (x - self.x).powi(2) + (y - self.y).powi(2);
+        let sigma_sq = self.radius.powi(2).max(f64::EPSILON);
+        self.shift * (-dist_sq / (2.0 * sigma_sq)).exp()
+    }
+}
+
+/// Static pH landscape over the dish.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhField {
+    /// Baseline pH in [0, 1]
+    pub neutral: f64,
+    pub spots: Vec<PhSpot>,
+}
+
+impl Default for PhField {
+    fn default() -> Self {
+        Self::uniform()
+    }
+}
+
+impl PhField {
+    /// Creates a neutral field everywhere.
+    #[must_use]
+    pub fn uniform() -> Self {
+        Self {
+            neutral: NEUTRAL_PH,
+            spots: Vec::new(),
+        }
+    }
+
+    /// Creates `count` random acidic and alkaline spots for a dish of the given size.
+    #[must_use]
+    pub fn random(rng: &mut impl Rng, count: usize, width: f64, height: f64) -> Self {
+        let margin_x = SOURCE_MARGIN.min(width / 4.0);
+        let margin_y = SOURCE_MARGIN.min(height / 4.0);
+        let spots = (0..count)
+            .map(|i| {
+                // Alternate acidic and alkaline spots so the field is not one-sided
+                let sign = if i % 2 == 0 { -1.0 } else { 1.0 };
+                PhSpot {
+                    x: rng.random_range(margin_x..=width - margin_x),
+                    y: rng.random_range(margin_y..=height - margin_y),
+                    radius: rng.random_range(PH_RADIUS_MIN..=PH_RADIUS_MAX),
+                    shift: sign * rng.random_range(0.5..=1.0) * PH_SHIFT_MAX,
+                }
+            })
+            .collect();
+        Self {
+            neutral: NEUTRAL_PH,
+            spots,
+        }
+    }
+
+    /// pH at (x, y), clamped to [0, 1].
+    #[must_use]
+    pub fn ph_at(&self, x: f64, y: f64) -> f64 {
+        let offset: f64 = self.spots.iter().map(|s| s.contribution(x, y)).sum();
+        (self.neutral + offset).clamp(0.0, 1.0)
+    }
+}
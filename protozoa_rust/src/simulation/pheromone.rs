@@ -0,0 +1,108 @@
+//! Pheromone trails laid by the agents.
+//!
+//! A `PheromoneField` is a raster over the dish (`PetriDish::pheromone`).
+//! Agents with a positive `Morphology::pheromone_deposit` mark the cell they
+//! are in every tick, and every level fades by `PHEROMONE_DECAY` per second.
+//! A pair of pheromone receptors at the sensor positions reads the field;
+//! `pheromone_sensitivity` turns the agent toward (> 0) or away from (< 0)
+//! the stronger side. With deposit d, decay λ and sensitivity s:
+//!
+//! ```text
+//! P ← min(1, P + d Δt)      in the agent's cell, each tick
+//! P ← λ^Δt P                everywhere, zeroed below PHEROMONE_FLOOR
+//! Δθ = g s (P_L − P_R)
+//! ```
+//!
+//! Both d and s default to 0. Agents that follow trails also reinforce
+//! them, so in a population well-used paths grow stronger while abandoned
+//! ones fade: stigmergic path formation.
+
+use crate::simulation::params::{PHEROMONE_COLS, PHEROMONE_FLOOR, PHEROMONE_ROWS};
+
+/// Trail levels in [0, 1] over a `rows × cols` grid covering the dish.
+#[derive(Clone, Debug)]
+pub struct PheromoneField {
+    levels: Vec<f64>,
+    rows: usize,
+    cols: usize,
+    world_width: f64,
+    world_height: f64,
+    /// Whether any cell is above zero (an empty field skips its decay sweep)
+    active: bool,
+}
+
+impl PheromoneField {
+    /// An empty field at the default resolution for a dish of the given size.
+    #[must_use]
+    pub fn new(world_width: f64, world_height: f64) -> Self {
+        Self {
+            levels: vec![0.0; PHEROMONE_ROWS * PHEROMONE_COLS],
+            rows: PHEROMONE_ROWS,
+            cols: PHEROMONE_COLS,
+            world_width,
+            world_height,
+            active: false,
+        }
+    }
+
+    /// Stretches the field over a resized dish, keeping the level per cell.
+    pub fn resize(&mut self, world_width: f64, world_height: f64) {
+        self.world_width = world_width;
+        self.world_height = world_height;
+    }
+
+    /// Cell containing (x, y), or `None` outside the dish.
+    #[allow(clippy::cast_precision_loss)] // Grid sizes are small
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn cell_of(&self, x: f64, y: f64) -> Option<usize> {
+        if !(0.0..=self.world_width).contains(&x) || !(0.0..=self.world_height).contains(&y) {
+            return None;
+        }
+        let col = ((x / self.world_width * self.cols as f64) as usize).min(self.cols - 1);
+        let row = ((y / self.world_height * self.rows as f64) as usize).min(self.rows - 1);
+        Some(row * self.cols + col)
+    }
+
+    /// Adds `amount` to the cell containing (x, y), saturating at 1.
+    /// Positions outside the dish and non-positive amounts are ignored.
+    pub fn deposit(&mut self, x: f64, y: f64, amount: f64) {
+        if amount <= 0.0 || !amount.is_finite() {
+            return;
+        }
+        if let Some(cell) = self.cell_of(x, y) {
+            self.levels[cell] = (self.levels[cell] + amount).min(1.0);
+            self.active = true;
+        }
+    }
+
+    /// Trail level at (x, y); 0 outside the dish.
+    #[must_use]
+    pub fn level_at(&self, x: f64, y: f64) -> f64 {
+        self.cell_of(x, y).map_or(0.0, |cell| self.levels[cell])
+    }
+
+    /// Multiplies every level by `factor` (the per-tick share of
+    /// `PHEROMONE_DECAY`), zeroing those that fall below `PHEROMONE_FLOOR`.
+    pub fn decay(&mut self, factor: f64) {
+        if !self.active {
+            return;
+        }
+        self.active = false;
+        for level in &mut self.levels {
+            *level *= factor;
+            if *level < PHEROMONE_FLOOR {
+                *level = 0.0;
+            } else {
+                self.active = true;
+            }
+        }
+    }
+
+    /// Sum of all levels (the amount of trail in the dish).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn total(&self) -> f64 {
+        self.levels.iter().sum()
+    }
+}
@@ -0,0 +1,160 @@
+//! The phases of an agent's tick, and pipelines that run them.
+//!
+//! `Protozoa::update_state` is the agent's `pipeline` run once: by default
+//! every `Phase` in `Phase::ALL` order, each a public method of its own
+//! (`infer`, `learn_precision`, `plan`, `act`, `remember`, `metabolize`,
+//! `locomote`, `regulate`). A `TickContext` carries what one phase hands the
+//! next (the tick's readings, the decision, the speed commanded). A
+//! `TickPipeline` with phases left out or reordered ablates them:
+//!
+//! ```text
+//! agent.pipeline = TickPipeline::default().without(Phase::LearnPrecision);
+//! ```
+//!
+//! Every agent stepped through `update_state` (populations, scenarios,
+//! sweeps) then runs the edited pipeline. Dead agents do nothing, dormant
+//! ones take their `dormant_step` whatever the pipeline, and a phase that
+//! kills the agent ends the tick. A strict agent (`numerics.rs`) checks its
+//! state after every phase and halts for good at the first non-finite
+//! value.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::controller::Decision;
+use crate::simulation::environment::Environment;
+use crate::simulation::inference::ReceptorObservation;
+
+/// One phase of an agent's tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Beliefs by VFE descent, the internal clock, VFE and stress
+    Infer,
+    /// Sensory precisions and the bias from prediction errors
+    LearnPrecision,
+    /// The controller's decision
+    Plan,
+    /// The decision executed as a heading and a speed
+    Act,
+    /// Spatial priors, short-term and episodic memory; advances the
+    /// agent's tick count and age
+    Remember,
+    /// Energy paid and eaten, starvation, dormancy and exhaustion
+    Metabolize,
+    /// The body moved along its heading and with the flow
+    Locomote,
+    /// Injury and regeneration, goal progress and arrivals
+    Regulate,
+}
+
+impl Phase {
+    /// Every phase, in the order of a full tick.
+    pub const ALL: [Self; 8] = [
+        Self::Infer,
+        Self::LearnPrecision,
+        Self::Plan,
+        Self::Act,
+        Self::Remember,
+        Self::Metabolize,
+        Self::Locomote,
+        Self::Regulate,
+    ];
+
+    /// Name of the phase (its method's name).
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Infer => "infer",
+            Self::LearnPrecision => "learn_precision",
+            Self::Plan => "plan",
+            Self::Act => "act",
+            Self::Remember => "remember",
+            Self::Metabolize => "metabolize",
+            Self::Locomote => "locomote",
+            Self::Regulate => "regulate",
+        }
+    }
+}
+
+/// What the phases of one tick hand each other.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TickContext {
+    /// Chemoreceptor pair readings (held between samples)
+    pub observations: (f64, f64),
+    /// Receptor-array readings inference runs on
+    pub chemoreceptors: Vec<ReceptorObservation>,
+    /// Thermoreceptor pair readings
+    pub thermal: (f64, f64),
+    /// Mean of the pair readings
+    pub mean_sense: f64,
+    /// The controller's command (straight on at cruise speed until `Plan`)
+    pub decision: Decision,
+    /// Speed commanded after motor noise and the vigor cap, before drag
+    /// (the current speed until `Act`)
+    pub commanded_speed: f64,
+    /// Drag where the agent acted (1 until `Act`)
+    pub viscosity: f64,
+    /// Speed at which the agent hit an obstacle this tick
+    pub impact: Option<f64>,
+    /// Heading at the start of the tick, before `Act` turns it
+    pub heading: f64,
+}
+
+/// Ordered phases run as one tick (`Protozoa::pipeline`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TickPipeline {
+    phases: Vec<Phase>,
+}
+
+impl Default for TickPipeline {
+    /// Every phase in `Phase::ALL` order: the full tick.
+    fn default() -> Self {
+        Self::new(Phase::ALL.to_vec())
+    }
+}
+
+impl TickPipeline {
+    /// A pipeline running `phases` in the given order (repeats allowed).
+    #[must_use]
+    pub fn new(phases: Vec<Phase>) -> Self {
+        Self { phases }
+    }
+
+    /// The phases run, in order.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    /// This pipeline with every run of `phase` left out.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn without(mut self, phase: Phase) -> Self {
+        self.phases.retain(|&p| p != phase);
+        self
+    }
+
+    /// Runs one tick of `agent` in `dish` through these phases.
+    pub fn run(&self, agent: &mut Protozoa, dish: &(impl Environment + ?Sized)) {
+        if !agent.is_alive() || agent.is_halted() {
+            return;
+        }
+        if agent.is_dormant() {
+            agent.dormant_step(dish);
+            agent.halt_on_fault("dormant_step", &agent.tick_context());
+            return;
+        }
+        let mut tick = agent.tick_context();
+        if agent.halt_on_fault("sense", &tick) {
+            return;
+        }
+        for &phase in &self.phases {
+            if !agent.is_alive() {
+                break;
+            }
+            agent.run_phase(phase, dish, &mut tick);
+            if agent.halt_on_fault(phase.name(), &tick) {
+                break;
+            }
+        }
+    }
+}
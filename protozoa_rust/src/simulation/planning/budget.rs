@@ -0,0 +1,94 @@
+//! Adaptive MCTS budget.
+//!
+//! A fixed budget wastes rollouts while the agent coasts through familiar,
+//! rich ground and under-plans when it is starving or lost. The agent picks
+//! a `PlanningBudget` at every replan from a planning pressure in [0, 1]:
+//! the larger of its energy need (0 from `MCTS_RELAXED_ENERGY` up, 1 at
+//! `MCTS_URGENT_ENERGY` and below) and its uncertainty, the current VFE as a
+//! share of `MAX_VFE` (the same measure that scales the heading noise),
+//! raised further to the urgency of its starvation forecast. Depth and
+//! rollouts grow linearly with the pressure from `MCTS_MIN_DEPTH` ×
+//! `MCTS_MIN_ROLLOUTS` to `MCTS_MAX_DEPTH` × `MCTS_MAX_ROLLOUTS`:
+//!
+//! ```text
+//! p = max(clamp((E_relaxed − E) / (E_relaxed − E_urgent), 0, 1), clamp(F / F_max, 0, 1))
+//! d = d_min + round(p (d_max − d_min))      N = N_min + round(p (N_max − N_min))
+//! ```
+//!
+//! Cached rollout estimates are kept per rollout state as Ḡ / (d + 1), so
+//! budgets of different depths share the cache.
+
+use crate::simulation::params::{
+    MAX_VFE, MCTS_DEPTH, MCTS_MAX_DEPTH, MCTS_MAX_ROLLOUTS, MCTS_MIN_DEPTH, MCTS_MIN_ROLLOUTS,
+    MCTS_RELAXED_ENERGY, MCTS_ROLLOUTS, MCTS_URGENT_ENERGY,
+};
+
+/// Rollout depth and count of one planning cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlanningBudget {
+    /// Steps simulated per rollout (and length of the plan)
+    pub depth: usize,
+    /// Rollouts per first action, cached ones included
+    pub rollouts: usize,
+}
+
+impl Default for PlanningBudget {
+    fn default() -> Self {
+        Self::FIXED
+    }
+}
+
+impl PlanningBudget {
+    /// The fixed `MCTS_ROLLOUTS` × `MCTS_DEPTH` budget a planner starts with.
+    pub const FIXED: Self = Self {
+        depth: MCTS_DEPTH,
+        rollouts: MCTS_ROLLOUTS,
+    };
+
+    /// Budget for a planning pressure in [0, 1] (clamped).
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // Budgets are small
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn for_pressure(pressure: f64) -> Self {
+        let pressure = if pressure.is_finite() {
+            pressure.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let lerp = |lo: usize, hi: usize| lo + (pressure * (hi - lo) as f64).round() as usize;
+        Self {
+            depth: lerp(MCTS_MIN_DEPTH, MCTS_MAX_DEPTH),
+            rollouts: lerp(MCTS_MIN_ROLLOUTS, MCTS_MAX_ROLLOUTS),
+        }
+    }
+
+    /// This budget limited to at most `cap`'s depth and rollouts (as is
+    /// without a cap).
+    #[must_use]
+    pub fn capped(self, cap: Option<Self>) -> Self {
+        match cap {
+            Some(cap) => Self {
+                depth: self.depth.min(cap.depth),
+                rollouts: self.rollouts.min(cap.rollouts),
+            },
+            None => self,
+        }
+    }
+
+    /// Budget for an agent with `energy` and current free energy `vfe`.
+    #[must_use]
+    pub fn adaptive(energy: f64, vfe: f64) -> Self {
+        Self::for_pressure(planning_pressure(energy, vfe))
+    }
+}
+
+/// Planning pressure in [0, 1]: the larger of the energy need and the VFE
+/// share.
+#[must_use]
+pub fn planning_pressure(energy: f64, vfe: f64) -> f64 {
+    let need = ((MCTS_RELAXED_ENERGY - energy) / (MCTS_RELAXED_ENERGY - MCTS_URGENT_ENERGY))
+        .clamp(0.0, 1.0);
+    let uncertainty = (vfe / MAX_VFE).clamp(0.0, 1.0);
+    need.max(uncertainty)
+}
@@ -0,0 +1,203 @@
+//! Rollout value cache for MCTS replanning.
+//!
+//! The agent replans from nearly identical states again and again, so rollout
+//! estimates are cached per discretized state and first action. Entries fade
+//! by `MCTS_CACHE_DECAY` on every planning cycle, because the spatial priors
+//! they were computed from keep changing.
+
+use crate::simulation::memory::SpatialGrid;
+use crate::simulation::params::{
+    MCTS_CACHE_CAPACITY, MCTS_CACHE_COMPACT_RATIO, MCTS_CACHE_DECAY, MCTS_CACHE_ENERGY_BUCKETS,
+    MCTS_CACHE_MIN_WEIGHT, MCTS_ROLLOUTS, SPEED_STEP,
+};
+use crate::simulation::planning::mcts::{Action, AgentState};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Discretized planning state: spatial-memory cell, heading octant, speed
+/// step, energy bucket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StateKey {
+    pub col: usize,
+    pub row: usize,
+    /// Heading in units of 45° (0..8)
+    pub octant: u8,
+    /// Speed in units of `SPEED_STEP`, rounded
+    pub speed_step: u8,
+    /// Energy in `MCTS_CACHE_ENERGY_BUCKETS` equal bins
+    pub energy_bucket: u8,
+}
+
+impl StateKey {
+    /// Discretizes a planning state on the grid of `priors`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new<const W: usize, const H: usize>(
+        state: &AgentState,
+        priors: &SpatialGrid<W, H>,
+    ) -> Self {
+        let (cell_w, cell_h) = priors.cell_dimensions();
+        let col = ((state.x / cell_w).max(0.0) as usize).min(W - 1);
+        let row = ((state.y / cell_h).max(0.0) as usize).min(H - 1);
+        let octant = ((state.angle.rem_euclid(2.0 * PI) / (PI / 4.0)) as u8).min(7);
+        let speed_step = (state.speed.max(0.0) / SPEED_STEP).round() as u8;
+        let buckets = MCTS_CACHE_ENERGY_BUCKETS;
+        let energy_bucket =
+            ((state.energy.clamp(0.0, 1.0) * buckets as f64) as usize).min(buckets - 1) as u8;
+        Self {
+            col,
+            row,
+            octant,
+            speed_step,
+            energy_bucket,
+        }
+    }
+}
+
+/// Running rollout estimate for one (state, first action) pair.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CachedValue {
+    /// Mean pragmatic value per rollout state (rollouts of any depth blend)
+    pub pragmatic: f64,
+    /// Mean epistemic value per rollout state
+    pub epistemic: f64,
+    /// Effective number of rollouts behind the estimate (decays over time)
+    pub weight: f64,
+}
+
+/// Decaying cache of rollout estimates keyed by discretized state.
+#[derive(Clone, Debug)]
+pub struct RolloutCache {
+    entries: HashMap<(StateKey, Action), CachedValue>,
+    /// Entry count that triggers compaction
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl Default for RolloutCache {
+    fn default() -> Self {
+        Self::with_capacity(MCTS_CACHE_CAPACITY)
+    }
+}
+
+impl RolloutCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a cache holding at most `capacity` entries.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Looks up an estimate, counting the hit or miss.
+    pub fn lookup(&mut self, key: StateKey, action: Action) -> Option<CachedValue> {
+        let value = self.entries.get(&(key, action)).copied();
+        if value.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        value
+    }
+
+    /// Stores an estimate, capping its weight at `MCTS_ROLLOUTS` so fresh
+    /// rollouts always carry a meaningful share of the next blend.
+    ///
+    /// Exceeding the capacity compacts the cache.
+    #[allow(clippy::cast_precision_loss)] // MCTS_ROLLOUTS is small (50)
+    pub fn store(&mut self, key: StateKey, action: Action, value: CachedValue) {
+        let weight = value.weight.min(MCTS_ROLLOUTS as f64);
+        self.entries
+            .insert((key, action), CachedValue { weight, ..value });
+        if self.entries.len() > self.capacity {
+            self.compact();
+        }
+    }
+
+    /// Evicts the lowest-weight entries down to `MCTS_CACHE_COMPACT_RATIO` of
+    /// the capacity and releases the freed storage.
+    ///
+    /// Compacting below the capacity (rather than to it) leaves headroom, so
+    /// a full cache is not re-sorted on every store.
+    #[allow(clippy::cast_precision_loss)] // Capacities are small
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn compact(&mut self) {
+        let keep = (self.capacity as f64 * MCTS_CACHE_COMPACT_RATIO) as usize;
+        if self.entries.len() > keep {
+            let mut entries: Vec<_> = self.entries.drain().collect();
+            entries.sort_unstable_by(|a, b| b.1.weight.total_cmp(&a.1.weight));
+            entries.truncate(keep);
+            self.entries.extend(entries);
+        }
+        self.entries.shrink_to_fit();
+    }
+
+    /// Entry count that triggers compaction.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity, compacting at once if the cache is over it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        if self.entries.len() > capacity {
+            self.compact();
+        }
+    }
+
+    /// Approximate bytes of storage held by the entry table.
+    #[must_use]
+    pub fn heap_bytes(&self) -> usize {
+        self.entries.capacity() * std::mem::size_of::<((StateKey, Action), CachedValue)>()
+    }
+
+    /// Fades every entry by `MCTS_CACHE_DECAY`, evicting those below
+    /// `MCTS_CACHE_MIN_WEIGHT`.
+    pub fn decay(&mut self) {
+        self.entries.retain(|_, value| {
+            value.weight *= MCTS_CACHE_DECAY;
+            value.weight >= MCTS_CACHE_MIN_WEIGHT
+        });
+    }
+
+    /// Number of cached (state, action) pairs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Fraction of lookups answered from the cache.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Lookup counts stay far below 2^52
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+
+    /// Drops all entries and statistics (the capacity is kept).
+    pub fn clear(&mut self) {
+        *self = Self::with_capacity(self.capacity);
+    }
+}
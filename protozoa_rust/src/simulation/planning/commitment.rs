@@ -0,0 +1,134 @@
+//! Plan commitment and interruption policy.
+//!
+//! The executor commits to the action sequence returned by the planner and
+//! plays it back one step per tick for up to `PLAN_COMMIT_HORIZON` ticks. The
+//! commitment ends early when an interruption criterion fires; either way the
+//! agent replans from its current state instead of acting on a stale plan.
+
+use crate::simulation::params::{
+    PLAN_COMMIT_HORIZON, PLAN_SURPRISE_MARGIN, PLAN_SURPRISE_RATIO, PLAN_SURPRISE_SMOOTHING,
+};
+use crate::simulation::planning::mcts::Action;
+use std::collections::VecDeque;
+
+/// Why a committed plan was abandoned before its horizon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interruption {
+    /// VFE jumped well above its running average
+    SurpriseSpike,
+    /// A sensor reads the toxic void (outside the dish or inside an obstacle)
+    Hazard,
+    /// A navigation target was reached since the plan was made
+    GoalReached,
+}
+
+/// Per-tick signals the interruption criteria look at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlanSignals {
+    /// Current Variational Free Energy
+    pub vfe: f64,
+    /// True if the agent is in danger (see `Interruption::Hazard`)
+    pub hazard: bool,
+    /// Total arrivals so far (`EventLog::count`)
+    pub arrivals: usize,
+}
+
+/// Plays back a committed plan and decides when to abandon it.
+#[derive(Clone, Debug, Default)]
+pub struct PlanExecutor {
+    /// Remaining planned actions, next first
+    steps: VecDeque<Action>,
+    /// Arrival count when the plan was committed
+    arrivals_at_commit: usize,
+    /// Running average of VFE (surprise baseline)
+    baseline_vfe: Option<f64>,
+    /// Reason the last plan was abandoned, if it was
+    last_interruption: Option<Interruption>,
+    /// Number of plans abandoned early
+    interruptions: u64,
+}
+
+impl PlanExecutor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Commits to `plan`, truncated to `PLAN_COMMIT_HORIZON` steps.
+    pub fn commit(&mut self, plan: &[Action], arrivals: usize) {
+        self.steps = plan.iter().copied().take(PLAN_COMMIT_HORIZON).collect();
+        self.arrivals_at_commit = arrivals;
+    }
+
+    /// Returns true while committed steps remain.
+    #[must_use]
+    pub fn is_committed(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    /// Number of committed steps left.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Pops the action for this tick.
+    pub fn next_action(&mut self) -> Option<Action> {
+        self.steps.pop_front()
+    }
+
+    /// Drops the remaining steps without counting an interruption (the plan
+    /// was not wrong, it no longer applies).
+    pub fn abandon(&mut self) {
+        self.steps.clear();
+    }
+
+    /// Evaluates the interruption criteria, dropping the plan if one fires.
+    ///
+    /// Also folds `signals.vfe` into the surprise baseline, after the check so
+    /// a spike is measured against the average that preceded it. A surprise
+    /// spike needs VFE above both `PLAN_SURPRISE_RATIO ×` and
+    /// `PLAN_SURPRISE_MARGIN +` the baseline.
+    pub fn check(&mut self, signals: &PlanSignals) -> Option<Interruption> {
+        let baseline = self.baseline_vfe.unwrap_or(signals.vfe);
+        self.baseline_vfe = Some(baseline + PLAN_SURPRISE_SMOOTHING * (signals.vfe - baseline));
+
+        if !self.is_committed() {
+            return None;
+        }
+        let interruption = if signals.arrivals > self.arrivals_at_commit {
+            Interruption::GoalReached
+        } else if signals.hazard {
+            Interruption::Hazard
+        } else if signals.vfe > PLAN_SURPRISE_RATIO * baseline
+            && signals.vfe > baseline + PLAN_SURPRISE_MARGIN
+        {
+            Interruption::SurpriseSpike
+        } else {
+            return None;
+        };
+
+        self.steps.clear();
+        self.last_interruption = Some(interruption);
+        self.interruptions += 1;
+        Some(interruption)
+    }
+
+    /// Reason the most recent early abandonment happened.
+    #[must_use]
+    pub fn last_interruption(&self) -> Option<Interruption> {
+        self.last_interruption
+    }
+
+    /// Number of plans abandoned before their horizon.
+    #[must_use]
+    pub fn interruptions(&self) -> u64 {
+        self.interruptions
+    }
+
+    /// Running VFE average used as the surprise baseline.
+    #[must_use]
+    pub fn baseline_vfe(&self) -> Option<f64> {
+        self.baseline_vfe
+    }
+}
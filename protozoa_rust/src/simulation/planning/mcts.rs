@@ -3,17 +3,35 @@
 //! Implements MCTS with Expected Free Energy as the value function,
 //! enabling the agent to plan multi-step trajectories that balance
 //! exploitation (seeking nutrients) with exploration (reducing uncertainty).
-
+//! Speed is part of the action space: faster rollouts reach unexplored
+//! cells sooner but burn more energy, so plans trade movement cost against
+//! information gain.
+//!
+//! A cell's uncertainty counts toward the epistemic value once per rollout,
+//! so information is only gained by moving on. Rollouts move through the
+//! agent's body (`with_body`, inertia) and its learned transition model
+//! (`with_transition`). `plan_avoiding` also charges every visited cell of a
+//! rollout the learned threat there.
+
+use crate::simulation::environment::Environment;
+use crate::simulation::hunger::Drive;
+use crate::simulation::inference::TransitionModel;
+use crate::simulation::kinematics::{Kinematics, Motion};
 use crate::simulation::memory::SpatialGrid;
+use crate::simulation::numerics::{NonFinite, finite_all};
 use crate::simulation::params::{
     BASE_METABOLIC_COST, DISH_HEIGHT, DISH_WIDTH, EXPLORATION_SCALE, INTAKE_RATE, MAX_SPEED,
-    MCTS_DEPTH, MCTS_ROLLOUTS, MIN_PRECISION, SPEED_METABOLIC_COST, TARGET_CONCENTRATION,
+    MCTS_CACHE_MIN_ROLLOUTS, MIN_PRECISION, SENSOR_DIST, SPEED_METABOLIC_COST, SPEED_STEP,
+    THREAT_RISK_WEIGHT,
 };
-use rand::Rng;
+use crate::simulation::planning::budget::PlanningBudget;
+use crate::simulation::planning::cache::{CachedValue, RolloutCache, StateKey};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::f64::consts::PI;
 
 /// Discrete actions available to the agent during planning.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Action {
     /// Turn left by 45 degrees
     TurnLeft,
@@ -21,6 +39,12 @@ pub enum Action {
     Straight,
     /// Turn right by 45 degrees
     TurnRight,
+    /// Keep the heading, speed up by `SPEED_STEP`
+    Accelerate,
+    /// Keep the heading, slow down by `SPEED_STEP`
+    Decelerate,
+    /// Keep the heading and halt
+    Stop,
 }
 
 /// Details about a planned action for visualization.
@@ -36,6 +60,8 @@ pub struct ActionDetail {
     pub epistemic_value: f64,
     /// Sample trajectory positions for visualization
     pub sample_trajectory: Vec<(f64, f64)>,
+    /// Rollouts simulated this cycle (the rest came from the rollout cache)
+    pub fresh_rollouts: usize,
 }
 
 impl Action {
@@ -44,15 +70,37 @@ impl Action {
     pub const fn angle_delta(self) -> f64 {
         match self {
             Self::TurnLeft => PI / 4.0,
-            Self::Straight => 0.0,
             Self::TurnRight => -PI / 4.0,
+            Self::Straight | Self::Accelerate | Self::Decelerate | Self::Stop => 0.0,
+        }
+    }
+
+    /// Speed after taking this action at `speed`, within [0, `MAX_SPEED`].
+    /// Turns and going straight keep the speed.
+    #[must_use]
+    pub fn next_speed(self, speed: f64) -> f64 {
+        match self {
+            Self::TurnLeft | Self::Straight | Self::TurnRight => speed,
+            Self::Accelerate => (speed + SPEED_STEP).min(MAX_SPEED),
+            Self::Decelerate => (speed - SPEED_STEP).max(0.0),
+            Self::Stop => 0.0,
         }
     }
 
+    /// The heading actions, which keep the speed.
+    pub const TURNS: [Action; 3] = [Action::TurnLeft, Action::Straight, Action::TurnRight];
+
     /// Returns all possible actions.
     #[must_use]
-    pub const fn all() -> [Action; 3] {
-        [Action::TurnLeft, Action::Straight, Action::TurnRight]
+    pub const fn all() -> [Action; 6] {
+        [
+            Action::TurnLeft,
+            Action::Straight,
+            Action::TurnRight,
+            Action::Accelerate,
+            Action::Decelerate,
+            Action::Stop,
+        ]
     }
 }
 
@@ -69,6 +117,15 @@ pub struct AgentState {
     pub speed: f64,
     /// Current energy level
     pub energy: f64,
+    /// Inertia of the body (none by default)
+    pub kinematics: Kinematics,
+    /// Sensor length the body's drag follows
+    pub sensor_dist: f64,
+    /// Speed and angular velocity actually carried, which lag `speed` (the
+    /// commanded speed) and the turns under inertia
+    pub motion: Motion,
+    /// How the body carries out commands (the commands exactly by default)
+    pub transition: TransitionModel,
 }
 
 impl AgentState {
@@ -81,27 +138,76 @@ impl AgentState {
             angle,
             speed,
             energy,
+            kinematics: Kinematics::new(0.0, 0.0),
+            sensor_dist: SENSOR_DIST,
+            motion: Motion { speed, spin: 0.0 },
+            transition: TransitionModel::new(0.0),
         }
     }
 
-    /// Simulates one tick forward using learned priors as world model.
-    ///
-    /// Returns the new state after taking the given action.
+    /// This state for a body with `kinematics` and sensors `sensor_dist`
+    /// long, currently in `motion`.
     #[must_use]
-    pub fn step(&self, action: Action, priors: &SpatialGrid<20, 10>) -> Self {
-        // Apply action to angle
-        let new_angle = (self.angle + action.angle_delta()).rem_euclid(2.0 * PI);
+    pub const fn with_body(self, kinematics: Kinematics, sensor_dist: f64, motion: Motion) -> Self {
+        Self {
+            kinematics,
+            sensor_dist,
+            motion,
+            ..self
+        }
+    }
+
+    /// This state for a body that moves as `transition` predicts.
+    #[must_use]
+    pub const fn with_transition(self, transition: TransitionModel) -> Self {
+        Self { transition, ..self }
+    }
 
-        // Get expected concentration at current position from learned priors
-        let expected = priors.get_cell(self.x, self.y).mean.clamp(0.0, 1.0);
+    /// Checks the pose, speeds and energy rollouts start from (strict
+    /// numerical mode).
+    ///
+    /// # Errors
+    /// The first one that is NaN or infinite.
+    pub fn check_finite(&self) -> Result<(), NonFinite> {
+        finite_all([
+            ("rollout.x", self.x),
+            ("rollout.y", self.y),
+            ("rollout.angle", self.angle),
+            ("rollout.speed", self.speed),
+            ("rollout.energy", self.energy),
+            ("rollout.motion.speed", self.motion.speed),
+            ("rollout.motion.spin", self.motion.spin),
+        ])
+    }
+
+    /// Simulates one tick forward in `model`.
+    ///
+    /// Rollouts use the learned priors (`SpatialGrid`) as world model; any
+    /// other `Environment`, such as the dish itself, can be stepped through
+    /// the same way. Returns the new state after taking the given action.
+    #[must_use]
+    pub fn step(&self, action: Action, model: &(impl Environment + ?Sized)) -> Self {
+        // Apply action to the commanded angle and speed; the body follows
+        // them as far as its inertia lets it (at once without inertia)
+        let new_speed = action.next_speed(self.speed);
+        let (motion, turn) = self.kinematics.advance(
+            self.motion,
+            self.transition.turn(action.angle_delta()),
+            new_speed,
+            self.sensor_dist,
+            1.0,
+            model.clock().dt(),
+        );
+        let new_angle = (self.angle + turn).rem_euclid(2.0 * PI);
 
-        // Predict speed based on expected error (as the real agent does)
-        let predicted_error = (expected - TARGET_CONCENTRATION).abs();
-        let new_speed = MAX_SPEED * predicted_error;
+        // Get expected concentration at current position from the model
+        let expected = model.get_concentration(self.x, self.y).clamp(0.0, 1.0);
 
         // Move in the new direction
-        let new_x = (self.x + new_speed * new_angle.cos()).clamp(0.0, DISH_WIDTH);
-        let new_y = (self.y + new_speed * new_angle.sin()).clamp(0.0, DISH_HEIGHT);
+        let (width, height) = model.bounds();
+        let (dx, dy) = self.transition.displacement(motion.speed, new_angle);
+        let new_x = (self.x + dx).clamp(0.0, width);
+        let new_y = (self.y + dy).clamp(0.0, height);
 
         // Estimate energy change using expected concentration
         let intake = INTAKE_RATE * expected;
@@ -114,6 +220,8 @@ impl AgentState {
             angle: new_angle,
             speed: new_speed,
             energy: new_energy,
+            motion,
+            ..*self
         }
     }
 }
@@ -123,8 +231,18 @@ impl AgentState {
 pub struct MCTSPlanner {
     /// Best action from last planning cycle
     best_action: Action,
+    /// Action sequence of the best rollout starting with `best_action`
+    best_plan: Vec<Action>,
     /// Details from the last planning cycle
     last_details: Vec<ActionDetail>,
+    /// Rollout estimates reused across replans from similar states
+    cache: RolloutCache,
+    /// Depth and rollouts of the next planning cycle
+    budget: PlanningBudget,
+    /// Random stream for rollout policies (`Stream::Rollouts`)
+    rng: StdRng,
+    /// Weights of food and information in a plan's value (`Hunger::drive`)
+    drive: Drive,
 }
 
 impl Default for MCTSPlanner {
@@ -139,55 +257,170 @@ impl MCTSPlanner {
     pub fn new() -> Self {
         Self {
             best_action: Action::Straight,
+            best_plan: Vec::new(),
             last_details: Vec::new(),
+            cache: RolloutCache::new(),
+            budget: PlanningBudget::FIXED,
+            rng: StdRng::seed_from_u64(rand::rng().random()),
+            drive: Drive::default(),
         }
     }
 
+    /// Reseeds the rollout stream (see `Stream::Rollouts`).
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Sets the weights of food and information for the next planning cycle.
+    pub fn set_drive(&mut self, drive: Drive) {
+        self.drive = drive;
+    }
+
+    /// Returns the weights of food and information in a plan's value.
+    #[must_use]
+    pub const fn drive(&self) -> Drive {
+        self.drive
+    }
+
+    /// Value of a rollout: `w_prag·pragmatic + EXPLORATION_SCALE·w_epi·epistemic`.
+    fn value(&self, pragmatic: f64, epistemic: f64) -> f64 {
+        self.drive.pragmatic * pragmatic + EXPLORATION_SCALE * self.drive.epistemic * epistemic
+    }
+
     /// Returns the best action from the last planning cycle.
     #[must_use]
     pub const fn best_action(&self) -> Action {
         self.best_action
     }
 
+    /// Returns the action sequence of the last planning cycle: the
+    /// highest-valued fresh rollout of the best first action.
+    #[must_use]
+    pub fn best_plan(&self) -> &[Action] {
+        &self.best_plan
+    }
+
     /// Returns details of the last planning cycle.
     #[must_use]
     pub fn last_plan_details(&self) -> &[ActionDetail] {
         &self.last_details
     }
 
+    /// Checks the EFE estimates of the last planning cycle (strict
+    /// numerical mode).
+    ///
+    /// # Errors
+    /// The first one that is NaN or infinite.
+    pub fn check_finite(&self) -> Result<(), NonFinite> {
+        self.last_details.iter().try_for_each(|detail| {
+            finite_all([
+                ("plan.total_efe", detail.total_efe),
+                ("plan.pragmatic_value", detail.pragmatic_value),
+                ("plan.epistemic_value", detail.epistemic_value),
+            ])
+        })
+    }
+
+    /// Returns the rollout cache.
+    #[must_use]
+    pub const fn cache(&self) -> &RolloutCache {
+        &self.cache
+    }
+
+    /// Returns the rollout cache for reconfiguration.
+    pub fn cache_mut(&mut self) -> &mut RolloutCache {
+        &mut self.cache
+    }
+
+    /// Returns the budget of the next (and last) planning cycle.
+    #[must_use]
+    pub const fn budget(&self) -> PlanningBudget {
+        self.budget
+    }
+
+    /// Sets the depth and rollouts of the following planning cycles.
+    pub fn set_budget(&mut self, budget: PlanningBudget) {
+        self.budget = budget;
+    }
+
     /// Plans the best action using Monte Carlo rollouts.
     ///
-    /// Performs `MCTS_ROLLOUTS` random rollouts for each action,
-    /// evaluating trajectories using Expected Free Energy.
+    /// Each action's value is the mean over the budget's rollouts (of the
+    /// budget's depth) evaluated by Expected Free Energy. Cached estimates for the same discretized
+    /// state stand in for part of them, so only the remainder (at least
+    /// `MCTS_CACHE_MIN_ROLLOUTS`) is simulated and blended in.
     pub fn plan(&mut self, state: &AgentState, priors: &SpatialGrid<20, 10>) -> Action {
-        let mut rng = rand::rng();
+        self.plan_avoiding(state, priors, None)
+    }
+
+    /// `plan`, valuing each rollout lower by `THREAT_RISK_WEIGHT` times the
+    /// mean of `threat` (a learned map of sensed predator scent) in every
+    /// cell it passes through.
+    #[allow(clippy::cast_precision_loss)] // Rollout counts are small (50)
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn plan_avoiding(
+        &mut self,
+        state: &AgentState,
+        priors: &SpatialGrid<20, 10>,
+        threat: Option<&SpatialGrid<20, 10>>,
+    ) -> Action {
+        // Drawn from a copy so rollouts can borrow the planner; written back below
+        let mut rng = self.rng.clone();
         let mut best_value = f64::NEG_INFINITY;
         let mut best_action = Action::Straight;
+        let mut best_plan = Vec::new();
         self.last_details.clear();
+        self.cache.decay();
+        let budget = self.budget;
+        let key = StateKey::new(state, priors);
+        // The cache holds values per rollout state, so budgets of any depth share it
+        let states = (budget.depth + 1) as f64;
 
         // Evaluate each possible action
         for action in Action::all() {
-            let mut total_pragmatic = 0.0;
-            let mut total_epistemic = 0.0;
+            let cached = self.cache.lookup(key, action);
+            let cached_weight = cached.map_or(0.0, |c| c.weight);
+            let fresh = budget
+                .rollouts
+                .saturating_sub(cached_weight as usize)
+                .max(MCTS_CACHE_MIN_ROLLOUTS);
+
+            let mut total_pragmatic = cached.map_or(0.0, |c| c.pragmatic * states * c.weight);
+            let mut total_epistemic = cached.map_or(0.0, |c| c.epistemic * states * c.weight);
             let mut sample_traj = Vec::new();
+            let mut best_rollout = (f64::NEG_INFINITY, Vec::new());
 
-            // Perform multiple rollouts
-            for i in 0..MCTS_ROLLOUTS {
-                let trajectory = self.rollout(*state, action, priors, &mut rng);
-                let (pragmatic, epistemic) = self.efe_components(&trajectory, priors);
+            // Perform the fresh rollouts
+            for i in 0..fresh {
+                let (trajectory, actions) =
+                    self.rollout(*state, action, budget.depth, priors, &mut rng);
+                let (pragmatic, epistemic) = self.efe_components(&trajectory, priors, threat);
                 total_pragmatic += pragmatic;
                 total_epistemic += epistemic;
 
                 if i == 0 {
                     sample_traj = trajectory.iter().map(|s| (s.x, s.y)).collect();
                 }
+                let value = self.value(pragmatic, epistemic);
+                if value > best_rollout.0 {
+                    best_rollout = (value, actions);
+                }
             }
 
-            #[allow(clippy::cast_precision_loss)] // MCTS_ROLLOUTS is small (50)
-            let avg_pragmatic = total_pragmatic / MCTS_ROLLOUTS as f64;
-            #[allow(clippy::cast_precision_loss)]
-            let avg_epistemic = total_epistemic / MCTS_ROLLOUTS as f64;
-            let avg_value = avg_pragmatic + EXPLORATION_SCALE * avg_epistemic;
+            let weight = cached_weight + fresh as f64;
+            let avg_pragmatic = total_pragmatic / weight;
+            let avg_epistemic = total_epistemic / weight;
+            let avg_value = self.value(avg_pragmatic, avg_epistemic);
+            self.cache.store(
+                key,
+                action,
+                CachedValue {
+                    pragmatic: avg_pragmatic / states,
+                    epistemic: avg_epistemic / states,
+                    weight,
+                },
+            );
 
             self.last_details.push(ActionDetail {
                 action,
@@ -195,62 +428,86 @@ impl MCTSPlanner {
                 pragmatic_value: avg_pragmatic,
                 epistemic_value: avg_epistemic,
                 sample_trajectory: sample_traj,
+                fresh_rollouts: fresh,
             });
 
             if avg_value > best_value {
                 best_value = avg_value;
                 best_action = action;
+                best_plan = best_rollout.1;
             }
         }
 
+        self.rng = rng;
         self.best_action = best_action;
+        self.best_plan = best_plan;
         best_action
     }
 
     /// Performs a single rollout from the given state.
     ///
     /// Takes the initial action, then selects random actions for the remaining depth.
+    /// Returns the visited states and the `depth` actions taken.
     #[allow(clippy::unused_self)] // Method signature for future extensibility
     fn rollout(
         &self,
         initial_state: AgentState,
         initial_action: Action,
+        depth: usize,
         priors: &SpatialGrid<20, 10>,
         rng: &mut impl Rng,
-    ) -> Vec<AgentState> {
-        let mut trajectory = Vec::with_capacity(MCTS_DEPTH + 1);
+    ) -> (Vec<AgentState>, Vec<Action>) {
+        let mut trajectory = Vec::with_capacity(depth + 1);
+        let mut taken = Vec::with_capacity(depth);
         trajectory.push(initial_state);
 
         // Take initial action
         let mut current_state = initial_state.step(initial_action, priors);
         trajectory.push(current_state);
+        taken.push(initial_action);
 
         // Continue with random actions
-        for _ in 1..MCTS_DEPTH {
+        for _ in 1..depth {
             let actions = Action::all();
-            let random_action = actions[rng.random_range(0..3)];
+            let random_action = actions[rng.random_range(0..actions.len())];
             current_state = current_state.step(random_action, priors);
             trajectory.push(current_state);
+            taken.push(random_action);
         }
 
-        trajectory
+        (trajectory, taken)
     }
 
-    /// Computes pragmatic and epistemic components separately.
+    /// Computes pragmatic and epistemic components separately; learned
+    /// `threat` counts against the pragmatic value (unvisited cells are safe).
+    /// A cell's uncertainty counts once per trajectory, however long the
+    /// rollout lingers in it, so information is only gained by moving on.
     #[allow(clippy::unused_self)] // Method signature for future extensibility
     fn efe_components(
         &self,
         trajectory: &[AgentState],
         priors: &SpatialGrid<20, 10>,
+        threat: Option<&SpatialGrid<20, 10>>,
     ) -> (f64, f64) {
         let mut pragmatic = 0.0;
         let mut epistemic = 0.0;
+        let mut seen = Vec::with_capacity(trajectory.len());
 
         for state in trajectory {
             let prior = priors.get_cell(state.x, state.y);
             pragmatic += prior.mean * state.energy;
-            let precision = prior.precision().max(MIN_PRECISION);
-            epistemic += 1.0 / precision;
+            let known = threat
+                .map(|t| t.get_cell(state.x, state.y))
+                .filter(|cell| cell.visits > 0);
+            if let Some(cell) = known {
+                pragmatic -= THREAT_RISK_WEIGHT * cell.mean;
+            }
+            let key = StateKey::new(state, priors);
+            if !seen.contains(&(key.col, key.row)) {
+                seen.push((key.col, key.row));
+                let precision = prior.precision().max(MIN_PRECISION);
+                epistemic += 1.0 / precision;
+            }
         }
 
         (pragmatic, epistemic)
@@ -263,22 +520,33 @@ impl MCTSPlanner {
     /// - Epistemic: prefers exploring uncertain regions (information gain)
     ///
     /// Higher values are better (we maximize EFE, not minimize).
-    #[allow(clippy::unused_self)] // Method signature for future extensibility
     fn expected_free_energy(&self, trajectory: &[AgentState], priors: &SpatialGrid<20, 10>) -> f64 {
-        let (pragmatic, epistemic) = self.efe_components(trajectory, priors);
-        pragmatic + EXPLORATION_SCALE * epistemic
+        let (pragmatic, epistemic) = self.efe_components(trajectory, priors, None);
+        self.value(pragmatic, epistemic)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
+    use crate::simulation::params::MCTS_DEPTH;
 
     #[test]
     fn test_action_angle_delta() {
         assert!((Action::TurnLeft.angle_delta() - PI / 4.0).abs() < 1e-10);
         assert!((Action::Straight.angle_delta()).abs() < 1e-10);
         assert!((Action::TurnRight.angle_delta() + PI / 4.0).abs() < 1e-10);
+        assert_eq!(Action::Stop.angle_delta(), 0.0);
+    }
+
+    #[test]
+    fn test_action_next_speed() {
+        assert_eq!(Action::TurnLeft.next_speed(0.7), 0.7);
+        assert!((Action::Accelerate.next_speed(0.7) - (0.7 + SPEED_STEP)).abs() < 1e-12);
+        assert_eq!(Action::Accelerate.next_speed(MAX_SPEED), MAX_SPEED);
+        assert_eq!(Action::Decelerate.next_speed(0.1), 0.0);
+        assert_eq!(Action::Stop.next_speed(0.7), 0.0);
     }
 
     #[test]
@@ -326,10 +594,7 @@ mod tests {
 
         let action = planner.plan(&state, &priors);
 
-        assert!(matches!(
-            action,
-            Action::TurnLeft | Action::Straight | Action::TurnRight
-        ));
+        assert!(Action::all().contains(&action));
     }
 
     #[test]
@@ -354,8 +619,8 @@ mod tests {
             let action = planner.plan(&state, &priors);
             match action {
                 Action::TurnLeft => action_counts[0] += 1,
-                Action::Straight => action_counts[1] += 1,
                 Action::TurnRight => action_counts[2] += 1,
+                _ => action_counts[1] += 1,
             }
         }
 
@@ -369,7 +634,7 @@ mod tests {
         // With strongly trained priors, should prefer forward directions
         // (straight or slight turns) over the opposite direction
         // This is a weak assertion due to stochastic nature
-        let forward_actions = action_counts[1]; // Straight
+        let forward_actions = action_counts[1]; // Keeping the heading
         assert!(
             forward_actions > 0 || action_counts[0] > 0 || action_counts[2] > 0,
             "Planner should make some decisions"
@@ -408,9 +673,7 @@ mod tests {
         // High-nutrient trajectory should have higher EFE (we maximize)
         assert!(
             high_efe > low_efe,
-            "High-nutrient trajectory should have higher EFE: {} vs {}",
-            high_efe,
-            low_efe
+            "High-nutrient trajectory should have higher EFE: {high_efe} vs {low_efe}"
         );
     }
 
@@ -430,7 +693,7 @@ mod tests {
         let efe = planner.expected_free_energy(&unexplored_traj, &priors);
 
         // EFE should be positive (epistemic value from unexplored regions)
-        assert!(efe > 0.0, "EFE should be positive for unexplored: {}", efe);
+        assert!(efe > 0.0, "EFE should be positive for unexplored: {efe}");
     }
 
     #[test]
@@ -440,10 +703,13 @@ mod tests {
         let planner = MCTSPlanner::new();
         let mut rng = rand::rng();
 
-        let trajectory = planner.rollout(state, Action::Straight, &priors, &mut rng);
+        let (trajectory, actions) =
+            planner.rollout(state, Action::Straight, MCTS_DEPTH, &priors, &mut rng);
 
         // Should have MCTS_DEPTH + 1 states (initial + depth steps)
         assert_eq!(trajectory.len(), MCTS_DEPTH + 1);
+        assert_eq!(actions.len(), MCTS_DEPTH);
+        assert_eq!(actions[0], Action::Straight);
 
         // All positions should be within bounds
         for state in &trajectory {
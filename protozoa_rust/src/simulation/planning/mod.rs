@@ -3,10 +3,19 @@
 //! This module provides:
 //! - Monte Carlo Tree Search for trajectory planning
 //! - Expected Free Energy computation for action evaluation
+//! - A decaying rollout cache keyed by discretized state
+//! - An adaptive rollout budget tied to energy and uncertainty
+//! - Plan commitment with interruption on surprise, hazard or arrival
 
 // Allow unused items - will be used when integrated with agent
 #![allow(dead_code, unused_imports)]
 
+mod budget;
+mod cache;
+mod commitment;
 mod mcts;
 
+pub use budget::{PlanningBudget, planning_pressure};
+pub use cache::{CachedValue, RolloutCache, StateKey};
+pub use commitment::{Interruption, PlanExecutor, PlanSignals};
 pub use mcts::{Action, ActionDetail, AgentState, MCTSPlanner};
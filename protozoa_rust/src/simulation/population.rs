@@ -0,0 +1,283 @@
+//! Many agents sharing one world.
+//!
+//! A `Population` holds the agents of a multi-agent run and which of them
+//! the dashboard follows. Each agent senses and acts in the shared dishes on
+//! its own, in index order, with its own random streams, meeting the others
+//! through the dish (the food they eat, their pheromone trails and their
+//! quorum signals) and by sensing its neighbors (see `social.rs`).
+//! Every agent is registered in the population's `Lineage`, whose ID it
+//! keeps as its stable identity (see `identity.rs`); the dead leave a corpse
+//! deposit in their dish and stay in `agents` (no longer stepped) until more
+//! than `MemoryLimits::dead_agents` have died, when the earliest added are
+//! dropped. The selected agent is always kept.
+//! Newborns start with the knowledge `belief_init` gives them (see
+//! `BeliefInit`). Predators living in a dish hunt the agents there. With
+//! `crowding` on, agents in the same dish also jostle apart and pay for
+//! their neighbors (see `Crowding`).
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::crowding::Crowding;
+use crate::simulation::events::DishEvent;
+use crate::simulation::footprint::{MemoryLimits, MemoryUsage};
+use crate::simulation::inheritance::BeliefInit;
+use crate::simulation::lineage::Lineage;
+use crate::simulation::params::DEAD_AGENT_CAPACITY;
+use crate::simulation::predator::hunt;
+use crate::simulation::social;
+use crate::simulation::spawn::SpawnConfig;
+use crate::simulation::world::World;
+use rand::Rng;
+use std::fmt::Write as _;
+
+/// Agents living in one shared world, one of them selected for display.
+#[derive(Clone)]
+pub struct Population {
+    pub agents: Vec<Protozoa>,
+    /// Family records of every agent ever added
+    pub lineage: Lineage,
+    /// Population steps taken
+    pub tick: u64,
+    /// Soft collisions and crowding costs between agents (off by default)
+    pub crowding: Crowding,
+    /// What newborns know at birth (blank by default)
+    pub belief_init: BeliefInit,
+    /// Index of the agent shown in the sidebar
+    selected: usize,
+    /// Dead agents kept before the earliest added are dropped
+    dead_capacity: usize,
+}
+
+impl Population {
+    /// No agents yet; constructors add at least one before returning.
+    fn empty() -> Self {
+        Self {
+            agents: Vec::new(),
+            lineage: Lineage::default(),
+            tick: 0,
+            crowding: Crowding::default(),
+            belief_init: BeliefInit::default(),
+            selected: 0,
+            dead_capacity: DEAD_AGENT_CAPACITY,
+        }
+    }
+
+    /// A population of one founder.
+    #[must_use]
+    pub fn single(agent: Protozoa) -> Self {
+        let mut population = Self::empty();
+        population.add(agent, None);
+        population
+    }
+
+    /// Spawns `count` founders (at least one) in the world's first dish, each
+    /// with its own start drawn from `spawn` and its streams seeded from `rng`.
+    pub fn spawn(count: usize, world: &World, spawn: &SpawnConfig, rng: &mut impl Rng) -> Self {
+        let mut population = Self::empty();
+        for _ in 0..count.max(1) {
+            let mut agent = spawn.spawn(&world.dishes[0], rng);
+            agent.seed_streams(rng.random());
+            population.add(agent, None);
+        }
+        population
+    }
+
+    /// Adds `agent` as offspring of the agent with lineage ID `parent`
+    /// (`None` for a founder), assigning its lineage ID, its creation tick
+    /// and the knowledge `belief_init` gives it; returns the ID. A name the
+    /// agent already has is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` is not in the lineage.
+    pub fn add(&mut self, mut agent: Protozoa, parent: Option<u64>) -> u64 {
+        let parent_agent = parent.and_then(|id| self.agents.iter().find(|a| a.lineage_id == id));
+        self.belief_init
+            .instill(&mut agent, parent_agent, &self.agents);
+        let record = self.lineage.register(parent, self.tick);
+        agent.lineage_id = record.id;
+        agent.created_at = self.tick;
+        self.agents.push(agent);
+        record.id
+    }
+
+    /// Number of agents.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    /// Whether the population has no agents.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+
+    /// Number of agents still alive.
+    #[must_use]
+    pub fn alive_count(&self) -> usize {
+        self.agents.iter().filter(|a| a.is_alive()).count()
+    }
+
+    /// Lets every living agent observe its neighbors (`social::observe`),
+    /// then senses and acts every living agent in its current dish, migrating
+    /// those that step into a portal (see `World::step_agent`), and lays its
+    /// pheromone trail and quorum signal there (`Protozoa::lay_pheromone`,
+    /// `Protozoa::broadcast`). An agent
+    /// that starves leaves a corpse deposit (`DishEvent::corpse`) where it
+    /// died and its death is recorded in the lineage. Then the agents in
+    /// each dish crowd each other (`Crowding::apply`) and its predators hunt
+    /// them (`predator::hunt`), and the dead past the capacity are dropped.
+    pub fn step(&mut self, world: &mut World) {
+        self.tick += 1;
+        for (id, dish) in world.dishes.iter().enumerate() {
+            social::observe(dish, self.agents.iter_mut().filter(|a| a.dish_id == id));
+        }
+        for agent in self.agents.iter_mut().filter(|a| a.is_alive()) {
+            world.step_agent(agent);
+            agent.lay_pheromone(&mut world.dishes[agent.dish_id]);
+            agent.broadcast(&mut world.dishes[agent.dish_id]);
+            if !agent.is_alive() {
+                // A corpse is always a valid pulse
+                let _ = world.dishes[agent.dish_id].inject(DishEvent::corpse(agent.x, agent.y));
+                self.lineage.record_death(agent.lineage_id, self.tick);
+            }
+        }
+        for (id, dish) in world.dishes.iter_mut().enumerate() {
+            self.crowding
+                .apply(dish, self.agents.iter_mut().filter(|a| a.dish_id == id));
+            hunt(dish, self.agents.iter_mut().filter(|a| a.dish_id == id));
+        }
+        self.compact();
+    }
+
+    /// Applies new capacities to the lineage, the dead kept and every agent's
+    /// growing structures, compacting any that are already over them.
+    #[allow(dead_code)] // Used by tests and long-run experiments
+    pub fn set_memory_limits(&mut self, limits: &MemoryLimits) {
+        self.lineage.set_capacity(limits.lineage);
+        self.dead_capacity = limits.dead_agents;
+        for agent in &mut self.agents {
+            agent.set_memory_limits(limits);
+        }
+        self.compact();
+    }
+
+    /// Approximate memory held by the agents and the lineage.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let lineage = MemoryUsage {
+            lineage: self.lineage.heap_bytes(),
+            ..MemoryUsage::default()
+        };
+        self.agents
+            .iter()
+            .map(Protozoa::memory_usage)
+            .sum::<MemoryUsage>()
+            + lineage
+    }
+
+    /// Drops the earliest added dead agents until at most `dead_capacity`
+    /// are kept, never the selected one.
+    fn compact(&mut self) {
+        let dead = self.agents.iter().filter(|a| !a.is_alive()).count();
+        let mut excess = dead.saturating_sub(self.dead_capacity);
+        if excess == 0 {
+            return;
+        }
+        let selected = self.agents[self.selected].lineage_id;
+        self.agents.retain(|a| {
+            let drop = excess > 0 && !a.is_alive() && a.lineage_id != selected;
+            excess -= usize::from(drop);
+            !drop
+        });
+        self.selected = self
+            .agents
+            .iter()
+            .position(|a| a.lineage_id == selected)
+            .unwrap_or_default();
+    }
+
+    /// The agent with lineage ID `id`, dead or alive.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and telemetry analyses
+    pub fn get(&self, id: u64) -> Option<&Protozoa> {
+        self.agents.iter().find(|a| a.lineage_id == id)
+    }
+
+    /// The agent named `name`, if any (the first one added).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and telemetry analyses
+    pub fn find_named(&self, name: &str) -> Option<&Protozoa> {
+        self.agents.iter().find(|a| a.name.as_deref() == Some(name))
+    }
+
+    /// Selects the agent with lineage ID `id`; returns false, keeping the
+    /// selection, if there is none.
+    #[allow(dead_code)] // Used by tests and telemetry analyses
+    pub fn select(&mut self, id: u64) -> bool {
+        match self.agents.iter().position(|a| a.lineage_id == id) {
+            Some(index) => {
+                self.selected = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Who is in the population, as CSV (`id,name,created,dish,alive`,
+    /// blank for no name), one row per agent in the order they were added.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests and telemetry analyses
+    pub fn roster_csv(&self) -> String {
+        let mut csv = String::from("id,name,created,dish,alive\n");
+        for agent in &self.agents {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                agent.lineage_id,
+                agent.name.as_deref().unwrap_or_default(),
+                agent.created_at,
+                agent.dish_id,
+                u8::from(agent.is_alive())
+            );
+        }
+        csv
+    }
+
+    /// Index of the selected agent.
+    #[must_use]
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    /// The agent shown in the sidebar.
+    #[must_use]
+    pub fn selected(&self) -> &Protozoa {
+        &self.agents[self.selected]
+    }
+
+    /// Mutable access to the selected agent (commands and pins go to it).
+    pub fn selected_mut(&mut self) -> &mut Protozoa {
+        &mut self.agents[self.selected]
+    }
+
+    /// Selects the next agent, wrapping around after the last.
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.agents.len();
+    }
+
+    /// Agents currently in `dish`.
+    pub fn in_dish(&self, dish: usize) -> impl Iterator<Item = &Protozoa> {
+        self.agents.iter().filter(move |a| a.dish_id == dish)
+    }
+
+    /// Positions of the living agents in dish `dish`, for the density view.
+    #[must_use]
+    pub fn positions_in(&self, dish: usize) -> Vec<(f64, f64)> {
+        self.in_dish(dish)
+            .filter(|a| a.is_alive())
+            .map(|a| (a.x, a.y))
+            .collect()
+    }
+}
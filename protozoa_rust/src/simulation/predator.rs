@@ -0,0 +1,136 @@
+//! Predators that hunt the protozoa.
+//!
+//! A `Predator` is a second species living in a `PetriDish` (`dish.predators`).
+//! It has no inner model: each tick it turns toward the nearest living prey
+//! within `PREDATOR_SENSE_RADIUS` (at most `PREDATOR_TURN_RATE`), swims at
+//! `PREDATOR_SPEED`, and drains `PREDATOR_DRAIN` energy per second from any
+//! prey within `PREDATOR_CONTACT_RADIUS`. Prey starved this way die like any
+//! other. Predators give off a threat scent, a Gaussian of width
+//! `THREAT_SCENT_RADIUS` (`Environment::get_threat`), which is what the prey
+//! senses and learns to avoid:
+//!
+//! ```text
+//! e = wrap(atan2(Δy, Δx) − θ)      θ ← θ + clamp(e, ±ω)     pursuit
+//! τ(p) = min(1, Σⱼ e^(−|p − qⱼ|² / 2r²))                  scent
+//! F_τ = ½ π_τ (τ_L² + τ_R²)                               prey VFE term
+//! ```
+//!
+//! The prey also learns a threat map τ̄ on sampled ticks, and MCTS rollouts
+//! lose w·τ̄ for every cell they visit.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::environment::{Environment, PetriDish};
+use crate::simulation::params::{
+    PREDATOR_CONTACT_RADIUS, PREDATOR_DRAIN, PREDATOR_SENSE_RADIUS, PREDATOR_SPEED,
+    PREDATOR_TURN_RATE, THREAT_SCENT_RADIUS,
+};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// A pursuit predator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Predator {
+    pub x: f64,
+    pub y: f64,
+    pub angle: f64,
+    /// Ticks spent in contact with prey
+    pub feeding_ticks: u64,
+}
+
+impl Predator {
+    /// A predator at (x, y) heading `angle`.
+    #[must_use]
+    pub fn new(x: f64, y: f64, angle: f64) -> Self {
+        Self {
+            x,
+            y,
+            angle,
+            feeding_ticks: 0,
+        }
+    }
+
+    /// `count` predators at uniform random open positions and headings in `dish`.
+    pub fn scatter(count: usize, dish: &PetriDish, rng: &mut impl Rng) -> Vec<Self> {
+        (0..count)
+            .map(|_| {
+                loop {
+                    let x = rng.random_range(0.0..=dish.width);
+                    let y = rng.random_range(0.0..=dish.height);
+                    if !dish.is_blocked(x, y) {
+                        break Self::new(x, y, rng.random_range(0.0..2.0 * PI));
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Nearest of `prey` within `PREDATOR_SENSE_RADIUS`.
+    fn nearest(&self, prey: &[(f64, f64)]) -> Option<(f64, f64)> {
+        prey.iter()
+            .map(|&(x, y)| (x, y, (x - self.x).hypot(y - self.y)))
+            .filter(|&(_, _, d)| d <= PREDATOR_SENSE_RADIUS)
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(x, y, _)| (x, y))
+    }
+
+    /// Turns toward the nearest of `prey` (else keeps its heading) and swims
+    /// one tick; a wall or the dish edge turns it around instead.
+    pub fn pursue(&mut self, prey: &[(f64, f64)], dish: &(impl Environment + ?Sized)) {
+        if let Some((x, y)) = self.nearest(prey) {
+            let wanted = (y - self.y).atan2(x - self.x);
+            let error = (wanted - self.angle + PI).rem_euclid(2.0 * PI) - PI;
+            self.angle += error.clamp(-PREDATOR_TURN_RATE, PREDATOR_TURN_RATE);
+        }
+        self.angle = self.angle.rem_euclid(2.0 * PI);
+        let step = dish.clock().rate(PREDATOR_SPEED);
+        let x = self.x + step * self.angle.cos();
+        let y = self.y + step * self.angle.sin();
+        if dish.contains(x, y) && !dish.is_blocked(x, y) {
+            (self.x, self.y) = (x, y);
+        } else {
+            self.angle = (self.angle + PI).rem_euclid(2.0 * PI);
+        }
+    }
+
+    /// Whether the predator touches something at (x, y).
+    #[must_use]
+    pub fn touches(&self, x: f64, y: f64) -> bool {
+        (x - self.x).hypot(y - self.y) <= PREDATOR_CONTACT_RADIUS
+    }
+}
+
+/// Threat scent of `predators` at (x, y): the sum of their Gaussians,
+/// capped at 1.
+#[must_use]
+pub fn threat_at(predators: &[Predator], x: f64, y: f64) -> f64 {
+    let two_sigma_sq = 2.0 * THREAT_SCENT_RADIUS * THREAT_SCENT_RADIUS;
+    predators
+        .iter()
+        .map(|p| (-((x - p.x).powi(2) + (y - p.y).powi(2)) / two_sigma_sq).exp())
+        .sum::<f64>()
+        .min(1.0)
+}
+
+/// One tick of hunting in `dish`: its predators pursue the living agents of
+/// `prey` (those in the dish), then drain every one they touch.
+pub fn hunt<'a>(dish: &mut PetriDish, prey: impl IntoIterator<Item = &'a mut Protozoa>) {
+    if dish.predators.is_empty() {
+        return;
+    }
+    let mut prey: Vec<&mut Protozoa> = prey.into_iter().filter(|a| a.is_alive()).collect();
+    let positions: Vec<(f64, f64)> = prey.iter().map(|a| (a.x, a.y)).collect();
+    let mut predators = std::mem::take(&mut dish.predators);
+    let drain = dish.clock.rate(PREDATOR_DRAIN);
+    for predator in &mut predators {
+        predator.pursue(&positions, dish);
+        let mut fed = false;
+        for agent in prey.iter_mut().filter(|a| predator.touches(a.x, a.y)) {
+            agent.energy = (agent.energy - drain).max(0.0);
+            fed = true;
+        }
+        if fed {
+            predator.feeding_ticks += 1;
+        }
+    }
+    dish.predators = predators;
+}
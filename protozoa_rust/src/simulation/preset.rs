@@ -0,0 +1,156 @@
+//! Canonical environment layouts for reproducible navigation benchmarks.
+//!
+//! Presets place static, non-decaying sources (and obstacles for the maze and
+//! corridor) at fixed fractions of the dish size, and suggest a matching
+//! deterministic start so runs differ only by the agent's own noise.
+
+use crate::simulation::environment::{NutrientSource, Obstacle};
+use crate::simulation::kernel::Kernel;
+use crate::simulation::params::{
+    MAZE_WALL_THICKNESS, PRESET_RING_SOURCES, PRESET_SOURCE_INTENSITY, PRESET_SOURCE_RADIUS,
+};
+use crate::simulation::spawn::{SpawnConfig, StartOrientation, StartPosition};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// A canonical test layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)] // Variants selected by `--preset`, benchmarks and tests
+pub enum EnvironmentPreset {
+    /// One broad source off-center: a smooth radial gradient to climb
+    Gradient,
+    /// `PRESET_RING_SOURCES` sources evenly spaced on a circle around the center
+    Ring,
+    /// Food behind three staggered walls with alternating gaps
+    Maze,
+    /// Food at the far end of a walled corridor through the middle
+    Corridor,
+}
+
+impl EnvironmentPreset {
+    /// All presets, in display order.
+    pub const ALL: [Self; 4] = [Self::Gradient, Self::Ring, Self::Maze, Self::Corridor];
+
+    /// Parses a preset name (`gradient`, `ring`, `maze`, `corridor`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Lowercase name used on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Gradient => "gradient",
+            Self::Ring => "ring",
+            Self::Maze => "maze",
+            Self::Corridor => "corridor",
+        }
+    }
+
+    /// Nutrient sources for a dish of the given size.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Source counts are tiny
+    pub fn sources(self, width: f64, height: f64) -> Vec<NutrientSource> {
+        match self {
+            Self::Gradient => vec![static_source(0.8 * width, 0.5 * height, 0.35 * width)],
+            Self::Ring => {
+                let radius = 0.35 * height;
+                (0..PRESET_RING_SOURCES)
+                    .map(|i| {
+                        let theta = 2.0 * PI * i as f64 / PRESET_RING_SOURCES as f64;
+                        static_source(
+                            0.5 * width + radius * theta.cos(),
+                            0.5 * height + radius * theta.sin(),
+                            PRESET_SOURCE_RADIUS,
+                        )
+                    })
+                    .collect()
+            }
+            Self::Maze | Self::Corridor => vec![static_source(
+                0.92 * width,
+                0.5 * height,
+                PRESET_SOURCE_RADIUS,
+            )],
+        }
+    }
+
+    /// Solid obstacles for a dish of the given size.
+    #[must_use]
+    pub fn obstacles(self, width: f64, height: f64) -> Vec<Obstacle> {
+        match self {
+            Self::Gradient | Self::Ring => Vec::new(),
+            Self::Maze => {
+                let wall = |x: f64, y_min: f64, y_max: f64| Obstacle {
+                    x_min: x - MAZE_WALL_THICKNESS / 2.0,
+                    y_min,
+                    x_max: x + MAZE_WALL_THICKNESS / 2.0,
+                    y_max,
+                };
+                // Gaps alternate bottom, top, bottom so the route zig-zags
+                vec![
+                    wall(0.25 * width, 0.0, 0.7 * height),
+                    wall(0.5 * width, 0.3 * height, height),
+                    wall(0.75 * width, 0.0, 0.7 * height),
+                ]
+            }
+            Self::Corridor => vec![
+                Obstacle {
+                    x_min: 0.1 * width,
+                    y_min: 0.0,
+                    x_max: 0.85 * width,
+                    y_max: 0.35 * height,
+                },
+                Obstacle {
+                    x_min: 0.1 * width,
+                    y_min: 0.65 * height,
+                    x_max: 0.85 * width,
+                    y_max: height,
+                },
+            ],
+        }
+    }
+
+    /// Deterministic start matching the layout (full energy).
+    #[must_use]
+    pub fn spawn_config(self, width: f64, height: f64) -> SpawnConfig {
+        let (position, orientation) = match self {
+            // Start broadside to the gradient so the agent has to turn into it
+            Self::Gradient => (
+                StartPosition::Fixed {
+                    x: 0.15 * width,
+                    y: 0.5 * height,
+                },
+                StartOrientation::Fixed(FRAC_PI_2),
+            ),
+            Self::Ring => (StartPosition::Center, StartOrientation::Fixed(0.0)),
+            Self::Maze | Self::Corridor => (
+                StartPosition::Fixed {
+                    x: 0.05 * width,
+                    y: 0.5 * height,
+                },
+                StartOrientation::Fixed(0.0),
+            ),
+        };
+        SpawnConfig {
+            position,
+            orientation,
+            ..SpawnConfig::default()
+        }
+    }
+}
+
+/// A source that never decays (and so never respawns elsewhere).
+fn static_source(x: f64, y: f64, radius: f64) -> NutrientSource {
+    NutrientSource {
+        x,
+        y,
+        radius,
+        intensity: PRESET_SOURCE_INTENSITY,
+        decay_rate: 1.0,
+        species: 0,
+        transient: false,
+        kernel: Kernel::Gaussian,
+    }
+}
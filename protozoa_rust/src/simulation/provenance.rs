@@ -0,0 +1,197 @@
+//! Per-tick action provenance.
+//!
+//! The active inference decision blends a dozen heading terms; `Provenance`
+//! records what each one contributed this tick (weighted, in radians), which
+//! actions the EFE selection and the MCTS plan chose, whether the plan was
+//! recomputed and the resulting turn and speed. `Protozoa::provenance` is
+//! refreshed every executed tick, so telemetry (the `driver` column of the
+//! export) and the dashboard can say why the agent turned. Baseline
+//! controllers attribute their whole turn to `Term::Policy`.
+
+use crate::simulation::controller::{Controller, ControllerKind, Decision};
+use crate::simulation::planning::Action;
+use std::fmt;
+
+/// One contribution to the heading change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Term {
+    /// EFE-selected action (weight 0.4)
+    Efe,
+    /// Committed MCTS plan step (`PLAN_STEP_WEIGHT`)
+    Plan,
+    /// Reactive gradient following (weight 0.2)
+    Reactive,
+    /// Per-species homeostatic steering
+    Species,
+    /// Thermotaxis
+    Thermal,
+    /// pH homeostasis
+    Ph,
+    /// Phototaxis
+    Light,
+    /// Trail following
+    Trail,
+    /// Recruitment toward the quorum signal
+    Signal,
+    /// Turn toward or away from company and along with the neighbors
+    Social,
+    /// Turn toward the gradient bearing found by scans
+    Scan,
+    /// Exploration draw, scaled by spatial uncertainty
+    Exploration,
+    /// Noise draw, scaled by VFE
+    Noise,
+    /// Panic turn on a falling reading
+    Panic,
+    /// Attraction to a remembered landmark when energy is low
+    Landmark,
+    /// Attraction to the exogenous goal
+    Goal,
+    /// Turn toward the demonstrated heading (imitation bootstrap)
+    Habit,
+    /// The whole turn of a baseline controller
+    Policy,
+    /// Turning cost of directional persistence, holding the last turn
+    Persistence,
+}
+
+/// Number of heading terms.
+pub const TERM_COUNT: usize = Term::ALL.len();
+
+impl Term {
+    /// Every term, in `Provenance::terms` order.
+    pub const ALL: [Self; 19] = [
+        Self::Efe,
+        Self::Plan,
+        Self::Reactive,
+        Self::Species,
+        Self::Thermal,
+        Self::Ph,
+        Self::Light,
+        Self::Trail,
+        Self::Signal,
+        Self::Social,
+        Self::Scan,
+        Self::Exploration,
+        Self::Noise,
+        Self::Panic,
+        Self::Landmark,
+        Self::Goal,
+        Self::Habit,
+        Self::Policy,
+        Self::Persistence,
+    ];
+
+    /// Name used in telemetry and the dashboard.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Efe => "efe",
+            Self::Plan => "plan",
+            Self::Reactive => "reactive",
+            Self::Species => "species",
+            Self::Thermal => "thermal",
+            Self::Ph => "ph",
+            Self::Light => "light",
+            Self::Trail => "trail",
+            Self::Signal => "signal",
+            Self::Social => "social",
+            Self::Scan => "scan",
+            Self::Exploration => "explore",
+            Self::Noise => "noise",
+            Self::Panic => "panic",
+            Self::Landmark => "landmark",
+            Self::Goal => "goal",
+            Self::Habit => "habit",
+            Self::Policy => "policy",
+            Self::Persistence => "persistence",
+        }
+    }
+}
+
+/// What produced one tick's decision.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Provenance {
+    /// Controller that decided
+    pub controller: ControllerKind,
+    /// Action picked by EFE (active inference only)
+    pub efe_action: Option<Action>,
+    /// Plan step executed (active inference only)
+    pub planned_action: Option<Action>,
+    /// Whether the MCTS plan was recomputed this tick
+    pub replanned: bool,
+    /// Weighted heading contributions (radians), indexed in `Term::ALL` order
+    pub terms: [f64; TERM_COUNT],
+    /// Heading change executed
+    pub turn: f64,
+    /// Speed the controller commanded (the cruise speed under active inference)
+    pub commanded_speed: f64,
+    /// Speed achieved after viscous drag
+    pub speed: f64,
+}
+
+impl Provenance {
+    /// Empty record for a tick decided by `controller`.
+    #[must_use]
+    pub fn new(controller: ControllerKind) -> Self {
+        Self {
+            controller,
+            ..Self::default()
+        }
+    }
+
+    /// Records the executed decision and the speed achieved; a baseline's
+    /// turn goes to `Term::Policy`.
+    pub fn settle(&mut self, decision: Decision, speed: f64) {
+        if self.controller != ControllerKind::ActiveInference {
+            self.terms[Term::Policy as usize] = decision.turn;
+        }
+        self.turn = decision.turn;
+        self.commanded_speed = decision.speed;
+        self.speed = speed;
+    }
+
+    /// Contribution of `term` to the turn.
+    #[must_use]
+    pub fn term(&self, term: Term) -> f64 {
+        self.terms[term as usize]
+    }
+
+    /// The term with the largest magnitude (the earliest on ties), or `None`
+    /// when every term is zero.
+    #[must_use]
+    pub fn dominant(&self) -> Option<(Term, f64)> {
+        self.ranked().first().copied()
+    }
+
+    /// Non-zero terms, largest magnitude first (`Term::ALL` order on ties).
+    #[must_use]
+    pub fn ranked(&self) -> Vec<(Term, f64)> {
+        let mut ranked: Vec<(Term, f64)> = Term::ALL
+            .into_iter()
+            .map(|term| (term, self.term(term)))
+            .filter(|&(_, value)| value != 0.0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+        ranked
+    }
+}
+
+impl fmt::Display for Provenance {
+    /// `active turn +0.120 speed 0.80: plan +0.100 efe +0.031 noise -0.011`
+    /// (the three largest terms).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} turn {:+.3} speed {:.2}",
+            self.controller.name(),
+            self.turn,
+            self.speed
+        )?;
+        for (i, (term, value)) in self.ranked().into_iter().take(3).enumerate() {
+            let separator = if i == 0 { ":" } else { "" };
+            write!(f, "{separator} {} {value:+.3}", term.name())?;
+        }
+        Ok(())
+    }
+}
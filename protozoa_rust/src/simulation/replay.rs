@@ -0,0 +1,270 @@
+//! Recorded runs that can be replayed from any tick.
+//!
+//! A `Recording` steps a `Run` headless and keeps a keyframe (a full copy
+//! of dish and agent) every `REPLAY_KEYFRAME_INTERVAL` ticks, plus the
+//! run's events. A run's future depends only on its state, random streams
+//! included, so any tick is rebuilt exactly by stepping forward from the
+//! keyframe before it; the replay view can seek without re-simulating from
+//! the start.
+//!
+//! A `Trajectory` is a run recorded elsewhere (an `export` CSV, possibly
+//! from another build) that the replay overlays on its own dish, tick for
+//! tick. Runs exported with `--dish-seed S` start in the same dish as
+//! `replay --seed S`, so the overlay shows how behavior differs there.
+
+use crate::simulation::arrival::ArrivalTarget;
+use crate::simulation::ensemble::Run;
+use crate::simulation::observer::SourceEvent;
+use std::path::Path;
+
+/// What happened at a `ReplayEvent`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplayEventKind {
+    /// The agent arrived at its goal or a landmark
+    Arrival(ArrivalTarget),
+    /// Something happened to a nutrient source
+    Source(SourceEvent),
+    /// The agent starved
+    Death,
+}
+
+/// An event of the recorded run, at the frame it first shows in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplayEvent {
+    pub tick: u64,
+    pub kind: ReplayEventKind,
+}
+
+impl ReplayEvent {
+    /// Short description for the scrubber (`arrived at goal`, `source 3 low`).
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self.kind {
+            ReplayEventKind::Arrival(ArrivalTarget::Goal) => "arrived at goal".to_string(),
+            ReplayEventKind::Arrival(ArrivalTarget::Landmark) => "arrived at landmark".to_string(),
+            ReplayEventKind::Source(SourceEvent::Depleted { index, .. }) => {
+                format!("source {index} low")
+            }
+            ReplayEventKind::Source(SourceEvent::Respawned { index, .. }) => {
+                format!("source {index} new")
+            }
+            ReplayEventKind::Source(SourceEvent::CrossedEdge { index, .. }) => {
+                format!("source {index} at edge")
+            }
+            ReplayEventKind::Source(SourceEvent::SeasonChanged { season }) => {
+                format!("{} season", season.label())
+            }
+            ReplayEventKind::Death => "agent died".to_string(),
+        }
+    }
+}
+
+/// A run recorded for replay: keyframes and events.
+#[derive(Clone)]
+pub struct Recording {
+    /// State at ticks 0, `interval`, 2·`interval`, …
+    keyframes: Vec<Run>,
+    interval: u64,
+    /// Ticks recorded (frames run from 0 to `length`)
+    length: u64,
+    /// Events in tick order
+    events: Vec<ReplayEvent>,
+}
+
+impl Recording {
+    /// Records `ticks` ticks of `run`, keeping a keyframe every `interval`
+    /// ticks (at least 1).
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)] // The recording owns the run's start
+    pub fn record(run: Run, ticks: u64, interval: u64) -> Self {
+        let interval = interval.max(1);
+        let mut keyframes = vec![run.clone()];
+        let mut events = Vec::new();
+        let mut run = run;
+        for tick in 1..=ticks {
+            let arrivals = run.agent.events.count();
+            let alive = run.agent.is_alive();
+            let mut sources = Vec::new();
+            run.step_observed(&mut |_, event| sources.push(event));
+            let mut push = |kind| events.push(ReplayEvent { tick, kind });
+            for event in sources {
+                push(ReplayEventKind::Source(event));
+            }
+            let arrived = run.agent.events.count() > arrivals;
+            if let Some(arrival) = run.agent.events.last().filter(|_| arrived) {
+                push(ReplayEventKind::Arrival(arrival.target));
+            }
+            if alive && !run.agent.is_alive() {
+                push(ReplayEventKind::Death);
+            }
+            if tick % interval == 0 {
+                keyframes.push(run.clone());
+            }
+        }
+        Self {
+            keyframes,
+            interval,
+            length: ticks,
+            events,
+        }
+    }
+
+    /// Ticks recorded; frames run from 0 to this.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    /// Whether nothing was recorded beyond the start.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Events in tick order.
+    #[must_use]
+    pub fn events(&self) -> &[ReplayEvent] {
+        &self.events
+    }
+
+    /// The run as it was after `tick` ticks (clamped to the recording).
+    #[must_use]
+    pub fn frame(&self, tick: u64) -> Run {
+        let tick = tick.min(self.length);
+        let index = usize::try_from(tick / self.interval).unwrap_or(usize::MAX);
+        let keyframe = index.min(self.keyframes.len() - 1);
+        let mut run = self.keyframes[keyframe].clone();
+        for _ in keyframe as u64 * self.interval..tick {
+            run.step();
+        }
+        run
+    }
+
+    /// Moves `run`, the frame at tick `at`, to tick `to`: steps forward when
+    /// that is no further than a keyframe interval, otherwise rebuilds from
+    /// the nearest keyframe. Returns the tick reached (clamped).
+    pub fn seek(&self, run: &mut Run, at: u64, to: u64) -> u64 {
+        let to = to.min(self.length);
+        if at <= to && to - at <= self.interval {
+            for _ in at..to {
+                run.step();
+            }
+        } else {
+            *run = self.frame(to);
+        }
+        to
+    }
+
+    /// First event after `tick`.
+    #[must_use]
+    pub fn next_event(&self, tick: u64) -> Option<&ReplayEvent> {
+        self.events.iter().find(|e| e.tick > tick)
+    }
+
+    /// Last event before `tick`.
+    #[must_use]
+    pub fn previous_event(&self, tick: u64) -> Option<&ReplayEvent> {
+        self.events.iter().rev().find(|e| e.tick < tick)
+    }
+}
+
+/// Position and energy of a loaded run at one tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrajectoryPoint {
+    pub tick: u64,
+    pub x: f64,
+    pub y: f64,
+    pub energy: f64,
+}
+
+/// A recorded run loaded for comparison: one point per tick in tick order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trajectory {
+    /// Where it came from (file name), for the legend
+    pub label: String,
+    points: Vec<TrajectoryPoint>,
+}
+
+impl Trajectory {
+    /// Parses an `export` CSV; columns are found by header name (`tick`,
+    /// `x`, `y` and `energy` are required, others are ignored).
+    ///
+    /// # Errors
+    /// Returns a message naming the missing column or the bad line.
+    pub fn from_csv(label: &str, text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim() == name)
+                .ok_or_else(|| format!("{label}: no `{name}` column"))
+        };
+        let (tick, x, y, energy) = (
+            column("tick")?,
+            column("x")?,
+            column("y")?,
+            column("energy")?,
+        );
+        let mut points: Vec<TrajectoryPoint> = Vec::new();
+        for (number, line) in lines.enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let fields: Vec<&str> = line.split(',').collect();
+            let bad = || format!("{label}: bad row on line {}", number + 2);
+            let value = |i: usize| fields.get(i).and_then(|v| v.trim().parse::<f64>().ok());
+            let point = TrajectoryPoint {
+                tick: fields
+                    .get(tick)
+                    .and_then(|v| v.trim().parse().ok())
+                    .ok_or_else(bad)?,
+                x: value(x).ok_or_else(bad)?,
+                y: value(y).ok_or_else(bad)?,
+                energy: value(energy).ok_or_else(bad)?,
+            };
+            if points.last().is_some_and(|p| p.tick >= point.tick) {
+                return Err(format!(
+                    "{label}: ticks out of order on line {}",
+                    number + 2
+                ));
+            }
+            points.push(point);
+        }
+        Ok(Self {
+            label: label.to_string(),
+            points,
+        })
+    }
+
+    /// Reads an exported `run.csv`, labelled with its path.
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_csv(&path.display().to_string(), &text)
+    }
+
+    /// Last tick recorded (0 when empty).
+    #[must_use]
+    pub fn last_tick(&self) -> u64 {
+        self.points.last().map_or(0, |p| p.tick)
+    }
+
+    /// The run at `tick`: the latest point at or before it (`None` before
+    /// the first).
+    #[must_use]
+    pub fn at(&self, tick: u64) -> Option<&TrajectoryPoint> {
+        let end = self.points.partition_point(|p| p.tick <= tick);
+        end.checked_sub(1).map(|i| &self.points[i])
+    }
+
+    /// Points with ticks in `from..=to`, oldest first.
+    #[must_use]
+    pub fn window(&self, from: u64, to: u64) -> &[TrajectoryPoint] {
+        let start = self.points.partition_point(|p| p.tick < from);
+        let end = self.points.partition_point(|p| p.tick <= to);
+        &self.points[start..end.max(start)]
+    }
+}
@@ -0,0 +1,84 @@
+//! Slow energy reserve: a glycogen-like second metabolic compartment.
+//!
+//! `Protozoa::energy` is the fast compartment: intake fills it, every cost
+//! drains it, and it is capped at 1. The reserve sits beside it. Intake
+//! that would overflow full energy is banked instead of lost, and energy
+//! above `RESERVE_STORE_LEVEL` is moved in at up to `RESERVE_STORE_RATE` per
+//! second. When energy falls below `RESERVE_RELEASE_LEVEL` the reserve
+//! refills it at up to `RESERVE_RELEASE_RATE` per second, so a run of bad
+//! luck is bridged rather than starved through. The reserve holds at most
+//! `capacity`; with zero capacity metabolism is single-compartment.
+
+use crate::simulation::clock::SimClock;
+use crate::simulation::params::{
+    RESERVE_CAPACITY, RESERVE_RELEASE_LEVEL, RESERVE_RELEASE_RATE, RESERVE_STORE_LEVEL,
+    RESERVE_STORE_RATE,
+};
+
+/// The slow compartment of a two-compartment metabolism.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnergyReserve {
+    /// Energy held in reserve, in [0, `capacity`]
+    pub level: f64,
+    /// Most the reserve can hold (0 disables it)
+    pub capacity: f64,
+}
+
+impl Default for EnergyReserve {
+    /// An empty reserve of `RESERVE_CAPACITY`.
+    fn default() -> Self {
+        Self {
+            level: 0.0,
+            capacity: RESERVE_CAPACITY,
+        }
+    }
+}
+
+impl EnergyReserve {
+    /// No reserve: the single-compartment metabolism.
+    #[allow(dead_code)] // Used by tests
+    pub const NONE: Self = Self {
+        level: 0.0,
+        capacity: 0.0,
+    };
+
+    /// Settles a tick's exchange with the fast compartment and returns the
+    /// new energy in [0, 1].
+    ///
+    /// `energy` is the fast compartment after the tick's costs and intake,
+    /// before clamping: anything above 1 is overflow to bank, anything below
+    /// 0 a deficit the reserve may cover.
+    pub fn exchange(&mut self, energy: f64, clock: &SimClock) -> f64 {
+        let room = (self.capacity - self.level).max(0.0);
+        let banked = (energy - 1.0).clamp(0.0, room);
+        self.level += banked;
+        let mut energy = energy.min(1.0);
+
+        if energy > RESERVE_STORE_LEVEL {
+            let stored = clock
+                .rate(RESERVE_STORE_RATE)
+                .min(energy - RESERVE_STORE_LEVEL)
+                .min(room - banked);
+            self.level += stored;
+            energy -= stored;
+        } else if energy < RESERVE_RELEASE_LEVEL {
+            let released = clock
+                .rate(RESERVE_RELEASE_RATE)
+                .min(RESERVE_RELEASE_LEVEL - energy)
+                .min(self.level);
+            self.level -= released;
+            energy += released;
+        }
+        energy.clamp(0.0, 1.0)
+    }
+
+    /// Share of the capacity held, or 0 without a reserve.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.capacity > 0.0 {
+            (self.level / self.capacity).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Active sensing scans: sampling the gradient at several orientations.
+//!
+//! A sensor pair reads only the slope across its baseline, so the agent
+//! cannot tell from one sample where the gradient points. A scan sweeps the
+//! pair around the body for one tick, sampling `scan_orientations` evenly
+//! spaced orientations, and fits the first harmonic of the readings,
+//! `c(ψ) ≈ a + A·cos(ψ − β)`, to estimate the gradient's bearing β before the
+//! agent commits to a turn. Over the n = 2K readings cⱼ at bearings ψⱼ:
+//!
+//! ```text
+//! β̂ = atan2(Σⱼ cⱼ sin ψⱼ, Σⱼ cⱼ cos ψⱼ)
+//! Â = (2/n) ‖(Σⱼ cⱼ cos ψⱼ, Σⱼ cⱼ sin ψⱼ)‖
+//! ```
+//!
+//! The agent keeps a `BearingBelief` `N(μ_β, σ²_β)` about β: its variance
+//! grows by `BEARING_DIFFUSION` per second as the agent moves and turns, and
+//! each scan fuses the fitted bearing in by its precision, with σ² the
+//! reading noise:
+//!
+//! ```text
+//! σ²_m = 2σ² / (n Â²)          k = σ²_β / (σ²_β + σ²_m)
+//! μ_β ← μ_β + k (β̂ − μ_β)      σ²_β ← (1 − k) σ²_β
+//! Δθ = g (1 − σ²_β / σ²_max)(μ_β − θ)
+//! ```
+//!
+//! `scan_efe` values a scan by how much it would shrink that variance, so
+//! scans pay off when the belief about the gradient's angle is vague.
+
+use crate::simulation::clock::SimClock;
+use crate::simulation::params::{BEARING_DIFFUSION, BEARING_MAX_VAR, SCAN_PRIOR_AMPLITUDE};
+use std::f64::consts::{PI, TAU};
+
+/// Fewest orientations that fit a first harmonic without aliasing.
+pub const MIN_SCAN_ORIENTATIONS: u32 = 3;
+
+/// Gaussian belief about the world-frame bearing of the nutrient gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BearingBelief {
+    /// Believed bearing (radians, in [0, 2π))
+    pub mean: f64,
+    /// Variance of the bearing (radians², at most `BEARING_MAX_VAR`)
+    pub var: f64,
+    /// Harmonic amplitude of the last scan (`SCAN_PRIOR_AMPLITUDE` before one)
+    pub amplitude: f64,
+}
+
+impl Default for BearingBelief {
+    fn default() -> Self {
+        Self {
+            mean: 0.0,
+            var: BEARING_MAX_VAR,
+            amplitude: SCAN_PRIOR_AMPLITUDE,
+        }
+    }
+}
+
+impl BearingBelief {
+    /// Grows the variance for one tick without a scan.
+    pub fn drift(&mut self, clock: &SimClock) {
+        self.var = (self.var + clock.rate(BEARING_DIFFUSION)).min(BEARING_MAX_VAR);
+    }
+
+    /// Fuses a scan's `(bearing, reading)` samples, each with variance
+    /// `noise_var`. A flat scan (no amplitude) carries no bearing.
+    pub fn observe(&mut self, samples: &[(f64, f64)], noise_var: f64) {
+        let Some((bearing, amplitude)) = fit_bearing(samples) else {
+            return;
+        };
+        self.amplitude = amplitude;
+        #[allow(clippy::cast_precision_loss)]
+        let measurement_var = 2.0 * noise_var / (samples.len() as f64 * amplitude.powi(2));
+        if !measurement_var.is_finite() {
+            return;
+        }
+        let gain = self.var / (self.var + measurement_var);
+        self.mean = (self.mean + gain * wrap(bearing - self.mean)).rem_euclid(TAU);
+        self.var *= 1.0 - gain;
+    }
+
+    /// Turn from `heading` to the believed bearing, in (−π, π].
+    #[must_use]
+    pub fn heading_error(&self, heading: f64) -> f64 {
+        wrap(self.mean - heading)
+    }
+
+    /// How settled the belief is: 0 at `BEARING_MAX_VAR`, 1 when certain.
+    #[must_use]
+    pub fn confidence(&self) -> f64 {
+        (1.0 - self.var / BEARING_MAX_VAR).clamp(0.0, 1.0)
+    }
+}
+
+/// World-frame bearings of the sensors during a scan: the pair at
+/// `heading ± spread` turned through `orientations` even steps of the full
+/// circle. Fewer than `MIN_SCAN_ORIENTATIONS` gives no bearings.
+#[must_use]
+pub fn scan_bearings(heading: f64, spread: f64, orientations: u32) -> Vec<f64> {
+    if orientations < MIN_SCAN_ORIENTATIONS {
+        return Vec::new();
+    }
+    (0..orientations)
+        .flat_map(|k| {
+            let turn = heading + TAU * f64::from(k) / f64::from(orientations);
+            [turn + spread, turn - spread]
+        })
+        .collect()
+}
+
+/// First-harmonic fit of `(bearing, reading)` samples spread evenly around
+/// the circle: the bearing β and amplitude A of `a + A·cos(ψ − β)`. `None`
+/// without samples or with a flat field.
+#[must_use]
+pub fn fit_bearing(samples: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if samples.is_empty() {
+        return None;
+    }
+    let (cos, sin) = samples.iter().fold((0.0, 0.0), |(c, s), &(psi, value)| {
+        (c + value * psi.cos(), s + value * psi.sin())
+    });
+    #[allow(clippy::cast_precision_loss)]
+    let amplitude = 2.0 * cos.hypot(sin) / samples.len() as f64;
+    (amplitude > f64::EPSILON).then(|| (sin.atan2(cos).rem_euclid(TAU), amplitude))
+}
+
+/// `angle` wrapped into (−π, π].
+fn wrap(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(TAU);
+    if wrapped > PI { wrapped - TAU } else { wrapped }
+}
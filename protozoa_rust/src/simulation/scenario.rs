@@ -0,0 +1,228 @@
+//! Scenario harness for behavioral tests.
+//!
+//! `run_scenario` builds a seeded dish and agent, steps them headless for a
+//! number of ticks and returns a `ScenarioOutcome`: the agent's trajectory,
+//! summary metrics and the final run. The assertion helpers state behavior
+//! in terms of that outcome ("reaches the patch within 500 ticks", "still
+//! has energy after 300 ticks") and panic with the trajectory facts a
+//! failing test needs, instead of poking at agent internals:
+//!
+//! ```text
+//! let outcome = run_scenario(&Scenario::Preset(EnvironmentPreset::Gradient), seed, 500);
+//! assert_reaches_region(&outcome, &Region::around_source(&outcome.run.dish.sources[0]), 500);
+//! ```
+
+// Library API for behavioral tests; the TUI binary does not use it
+#![allow(dead_code)]
+
+use crate::simulation::ensemble::Run;
+use crate::simulation::environment::{NutrientSource, PetriDish};
+use crate::simulation::generator::GeneratorConfig;
+use crate::simulation::params::{DISH_HEIGHT, DISH_WIDTH, EXHAUSTION_THRESHOLD};
+use crate::simulation::preset::EnvironmentPreset;
+use crate::simulation::spawn::SpawnConfig;
+use crate::simulation::streams::Stream;
+
+/// Environment a scenario runs in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)] // Built once per run
+pub enum Scenario {
+    /// A canonical layout at the default dish size, with its matching start
+    Preset(EnvironmentPreset),
+    /// A procedurally generated dish, agent placed by `spawn`
+    Generated {
+        generator: GeneratorConfig,
+        spawn: SpawnConfig,
+    },
+}
+
+impl Scenario {
+    /// Builds the dish and agent for `seed`.
+    ///
+    /// As in `VecEnv`, the dish, the agent's start and all of its random
+    /// streams derive from the seed, so the same seed replays exactly.
+    #[must_use]
+    pub fn build(&self, seed: u64) -> Run {
+        let (dish, spawn) = match self {
+            Self::Preset(preset) => (
+                PetriDish::from_preset(*preset, DISH_WIDTH, DISH_HEIGHT),
+                preset.spawn_config(DISH_WIDTH, DISH_HEIGHT),
+            ),
+            Self::Generated { generator, spawn } => (PetriDish::generate(seed, generator), *spawn),
+        };
+        let mut agent = spawn.spawn(&dish, &mut Stream::Spawn.rng(seed));
+        agent.seed_streams(seed);
+        agent.sensor_noise = dish.config.sensor_noise;
+        agent.motor_noise = dish.config.motor_noise;
+        agent.inference = dish.config.inference;
+        Run { dish, agent }
+    }
+}
+
+/// Agent state after one tick of a scenario.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TracePoint {
+    /// Tick just completed (the start is tick 0)
+    pub tick: u64,
+    pub x: f64,
+    pub y: f64,
+    pub energy: f64,
+    /// Variational Free Energy
+    pub vfe: f64,
+}
+
+/// Summary of a finished scenario.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScenarioMetrics {
+    pub final_energy: f64,
+    pub min_energy: f64,
+    /// Path length travelled by the agent
+    pub distance: f64,
+    /// Fraction of spatial grid cells visited
+    pub coverage: f64,
+    /// First tick the agent was exhausted, if ever
+    pub exhausted_at: Option<u64>,
+}
+
+/// Everything a behavioral test can assert on.
+#[derive(Clone)]
+pub struct ScenarioOutcome {
+    /// Seed the scenario ran with (for failure messages)
+    pub seed: u64,
+    /// One point per tick, starting with the spawn at tick 0
+    pub trace: Vec<TracePoint>,
+    pub metrics: ScenarioMetrics,
+    /// Dish and agent as they ended
+    pub run: Run,
+}
+
+impl ScenarioOutcome {
+    /// First tick at which the agent was inside `region`.
+    #[must_use]
+    pub fn first_tick_in(&self, region: &Region) -> Option<u64> {
+        self.trace
+            .iter()
+            .find(|p| region.contains(p.x, p.y))
+            .map(|p| p.tick)
+    }
+
+    /// Trace point at `tick` (the last one if the run was shorter).
+    #[must_use]
+    pub fn at(&self, tick: u64) -> Option<&TracePoint> {
+        self.trace.iter().take_while(|p| p.tick <= tick).last()
+    }
+}
+
+/// A disk of the dish, e.g. a food patch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Region {
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+}
+
+impl Region {
+    /// Disk of `radius` around (x, y).
+    #[must_use]
+    pub fn around(x: f64, y: f64, radius: f64) -> Self {
+        Self { x, y, radius }
+    }
+
+    /// Disk of the source's radius around its center.
+    #[must_use]
+    pub fn around_source(source: &NutrientSource) -> Self {
+        Self::around(source.x, source.y, source.radius)
+    }
+
+    /// Returns true if (x, y) lies in the disk.
+    #[must_use]
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        (x - self.x).hypot(y - self.y) <= self.radius
+    }
+}
+
+/// Runs `scenario` under `seed` for `ticks` ticks and records the outcome.
+#[must_use]
+pub fn run_scenario(scenario: &Scenario, seed: u64, ticks: u64) -> ScenarioOutcome {
+    run_prepared(scenario.build(seed), seed, ticks)
+}
+
+/// Like `run_scenario`, for a run built with `Scenario::build` and then
+/// adjusted (e.g. a morphology parameter changed) before the first tick.
+#[must_use]
+pub fn run_prepared(mut run: Run, seed: u64, ticks: u64) -> ScenarioOutcome {
+    let point = |tick: u64, run: &Run| TracePoint {
+        tick,
+        x: run.agent.x,
+        y: run.agent.y,
+        energy: run.agent.energy,
+        vfe: run.agent.current_vfe,
+    };
+
+    let mut trace = Vec::with_capacity(usize::try_from(ticks).unwrap_or(0) + 1);
+    trace.push(point(0, &run));
+    let mut metrics = ScenarioMetrics {
+        min_energy: run.agent.energy,
+        ..ScenarioMetrics::default()
+    };
+    for tick in 1..=ticks {
+        let (x, y) = (run.agent.x, run.agent.y);
+        run.step();
+        metrics.distance += (run.agent.x - x).hypot(run.agent.y - y);
+        metrics.min_energy = metrics.min_energy.min(run.agent.energy);
+        if metrics.exhausted_at.is_none() && run.agent.energy <= EXHAUSTION_THRESHOLD {
+            metrics.exhausted_at = Some(tick);
+        }
+        trace.push(point(tick, &run));
+    }
+    metrics.final_energy = run.agent.energy;
+    metrics.coverage = run.agent.spatial_priors.visited_fraction();
+
+    ScenarioOutcome {
+        seed,
+        trace,
+        metrics,
+        run,
+    }
+}
+
+/// Asserts that the agent entered `region` by tick `within`.
+///
+/// # Panics
+/// Panics with the closest approach if it did not.
+pub fn assert_reaches_region(outcome: &ScenarioOutcome, region: &Region, within: u64) {
+    match outcome.first_tick_in(region) {
+        Some(tick) if tick <= within => {}
+        reached => {
+            let closest = outcome
+                .trace
+                .iter()
+                .filter(|p| p.tick <= within)
+                .map(|p| (p.x - region.x).hypot(p.y - region.y))
+                .fold(f64::INFINITY, f64::min);
+            panic!(
+                "seed {}: agent did not reach {region:?} within {within} ticks \
+                 (closest {closest:.1}, first entry {reached:?})",
+                outcome.seed
+            );
+        }
+    }
+}
+
+/// Asserts that the agent's energy was above `threshold` at tick `after`.
+///
+/// # Panics
+/// Panics if it was not, or if the scenario ran for fewer than `after` ticks.
+pub fn assert_energy_above_after(outcome: &ScenarioOutcome, after: u64, threshold: f64) {
+    let point = outcome
+        .at(after)
+        .filter(|p| p.tick == after)
+        .unwrap_or_else(|| panic!("seed {}: scenario ended before tick {after}", outcome.seed));
+    assert!(
+        point.energy > threshold,
+        "seed {}: energy {:.3} at tick {after} is not above {threshold} (min {:.3})",
+        outcome.seed,
+        point.energy,
+        outcome.metrics.min_energy
+    );
+}
@@ -0,0 +1,79 @@
+//! Aging: an age counter that slowly wears down the agent's limits.
+//!
+//! `Protozoa::age` counts the simulated seconds an agent has lived, dormant
+//! or not. Until `onset` nothing changes; from then on the agent's vigor
+//! decays exponentially at `rate` per second toward `floor`. Vigor caps the
+//! commanded speed at its share of `MAX_SPEED`, shortens the sensors' reach
+//! and slows belief learning, so an old agent swims slower, senses only
+//! close by and adapts sluggishly, and a lifespan follows from the dish
+//! rather than being fixed. With `rate` 0 (the default) agents never age.
+
+use crate::simulation::params::{SENESCENCE_FLOOR, SENESCENCE_ONSET, SENESCENCE_RATE};
+
+/// How an agent's vigor declines with age.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Senescence {
+    /// Age in seconds at which decline begins
+    pub onset: f64,
+    /// Exponential decline of vigor per second past `onset` (0 = ageless)
+    pub rate: f64,
+    /// Vigor the decline levels off at, in [0, 1]
+    pub floor: f64,
+}
+
+impl Default for Senescence {
+    fn default() -> Self {
+        Self {
+            onset: SENESCENCE_ONSET,
+            rate: SENESCENCE_RATE,
+            floor: SENESCENCE_FLOOR,
+        }
+    }
+}
+
+impl Senescence {
+    /// Decline at `rate` per second from `onset`, to the default floor.
+    #[must_use]
+    pub fn new(onset: f64, rate: f64) -> Self {
+        Self {
+            onset,
+            rate,
+            ..Self::default()
+        }
+    }
+
+    /// Returns true if the agent never ages.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.rate <= 0.0
+    }
+
+    /// Share of its young limits an agent of `age` seconds keeps, in
+    /// [`floor`, 1].
+    #[must_use]
+    pub fn vigor(&self, age: f64) -> f64 {
+        let past = age - self.onset;
+        if self.is_off() || past <= 0.0 {
+            return 1.0;
+        }
+        self.floor + (1.0 - self.floor) * (-self.rate * past).exp()
+    }
+
+    /// Checks that onset and rate are finite and not negative, and the
+    /// floor within [0, 1].
+    ///
+    /// # Errors
+    /// Names the first offending value.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.onset.is_finite() && self.onset >= 0.0) {
+            return Err(format!("senescence onset out of range: {}", self.onset));
+        }
+        if !(self.rate.is_finite() && self.rate >= 0.0) {
+            return Err(format!("senescence rate out of range: {}", self.rate));
+        }
+        if !(0.0..=1.0).contains(&self.floor) {
+            return Err(format!("senescence floor out of range: {}", self.floor));
+        }
+        Ok(())
+    }
+}
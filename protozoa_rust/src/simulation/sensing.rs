@@ -0,0 +1,324 @@
+//! The `Sensor` abstraction and the agent's sensor suite.
+//!
+//! A sensor samples the dish from the agent's body (`Pose`: position,
+//! heading, and the spread and reach of its sensors this tick) and appends
+//! a fixed number of values to a reading. The chemoreceptor array that
+//! feeds inference (`Morphology::sensors`, a `SensorArray`) is one; the
+//! agent applies noise, calibration and bias correction to its raw
+//! readings. Further sensors go in the agent's `SensorSuite`
+//! (`Protozoa::sensor_suite`, `--suite`), sampled on the same ticks:
+//!
+//! - `ChemoreceptorPair`: nutrient at a left and a right tip
+//! - `TemporalDerivative`: rate of change of the nutrient at the body
+//!   between samples (how bacteria read a gradient, one point over time)
+//! - `TouchSensor`: contact of a left and a right feeler with an obstacle
+//!   or the dish edge
+//!
+//! As with `Controller`, `SensorKind` dispatches over the built-in sensors
+//! so the suite stays `Clone` and comparable. The default suite is empty.
+
+use crate::simulation::environment::Environment;
+use crate::simulation::numerics::{NonFinite, finite};
+use crate::simulation::params::{MAX_SUITE_SENSORS, TOUCH_REACH};
+
+/// The agent's body as its sensors see it this tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pose {
+    pub x: f64,
+    pub y: f64,
+    /// Heading (radians)
+    pub angle: f64,
+    /// Angle of the pair's tips off the heading (`sensor_angle`, widened by
+    /// a saccade)
+    pub spread: f64,
+    /// Distance of the pair's tips from the body center (`sensor_reach`)
+    pub reach: f64,
+    /// The agent's tick count, timing samples in dishes without a clock
+    pub tick: u64,
+}
+
+impl Pose {
+    /// Point at `bearing` (a share of `spread`, +1 = left) and `reach` (a
+    /// share of the pose's reach).
+    #[must_use]
+    pub fn point(&self, bearing: f64, reach: f64) -> (f64, f64) {
+        let theta = self.angle + self.spread * bearing;
+        let distance = self.reach * reach;
+        (
+            self.x + distance * theta.cos(),
+            self.y + distance * theta.sin(),
+        )
+    }
+}
+
+/// A source of readings from the dish.
+pub trait Sensor {
+    /// Lowercase name used on the command line and in readings.
+    fn name(&self) -> &'static str;
+
+    /// Number of values one sample appends.
+    fn channels(&self) -> usize;
+
+    /// Samples the dish from `pose`, appending `channels()` values to `out`.
+    fn sample(&mut self, pose: &Pose, dish: &(impl Environment + ?Sized), out: &mut Vec<f64>);
+}
+
+/// A left and a right chemoreceptor at the pair's bearings (±1), reading
+/// the nutrient as the array does but without its noise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChemoreceptorPair {
+    /// Distance of the tips as a share of the pose's reach
+    pub reach: f64,
+}
+
+impl Default for ChemoreceptorPair {
+    fn default() -> Self {
+        Self { reach: 1.0 }
+    }
+}
+
+impl Sensor for ChemoreceptorPair {
+    fn name(&self) -> &'static str {
+        "pair"
+    }
+
+    fn channels(&self) -> usize {
+        2
+    }
+
+    fn sample(&mut self, pose: &Pose, dish: &(impl Environment + ?Sized), out: &mut Vec<f64>) {
+        for bearing in [1.0, -1.0] {
+            let (x, y) = pose.point(bearing, self.reach);
+            out.push(dish.sensed_concentration(x, y));
+        }
+    }
+}
+
+/// Rate of change of the nutrient at the body center between two samples,
+/// per second: `(c − c_last) / ((t − t_last)·dt)` over the agent's ticks t.
+/// The first sample reads 0.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TemporalDerivative {
+    /// Concentration and tick of the last sample
+    last: Option<(f64, u64)>,
+}
+
+impl TemporalDerivative {
+    /// Concentration and tick of the last sample, if any.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub const fn last(&self) -> Option<(f64, u64)> {
+        self.last
+    }
+}
+
+impl Sensor for TemporalDerivative {
+    fn name(&self) -> &'static str {
+        "temporal"
+    }
+
+    fn channels(&self) -> usize {
+        1
+    }
+
+    #[allow(clippy::cast_precision_loss)] // Tick gaps are small
+    fn sample(&mut self, pose: &Pose, dish: &(impl Environment + ?Sized), out: &mut Vec<f64>) {
+        let dt = dish.clock().seconds_per_tick;
+        let concentration = dish.sensed_concentration(pose.x, pose.y);
+        let rate = match self.last {
+            Some((last, tick)) if pose.tick > tick => {
+                (concentration - last) / ((pose.tick - tick) as f64 * dt)
+            }
+            _ => 0.0,
+        };
+        self.last = Some((concentration, pose.tick));
+        out.push(rate);
+    }
+}
+
+/// A left and a right feeler at the pair's bearings, reading 1 where the tip
+/// touches an obstacle or lies outside the dish and 0 elsewhere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchSensor {
+    /// Length of the feelers as a share of the pose's reach
+    pub reach: f64,
+}
+
+impl Default for TouchSensor {
+    fn default() -> Self {
+        Self { reach: TOUCH_REACH }
+    }
+}
+
+impl Sensor for TouchSensor {
+    fn name(&self) -> &'static str {
+        "touch"
+    }
+
+    fn channels(&self) -> usize {
+        2
+    }
+
+    fn sample(&mut self, pose: &Pose, dish: &(impl Environment + ?Sized), out: &mut Vec<f64>) {
+        for bearing in [1.0, -1.0] {
+            let (x, y) = pose.point(bearing, self.reach);
+            let touching = !dish.contains(x, y) || dish.is_blocked(x, y);
+            out.push(if touching { 1.0 } else { 0.0 });
+        }
+    }
+}
+
+/// The built-in sensors a suite can hold.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorKind {
+    Pair(ChemoreceptorPair),
+    Temporal(TemporalDerivative),
+    Touch(TouchSensor),
+}
+
+impl SensorKind {
+    /// Every kind, with default settings.
+    pub const ALL: [Self; 3] = [
+        Self::Pair(ChemoreceptorPair { reach: 1.0 }),
+        Self::Temporal(TemporalDerivative { last: None }),
+        Self::Touch(TouchSensor { reach: TOUCH_REACH }),
+    ];
+
+    /// The kind named `name` (case-insensitive), with default settings.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl Sensor for SensorKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Pair(sensor) => sensor.name(),
+            Self::Temporal(sensor) => sensor.name(),
+            Self::Touch(sensor) => sensor.name(),
+        }
+    }
+
+    fn channels(&self) -> usize {
+        match self {
+            Self::Pair(sensor) => sensor.channels(),
+            Self::Temporal(sensor) => sensor.channels(),
+            Self::Touch(sensor) => sensor.channels(),
+        }
+    }
+
+    fn sample(&mut self, pose: &Pose, dish: &(impl Environment + ?Sized), out: &mut Vec<f64>) {
+        match self {
+            Self::Pair(sensor) => sensor.sample(pose, dish, out),
+            Self::Temporal(sensor) => sensor.sample(pose, dish, out),
+            Self::Touch(sensor) => sensor.sample(pose, dish, out),
+        }
+    }
+}
+
+/// Sensors the agent samples besides its chemoreceptor array, and their
+/// last readings (`Protozoa::sensor_suite`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SensorSuite {
+    sensors: Vec<SensorKind>,
+    /// Values of the last sample, sensor after sensor
+    readings: Vec<f64>,
+}
+
+impl SensorSuite {
+    /// A suite of `sensors`, not yet sampled.
+    ///
+    /// # Errors
+    /// Returns a message if there are more than `MAX_SUITE_SENSORS`.
+    pub fn new(sensors: Vec<SensorKind>) -> Result<Self, String> {
+        if sensors.len() > MAX_SUITE_SENSORS {
+            return Err(format!(
+                "a sensor suite holds at most {MAX_SUITE_SENSORS} sensors, not {}",
+                sensors.len()
+            ));
+        }
+        Ok(Self {
+            sensors,
+            readings: Vec::new(),
+        })
+    }
+
+    /// Parses a comma-separated list of sensor names such as
+    /// `temporal,touch` (see `SensorKind::ALL`).
+    ///
+    /// # Errors
+    /// Returns a message naming an unknown sensor, or if there are too many.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let sensors = spec
+            .split(',')
+            .map(|name| {
+                SensorKind::parse(name).ok_or_else(|| {
+                    let known: Vec<&str> = SensorKind::ALL.iter().map(Sensor::name).collect();
+                    format!(
+                        "unknown sensor: {} (expected {})",
+                        name.trim(),
+                        known.join(", ")
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Self::new(sensors)
+    }
+
+    /// Returns true if the suite holds no sensors.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty()
+    }
+
+    /// The sensors, in sampling order.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn sensors(&self) -> &[SensorKind] {
+        &self.sensors
+    }
+
+    /// Samples every sensor from `pose`, replacing the last readings.
+    pub fn sense(&mut self, pose: &Pose, dish: &(impl Environment + ?Sized)) {
+        self.readings.clear();
+        for sensor in &mut self.sensors {
+            sensor.sample(pose, dish, &mut self.readings);
+        }
+    }
+
+    /// Every value of the last sample, sensor after sensor.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn readings(&self) -> &[f64] {
+        &self.readings
+    }
+
+    /// Values of the first sensor named `name` in the last sample (empty
+    /// before the first sample).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn reading(&self, name: &str) -> Option<&[f64]> {
+        let mut start = 0;
+        for sensor in &self.sensors {
+            let end = start + sensor.channels();
+            if sensor.name() == name {
+                return Some(self.readings.get(start..end).unwrap_or(&[]));
+            }
+            start = end;
+        }
+        None
+    }
+
+    /// Checks the last readings (strict numerical mode).
+    ///
+    /// # Errors
+    /// The first one that is NaN or infinite.
+    pub fn check_finite(&self) -> Result<(), NonFinite> {
+        self.readings
+            .iter()
+            .try_for_each(|&value| finite(value, "sensor_suite.reading").map(|_| ()))
+    }
+}
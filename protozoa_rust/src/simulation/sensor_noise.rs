@@ -0,0 +1,120 @@
+//! Chemoreceptor noise: what makes a reading differ from the field.
+//!
+//! Each receptor channel adds three errors to the raw concentration before
+//! calibration: a constant `bias`, an offset that random-walks by `drift`
+//! per √second, and white Gaussian noise with standard deviation `std`
+//! drawn afresh for every reading. Noisy readings stop at 0, so noise never
+//! fakes the toxic void (-1), and void readings pass through untouched.
+//!
+//! The default is noiseless. White noise gives a `PrecisionEstimator` a
+//! true precision to find, `1/std²`, while bias and drift are slow errors
+//! left to the beliefs and the `BiasEstimator`. Noise draws come from the
+//! agent's own `Stream::Sensing`.
+
+use crate::simulation::clock::SimClock;
+use crate::simulation::streams::standard_normal;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Noise parameters of one receptor channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChannelNoise {
+    /// Standard deviation of the white noise on each reading
+    pub std: f64,
+    /// Constant offset on every reading
+    pub bias: f64,
+    /// Spread of the wandering offset after one second (random walk)
+    pub drift: f64,
+}
+
+impl ChannelNoise {
+    /// A noisy reading of `raw` with the channel's current drift `offset`.
+    pub fn read(&self, raw: f64, offset: f64, rng: &mut impl Rng) -> f64 {
+        if raw < 0.0 {
+            return raw;
+        }
+        let white = if self.std > 0.0 {
+            self.std * standard_normal(rng)
+        } else {
+            0.0
+        };
+        (raw + self.bias + offset + white).max(0.0)
+    }
+
+    /// The drift `offset` one tick later.
+    pub fn drift(&self, offset: f64, clock: &SimClock, rng: &mut impl Rng) -> f64 {
+        if self.drift > 0.0 {
+            offset + clock.diffusion(self.drift) * standard_normal(rng)
+        } else {
+            offset
+        }
+    }
+}
+
+/// Noise of the left and right chemoreceptor channels (`[sensor_noise]`
+/// in a dish config).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SensorNoise {
+    pub left: ChannelNoise,
+    pub right: ChannelNoise,
+}
+
+impl SensorNoise {
+    /// The same noise on both channels.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub const fn symmetric(channel: ChannelNoise) -> Self {
+        Self {
+            left: channel,
+            right: channel,
+        }
+    }
+
+    /// Returns true if readings equal the raw reads.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Noisy `(left, right)` readings of `raw` under drift `offsets`.
+    pub fn read(&self, raw: f64, offsets: (f64, f64), rng: &mut impl Rng) -> (f64, f64) {
+        (
+            self.left.read(raw, offsets.0, rng),
+            self.right.read(raw, offsets.1, rng),
+        )
+    }
+
+    /// The drift `offsets` one tick later.
+    pub fn drift(&self, offsets: (f64, f64), clock: &SimClock, rng: &mut impl Rng) -> (f64, f64) {
+        (
+            self.left.drift(offsets.0, clock, rng),
+            self.right.drift(offsets.1, clock, rng),
+        )
+    }
+
+    /// Checks that every value is finite and no spread is negative.
+    ///
+    /// # Errors
+    /// Names the first offending key.
+    pub fn validate(&self) -> Result<(), String> {
+        for (side, channel) in [("left", &self.left), ("right", &self.right)] {
+            for (key, value) in [
+                ("std", channel.std),
+                ("bias", channel.bias),
+                ("drift", channel.drift),
+            ] {
+                if !value.is_finite() {
+                    return Err(format!("sensor_noise.{side}.{key} must be finite"));
+                }
+            }
+            if channel.std < 0.0 || channel.drift < 0.0 {
+                return Err(format!(
+                    "sensor_noise.{side}.std and drift must not be negative"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
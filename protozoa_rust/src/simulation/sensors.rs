@@ -0,0 +1,190 @@
+//! Chemoreceptor arrays.
+//!
+//! The agent samples nutrient with an array of chemoreceptors instead of a
+//! fixed left/right pair. Each `Chemoreceptor` sits at a bearing and a reach
+//! relative to the morphology's `sensor_angle` and `sensor_dist`, so
+//! morphogenesis, sweeps and evolution still scale the whole array. The
+//! default array is the classic pair (bearings +1 and −1, reach 1), which
+//! reads exactly as before.
+//!
+//! The readings feed the observation model receptor by receptor
+//! (`GenerativeModel::receptor_prediction`); the left and right channels
+//! (`Protozoa::val_l`/`val_r`) that steering, memory and the dashboard read
+//! are the means over each side (`side_means`). As a `Sensor` the array
+//! appends each receptor's raw concentration in sampling order.
+
+use crate::simulation::environment::Environment;
+use crate::simulation::inference::ReceptorObservation;
+use crate::simulation::params::MAX_CHEMORECEPTORS;
+use crate::simulation::sensing::{Pose, Sensor};
+
+/// One chemoreceptor, placed relative to the agent's heading.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chemoreceptor {
+    /// Bearing as a share of `sensor_angle` (+1 = left of the pair, −1 =
+    /// right, 0 = straight ahead)
+    pub bearing: f64,
+    /// Distance from the body center as a share of `sensor_dist`
+    pub reach: f64,
+}
+
+impl Chemoreceptor {
+    /// A receptor at `bearing` and `reach`.
+    #[must_use]
+    pub const fn new(bearing: f64, reach: f64) -> Self {
+        Self { bearing, reach }
+    }
+}
+
+/// Up to `MAX_CHEMORECEPTORS` receptors, stored inline so `Morphology`
+/// stays `Copy`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SensorArray {
+    len: usize,
+    receptors: [Chemoreceptor; MAX_CHEMORECEPTORS],
+}
+
+impl Default for SensorArray {
+    fn default() -> Self {
+        Self::pair()
+    }
+}
+
+impl SensorArray {
+    /// The classic left/right pair.
+    #[must_use]
+    pub fn pair() -> Self {
+        let mut receptors = [Chemoreceptor::new(0.0, 1.0); MAX_CHEMORECEPTORS];
+        receptors[0] = Chemoreceptor::new(1.0, 1.0);
+        receptors[1] = Chemoreceptor::new(-1.0, 1.0);
+        Self { len: 2, receptors }
+    }
+
+    /// `count` receptors fanned evenly from the left of the pair (+1) to the
+    /// right (−1) at reach 1; a single receptor points straight ahead.
+    ///
+    /// # Errors
+    /// Returns a message if `count` is 0 or above `MAX_CHEMORECEPTORS`.
+    #[allow(clippy::cast_precision_loss)] // Receptor indices are small
+    pub fn fan(count: usize) -> Result<Self, String> {
+        let receptors: Vec<Chemoreceptor> = (0..count)
+            .map(|i| {
+                let bearing = if count == 1 {
+                    0.0
+                } else {
+                    1.0 - 2.0 * i as f64 / (count - 1) as f64
+                };
+                Chemoreceptor::new(bearing, 1.0)
+            })
+            .collect();
+        Self::new(&receptors)
+    }
+
+    /// An array of the given receptors.
+    ///
+    /// # Errors
+    /// Returns a message if there are none or more than
+    /// `MAX_CHEMORECEPTORS`, a bearing is not finite or a reach is not
+    /// positive.
+    pub fn new(receptors: &[Chemoreceptor]) -> Result<Self, String> {
+        if receptors.is_empty() || receptors.len() > MAX_CHEMORECEPTORS {
+            return Err(format!(
+                "a sensor array has 1 to {MAX_CHEMORECEPTORS} receptors, not {}",
+                receptors.len()
+            ));
+        }
+        if let Some(bad) = receptors
+            .iter()
+            .find(|r| !r.bearing.is_finite() || !r.reach.is_finite() || r.reach <= 0.0)
+        {
+            return Err(format!(
+                "invalid receptor {}:{} (finite bearing, positive reach)",
+                bad.bearing, bad.reach
+            ));
+        }
+        let mut array = Self {
+            len: receptors.len(),
+            receptors: [Chemoreceptor::new(0.0, 1.0); MAX_CHEMORECEPTORS],
+        };
+        array.receptors[..receptors.len()].copy_from_slice(receptors);
+        Ok(array)
+    }
+
+    /// Parses `N` (a fan of N receptors) or a comma-separated list of
+    /// `BEARING:REACH` receptors such as `1:1,0:1.5,-1:1`.
+    ///
+    /// # Errors
+    /// Returns a message for a malformed spec or an invalid array.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        if let Ok(count) = spec.trim().parse::<usize>() {
+            return Self::fan(count);
+        }
+        let receptors = spec
+            .split(',')
+            .map(|item| {
+                let (bearing, reach) = item
+                    .trim()
+                    .split_once(':')
+                    .ok_or_else(|| format!("expected BEARING:REACH: {item}"))?;
+                let number = |text: &str| {
+                    text.trim()
+                        .parse::<f64>()
+                        .map_err(|_| format!("invalid number in {item}: {text}"))
+                };
+                Ok(Chemoreceptor::new(number(bearing)?, number(reach)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Self::new(&receptors)
+    }
+
+    /// The receptors, in sampling order.
+    #[must_use]
+    pub fn receptors(&self) -> &[Chemoreceptor] {
+        &self.receptors[..self.len]
+    }
+
+    /// Sampling cost relative to the pair: the total reach over 2, so the
+    /// pair costs exactly `sensing_cost · sensor_dist` per sample.
+    #[must_use]
+    pub fn cost_factor(&self) -> f64 {
+        self.receptors().iter().map(|r| r.reach).sum::<f64>() / 2.0
+    }
+}
+
+impl Sensor for SensorArray {
+    fn name(&self) -> &'static str {
+        "array"
+    }
+
+    fn channels(&self) -> usize {
+        self.len
+    }
+
+    fn sample(&mut self, pose: &Pose, dish: &(impl Environment + ?Sized), out: &mut Vec<f64>) {
+        for receptor in self.receptors() {
+            let (x, y) = pose.point(receptor.bearing, receptor.reach);
+            out.push(dish.sensed_concentration(x, y));
+        }
+    }
+}
+
+/// Left and right channel readings: the means over receptors believed at a
+/// positive and a negative bearing, receptors at bearing 0 counting on both
+/// sides. A side without receptors reads the mean of all of them.
+#[must_use]
+pub fn side_means(readings: &[ReceptorObservation]) -> (f64, f64) {
+    let mean = |keep: fn(f64) -> bool| {
+        let (sum, count) = readings
+            .iter()
+            .filter(|reading| keep(reading.bearing))
+            .fold((0.0, 0.0), |(sum, count), reading| {
+                (sum + reading.value, count + 1.0)
+            });
+        (count > 0.0).then(|| sum / count)
+    };
+    let all = mean(|_| true).unwrap_or(0.0);
+    (
+        mean(|b| b >= 0.0).unwrap_or(all),
+        mean(|b| b <= 0.0).unwrap_or(all),
+    )
+}
@@ -0,0 +1,75 @@
+//! Quorum-sensing signals broadcast between agents.
+//!
+//! An agent exploiting a patch broadcasts a signal (`Morphology::signal_emission`
+//! per second) into its dish's `SignalField`. A broadcast is heard across the
+//! dish as a Gaussian of width `SIGNAL_RADIUS` around where it was sent and
+//! fades by `SIGNAL_DECAY` per second. Agents sense the field with a pair of
+//! signal receptors; how much the reading matters is the precision of their
+//! preference for it (`PriorPrecision::signal`), so a model that weights the
+//! signal is drawn toward the crowd at a good patch: recruitment. Over
+//! broadcasts b of level `ℓ_b` sent at `q_b`, with precision `π_σ`:
+//!
+//! ```text
+//! σ(p) = min(1, Σ_b ℓ_b e^(−|p − q_b|² / 2r²))
+//! F_σ = ½ π_σ [(1 − σ_L)² + (1 − σ_R)²]
+//! Δθ = g π_σ (σ_L − σ_R)
+//! ```
+//!
+//! At `π_σ` = 0 (the default) the signal is ignored.
+
+use crate::simulation::params::{SIGNAL_FLOOR, SIGNAL_RADIUS};
+
+/// A signal sent from (x, y), currently at `level`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Broadcast {
+    pub x: f64,
+    pub y: f64,
+    pub level: f64,
+}
+
+/// The broadcasts still audible in a dish.
+#[derive(Clone, Debug, Default)]
+pub struct SignalField {
+    pub broadcasts: Vec<Broadcast>,
+}
+
+impl SignalField {
+    /// Sends a signal of `amount` from (x, y); non-positive amounts are ignored.
+    pub fn emit(&mut self, x: f64, y: f64, amount: f64) {
+        if amount > 0.0 && amount.is_finite() {
+            self.broadcasts.push(Broadcast {
+                x,
+                y,
+                level: amount,
+            });
+        }
+    }
+
+    /// Signal concentration at (x, y): `min(1, Σ level · exp(-d² / 2r²))`.
+    #[must_use]
+    pub fn level_at(&self, x: f64, y: f64) -> f64 {
+        let two_sigma_sq = 2.0 * SIGNAL_RADIUS * SIGNAL_RADIUS;
+        self.broadcasts
+            .iter()
+            .map(|b| b.level * (-((x - b.x).powi(2) + (y - b.y).powi(2)) / two_sigma_sq).exp())
+            .sum::<f64>()
+            .min(1.0)
+    }
+
+    /// Multiplies every broadcast by `factor` (the per-tick share of
+    /// `SIGNAL_DECAY`), dropping those that fall below `SIGNAL_FLOOR`.
+    pub fn decay(&mut self, factor: f64) {
+        for broadcast in &mut self.broadcasts {
+            broadcast.level *= factor;
+        }
+        self.broadcasts.retain(|b| b.level >= SIGNAL_FLOOR);
+    }
+
+    /// Moves every broadcast with a resized dish.
+    pub fn rescale(&mut self, sx: f64, sy: f64) {
+        for broadcast in &mut self.broadcasts {
+            broadcast.x *= sx;
+            broadcast.y *= sy;
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Social sensing: observing nearby conspecifics.
+//!
+//! Quorum signals and trails reach an agent through the dish; social
+//! observations come from the other agents themselves. Once per population
+//! step, `observe` gives every living agent a `SocialObservation` of the
+//! living agents within `SOCIAL_RADIUS` in its dish: their presence on its
+//! left and right (each weighted by closeness, `1 - d/r`), the resulting
+//! density and their mean heading. A neighbor j at distance d < r counts
+//! toward the left or right by the sign of `cos θ·Δy − sin θ·Δx`:
+//!
+//! ```text
+//! w_j = 1 − d_j / r              ρ = ρ_L + ρ_R
+//! φ̄ = atan2(Σ_j w_j sin θ_j, Σ_j w_j cos θ_j)
+//! ```
+//!
+//! What the agent makes of it is a `SocialPreference` in its generative
+//! model: a preferred density ρ* (aggregation above 0, avoidance at 0) held
+//! with precision `π_s`, and how strongly it prefers to swim the way its
+//! neighbors do (alignment a, which turns aggregation into schooling):
+//!
+//! ```text
+//! F_social = ½ π_s (ρ − ρ*)² + a (1 − cos(φ̄ − θ))
+//! Δθ = −g π_s (ρ − ρ*)(ρ_L − ρ_R) + g_a a sin(φ̄ − θ)
+//! ```
+//!
+//! The default preference is off, so social observations change nothing.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::crowding::NeighborIndex;
+use crate::simulation::environment::PetriDish;
+use crate::simulation::params::{
+    SOCIAL_ALIGNMENT, SOCIAL_PRIOR_PRECISION, SOCIAL_RADIUS, SOCIAL_TARGET_DENSITY,
+};
+
+/// What an agent senses of its neighbors.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SocialObservation {
+    /// Closeness-weighted presence of neighbors on the left
+    pub left: f64,
+    /// Closeness-weighted presence of neighbors on the right
+    pub right: f64,
+    /// Mean heading of the neighbors, closer ones weighing more (`None`
+    /// without neighbors or when their headings cancel out)
+    pub heading: Option<f64>,
+    /// Neighbors within range
+    pub neighbors: usize,
+}
+
+impl SocialObservation {
+    /// Closeness-weighted number of neighbors on both sides.
+    #[must_use]
+    pub fn density(&self) -> f64 {
+        self.left + self.right
+    }
+}
+
+/// Preference over the social observations, part of the generative model.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SocialPreference {
+    /// Preferred neighbor density (0 = avoid company)
+    pub density: f64,
+    /// Strength of the density preference (0 = ignores neighbors)
+    pub precision: f64,
+    /// Strength of the preference for the neighbors' heading (0 = ignores it)
+    pub alignment: f64,
+}
+
+impl SocialPreference {
+    /// Whether neither density nor heading of the neighbors matter.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.precision <= 0.0 && self.alignment <= 0.0
+    }
+}
+
+/// Named social preferences for the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SocialMode {
+    /// Seek company up to `SOCIAL_TARGET_DENSITY`
+    Aggregate,
+    /// Keep away from other agents
+    Avoid,
+    /// Aggregate and swim the way the neighbors do
+    School,
+}
+
+impl SocialMode {
+    /// Every mode, in display order.
+    pub const ALL: [Self; 3] = [Self::Aggregate, Self::Avoid, Self::School];
+
+    /// Parses a mode name (`aggregate`, `avoid`, `school`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Lowercase name used on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Aggregate => "aggregate",
+            Self::Avoid => "avoid",
+            Self::School => "school",
+        }
+    }
+
+    /// The preference this mode installs.
+    #[must_use]
+    pub const fn preference(self) -> SocialPreference {
+        match self {
+            Self::Aggregate => SocialPreference {
+                density: SOCIAL_TARGET_DENSITY,
+                precision: SOCIAL_PRIOR_PRECISION,
+                alignment: 0.0,
+            },
+            Self::Avoid => SocialPreference {
+                density: 0.0,
+                precision: SOCIAL_PRIOR_PRECISION,
+                alignment: 0.0,
+            },
+            Self::School => SocialPreference {
+                density: SOCIAL_TARGET_DENSITY,
+                precision: SOCIAL_PRIOR_PRECISION,
+                alignment: SOCIAL_ALIGNMENT,
+            },
+        }
+    }
+}
+
+/// Refreshes the `social` observation of every living agent in `agents`
+/// (those in `dish`) from the others' positions and headings on entry;
+/// the dead are neither observed nor observe.
+pub fn observe<'a>(dish: &PetriDish, agents: impl IntoIterator<Item = &'a mut Protozoa>) {
+    let mut agents: Vec<&mut Protozoa> = agents.into_iter().filter(|a| a.is_alive()).collect();
+    let positions: Vec<(f64, f64)> = agents.iter().map(|a| (a.x, a.y)).collect();
+    let headings: Vec<f64> = agents.iter().map(|a| a.angle).collect();
+    let index = NeighborIndex::build(&positions, dish.width, dish.height, SOCIAL_RADIUS);
+    for (i, agent) in agents.iter_mut().enumerate() {
+        let (x, y) = positions[i];
+        let mut observation = SocialObservation::default();
+        let (mut sin, mut cos) = (0.0, 0.0);
+        for j in index.neighbors(i, SOCIAL_RADIUS) {
+            let (dx, dy) = (positions[j].0 - x, positions[j].1 - y);
+            let weight = 1.0 - dx.hypot(dy) / SOCIAL_RADIUS;
+            // Positive when the neighbor is counterclockwise of the heading
+            let side = headings[i].cos() * dy - headings[i].sin() * dx;
+            if side > 0.0 {
+                observation.left += weight;
+            } else if side < 0.0 {
+                observation.right += weight;
+            } else {
+                observation.left += 0.5 * weight;
+                observation.right += 0.5 * weight;
+            }
+            sin += weight * headings[j].sin();
+            cos += weight * headings[j].cos();
+            observation.neighbors += 1;
+        }
+        if sin.hypot(cos) > f64::EPSILON {
+            observation.heading = Some(sin.atan2(cos));
+        }
+        agent.social = observation;
+    }
+}
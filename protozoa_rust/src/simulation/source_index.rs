@@ -0,0 +1,91 @@
+//! Spatial hash over nutrient sources for batches of concentration queries.
+//!
+//! `PetriDish::get_concentration` sums every source, which is exact but costs
+//! one `exp` per source per query. A `SourceIndex` buckets the sources into a
+//! uniform grid of `SOURCE_INDEX_CELL`-sized cells, registering each source in
+//! every cell its support (`NutrientSource::reach`: `SOURCE_INDEX_CUTOFF` radii
+//! for Gaussians) overlaps, so a query only evaluates the sources near it.
+//! Contributions beyond the reach are dropped; that is below 4e-6 per unit
+//! intensity for every kernel.
+//!
+//! The index is a snapshot: build it once per frame or batch
+//! (`PetriDish::source_index`) and rebuild it after the sources change. A
+//! stale index never panics, it just answers for the old positions.
+
+use crate::simulation::environment::NutrientSource;
+use crate::simulation::params::SOURCE_INDEX_CELL;
+
+/// Uniform-grid bucketing of source indices.
+#[derive(Clone, Debug, Default)]
+pub struct SourceIndex {
+    cols: usize,
+    rows: usize,
+    /// Source indices per cell, row-major
+    cells: Vec<Vec<usize>>,
+}
+
+impl SourceIndex {
+    /// Buckets `sources` over a `width` × `height` dish.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // Cell counts are small and non-negative
+    #[allow(clippy::cast_sign_loss)]
+    pub fn build(sources: &[NutrientSource], width: f64, height: f64) -> Self {
+        let cols = (width / SOURCE_INDEX_CELL).ceil().max(1.0) as usize;
+        let rows = (height / SOURCE_INDEX_CELL).ceil().max(1.0) as usize;
+        let mut cells = vec![Vec::new(); cols * rows];
+        let cell_of =
+            |v: f64, n: usize| ((v / SOURCE_INDEX_CELL).floor().max(0.0) as usize).min(n - 1);
+        for (i, source) in sources.iter().enumerate() {
+            let reach = source.reach();
+            let (c0, c1) = (
+                cell_of(source.x - reach, cols),
+                cell_of(source.x + reach, cols),
+            );
+            let (r0, r1) = (
+                cell_of(source.y - reach, rows),
+                cell_of(source.y + reach, rows),
+            );
+            for r in r0..=r1 {
+                for c in c0..=c1 {
+                    cells[r * cols + c].push(i);
+                }
+            }
+        }
+        Self { cols, rows, cells }
+    }
+
+    /// Sources of `sources` whose reach contains (x, y).
+    ///
+    /// `sources` must be the slice the index was built from.
+    pub fn near<'a>(
+        &'a self,
+        sources: &'a [NutrientSource],
+        x: f64,
+        y: f64,
+    ) -> impl Iterator<Item = &'a NutrientSource> {
+        self.cell(x, y)
+            .into_iter()
+            .flatten()
+            .filter_map(|&i| sources.get(i))
+            .filter(move |s| (s.x - x).powi(2) + (s.y - y).powi(2) <= s.reach().powi(2))
+    }
+
+    /// Number of sources registered in the cell containing (x, y).
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn candidates(&self, x: f64, y: f64) -> usize {
+        self.cell(x, y).map_or(0, Vec::len)
+    }
+
+    /// Bucket containing (x, y); points past the far edges use the edge cells.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn cell(&self, x: f64, y: f64) -> Option<&Vec<usize>> {
+        if !(x >= 0.0 && y >= 0.0) {
+            return None;
+        }
+        let c = ((x / SOURCE_INDEX_CELL) as usize).min(self.cols.saturating_sub(1));
+        let r = ((y / SOURCE_INDEX_CELL) as usize).min(self.rows.saturating_sub(1));
+        self.cells.get(r * self.cols + c)
+    }
+}
@@ -0,0 +1,170 @@
+//! Initial agent placement and orientation distributions.
+//!
+//! Scenarios describe *how* an agent enters the dish instead of hard-coding
+//! the dish center, so comparisons are not confounded by start position.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::environment::PetriDish;
+use crate::simulation::params::{SPAWN_MARGIN, SPAWN_MAX_ATTEMPTS};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Distribution over the agent's starting position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(dead_code)] // Variants selected by scenarios and tests
+pub enum StartPosition {
+    /// Center of the dish
+    Center,
+    /// Exact start coordinates
+    Fixed { x: f64, y: f64 },
+    /// Uniform over the dish, keeping `SPAWN_MARGIN` from the walls
+    Uniform,
+    /// Uniform on a circle around the dish center
+    Ring { radius: f64 },
+    /// Within `max_dist` of a randomly chosen nutrient source
+    NearSource { max_dist: f64 },
+    /// At least `min_dist` away from every nutrient source
+    FarFromSources { min_dist: f64 },
+}
+
+/// Distribution over the agent's starting heading.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(dead_code)] // Variants selected by scenarios and tests
+pub enum StartOrientation {
+    /// Exact heading in radians
+    Fixed(f64),
+    /// Uniform over [0, 2π)
+    Uniform,
+    /// Pointing at the dish center
+    TowardCenter,
+}
+
+/// Full specification of how a new agent is placed in the dish.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpawnConfig {
+    /// Start-position distribution
+    pub position: StartPosition,
+    /// Start-heading distribution
+    pub orientation: StartOrientation,
+    /// Minimum initial energy (inclusive)
+    pub energy_min: f64,
+    /// Maximum initial energy (inclusive)
+    pub energy_max: f64,
+}
+
+impl Default for SpawnConfig {
+    /// Dish center, random heading, full energy (the historical start).
+    fn default() -> Self {
+        Self {
+            position: StartPosition::Center,
+            orientation: StartOrientation::Uniform,
+            energy_min: 1.0,
+            energy_max: 1.0,
+        }
+    }
+}
+
+impl SpawnConfig {
+    /// Samples a starting pose and energy and builds an agent from it, with
+    /// maps covering `dish`.
+    pub fn spawn(&self, dish: &PetriDish, rng: &mut impl Rng) -> Protozoa {
+        let (x, y) = self.sample_position(dish, rng);
+        let angle = self.sample_orientation(dish, x, y, rng);
+        Protozoa::builder()
+            .dish(dish.width, dish.height)
+            .position(x, y)
+            .heading(angle)
+            .energy(self.sample_energy(rng))
+            .build()
+    }
+
+    /// Samples a start position inside the dish.
+    #[must_use]
+    pub fn sample_position(&self, dish: &PetriDish, rng: &mut impl Rng) -> (f64, f64) {
+        let (cx, cy) = (dish.width / 2.0, dish.height / 2.0);
+        let (x, y) = match self.position {
+            StartPosition::Center => (cx, cy),
+            StartPosition::Fixed { x, y } => (x, y),
+            StartPosition::Uniform => uniform_point(dish, rng),
+            StartPosition::Ring { radius } => {
+                let theta = rng.random_range(0.0..2.0 * PI);
+                (cx + radius * theta.cos(), cy + radius * theta.sin())
+            }
+            StartPosition::NearSource { max_dist } => {
+                if dish.sources.is_empty() {
+                    (cx, cy)
+                } else {
+                    let source = &dish.sources[rng.random_range(0..dish.sources.len())];
+                    // sqrt keeps the sample uniform over the disk area
+                    let r = max_dist.max(0.0) * rng.random::<f64>().sqrt();
+                    let theta = rng.random_range(0.0..2.0 * PI);
+                    (source.x + r * theta.cos(), source.y + r * theta.sin())
+                }
+            }
+            StartPosition::FarFromSources { min_dist } => far_from_sources(dish, min_dist, rng),
+        };
+
+        (x.clamp(0.0, dish.width), y.clamp(0.0, dish.height))
+    }
+
+    /// Samples a start heading in [0, 2π).
+    #[must_use]
+    pub fn sample_orientation(&self, dish: &PetriDish, x: f64, y: f64, rng: &mut impl Rng) -> f64 {
+        let angle = match self.orientation {
+            StartOrientation::Fixed(angle) => angle,
+            StartOrientation::Uniform => rng.random_range(0.0..2.0 * PI),
+            StartOrientation::TowardCenter => (dish.height / 2.0 - y).atan2(dish.width / 2.0 - x),
+        };
+        angle.rem_euclid(2.0 * PI)
+    }
+
+    /// Samples initial energy from `[energy_min, energy_max]`, clamped to [0, 1].
+    #[must_use]
+    pub fn sample_energy(&self, rng: &mut impl Rng) -> f64 {
+        let lo = self.energy_min.min(self.energy_max);
+        let hi = self.energy_min.max(self.energy_max);
+        let energy = if hi > lo {
+            rng.random_range(lo..=hi)
+        } else {
+            lo
+        };
+        energy.clamp(0.0, 1.0)
+    }
+}
+
+/// Uniform point keeping `SPAWN_MARGIN` from the walls (if the dish is large enough).
+fn uniform_point(dish: &PetriDish, rng: &mut impl Rng) -> (f64, f64) {
+    let margin_x = SPAWN_MARGIN.min(dish.width / 4.0);
+    let margin_y = SPAWN_MARGIN.min(dish.height / 4.0);
+    (
+        rng.random_range(margin_x..=dish.width - margin_x),
+        rng.random_range(margin_y..=dish.height - margin_y),
+    )
+}
+
+/// Rejection-samples a point at least `min_dist` from all sources.
+///
+/// Falls back to the farthest candidate seen if no sample satisfies the constraint.
+fn far_from_sources(dish: &PetriDish, min_dist: f64, rng: &mut impl Rng) -> (f64, f64) {
+    let mut best = uniform_point(dish, rng);
+    let mut best_dist = f64::NEG_INFINITY;
+
+    for _ in 0..SPAWN_MAX_ATTEMPTS {
+        let (x, y) = uniform_point(dish, rng);
+        let nearest = dish
+            .sources
+            .iter()
+            .map(|s| (s.x - x).hypot(s.y - y))
+            .fold(f64::INFINITY, f64::min);
+
+        if nearest >= min_dist {
+            return (x, y);
+        }
+        if nearest > best_dist {
+            best_dist = nearest;
+            best = (x, y);
+        }
+    }
+
+    best
+}
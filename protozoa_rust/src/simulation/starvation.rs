@@ -0,0 +1,63 @@
+//! Time-to-starvation forecast.
+//!
+//! The energy percentage alone hides how dire things are: 30% on a rich
+//! patch is comfortable, 30% while crossing a void is a countdown. The
+//! forecast keeps exponential moving averages (weight `STARVATION_SMOOTHING`,
+//! a plain running mean while warming up) of the per-tick net change of the
+//! stored energy (fast compartment plus reserve) and of the per-tick cost.
+//! Dividing what is stored by them gives two estimates of the ticks left:
+//! at the current net rate (none while the agent breaks even or gains), and
+//! a pessimistic one assuming nothing more is eaten.
+//!
+//! The estimate at the current rate doubles as a planning urgency: 0 from
+//! `STARVATION_RELAXED_TICKS` up, 1 at `STARVATION_URGENT_TICKS` and below.
+
+use crate::simulation::params::{
+    STARVATION_RELAXED_TICKS, STARVATION_SMOOTHING, STARVATION_URGENT_TICKS,
+};
+
+/// Smoothed energy rates of one agent.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StarvationForecast {
+    /// Mean change of stored energy per tick (negative while declining)
+    pub net_rate: f64,
+    /// Mean energy spent per tick, intake aside
+    pub cost_rate: f64,
+    /// Ticks observed so far
+    pub samples: u64,
+}
+
+impl StarvationForecast {
+    /// Folds in one tick's change of stored energy `net` and its `cost`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn observe(&mut self, net: f64, cost: f64) {
+        self.samples += 1;
+        let weight = STARVATION_SMOOTHING.max(1.0 / self.samples as f64);
+        self.net_rate += weight * (net - self.net_rate);
+        self.cost_rate += weight * (cost - self.cost_rate);
+    }
+
+    /// Ticks until `stored` energy runs out at the current net rate, or
+    /// `None` before any observation or while the agent is not losing energy.
+    #[must_use]
+    pub fn ticks_left(&self, stored: f64) -> Option<f64> {
+        (self.samples > 0 && self.net_rate < 0.0).then(|| stored.max(0.0) / -self.net_rate)
+    }
+
+    /// Ticks until `stored` energy runs out if nothing more is eaten, or
+    /// `None` before any observation.
+    #[must_use]
+    pub fn ticks_left_fasting(&self, stored: f64) -> Option<f64> {
+        (self.samples > 0 && self.cost_rate > 0.0).then(|| stored.max(0.0) / self.cost_rate)
+    }
+
+    /// Planning urgency in [0, 1] of the ticks left at the current rate.
+    #[must_use]
+    pub fn urgency(&self, stored: f64) -> f64 {
+        self.ticks_left(stored).map_or(0.0, |ticks| {
+            ((STARVATION_RELAXED_TICKS - ticks)
+                / (STARVATION_RELAXED_TICKS - STARVATION_URGENT_TICKS))
+                .clamp(0.0, 1.0)
+        })
+    }
+}
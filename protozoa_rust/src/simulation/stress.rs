@@ -0,0 +1,118 @@
+//! Stress response: a slow variable driven by sustained surprise.
+//!
+//! An agent's stress `S` in [0, 1) tracks how surprised it has been lately.
+//! Each tick its VFE sets a drive `F / (F + STRESS_HALF_VFE)` that `S`
+//! relaxes toward: quickly (`rise_time`) while the drive is above it, slowly
+//! (`recovery_time`) once things calm down, so one surprising reading does
+//! little but a sustained run of them builds up.
+//!
+//! Stress acts globally: every learned sensory precision is scaled by
+//! `1 - precision_depth · S` when installed in the generative model (a
+//! stressed agent trusts its senses less) and the VFE-driven heading noise
+//! by `1 + noise_gain · S`. Panic is a consequence of it: with a stress
+//! response the agent panics while `S` is at least `STRESS_PANIC_LEVEL`
+//! rather than on a single falling reading. With both effects 0 (the
+//! default) no stress is kept and panic stays the reading threshold.
+
+use crate::simulation::clock::SimClock;
+use crate::simulation::params::{
+    STRESS_HALF_VFE, STRESS_NOISE_GAIN, STRESS_PRECISION_DEPTH, STRESS_RECOVERY_TIME,
+    STRESS_RISE_TIME,
+};
+
+/// How an agent's stress builds up, recovers and what it does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StressResponse {
+    /// Share of sensory precision lost at full stress, in [0, 1]
+    pub precision_depth: f64,
+    /// Extra heading noise at full stress, as a multiple of the calm noise
+    pub noise_gain: f64,
+    /// Time constant in seconds while stress rises
+    pub rise_time: f64,
+    /// Time constant in seconds while stress recovers
+    pub recovery_time: f64,
+}
+
+impl Default for StressResponse {
+    fn default() -> Self {
+        Self::new(STRESS_PRECISION_DEPTH, STRESS_NOISE_GAIN)
+    }
+}
+
+impl StressResponse {
+    /// A response with the given effects and the default time constants.
+    #[must_use]
+    pub fn new(precision_depth: f64, noise_gain: f64) -> Self {
+        Self {
+            precision_depth,
+            noise_gain,
+            rise_time: STRESS_RISE_TIME,
+            recovery_time: STRESS_RECOVERY_TIME,
+        }
+    }
+
+    /// Returns true if stress would change nothing (and is not kept).
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.precision_depth <= 0.0 && self.noise_gain <= 0.0
+    }
+
+    /// Stress level `vfe` drives toward: `F / (F + F_half)`, 0 for no
+    /// surprise and approaching 1 for a lot.
+    #[must_use]
+    pub fn drive(vfe: f64) -> f64 {
+        let vfe = vfe.max(0.0);
+        vfe / (vfe + STRESS_HALF_VFE)
+    }
+
+    /// Stress after one tick of `clock` at surprise `vfe`: an exact
+    /// exponential relaxation of `stress` toward the drive.
+    #[must_use]
+    pub fn step(&self, stress: f64, vfe: f64, clock: &SimClock) -> f64 {
+        let target = Self::drive(vfe);
+        let tau = if target > stress {
+            self.rise_time
+        } else {
+            self.recovery_time
+        };
+        stress + (target - stress) * (1.0 - (-clock.dt() / tau).exp())
+    }
+
+    /// Factor on the sensory precisions at `stress`: `1 - d S`.
+    #[must_use]
+    pub fn precision_factor(&self, stress: f64) -> f64 {
+        1.0 - self.precision_depth * stress
+    }
+
+    /// Factor on the heading noise at `stress`: `1 + g S`.
+    #[must_use]
+    pub fn noise_factor(&self, stress: f64) -> f64 {
+        1.0 + self.noise_gain * stress
+    }
+
+    /// Checks that the precision depth lies in [0, 1], the noise gain is
+    /// finite and non-negative and both time constants finite and positive.
+    ///
+    /// # Errors
+    /// Names the first offending value.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.precision_depth) {
+            return Err(format!(
+                "stress precision depth out of range: {}",
+                self.precision_depth
+            ));
+        }
+        if !(self.noise_gain.is_finite() && self.noise_gain >= 0.0) {
+            return Err(format!(
+                "stress noise gain out of range: {}",
+                self.noise_gain
+            ));
+        }
+        for tau in [self.rise_time, self.recovery_time] {
+            if !(tau.is_finite() && tau > 0.0) {
+                return Err(format!("stress time constant out of range: {tau}"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,307 @@
+//! Parameter sweeps run on a worker pool with a resumable job journal.
+//!
+//! A `Sweep` crosses the values of one morphology parameter with a set of
+//! seeds; every (value, seed) pair is a cell, simulated headless as a
+//! `Scenario`. `run_sweep` hands the pending cells to `jobs` worker threads
+//! and appends each finished cell to a JSON-lines journal as soon as it
+//! completes (one `CellResult` per line, flushed). Running the same sweep
+//! against an existing journal skips the cells it already holds, so an
+//! interrupted sweep resumes where it stopped; a half-written last line is
+//! dropped and its cell runs again. Each result keeps the raw episode data,
+//! so `SweepReport::fitness_by_value` can score the cells under any
+//! `Fitness` without running them again.
+
+use crate::simulation::annotation::{MorphologyParam, ParamCommand};
+use crate::simulation::ensemble::Stat;
+use crate::simulation::fitness::{Episode, Fitness};
+use crate::simulation::scenario::{Scenario, ScenarioOutcome, run_prepared};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// One parameter crossed with seeds, each cell run for `ticks` ticks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sweep {
+    pub scenario: Scenario,
+    pub param: MorphologyParam,
+    /// Parameter values, in sweep order
+    pub values: Vec<f64>,
+    pub seeds: Vec<u64>,
+    pub ticks: u64,
+}
+
+/// A single (value, seed) run of a sweep.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    /// Position in `Sweep::cells` (values outer, seeds inner)
+    pub index: usize,
+    pub command: ParamCommand,
+    pub seed: u64,
+}
+
+/// A finished cell, one journal line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CellResult {
+    pub cell: usize,
+    pub param: String,
+    pub value: f64,
+    pub seed: u64,
+    pub ticks: u64,
+    pub final_energy: f64,
+    pub min_energy: f64,
+    pub distance: f64,
+    pub coverage: f64,
+    pub exhausted_at: Option<u64>,
+    /// Ticks the agent was alive
+    pub lifespan: u64,
+    /// Energy integrated over the agent's lifetime
+    pub energy_integral: f64,
+}
+
+impl CellResult {
+    fn new(cell: &Cell, ticks: u64, outcome: &ScenarioOutcome) -> Self {
+        let metrics = &outcome.metrics;
+        let episode = Episode::of_outcome(outcome);
+        Self {
+            cell: cell.index,
+            param: cell.command.param.name().to_string(),
+            value: cell.command.value,
+            seed: cell.seed,
+            ticks,
+            final_energy: metrics.final_energy,
+            min_energy: metrics.min_energy,
+            distance: metrics.distance,
+            coverage: metrics.coverage,
+            exhausted_at: metrics.exhausted_at,
+            lifespan: episode.lifespan,
+            energy_integral: episode.energy_integral,
+        }
+    }
+
+    /// The cell's run as a fitness episode.
+    #[must_use]
+    pub fn episode(&self) -> Episode {
+        Episode {
+            lifespan: self.lifespan,
+            energy_integral: self.energy_integral,
+            final_energy: self.final_energy,
+            coverage: self.coverage,
+            offspring: 0,
+        }
+    }
+
+    /// Returns true if this result was produced by `cell` of a sweep of
+    /// `ticks` ticks (values compared with a tolerance for the JSON round trip).
+    fn matches(&self, cell: &Cell, ticks: u64) -> bool {
+        let value = cell.command.value;
+        self.cell == cell.index
+            && self.param == cell.command.param.name()
+            && self.seed == cell.seed
+            && self.ticks == ticks
+            && (self.value - value).abs() <= 1e-12 * value.abs().max(1.0)
+    }
+}
+
+/// Every cell of a sweep after `run_sweep`, in cell order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SweepReport {
+    pub results: Vec<CellResult>,
+    /// Cells found in the journal and skipped
+    pub resumed: usize,
+    /// Cells simulated by this call
+    pub ran: usize,
+}
+
+impl SweepReport {
+    /// Final-energy statistics across seeds for each value, in sweep order.
+    #[must_use]
+    pub fn final_energy_by_value(&self) -> Vec<(f64, Stat)> {
+        self.by_value(|result| result.final_energy)
+    }
+
+    /// Statistics of the `fitness` score across seeds for each value, in
+    /// sweep order.
+    #[must_use]
+    pub fn fitness_by_value(&self, fitness: &impl Fitness) -> Vec<(f64, Stat)> {
+        self.by_value(|result| fitness.score(&result.episode()))
+    }
+
+    /// Statistics of `metric` across seeds for each value, in sweep order.
+    fn by_value(&self, metric: impl Fn(&CellResult) -> f64) -> Vec<(f64, Stat)> {
+        let mut groups: Vec<(f64, Vec<f64>)> = Vec::new();
+        for result in &self.results {
+            match groups.last_mut() {
+                Some((value, metrics)) if value.to_bits() == result.value.to_bits() => {
+                    metrics.push(metric(result));
+                }
+                _ => groups.push((result.value, vec![metric(result)])),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(value, metrics)| (value, Stat::from_values(&metrics)))
+            .collect()
+    }
+}
+
+impl Sweep {
+    /// Parses a `NAME=V1,V2,...` axis for `scenario`, validating every value
+    /// like `set NAME V` would.
+    ///
+    /// # Errors
+    /// Returns a message for a malformed spec, an unknown name or a bad value.
+    #[allow(clippy::large_types_passed_by_value)] // Moved into the sweep
+    pub fn parse_axis(
+        scenario: Scenario,
+        spec: &str,
+        seeds: Vec<u64>,
+        ticks: u64,
+    ) -> Result<Self, String> {
+        let (name, list) = spec.split_once('=').ok_or("usage: --set NAME=V1,V2,...")?;
+        let commands = list
+            .split(',')
+            .map(|value| ParamCommand::parse(&format!("set {name} {value}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let param = commands[0].param;
+        Ok(Self {
+            scenario,
+            param,
+            values: commands.iter().map(|c| c.value).collect(),
+            seeds,
+            ticks,
+        })
+    }
+
+    /// All cells, values outer and seeds inner.
+    #[must_use]
+    pub fn cells(&self) -> Vec<Cell> {
+        self.values
+            .iter()
+            .flat_map(|&value| self.seeds.iter().map(move |&seed| (value, seed)))
+            .enumerate()
+            .map(|(index, (value, seed))| Cell {
+                index,
+                command: ParamCommand {
+                    param: self.param,
+                    value,
+                },
+                seed,
+            })
+            .collect()
+    }
+
+    /// Simulates one cell.
+    #[must_use]
+    pub fn run_cell(&self, cell: &Cell) -> CellResult {
+        let mut run = self.scenario.build(cell.seed);
+        cell.command.apply(&mut run.agent.morphology);
+        let outcome = run_prepared(run, cell.seed, self.ticks);
+        CellResult::new(cell, self.ticks, &outcome)
+    }
+}
+
+/// Reads the completed cells of `sweep` from the journal at `path`,
+/// rewriting it without a torn last line.
+fn load_journal(path: &Path, sweep: &Sweep) -> Result<HashMap<usize, CellResult>, String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(format!("cannot read {}: {e}", path.display())),
+    };
+    let cells = sweep.cells();
+    let mut done = HashMap::new();
+    let mut complete = String::new();
+    // Only newline-terminated lines were fully written
+    for line in text.split_inclusive('\n').filter(|l| l.ends_with('\n')) {
+        let result: CellResult = serde_json::from_str(line)
+            .map_err(|e| format!("{}: corrupt journal line: {e}", path.display()))?;
+        if !cells
+            .get(result.cell)
+            .is_some_and(|cell| result.matches(cell, sweep.ticks))
+        {
+            return Err(format!(
+                "{} belongs to a different sweep (cell {})",
+                path.display(),
+                result.cell
+            ));
+        }
+        complete.push_str(line);
+        done.insert(result.cell, result);
+    }
+    if complete.len() != text.len() {
+        std::fs::write(path, complete)
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    }
+    Ok(done)
+}
+
+/// Runs every cell of `sweep` not yet in the journal at `path` on `jobs`
+/// worker threads, appending each result as it finishes.
+///
+/// # Errors
+/// Returns a message if the journal cannot be read or written, or belongs
+/// to a different sweep. Cells finished before a write error stay journaled.
+pub fn run_sweep(
+    sweep: &Sweep,
+    path: impl AsRef<Path>,
+    jobs: usize,
+) -> Result<SweepReport, String> {
+    let path = path.as_ref();
+    let mut done = load_journal(path, sweep)?;
+    let resumed = done.len();
+    let pending: Vec<Cell> = sweep
+        .cells()
+        .into_iter()
+        .filter(|cell| !done.contains_key(&cell.index))
+        .collect();
+    let mut journal = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("cannot open {}: {e}", path.display()))?;
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+    let written = std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, pending.len().max(1)) {
+            let sender = sender.clone();
+            let (next, stop, pending) = (&next, &stop, &pending);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let Some(cell) = pending.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                        break;
+                    };
+                    if sender.send(sweep.run_cell(cell)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Single writer: workers only simulate, this thread journals
+        let mut ran = 0;
+        for result in receiver {
+            let line = serde_json::to_string(&result).unwrap_or_default();
+            if let Err(e) = writeln!(journal, "{line}").and_then(|()| journal.flush()) {
+                stop.store(true, Ordering::Relaxed);
+                return Err(format!("cannot write {}: {e}", path.display()));
+            }
+            done.insert(result.cell, result);
+            ran += 1;
+        }
+        Ok(ran)
+    })?;
+
+    let mut results: Vec<CellResult> = done.into_values().collect();
+    results.sort_by_key(|r| r.cell);
+    Ok(SweepReport {
+        results,
+        resumed,
+        ran: written,
+    })
+}
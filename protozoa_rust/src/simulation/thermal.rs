@@ -0,0 +1,78 @@
+//! Temperature field: a second sensory modality.
+//!
+//! Temperature is an ambient baseline plus signed Gaussian hot and cold spots.
+//! Unlike nutrient sources, thermal sources neither decay nor drift, so the
+//! thermal landscape is a stable cue the agent can weigh against chemistry.
+
+use crate::simulation::params::{
+    AMBIENT_TEMPERATURE, SOURCE_MARGIN, THERMAL_INTENSITY_MAX, THERMAL_RADIUS_MAX,
+    THERMAL_RADIUS_MIN, THERMAL_SOURCE_COUNT,
+};
+use rand::Rng;
+
+/// A hot (positive intensity) or cold (negative intensity) spot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThermalSource {
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+    /// Signed temperature offset at the center
+    pub intensity: f64,
+}
+
+impl ThermalSource {
+    /// Gaussian temperature offset of this source at (x, y).
+    #[must_use]
+    pub fn contribution(&self, x: f64, y: f64) -> f64 {
+        let dist_sq = (x - self.x).powi(2) + (y - self.y).powi(2);
+        let sigma_sq = self.radius.powi(2).max(f64::EPSILON);
+        self.intensity * (-dist_sq / (2.0 * sigma_sq)).exp()
+    }
+}
+
+/// Static temperature landscape over the dish.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThermalField {
+    /// Baseline temperature in [0, 1]
+    pub ambient: f64,
+    pub sources: Vec<ThermalSource>,
+}
+
+impl ThermalField {
+    /// Creates `THERMAL_SOURCE_COUNT` random hot and cold spots for a dish of the given size.
+    #[must_use]
+    pub fn random(rng: &mut impl Rng, width: f64, height: f64) -> Self {
+        let sources = (0..THERMAL_SOURCE_COUNT)
+            .map(|i| {
+                // Alternate hot and cold spots so the field is not one-sided
+                let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                ThermalSource {
+                    x: rng.random_range(SOURCE_MARGIN..width - SOURCE_MARGIN),
+                    y: rng.random_range(SOURCE_MARGIN..height - SOURCE_MARGIN),
+                    radius: rng.random_range(THERMAL_RADIUS_MIN..THERMAL_RADIUS_MAX),
+                    intensity: sign * rng.random_range(0.5..=1.0) * THERMAL_INTENSITY_MAX,
+                }
+            })
+            .collect();
+        Self {
+            ambient: AMBIENT_TEMPERATURE,
+            sources,
+        }
+    }
+
+    /// Creates a uniform field at the ambient temperature.
+    #[must_use]
+    pub fn uniform() -> Self {
+        Self {
+            ambient: AMBIENT_TEMPERATURE,
+            sources: Vec::new(),
+        }
+    }
+
+    /// Temperature at (x, y), clamped to [0, 1].
+    #[must_use]
+    pub fn temperature_at(&self, x: f64, y: f64) -> f64 {
+        let offset: f64 = self.sources.iter().map(|s| s.contribution(x, y)).sum();
+        (self.ambient + offset).clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,113 @@
+//! Structured sensory turbulence.
+//!
+//! Real chemical fields flicker: eddies carry patches of higher and lower
+//! concentration past the sensors, so neighbouring readings and consecutive
+//! readings are correlated. `Turbulence` models this as 3D gradient (Perlin)
+//! noise over (x, y, time), scaled by `amplitude` and added to what the
+//! agent's chemoreceptors read (`PetriDish::sensed_concentration`). The
+//! deterministic field (`get_concentration`, `gradient`, the dish view) is
+//! left as is, so the noise only affects what the agent perceives.
+//!
+//! `scale` is the eddy size in world units and `period` how many sim-seconds
+//! it takes the pattern to decorrelate. Amplitude 0 (the default) turns it off.
+
+use crate::simulation::params::{TURBULENCE_AMPLITUDE, TURBULENCE_PERIOD, TURBULENCE_SCALE};
+
+/// Gradient-noise turbulence layered on the sensed concentration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Turbulence {
+    /// Largest concentration offset (noise values lie in [-amplitude, amplitude])
+    pub amplitude: f64,
+    /// Eddy size in world units (noise lattice spacing)
+    pub scale: f64,
+    /// Sim-seconds per lattice step in time
+    pub period: f64,
+    /// Selects the noise pattern
+    pub seed: u64,
+}
+
+impl Default for Turbulence {
+    fn default() -> Self {
+        Self {
+            amplitude: TURBULENCE_AMPLITUDE,
+            scale: TURBULENCE_SCALE,
+            period: TURBULENCE_PERIOD,
+            seed: 0,
+        }
+    }
+}
+
+impl Turbulence {
+    /// Returns true if the turbulence adds nothing.
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        self.amplitude == 0.0
+    }
+
+    /// Offset at (x, y) and sim time `t`, in [-amplitude, amplitude].
+    #[must_use]
+    pub fn sample(&self, x: f64, y: f64, t: f64) -> f64 {
+        if self.is_off() {
+            return 0.0;
+        }
+        let p = [
+            x / self.scale.max(f64::EPSILON),
+            y / self.scale.max(f64::EPSILON),
+            t / self.period.max(f64::EPSILON),
+        ];
+        self.amplitude * self.noise(p).clamp(-1.0, 1.0)
+    }
+
+    /// Perlin noise at `p`, roughly in [-1, 1] and 0 on every lattice point.
+    #[allow(clippy::cast_possible_truncation)] // Lattice coordinates are floored
+    fn noise(&self, p: [f64; 3]) -> f64 {
+        let cell = p.map(|v| v.floor() as i64);
+        let local = [0, 1, 2].map(|i| p[i] - p[i].floor());
+        let fade = local.map(|v| v * v * v * (v * (v * 6.0 - 15.0) + 10.0));
+
+        let mut corners = [0.0; 8];
+        for (corner, value) in corners.iter_mut().enumerate() {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let lattice = [0, 1, 2].map(|i| cell[i] + i64::from(offset[i] == 1));
+            let gradient = GRADIENTS[self.hash(lattice) % GRADIENTS.len()];
+            *value = (0..3)
+                .map(|i| gradient[i] * (local[i] - f64::from(u8::from(offset[i] == 1))))
+                .sum();
+        }
+        let lerp = |a: f64, b: f64, t: f64| a + t * (b - a);
+        let x0 = lerp(corners[0], corners[1], fade[0]);
+        let x1 = lerp(corners[2], corners[3], fade[0]);
+        let x2 = lerp(corners[4], corners[5], fade[0]);
+        let x3 = lerp(corners[6], corners[7], fade[0]);
+        lerp(lerp(x0, x1, fade[1]), lerp(x2, x3, fade[1]), fade[2])
+    }
+
+    /// Mixes a lattice point with the seed (`SplitMix64` finalizer).
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Bit mixing
+    fn hash(&self, lattice: [i64; 3]) -> usize {
+        let mut z = self.seed;
+        for coordinate in lattice {
+            z ^= (coordinate as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+        }
+        z as usize
+    }
+}
+
+/// Perlin's twelve edge gradients of the unit cube.
+const GRADIENTS: [[f64; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
@@ -0,0 +1,207 @@
+//! Batched stepping of many independent environments.
+//!
+//! `VecEnv` advances N dish/agent pairs in one call (in parallel with `rayon`
+//! when the `parallel` feature is on)
+//! and writes the results into contiguous arrays: an N × `OBS_DIM` row-major
+//! observation matrix plus per-environment rewards and done flags. Optimizers
+//! and evolution loops read those slices directly instead of calling into
+//! every environment separately.
+//!
+//! Each environment's dish, spawn and agent streams derive from its seed, so
+//! batches are reproducible; a done environment stops stepping until
+//! `reset(i)` regenerates it from that seed.
+
+// Library API for optimizers and bindings; the TUI binary does not use it
+#![allow(dead_code)]
+
+use crate::simulation::ensemble::Run;
+use crate::simulation::environment::PetriDish;
+use crate::simulation::generator::GeneratorConfig;
+use crate::simulation::params::EXHAUSTION_THRESHOLD;
+use crate::simulation::spawn::SpawnConfig;
+use crate::simulation::streams::Stream;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Values per observation row.
+pub const OBS_DIM: usize = 8;
+
+/// Column names of an observation row, in order.
+pub const OBS_FIELDS: [&str; OBS_DIM] = [
+    "x",
+    "y",
+    "angle",
+    "speed",
+    "energy",
+    "sensor_left",
+    "sensor_right",
+    "vfe",
+];
+
+/// N independent environments stepped as one batch.
+pub struct VecEnv {
+    runs: Vec<Run>,
+    /// Dish seed of each environment (reused by `reset`)
+    seeds: Vec<u64>,
+    generator: GeneratorConfig,
+    spawn: SpawnConfig,
+    /// Row-major N × `OBS_DIM` observations
+    observations: Vec<f64>,
+    /// Energy change during the last step
+    rewards: Vec<f64>,
+    /// Agent exhausted; done environments are no longer stepped
+    dones: Vec<bool>,
+    /// Batch steps taken
+    pub tick: u64,
+}
+
+impl VecEnv {
+    /// Creates one environment per seed, each generated with `generator`.
+    ///
+    /// The agent's start and all of its random streams derive from the same
+    /// seed, so a batch is reproducible end to end.
+    #[must_use]
+    pub fn new(seeds: &[u64], generator: &GeneratorConfig, spawn: &SpawnConfig) -> Self {
+        let runs: Vec<Run> = seeds
+            .iter()
+            .map(|&seed| Self::make_run(seed, generator, spawn))
+            .collect();
+        let n = runs.len();
+        let mut env = Self {
+            runs,
+            seeds: seeds.to_vec(),
+            generator: *generator,
+            spawn: *spawn,
+            observations: vec![0.0; n * OBS_DIM],
+            rewards: vec![0.0; n],
+            dones: vec![false; n],
+            tick: 0,
+        };
+        for i in 0..n {
+            env.observe(i);
+        }
+        env
+    }
+
+    fn make_run(seed: u64, generator: &GeneratorConfig, spawn: &SpawnConfig) -> Run {
+        let dish = PetriDish::generate(seed, generator);
+        let mut agent = spawn.spawn(&dish, &mut Stream::Spawn.rng(seed));
+        agent.seed_streams(seed);
+        Run { dish, agent }
+    }
+
+    /// Number of environments.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Returns true if the batch is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Advances every environment that is not done by one tick, in parallel.
+    pub fn step(&mut self) {
+        #[cfg(feature = "parallel")]
+        self.runs
+            .par_iter_mut()
+            .zip(self.observations.par_chunks_mut(OBS_DIM))
+            .zip(self.rewards.par_iter_mut())
+            .zip(self.dones.par_iter_mut())
+            .for_each(|(((run, obs), reward), done)| step_one(run, obs, reward, done));
+        #[cfg(not(feature = "parallel"))]
+        self.runs
+            .iter_mut()
+            .zip(self.observations.chunks_mut(OBS_DIM))
+            .zip(self.rewards.iter_mut())
+            .zip(self.dones.iter_mut())
+            .for_each(|(((run, obs), reward), done)| step_one(run, obs, reward, done));
+        self.tick += 1;
+    }
+
+    /// Takes `ticks` batch steps.
+    pub fn step_n(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            self.step();
+        }
+    }
+
+    /// Regenerates environment `i` from its seed with a fresh agent.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of range.
+    pub fn reset(&mut self, i: usize) {
+        self.runs[i] = Self::make_run(self.seeds[i], &self.generator, &self.spawn);
+        self.rewards[i] = 0.0;
+        self.dones[i] = false;
+        self.observe(i);
+    }
+
+    /// Row-major N × `OBS_DIM` observation matrix.
+    #[must_use]
+    pub fn observations(&self) -> &[f64] {
+        &self.observations
+    }
+
+    /// Observation row of environment `i`.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of range.
+    #[must_use]
+    pub fn observation(&self, i: usize) -> &[f64] {
+        &self.observations[i * OBS_DIM..(i + 1) * OBS_DIM]
+    }
+
+    /// Energy change of each environment during the last step.
+    #[must_use]
+    pub fn rewards(&self) -> &[f64] {
+        &self.rewards
+    }
+
+    /// Exhaustion flag of each environment.
+    #[must_use]
+    pub fn dones(&self) -> &[bool] {
+        &self.dones
+    }
+
+    /// The underlying runs (dish + agent), for inspection.
+    #[must_use]
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+
+    fn observe(&mut self, i: usize) {
+        let row = &mut self.observations[i * OBS_DIM..(i + 1) * OBS_DIM];
+        write_observation(&self.runs[i], row);
+    }
+}
+
+/// Steps one environment unless it is done, recording its reward and row.
+fn step_one(run: &mut Run, obs: &mut [f64], reward: &mut f64, done: &mut bool) {
+    if *done {
+        *reward = 0.0;
+        return;
+    }
+    let energy = run.agent.energy;
+    run.step();
+    *reward = run.agent.energy - energy;
+    *done = run.agent.energy <= EXHAUSTION_THRESHOLD;
+    write_observation(run, obs);
+}
+
+/// Writes the `OBS_FIELDS` of one run into `row`.
+fn write_observation(run: &Run, row: &mut [f64]) {
+    let agent = &run.agent;
+    row.copy_from_slice(&[
+        agent.x,
+        agent.y,
+        agent.angle,
+        agent.speed,
+        agent.energy,
+        agent.val_l,
+        agent.val_r,
+        agent.current_vfe,
+    ]);
+}
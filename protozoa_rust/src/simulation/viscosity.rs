@@ -0,0 +1,69 @@
+//! Viscosity zones: regions of thick medium that hamper locomotion.
+//!
+//! Viscosity is 1 (plain medium) plus the Gaussian excess of each zone. The
+//! agent's effective speed is its commanded speed divided by the local
+//! viscosity, while the swimming part of its metabolic cost is multiplied by
+//! it, so crossing a viscous zone is both slow and expensive.
+
+use crate::simulation::params::{
+    SOURCE_MARGIN, VISCOSITY_EXCESS_MAX, VISCOSITY_EXCESS_MIN, VISCOSITY_RADIUS_MAX,
+    VISCOSITY_RADIUS_MIN,
+};
+use rand::Rng;
+
+/// A Gaussian patch of thick medium.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViscosityZone {
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+    /// Extra viscosity at the center (1.0 doubles the drag)
+    pub excess: f64,
+}
+
+impl ViscosityZone {
+    /// Gaussian extra viscosity of this zone at (x, y).
+    #[must_use]
+    pub fn contribution(&self, x: f64, y: f64) -> f64 {
+        let dist_sq = (x - self.x).powi(2) + (y - self.y).powi(2);
+        let sigma_sq = self.radius.powi(2).max(f64::EPSILON);
+        self.excess * (-dist_sq / (2.0 * sigma_sq)).exp()
+    }
+}
+
+/// Static viscosity landscape over the dish.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViscosityField {
+    pub zones: Vec<ViscosityZone>,
+}
+
+impl ViscosityField {
+    /// Creates a field with plain medium everywhere.
+    #[must_use]
+    pub fn uniform() -> Self {
+        Self::default()
+    }
+
+    /// Creates `count` random zones for a dish of the given size.
+    #[must_use]
+    pub fn random(rng: &mut impl Rng, count: usize, width: f64, height: f64) -> Self {
+        let margin_x = SOURCE_MARGIN.min(width / 4.0);
+        let margin_y = SOURCE_MARGIN.min(height / 4.0);
+        let zones = (0..count)
+            .map(|_| ViscosityZone {
+                x: rng.random_range(margin_x..=width - margin_x),
+                y: rng.random_range(margin_y..=height - margin_y),
+                radius: rng.random_range(VISCOSITY_RADIUS_MIN..=VISCOSITY_RADIUS_MAX),
+                excess: rng.random_range(VISCOSITY_EXCESS_MIN..=VISCOSITY_EXCESS_MAX),
+            })
+            .collect();
+        Self { zones }
+    }
+
+    /// Viscosity at (x, y): 1.0 in plain medium, higher inside zones.
+    #[must_use]
+    pub fn viscosity_at(&self, x: f64, y: f64) -> f64 {
+        let excess: f64 = self.zones.iter().map(|z| z.contribution(x, y)).sum();
+        1.0 + excess.max(0.0)
+    }
+}
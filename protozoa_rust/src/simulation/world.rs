@@ -0,0 +1,188 @@
+//! Worlds made of several dishes linked by portals.
+//!
+//! Each dish keeps its own coordinates, fields and clock. A `Portal` is a
+//! small circular opening in one dish that leads to a point in another; an
+//! agent that swims into it leaves its dish and reappears at the far end with
+//! its heading unchanged. A two-way channel is a pair of portals whose exits
+//! sit on each other's openings. Migration fires only when the agent enters an
+//! opening from outside, so arriving on the return portal does not bounce the
+//! agent straight back.
+//!
+//! An agent keeps its spatial priors and landmarks per dish and swaps them on
+//! migration. `World::chain` seeds dish i with `seed + i`, and `resize`
+//! stretches every dish along with the portals and the agents' memories.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::environment::PetriDish;
+use crate::simulation::generator::GeneratorConfig;
+use crate::simulation::observer::DishObserver;
+use crate::simulation::params::{PORTAL_MARGIN, PORTAL_RADIUS};
+
+/// One-way opening from a point in one dish to a point in another.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Portal {
+    /// Dish the opening is in
+    pub from: usize,
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+    /// Dish the portal leads to
+    pub to: usize,
+    /// Exit point in the destination dish
+    pub to_x: f64,
+    pub to_y: f64,
+}
+
+impl Portal {
+    /// Returns true if (x, y) in dish `from` lies inside the opening.
+    #[must_use]
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        (x - self.x).hypot(y - self.y) <= self.radius
+    }
+}
+
+/// Several dishes and the portals between them.
+#[derive(Clone, Debug)]
+pub struct World {
+    pub dishes: Vec<PetriDish>,
+    pub portals: Vec<Portal>,
+}
+
+impl World {
+    /// A world of one dish and no portals.
+    #[must_use]
+    pub fn single(dish: PetriDish) -> Self {
+        Self {
+            dishes: vec![dish],
+            portals: Vec::new(),
+        }
+    }
+
+    /// `count` generated dishes in a row (dish `i` seeded with `seed + i`),
+    /// each joined to the next by a two-way channel from its right edge to
+    /// the next dish's left edge.
+    #[must_use]
+    pub fn chain(count: usize, config: &GeneratorConfig, seed: u64) -> Self {
+        let mut world = Self {
+            dishes: (0..count.max(1) as u64)
+                .map(|i| PetriDish::generate(seed.wrapping_add(i), config))
+                .collect(),
+            portals: Vec::new(),
+        };
+        for i in 1..world.dishes.len() {
+            let (left, right) = (&world.dishes[i - 1], &world.dishes[i]);
+            let exit = (i - 1, left.width - PORTAL_MARGIN, left.height / 2.0);
+            let entry = (i, PORTAL_MARGIN, right.height / 2.0);
+            world.connect(exit, entry, PORTAL_RADIUS);
+        }
+        world
+    }
+
+    /// Joins `(dish, x, y)` points `a` and `b` with a two-way channel.
+    ///
+    /// Obstacles covering either opening are removed so the channel is
+    /// reachable.
+    pub fn connect(&mut self, a: (usize, f64, f64), b: (usize, f64, f64), radius: f64) {
+        for (dish, x, y) in [a, b] {
+            self.dishes[dish].obstacles.retain(|o| !o.contains(x, y));
+        }
+        for ((from, x, y), (to, to_x, to_y)) in [(a, b), (b, a)] {
+            self.portals.push(Portal {
+                from,
+                x,
+                y,
+                radius,
+                to,
+                to_x,
+                to_y,
+            });
+        }
+    }
+
+    /// Number of dishes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dishes.len()
+    }
+
+    /// True for a world without dishes.
+    #[must_use]
+    #[allow(dead_code)] // Pairs with `len`
+    pub fn is_empty(&self) -> bool {
+        self.dishes.is_empty()
+    }
+
+    /// Advances every dish by one tick.
+    pub fn update(&mut self) {
+        for dish in &mut self.dishes {
+            dish.update();
+        }
+    }
+
+    /// `update`, reporting each dish's source events to the observer at the
+    /// same index (dishes without one update unobserved).
+    pub fn update_observed(&mut self, observers: &mut [impl DishObserver]) {
+        for (i, dish) in self.dishes.iter_mut().enumerate() {
+            match observers.get_mut(i) {
+                Some(observer) => dish.update_observed(observer),
+                None => dish.update(),
+            }
+        }
+    }
+
+    /// Resizes every dish to `width` × `height` (see `PetriDish::resize`),
+    /// moving portal openings and exits and the agents' maps along.
+    /// Returns the scale factors of each dish.
+    ///
+    /// Only the maps of dishes an agent has visited exist to be stretched;
+    /// the others are built at the dish's current size on the agent's first
+    /// visit (`step_agent` passes it to `Protozoa::migrate`).
+    pub fn resize(&mut self, width: f64, height: f64, agents: &mut [Protozoa]) -> Vec<(f64, f64)> {
+        let scales: Vec<(f64, f64)> = self
+            .dishes
+            .iter_mut()
+            .map(|dish| dish.resize(width, height))
+            .collect();
+        for portal in &mut self.portals {
+            let ((sx, sy), (tx, ty)) = (scales[portal.from], scales[portal.to]);
+            portal.x *= sx;
+            portal.y *= sy;
+            portal.to_x *= tx;
+            portal.to_y *= ty;
+        }
+        for agent in agents {
+            for (dish_id, &(sx, sy)) in scales.iter().enumerate() {
+                agent.rescale_dish(dish_id, sx, sy);
+            }
+        }
+        scales
+    }
+
+    /// Portals whose opening is in `dish`.
+    pub fn portals_in(&self, dish: usize) -> impl Iterator<Item = &Portal> {
+        self.portals.iter().filter(move |p| p.from == dish)
+    }
+
+    /// Portal whose opening covers (x, y) in `dish`, if any.
+    #[must_use]
+    pub fn portal_at(&self, dish: usize, x: f64, y: f64) -> Option<&Portal> {
+        self.portals_in(dish).find(|p| p.contains(x, y))
+    }
+
+    /// Senses and acts in the agent's current dish, then migrates it if it
+    /// swam into a portal this tick. Returns the new dish on migration.
+    pub fn step_agent(&self, agent: &mut Protozoa) -> Option<usize> {
+        let dish = &self.dishes[agent.dish_id];
+        let (px, py) = (agent.x, agent.y);
+        agent.sense(dish);
+        agent.update_state(dish);
+
+        let portal = self.portal_at(agent.dish_id, agent.x, agent.y)?;
+        if portal.contains(px, py) {
+            return None;
+        }
+        let to = &self.dishes[portal.to];
+        agent.migrate(portal.to, portal.to_x, portal.to_y, to.width, to.height);
+        Some(portal.to)
+    }
+}
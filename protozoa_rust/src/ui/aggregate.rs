@@ -0,0 +1,209 @@
+//! Multi-run aggregate dashboard.
+//!
+//! Renders live mean ± std curves of energy, VFE and coverage across the
+//! replicate runs of an `Ensemble`, in place of the single-dish view.
+//! Parameter changes (`Ensemble::markers`) are drawn as vertical lines on
+//! every chart.
+
+use crate::simulation::annotation::Marker as ChangeMarker;
+use crate::simulation::ensemble::{EnsembleSample, Stat};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+};
+
+/// Series for one metric: mean, lower band and upper band as (tick, value) points.
+pub struct BandSeries {
+    pub mean: Vec<(f64, f64)>,
+    pub lower: Vec<(f64, f64)>,
+    pub upper: Vec<(f64, f64)>,
+}
+
+/// Extracts band series for one metric from the sample history, over sim time.
+#[must_use]
+pub fn band_series<F>(history: &[EnsembleSample], metric: F) -> BandSeries
+where
+    F: Fn(&EnsembleSample) -> Stat,
+{
+    let mut series = BandSeries {
+        mean: Vec::with_capacity(history.len()),
+        lower: Vec::with_capacity(history.len()),
+        upper: Vec::with_capacity(history.len()),
+    };
+    for sample in history {
+        let stat = metric(sample);
+        let t = sample.time;
+        series.mean.push((t, stat.mean));
+        series.lower.push((t, stat.lower()));
+        series.upper.push((t, stat.upper()));
+    }
+    series
+}
+
+/// Y-axis bounds covering the band, padded so flat curves stay visible.
+#[must_use]
+pub fn band_bounds(series: &BandSeries) -> [f64; 2] {
+    let lo = series
+        .lower
+        .iter()
+        .map(|p| p.1)
+        .fold(f64::INFINITY, f64::min);
+    let hi = series
+        .upper
+        .iter()
+        .map(|p| p.1)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if !lo.is_finite() || !hi.is_finite() {
+        return [0.0, 1.0];
+    }
+    let pad = ((hi - lo) * 0.1).max(0.01);
+    [lo - pad, hi + pad]
+}
+
+/// Draws the aggregate dashboard: header line plus one chart per metric,
+/// with `markers` as vertical lines and `command_line` (prompt or feedback)
+/// in the header title.
+pub fn draw_aggregate_dashboard(
+    f: &mut Frame,
+    history: &[EnsembleSample],
+    run_count: usize,
+    markers: &[ChangeMarker],
+    command_line: Option<&str>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
+        .split(f.area());
+
+    let header = match history.last() {
+        Some(s) => format!(
+            "Runs: {run_count}  Tick: {} ({:.0}s)  E: {:.2}±{:.2}  F: {:.2}±{:.2}  Cov: {:.0}%",
+            s.tick,
+            s.time,
+            s.energy.mean,
+            s.energy.std,
+            s.vfe.mean,
+            s.vfe.std,
+            s.coverage.mean * 100.0
+        ),
+        None => format!("Runs: {run_count}  warming up..."),
+    };
+    let header = match markers.last() {
+        Some(m) => format!("{header}  Changed: {} @ {:.0}s", m.label, m.time),
+        None => header,
+    };
+    let title = match command_line {
+        Some(command) => format!(" Ensemble [{command}] "),
+        None => " Ensemble ".to_string(),
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
+    f.render_widget(
+        Paragraph::new(Line::from(Span::raw(header))).block(block),
+        chunks[0],
+    );
+
+    draw_band_chart(
+        f,
+        chunks[1],
+        " Energy ",
+        &band_series(history, |s| s.energy),
+        Color::Green,
+        markers,
+    );
+    draw_band_chart(
+        f,
+        chunks[2],
+        " VFE ",
+        &band_series(history, |s| s.vfe),
+        Color::Red,
+        markers,
+    );
+    draw_band_chart(
+        f,
+        chunks[3],
+        " Coverage ",
+        &band_series(history, |s| s.coverage),
+        Color::Cyan,
+        markers,
+    );
+}
+
+fn draw_band_chart(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    series: &BandSeries,
+    color: Color,
+    markers: &[ChangeMarker],
+) {
+    let x_lo = series.mean.first().map_or(0.0, |p| p.0);
+    let x_hi = series.mean.last().map_or(1.0, |p| p.0).max(x_lo + 1.0);
+    let y_bounds = band_bounds(series);
+    let marker_lines = marker_lines(markers, [x_lo, x_hi], y_bounds);
+
+    let band_style = Style::default().fg(Color::DarkGray);
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(band_style)
+            .data(&series.lower),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(band_style)
+            .data(&series.upper),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(color))
+            .data(&series.mean),
+    ];
+    let datasets = datasets
+        .into_iter()
+        .chain(marker_lines.iter().map(|line| {
+            Dataset::default()
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Yellow))
+                .data(line)
+        }))
+        .collect();
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .x_axis(
+            Axis::default()
+                .bounds([x_lo, x_hi])
+                .labels([format!("{x_lo:.0}"), format!("{x_hi:.0}")]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds(y_bounds)
+                .labels([format!("{:.2}", y_bounds[0]), format!("{:.2}", y_bounds[1])]),
+        );
+    f.render_widget(chart, area);
+}
+
+/// Vertical segments spanning `y_bounds` at each marker time inside `x_bounds`.
+#[must_use]
+pub fn marker_lines(
+    markers: &[ChangeMarker],
+    [x_lo, x_hi]: [f64; 2],
+    [y_lo, y_hi]: [f64; 2],
+) -> Vec<[(f64, f64); 2]> {
+    markers
+        .iter()
+        .filter(|m| (x_lo..=x_hi).contains(&m.time))
+        .map(|m| [(m.time, y_lo), (m.time, y_hi)])
+        .collect()
+}
@@ -0,0 +1,37 @@
+//! Caption banner for the `demo` tour.
+//!
+//! `draw_banner` renders a boxed banner above the dashboard: the segment
+//! heading as its title, the caption wrapped over the first lines and the
+//! note of the last cue fired, highlighted, on the line under it.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+/// Rows the banner takes: borders, two caption lines and the note line.
+pub const BANNER_HEIGHT: u16 = 5;
+
+/// Draws the banner in `area` with `heading` as its title, `caption` below
+/// it and `note` (if any) after an arrow.
+pub fn draw_banner(f: &mut Frame, area: Rect, heading: &str, caption: &str, note: Option<&str>) {
+    let block = Block::default()
+        .title(format!(" {heading} "))
+        .title_style(Style::default().add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let mut lines = vec![Line::from(caption.to_string())];
+    if let Some(note) = note {
+        lines.push(Line::from(Span::styled(
+            format!("-> {note}"),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,421 @@
+//! Live evolution dashboard (`evolve --dashboard`).
+//!
+//! A headless `evolve` run prints one line per generation and can take
+//! hours. The dashboard shows it as it goes: the generation and fitness on
+//! top, best and mean fitness per generation, the fitness distribution of
+//! the last `EVOLUTION_BOX_PLOTS` generations as box plots and of the
+//! latest as a histogram, the champion's genes, and a small dish in which
+//! the champion (the best of the latest generation) lives its evaluation
+//! episodes one seed after the other. The evolution runs on a background
+//! thread and hands each `GenerationSummary` to an `EvolutionView`.
+
+use crate::simulation::ensemble::Run;
+use crate::simulation::evolution::{Evolution, Gene, GenerationSummary, Genome};
+use crate::simulation::params::{EVOLUTION_BOX_PLOTS, EVOLUTION_HISTOGRAM_BINS};
+use crate::ui::field::compute_field_grid;
+use crate::ui::render::world_to_grid_coords;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    text::Line,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline},
+};
+
+/// Five-number summary of a fitness distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoxPlot {
+    pub min: f64,
+    /// First quartile
+    pub q1: f64,
+    pub median: f64,
+    /// Third quartile
+    pub q3: f64,
+    pub max: f64,
+}
+
+impl BoxPlot {
+    /// Quartiles of `values` (linearly interpolated), or `None` if empty.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Population sizes are small
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Positions within the list
+    pub fn of(values: &[f64]) -> Option<Self> {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let last = sorted.len().checked_sub(1)?;
+        let quantile = |p: f64| {
+            let position = p * last as f64;
+            let below = position.floor() as usize;
+            let above = (below + 1).min(last);
+            let weight = position - below as f64;
+            sorted[below] + (sorted[above] - sorted[below]) * weight
+        };
+        Some(Self {
+            min: sorted[0],
+            q1: quantile(0.25),
+            median: quantile(0.5),
+            q3: quantile(0.75),
+            max: sorted[last],
+        })
+    }
+
+    /// The plot as `width` characters over `[lo, hi]`: `|` at the extremes,
+    /// `-` whiskers, the box from `[` to `]` filled with `=`, `#` at the
+    /// median.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Widths are small
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Clamped to the width
+    pub fn line(&self, [lo, hi]: [f64; 2], width: usize) -> String {
+        let mut cells = vec![' '; width];
+        if width == 0 {
+            return String::new();
+        }
+        let column = |value: f64| {
+            if hi > lo {
+                (((value - lo) / (hi - lo)) * (width - 1) as f64)
+                    .round()
+                    .clamp(0.0, (width - 1) as f64) as usize
+            } else {
+                0
+            }
+        };
+        let (min, q1, median, q3, max) = (
+            column(self.min),
+            column(self.q1),
+            column(self.median),
+            column(self.q3),
+            column(self.max),
+        );
+        cells[min..=max].fill('-');
+        cells[q1..=q3].fill('=');
+        cells[min] = '|';
+        cells[max] = '|';
+        cells[q1] = '[';
+        cells[q3] = ']';
+        cells[median] = '#';
+        cells.into_iter().collect()
+    }
+}
+
+/// Counts of `values` in `bins` equal bins spanning their range (all in
+/// the first bin if they are equal).
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // Bin counts are small
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Clamped to the bins
+pub fn histogram(values: &[f64], bins: usize) -> Vec<u64> {
+    let mut counts = vec![0; bins];
+    let Some(plot) = BoxPlot::of(values) else {
+        return counts;
+    };
+    if bins == 0 {
+        return counts;
+    }
+    let span = plot.max - plot.min;
+    for value in values {
+        let bin = if span > 0.0 {
+            (((value - plot.min) / span) * bins as f64).floor() as usize
+        } else {
+            0
+        };
+        counts[bin.min(bins - 1)] += 1;
+    }
+    counts
+}
+
+/// The champion living its evaluation episodes in a preview dish.
+#[derive(Clone)]
+pub struct ChampionPreview {
+    pub genome: Genome,
+    pub run: Run,
+    /// Evaluation seed of the episode shown
+    pub seed: u64,
+    /// Ticks into the episode
+    pub tick: u64,
+}
+
+impl ChampionPreview {
+    /// `genome` at the start of its episode under the first evaluation seed.
+    #[must_use]
+    pub fn new(evolution: &Evolution, genome: Genome) -> Self {
+        let seed = evolution.seeds.first().copied().unwrap_or(0);
+        Self {
+            genome,
+            run: evolution.build(&genome, seed),
+            seed,
+            tick: 0,
+        }
+    }
+
+    /// Advances the episode a tick; once the champion has died or reached
+    /// the evolution's tick cap, starts over on the next evaluation seed.
+    pub fn step(&mut self, evolution: &Evolution) {
+        if self.run.agent.is_alive() && self.tick < evolution.ticks {
+            self.run.step();
+            self.tick += 1;
+            return;
+        }
+        let seeds = &evolution.seeds;
+        self.seed = match seeds.iter().position(|&s| s == self.seed) {
+            Some(i) => seeds[(i + 1) % seeds.len()],
+            None => seeds.first().copied().unwrap_or(0),
+        };
+        self.run = evolution.build(&self.genome, self.seed);
+        self.tick = 0;
+    }
+}
+
+/// What the evolution dashboard shows.
+#[derive(Clone)]
+pub struct EvolutionView {
+    /// Summaries of the generations run so far
+    pub generations: Vec<GenerationSummary>,
+    /// Generations the run takes
+    pub planned: usize,
+    /// The run's fitness (`FitnessConfig::describe`)
+    pub fitness: String,
+    pub preview: Option<ChampionPreview>,
+    /// True once the last generation has been summarized
+    pub finished: bool,
+}
+
+impl EvolutionView {
+    /// An empty view of `evolution`, before its first generation.
+    #[must_use]
+    pub fn new(evolution: &Evolution) -> Self {
+        Self {
+            generations: Vec::new(),
+            planned: evolution.generations,
+            fitness: evolution.fitness.describe(),
+            preview: None,
+            finished: false,
+        }
+    }
+
+    /// Adds a generation; a new champion restarts the preview.
+    pub fn push(&mut self, summary: GenerationSummary, evolution: &Evolution) {
+        let champion = summary.best.genome;
+        if self.preview.as_ref().is_none_or(|p| p.genome != champion) {
+            self.preview = Some(ChampionPreview::new(evolution, champion));
+        }
+        self.generations.push(summary);
+        self.finished = self.generations.len() >= self.planned;
+    }
+
+    /// Advances the champion preview `steps` ticks.
+    pub fn step_preview(&mut self, evolution: &Evolution, steps: u64) {
+        if let Some(preview) = &mut self.preview {
+            for _ in 0..steps {
+                preview.step(evolution);
+            }
+        }
+    }
+
+    /// The header line: progress and the latest generation's fitness.
+    #[must_use]
+    pub fn header(&self) -> String {
+        let status = if self.finished { "done" } else { "running" };
+        match self.generations.last() {
+            Some(g) => format!(
+                "Generation {}/{} ({status})  best {:.1}  mean {:.1} ± {:.1}  \
+                 survival {:.0}  front {}  step {:.3}  fitness: {}",
+                g.generation + 1,
+                self.planned,
+                g.best.fitness,
+                g.fitness.mean,
+                g.fitness.std,
+                g.best.survival,
+                g.front.len(),
+                g.mutation_scale,
+                self.fitness
+            ),
+            None => format!(
+                "Generation 0/{} (evaluating the founders...)  fitness: {}",
+                self.planned, self.fitness
+            ),
+        }
+    }
+}
+
+/// Draws the evolution dashboard: the header, then fitness and its
+/// distribution on the left, the champion's genes and preview dish on the
+/// right.
+pub fn draw_evolution_dashboard(f: &mut Frame, view: &EvolutionView) {
+    let [header, body] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(f.area());
+    f.render_widget(
+        Paragraph::new(view.header())
+            .block(Block::default().title(" Evolution ").borders(Borders::ALL)),
+        header,
+    );
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(body);
+    let [chart, spread] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(left);
+    let [genes, dish] = Layout::vertical([
+        Constraint::Length(u16::try_from(Gene::ALL.len()).unwrap_or(0) + 2),
+        Constraint::Min(0),
+    ])
+    .areas(right);
+    draw_fitness_chart(f, chart, view);
+    draw_distribution(f, spread, view);
+    draw_genes(f, genes, view);
+    draw_preview(f, dish, view);
+}
+
+/// Best and mean fitness per generation.
+#[allow(clippy::cast_precision_loss)] // Generation counts are small
+fn draw_fitness_chart(f: &mut Frame, area: Rect, view: &EvolutionView) {
+    let best: Vec<(f64, f64)> = view
+        .generations
+        .iter()
+        .map(|g| (g.generation as f64, g.best.fitness))
+        .collect();
+    let mean: Vec<(f64, f64)> = view
+        .generations
+        .iter()
+        .map(|g| (g.generation as f64, g.fitness.mean))
+        .collect();
+    let x_hi = (view.planned.saturating_sub(1) as f64).max(1.0);
+    let (lo, hi) = view
+        .generations
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), g| {
+            (lo.min(g.fitness.min), hi.max(g.fitness.max))
+        });
+    let y_bounds = if lo.is_finite() && hi.is_finite() {
+        let pad = ((hi - lo) * 0.1).max(0.01);
+        [lo - pad, hi + pad]
+    } else {
+        [0.0, 1.0]
+    };
+    let datasets = vec![
+        Dataset::default()
+            .name("mean")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(&mean),
+        Dataset::default()
+            .name("best")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(&best),
+    ];
+    let chart = Chart::new(datasets)
+        .block(Block::default().title(" Fitness ").borders(Borders::ALL))
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, x_hi])
+                .labels(["0".to_string(), format!("{x_hi:.0}")]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds(y_bounds)
+                .labels([format!("{:.1}", y_bounds[0]), format!("{:.1}", y_bounds[1])]),
+        );
+    f.render_widget(chart, area);
+}
+
+/// Box plots of the recent generations over a shared scale, and the
+/// latest generation's histogram below them.
+fn draw_distribution(f: &mut Frame, area: Rect, view: &EvolutionView) {
+    let block = Block::default()
+        .title(" Fitness distribution ")
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let recent = &view.generations[view.generations.len().saturating_sub(EVOLUTION_BOX_PLOTS)..];
+    let plots: Vec<(usize, BoxPlot)> = recent
+        .iter()
+        .filter_map(|g| BoxPlot::of(&g.fitnesses).map(|plot| (g.generation, plot)))
+        .collect();
+    let lo = plots
+        .iter()
+        .map(|(_, p)| p.min)
+        .fold(f64::INFINITY, f64::min);
+    let hi = plots
+        .iter()
+        .map(|(_, p)| p.max)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let [boxes, bars] = Layout::vertical([
+        Constraint::Length(u16::try_from(plots.len()).unwrap_or(u16::MAX)),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+    let label_width = 5;
+    let width = usize::from(boxes.width).saturating_sub(label_width);
+    let lines: Vec<Line> = plots
+        .iter()
+        .map(|(generation, plot)| {
+            Line::from(format!("{generation:>4} {}", plot.line([lo, hi], width)))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), boxes);
+    if let Some(latest) = view.generations.last() {
+        let counts = histogram(&latest.fitnesses, EVOLUTION_HISTOGRAM_BINS);
+        let title = format!(" Generation {} histogram ", latest.generation);
+        f.render_widget(
+            Sparkline::default()
+                .block(Block::default().title(title).borders(Borders::TOP))
+                .data(&counts)
+                .style(Style::default().fg(Color::Cyan)),
+            bars,
+        );
+    }
+}
+
+/// The champion's gene values.
+fn draw_genes(f: &mut Frame, area: Rect, view: &EvolutionView) {
+    let lines: Vec<Line> = match view.preview.as_ref() {
+        Some(preview) => Gene::ALL
+            .iter()
+            .map(|&gene| {
+                Line::from(format!(
+                    "{:<20}{:.3}",
+                    gene.name(),
+                    preview.genome.get(gene)
+                ))
+            })
+            .collect(),
+        None => vec![Line::from("no champion yet")],
+    };
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().title(" Champion ").borders(Borders::ALL)),
+        area,
+    );
+}
+
+/// The preview dish with the champion drawn as `O` (`x` once dead).
+fn draw_preview(f: &mut Frame, area: Rect, view: &EvolutionView) {
+    let Some(preview) = view.preview.as_ref() else {
+        f.render_widget(
+            Block::default().title(" Preview ").borders(Borders::ALL),
+            area,
+        );
+        return;
+    };
+    let agent = &preview.run.agent;
+    let title = format!(
+        " Preview [seed {} tick {} E {:.2}] ",
+        preview.seed, preview.tick, agent.energy
+    );
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let dish = &preview.run.dish;
+    let (rows, cols) = (usize::from(inner.height), usize::from(inner.width));
+    let mut grid = compute_field_grid(dish, rows, cols);
+    if rows > 0 && cols > 0 {
+        let (r, c) = world_to_grid_coords(agent.x, agent.y, dish.width, dish.height, rows, cols);
+        let glyph = if agent.is_alive() { "O" } else { "x" };
+        if let Some(line) = grid.get_mut(r)
+            && c < line.len()
+        {
+            line.replace_range(c..=c, glyph);
+        }
+    }
+    let lines: Vec<Line> = grid.into_iter().map(Line::from).collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
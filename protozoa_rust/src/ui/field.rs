@@ -1,8 +1,50 @@
+//! Dish layer rasters for the petri dish panel.
+//!
+//! Each layer maps a field to ASCII density characters row by row (in
+//! parallel with the `parallel` feature): nutrient, occupancy (log scale),
+//! temperature, light and agent density (scaled to the most crowded cell,
+//! any occupied cell at least `.`). Overlays draw sparse flow arrows on faint
+//! cells and, on the sources layer, a `~` ring at each source's radius with
+//! a 1–9 intensity digit at its center, leaving obstacle glyphs intact.
+
 use crate::simulation::environment::PetriDish;
+use crate::simulation::occupancy::OccupancyMap;
+use crate::simulation::params::{
+    FLOW_GLYPH_MIN_SPEED, FLOW_GLYPH_SPACING, VISCOSITY_TEXTURE_THRESHOLD,
+};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
+use std::f64::consts::PI;
 
 const CHARS: [char; 10] = [' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
 
+/// Glyph for cells inside an obstacle.
+pub const OBSTACLE_GLYPH: char = '█';
+
+/// Background texture for empty cells in viscous medium.
+pub const VISCOSITY_GLYPH: char = '`';
+
+/// Ring drawn at a nutrient source's radius on the sources layer.
+pub const SOURCE_RING_GLYPH: char = '~';
+
+/// Builds one line per row, in parallel when the `parallel` feature is on.
+#[cfg(feature = "parallel")]
+fn map_rows<F>(rows: usize, line: F) -> Vec<String>
+where
+    F: Fn(usize) -> String + Sync + Send,
+{
+    (0..rows).into_par_iter().map(line).collect()
+}
+
+/// Builds one line per row, in parallel when the `parallel` feature is on.
+#[cfg(not(feature = "parallel"))]
+fn map_rows<F>(rows: usize, line: F) -> Vec<String>
+where
+    F: Fn(usize) -> String,
+{
+    (0..rows).map(line).collect()
+}
+
 #[allow(clippy::cast_precision_loss)]
 #[allow(clippy::cast_possible_truncation)]
 #[allow(clippy::cast_sign_loss)]
@@ -14,25 +56,271 @@ pub fn compute_field_grid(dish: &PetriDish, rows: usize, cols: usize) -> Vec<Str
 
     let scale_y = dish.height / rows as f64;
     let scale_x = dish.width / cols as f64;
+    let index = dish.source_index();
+
+    map_rows(rows, |r| {
+        let mut line = String::with_capacity(cols);
+        for c in 0..cols {
+            let world_y = r as f64 * scale_y;
+            let world_x = c as f64 * scale_x;
+
+            if dish.is_blocked(world_x, world_y) {
+                line.push(OBSTACLE_GLYPH);
+                continue;
+            }
+
+            let val = dish.get_concentration_indexed(&index, world_x, world_y);
+
+            // Map 0.0..1.0 to index 0..9
+            let idx = (val * (CHARS.len() - 1) as f64).round() as usize;
+            let idx = idx.min(CHARS.len() - 1); // Safety clamp
+
+            if idx == 0 && dish.get_viscosity(world_x, world_y) >= VISCOSITY_TEXTURE_THRESHOLD {
+                line.push(VISCOSITY_GLYPH);
+                continue;
+            }
+
+            line.push(CHARS[idx]);
+        }
+        line
+    })
+}
+
+/// Renders the temperature field at field resolution using the density ramp.
+#[must_use]
+pub fn compute_temperature_grid(dish: &PetriDish, rows: usize, cols: usize) -> Vec<String> {
+    compute_scalar_grid(dish, rows, cols, PetriDish::get_temperature)
+}
+
+/// Renders the light field at field resolution using the density ramp.
+#[must_use]
+pub fn compute_light_grid(dish: &PetriDish, rows: usize, cols: usize) -> Vec<String> {
+    compute_scalar_grid(dish, rows, cols, PetriDish::get_light)
+}
+
+/// Renders the pH field at field resolution using the density ramp (dense = alkaline).
+#[must_use]
+pub fn compute_ph_grid(dish: &PetriDish, rows: usize, cols: usize) -> Vec<String> {
+    compute_scalar_grid(dish, rows, cols, PetriDish::get_ph)
+}
+
+/// Renders the pheromone trails at field resolution using the density ramp.
+#[must_use]
+pub fn compute_pheromone_grid(dish: &PetriDish, rows: usize, cols: usize) -> Vec<String> {
+    compute_scalar_grid(dish, rows, cols, PetriDish::get_pheromone)
+}
+
+/// Renders the quorum signal concentration using the density ramp.
+#[must_use]
+pub fn compute_signal_grid(dish: &PetriDish, rows: usize, cols: usize) -> Vec<String> {
+    compute_scalar_grid(dish, rows, cols, PetriDish::get_signal)
+}
+
+/// Samples a [0, 1] scalar field of the dish onto the density ramp.
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn compute_scalar_grid(
+    dish: &PetriDish,
+    rows: usize,
+    cols: usize,
+    sample: fn(&PetriDish, f64, f64) -> f64,
+) -> Vec<String> {
+    if rows == 0 || cols == 0 {
+        return Vec::new();
+    }
+
+    let scale_y = dish.height / rows as f64;
+    let scale_x = dish.width / cols as f64;
+
+    map_rows(rows, |r| {
+        (0..cols)
+            .map(|c| {
+                let val = sample(dish, c as f64 * scale_x, r as f64 * scale_y);
+                let idx = (val.max(0.0) * (CHARS.len() - 1) as f64).round() as usize;
+                CHARS[idx.min(CHARS.len() - 1)]
+            })
+            .collect()
+    })
+}
+
+/// Renders the occupancy heatmap at field resolution using the density ramp.
+///
+/// Intensities are log-scaled so rarely visited cells remain visible next to
+/// habitual loops.
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+#[must_use]
+pub fn compute_occupancy_grid(
+    dish: &PetriDish,
+    occupancy: &OccupancyMap,
+    rows: usize,
+    cols: usize,
+) -> Vec<String> {
+    if rows == 0 || cols == 0 {
+        return Vec::new();
+    }
+
+    let scale_y = dish.height / rows as f64;
+    let scale_x = dish.width / cols as f64;
+
+    map_rows(rows, |r| {
+        (0..cols)
+            .map(|c| {
+                let val = occupancy.log_intensity(c as f64 * scale_x, r as f64 * scale_y);
+                let idx = (val * (CHARS.len() - 1) as f64).round() as usize;
+                CHARS[idx.min(CHARS.len() - 1)]
+            })
+            .collect()
+    })
+}
 
-    // Use rayon to compute rows in parallel
-    (0..rows)
-        .into_par_iter()
-        .map(|r| {
-            let mut line = String::with_capacity(cols);
-            for c in 0..cols {
-                let world_y = r as f64 * scale_y;
-                let world_x = c as f64 * scale_x;
+/// Renders the density of agents at `positions` using the density ramp.
+///
+/// Counts are scaled to the most crowded cell; any occupied cell shows at
+/// least the faintest glyph, so a lone agent is not lost in a swarm.
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+#[must_use]
+pub fn compute_density_grid(
+    dish: &PetriDish,
+    positions: &[(f64, f64)],
+    rows: usize,
+    cols: usize,
+) -> Vec<String> {
+    if rows == 0 || cols == 0 {
+        return Vec::new();
+    }
 
-                let val = dish.get_concentration(world_x, world_y);
+    let scale_y = dish.height / rows as f64;
+    let scale_x = dish.width / cols as f64;
+    let mut counts = vec![0_u32; rows * cols];
+    for &(x, y) in positions {
+        let r = ((y / scale_y).floor().max(0.0) as usize).min(rows - 1);
+        let c = ((x / scale_x).floor().max(0.0) as usize).min(cols - 1);
+        counts[r * cols + c] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(0).max(1);
 
-                // Map 0.0..1.0 to index 0..9
+    map_rows(rows, |r| {
+        (0..cols)
+            .map(|c| {
+                if dish.is_blocked(c as f64 * scale_x, r as f64 * scale_y) {
+                    return OBSTACLE_GLYPH;
+                }
+                let count = counts[r * cols + c];
+                if count == 0 {
+                    return CHARS[0];
+                }
+                let val = f64::from(count) / f64::from(most);
                 let idx = (val * (CHARS.len() - 1) as f64).round() as usize;
-                let idx = idx.min(CHARS.len() - 1); // Safety clamp
+                CHARS[idx.clamp(1, CHARS.len() - 1)]
+            })
+            .collect()
+    })
+}
 
-                line.push(CHARS[idx]);
+/// Center glyph of a source of `intensity`: a digit from `1` (faint) to
+/// `9` (full strength or more).
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+pub fn source_center_glyph(intensity: f64) -> char {
+    let level = (intensity.clamp(0.0, 1.0) * 9.0).round().max(1.0) as u32;
+    char::from_digit(level, 10).unwrap_or('9')
+}
+
+/// Marks every nutrient source of `dish`: a `SOURCE_RING_GLYPH` ring at its
+/// radius and its center as `source_center_glyph(intensity)`, so sources
+/// stand out from where their concentration has merely spread.
+///
+/// Works on characters rather than bytes, so obstacle glyphs stay intact.
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+pub fn overlay_sources(grid: &mut [String], dish: &PetriDish) {
+    let mut cells: Vec<Vec<char>> = grid.iter().map(|line| line.chars().collect()).collect();
+    let rows = cells.len();
+    let cols = cells.first().map_or(0, Vec::len);
+    if rows == 0 || cols == 0 {
+        return;
+    }
+
+    let scale_y = dish.height / rows as f64;
+    let scale_x = dish.width / cols as f64;
+    let cell = |x: f64, y: f64| {
+        let (r, c) = ((y / scale_y).floor(), (x / scale_x).floor());
+        (r >= 0.0 && c >= 0.0 && r < rows as f64 && c < cols as f64)
+            .then_some((r as usize, c as usize))
+    };
+
+    // Rings first, sampled finely enough to leave no gaps between cells
+    for source in &dish.sources {
+        let steps = (2.0 * PI * source.radius / scale_x.min(scale_y))
+            .ceil()
+            .max(8.0) as usize;
+        for i in 0..steps {
+            let theta = 2.0 * PI * i as f64 / steps as f64;
+            let x = source.x + source.radius * theta.cos();
+            let y = source.y + source.radius * theta.sin();
+            if let Some((r, c)) = cell(x, y)
+                && cells[r][c] != OBSTACLE_GLYPH
+            {
+                cells[r][c] = SOURCE_RING_GLYPH;
             }
-            line
-        })
-        .collect()
+        }
+    }
+    for source in &dish.sources {
+        if let Some((r, c)) = cell(source.x, source.y) {
+            cells[r][c] = source_center_glyph(source.intensity);
+        }
+    }
+
+    for (line, row) in grid.iter_mut().zip(cells) {
+        *line = row.into_iter().collect();
+    }
+}
+
+/// Maps a flow direction (world coordinates, y down on screen) to an ASCII glyph.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn flow_glyph(v_x: f64, v_y: f64) -> char {
+    const GLYPHS: [char; 8] = ['>', '\\', 'v', '/', '<', '\\', '^', '/'];
+    let angle = v_y.atan2(v_x);
+    let octant = ((angle + PI / 8.0) / (PI / 4.0)).floor() as i32;
+    GLYPHS[octant.rem_euclid(8) as usize]
+}
+
+/// Overlays flow direction glyphs on a sparse lattice of low-density field cells.
+///
+/// Glyphs are only drawn on blank, faint or viscous-texture cells so nutrient
+/// blobs stay readable.
+#[allow(clippy::cast_precision_loss)]
+pub fn overlay_flow_glyphs(grid: &mut [String], dish: &PetriDish) {
+    let rows = grid.len();
+    let cols = grid.first().map_or(0, String::len);
+    if dish.flow.is_still() || rows == 0 || cols == 0 {
+        return;
+    }
+
+    let scale_y = dish.height / rows as f64;
+    let scale_x = dish.width / cols as f64;
+    let half = FLOW_GLYPH_SPACING / 2;
+
+    for r in (half..rows).step_by(FLOW_GLYPH_SPACING) {
+        let line = &mut grid[r];
+        for c in (half..cols).step_by(FLOW_GLYPH_SPACING) {
+            if !matches!(line.as_bytes().get(c), Some(b' ' | b'.' | b'`')) {
+                continue;
+            }
+            let (v_x, v_y) = dish.flow_at(c as f64 * scale_x, r as f64 * scale_y);
+            if v_x.hypot(v_y) < FLOW_GLYPH_MIN_SPEED {
+                continue;
+            }
+            let mut buf = [0u8; 4];
+            line.replace_range(c..=c, flow_glyph(v_x, v_y).encode_utf8(&mut buf));
+        }
+    }
 }
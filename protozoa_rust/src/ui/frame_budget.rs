@@ -0,0 +1,199 @@
+//! Frame-budget guard for the live dashboard.
+//!
+//! Each tick's work (stepping the simulation and drawing the frame) should
+//! fit in the tick interval. `FrameBudget` keeps a smoothed frame time and
+//! walks a `Degradation` ladder: after `FRAME_BUDGET_DEGRADE_FRAMES` frames
+//! in a row over budget it gives up one more nicety, and after
+//! `FRAME_BUDGET_RESTORE_FRAMES` frames in a row under
+//! `FRAME_BUDGET_HEADROOM` of it it takes one back. The gap between the two
+//! thresholds keeps the level from flapping. The current level is shown in
+//! the dish panel's title. `FieldCache` holds the dish field between frames
+//! for the levels that reuse it.
+
+use crate::simulation::params::{
+    FRAME_BUDGET_DEGRADE_FRAMES, FRAME_BUDGET_HEADROOM, FRAME_BUDGET_RESTORE_FRAMES,
+    FRAME_BUDGET_SMOOTHING, FRAME_FIELD_REFRESH, MCTS_MIN_DEPTH, MCTS_MIN_ROLLOUTS,
+};
+use crate::simulation::planning::PlanningBudget;
+use crate::ui::DishLayer;
+use std::time::Duration;
+
+/// How much the dashboard has given up to keep up; each level keeps the
+/// savings of the ones before it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Degradation {
+    /// Everything as configured
+    #[default]
+    Full,
+    /// The dish field is recomputed only every `FRAME_FIELD_REFRESH` frames
+    ReuseField,
+    /// Optional layers drop back to the nutrient field
+    NutrientOnly,
+    /// Replans use the smallest MCTS budget
+    LeanPlanning,
+}
+
+impl Degradation {
+    /// One level further down (the last level stays).
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Full => Self::ReuseField,
+            Self::ReuseField => Self::NutrientOnly,
+            Self::NutrientOnly | Self::LeanPlanning => Self::LeanPlanning,
+        }
+    }
+
+    /// One level back up (full stays full).
+    #[must_use]
+    pub const fn previous(self) -> Self {
+        match self {
+            Self::Full | Self::ReuseField => Self::Full,
+            Self::NutrientOnly => Self::ReuseField,
+            Self::LeanPlanning => Self::NutrientOnly,
+        }
+    }
+
+    /// Short label for the status line.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::ReuseField => "reused field",
+            Self::NutrientOnly => "nutrient only",
+            Self::LeanPlanning => "lean planning",
+        }
+    }
+
+    /// Returns true if the dish field may be reused between frames.
+    #[must_use]
+    pub fn reuses_field(self) -> bool {
+        self >= Self::ReuseField
+    }
+
+    /// Layer to draw in place of `layer`.
+    #[must_use]
+    pub fn layer(self, layer: DishLayer) -> DishLayer {
+        if self >= Self::NutrientOnly {
+            DishLayer::Nutrient
+        } else {
+            layer
+        }
+    }
+
+    /// Cap on every agent's planning budget (`Protozoa::planning_cap`).
+    #[must_use]
+    pub fn planning_cap(self) -> Option<PlanningBudget> {
+        (self >= Self::LeanPlanning).then_some(PlanningBudget {
+            depth: MCTS_MIN_DEPTH,
+            rollouts: MCTS_MIN_ROLLOUTS,
+        })
+    }
+}
+
+/// Smoothed frame time against the tick budget, and the level it has led to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameBudget {
+    /// Time one tick's work may take
+    pub budget: Duration,
+    pub level: Degradation,
+    /// Smoothed frame time in seconds (`None` before the first frame)
+    mean: Option<f64>,
+    /// Consecutive frames over budget
+    over: u32,
+    /// Consecutive frames with headroom
+    under: u32,
+}
+
+impl FrameBudget {
+    /// A guard for frames of at most `budget`, starting at full quality.
+    #[must_use]
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            level: Degradation::Full,
+            mean: None,
+            over: 0,
+            under: 0,
+        }
+    }
+
+    /// Smoothed frame time so far.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests
+    pub fn mean_frame(&self) -> Duration {
+        Duration::from_secs_f64(self.mean.unwrap_or(0.0))
+    }
+
+    /// Records one frame's work; returns true if the level changed.
+    pub fn record(&mut self, work: Duration) -> bool {
+        let work = work.as_secs_f64();
+        let mean = match self.mean {
+            Some(mean) => mean + FRAME_BUDGET_SMOOTHING * (work - mean),
+            None => work,
+        };
+        self.mean = Some(mean);
+        let budget = self.budget.as_secs_f64();
+        let level = self.level;
+        if mean > budget {
+            self.under = 0;
+            self.over += 1;
+            if self.over >= FRAME_BUDGET_DEGRADE_FRAMES {
+                self.over = 0;
+                self.level = level.next();
+            }
+        } else if mean < budget * FRAME_BUDGET_HEADROOM {
+            self.over = 0;
+            self.under += 1;
+            if self.under >= FRAME_BUDGET_RESTORE_FRAMES {
+                self.under = 0;
+                self.level = level.previous();
+            }
+        } else {
+            self.over = 0;
+            self.under = 0;
+        }
+        self.level != level
+    }
+
+    /// Status text while degraded, e.g. `lag 2/3: nutrient only`.
+    #[must_use]
+    pub fn status(&self) -> Option<String> {
+        let step = self.level as usize;
+        let last = Degradation::LeanPlanning as usize;
+        (step > 0).then(|| format!("lag {step}/{last}: {}", self.level.label()))
+    }
+}
+
+/// What a cached dish field shows: drawn layer, shown dish and panel size.
+pub type FieldKey = (DishLayer, usize, (usize, usize));
+
+/// The last computed dish field, reused while the frame budget allows.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldCache {
+    key: Option<FieldKey>,
+    grid: Vec<String>,
+    /// Frames the grid has been shown for
+    age: u64,
+}
+
+impl FieldCache {
+    /// The field for `key`: the cached one if `reuse` allows, it shows the
+    /// same thing and is younger than `FRAME_FIELD_REFRESH` frames, else a
+    /// fresh one from `compute`.
+    pub fn grid(
+        &mut self,
+        key: FieldKey,
+        reuse: bool,
+        compute: impl FnOnce() -> Vec<String>,
+    ) -> Vec<String> {
+        if reuse && self.key == Some(key) && self.age < FRAME_FIELD_REFRESH {
+            self.age += 1;
+        } else {
+            self.grid = compute();
+            self.key = Some(key);
+            self.age = 1;
+        }
+        self.grid.clone()
+    }
+}
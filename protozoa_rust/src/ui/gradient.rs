@@ -0,0 +1,125 @@
+//! Belief-gradient dish layer.
+//!
+//! For every spatial-memory region, draws the direction in which the agent
+//! *believes* concentration increases (finite differences of its learned
+//! priors) next to the true gradient of the dish. Mismatched arrow pairs show
+//! where the world model misleads navigation.
+
+use crate::simulation::environment::PetriDish;
+use crate::simulation::memory::SpatialGrid;
+use crate::simulation::params::GRADIENT_GLYPH_MIN;
+use crate::ui::field::flow_glyph;
+
+/// Belief and true gradient for one spatial-memory region.
+#[derive(Clone, Copy, Debug)]
+pub struct RegionGradient {
+    /// Region center in world coordinates
+    pub x: f64,
+    pub y: f64,
+    /// Gradient implied by the spatial priors (`None` if unexplored)
+    pub belief: Option<(f64, f64)>,
+    /// True gradient of the nutrient field
+    pub truth: (f64, f64),
+}
+
+impl RegionGradient {
+    /// Cosine similarity between believed and true direction in [-1, 1].
+    ///
+    /// `None` if the region is unexplored or either gradient is too flat to
+    /// have a direction.
+    #[must_use]
+    pub fn alignment(&self) -> Option<f64> {
+        let (b_x, b_y) = self.belief?;
+        let (t_x, t_y) = self.truth;
+        let b_norm = b_x.hypot(b_y);
+        let t_norm = t_x.hypot(t_y);
+        if b_norm < GRADIENT_GLYPH_MIN || t_norm < GRADIENT_GLYPH_MIN {
+            return None;
+        }
+        Some(((b_x * t_x + b_y * t_y) / (b_norm * t_norm)).clamp(-1.0, 1.0))
+    }
+}
+
+/// Computes belief and true gradients at the center of every grid region.
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // Grid dimensions are small
+pub fn region_gradients<const W: usize, const H: usize>(
+    dish: &PetriDish,
+    priors: &SpatialGrid<W, H>,
+) -> Vec<RegionGradient> {
+    let (cell_w, cell_h) = priors.cell_dimensions();
+    let mut regions = Vec::with_capacity(W * H);
+    for row in 0..H {
+        for col in 0..W {
+            let x = (col as f64 + 0.5) * cell_w;
+            let y = (row as f64 + 0.5) * cell_h;
+            regions.push(RegionGradient {
+                x,
+                y,
+                belief: priors.mean_gradient(x, y),
+                truth: dish.gradient(x, y),
+            });
+        }
+    }
+    regions
+}
+
+/// Mean alignment over regions where both directions are defined.
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // Region counts are small
+pub fn mean_alignment(regions: &[RegionGradient]) -> Option<f64> {
+    let scores: Vec<f64> = regions
+        .iter()
+        .filter_map(RegionGradient::alignment)
+        .collect();
+    if scores.is_empty() {
+        return None;
+    }
+    Some(scores.iter().sum::<f64>() / scores.len() as f64)
+}
+
+/// Arrow for a gradient, `.` if too flat, `?` if unknown.
+fn gradient_glyph(gradient: Option<(f64, f64)>) -> char {
+    match gradient {
+        None => '?',
+        Some((g_x, g_y)) if g_x.hypot(g_y) < GRADIENT_GLYPH_MIN => '.',
+        Some((g_x, g_y)) => flow_glyph(g_x, g_y),
+    }
+}
+
+/// Renders the gradient layer: one `belief true` glyph pair per region.
+///
+/// The belief glyph sits left of the region center and the true glyph to its
+/// right, so aligned regions read as matching pairs (e.g. `> >`).
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+#[must_use]
+pub fn compute_gradient_grid<const W: usize, const H: usize>(
+    dish: &PetriDish,
+    priors: &SpatialGrid<W, H>,
+    rows: usize,
+    cols: usize,
+) -> Vec<String> {
+    if rows == 0 || cols == 0 {
+        return Vec::new();
+    }
+
+    let mut cells = vec![vec![' '; cols]; rows];
+    let scale_y = rows as f64 / dish.height;
+    let scale_x = cols as f64 / dish.width;
+
+    for region in region_gradients(dish, priors) {
+        let r = ((region.y * scale_y) as usize).min(rows - 1);
+        let c = ((region.x * scale_x) as usize).min(cols - 1);
+        let line = &mut cells[r];
+        if let Some(slot) = c.checked_sub(1) {
+            line[slot] = gradient_glyph(region.belief);
+        }
+        if let Some(slot) = line.get_mut(c + 1) {
+            *slot = gradient_glyph(Some(region.truth));
+        }
+    }
+
+    cells.into_iter().map(String::from_iter).collect()
+}
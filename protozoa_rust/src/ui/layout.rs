@@ -0,0 +1,226 @@
+//! Responsive dashboard layout.
+//!
+//! `DashboardLayout::for_area` sizes the dashboard from the terminal area
+//! instead of fixed panel heights. The petri dish keeps 70% of the width, so
+//! its field grid gains resolution with the terminal. Sidebar panels get the
+//! rows their content needs; rows beyond that go to the event log until it
+//! shows its whole history, then to the spatial memory grid, which is drawn
+//! upscaled once it has room (`grid_scale`). From `WIDE_TERMINAL_COLUMNS` on,
+//! landmarks and events move to an extra detail column, leaving the spatial
+//! grid and the event log the full height of their columns.
+//!
+//! `LayoutKind::Quadrant` is the alternative arrangement (`DashboardLayout::quadrants`):
+//! field, beliefs, planning and memory in four equal quadrants.
+
+use crate::simulation::params::{
+    EVENT_LOG_CAPACITY, GRID_HEIGHT, MAX_LANDMARKS, WIDE_TERMINAL_COLUMNS,
+};
+use crate::ui::render::compute_quadrant_layout;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::widgets::{Block, Borders};
+use serde::{Deserialize, Serialize};
+
+/// Arrangement of the dashboard panels (toggled with `v`, saved in the render config).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutKind {
+    /// Petri dish on the left, the other panels stacked in a sidebar
+    #[default]
+    Sidebar,
+    /// Field, beliefs, planning and memory quadrants
+    Quadrant,
+}
+
+impl LayoutKind {
+    /// The other layout.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Sidebar => Self::Quadrant,
+            Self::Quadrant => Self::Sidebar,
+        }
+    }
+}
+
+/// Rows of the agent metrics panel
+pub const METRICS_HEIGHT: u16 = 9;
+/// Rows of the MCTS planning panel
+pub const MCTS_HEIGHT: u16 = 9;
+/// Rows of the event log on small terminals
+pub const EVENTS_MIN_HEIGHT: u16 = 6;
+
+/// Borders plus the two header rows and one row per landmark slot.
+#[allow(clippy::cast_possible_truncation)] // MAX_LANDMARKS is small
+pub const LANDMARKS_HEIGHT: u16 = MAX_LANDMARKS as u16 + 4;
+
+/// Borders plus both summaries and a full history of arrivals and source events.
+#[allow(clippy::cast_possible_truncation)] // EVENT_LOG_CAPACITY is small
+pub const EVENTS_FULL_HEIGHT: u16 = 2 * EVENT_LOG_CAPACITY as u16 + 4;
+
+/// Borders plus one row per spatial memory row.
+#[allow(clippy::cast_possible_truncation)] // GRID_HEIGHT is small
+pub const SPATIAL_HEIGHT: u16 = GRID_HEIGHT as u16 + 2;
+
+/// Panel areas of the dashboard for one terminal size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DashboardLayout {
+    /// Petri dish panel
+    pub main: Rect,
+    pub metrics: Rect,
+    pub mcts: Rect,
+    pub landmarks: Rect,
+    pub events: Rect,
+    pub spatial: Rect,
+    /// Extra column holding landmarks and events (wide terminals only)
+    pub detail: Option<Rect>,
+}
+
+impl DashboardLayout {
+    /// Lays the dashboard out in `area` as `kind`.
+    #[must_use]
+    pub fn new(kind: LayoutKind, area: Rect) -> Self {
+        match kind {
+            LayoutKind::Sidebar => Self::for_area(area),
+            LayoutKind::Quadrant => Self::quadrants(area),
+        }
+    }
+
+    /// The quadrant layout: field (top left), beliefs as the agent metrics
+    /// (top right), planning over the event log (bottom left) and landmarks
+    /// over spatial memory (bottom right).
+    #[must_use]
+    pub fn quadrants(area: Rect) -> Self {
+        let quadrants = compute_quadrant_layout(area);
+        let [mcts, events] =
+            Layout::vertical([Constraint::Length(MCTS_HEIGHT), Constraint::Min(0)])
+                .areas(quadrants[2]);
+        let [landmarks, spatial] =
+            Layout::vertical([Constraint::Length(LANDMARKS_HEIGHT), Constraint::Min(0)])
+                .areas(quadrants[3]);
+        Self {
+            main: quadrants[0],
+            metrics: quadrants[1],
+            mcts,
+            landmarks,
+            events,
+            spatial,
+            detail: None,
+        }
+    }
+
+    /// Sidebar layout of `area`, responsive to its size.
+    #[must_use]
+    pub fn for_area(area: Rect) -> Self {
+        if area.width >= WIDE_TERMINAL_COLUMNS {
+            let [main, sidebar, detail] = Layout::horizontal([
+                Constraint::Percentage(70),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+            ])
+            .areas(area);
+            let [metrics, mcts, spatial] = Layout::vertical([
+                Constraint::Length(METRICS_HEIGHT),
+                Constraint::Length(MCTS_HEIGHT),
+                Constraint::Min(0),
+            ])
+            .areas(sidebar);
+            let [landmarks, events] =
+                Layout::vertical([Constraint::Length(LANDMARKS_HEIGHT), Constraint::Min(0)])
+                    .areas(detail);
+            return Self {
+                main,
+                metrics,
+                mcts,
+                landmarks,
+                events,
+                spatial,
+                detail: Some(detail),
+            };
+        }
+
+        let [main, sidebar] =
+            Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)])
+                .areas(area);
+        // Rows left once every panel has what its content needs
+        let surplus = sidebar.height.saturating_sub(
+            METRICS_HEIGHT + MCTS_HEIGHT + LANDMARKS_HEIGHT + EVENTS_MIN_HEIGHT + SPATIAL_HEIGHT,
+        );
+        let events_height = EVENTS_MIN_HEIGHT + surplus.min(EVENTS_FULL_HEIGHT - EVENTS_MIN_HEIGHT);
+        let [metrics, mcts, landmarks, events, spatial] = Layout::vertical([
+            Constraint::Length(METRICS_HEIGHT),
+            Constraint::Length(MCTS_HEIGHT),
+            Constraint::Length(LANDMARKS_HEIGHT),
+            Constraint::Length(events_height),
+            Constraint::Min(0),
+        ])
+        .areas(sidebar);
+        Self {
+            main,
+            metrics,
+            mcts,
+            landmarks,
+            events,
+            spatial,
+            detail: None,
+        }
+    }
+
+    /// (rows, columns) of the field grid inside the petri dish panel.
+    #[must_use]
+    pub fn field_size(&self) -> (usize, usize) {
+        let inner = Block::default().borders(Borders::ALL).inner(self.main);
+        (usize::from(inner.height), usize::from(inner.width))
+    }
+
+    /// World coordinates of a terminal cell inside the petri dish panel
+    /// (`None` for cells outside the field).
+    #[must_use]
+    pub fn screen_to_world(
+        &self,
+        column: u16,
+        row: u16,
+        width: f64,
+        height: f64,
+    ) -> Option<(f64, f64)> {
+        let inner = Block::default().borders(Borders::ALL).inner(self.main);
+        if inner.width == 0
+            || inner.height == 0
+            || column < inner.x
+            || row < inner.y
+            || column >= inner.x + inner.width
+            || row >= inner.y + inner.height
+        {
+            return None;
+        }
+        let c = f64::from(column - inner.x) + 0.5;
+        let r = f64::from(row - inner.y) + 0.5;
+        Some((
+            c * width / f64::from(inner.width),
+            r * height / f64::from(inner.height),
+        ))
+    }
+
+    /// Sidebar panels in drawing order: [Metrics, MCTS, Landmarks, Events, Spatial].
+    #[must_use]
+    pub fn panels(&self) -> Vec<Rect> {
+        vec![
+            self.metrics,
+            self.mcts,
+            self.landmarks,
+            self.events,
+            self.spatial,
+        ]
+    }
+}
+
+/// Whole-cell (horizontal, vertical) upscaling of a `cols` × `rows` grid
+/// drawn in `inner`, never stretched taller than wide (1 = drawn as is).
+#[must_use]
+pub fn grid_scale(inner: Rect, cols: usize, rows: usize) -> (usize, usize) {
+    if cols == 0 || rows == 0 {
+        return (1, 1);
+    }
+    let across = (usize::from(inner.width) / cols).max(1);
+    let down = (usize::from(inner.height) / rows).clamp(1, across);
+    (across, down)
+}
@@ -1,11 +1,183 @@
+pub mod aggregate;
+pub mod banner;
+pub mod evolution;
 pub mod field;
+pub mod frame_budget;
+pub mod gradient;
+pub mod layout;
 pub mod render;
+pub mod scrubber;
+pub mod terminal;
 
 use crate::simulation::agent::{AgentMode, Protozoa};
+use crate::simulation::arrival::ArrivalEvent;
+use crate::simulation::controller::ControllerKind;
+use crate::simulation::cycle::Season;
 use crate::simulation::environment::PetriDish;
+use crate::simulation::goal::Goal;
+use crate::simulation::inference::{Likelihood, ParticleBelief};
 use crate::simulation::memory::CellPrior;
-use crate::simulation::params::{LANDMARK_VISIT_RADIUS, TARGET_CONCENTRATION};
-use crate::simulation::planning::ActionDetail;
+use crate::simulation::observer::SourceEvent;
+use crate::simulation::params::{PIN_CURSOR_STEP, SWARM_VIEW_THRESHOLD, TARGET_CONCENTRATION};
+use crate::simulation::planning::{ActionDetail, PlanningBudget};
+use crate::simulation::provenance::Provenance;
+use crate::ui::gradient::{mean_alignment, region_gradients};
+use crate::ui::layout::LayoutKind;
+use crossterm::event::KeyCode;
+use ratatui::style::Color;
+
+/// Which world-space quantity the petri dish panel shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DishLayer {
+    /// True nutrient concentration field
+    #[default]
+    Nutrient,
+    /// Log-scaled time-spent heatmap of the agent
+    Occupancy,
+    /// Believed vs. true concentration gradient per memory region
+    Gradient,
+    /// Temperature field (second sensory modality)
+    Temperature,
+    /// Directional light field (phototaxis)
+    Light,
+    /// pH field (second homeostatic variable)
+    Ph,
+    /// Pheromone trails laid by the agents
+    Pheromone,
+    /// Quorum signals broadcast by exploiting agents
+    Quorum,
+    /// Heatmap of living agents, in place of one glyph each
+    Density,
+    /// Nutrient field with each source's center and radius marked
+    Sources,
+}
+
+impl DishLayer {
+    /// Cycles to the next layer (bound to `l`).
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Nutrient => Self::Occupancy,
+            Self::Occupancy => Self::Gradient,
+            Self::Gradient => Self::Temperature,
+            Self::Temperature => Self::Light,
+            Self::Light => Self::Ph,
+            Self::Ph => Self::Pheromone,
+            Self::Pheromone => Self::Quorum,
+            Self::Quorum => Self::Density,
+            Self::Density => Self::Sources,
+            Self::Sources => Self::Nutrient,
+        }
+    }
+
+    /// Layer actually drawn for a dish holding `alive` living agents: the
+    /// nutrient view of a swarm above `SWARM_VIEW_THRESHOLD` becomes the
+    /// density view, since that many glyphs are unreadable.
+    #[must_use]
+    pub const fn for_swarm(self, alive: usize) -> Self {
+        match self {
+            Self::Nutrient if alive > SWARM_VIEW_THRESHOLD => Self::Density,
+            layer => layer,
+        }
+    }
+
+    /// Short label shown in the panel title.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Nutrient => "Nutrient",
+            Self::Occupancy => "Occupancy",
+            Self::Gradient => "Gradient belief|true",
+            Self::Temperature => "Temperature",
+            Self::Light => "Light",
+            Self::Ph => "pH",
+            Self::Pheromone => "Pheromone",
+            Self::Quorum => "Quorum",
+            Self::Density => "Agent density",
+            Self::Sources => "Sources",
+        }
+    }
+}
+
+/// Cursor for pinning landmarks by hand (toggled with `p`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PinCursor {
+    pub active: bool,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl PinCursor {
+    /// Enters cursor mode at (x, y), or leaves it if already active.
+    pub fn toggle(&mut self, x: f64, y: f64) {
+        if self.active {
+            self.active = false;
+        } else {
+            *self = Self { active: true, x, y };
+        }
+    }
+
+    /// Moves the cursor by `steps` of `PIN_CURSOR_STEP`, clamped to the dish.
+    pub fn nudge(&mut self, steps_x: f64, steps_y: f64, width: f64, height: f64) {
+        self.x = (self.x + steps_x * PIN_CURSOR_STEP).clamp(0.0, width);
+        self.y = (self.y + steps_y * PIN_CURSOR_STEP).clamp(0.0, height);
+    }
+
+    /// Cursor position while active.
+    #[must_use]
+    pub fn position(&self) -> Option<(f64, f64)> {
+        self.active.then_some((self.x, self.y))
+    }
+}
+
+/// Single-line command entry opened with `:` (e.g. `goal 30 20 2`, `clear`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandLine {
+    /// Text typed so far, `None` when closed
+    pub input: Option<String>,
+    /// Feedback from the last submitted command
+    pub message: Option<String>,
+}
+
+impl CommandLine {
+    /// Opens an empty command line.
+    pub fn open(&mut self) {
+        self.input = Some(String::new());
+        self.message = None;
+    }
+
+    /// Returns true while the user is typing a command.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// Feeds a key press; returns the submitted text on Enter.
+    ///
+    /// Esc closes the line without submitting.
+    pub fn handle_key(&mut self, code: KeyCode) -> Option<String> {
+        let input = self.input.as_mut()?;
+        match code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => return self.input.take(),
+            KeyCode::Esc => self.input = None,
+            _ => {}
+        }
+        None
+    }
+
+    /// Text to show in the dashboard: the prompt while typing, else the last message.
+    #[must_use]
+    pub fn display(&self) -> Option<String> {
+        match &self.input {
+            Some(input) => Some(format!(":{input}_")),
+            None => self.message.clone(),
+        }
+    }
+}
 
 /// Snapshot of agent state for dashboard rendering.
 #[derive(Clone, Debug)]
@@ -19,6 +191,14 @@ pub struct DashboardState {
 
     // Metrics
     pub energy: f64,
+    /// Share of the slow energy reserve held
+    pub reserve: f64,
+    /// Structural integrity in [0, 1]
+    pub health: f64,
+    /// Forecast ticks until starvation at the current net energy rate
+    pub starvation_ticks: Option<f64>,
+    /// Forecast ticks until starvation assuming no further intake
+    pub fasting_ticks: Option<f64>,
     pub mode: AgentMode,
     pub prediction_error: f64,
     pub precision: f64,
@@ -31,14 +211,73 @@ pub struct DashboardState {
     pub grid_width: usize,
     pub grid_height: usize,
 
-    // MCTS planning
+    // MCTS planning, with the budget of the last cycle
     pub plan_details: Vec<ActionDetail>,
+    pub planning_budget: PlanningBudget,
     pub ticks_until_replan: u64,
 
     // Episodic memory
     pub landmarks: Vec<LandmarkSnapshot>,
     pub landmark_count: usize,
     pub nav_target_index: Option<usize>,
+
+    // World-model check: mean cosine between believed and true gradients
+    pub gradient_alignment: Option<f64>,
+
+    // View
+    pub layer: DishLayer,
+    /// Panel arrangement
+    pub layout: LayoutKind,
+    /// Landmark pinning cursor position (world coordinates) while active
+    pub pin_cursor: Option<(f64, f64)>,
+    /// Command line prompt or feedback (see `CommandLine::display`)
+    pub command_line: Option<String>,
+    /// Viewed dish in a multi-dish world (e.g. `Dish 2/3`)
+    pub dish_label: Option<String>,
+    /// Selected agent in a population (e.g. `2/5`)
+    pub agent_label: Option<String>,
+    /// Frame-budget degradation while the dashboard is lagging (see
+    /// `FrameBudget::status`)
+    pub frame_status: Option<String>,
+    /// Dish glyphs drawn in their own color (compared runs in a replay)
+    pub glyph_tints: Vec<(char, Color)>,
+
+    // External goal with progress metrics
+    pub goal: Option<Goal>,
+
+    // Arrival events (recent, oldest first) and running statistics
+    pub arrivals: Vec<ArrivalEvent>,
+    pub arrival_count: usize,
+    pub mean_travel_ticks: Option<f64>,
+
+    // Source events of the viewed dish (recent, oldest first) and counts
+    // (depleted, respawned, crossed edge); empty unless a `SourceEventLog` is
+    // attached
+    pub source_events: Vec<(u64, SourceEvent)>,
+    pub source_counts: (usize, usize, usize),
+
+    // Day/night cycle: daylight level (None when the cycle is off) and phase
+    pub daylight: Option<f64>,
+    pub night: bool,
+
+    // Seasonal regime (None when seasons are off)
+    pub season: Option<Season>,
+
+    // Diagnostics: approximate bytes held by the agent (by the population and
+    // its lineage in the live view)
+    pub memory_bytes: usize,
+
+    // Policy driving the agent (baselines are named in the metrics title)
+    pub controller: ControllerKind,
+
+    // What produced the last decision (largest term in the planning title)
+    pub provenance: Provenance,
+
+    // Particle belief over the patch location (None unless the agent tracks one)
+    pub particles: Option<ParticleCloud>,
+
+    // Learned likelihood parameters (None unless the agent learns them)
+    pub likelihood: Option<Likelihood>,
 }
 
 /// Snapshot of a landmark for rendering.
@@ -49,6 +288,37 @@ pub struct LandmarkSnapshot {
     pub y: f64,
     pub reliability: f64,
     pub visit_count: u64,
+    pub pinned: bool,
+}
+
+/// Snapshot of a particle belief for rendering.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParticleCloud {
+    /// (x, y, weight) of every particle
+    pub points: Vec<(f64, f64, f64)>,
+    /// Effective sample size
+    pub ess: f64,
+    /// Dish extent the particles cover
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ParticleCloud {
+    /// The cloud of `belief`.
+    #[must_use]
+    pub fn of(belief: &ParticleBelief) -> Self {
+        let (width, height) = belief.bounds();
+        Self {
+            points: belief
+                .particles()
+                .iter()
+                .map(|p| (p.x, p.y, p.weight))
+                .collect(),
+            ess: belief.effective_sample_size(),
+            width,
+            height,
+        }
+    }
 }
 
 impl DashboardState {
@@ -60,6 +330,7 @@ impl DashboardState {
         let prediction_error = mean_sense - TARGET_CONCENTRATION;
         let precision = agent.spatial_priors.get_cell(agent.x, agent.y).precision();
         let temporal_gradient = agent.temp_gradient;
+        let (starvation_ticks, fasting_ticks) = agent.starvation_forecast();
 
         // Flatten spatial grid
         let (gw, gh) = agent.spatial_priors.dimensions();
@@ -83,19 +354,17 @@ impl DashboardState {
                 y: lm.y,
                 reliability: lm.reliability,
                 visit_count: lm.visit_count,
+                pinned: lm.is_pinned(),
             })
             .collect();
 
         // Find nav target (if in GoalNav mode)
         let nav_target_index = if agent.current_mode(dish) == AgentMode::GoalNav {
-            agent
-                .episodic_memory
-                .best_distant_landmark(agent.x, agent.y, LANDMARK_VISIT_RADIUS)
-                .and_then(|target| {
-                    landmarks.iter().position(|lm| {
-                        (lm.x - target.x).abs() < 0.1 && (lm.y - target.y).abs() < 0.1
-                    })
-                })
+            agent.navigation_landmark().and_then(|target| {
+                landmarks
+                    .iter()
+                    .position(|lm| (lm.x - target.x).abs() < 0.1 && (lm.y - target.y).abs() < 0.1)
+            })
         } else {
             None
         };
@@ -106,6 +375,10 @@ impl DashboardState {
             angle: agent.angle,
             speed: agent.speed,
             energy: agent.energy,
+            reserve: agent.reserve.fraction(),
+            health: agent.health,
+            starvation_ticks,
+            fasting_ticks,
             mode: agent.current_mode(dish),
             prediction_error,
             precision,
@@ -116,10 +389,37 @@ impl DashboardState {
             grid_width: gw,
             grid_height: gh,
             plan_details: agent.planner.last_plan_details().to_vec(),
+            planning_budget: agent.planner.budget(),
             ticks_until_replan: agent.ticks_until_replan(),
             landmarks,
             landmark_count: agent.episodic_memory.count(),
             nav_target_index,
+            gradient_alignment: mean_alignment(&region_gradients(dish, &agent.spatial_priors)),
+            layer: DishLayer::default(),
+            layout: LayoutKind::default(),
+            pin_cursor: None,
+            command_line: None,
+            dish_label: None,
+            agent_label: None,
+            frame_status: None,
+            glyph_tints: Vec::new(),
+            goal: agent.goal,
+            arrivals: agent.events.iter().copied().collect(),
+            arrival_count: agent.events.count(),
+            mean_travel_ticks: agent.events.mean_travel_ticks(),
+            source_events: Vec::new(),
+            source_counts: (0, 0, 0),
+            daylight: (dish.config.day_night_amplitude > 0.0).then(|| dish.daylight()),
+            night: dish.is_night(),
+            season: (!dish.config.seasons().is_off()).then(|| dish.season()),
+            memory_bytes: agent.memory_usage().total(),
+            controller: agent.controller,
+            provenance: agent.provenance,
+            particles: agent.particles.as_ref().map(ParticleCloud::of),
+            likelihood: {
+                let likelihood = agent.generative_model.likelihood;
+                (!likelihood.is_off()).then_some(likelihood)
+            },
         }
     }
 }
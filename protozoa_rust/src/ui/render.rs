@@ -1,8 +1,15 @@
 use crate::simulation::agent::AgentMode;
+use crate::simulation::arrival::{ArrivalEvent, ArrivalTarget};
+use crate::simulation::controller::{Controller, ControllerKind};
+use crate::simulation::goal::Goal;
+use crate::simulation::inference::Likelihood;
 use crate::simulation::memory::CellPrior;
-use crate::simulation::params::{MCTS_DEPTH, MCTS_ROLLOUTS};
-use crate::simulation::planning::{Action, ActionDetail};
-use crate::ui::{DashboardState, LandmarkSnapshot};
+use crate::simulation::observer::SourceEvent;
+use crate::simulation::planning::{Action, ActionDetail, PlanningBudget};
+use crate::simulation::provenance::Provenance;
+use crate::ui::field::VISCOSITY_GLYPH;
+use crate::ui::layout::{DashboardLayout, grid_scale};
+use crate::ui::{DashboardState, DishLayer, LandmarkSnapshot, ParticleCloud};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -12,41 +19,23 @@ use ratatui::{
 };
 
 /// Computes the main + sidebar layout for the dashboard.
-/// Returns (`main_area`, `sidebar_panels`) where `sidebar_panels` is [Metrics, MCTS, Landmarks, Spatial].
+/// Returns (`main_area`, `sidebar_panels`) where `sidebar_panels` is [Metrics, MCTS, Landmarks, Events, Spatial]
+/// (landmarks and events sit in the detail column on wide terminals, see `DashboardLayout`).
 #[must_use]
+#[allow(dead_code)] // Sidebar layout only; used by tests
 pub fn compute_sidebar_layout(area: Rect) -> (Rect, Vec<Rect>) {
-    // Horizontal split: 70% main, 30% sidebar
-    let horizontal = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
-        .split(area);
-
-    let main = horizontal[0];
-
-    // Sidebar vertical split: fixed heights for top 3, remaining for Spatial
-    let sidebar_panels = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(8),  // Metrics
-            Constraint::Length(9),  // MCTS
-            Constraint::Length(12), // Landmarks
-            Constraint::Min(0),     // Spatial (remaining)
-        ])
-        .split(horizontal[1]);
-
-    (main, sidebar_panels.to_vec())
+    let layout = DashboardLayout::for_area(area);
+    (layout.main, layout.panels())
 }
 
 #[must_use]
+#[allow(dead_code)] // Sidebar layout only; used by tests
 pub fn petri_dish_grid_size(area: Rect) -> (usize, usize) {
-    let (main_area, _) = compute_sidebar_layout(area);
-    let inner = Block::default().borders(Borders::ALL).inner(main_area);
-    (inner.height as usize, inner.width as usize)
+    DashboardLayout::for_area(area).field_size()
 }
 
 /// Computes the four quadrant areas for the dashboard layout.
 #[must_use]
-#[allow(dead_code)] // Legacy layout, kept as fallback
 pub fn compute_quadrant_layout(area: Rect) -> Vec<Rect> {
     // Split vertically into top and bottom
     let vertical = Layout::default()
@@ -70,56 +59,137 @@ pub fn compute_quadrant_layout(area: Rect) -> Vec<Rect> {
 
 /// Draws the full cognitive dashboard with sidebar layout.
 pub fn draw_dashboard(f: &mut Frame, grid_lines: Vec<String>, state: &DashboardState) {
-    let (main_area, sidebar) = compute_sidebar_layout(f.area());
+    draw_dashboard_in(f, f.area(), grid_lines, state);
+}
 
-    // === Left: Petri Dish (full height) ===
-    draw_petri_dish_panel(f, main_area, grid_lines);
+/// Draws the dashboard in `area` instead of the whole frame (the replay view
+/// keeps rows below it for the scrubber).
+pub fn draw_dashboard_in(
+    f: &mut Frame,
+    area: Rect,
+    grid_lines: Vec<String>,
+    state: &DashboardState,
+) {
+    let layout = DashboardLayout::new(state.layout, area);
+
+    // === Left: Petri Dish (full height; top left in the quadrant layout) ===
+    draw_petri_dish_panel(f, layout.main, grid_lines, state);
 
     // === Right Sidebar ===
-    // [0] Metrics (top)
-    draw_metrics_panel(f, sidebar[0], state);
+    // Metrics (top)
+    draw_metrics_panel(f, layout.metrics, state);
+
+    // MCTS Planning
+    draw_mcts_panel(f, layout.mcts, state);
 
-    // [1] MCTS Planning
-    draw_mcts_panel(f, sidebar[1], state);
+    // Landmarks (top of the detail column on wide terminals)
+    draw_landmarks_panel(f, layout.landmarks, state);
 
-    // [2] Landmarks
-    draw_landmarks_panel(f, sidebar[2], state);
+    // Arrival events
+    draw_events_panel(f, layout.events, state);
 
-    // [3] Spatial Memory (bottom, takes remaining space)
-    draw_spatial_grid_panel(f, sidebar[3], state);
+    // Spatial Memory (bottom, takes remaining space), shared with the
+    // particle cloud when the agent tracks one
+    if state.particles.is_some() {
+        let [spatial, particles] =
+            Layout::vertical([Constraint::Percentage(50); 2]).areas(layout.spatial);
+        draw_spatial_grid_panel(f, spatial, state);
+        draw_particle_panel(f, particles, state);
+    } else {
+        draw_spatial_grid_panel(f, layout.spatial, state);
+    }
 }
 
-fn draw_petri_dish_panel(f: &mut Frame, area: Rect, grid_lines: Vec<String>) {
-    let block = Block::default().title(" Petri Dish ").borders(Borders::ALL);
+fn draw_petri_dish_panel(
+    f: &mut Frame,
+    area: Rect,
+    grid_lines: Vec<String>,
+    state: &DashboardState,
+) {
+    let layer = state.layer;
+    let title = if let Some(command) = &state.command_line {
+        format!(" Petri Dish [{command}] ")
+    } else if let Some((x, y)) = state.pin_cursor {
+        format!(" Petri Dish [Pin ({x:.0},{y:.0}): arrows/click, Enter pin, Del unpin, Esc] ")
+    } else {
+        match (layer, state.gradient_alignment) {
+            (DishLayer::Nutrient, _) => " Petri Dish ".to_string(),
+            (DishLayer::Gradient, Some(align)) => {
+                format!(" Petri Dish [{}] align {align:+.2} ", layer.label())
+            }
+            _ => format!(" Petri Dish [{}] ", layer.label()),
+        }
+    };
+    let title = match state.daylight {
+        Some(level) => format!("{title}[{}] ", format_daylight(level, state.night)),
+        None => title,
+    };
+    let title = match state.season {
+        Some(season) => format!("{title}[{} season] ", season.label()),
+        None => title,
+    };
+    let title = match &state.dish_label {
+        Some(label) => format!("{title}[{label}] "),
+        None => title,
+    };
+    let title = match &state.frame_status {
+        Some(status) => format!("{title}[{status}] "),
+        None => title,
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
     let inner = block.inner(area);
     f.render_widget(block, area);
 
     // Render field only (no overlay - metrics moved to sidebar)
     let text: Vec<Line> = grid_lines
         .into_iter()
-        .map(|s| Line::from(Span::raw(s)))
+        .map(|line| tint_grid_line(line, &state.glyph_tints))
         .collect();
     let field = Paragraph::new(text);
     f.render_widget(field, inner);
 }
 
 fn draw_metrics_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
-    let block = Block::default().title(" Agent ").borders(Borders::ALL);
+    let title = match &state.agent_label {
+        Some(label) => format!(" Agent {label} [Mem {}] ", format_bytes(state.memory_bytes)),
+        None => format!(" Agent [Mem {}] ", format_bytes(state.memory_bytes)),
+    };
+    let title = match state.controller {
+        ControllerKind::ActiveInference => title,
+        baseline => format!("{title}[{}] ", baseline.name()),
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let angle_deg = state.angle.to_degrees();
-    let lines = format_metrics_overlay(
-        state.energy,
-        state.mode,
-        state.prediction_error,
-        state.precision,
-        state.speed,
-        angle_deg,
-        state.sensor_left,
-        state.sensor_right,
-        state.temporal_gradient,
-    );
+    // The reserve, health, starvation forecast and learned likelihood share
+    // the overlay's lines, keeping the panel at its height
+    let inference = format_inference_line(state.prediction_error, state.precision);
+    let inference = match &state.likelihood {
+        Some(likelihood) => format!("{inference}  {}", format_likelihood_line(likelihood)),
+        None => inference,
+    };
+    let lines = vec![
+        format!(
+            "{} {}",
+            format_energy_line(state.energy),
+            format_reserve_line(state.reserve)
+        ),
+        format!(
+            "{}  {}",
+            format_mode_line(state.mode),
+            format_health_line(state.health)
+        ),
+        inference,
+        format_motion_line(state.speed, state.angle.to_degrees()),
+        format_sensor_line(state.sensor_left, state.sensor_right),
+        format!(
+            "{}  {}",
+            format_gradient_line(state.temporal_gradient),
+            format_starvation_line(state.starvation_ticks, state.fasting_ticks)
+        ),
+        format_goal_line(state.goal.as_ref()),
+    ];
 
     let text: Vec<Line> = lines
         .into_iter()
@@ -173,7 +243,8 @@ fn draw_spatial_grid_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
 
     let lines =
         render_spatial_grid_lines(&display_cells, display_width, state.grid_height, agent_cell);
-    let text: Vec<Line> = lines
+    let (across, down) = grid_scale(inner, display_width, state.grid_height);
+    let text: Vec<Line> = upscale_grid_lines(&lines, across, down)
         .into_iter()
         .map(|s| Line::from(Span::raw(s)))
         .collect();
@@ -181,14 +252,42 @@ fn draw_spatial_grid_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
     f.render_widget(grid, inner);
 }
 
+fn draw_particle_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let Some(cloud) = &state.particles else {
+        return;
+    };
+    let title = format!(" Particles [ESS {:.0}/{}] ", cloud.ess, cloud.points.len());
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines = render_particle_lines(
+        cloud,
+        inner.width as usize,
+        inner.height as usize,
+        (state.x, state.y),
+    );
+    let text: Vec<Line> = lines
+        .into_iter()
+        .map(|s| Line::from(Span::raw(s)))
+        .collect();
+    f.render_widget(Paragraph::new(text), inner);
+}
+
 fn draw_mcts_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
-    let block = Block::default()
-        .title(" MCTS Planning ")
-        .borders(Borders::ALL);
+    let title = match format_driver(&state.provenance) {
+        Some(driver) => format!(" MCTS Planning [{driver}] "),
+        None => " MCTS Planning ".to_string(),
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let lines = format_mcts_summary(&state.plan_details, state.ticks_until_replan);
+    let lines = format_mcts_summary(
+        &state.plan_details,
+        state.planning_budget,
+        state.ticks_until_replan,
+    );
     let text: Vec<Line> = lines
         .into_iter()
         .map(|s| Line::from(Span::raw(s)))
@@ -211,12 +310,133 @@ fn draw_landmarks_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
     f.render_widget(list, inner);
 }
 
+fn draw_events_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let block = Block::default().title(" Events ").borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // Arrivals get the top half, dish source events the rest
+    let rows = inner.height as usize;
+    let mut lines = format_event_log(
+        &state.arrivals,
+        state.arrival_count,
+        state.mean_travel_ticks,
+        rows.saturating_sub(2) / 2,
+    );
+    let remaining = rows.saturating_sub(lines.len() + 1);
+    lines.extend(format_source_events(
+        &state.source_events,
+        state.source_counts,
+        remaining,
+    ));
+    let text: Vec<Line> = lines
+        .into_iter()
+        .map(|s| Line::from(Span::raw(s)))
+        .collect();
+    f.render_widget(Paragraph::new(text), inner);
+}
+
+/// Splits a field line into spans, drawing the viscosity texture dimmed and
+/// each glyph in `tints` in its color.
+fn tint_grid_line(line: String, tints: &[(char, Color)]) -> Line<'static> {
+    let color_of = |ch: char| {
+        if ch == VISCOSITY_GLYPH {
+            return Some(Color::DarkGray);
+        }
+        tints
+            .iter()
+            .find(|(glyph, _)| *glyph == ch)
+            .map(|&(_, c)| c)
+    };
+    if !line.chars().any(|ch| color_of(ch).is_some()) {
+        return Line::from(Span::raw(line));
+    }
+    let span = |text: String, color: Option<Color>| match color {
+        Some(color) => Span::styled(text, Style::default().fg(color)),
+        None => Span::raw(text),
+    };
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_color = None;
+    for ch in line.chars() {
+        let color = color_of(ch);
+        if color != run_color && !run.is_empty() {
+            spans.push(span(std::mem::take(&mut run), run_color));
+        }
+        run_color = color;
+        run.push(ch);
+    }
+    spans.push(span(run, run_color));
+    Line::from(spans)
+}
+
+/// Formats the arrival summary followed by up to `max_events` recent
+/// arrivals, newest first (`G` = goal, `L` = landmark).
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn format_event_log(
+    events: &[ArrivalEvent],
+    total: usize,
+    mean_travel_ticks: Option<f64>,
+    max_events: usize,
+) -> Vec<String> {
+    let summary = match mean_travel_ticks {
+        Some(mean) => format!("Arrivals: {total}  avg {mean:.0}t"),
+        None => format!("Arrivals: {total}"),
+    };
+    let mut lines = vec![summary];
+    for event in events.iter().rev().take(max_events) {
+        let kind = match event.target {
+            ArrivalTarget::Goal => 'G',
+            ArrivalTarget::Landmark => 'L',
+        };
+        lines.push(format!(
+            "{kind}({:>3},{:>3}) {:>4}t {:>3.0}u {:>3.0}%",
+            event.x as i32,
+            event.y as i32,
+            event.travel_ticks,
+            event.path_length,
+            event.path_efficiency() * 100.0
+        ));
+    }
+    lines
+}
+
+/// Formats the source event summary (depleted, respawned, crossed edge)
+/// followed by up to `max_events` recent events, newest first.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn format_source_events(
+    events: &[(u64, SourceEvent)],
+    (depleted, respawned, crossed_edge): (usize, usize, usize),
+    max_events: usize,
+) -> Vec<String> {
+    let mut lines = vec![format!(
+        "Sources: {depleted} low {respawned} new {crossed_edge} edge"
+    )];
+    for (tick, event) in events.iter().rev().take(max_events) {
+        lines.push(match *event {
+            SourceEvent::Depleted { index, species } => {
+                format!("{tick:>6}t #{index} low (sp {species})")
+            }
+            SourceEvent::Respawned { index, x, y, .. } => {
+                format!("{tick:>6}t #{index} new ({:>3},{:>3})", x as i32, y as i32)
+            }
+            SourceEvent::CrossedEdge { index, x, y } => {
+                format!("{tick:>6}t #{index} edge ({:>3},{:>3})", x as i32, y as i32)
+            }
+            SourceEvent::SeasonChanged { season } => {
+                format!("{tick:>6}t {} season", season.label())
+            }
+        });
+    }
+    lines
+}
+
 /// Formats the metrics overlay lines for the petri dish panel.
 #[must_use]
 #[allow(dead_code)] // Used by tests and will be used by dashboard renderer
 #[allow(clippy::too_many_arguments)]
-#[allow(clippy::cast_possible_truncation)]
-#[allow(clippy::cast_sign_loss)]
 pub fn format_metrics_overlay(
     energy: f64,
     mode: AgentMode,
@@ -228,28 +448,115 @@ pub fn format_metrics_overlay(
     sensor_right: f64,
     temporal_gradient: f64,
 ) -> Vec<String> {
-    // Energy bar (10 chars)
+    vec![
+        format_energy_line(energy),
+        format_mode_line(mode),
+        format_inference_line(prediction_error, precision),
+        format_motion_line(speed, angle_deg),
+        format_sensor_line(sensor_left, sensor_right),
+        format_gradient_line(temporal_gradient),
+    ]
+}
+
+/// Formats the energy bar (10 chars) and percentage.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn format_energy_line(energy: f64) -> String {
     let filled = (energy * 10.0).round() as usize;
     let empty = 10 - filled.min(10);
     let bar: String = "\u{2588}".repeat(filled.min(10)) + &"\u{2591}".repeat(empty);
     let pct = (energy * 100.0).round() as i32;
+    format!("E:[{bar}] {pct:>3}%")
+}
 
+fn format_mode_line(mode: AgentMode) -> String {
     let mode_str = match mode {
         AgentMode::Exploring => "EXPLORING",
         AgentMode::Exploiting => "EXPLOITING",
         AgentMode::Panicking => "PANICKING",
         AgentMode::Exhausted => "EXHAUSTED",
         AgentMode::GoalNav => "GOAL-NAV",
+        AgentMode::Dead => "DEAD",
+        AgentMode::Dormant => "DORMANT",
     };
+    format!("Mode: {mode_str}")
+}
 
-    vec![
-        format!("E:[{bar}] {pct:>3}%"),
-        format!("Mode: {mode_str}"),
-        format!("PE:{prediction_error:>6.2}  \u{03C1}:{precision:.2}"),
-        format!("v:{speed:>4.1}  \u{03B8}:{angle_deg:>4.0}\u{00B0}"),
-        format!("L:{sensor_left:.2}  R:{sensor_right:.2}"),
-        format!("\u{2202}t:{temporal_gradient:>6.2}"),
-    ]
+fn format_inference_line(prediction_error: f64, precision: f64) -> String {
+    format!("PE:{prediction_error:>6.2}  \u{03C1}:{precision:.2}")
+}
+
+fn format_motion_line(speed: f64, angle_deg: f64) -> String {
+    format!("v:{speed:>4.1}  \u{03B8}:{angle_deg:>4.0}\u{00B0}")
+}
+
+fn format_sensor_line(sensor_left: f64, sensor_right: f64) -> String {
+    format!("L:{sensor_left:.2}  R:{sensor_right:.2}")
+}
+
+fn format_gradient_line(temporal_gradient: f64) -> String {
+    format!("\u{2202}t:{temporal_gradient:>6.2}")
+}
+
+/// Formats the share of the slow energy reserve held, e.g. `R: 40%`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn format_reserve_line(fraction: f64) -> String {
+    format!("R:{:>3}%", (fraction * 100.0).round() as i32)
+}
+
+/// Formats the agent's structural integrity, e.g. `H: 90%`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn format_health_line(health: f64) -> String {
+    format!("H:{:>3}%", (health * 100.0).round() as i32)
+}
+
+/// Formats the time-to-starvation forecast, e.g. `T-340 (120)`: ticks left
+/// at the current net rate, then assuming no further intake. `∞` marks no
+/// decline (or no estimate yet); long horizons are shown in thousands.
+#[must_use]
+pub fn format_starvation_line(ticks: Option<f64>, fasting: Option<f64>) -> String {
+    format!(
+        "T-{} ({})",
+        format_ticks_left(ticks),
+        format_ticks_left(fasting)
+    )
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn format_ticks_left(ticks: Option<f64>) -> String {
+    match ticks {
+        Some(t) if t < 1000.0 => format!("{}", t.floor() as i64),
+        Some(t) if t < 100_000.0 => format!("{}k", (t / 1000.0).floor() as i64),
+        _ => "\u{221E}".to_string(),
+    }
+}
+
+/// Formats the external goal status line: target, progress and distance.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn format_goal_line(goal: Option<&Goal>) -> String {
+    match goal {
+        Some(g) => format!(
+            "Goal:({:>3},{:>3}) {:>3.0}% d:{:.1}",
+            g.x as i32,
+            g.y as i32,
+            g.progress() * 100.0,
+            g.distance
+        ),
+        None => "Goal: none".to_string(),
+    }
+}
+
+/// Formats the learned likelihood parameters, e.g. `k:0.24 β:+0.01/-0.01`:
+/// the gain of the lateral term and the left/right offsets.
+#[must_use]
+pub fn format_likelihood_line(likelihood: &Likelihood) -> String {
+    format!(
+        "k:{:.2} \u{03B2}:{:+.2}/{:+.2}",
+        likelihood.gain, likelihood.bias_left, likelihood.bias_right
+    )
 }
 
 /// ASCII density characters for heat map visualization (low to high).
@@ -345,6 +652,73 @@ pub fn render_spatial_grid_lines(
     lines
 }
 
+/// The particle cloud as a `width` × `height` map of the dish: each cell
+/// shaded by the weight of its particles relative to the heaviest cell
+/// (` .:*#`), the agent at `agent` drawn as `@`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::cast_precision_loss)]
+pub fn render_particle_lines(
+    cloud: &ParticleCloud,
+    width: usize,
+    height: usize,
+    agent: (f64, f64),
+) -> Vec<String> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let cell = |x: f64, y: f64| {
+        let col = (x / cloud.width * width as f64).floor().max(0.0) as usize;
+        let row = (y / cloud.height * height as f64).floor().max(0.0) as usize;
+        (row.min(height - 1), col.min(width - 1))
+    };
+    let mut weights = vec![0.0; width * height];
+    for &(x, y, weight) in &cloud.points {
+        let (row, col) = cell(x, y);
+        weights[row * width + col] += weight;
+    }
+    let heaviest = weights.iter().copied().fold(0.0, f64::max);
+    let agent_cell = cell(agent.0, agent.1);
+
+    (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| {
+                    let share = weights[row * width + col] / heaviest.max(f64::EPSILON);
+                    if (row, col) == agent_cell {
+                        '@'
+                    } else if share <= 0.0 {
+                        ' '
+                    } else if share < 0.25 {
+                        '.'
+                    } else if share < 0.5 {
+                        ':'
+                    } else if share < 0.75 {
+                        '*'
+                    } else {
+                        '#'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Repeats every character of `lines` `across` times and every line `down` times.
+#[must_use]
+pub fn upscale_grid_lines(lines: &[String], across: usize, down: usize) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            line.chars()
+                .flat_map(|ch| std::iter::repeat_n(ch, across))
+                .collect::<String>()
+        })
+        .flat_map(|line| std::iter::repeat_n(line, down))
+        .collect()
+}
+
 /// Direction arrow for an action based on base angle.
 #[allow(dead_code)] // Used by format_mcts_summary
 #[allow(clippy::cast_possible_truncation)]
@@ -373,13 +747,44 @@ fn action_to_name(action: Action) -> &'static str {
         Action::TurnLeft => "L",
         Action::Straight => "S",
         Action::TurnRight => "R",
+        Action::Accelerate => "+",
+        Action::Decelerate => "-",
+        Action::Stop => "0",
+    }
+}
+
+/// Formats the day/night indicator, e.g. `Night 35%`.
+#[must_use]
+pub fn format_daylight(level: f64, night: bool) -> String {
+    let phase = if night { "Night" } else { "Day" };
+    format!("{phase} {:.0}%", 100.0 * level)
+}
+
+/// Formats a byte count with a binary unit (`512 B`, `41.2 KiB`, `3.0 MiB`).
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // Display only
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
     }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
 }
 
-/// Formats MCTS planning summary text.
+/// Formats MCTS planning summary text; rollouts and depth are out of `budget`.
 #[must_use]
 #[allow(dead_code)] // Used by tests and will be used by dashboard renderer
-pub fn format_mcts_summary(details: &[ActionDetail], ticks_until_replan: u64) -> Vec<String> {
+pub fn format_mcts_summary(
+    details: &[ActionDetail],
+    budget: PlanningBudget,
+    ticks_until_replan: u64,
+) -> Vec<String> {
     // Find best action (highest EFE)
     let best = details
         .iter()
@@ -395,8 +800,8 @@ pub fn format_mcts_summary(details: &[ActionDetail], ticks_until_replan: u64) ->
             format!("G: {:.2}", best.total_efe),
             format!("├─Prag: {:.2}", best.pragmatic_value),
             format!("└─Epis: {:.2}", best.epistemic_value),
-            format!("Rolls: {}", MCTS_ROLLOUTS),
-            format!("Depth: {}", MCTS_DEPTH),
+            format!("Rolls: {}/{}", best.fresh_rollouts, budget.rollouts),
+            format!("Depth: {}", budget.depth),
             format!("Replan: {}", ticks_until_replan),
         ]
     } else {
@@ -404,6 +809,15 @@ pub fn format_mcts_summary(details: &[ActionDetail], ticks_until_replan: u64) ->
     }
 }
 
+/// Formats the largest heading term of the last decision (e.g. `plan +0.10`),
+/// or `None` before the first decision or when nothing turned the agent.
+#[must_use]
+pub fn format_driver(provenance: &Provenance) -> Option<String> {
+    provenance
+        .dominant()
+        .map(|(term, value)| format!("{} {value:+.2}", term.name()))
+}
+
 /// Formats landmarks as a list table.
 #[must_use]
 #[allow(dead_code)] // Used by tests and will be used by dashboard renderer
@@ -419,11 +833,13 @@ pub fn format_landmarks_list(
 
     for (i, lm) in landmarks.iter().enumerate() {
         let prefix = if nav_target == Some(i) { "→" } else { " " };
+        let pin = if lm.pinned { '*' } else { ' ' };
         let reliability = format!("{:>4.2}", lm.reliability.clamp(0.0, 1.0));
         lines.push(format!(
-            "{}{} │({:>3},{:>3})│{}│ {}",
+            "{}{}{}│({:>3},{:>3})│{}│ {}",
             prefix,
             i + 1,
+            pin,
             lm.x as i32,
             lm.y as i32,
             reliability,
@@ -494,9 +910,25 @@ pub fn world_to_grid_coords(
     (r, c)
 }
 
+/// Maps a terminal cell inside the petri dish panel to world coordinates.
+///
+/// `area` is the full frame area; returns `None` for cells outside the field.
+#[must_use]
+#[allow(dead_code)] // Sidebar layout only; used by tests
+pub fn screen_to_world(
+    area: Rect,
+    column: u16,
+    row: u16,
+    width: f64,
+    height: f64,
+) -> Option<(f64, f64)> {
+    DashboardLayout::for_area(area).screen_to_world(column, row, width, height)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::layout::LayoutKind;
 
     #[test]
     fn test_draw_metrics_panel_renders_without_panic() {
@@ -515,6 +947,10 @@ mod tests {
             angle: 1.0,
             speed: 0.5,
             energy: 0.8,
+            reserve: 0.4,
+            health: 0.9,
+            starvation_ticks: Some(340.0),
+            fasting_ticks: Some(120.0),
             mode: AgentMode::Exploring,
             prediction_error: -0.2,
             precision: 5.0,
@@ -525,10 +961,34 @@ mod tests {
             grid_width: 20,
             grid_height: 10,
             plan_details: vec![],
+            planning_budget: PlanningBudget::FIXED,
             ticks_until_replan: 15,
             landmarks: vec![],
             landmark_count: 0,
             nav_target_index: None,
+            gradient_alignment: None,
+            layer: DishLayer::default(),
+            pin_cursor: None,
+            command_line: None,
+            dish_label: None,
+            agent_label: None,
+            frame_status: None,
+            glyph_tints: Vec::new(),
+            goal: None,
+            arrivals: Vec::new(),
+            arrival_count: 0,
+            mean_travel_ticks: None,
+            source_events: Vec::new(),
+            source_counts: (0, 0, 0),
+            daylight: None,
+            night: false,
+            season: None,
+            memory_bytes: 0,
+            layout: LayoutKind::default(),
+            controller: ControllerKind::default(),
+            provenance: Provenance::default(),
+            particles: None,
+            likelihood: None,
         };
 
         terminal
@@ -558,7 +1018,7 @@ mod tests {
         assert_eq!(main.x, 0);
 
         // Sidebar should be ~30% width
-        assert!(sidebar.len() == 4, "should have 4 sidebar panels");
+        assert!(sidebar.len() == 5, "should have 5 sidebar panels");
         assert!(
             sidebar[0].width >= 28 && sidebar[0].width <= 32,
             "sidebar width: {}",
@@ -606,10 +1066,9 @@ mod tests {
 
         // 4x2 grid, target width 4 (no compression)
         let cells: Vec<CellPrior> = (0..8)
-            .map(|i| {
-                let mut c = CellPrior::default();
-                c.mean = i as f64 * 0.1;
-                c
+            .map(|i| CellPrior {
+                mean: f64::from(i) * 0.1,
+                ..CellPrior::default()
             })
             .collect();
 
@@ -629,9 +1088,10 @@ mod tests {
         let mut cells = Vec::new();
         for row in 0..2 {
             for col in 0..4 {
-                let mut c = CellPrior::default();
-                c.mean = (row * 4 + col) as f64 * 0.2;
-                cells.push(c);
+                cells.push(CellPrior {
+                    mean: f64::from(row * 4 + col) * 0.2,
+                    ..CellPrior::default()
+                });
             }
         }
 
@@ -668,6 +1128,10 @@ mod tests {
             angle: 1.0,
             speed: 0.5,
             energy: 0.8,
+            reserve: 0.4,
+            health: 0.9,
+            starvation_ticks: Some(340.0),
+            fasting_ticks: Some(120.0),
             mode: AgentMode::Exploring,
             prediction_error: -0.2,
             precision: 5.0,
@@ -678,10 +1142,34 @@ mod tests {
             grid_width: 20,
             grid_height: 10,
             plan_details: vec![],
+            planning_budget: PlanningBudget::FIXED,
             ticks_until_replan: 15,
             landmarks: vec![],
             landmark_count: 0,
             nav_target_index: None,
+            gradient_alignment: None,
+            layer: DishLayer::default(),
+            pin_cursor: None,
+            command_line: None,
+            dish_label: None,
+            agent_label: None,
+            frame_status: None,
+            glyph_tints: Vec::new(),
+            goal: None,
+            arrivals: Vec::new(),
+            arrival_count: 0,
+            mean_travel_ticks: None,
+            source_events: Vec::new(),
+            source_counts: (0, 0, 0),
+            daylight: None,
+            night: false,
+            season: None,
+            memory_bytes: 0,
+            layout: LayoutKind::default(),
+            controller: ControllerKind::default(),
+            provenance: Provenance::default(),
+            particles: None,
+            likelihood: None,
         };
 
         // Should not panic even with narrow width
@@ -710,6 +1198,10 @@ mod tests {
             angle: 1.0,
             speed: 0.5,
             energy: 0.8,
+            reserve: 0.4,
+            health: 0.9,
+            starvation_ticks: Some(340.0),
+            fasting_ticks: Some(120.0),
             mode: AgentMode::Exploring,
             prediction_error: -0.2,
             precision: 5.0,
@@ -720,10 +1212,34 @@ mod tests {
             grid_width: 20,
             grid_height: 10,
             plan_details: vec![],
+            planning_budget: PlanningBudget::FIXED,
             ticks_until_replan: 15,
             landmarks: vec![],
             landmark_count: 0,
             nav_target_index: None,
+            gradient_alignment: None,
+            layer: DishLayer::default(),
+            pin_cursor: None,
+            command_line: None,
+            dish_label: None,
+            agent_label: None,
+            frame_status: None,
+            glyph_tints: Vec::new(),
+            goal: None,
+            arrivals: Vec::new(),
+            arrival_count: 0,
+            mean_travel_ticks: None,
+            source_events: Vec::new(),
+            source_counts: (0, 0, 0),
+            daylight: None,
+            night: false,
+            season: None,
+            memory_bytes: 0,
+            layout: LayoutKind::default(),
+            controller: ControllerKind::default(),
+            provenance: Provenance::default(),
+            particles: None,
+            likelihood: None,
         };
 
         let grid_lines: Vec<String> = (0..30).map(|_| ".".repeat(60)).collect();
@@ -738,13 +1254,21 @@ mod tests {
         let buffer = terminal.backend().buffer();
 
         // Check "Petri Dish" title is in top-left area
-        let petri_title_found =
-            (0..20).any(|x| buffer.cell((x, 0)).map(|c| c.symbol()).unwrap_or("") == "P");
+        let petri_title_found = (0..20).any(|x| {
+            buffer
+                .cell((x, 0))
+                .map_or("", ratatui::buffer::Cell::symbol)
+                == "P"
+        });
         assert!(petri_title_found, "Petri Dish title should be on left side");
 
         // Check "Agent" title is in right sidebar area (x > 60)
-        let agent_title_found =
-            (60..100).any(|x| buffer.cell((x, 0)).map(|c| c.symbol()).unwrap_or("") == "A");
+        let agent_title_found = (60..100).any(|x| {
+            buffer
+                .cell((x, 0))
+                .map_or("", ratatui::buffer::Cell::symbol)
+                == "A"
+        });
         assert!(
             agent_title_found,
             "Agent panel title should be on right side"
@@ -0,0 +1,225 @@
+//! Timeline scrubber for the replay view.
+//!
+//! `Scrubber` is the playback state of a `Recording`: the shown tick,
+//! play/pause, speed (one of `REPLAY_SPEEDS`) and an optional loop region.
+//! `draw_scrubber` renders it under the dashboard as a timeline bar with
+//! the recording's events marked on it.
+
+use crate::simulation::params::REPLAY_SPEEDS;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+use std::fmt::Write as _;
+
+/// Playback position, speed and loop region of a replay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scrubber {
+    /// Tick shown
+    pub tick: u64,
+    /// Last tick of the recording
+    pub length: u64,
+    pub playing: bool,
+    /// Index into `REPLAY_SPEEDS`
+    speed_index: usize,
+    /// Fraction of a tick owed by playback slower than one tick per frame
+    carry: f64,
+    /// Inclusive `(start, end)` ticks playback loops over
+    pub loop_region: Option<(u64, u64)>,
+}
+
+impl Scrubber {
+    /// Playing from tick 0 at one tick per frame.
+    #[must_use]
+    pub fn new(length: u64) -> Self {
+        Self {
+            tick: 0,
+            length,
+            playing: true,
+            speed_index: REPLAY_SPEEDS
+                .iter()
+                .position(|&s| (s - 1.0).abs() < f64::EPSILON)
+                .unwrap_or(0),
+            carry: 0.0,
+            loop_region: None,
+        }
+    }
+
+    /// Ticks advanced per frame while playing.
+    #[must_use]
+    pub fn speed(&self) -> f64 {
+        REPLAY_SPEEDS[self.speed_index]
+    }
+
+    /// Next faster speed (stays at the fastest).
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(REPLAY_SPEEDS.len() - 1);
+    }
+
+    /// Next slower speed (stays at the slowest).
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    /// Pauses or resumes; resuming at the end of a loopless recording
+    /// restarts it.
+    pub fn toggle_play(&mut self) {
+        self.playing = !self.playing;
+        if self.playing && self.loop_region.is_none() && self.tick >= self.length {
+            self.tick = 0;
+        }
+    }
+
+    /// Moves to `tick` (clamped to the recording).
+    pub fn seek(&mut self, tick: u64) {
+        self.tick = tick.min(self.length);
+        self.carry = 0.0;
+    }
+
+    /// Moves `delta` ticks back or forward and pauses.
+    pub fn step_by(&mut self, delta: i64) {
+        self.playing = false;
+        self.seek(self.tick.saturating_add_signed(delta));
+    }
+
+    /// Advances one frame of playback and returns the tick to show. Past
+    /// the loop region's end playback wraps to its start; without a loop it
+    /// pauses at the end.
+    #[allow(clippy::cast_possible_truncation)] // Whole ticks of a small carry
+    #[allow(clippy::cast_sign_loss)]
+    pub fn advance(&mut self) -> u64 {
+        if !self.playing {
+            return self.tick;
+        }
+        self.carry += self.speed();
+        let whole = self.carry.floor();
+        self.carry -= whole;
+        let target = self.tick + whole as u64;
+        match self.loop_region {
+            Some((start, end)) if self.tick <= end && target > end => self.tick = start,
+            _ if target >= self.length => {
+                self.tick = self.length;
+                self.playing = false;
+            }
+            _ => self.tick = target,
+        }
+        self.tick
+    }
+
+    /// Starts the loop region at the current tick (ending at the old end if
+    /// that is still later, else at the end of the recording).
+    pub fn set_loop_start(&mut self) {
+        let end = self
+            .loop_region
+            .map_or(self.length, |(_, end)| end)
+            .max(self.tick);
+        self.loop_region = Some((self.tick, end));
+    }
+
+    /// Ends the loop region at the current tick (starting at the old start
+    /// if that is still earlier, else at tick 0).
+    pub fn set_loop_end(&mut self) {
+        let start = self
+            .loop_region
+            .map_or(0, |(start, _)| start)
+            .min(self.tick);
+        self.loop_region = Some((start, self.tick));
+    }
+
+    /// Removes the loop region.
+    pub fn clear_loop(&mut self) {
+        self.loop_region = None;
+    }
+
+    /// Tick under `column` of a timeline `width` columns wide.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Recordings are far shorter than 2^52 ticks
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn tick_at_column(&self, column: u16, width: u16) -> u64 {
+        if width < 2 {
+            return 0;
+        }
+        let fraction = f64::from(column.min(width - 1)) / f64::from(width - 1);
+        (fraction * self.length as f64).round() as u64
+    }
+
+    /// Column of a timeline `width` columns wide that shows `tick`.
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn column_of(&self, tick: u64, width: usize) -> usize {
+        if self.length == 0 || width < 2 {
+            return 0;
+        }
+        ((tick.min(self.length) as f64 / self.length as f64) * (width - 1) as f64).round() as usize
+    }
+
+    /// Timeline bar `width` columns wide: `=` played, `-` ahead, `*` an
+    /// event in `event_ticks`, `[` `]` the loop region and `|` the tick shown.
+    #[must_use]
+    pub fn timeline(&self, width: usize, event_ticks: &[u64]) -> String {
+        let position = self.column_of(self.tick, width);
+        let mut cells: Vec<char> = (0..width)
+            .map(|c| if c <= position { '=' } else { '-' })
+            .collect();
+        for &tick in event_ticks {
+            if let Some(cell) = cells.get_mut(self.column_of(tick, width)) {
+                *cell = '*';
+            }
+        }
+        if let Some((start, end)) = self.loop_region {
+            if let Some(cell) = cells.get_mut(self.column_of(start, width)) {
+                *cell = '[';
+            }
+            if let Some(cell) = cells.get_mut(self.column_of(end, width)) {
+                *cell = ']';
+            }
+        }
+        if let Some(cell) = cells.get_mut(position) {
+            *cell = '|';
+        }
+        cells.into_iter().collect()
+    }
+
+    /// Status line: play state, speed, tick and loop region.
+    #[must_use]
+    pub fn status(&self) -> String {
+        let state = if self.playing { "PLAY " } else { "PAUSE" };
+        let mut line = format!(
+            "{state} {}x  tick {}/{}",
+            self.speed(),
+            self.tick,
+            self.length
+        );
+        if let Some((start, end)) = self.loop_region {
+            let _ = write!(line, "  loop {start}-{end}");
+        }
+        line
+    }
+}
+
+/// Draws the scrubber in `area`: the timeline bar, then the status line
+/// followed by `note` (e.g. the next event).
+pub fn draw_scrubber(
+    f: &mut Frame,
+    area: Rect,
+    scrubber: &Scrubber,
+    event_ticks: &[u64],
+    note: Option<&str>,
+) {
+    let block = Block::default().title(" Replay ").borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let status = match note {
+        Some(note) => format!("{}  {note}", scrubber.status()),
+        None => scrubber.status(),
+    };
+    let lines = vec![
+        Line::from(scrubber.timeline(inner.width as usize, event_ticks)),
+        Line::from(status),
+    ];
+    f.render_widget(Paragraph::new(lines), inner);
+}
@@ -0,0 +1,446 @@
+//! Terminal capability detection and renderer selection.
+//!
+//! `TerminalCaps::detect()` reads what the terminal supports from the
+//! environment (`TERM`, `COLORTERM`, `TERM_PROGRAM`, the locale, `NO_COLOR`
+//! and terminal-specific variables) without escape-sequence round trips, so it
+//! is safe before raw mode. `RenderConfig::from_caps()` picks the richest glyph
+//! set and color mode the terminal handles, and `RenderOverrides` (a TOML file
+//! passed with `--render-config`, else `default_path()` when it exists) forces
+//! any of them:
+//!
+//! ```toml
+//! glyphs = "ascii"   # or "unicode"
+//! color = "ansi16"   # or "truecolor", "mono"
+//! mouse = false
+//! layout = "quadrant" # or "sidebar"; written back when `v` toggles it
+//! ```
+//!
+//! The widgets always draw their Unicode glyphs and full colors;
+//! `adapt_buffer()` then rewrites each finished frame to the chosen
+//! configuration, so no drawing code needs a fallback path.
+
+use crate::simulation::agent::Protozoa;
+use crate::simulation::doctor::Check;
+use crate::simulation::environment::PetriDish;
+use crate::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use crate::ui::DashboardState;
+use crate::ui::field::compute_field_grid;
+use crate::ui::layout::{DashboardLayout, LayoutKind};
+use crate::ui::render::draw_dashboard;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Inline image protocol the terminal understands.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    #[default]
+    None,
+    Kitty,
+    Sixel,
+}
+
+/// What the terminal supports, as far as the environment tells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // Independent yes/no capabilities
+pub struct TerminalCaps {
+    /// Any color at all (false for dumb terminals and under `NO_COLOR`)
+    pub color: bool,
+    /// 24-bit RGB colors
+    pub truecolor: bool,
+    /// UTF-8 locale and a font with box-drawing, arrow and braille glyphs
+    pub unicode: bool,
+    /// Mouse reporting
+    pub mouse: bool,
+    /// Inline image protocol (reported only; the dashboard is text)
+    pub graphics: GraphicsProtocol,
+}
+
+impl TerminalCaps {
+    /// Capabilities of the terminal the process runs in.
+    #[must_use]
+    pub fn detect() -> Self {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Capabilities implied by the environment variables `var` returns.
+    #[must_use]
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let get = |name: &str| {
+            var(name)
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_ascii_lowercase())
+        };
+        let term = get("TERM").unwrap_or_default();
+        let program = get("TERM_PROGRAM").unwrap_or_default();
+        let colorterm = get("COLORTERM").unwrap_or_default();
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .find_map(get)
+            .unwrap_or_default();
+
+        // Windows Terminal sets no TERM but handles everything
+        let windows_terminal = get("WT_SESSION").is_some();
+        let dumb = !windows_terminal && (term.is_empty() || term == "dumb");
+        // The Linux console decodes UTF-8 but its font lacks most of our glyphs
+        let console = term == "linux";
+
+        let color = !dumb && get("NO_COLOR").is_none();
+        let truecolor = color
+            && (matches!(colorterm.as_str(), "truecolor" | "24bit")
+                || term.ends_with("-direct")
+                || term == "xterm-kitty"
+                || windows_terminal
+                || matches!(program.as_str(), "iterm.app" | "wezterm" | "vscode"));
+        let unicode = windows_terminal
+            || (!dumb && !console && (locale.contains("utf-8") || locale.contains("utf8")));
+        let graphics = if term == "xterm-kitty"
+            || get("KITTY_WINDOW_ID").is_some()
+            || matches!(program.as_str(), "wezterm" | "ghostty")
+        {
+            GraphicsProtocol::Kitty
+        } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+            GraphicsProtocol::Sixel
+        } else {
+            GraphicsProtocol::None
+        };
+
+        Self {
+            color,
+            truecolor,
+            unicode,
+            mouse: !dumb && !console,
+            graphics,
+        }
+    }
+}
+
+impl fmt::Display for TerminalCaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+        write!(
+            f,
+            "color: {}, truecolor: {}, unicode: {}, mouse: {}, graphics: {:?}",
+            yes_no(self.color),
+            yes_no(self.truecolor),
+            yes_no(self.unicode),
+            yes_no(self.mouse),
+            self.graphics
+        )
+    }
+}
+
+/// Glyphs the renderer may emit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GlyphSet {
+    /// Box drawing, arrows, blocks and braille
+    #[default]
+    Unicode,
+    /// ASCII look-alikes only
+    Ascii,
+}
+
+/// Colors the renderer may emit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Colors as drawn, RGB included
+    #[default]
+    TrueColor,
+    /// RGB and 256-palette colors reduced to the 16 ANSI colors
+    Ansi16,
+    /// Terminal default colors only
+    Mono,
+}
+
+/// Renderer configuration applied to every frame (see `adapt_buffer`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderConfig {
+    pub glyphs: GlyphSet,
+    pub color: ColorMode,
+    /// Capture the mouse (needed for click-to-pin)
+    pub mouse: bool,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            glyphs: GlyphSet::Unicode,
+            color: ColorMode::TrueColor,
+            mouse: true,
+        }
+    }
+}
+
+impl RenderConfig {
+    /// Best configuration the terminal supports.
+    #[must_use]
+    pub fn from_caps(caps: TerminalCaps) -> Self {
+        Self {
+            glyphs: if caps.unicode {
+                GlyphSet::Unicode
+            } else {
+                GlyphSet::Ascii
+            },
+            color: match (caps.color, caps.truecolor) {
+                (false, _) => ColorMode::Mono,
+                (true, false) => ColorMode::Ansi16,
+                (true, true) => ColorMode::TrueColor,
+            },
+            mouse: caps.mouse,
+        }
+    }
+
+    /// This configuration with every set override applied.
+    #[must_use]
+    pub fn with_overrides(self, overrides: RenderOverrides) -> Self {
+        Self {
+            glyphs: overrides.glyphs.unwrap_or(self.glyphs),
+            color: overrides.color.unwrap_or(self.color),
+            mouse: overrides.mouse.unwrap_or(self.mouse),
+        }
+    }
+}
+
+/// User overrides for the detected configuration (unset keys keep it).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RenderOverrides {
+    pub glyphs: Option<GlyphSet>,
+    pub color: Option<ColorMode>,
+    pub mouse: Option<bool>,
+    /// Dashboard layout (not part of `RenderConfig`; the dashboard reads it directly)
+    pub layout: Option<LayoutKind>,
+}
+
+impl RenderOverrides {
+    /// Parses a TOML document.
+    ///
+    /// # Errors
+    /// Returns a message for malformed TOML, unknown keys or invalid values.
+    pub fn from_toml_str(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("invalid render config: {e}"))
+    }
+
+    /// Reads a TOML file (see `from_toml_str`).
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be read or is not a valid config.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Writes these overrides to `path` as TOML, creating its directory.
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be written.
+    pub fn save(self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text =
+            toml::to_string(&self).map_err(|e| format!("cannot encode render config: {e}"))?;
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+        }
+        std::fs::write(path, text).map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+
+    /// Sets the layout in the config at `path`, keeping its other keys
+    /// (the file is created if missing).
+    ///
+    /// # Errors
+    /// Returns a message if an existing file is invalid or the file cannot be written.
+    pub fn save_layout(path: impl AsRef<Path>, layout: LayoutKind) -> Result<(), String> {
+        let path = path.as_ref();
+        let mut overrides = if path.exists() {
+            Self::load(path)?
+        } else {
+            Self::default()
+        };
+        overrides.layout = Some(layout);
+        overrides.save(path)
+    }
+
+    /// Per-user config file: `$XDG_CONFIG_HOME/protozoa_rust/render.toml`,
+    /// else `$HOME/.config/protozoa_rust/render.toml`.
+    #[must_use]
+    pub fn default_path(var: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+        let get = |name: &str| var(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+        get("XDG_CONFIG_HOME")
+            .or_else(|| get("HOME").map(|home| home.join(".config")))
+            .map(|dir| dir.join("protozoa_rust").join("render.toml"))
+    }
+}
+
+/// Rewrites a drawn frame to `config`: non-ASCII glyphs become ASCII
+/// look-alikes and colors are reduced to what the color mode allows.
+pub fn adapt_buffer(buf: &mut Buffer, config: RenderConfig) {
+    if config == RenderConfig::default() {
+        return;
+    }
+    for cell in &mut buf.content {
+        if config.glyphs == GlyphSet::Ascii && !cell.symbol().is_ascii() {
+            let glyph = cell.symbol().chars().next().map_or(' ', ascii_fallback);
+            cell.set_char(glyph);
+        }
+        cell.fg = reduce_color(cell.fg, config.color);
+        cell.bg = reduce_color(cell.bg, config.color);
+    }
+}
+
+/// Terminal sizes the renderer smoke test draws at, down to one too small
+/// for most panels.
+const SMOKE_SIZES: [(u16, u16); 4] = [(80, 24), (120, 40), (300, 90), (20, 6)];
+
+/// Draws the dashboard on a virtual terminal at each `SMOKE_SIZES` size, in
+/// both layouts and three render configurations, and checks that nothing
+/// panics and that ASCII mode leaves only ASCII glyphs.
+#[must_use]
+pub fn renderer_smoke_test() -> Check {
+    let configs = [
+        RenderConfig::default(),
+        RenderConfig {
+            glyphs: GlyphSet::Ascii,
+            color: ColorMode::Ansi16,
+            mouse: false,
+        },
+        RenderConfig {
+            color: ColorMode::Mono,
+            ..RenderConfig::default()
+        },
+    ];
+    Check::guarded("renderer", || {
+        let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+        let mut state = DashboardState::from_agent(&Protozoa::new(50.0, 25.0), &dish);
+        let mut frames = 0;
+        for (width, height) in SMOKE_SIZES {
+            for layout in [LayoutKind::Sidebar, LayoutKind::Quadrant] {
+                state.layout = layout;
+                let area = ratatui::layout::Rect::new(0, 0, width, height);
+                let (rows, cols) = DashboardLayout::new(layout, area).field_size();
+                for config in configs {
+                    let mut terminal = Terminal::new(TestBackend::new(width, height))
+                        .map_err(|e| e.to_string())?;
+                    terminal
+                        .draw(|f| {
+                            draw_dashboard(f, compute_field_grid(&dish, rows, cols), &state);
+                            adapt_buffer(f.buffer_mut(), config);
+                        })
+                        .map_err(|e| e.to_string())?;
+                    let ascii = terminal
+                        .backend()
+                        .buffer()
+                        .content
+                        .iter()
+                        .all(|cell| cell.symbol().is_ascii());
+                    if config.glyphs == GlyphSet::Ascii && !ascii {
+                        return Err(format!("non-ASCII glyph at {width}x{height} ({layout:?})"));
+                    }
+                    frames += 1;
+                }
+            }
+        }
+        Ok(format!(
+            "{frames} frames drawn at {} sizes",
+            SMOKE_SIZES.len()
+        ))
+    })
+}
+
+/// ASCII look-alike of a Unicode glyph (`?` for unknown ones).
+#[must_use]
+pub fn ascii_fallback(glyph: char) -> char {
+    match glyph {
+        '─' | '━' | '═' => '-',
+        '│' | '┃' | '║' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╰' | '╯' => {
+            '+'
+        }
+        '→' => '>',
+        '←' => '<',
+        '↑' => '^',
+        '↓' => 'v',
+        '↗' | '↙' => '/',
+        '↖' | '↘' => '\\',
+        '○' | '●' => 'o',
+        '±' => '~',
+        // Sparse blocks, bullets and braille dots
+        '•' | '·' | '▁'..='▃' | '\u{2801}'..='\u{28FF}' => '.',
+        '▒' | '░' | '▄'..='▆' => ':',
+        // Solid blocks; walls use `#`, as in ASCII layouts
+        '█' | '▓' | '▇' => '#',
+        '\u{2800}' => ' ',
+        _ => '?',
+    }
+}
+
+/// `color` as allowed by `mode`.
+#[must_use]
+pub fn reduce_color(color: Color, mode: ColorMode) -> Color {
+    match (mode, color) {
+        (ColorMode::TrueColor, _) | (_, Color::Reset) => color,
+        (ColorMode::Mono, _) => Color::Reset,
+        (ColorMode::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi16(r, g, b),
+        (ColorMode::Ansi16, Color::Indexed(i)) => {
+            let (r, g, b) = palette_rgb(i);
+            nearest_ansi16(r, g, b)
+        }
+        (ColorMode::Ansi16, named) => named,
+    }
+}
+
+/// The 16 ANSI colors with their usual (xterm) RGB values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// ANSI color closest to (r, g, b) in squared RGB distance.
+fn nearest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    let distance = |&(_, (cr, cg, cb)): &(Color, (u8, u8, u8))| {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        d(r, cr) + d(g, cg) + d(b, cb)
+    };
+    ANSI16
+        .iter()
+        .min_by_key(|entry| distance(entry))
+        .map_or(Color::Reset, |&(color, _)| color)
+}
+
+/// RGB value of an xterm 256-palette index.
+fn palette_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[usize::from(index)].1,
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + 40 * v };
+            let i = index - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        232..=255 => {
+            let v = 8 + 10 * (index - 232);
+            (v, v, v)
+        }
+    }
+}
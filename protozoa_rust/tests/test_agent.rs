@@ -205,31 +205,21 @@ fn test_temporal_gradient_tracking() {
 }
 
 #[test]
-fn test_speed_proportional_to_error() {
-    let mut agent = Protozoa::new(50.0, 50.0);
+fn test_speed_follows_the_planned_action() {
     let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
-
-    // Target is 0.8, so if mean_sense = 0.0, error = -0.8
+    let mut agent = Protozoa::new(50.0, 25.0);
     agent.val_l = 0.0;
     agent.val_r = 0.0;
+    let cruise = agent.cruise_speed;
     agent.update_state(&dish);
-    let speed_high_error = agent.speed;
 
-    // Reset and test with lower error
-    let mut agent2 = Protozoa::new(50.0, 50.0);
-    // If mean_sense = 0.7, error = 0.7 - 0.8 = -0.1
-    agent2.val_l = 0.7;
-    agent2.val_r = 0.7;
-    agent2.update_state(&dish);
-    let speed_low_error = agent2.speed;
-
-    // Higher error should result in higher speed
-    assert!(
-        speed_high_error > speed_low_error,
-        "Speed with high error ({}) should be > speed with low error ({})",
-        speed_high_error,
-        speed_low_error
+    // The plan step's speed action sets the commanded speed
+    assert_float_eq(
+        agent.cruise_speed,
+        agent.planned_action.next_speed(cruise),
+        "cruise speed",
     );
+    assert_float_eq(agent.speed, agent.cruise_speed, "speed");
 }
 
 #[test]
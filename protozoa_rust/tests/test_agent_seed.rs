@@ -0,0 +1,105 @@
+//! Tests for per-agent seeds: every agent owns the master seed of its
+//! random streams, so its run can be reproduced from it.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+use protozoa_rust::simulation::streams::{AgentStreams, Stream};
+use protozoa_rust::simulation::world::World;
+use rand::Rng;
+
+fn ring() -> PetriDish {
+    PetriDish::from_preset(EnvironmentPreset::Ring, DISH_WIDTH, DISH_HEIGHT)
+}
+
+/// Path of `agent` over `ticks` ticks in `dish`.
+fn path(mut agent: Protozoa, dish: &PetriDish, ticks: usize) -> Vec<(f64, f64, f64)> {
+    (0..ticks)
+        .map(|_| {
+            agent.sense(dish);
+            agent.update_state(dish);
+            (agent.x, agent.y, agent.angle)
+        })
+        .collect()
+}
+
+#[test]
+fn test_agents_own_their_seed() {
+    let agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut fresh = AgentStreams::from_seed(agent.seed);
+    let mut streams = agent.streams.clone();
+    assert_eq!(
+        streams.exploration.random::<u64>(),
+        fresh.exploration.random::<u64>()
+    );
+    assert_eq!(streams.motor.random::<u64>(), fresh.motor.random::<u64>());
+    // Every agent draws its own
+    assert_ne!(agent.seed, Protozoa::with_heading(50.0, 25.0, 0.0).seed);
+}
+
+#[test]
+fn test_reseeding_records_the_seed() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(17);
+    assert_eq!(agent.seed, 17);
+    let built = Protozoa::builder().position(50.0, 25.0).seed(17).build();
+    assert_eq!(built.seed, 17);
+}
+
+#[test]
+fn test_same_seed_same_behavior() {
+    let dish = ring();
+    let make = || {
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(8);
+        agent
+    };
+    assert_eq!(path(make(), &dish, 80), path(make(), &dish, 80));
+}
+
+#[test]
+fn test_unseeded_run_replays_from_its_seed() {
+    let dish = ring();
+    // The planner's rollouts come from the drawn seed as well
+    let original = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut replayed = Protozoa::with_heading(50.0, 25.0, 0.0);
+    replayed.seed_streams(original.seed);
+    assert_eq!(path(original, &dish, 80), path(replayed, &dish, 80));
+}
+
+#[test]
+fn test_different_seeds_diverge() {
+    let dish = ring();
+    let seeded = |seed| {
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(seed);
+        path(agent, &dish, 80)
+    };
+    assert_ne!(seeded(1), seeded(2));
+}
+
+#[test]
+fn test_seeded_populations_reproduce() {
+    let run = |seed: u64| {
+        let mut world = World::single(PetriDish::generate(5, &GeneratorConfig::default()));
+        let mut rng = Stream::Spawn.rng(seed);
+        let mut population = Population::spawn(3, &world, &SpawnConfig::default(), &mut rng);
+        for _ in 0..40 {
+            population.step(&mut world);
+        }
+        population
+            .agents
+            .iter()
+            .map(|a| (a.seed, a.x, a.y, a.energy))
+            .collect::<Vec<_>>()
+    };
+    let first = run(12);
+    assert_eq!(first, run(12));
+    assert_ne!(first, run(13));
+    // Each agent gets a seed of its own
+    assert_ne!(first[0].0, first[1].0);
+}
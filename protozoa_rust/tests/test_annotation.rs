@@ -0,0 +1,132 @@
+//! Tests for runtime parameter changes and their chart markers.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::annotation::{MarkerLog, MorphologyParam, ParamCommand};
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::ensemble::Ensemble;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::aggregate::{draw_aggregate_dashboard, marker_lines};
+#[cfg(feature = "tui")]
+use ratatui::{Terminal, backend::TestBackend};
+
+#[test]
+fn test_parse_param_commands() {
+    assert_eq!(
+        ParamCommand::parse("set light -2.5"),
+        Ok(ParamCommand {
+            param: MorphologyParam::Light,
+            value: -2.5
+        })
+    );
+    assert_eq!(
+        ParamCommand::parse("  set   ph 0.3 ").map(|c| c.param),
+        Ok(MorphologyParam::Ph)
+    );
+    assert!(ParamCommand::parse("set").unwrap_err().starts_with("usage"));
+    assert!(
+        ParamCommand::parse("goal 1 2")
+            .unwrap_err()
+            .starts_with("usage")
+    );
+    assert!(
+        ParamCommand::parse("set size 2")
+            .unwrap_err()
+            .contains("unknown")
+    );
+    assert!(
+        ParamCommand::parse("set light nan")
+            .unwrap_err()
+            .contains("invalid")
+    );
+    assert!(
+        ParamCommand::parse("set ph 1.5")
+            .unwrap_err()
+            .contains("range")
+    );
+    assert!(ParamCommand::parse("set sensor_dist 0").is_err());
+}
+
+#[test]
+fn test_apply_changes_morphology_and_labels() {
+    let mut agent = Protozoa::new(50.0, 25.0);
+    let command = ParamCommand::parse("set learning_rate 0.5").unwrap();
+    let old = MorphologyParam::LearningRate.get(&agent.morphology);
+    let label = command.apply(&mut agent.morphology);
+    assert!((agent.morphology.belief_learning_rate - 0.5).abs() < 1e-12);
+    assert_eq!(label, format!("learning_rate {old:.2} -> 0.50"));
+    for param in MorphologyParam::ALL {
+        let text = format!("set {} 0.25", param.name());
+        ParamCommand::parse(&text)
+            .unwrap()
+            .apply(&mut agent.morphology);
+        assert!((param.get(&agent.morphology) - 0.25).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_marker_log_is_bounded() {
+    let mut log = MarkerLog::with_capacity(2);
+    let mut clock = SimClock::new(0.5);
+    for i in 0..3 {
+        clock.tick = 10 * i;
+        log.record(clock, format!("change {i}"));
+    }
+    assert_eq!(log.count(), 3);
+    let kept: Vec<_> = log.iter().map(|m| (m.tick, m.time)).collect();
+    assert_eq!(kept, vec![(10, 5.0), (20, 10.0)]);
+    assert_eq!(log.iter().last().unwrap().label, "change 2");
+}
+
+#[test]
+fn test_ensemble_marks_changes() {
+    let mut ensemble = Ensemble::new(3, &SpawnConfig::default());
+    for _ in 0..5 {
+        ensemble.step();
+    }
+    let label = ensemble.apply_param(ParamCommand::parse("set light 1.5").unwrap());
+    assert!(label.ends_with("-> 1.50"));
+    assert!(
+        ensemble
+            .runs
+            .iter()
+            .all(|r| (r.agent.morphology.light_sensitivity - 1.5).abs() < 1e-12)
+    );
+    let marker = ensemble.markers.iter().next().unwrap();
+    assert_eq!(marker.tick, 5);
+    assert!((marker.time - ensemble.sample().time).abs() < 1e-12);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_markers_drawn_on_charts() {
+    let mut ensemble = Ensemble::new(2, &SpawnConfig::default());
+    for _ in 0..10 {
+        ensemble.step();
+    }
+    ensemble.apply_param(ParamCommand::parse("set ph 0.7").unwrap());
+    let markers: Vec<_> = ensemble.markers.iter().cloned().collect();
+    let t = markers[0].time;
+    assert_eq!(
+        marker_lines(&markers, [0.0, t + 1.0], [0.0, 1.0]),
+        vec![[(t, 0.0), (t, 1.0)]]
+    );
+    assert!(marker_lines(&markers, [t + 1.0, t + 2.0], [0.0, 1.0]).is_empty());
+
+    let history: Vec<_> = ensemble.history.iter().copied().collect();
+    let mut terminal = Terminal::new(TestBackend::new(140, 40)).unwrap();
+    terminal
+        .draw(|f| {
+            draw_aggregate_dashboard(f, &history, ensemble.len(), &markers, Some(":set_"));
+        })
+        .unwrap();
+    let text: String = terminal
+        .backend()
+        .buffer()
+        .content
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(text.contains("Changed: ph 0.50 -> 0.70"));
+    assert!(text.contains("Ensemble [:set_]"));
+}
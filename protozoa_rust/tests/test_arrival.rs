@@ -0,0 +1,157 @@
+//! Tests for arrival detection and the event log.
+
+use protozoa_rust::simulation::agent::{AgentMode, Protozoa};
+use protozoa_rust::simulation::arrival::{ArrivalEvent, ArrivalTarget, ArrivalTracker, EventLog};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{
+    ARRIVAL_DWELL_TICKS, ARRIVAL_RADIUS, DISH_HEIGHT, DISH_WIDTH, EVENT_LOG_CAPACITY,
+};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::render::format_event_log;
+
+fn event(travel_ticks: u64, path_length: f64) -> ArrivalEvent {
+    ArrivalEvent {
+        target: ArrivalTarget::Landmark,
+        x: 10.0,
+        y: 20.0,
+        tick: travel_ticks,
+        travel_ticks,
+        path_length,
+        straight_distance: 10.0,
+    }
+}
+
+/// Walks the tracker along +x from 0 toward a goal at (20, 0), one unit per tick.
+fn walk_to_goal(tracker: &mut ArrivalTracker, ticks: u64) -> Vec<ArrivalEvent> {
+    let target = Some((ArrivalTarget::Goal, 20.0, 0.0));
+    (0..ticks)
+        .filter_map(|tick| {
+            #[allow(clippy::cast_precision_loss)]
+            let x = (tick as f64).min(20.0);
+            tracker.update(target, x, 0.0, tick)
+        })
+        .collect()
+}
+
+#[test]
+fn test_arrival_requires_dwell() {
+    let mut tracker = ArrivalTracker::new();
+    let entry_tick = 20 - ARRIVAL_RADIUS.floor() as u64;
+
+    // Inside the radius but not yet for long enough
+    assert!(walk_to_goal(&mut tracker, entry_tick + ARRIVAL_DWELL_TICKS - 1).is_empty());
+    assert_eq!(
+        tracker.current_target(),
+        Some((ArrivalTarget::Goal, 20.0, 0.0))
+    );
+
+    let mut tracker = ArrivalTracker::new();
+    let events = walk_to_goal(&mut tracker, 60);
+    assert_eq!(events.len(), 1, "arrival is reported once per leg");
+    let arrival = events[0];
+    assert_eq!(arrival.travel_ticks, entry_tick);
+    assert_eq!(arrival.tick, entry_tick + ARRIVAL_DWELL_TICKS - 1);
+    #[allow(clippy::cast_precision_loss)]
+    let expected_path = entry_tick as f64;
+    assert!((arrival.path_length - expected_path).abs() < 1e-9);
+    // Entering the radius early makes the path shorter than the straight line
+    assert!((arrival.straight_distance - 20.0).abs() < 1e-9);
+    assert!((arrival.path_efficiency() - 1.0).abs() < 1e-12);
+    assert_eq!(tracker.current_target(), None);
+}
+
+#[test]
+fn test_leaving_radius_resets_dwell() {
+    let mut tracker = ArrivalTracker::new();
+    let target = Some((ArrivalTarget::Goal, 0.0, 0.0));
+    tracker.update(target, 10.0, 0.0, 0);
+    for tick in 1..ARRIVAL_DWELL_TICKS {
+        assert!(tracker.update(target, 0.0, 0.0, tick).is_none());
+    }
+    // Stepping out before the dwell completes restarts the count
+    assert!(tracker.update(target, 10.0, 0.0, 10).is_none());
+    let mut confirmed = None;
+    for tick in 11..11 + ARRIVAL_DWELL_TICKS {
+        confirmed = confirmed.or(tracker.update(target, 0.0, 0.0, tick));
+    }
+    let arrival = confirmed.expect("arrival after a full dwell");
+    assert_eq!(arrival.travel_ticks, 11);
+}
+
+#[test]
+fn test_landmark_leg_kept_while_dwelling() {
+    let mut tracker = ArrivalTracker::new();
+    let landmark = Some((ArrivalTarget::Landmark, 50.0, 25.0));
+    tracker.update(landmark, 40.0, 25.0, 0);
+    // Once inside, the navigation drive may lapse without aborting the leg
+    tracker.update(landmark, 50.0, 25.0, 1);
+    let mut arrival = None;
+    for tick in 2..2 + ARRIVAL_DWELL_TICKS {
+        arrival = arrival.or(tracker.update(None, 50.0, 25.0, tick));
+    }
+    assert_eq!(arrival.map(|a| a.target), Some(ArrivalTarget::Landmark));
+}
+
+#[test]
+fn test_event_log_bounded_with_running_means() {
+    let mut log = EventLog::new();
+    assert_eq!(log.count(), 0);
+    assert!(log.mean_travel_ticks().is_none());
+
+    for i in 0..EVENT_LOG_CAPACITY as u64 + 2 {
+        #[allow(clippy::cast_precision_loss)]
+        log.push(event(10 * (i + 1), 5.0 * (i + 1) as f64));
+    }
+    assert_eq!(log.iter().count(), EVENT_LOG_CAPACITY);
+    assert_eq!(log.count(), EVENT_LOG_CAPACITY + 2);
+    let n = (EVENT_LOG_CAPACITY + 2) as f64;
+    assert!((log.mean_travel_ticks().unwrap() - 5.0 * (n + 1.0)).abs() < 1e-9);
+    assert!((log.mean_path_length().unwrap() - 2.5 * (n + 1.0)).abs() < 1e-9);
+    assert_eq!(
+        log.last().unwrap().travel_ticks,
+        10 * (EVENT_LOG_CAPACITY as u64 + 2)
+    );
+}
+
+#[test]
+fn test_agent_completes_goal_on_arrival() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.set_goal(50.5, 25.0, 1.0);
+    assert_eq!(agent.current_mode(&dish), AgentMode::GoalNav);
+
+    for _ in 0..ARRIVAL_DWELL_TICKS + 20 {
+        if agent.goal.is_none() {
+            break;
+        }
+        // Hold the agent on the target so the dwell is deterministic
+        agent.x = 50.5;
+        agent.y = 25.0;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+
+    assert!(agent.goal.is_none(), "goal completed on arrival");
+    assert_eq!(agent.events.count(), 1);
+    let arrival = agent.events.last().unwrap();
+    assert_eq!(arrival.target, ArrivalTarget::Goal);
+    assert!((arrival.x - 50.5).abs() < 1e-12);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_event_log_format() {
+    let lines = format_event_log(&[], 0, None, 3);
+    assert_eq!(lines, vec!["Arrivals: 0".to_string()]);
+
+    let events = [event(40, 20.0), event(120, 12.5)];
+    let lines = format_event_log(&events, 7, Some(80.0), 3);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "Arrivals: 7  avg 80t");
+    // Newest first, with kind, position, travel time, path length and efficiency
+    assert!(lines[1].starts_with("L( 10, 20)"), "{}", lines[1]);
+    assert!(lines[1].contains(" 120t"));
+    assert!(lines[1].ends_with(" 80%"), "{}", lines[1]);
+    assert!(lines[2].ends_with(" 50%"), "{}", lines[2]);
+    assert_eq!(format_event_log(&events, 2, Some(80.0), 1).len(), 2);
+}
@@ -0,0 +1,167 @@
+//! Tests for building agents with a chosen internal state.
+
+use protozoa_rust::simulation::agent::{DishMemory, Protozoa};
+use protozoa_rust::simulation::controller::ControllerKind;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::memory::{EpisodicMemory, SpatialGrid};
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, SENSOR_DIST};
+use protozoa_rust::simulation::spawn::{SpawnConfig, StartOrientation, StartPosition};
+use protozoa_rust::simulation::streams::Stream;
+use protozoa_rust::simulation::stress::StressResponse;
+use std::f64::consts::PI;
+
+/// A generated dish with sources and no obstacles.
+fn dish(seed: u64) -> PetriDish {
+    let config = GeneratorConfig {
+        obstacle_count: 0,
+        ..GeneratorConfig::default()
+    };
+    PetriDish::generate(seed, &config)
+}
+
+/// Positions over `ticks` ticks in `dish`.
+fn trajectory(mut agent: Protozoa, dish: &PetriDish, ticks: usize) -> Vec<(f64, f64)> {
+    (0..ticks)
+        .map(|_| {
+            agent.sense(dish);
+            agent.update_state(dish);
+            (agent.x, agent.y)
+        })
+        .collect()
+}
+
+#[test]
+fn test_builder_defaults_match_a_fresh_agent() {
+    let agent = Protozoa::builder().build();
+    assert_eq!((agent.x, agent.y), (DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0));
+    assert!((0.0..2.0 * PI).contains(&agent.angle));
+    assert_eq!(agent.energy, 1.0);
+    assert_eq!(agent.dish_id, 0);
+    assert_eq!(agent.controller, ControllerKind::ActiveInference);
+    assert_eq!(agent.morphology.sensor_dist, SENSOR_DIST);
+    assert!(agent.morphology.stress.is_off());
+    assert_eq!(agent.spatial_priors.total_visits(), 0);
+    assert_eq!(agent.episodic_memory.count(), 0);
+}
+
+#[test]
+fn test_builder_sets_pose_and_energy() {
+    let agent = Protozoa::builder()
+        .position(20.0, 10.0)
+        .heading(1.0)
+        .energy(0.3)
+        .build();
+    assert_eq!((agent.x, agent.y, agent.angle), (20.0, 10.0, 1.0));
+    assert_eq!(agent.energy, 0.3);
+    assert_eq!(agent.beliefs.mean.x, 20.0);
+    assert_eq!(agent.beliefs.mean.y, 10.0);
+}
+
+#[test]
+fn test_seeded_builds_replay_exactly() {
+    let dish = dish(1);
+    let built = || Protozoa::builder().position(30.0, 20.0).seed(9).build();
+    assert_eq!(built().angle, built().angle);
+    assert_eq!(
+        trajectory(built(), &dish, 60),
+        trajectory(built(), &dish, 60)
+    );
+
+    // The same as seeding a hand-made agent
+    let mut by_hand = Protozoa::with_heading(30.0, 20.0, 0.5);
+    by_hand.seed_streams(9);
+    let seeded = Protozoa::builder()
+        .position(30.0, 20.0)
+        .heading(0.5)
+        .seed(9)
+        .build();
+    assert_eq!(
+        trajectory(by_hand, &dish, 60),
+        trajectory(seeded, &dish, 60)
+    );
+}
+
+#[test]
+fn test_seed_draws_the_heading_unless_given() {
+    let heading = |seed: u64| Protozoa::builder().seed(seed).build().angle;
+    assert_ne!(heading(1), heading(2));
+    assert!((0.0..2.0 * PI).contains(&heading(3)));
+    let given = Protozoa::builder().seed(1).heading(2.5).build();
+    assert_eq!(given.angle, 2.5);
+
+    // The draw leaves the behavioral streams untouched
+    let agent = Protozoa::builder().seed(4).build();
+    let mut reseeded = Protozoa::with_heading(agent.x, agent.y, agent.angle);
+    reseeded.seed_streams(4);
+    let dish = dish(2);
+    assert_eq!(
+        trajectory(agent, &dish, 30),
+        trajectory(reseeded, &dish, 30)
+    );
+}
+
+#[test]
+fn test_builder_installs_a_body_plan() {
+    let mut morphology = Protozoa::builder().build().morphology;
+    morphology.sensor_dist = 6.0;
+    morphology.stress = StressResponse::new(0.5, 1.0);
+    let built = |morphology| {
+        Protozoa::builder()
+            .position(30.0, 20.0)
+            .seed(5)
+            .morphology(morphology)
+            .build()
+    };
+    let agent = built(&morphology);
+    assert_eq!(agent.morphology.sensor_dist, 6.0);
+    assert!(!agent.morphology.stress.is_off());
+
+    // The same agent with the default body plan senses, and so swims, differently
+    let plain = built(&Protozoa::builder().build().morphology);
+    assert_eq!(plain.morphology.sensor_dist, SENSOR_DIST);
+    let dish = dish(6);
+    assert_ne!(trajectory(agent, &dish, 60), trajectory(plain, &dish, 60));
+}
+
+#[test]
+fn test_builder_starts_with_given_maps() {
+    let mut spatial_priors = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    for _ in 0..3 {
+        spatial_priors.update(80.0, 40.0, 0.9);
+    }
+    let mut episodic_memory = EpisodicMemory::new();
+    assert!(episodic_memory.pin(80.0, 40.0, 0));
+    let agent = Protozoa::builder()
+        .memory(DishMemory {
+            dish_id: 2,
+            spatial_priors,
+            episodic_memory,
+        })
+        .build();
+    assert_eq!(agent.dish_id, 2);
+    assert_eq!(agent.spatial_priors.total_visits(), 3);
+    assert!((agent.spatial_priors.expected(80.0, 40.0) - 0.9).abs() < 1e-12);
+    let landmark = agent.episodic_memory.iter().next().unwrap();
+    assert!(landmark.is_pinned());
+    assert_eq!((landmark.x, landmark.y), (80.0, 40.0));
+}
+
+#[test]
+fn test_builder_sets_the_controller_and_backs_spawning() {
+    let agent = Protozoa::builder()
+        .controller(ControllerKind::RunAndTumble)
+        .build();
+    assert_eq!(agent.controller, ControllerKind::RunAndTumble);
+
+    let spawn = SpawnConfig {
+        position: StartPosition::Fixed { x: 12.0, y: 34.0 },
+        orientation: StartOrientation::Fixed(0.25),
+        energy_min: 0.4,
+        energy_max: 0.4,
+    };
+    let spawned = spawn.spawn(&dish(3), &mut Stream::Spawn.rng(1));
+    assert_eq!((spawned.x, spawned.y, spawned.angle), (12.0, 34.0, 0.25));
+    assert_eq!(spawned.energy, 0.4);
+    assert_eq!(spawned.controller, ControllerKind::ActiveInference);
+}
@@ -0,0 +1,149 @@
+//! Tests for sensor calibration and learned bias compensation.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::calibration::{ChannelCalibration, SensorCalibration};
+use protozoa_rust::simulation::environment::{Environment, PetriDish};
+use protozoa_rust::simulation::inference::BiasEstimator;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+/// Uniform field: any left/right difference is sensor error.
+struct Flat;
+
+impl Environment for Flat {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) { 0.5 } else { -1.0 }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+/// Mean `val_l - val_r` over the last 200 of 1000 ticks in the flat field,
+/// leaving out ticks where a sensor reaches past the rim.
+fn residual_mismatch(calibration: SensorCalibration, rate: f64) -> f64 {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.3);
+    agent.seed_streams(1);
+    agent.calibration = calibration;
+    agent.bias_estimator = BiasEstimator::new(rate);
+    let (mut total, mut ticks) = (0.0, 0.0);
+    for tick in 0..1000 {
+        agent.sense(&Flat);
+        agent.update_state(&Flat);
+        if tick >= 800 && agent.val_l >= 0.0 && agent.val_r >= 0.0 {
+            total += agent.val_l - agent.val_r;
+            ticks += 1.0;
+        }
+    }
+    total / ticks
+}
+
+#[test]
+fn test_channel_mapping() {
+    let identity = SensorCalibration::default();
+    assert!(identity.is_identity());
+    assert_eq!(identity.apply(0.3, 0.7), (0.3, 0.7));
+
+    let calibration = SensorCalibration {
+        left: ChannelCalibration {
+            gain: 2.0,
+            offset: 0.1,
+        },
+        right: ChannelCalibration {
+            gain: 0.5,
+            offset: -0.2,
+        },
+        swapped: false,
+        saturation: 0.8,
+    };
+    let (l, r) = calibration.apply(0.3, 0.3);
+    assert!((l - 0.7).abs() < 1e-12);
+    assert_eq!(r, 0.0, "clamped at zero");
+    assert_eq!(calibration.apply(0.6, 0.9).0, 0.8, "saturates");
+    assert_eq!(
+        calibration.apply(-1.0, -1.0),
+        (-1.0, -1.0),
+        "void stays void"
+    );
+}
+
+#[test]
+fn test_parse_spec() {
+    let calibration =
+        SensorCalibration::parse("gain_l=1.5, offset_r=0.1,swap,saturation=0.9").unwrap();
+    assert_eq!(calibration.left.gain, 1.5);
+    assert_eq!(calibration.right.offset, 0.1);
+    assert!(calibration.swapped);
+    assert_eq!(calibration.saturation, 0.9);
+    assert!(SensorCalibration::parse("").unwrap().is_identity());
+
+    assert!(SensorCalibration::parse("gain_x=1").is_err());
+    assert!(SensorCalibration::parse("gain_l").is_err());
+    assert!(SensorCalibration::parse("gain_l=-1").is_err());
+    assert!(SensorCalibration::parse("offset_l=nan").is_err());
+    assert!(SensorCalibration::parse("saturation=0").is_err());
+}
+
+#[test]
+fn test_swapped_agent_reads_mirrored() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    let mut matched = Protozoa::with_heading(40.0, 25.0, 1.2);
+    let mut crossed = matched.clone();
+    crossed.calibration = SensorCalibration::parse("swap").unwrap();
+    matched.sense(&dish);
+    crossed.sense(&dish);
+    assert!(
+        (matched.val_l - matched.val_r).abs() > 1e-6,
+        "heading sees a slope"
+    );
+    assert_eq!(
+        (crossed.val_l, crossed.val_r),
+        (matched.val_r, matched.val_l)
+    );
+    assert_eq!(crossed.thermo_l, matched.thermo_l, "other senses unchanged");
+}
+
+#[test]
+fn test_bias_estimator_learns_antisymmetric_offset() {
+    let mut estimator = BiasEstimator::new(0.05);
+    for _ in 0..500 {
+        // Readings of a field at 0.5 with the left sensor 0.2 high
+        let (l, r) = estimator.correct(0.7, 0.5);
+        let belief = f64::midpoint(l, r);
+        estimator.update(l - belief, r - belief);
+    }
+    let (bias_l, bias_r) = estimator.bias();
+    assert!((bias_l - 0.1).abs() < 1e-3 && (bias_r + 0.1).abs() < 1e-3);
+    let (l, r) = estimator.correct(0.7, 0.5);
+    assert!((l - r).abs() < 1e-3);
+    assert_eq!(estimator.correct(-1.0, 0.5).0, -1.0);
+
+    // A common offset is not learned; off means off
+    let mut common = BiasEstimator::new(0.05);
+    common.update(0.3, 0.3);
+    assert_eq!(common.bias(), (0.0, 0.0));
+    let mut off = BiasEstimator::default();
+    off.update(0.3, -0.3);
+    assert_eq!(off.bias(), (0.0, 0.0));
+}
+
+#[test]
+fn test_agent_compensates_miscalibrated_sensors() {
+    assert!(residual_mismatch(SensorCalibration::default(), 0.0).abs() < 1e-9);
+    for spec in ["offset_l=0.1", "offset_r=0.15", "gain_l=1.4"] {
+        let calibration = SensorCalibration::parse(spec).unwrap();
+        let uncorrected = residual_mismatch(calibration, 0.0);
+        let corrected = residual_mismatch(calibration, 0.02);
+        assert!(uncorrected.abs() > 0.09, "{spec}: {uncorrected}");
+        assert!(
+            corrected.abs() < 0.2 * uncorrected.abs(),
+            "{spec}: {uncorrected} -> {corrected}"
+        );
+    }
+}
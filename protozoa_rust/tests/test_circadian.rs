@@ -0,0 +1,157 @@
+//! Tests for the circadian clock and its entrainment by daylight.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::circadian::{Circadian, daytime};
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::inference::{
+    BeliefState, GenerativeModel, circadian_free_energy, circadian_gradient,
+};
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, MAX_PHASE_VARIANCE};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Smallest angle between two phases.
+fn phase_error(a: f64, b: f64) -> f64 {
+    let diff = (a - b).rem_euclid(2.0 * PI);
+    diff.min(2.0 * PI - diff)
+}
+
+#[test]
+fn test_clock_is_off_by_default() {
+    let circadian = Circadian::default();
+    assert!(circadian.is_off());
+    assert!(circadian.validate().is_ok());
+    assert_eq!(circadian.metabolic_factor(PI), 1.0);
+    assert_eq!(circadian.exploration_factor(PI), 1.0);
+
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(1);
+    for _ in 0..20 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+    assert_eq!(agent.beliefs.mean.phase, 0.0, "keeps no time");
+    assert_eq!(agent.beliefs.covariance.phase_var, 1.0);
+    assert_eq!(agent.circadian_metabolism(), 1.0);
+    assert_eq!(agent.daylight, 1.0, "always noon without a cycle");
+}
+
+#[test]
+fn test_circadian_settings_are_validated() {
+    let circadian = Circadian::new(500.0, 0.4);
+    assert_eq!(circadian.period, 500.0);
+    assert_eq!(circadian.metabolic_depth, 0.4);
+    assert_eq!(circadian.exploration_depth, 0.4);
+    assert!(!circadian.is_off());
+    assert!(circadian.validate().is_ok());
+    for bad in [
+        Circadian::new(0.0, 0.5),
+        Circadian::new(-10.0, 0.5),
+        Circadian::new(f64::INFINITY, 0.5),
+        Circadian::new(500.0, 1.5),
+        Circadian::new(500.0, -0.1),
+    ] {
+        assert!(bad.validate().is_err(), "{bad:?}");
+    }
+}
+
+#[test]
+fn test_believed_time_of_day_sets_the_modulation() {
+    assert!((daytime(0.0) - 1.0).abs() < 1e-12);
+    assert!(daytime(PI).abs() < 1e-12);
+    assert!((daytime(FRAC_PI_2) - 0.5).abs() < 1e-12);
+
+    let circadian = Circadian {
+        period: 100.0,
+        metabolic_depth: 0.6,
+        exploration_depth: 0.2,
+    };
+    assert!((circadian.metabolic_factor(0.0) - 1.0).abs() < 1e-12);
+    assert!((circadian.metabolic_factor(PI) - 0.4).abs() < 1e-12);
+    assert!((circadian.exploration_factor(PI) - 0.8).abs() < 1e-12);
+    assert!((circadian.phase_step(&SimClock::default()) - 2.0 * PI / 100.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_phase_belief_free_runs_and_entrains() {
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.0);
+    beliefs.advance_phase(2.0 * PI - 0.1, 0.5);
+    assert!((beliefs.mean.phase - (2.0 * PI - 0.1)).abs() < 1e-12);
+    beliefs.advance_phase(0.3, 0.5);
+    assert!((beliefs.mean.phase - 0.2).abs() < 1e-12, "wraps");
+    assert!((beliefs.covariance.phase_var - 2.0).abs() < 1e-12);
+    beliefs.advance_phase(0.0, 10.0);
+    assert_eq!(beliefs.covariance.phase_var, MAX_PHASE_VARIANCE);
+
+    // A Gauss-Newton step: information shrinks the variance and the step
+    let before = beliefs.mean.phase;
+    beliefs.entrain_phase(1.0, 1.0 / MAX_PHASE_VARIANCE);
+    assert!((beliefs.covariance.phase_var - MAX_PHASE_VARIANCE / 2.0).abs() < 1e-12);
+    assert!((beliefs.mean.phase - before - MAX_PHASE_VARIANCE / 2.0).abs() < 1e-12);
+    beliefs.entrain_phase(0.0, 0.0);
+    assert!((beliefs.covariance.phase_var - MAX_PHASE_VARIANCE / 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_circadian_free_energy_pulls_toward_the_reading() {
+    let model = GenerativeModel::new();
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.0);
+    beliefs.mean.phase = 1.0;
+    let predicted = model.daylight_prediction(1.0);
+    assert!((model.daylight_prediction(0.0) - 1.0).abs() < 1e-12);
+    assert!(model.daylight_prediction(PI) < 1.0);
+    assert!(circadian_free_energy(predicted, &beliefs, &model).abs() < 1e-12);
+    assert!(circadian_gradient(predicted, &beliefs, &model).abs() < 1e-12);
+
+    // Darker than predicted in the afternoon: the day is further along
+    assert!(circadian_free_energy(predicted - 0.1, &beliefs, &model) > 0.0);
+    assert!(circadian_gradient(predicted - 0.1, &beliefs, &model) > 0.0);
+    assert!(circadian_gradient(predicted + 0.1, &beliefs, &model) < 0.0);
+}
+
+#[test]
+fn test_clock_entrains_to_the_dish_day() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    dish.config.day_night_period = 200;
+    dish.config.day_night_amplitude = 0.5;
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(7);
+    // A clock running 10% fast, set a quarter day wrong
+    agent.morphology.circadian = Circadian::new(180.0, 0.3);
+    agent.beliefs.mean.phase = FRAC_PI_2;
+    let start_var = agent.beliefs.covariance.phase_var;
+    for _ in 0..800 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        dish.update();
+    }
+    assert!(agent.is_alive());
+    let true_phase = 2.0 * PI * dish.clock.seconds() / 200.0;
+    let error = phase_error(agent.beliefs.mean.phase, true_phase);
+    assert!(error < 0.3, "phase off by {error}");
+    assert!(agent.beliefs.covariance.phase_var < start_var);
+}
+
+#[test]
+fn test_believed_night_lowers_metabolism_and_exploration() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let spent = |phase: f64| {
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(2);
+        agent.morphology.circadian = Circadian::new(1e9, 0.5);
+        agent.beliefs.mean.phase = phase;
+        assert!((agent.circadian_exploration() - agent.circadian_metabolism()).abs() < 1e-12);
+        let factor = agent.circadian_metabolism();
+        agent.cruise_speed = 0.0;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        (1.0 - agent.energy, factor)
+    };
+    let (noon, noon_factor) = spent(0.0);
+    let (midnight, midnight_factor) = spent(PI);
+    assert!((noon_factor - 1.0).abs() < 1e-9);
+    assert!((midnight_factor - 0.5).abs() < 1e-9);
+    assert!(midnight < noon, "{midnight} vs {noon}");
+}
@@ -0,0 +1,113 @@
+//! Tests for the simulation clock and sim-time rate semantics.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::ensemble::Ensemble;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, SECONDS_PER_TICK};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+
+#[test]
+fn test_clock_conversions() {
+    let clock = SimClock::new(0.25);
+    assert!((clock.dt() - 0.25).abs() < 1e-12);
+    assert!((clock.rate(2.0) - 0.5).abs() < 1e-12);
+    assert!((clock.factor(0.81) - 0.81_f64.powf(0.25)).abs() < 1e-12);
+    assert!((clock.diffusion(0.2) - 0.1).abs() < 1e-12);
+    assert_eq!(clock.ticks_for(1.0), 4);
+    assert_eq!(clock.ticks_for(0.01), 1);
+    assert_eq!(clock.ticks_for(0.0), 0);
+
+    // Four quarter-second chances compound to the per-second chance
+    let p = clock.probability(0.5);
+    assert!((1.0 - (1.0 - p).powi(4) - 0.5).abs() < 1e-12);
+    assert!((clock.probability(1.0) - 1.0).abs() < 1e-12);
+    assert!(clock.probability(0.0).abs() < 1e-12);
+
+    let mut clock = SimClock::default();
+    assert!((clock.dt() - SECONDS_PER_TICK).abs() < 1e-12);
+    for _ in 0..3 {
+        clock.advance();
+    }
+    assert_eq!(clock.tick, 3);
+    assert!((clock.seconds() - 3.0 * SECONDS_PER_TICK).abs() < 1e-12);
+}
+
+#[test]
+fn test_source_decay_is_tick_rate_invariant() {
+    let config = |seconds_per_tick| GeneratorConfig {
+        dish: DishConfig {
+            decay_min: 0.95,
+            decay_max: 0.95,
+            brownian_step: 0.0,
+            respawn_threshold: 0.0,
+            seconds_per_tick,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    let mut coarse = PetriDish::generate(9, &config(1.0));
+    let mut fine = PetriDish::generate(9, &config(0.25));
+    for _ in 0..10 {
+        coarse.update();
+    }
+    for _ in 0..40 {
+        fine.update();
+    }
+    assert!((coarse.clock.seconds() - fine.clock.seconds()).abs() < 1e-12);
+    for (a, b) in coarse.sources.iter().zip(&fine.sources) {
+        assert!((a.intensity - b.intensity).abs() < 1e-9);
+        assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_agent_drain_scales_with_tick_length() {
+    let mut coarse = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    coarse.sources.clear();
+    let mut fine = coarse.clone();
+    fine.clock = SimClock::new(0.5);
+
+    // Far from food, one full second costs about twice half a second
+    let spend = |dish: &PetriDish| {
+        let mut agent = Protozoa::with_heading(20.0, 20.0, 0.0);
+        agent.seed_streams(1);
+        agent.energy = 0.5;
+        agent.sense(dish);
+        agent.update_state(dish);
+        0.5 - agent.energy
+    };
+    let (full, half) = (spend(&coarse), spend(&fine));
+    assert!(full > 0.0 && half > 0.0);
+    assert!((full / half - 2.0).abs() < 0.05, "{full} vs {half}");
+}
+
+#[test]
+fn test_seconds_per_tick_config() {
+    assert!((DishConfig::default().seconds_per_tick - SECONDS_PER_TICK).abs() < 1e-12);
+    let parsed = DishConfig::from_toml_str("seconds_per_tick = 0.5").unwrap();
+    assert!((parsed.seconds_per_tick - 0.5).abs() < 1e-12);
+    assert!(DishConfig::from_toml_str("seconds_per_tick = 0.0").is_err());
+    assert!(DishConfig::from_toml_str("seconds_per_tick = -1.0").is_err());
+
+    let config = GeneratorConfig {
+        dish: parsed,
+        ..GeneratorConfig::default()
+    };
+    let dish = PetriDish::generate(1, &config);
+    assert!((dish.clock.dt() - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_ensemble_samples_carry_sim_time() {
+    let mut ensemble = Ensemble::new(2, &SpawnConfig::default());
+    for _ in 0..5 {
+        ensemble.step();
+    }
+    let sample = ensemble.history.back().unwrap();
+    assert_eq!(sample.tick, 5);
+    assert!((sample.time - 5.0 * SECONDS_PER_TICK).abs() < 1e-12);
+}
@@ -0,0 +1,104 @@
+//! Tests for loading exported runs and overlaying them in a replay.
+
+#[cfg(feature = "tui")]
+use protozoa_rust::simulation::agent::Protozoa;
+#[cfg(feature = "tui")]
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::export::record_csv;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::replay::{Recording, Trajectory};
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::world::World;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::{DashboardState, render::draw_dashboard};
+#[cfg(feature = "tui")]
+use ratatui::{Terminal, backend::TestBackend, style::Color};
+
+const CSV: &str =
+    "tick,x,extra,y,energy\n1,10.0,?,20.0,0.9\n2,11.0,?,20.5,0.8\n\n5,14.0,?,22.0,0.7\n";
+
+#[test]
+fn test_parses_export_columns_by_name() {
+    let trajectory = Trajectory::from_csv("a.csv", CSV).unwrap();
+    assert_eq!(trajectory.label, "a.csv");
+    assert_eq!(trajectory.last_tick(), 5);
+    let point = trajectory.at(2).unwrap();
+    assert_eq!((point.x, point.y, point.energy), (11.0, 20.5, 0.8));
+
+    let missing = Trajectory::from_csv("b.csv", "tick,x,y\n1,2,3\n").unwrap_err();
+    assert!(missing.contains("`energy`"), "{missing}");
+    let bad = Trajectory::from_csv("c.csv", "tick,x,y,energy\n1,2,oops,4\n").unwrap_err();
+    assert!(bad.contains("line 2"), "{bad}");
+    let unordered = "tick,x,y,energy\n3,0,0,1\n2,0,0,1\n";
+    assert!(Trajectory::from_csv("d.csv", unordered).is_err());
+}
+
+#[test]
+fn test_lookup_holds_the_last_point() {
+    let trajectory = Trajectory::from_csv("a.csv", CSV).unwrap();
+    assert!(trajectory.at(0).is_none(), "before the first row");
+    assert_eq!(
+        trajectory.at(4).unwrap().tick,
+        2,
+        "gap holds the last point"
+    );
+    assert_eq!(trajectory.at(99).unwrap().tick, 5);
+
+    let ticks =
+        |from, to| -> Vec<u64> { trajectory.window(from, to).iter().map(|p| p.tick).collect() };
+    assert_eq!(ticks(0, 2), [1, 2]);
+    assert_eq!(ticks(2, 5), [2, 5]);
+    assert_eq!(ticks(3, 4), Vec::<u64>::new());
+    assert_eq!(ticks(6, 3), Vec::<u64>::new());
+}
+
+#[test]
+fn test_export_of_the_same_seed_lines_up_with_the_replay() {
+    let run = Scenario::Preset(EnvironmentPreset::Gradient).build(4);
+    let mut world = World::single(run.dish.clone());
+    let mut agent = run.agent.clone();
+    let csv = record_csv(&mut world, &mut agent, 200);
+    let trajectory = Trajectory::from_csv("run.csv", &csv).unwrap();
+    assert_eq!(trajectory.last_tick(), 200);
+
+    let recording = Recording::record(run, 200, 50);
+    for tick in [1, 37, 50, 199, 200] {
+        let frame = recording.frame(tick);
+        let point = trajectory.at(tick).unwrap();
+        assert_eq!(point.tick, tick);
+        assert_eq!((point.x, point.y), (frame.agent.x, frame.agent.y));
+    }
+}
+
+#[test]
+fn test_loads_from_file() {
+    let path = std::env::temp_dir().join(format!("compare_{}.csv", std::process::id()));
+    std::fs::write(&path, CSV).unwrap();
+    let trajectory = Trajectory::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(trajectory.label, path.display().to_string());
+    assert_eq!(trajectory.last_tick(), 5);
+    assert!(Trajectory::load(&path).unwrap_err().contains("cannot read"));
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_tinted_glyphs_drawn_in_their_color() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut state = DashboardState::from_agent(&agent, &dish);
+    state.glyph_tints = vec![('A', Color::Cyan), ('b', Color::Magenta)];
+    let mut grid = vec![" ".repeat(40); 10];
+    grid[2] = format!("{:<40}", "..aaA...bbB");
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| draw_dashboard(f, grid, &state)).unwrap();
+    let buffer = terminal.backend().buffer();
+    // The dish panel's inner area starts at (1, 1)
+    let fg = |column: u16| buffer[(1 + column, 3)].fg;
+    assert_eq!(fg(4), Color::Cyan);
+    assert_eq!(fg(8), Color::Magenta);
+    assert_eq!(fg(9), Color::Magenta);
+    assert_eq!(fg(2), Color::Reset, "untinted glyph");
+    assert_eq!(fg(10), Color::Reset);
+}
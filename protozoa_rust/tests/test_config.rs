@@ -0,0 +1,125 @@
+//! Tests for the TOML dish configuration.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::flow::FlowField;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::params::{BROWNIAN_STEP, DISH_WIDTH, SOURCE_COUNT_MAX};
+
+#[test]
+fn test_missing_keys_fall_back_to_params() {
+    assert_eq!(
+        DishConfig::from_toml_str("").unwrap(),
+        DishConfig::default()
+    );
+
+    let config = DishConfig::from_toml_str("height = 60.0\nbrownian_step = 0.1\n").unwrap();
+    assert!((config.height - 60.0).abs() < 1e-12);
+    assert!((config.brownian_step - 0.1).abs() < 1e-12);
+    assert!((config.width - DISH_WIDTH).abs() < 1e-12);
+    assert_eq!(config.source_count_max, SOURCE_COUNT_MAX);
+}
+
+#[test]
+fn test_round_trip_through_toml() {
+    let config = DishConfig {
+        source_count_min: 2,
+        source_count_max: 4,
+        decay_min: 0.95,
+        decay_max: 0.97,
+        ..DishConfig::sized(120.0, 70.0)
+    };
+    let text = config.to_toml_string();
+    assert_eq!(DishConfig::from_toml_str(&text).unwrap(), config);
+}
+
+#[test]
+fn test_invalid_configs_are_rejected() {
+    for (text, key) in [
+        ("widht = 10.0", "widht"),
+        ("width = -5.0", "width"),
+        ("decay_min = 0.99\ndecay_max = 0.9", "decay"),
+        ("decay_max = 1.5", "decay"),
+        ("source_count_min = 8\nsource_count_max = 2", "source_count"),
+        ("source_margin = 30.0", "source_margin"),
+        ("brownian_step = -1.0", "brownian_step"),
+        ("width = \"wide\"", "width"),
+    ] {
+        let err = DishConfig::from_toml_str(text).unwrap_err();
+        assert!(err.contains(key), "{text:?} gave {err:?}");
+    }
+}
+
+#[test]
+fn test_load_reads_file() {
+    let path = std::env::temp_dir().join(format!("dish_config_{}.toml", std::process::id()));
+    std::fs::write(&path, "source_count_min = 1\nsource_count_max = 1\n").unwrap();
+    let config = DishConfig::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(config.source_count_range(), (1, 1));
+
+    assert!(DishConfig::load(&path).unwrap_err().contains("cannot read"));
+}
+
+#[test]
+fn test_dish_follows_config() {
+    let config = DishConfig {
+        source_count_min: 2,
+        source_count_max: 2,
+        source_radius_min: 3.0,
+        source_radius_max: 3.0,
+        decay_min: 0.9,
+        decay_max: 0.9,
+        ..DishConfig::sized(60.0, 40.0)
+    };
+    let dish = PetriDish::from_config(&config, 1);
+    assert!((dish.width - 60.0).abs() < 1e-12 && (dish.height - 40.0).abs() < 1e-12);
+    assert_eq!(dish.sources.len(), 2);
+    for source in &dish.sources {
+        assert!((source.radius - 3.0).abs() < 1e-12);
+        assert!((source.decay_rate - 0.9).abs() < 1e-12);
+        let margin = config.source_margin;
+        assert!(source.y >= margin && source.y <= 40.0 - margin);
+    }
+}
+
+#[test]
+fn test_brownian_step_from_config() {
+    let frozen = DishConfig {
+        brownian_step: 0.0,
+        decay_min: 1.0,
+        decay_max: 1.0,
+        ..DishConfig::default()
+    };
+    let mut dish = PetriDish::generate(
+        3,
+        &GeneratorConfig {
+            dish: frozen,
+            ..GeneratorConfig::default()
+        },
+    );
+    dish.flow = FlowField::still(dish.width, dish.height);
+    let before: Vec<(f64, f64)> = dish.sources.iter().map(|s| (s.x, s.y)).collect();
+    for _ in 0..50 {
+        dish.update();
+    }
+    let after: Vec<(f64, f64)> = dish.sources.iter().map(|s| (s.x, s.y)).collect();
+    assert_eq!(before, after, "zero brownian step keeps sources in place");
+
+    let mut jittery = PetriDish::from_config(
+        &DishConfig {
+            brownian_step: 4.0 * BROWNIAN_STEP,
+            ..frozen
+        },
+        1,
+    );
+    jittery.flow = FlowField::still(jittery.width, jittery.height);
+    let before: Vec<(f64, f64)> = jittery.sources.iter().map(|s| (s.x, s.y)).collect();
+    jittery.update();
+    let moved = jittery
+        .sources
+        .iter()
+        .zip(&before)
+        .any(|(s, &(x, y))| (s.x - x).abs() > 1e-12 || (s.y - y).abs() > 1e-12);
+    assert!(moved);
+}
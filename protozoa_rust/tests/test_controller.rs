@@ -0,0 +1,121 @@
+//! Tests for pluggable controllers (active inference and the baselines).
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::controller::{
+    ActiveInferenceController, Braitenberg, Controller, ControllerKind, PureGradientFollower,
+    RandomWalk,
+};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{
+    BASELINE_SPEED_FRACTION, DISH_HEIGHT, DISH_WIDTH, GRADIENT_FOLLOWER_GAIN, MAX_SPEED,
+    RANDOM_WALK_TURN,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+
+/// An agent with fixed readings, stronger on the left.
+fn reading_left(left: f64, right: f64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 1.0);
+    agent.seed_streams(3);
+    agent.val_l = left;
+    agent.val_r = right;
+    agent
+}
+
+/// Mean chemoreceptor reading over `ticks` ticks in the gradient preset.
+fn mean_reading(controller: ControllerKind, seed: u64, ticks: u32) -> f64 {
+    let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(seed);
+    run.agent.controller = controller;
+    let mut total = 0.0;
+    for _ in 0..ticks {
+        run.step();
+        total += f64::midpoint(run.agent.val_l, run.agent.val_r);
+    }
+    total / f64::from(ticks)
+}
+
+#[test]
+fn test_controller_names_parse() {
+    assert_eq!(ControllerKind::default(), ControllerKind::ActiveInference);
+    assert_eq!(
+        Protozoa::new(50.0, 25.0).controller,
+        ControllerKind::ActiveInference
+    );
+    for kind in ControllerKind::ALL {
+        assert_eq!(ControllerKind::parse(kind.name()), Some(kind));
+    }
+    assert_eq!(
+        ControllerKind::parse("Braitenberg"),
+        Some(ControllerKind::Braitenberg)
+    );
+    assert_eq!(ControllerKind::parse("mcts"), None);
+    assert_eq!(
+        ControllerKind::GradientFollower.name(),
+        PureGradientFollower.name()
+    );
+}
+
+#[test]
+fn test_active_inference_controller_is_the_agents_own_decision() {
+    let mut a = reading_left(0.6, 0.4);
+    let mut b = a.clone();
+    let mut c = a.clone();
+    let decision = ActiveInferenceController.decide(&mut a);
+    assert_eq!(decision, b.active_inference_decision());
+    assert_eq!(decision, ControllerKind::ActiveInference.decide(&mut c));
+    assert!(decision.speed > 0.0 && decision.speed <= MAX_SPEED);
+    assert_eq!(a.last_plan_tick, b.last_plan_tick);
+}
+
+#[test]
+fn test_gradient_baselines_turn_toward_the_stronger_sensor() {
+    let decision = PureGradientFollower.decide(&mut reading_left(0.6, 0.4));
+    assert!((decision.turn - GRADIENT_FOLLOWER_GAIN * 0.2).abs() < 1e-12);
+    assert_eq!(decision.speed, BASELINE_SPEED_FRACTION * MAX_SPEED);
+    let mirrored = PureGradientFollower.decide(&mut reading_left(0.4, 0.6));
+    assert!((mirrored.turn + decision.turn).abs() < 1e-12);
+
+    // The vehicle turns the same way but speeds up with the readings
+    let weak = Braitenberg.decide(&mut reading_left(0.2, 0.1));
+    let strong = Braitenberg.decide(&mut reading_left(0.8, 0.7));
+    assert!(weak.turn > 0.0 && (weak.turn - strong.turn).abs() < 1e-12);
+    assert!(strong.speed > weak.speed);
+    let void = Braitenberg.decide(&mut reading_left(-1.0, -1.0));
+    assert!((void.speed - 0.1 * MAX_SPEED).abs() < 1e-12, "never stalls");
+}
+
+#[test]
+fn test_random_walk_ignores_readings() {
+    let mut left = reading_left(0.9, 0.1);
+    let mut right = reading_left(0.1, 0.9);
+    for _ in 0..100 {
+        let a = RandomWalk.decide(&mut left);
+        assert_eq!(a, RandomWalk.decide(&mut right), "same stream, same turns");
+        assert!(a.turn.abs() < RANDOM_WALK_TURN);
+        assert_eq!(a.speed, BASELINE_SPEED_FRACTION * MAX_SPEED);
+    }
+
+    // Through `update_state` the decision sets the heading; nothing is planned
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let mut agent = reading_left(0.5, 0.5);
+    agent.controller = ControllerKind::RandomWalk;
+    let mut stream = agent.clone();
+    agent.update_state(&dish);
+    let turn = RandomWalk.decide(&mut stream).turn;
+    assert!((agent.angle - (1.0 + turn)).abs() < 1e-12);
+    assert!(agent.planner.last_plan_details().is_empty());
+    assert_eq!(agent.tick_count, 1, "memory and metabolism still run");
+}
+
+#[test]
+fn test_gradient_follower_outclimbs_random_walk() {
+    // Short runs: without a set-point the follower later overshoots the
+    // peak and stalls at the rim
+    let climbing: f64 = (0..3)
+        .map(|seed| mean_reading(ControllerKind::GradientFollower, seed, 120))
+        .sum();
+    let wandering: f64 = (0..3)
+        .map(|seed| mean_reading(ControllerKind::RandomWalk, seed, 120))
+        .sum();
+    assert!(climbing > wandering, "{climbing} vs {wandering}");
+}
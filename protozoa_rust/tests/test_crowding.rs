@@ -0,0 +1,151 @@
+//! Tests for soft collisions and crowding costs between agents.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::crowding::{Crowding, NeighborIndex};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::world::World;
+
+/// Collisions only, removing the whole overlap in one tick.
+fn collisions() -> Crowding {
+    Crowding {
+        stiffness: 1.0,
+        cost: 0.0,
+        ..Crowding::default()
+    }
+}
+
+/// Smallest distance between two of the agents.
+fn closest_pair(agents: &[Protozoa]) -> f64 {
+    let mut closest = f64::INFINITY;
+    for (i, a) in agents.iter().enumerate() {
+        for b in &agents[i + 1..] {
+            closest = closest.min((a.x - b.x).hypot(a.y - b.y));
+        }
+    }
+    closest
+}
+
+#[test]
+fn test_neighbor_index_finds_agents_within_radius() {
+    let positions = [
+        (10.0, 10.0),
+        (12.0, 10.0),
+        (10.0, 17.0),
+        (40.0, 40.0),
+        (99.0, 49.0),
+    ];
+    let index = NeighborIndex::build(&positions, 100.0, 50.0, 5.0);
+    let mut near: Vec<usize> = index.neighbors(0, 8.0).collect();
+    near.sort_unstable();
+    assert_eq!(near, vec![1, 2], "across cells, itself excluded");
+    assert_eq!(index.neighbors(0, 2.0).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(index.neighbors(3, 8.0).count(), 0);
+    // Radii wider than a cell scan enough cells
+    assert_eq!(index.neighbors(3, 200.0).count(), 4);
+    assert_eq!(index.neighbors(4, 1.0).count(), 0);
+}
+
+#[test]
+fn test_crowding_is_off_by_default() {
+    let crowding = Crowding::default();
+    assert!(crowding.is_off());
+    assert!(
+        Population::single(Protozoa::new(50.0, 25.0))
+            .crowding
+            .is_off()
+    );
+
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut a = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut b = Protozoa::with_heading(50.5, 25.0, 0.0);
+    crowding.apply(&dish, [&mut a, &mut b]);
+    assert_eq!((a.x, b.x), (50.0, 50.5));
+    assert_eq!((a.energy, b.energy), (1.0, 1.0));
+}
+
+#[test]
+fn test_soft_collision_pushes_overlapping_agents_apart() {
+    let dish = PetriDish::empty(100.0, 50.0, 2);
+    let crowding = collisions();
+    let radius = crowding.collision_radius;
+
+    let mut a = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut b = Protozoa::with_heading(50.0 + radius / 2.0, 25.0, 0.0);
+    crowding.apply(&dish, [&mut a, &mut b]);
+    assert!(
+        (a.x - (50.0 - radius / 4.0)).abs() < 1e-12,
+        "half the overlap each"
+    );
+    assert!(((b.x - a.x) - radius).abs() < 1e-12);
+    assert_eq!((a.y, b.y), (25.0, 25.0));
+
+    // Coincident agents split along x; the rim holds them in the dish
+    let mut c = Protozoa::with_heading(0.0, 10.0, 0.0);
+    let mut d = Protozoa::with_heading(0.0, 10.0, 0.0);
+    crowding.apply(&dish, [&mut c, &mut d]);
+    assert_eq!(c.x, 0.0);
+    assert!((d.x - radius / 2.0).abs() < 1e-12);
+
+    // Agents farther apart than the radius, or dead, are not moved
+    let mut e = Protozoa::with_heading(20.0, 20.0, 0.0);
+    let mut f = Protozoa::with_heading(20.0 + radius * 2.0, 20.0, 0.0);
+    let mut dead = Protozoa::with_heading(20.0, 20.5, 0.0);
+    dead.died_at = Some(1);
+    crowding.apply(&dish, [&mut e, &mut f, &mut dead]);
+    assert_eq!(
+        (e.x, e.y, f.x, dead.y),
+        (20.0, 20.0, 20.0 + radius * 2.0, 20.5)
+    );
+}
+
+#[test]
+fn test_crowding_cost_scales_with_neighbors() {
+    let dish = PetriDish::empty(100.0, 50.0, 3);
+    let crowding = Crowding {
+        stiffness: 0.0,
+        cost: 0.01,
+        ..Crowding::default()
+    };
+    let spacing = crowding.neighbor_radius * 0.6;
+    let mut agents: Vec<Protozoa> = (0..3)
+        .map(|i| Protozoa::with_heading(30.0 + spacing * f64::from(i), 25.0, 0.0))
+        .collect();
+    let mut loner = Protozoa::with_heading(90.0, 45.0, 0.0);
+    crowding.apply(&dish, agents.iter_mut().chain([&mut loner]));
+    let per_neighbor = dish.clock.rate(0.01);
+    assert!((agents[0].energy - (1.0 - per_neighbor)).abs() < 1e-12);
+    assert!((agents[1].energy - (1.0 - 2.0 * per_neighbor)).abs() < 1e-12);
+    assert_eq!(loner.energy, 1.0);
+    assert_eq!(agents[0].x, 30.0, "no collisions without stiffness");
+
+    // Energy bottoms out at zero
+    agents[1].energy = per_neighbor / 2.0;
+    crowding.apply(&dish, agents.iter_mut());
+    assert_eq!(agents[1].energy, 0.0);
+}
+
+#[test]
+fn test_population_cluster_disperses() {
+    let stacked = |crowding: Crowding| {
+        let mut world = World::single(PetriDish::empty(100.0, 50.0, 4));
+        let mut population = Population::single(Protozoa::with_heading(50.0, 25.0, 0.0));
+        for _ in 0..4 {
+            population.add(Protozoa::with_heading(50.0, 25.0, 0.0), None);
+        }
+        for (i, agent) in (0..).zip(&mut population.agents) {
+            agent.seed_streams(i);
+        }
+        population.crowding = crowding;
+        for _ in 0..5 {
+            population.step(&mut world);
+        }
+        closest_pair(&population.agents)
+    };
+    let packed = stacked(Crowding::default());
+    let dispersed = stacked(collisions());
+    assert!(
+        dispersed > packed && dispersed > collisions().collision_radius / 2.0,
+        "{packed} -> {dispersed}"
+    );
+}
@@ -0,0 +1,96 @@
+//! Tests for the day/night nutrient cycle.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::cycle::DayNightCycle;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::render::format_daylight;
+
+#[test]
+fn test_daylight_level_is_sinusoidal() {
+    let cycle = DayNightCycle {
+        period: 100,
+        amplitude: 0.6,
+    };
+    assert!((cycle.level(0.0) - 1.0).abs() < 1e-12, "noon");
+    assert!((cycle.level(50.0) - 0.4).abs() < 1e-12, "midnight");
+    assert!((cycle.level(25.0) - 0.7).abs() < 1e-12, "dusk");
+    assert!((cycle.level(100.0) - 1.0).abs() < 1e-12, "next noon");
+    assert!(!cycle.is_night(10.0) && cycle.is_night(50.0) && !cycle.is_night(90.0));
+
+    let off = DayNightCycle {
+        period: 100,
+        amplitude: 0.0,
+    };
+    assert!((off.level(50.0) - 1.0).abs() < 1e-12);
+    assert!(!off.is_night(50.0));
+}
+
+#[test]
+fn test_night_dims_concentration() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    dish.config.day_night_period = 100;
+    dish.config.day_night_amplitude = 0.8;
+    let source = dish.sources[0].clone();
+    let noon = dish.get_concentration(source.x, source.y);
+
+    for _ in 0..50 {
+        dish.update();
+    }
+    assert!(dish.is_night());
+    let midnight = dish.get_concentration(source.x, source.y);
+    assert!((midnight - 0.2 * noon).abs() < 1e-9, "{midnight} vs {noon}");
+    let species = dish.get_species_concentration(0, source.x, source.y);
+    assert!((species - midnight).abs() < 1e-12);
+}
+
+#[test]
+fn test_depleted_sources_wait_for_daylight() {
+    let config = GeneratorConfig {
+        dish: DishConfig {
+            decay_min: 0.5,
+            decay_max: 0.5,
+            day_night_period: 1_000_000,
+            day_night_amplitude: 1.0,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    let threshold = config.dish.respawn_threshold;
+
+    // Around midnight (daylight ≈ 0) nothing respawns
+    let mut night = PetriDish::generate(5, &config);
+    night.clock.tick = 500_000;
+    for _ in 0..10 {
+        night.update();
+    }
+    assert!(night.sources.iter().all(|s| s.intensity < threshold));
+
+    // Around noon depleted sources come back
+    let mut day = PetriDish::generate(5, &config);
+    for _ in 0..10 {
+        day.update();
+    }
+    assert!(day.sources.iter().any(|s| s.intensity >= threshold));
+}
+
+#[test]
+fn test_day_night_config_keys() {
+    let config =
+        DishConfig::from_toml_str("day_night_period = 400\nday_night_amplitude = 0.5\n").unwrap();
+    assert_eq!(config.day_night().period, 400);
+    assert!((config.day_night().amplitude - 0.5).abs() < 1e-12);
+
+    assert!(DishConfig::from_toml_str("day_night_amplitude = 1.5").is_err());
+    assert!(DishConfig::from_toml_str("day_night_period = 0").is_err());
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_format_daylight() {
+    assert_eq!(format_daylight(1.0, false), "Day 100%");
+    assert_eq!(format_daylight(0.35, true), "Night 35%");
+}
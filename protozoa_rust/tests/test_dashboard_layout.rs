@@ -0,0 +1,130 @@
+#![cfg(feature = "tui")]
+//! Tests for the responsive dashboard layout.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, GRID_HEIGHT, GRID_WIDTH};
+use protozoa_rust::ui::DashboardState;
+use protozoa_rust::ui::layout::{
+    DashboardLayout, EVENTS_FULL_HEIGHT, EVENTS_MIN_HEIGHT, LANDMARKS_HEIGHT, METRICS_HEIGHT,
+    grid_scale,
+};
+use protozoa_rust::ui::render::{draw_dashboard, petri_dish_grid_size, upscale_grid_lines};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+
+/// Column of the first occurrence of `text` on buffer row `y`.
+fn find_on_row(terminal: &Terminal<TestBackend>, y: u16, text: &str) -> Option<u16> {
+    let buffer = terminal.backend().buffer();
+    let row: String = (0..buffer.area.width)
+        .map(|x| {
+            buffer
+                .cell((x, y))
+                .map_or(" ", ratatui::buffer::Cell::symbol)
+        })
+        .collect::<Vec<_>>()
+        .concat();
+    row.find(text)
+        .map(|byte| u16::try_from(row[..byte].chars().count()).unwrap())
+}
+
+#[test]
+fn test_small_terminals_keep_the_fixed_sidebar() {
+    let layout = DashboardLayout::for_area(Rect::new(0, 0, 100, 40));
+    assert!(layout.detail.is_none());
+    assert_eq!(layout.main.width, 70);
+    assert_eq!(layout.metrics.height, METRICS_HEIGHT);
+    assert_eq!(layout.landmarks.height, LANDMARKS_HEIGHT);
+    assert_eq!(layout.events.height, EVENTS_MIN_HEIGHT);
+    assert_eq!(layout.spatial.height, 4, "whatever is left");
+    assert_eq!(layout.panels()[3], layout.events);
+}
+
+#[test]
+fn test_tall_terminals_grow_events_then_spatial_memory() {
+    let layout = DashboardLayout::for_area(Rect::new(0, 0, 120, 56));
+    // 8 rows beyond the natural heights, all to the event log
+    assert_eq!(layout.events.height, EVENTS_MIN_HEIGHT + 8);
+    assert_eq!(usize::from(layout.spatial.height), GRID_HEIGHT + 2);
+
+    let layout = DashboardLayout::for_area(Rect::new(0, 0, 120, 90));
+    assert_eq!(layout.events.height, EVENTS_FULL_HEIGHT);
+    assert_eq!(layout.spatial.bottom(), 90);
+    assert_eq!(
+        layout.spatial.height,
+        90 - 2 * METRICS_HEIGHT - LANDMARKS_HEIGHT - EVENTS_FULL_HEIGHT
+    );
+}
+
+#[test]
+fn test_wide_terminals_add_a_detail_column() {
+    let area = Rect::new(0, 0, 300, 90);
+    let layout = DashboardLayout::for_area(area);
+    let detail = layout.detail.expect("detail column");
+    assert_eq!(layout.main.width, 210);
+    assert_eq!(detail.right(), 300);
+    assert!(detail.contains(layout.landmarks.as_position()));
+    assert!(detail.contains(layout.events.as_position()));
+    assert_eq!(layout.events.bottom(), 90);
+    // The sidebar keeps metrics, planning and a full-height spatial grid
+    assert_eq!(layout.metrics.x, layout.spatial.x);
+    assert!(layout.spatial.right() <= detail.x);
+    assert_eq!(layout.spatial.height, 90 - 2 * METRICS_HEIGHT);
+
+    // The field grid follows the petri dish panel
+    assert_eq!(petri_dish_grid_size(area), (88, 208));
+    let narrower = petri_dish_grid_size(Rect::new(0, 0, 199, 90));
+    assert!(narrower.1 < 208);
+}
+
+#[test]
+fn test_spatial_grid_upscales_in_whole_cells() {
+    assert_eq!(
+        grid_scale(Rect::new(0, 0, 28, 10), GRID_WIDTH, GRID_HEIGHT),
+        (1, 1)
+    );
+    assert_eq!(
+        grid_scale(Rect::new(0, 0, 43, 70), GRID_WIDTH, GRID_HEIGHT),
+        (2, 2)
+    );
+    assert_eq!(
+        grid_scale(Rect::new(0, 0, 65, 25), GRID_WIDTH, GRID_HEIGHT),
+        (3, 2)
+    );
+    assert_eq!(
+        grid_scale(Rect::new(0, 0, 5, 5), GRID_WIDTH, GRID_HEIGHT),
+        (1, 1)
+    );
+    assert_eq!(grid_scale(Rect::new(0, 0, 40, 20), 0, 0), (1, 1));
+
+    let lines = vec!["ab".to_string(), "c○".to_string()];
+    assert_eq!(
+        upscale_grid_lines(&lines, 2, 2),
+        vec!["aabb", "aabb", "cc○○", "cc○○"]
+    );
+    assert_eq!(upscale_grid_lines(&lines, 1, 1), lines);
+}
+
+#[test]
+fn test_wide_dashboard_draws_every_panel() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let state = DashboardState::from_agent(&Protozoa::new(50.0, 25.0), &dish);
+    let mut terminal = Terminal::new(TestBackend::new(300, 90)).unwrap();
+    terminal
+        .draw(|f| draw_dashboard(f, vec![".".repeat(208); 88], &state))
+        .unwrap();
+
+    let layout = DashboardLayout::for_area(Rect::new(0, 0, 300, 90));
+    let detail = layout.detail.unwrap();
+    assert!(find_on_row(&terminal, 0, "Petri Dish").unwrap() < layout.main.right());
+    assert!(find_on_row(&terminal, 0, "Landmarks").unwrap() >= detail.x);
+    assert!(find_on_row(&terminal, layout.events.y, "Events").unwrap() >= detail.x);
+    let spatial = find_on_row(&terminal, layout.spatial.y, "Spatial Memory").unwrap();
+    assert!(spatial >= layout.spatial.x && spatial < detail.x);
+    // Upscaled 2×2: the agent's cell (row 5, column 10) covers a 2×2 block
+    let agent = (layout.spatial.x + 1 + 20, layout.spatial.y + 1 + 10);
+    for y in [agent.1, agent.1 + 1] {
+        assert_eq!(find_on_row(&terminal, y, "○○"), Some(agent.0));
+    }
+}
@@ -0,0 +1,206 @@
+//! Tests for the built-in demo tour and its playback.
+
+use protozoa_rust::simulation::agent::AgentMode;
+use protozoa_rust::simulation::demo::{DemoAction, DemoPlayer, DemoTour};
+use protozoa_rust::simulation::params::{DEMO_SEED, SENSOR_ANGLE, SENSOR_DIST};
+
+/// A player cut to segment `index` of the built-in tour.
+fn at_segment(index: usize) -> DemoPlayer {
+    let mut player = DemoPlayer::new(DemoTour::builtin(DEMO_SEED));
+    for _ in 0..index {
+        player.skip();
+    }
+    player
+}
+
+/// Steps `player` until its segment clock reaches `tick`.
+fn play_to(player: &mut DemoPlayer, tick: u64) {
+    while player.tick() < tick {
+        player.step();
+    }
+}
+
+#[test]
+fn test_builtin_tour_covers_the_showcase() {
+    let tour = DemoTour::builtin(DEMO_SEED);
+    assert_eq!(tour.seed, DEMO_SEED);
+    let titles: Vec<&str> = tour.segments.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        [
+            "Exploration",
+            "Landmark navigation",
+            "Panic",
+            "Morphogenesis"
+        ]
+    );
+    for segment in &tour.segments {
+        assert!(!segment.caption.is_empty());
+        assert!(segment.ticks > 0);
+        assert!(segment.cues.windows(2).all(|w| w[0].tick <= w[1].tick));
+        for cue in &segment.cues {
+            assert!(cue.tick >= 1 && cue.tick <= segment.ticks, "{cue:?}");
+            assert!(!cue.note.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_player_walks_the_segments_in_order() {
+    let tour = DemoTour::builtin(DEMO_SEED);
+    let total: u64 = tour.segments.iter().map(|s| s.ticks).sum();
+    let count = tour.segments.len();
+    let mut player = DemoPlayer::new(tour);
+    assert_eq!(player.heading(), "Demo 1/4 · Exploration");
+    assert_eq!(player.note(), None);
+
+    let mut headings = vec![player.heading()];
+    let mut steps = 0;
+    while !player.is_finished() {
+        player.step();
+        steps += 1;
+        if headings.last() != Some(&player.heading()) {
+            headings.push(player.heading());
+        }
+    }
+    // Each segment runs its length, then one step cuts to the next
+    assert_eq!(steps, total + count as u64);
+    assert_eq!(headings.len(), count + 1);
+    assert_eq!(headings[1], "Demo 2/4 · Landmark navigation");
+    assert_eq!(headings[count], "Demo 4/4 · End of the tour");
+    assert_eq!(player.segment().title, "Morphogenesis");
+
+    let tick = player.tick();
+    player.step();
+    assert_eq!(player.tick(), tick, "nothing plays after the tour");
+}
+
+#[test]
+fn test_cues_fire_through_the_event_schedule() {
+    let mut player = at_segment(1);
+    assert_eq!(player.tick(), 0);
+    let segment = player.segment().clone();
+    let events = segment
+        .cues
+        .iter()
+        .filter(|c| matches!(c.action, DemoAction::Event(_)))
+        .count();
+    assert_eq!(player.run().dish.events.pending().len(), events);
+    assert!(
+        player
+            .run()
+            .agent
+            .episodic_memory
+            .iter()
+            .all(|l| !l.is_pinned())
+    );
+
+    player.step();
+    let run = player.run();
+    assert!(run.dish.events.is_empty());
+    assert_eq!(run.dish.events.fired(), events);
+    assert_eq!(
+        run.dish.sources.len(),
+        1,
+        "the ring is cleared, one source added"
+    );
+    let landmark = run
+        .agent
+        .episodic_memory
+        .iter()
+        .find(|l| l.is_pinned())
+        .unwrap();
+    assert!((landmark.x - run.dish.sources[0].x).abs() < 1e-9);
+    assert_eq!(player.note(), Some(segment.cues[2].note.as_str()));
+}
+
+#[test]
+fn test_landmark_segment_sends_the_hungry_agent_to_its_landmark() {
+    let mut player = at_segment(1);
+    play_to(&mut player, 1);
+    let landmark = *player
+        .run()
+        .agent
+        .episodic_memory
+        .iter()
+        .find(|l| l.is_pinned())
+        .unwrap();
+    let start = landmark.distance_to(player.run().agent.x, player.run().agent.y);
+    let (mut closest, mut navigated) = (start, false);
+    while player.tick() < player.segment().ticks {
+        player.step();
+        let run = player.run();
+        closest = closest.min(landmark.distance_to(run.agent.x, run.agent.y));
+        navigated |= run.agent.current_mode(&run.dish) == AgentMode::GoalNav;
+    }
+    assert!(navigated);
+    assert!(closest < 10.0 && start > 30.0, "{closest} from {start}");
+}
+
+#[test]
+fn test_panic_segment_panics_once_the_source_is_gone() {
+    let mut player = at_segment(2);
+    let wipe = player.segment().cues[0].tick;
+    let pulse = player.segment().cues[1].tick;
+    play_to(&mut player, wipe - 1);
+    assert_eq!(player.run().dish.sources.len(), 1);
+    assert_eq!(player.note(), None);
+
+    let mut panicked = false;
+    while player.tick() < pulse - 1 {
+        player.step();
+        let run = player.run();
+        assert!(run.dish.sources.is_empty());
+        panicked |= run.agent.current_mode(&run.dish) == AgentMode::Panicking;
+    }
+    assert!(panicked);
+    assert_eq!(player.note(), Some("the source is wiped out"));
+    player.step();
+    assert_eq!(player.run().dish.sources.len(), 1, "the pulse lands");
+}
+
+#[test]
+fn test_morphogenesis_segment_rewrites_the_body_plan() {
+    let mut player = at_segment(3);
+    let cues = player.segment().cues.clone();
+    play_to(&mut player, cues[0].tick - 1);
+    let morphology = &player.run().agent.morphology;
+    assert_eq!(morphology.sensor_dist, SENSOR_DIST);
+    assert_eq!(morphology.sensor_angle, SENSOR_ANGLE);
+
+    play_to(&mut player, cues[0].tick);
+    assert!(player.run().agent.morphology.sensor_dist > SENSOR_DIST);
+    assert_eq!(player.note(), Some(cues[0].note.as_str()));
+    play_to(&mut player, cues[1].tick);
+    let morphology = &player.run().agent.morphology;
+    assert!(morphology.sensor_angle > SENSOR_ANGLE);
+    assert_eq!(player.note(), Some(cues[1].note.as_str()));
+}
+
+#[test]
+fn test_tours_replay_exactly_and_restart() {
+    let positions = |seed: u64| {
+        let mut player = DemoPlayer::new(DemoTour::builtin(seed));
+        (0..100)
+            .map(|_| {
+                player.step();
+                (player.run().agent.x, player.run().agent.y)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(positions(3), positions(3));
+    assert_ne!(positions(3), positions(4));
+
+    let mut player = at_segment(3);
+    play_to(&mut player, 50);
+    player.restart();
+    assert_eq!(player.heading(), "Demo 1/4 · Exploration");
+    assert_eq!(player.tick(), 0);
+    player.skip();
+    player.skip();
+    player.skip();
+    player.skip();
+    assert!(player.is_finished());
+    player.skip();
+    assert!(player.is_finished());
+}
@@ -0,0 +1,88 @@
+//! Tests for the `doctor` self-checks.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::doctor::{
+    Check, Diagnosis, check_determinism, check_dish_config, check_nan_stress, platform, run_digest,
+};
+
+#[test]
+fn test_run_digest_is_deterministic_and_seed_sensitive() {
+    let config = DishConfig::default();
+    assert_eq!(run_digest(&config, 7, 50), run_digest(&config, 7, 50));
+    assert_ne!(run_digest(&config, 7, 50), run_digest(&config, 8, 50));
+    assert_ne!(run_digest(&config, 7, 50), run_digest(&config, 7, 51));
+
+    let wide = DishConfig {
+        width: 150.0,
+        ..DishConfig::default()
+    };
+    assert_ne!(run_digest(&config, 7, 50), run_digest(&wide, 7, 50));
+}
+
+#[test]
+fn test_simulation_checks_pass_on_defaults() {
+    let config = DishConfig::default();
+    let determinism = check_determinism(&config);
+    assert!(determinism.passed, "{}", determinism.detail);
+    assert!(determinism.detail.starts_with("digest "));
+    let stress = check_nan_stress(&config);
+    assert!(stress.passed, "{}", stress.detail);
+
+    let diagnosis = Diagnosis::simulation(Ok(config));
+    assert_eq!(diagnosis.failures(), 0);
+    assert_eq!(diagnosis.checks[0], determinism, "same digest every time");
+}
+
+#[test]
+fn test_invalid_dish_config_fails_its_check_only() {
+    let bad = DishConfig {
+        width: -1.0,
+        ..DishConfig::default()
+    };
+    assert!(!check_dish_config(Ok(bad)).passed);
+    let unreadable = check_dish_config(Err("no such file".into()));
+    assert_eq!(unreadable, Check::fail("dish config", "no such file"));
+
+    // The dish checks fall back to the defaults
+    let diagnosis = Diagnosis::simulation(Err("no such file".into()));
+    assert_eq!(diagnosis.failures(), 1);
+    assert!(diagnosis.checks[..2].iter().all(|check| check.passed));
+}
+
+#[test]
+fn test_guarded_check_reports_panics() {
+    let check = Check::guarded("boom", || panic!("sensor {} exploded", 2));
+    assert!(!check.passed);
+    assert_eq!(check.detail, "panicked: sensor 2 exploded");
+    assert_eq!(
+        Check::guarded("fine", || Ok("42".into())),
+        Check::pass("fine", "42")
+    );
+    assert_eq!(
+        Check::from_result("err", Err("bad".into())),
+        Check::fail("err", "bad")
+    );
+}
+
+#[test]
+fn test_diagnosis_report_lists_every_check() {
+    let mut diagnosis = Diagnosis::default();
+    diagnosis.push(Check::pass("determinism", "digest 00"));
+    diagnosis.push(Check::fail("renderer", "panicked: oops"));
+    let report = diagnosis.to_string();
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], platform());
+    assert!(lines[0].contains(std::env::consts::OS));
+    assert_eq!(lines[1], "ok    determinism  digest 00");
+    assert_eq!(lines[2], "FAIL  renderer     panicked: oops");
+    assert_eq!(lines[3], "1/2 checks passed");
+    assert_eq!(diagnosis.failures(), 1);
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_renderer_smoke_test_passes() {
+    let check = protozoa_rust::ui::terminal::renderer_smoke_test();
+    assert!(check.passed, "{}", check.detail);
+    assert_eq!(check.name, "renderer");
+}
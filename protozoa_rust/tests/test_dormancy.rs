@@ -0,0 +1,151 @@
+//! Tests for dormancy (sporulation) of starving agents.
+
+use protozoa_rust::simulation::agent::{AgentMode, Protozoa};
+use protozoa_rust::simulation::environment::{NutrientSource, PetriDish};
+use protozoa_rust::simulation::kernel::Kernel;
+use protozoa_rust::simulation::params::{
+    BASE_METABOLIC_COST, DORMANCY_THRESHOLD, DORMANT_METABOLIC_FACTOR, DORMANT_SAMPLE_INTERVAL,
+};
+use protozoa_rust::simulation::reserve::EnergyReserve;
+
+/// A hungry agent with no reserve that goes dormant below `threshold`.
+fn hungry(threshold: f64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(5);
+    agent.energy = 0.1;
+    agent.reserve = EnergyReserve::NONE;
+    agent.morphology.dormancy_threshold = threshold;
+    agent
+}
+
+fn tick(agent: &mut Protozoa, dish: &PetriDish) {
+    agent.sense(dish);
+    agent.update_state(dish);
+}
+
+/// Ticks until the agent dies, capped at `limit`.
+fn lifetime(mut agent: Protozoa, dish: &PetriDish, limit: u32) -> u32 {
+    let mut ticks = 0;
+    while agent.is_alive() && ticks < limit {
+        tick(&mut agent, dish);
+        ticks += 1;
+    }
+    ticks
+}
+
+#[test]
+fn test_starving_agent_goes_dormant() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = hungry(0.2);
+    tick(&mut agent, &dish);
+    assert!(agent.is_dormant());
+    assert_eq!(agent.dormant_since, Some(1));
+    assert_eq!(agent.current_mode(&dish), AgentMode::Dormant);
+
+    // A dormant agent holds still
+    let (x, y) = (agent.x, agent.y);
+    for _ in 0..50 {
+        tick(&mut agent, &dish);
+    }
+    assert_eq!((agent.x, agent.y, agent.speed), (x, y, 0.0));
+    assert!(agent.is_dormant());
+}
+
+#[test]
+fn test_dormancy_is_off_by_default() {
+    assert_eq!(DORMANCY_THRESHOLD, 0.0);
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = hungry(DORMANCY_THRESHOLD);
+    agent.energy = 0.0;
+    for _ in 0..10 {
+        tick(&mut agent, &dish);
+    }
+    assert!(!agent.is_dormant());
+    assert_ne!(agent.current_mode(&dish), AgentMode::Dormant);
+}
+
+#[test]
+fn test_a_landmark_to_head_for_keeps_it_awake() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = hungry(0.2);
+    // Near enough to reach on what is left
+    agent.episodic_memory.maybe_store(75.0, 30.0, 0.9, 0);
+    for _ in 0..10 {
+        tick(&mut agent, &dish);
+    }
+    assert!(!agent.is_dormant());
+    assert_eq!(agent.current_mode(&dish), AgentMode::GoalNav);
+}
+
+#[test]
+fn test_dormancy_slows_starvation() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = hungry(0.2);
+    tick(&mut agent, &dish);
+    let before = agent.energy;
+    // A full sampling cycle costs the reduced metabolism and one sample
+    for _ in 0..DORMANT_SAMPLE_INTERVAL {
+        tick(&mut agent, &dish);
+    }
+    let expected =
+        f64::from(DORMANT_SAMPLE_INTERVAL) * BASE_METABOLIC_COST * DORMANT_METABOLIC_FACTOR
+            + agent.morphology.sample_cost();
+    assert!((before - agent.energy - expected).abs() < 1e-12);
+
+    let awake = lifetime(hungry(0.0), &dish, 100_000);
+    let dormant = lifetime(hungry(0.2), &dish, 100_000);
+    assert!(awake < 100_000, "the awake agent starves");
+    assert!(dormant > 5 * awake, "{dormant} vs {awake}");
+}
+
+#[test]
+fn test_dormant_agent_samples_rarely() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = hungry(0.2);
+    tick(&mut agent, &dish);
+    let mut samples = 0;
+    for _ in 0..10 * DORMANT_SAMPLE_INTERVAL {
+        tick(&mut agent, &dish);
+        samples += u32::from(agent.sampled);
+    }
+    assert_eq!(samples, 10);
+}
+
+#[test]
+fn test_food_wakes_a_dormant_agent() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = hungry(0.2);
+    tick(&mut agent, &dish);
+    assert!(agent.is_dormant());
+
+    dish.sources.push(NutrientSource {
+        x: 50.0,
+        y: 25.0,
+        radius: 10.0,
+        intensity: 1.0,
+        decay_rate: 1.0,
+        species: 0,
+        transient: false,
+        kernel: Kernel::Gaussian,
+    });
+    for _ in 0..DORMANT_SAMPLE_INTERVAL {
+        tick(&mut agent, &dish);
+    }
+    assert!(!agent.is_dormant(), "woken within one sampling interval");
+    assert_ne!(agent.current_mode(&dish), AgentMode::Dormant);
+    let energy = agent.energy;
+    for _ in 0..20 {
+        tick(&mut agent, &dish);
+    }
+    assert!(!agent.is_dormant(), "does not fall back asleep on food");
+    assert!(agent.energy > energy, "it eats again");
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_dashboard_names_the_dormant_mode() {
+    use protozoa_rust::ui::render::format_metrics_overlay;
+
+    let lines = format_metrics_overlay(0.1, AgentMode::Dormant, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    assert_eq!(lines[1], "Mode: DORMANT");
+}
@@ -0,0 +1,156 @@
+//! Tests for the extended Kalman filter inference scheme and its
+//! comparison with gradient descent on VFE.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::inference::{
+    BeliefState, GenerativeModel, InferenceScheme, ReceptorObservation, ekf_thermal_update,
+    ekf_update,
+};
+use protozoa_rust::simulation::params::{
+    BELIEF_LEARNING_RATE, EKF_VARIANCE_FLOOR, UNCERTAINTY_GROWTH,
+};
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+
+fn beliefs() -> BeliefState {
+    BeliefState::new(50.0, 25.0, 0.0)
+}
+
+/// Nutrient error after each sample of `readings` (a uniform field, read
+/// by the pair), updated by `scheme`.
+fn errors(scheme: InferenceScheme, readings: &[f64]) -> Vec<f64> {
+    let model = GenerativeModel::new();
+    let mut beliefs = beliefs();
+    readings
+        .iter()
+        .map(|&value| {
+            let obs = ReceptorObservation::pair((value, value));
+            scheme.update(&mut beliefs, &obs, (0.5, 0.5), &model, BELIEF_LEARNING_RATE);
+            (beliefs.mean.nutrient - value).abs()
+        })
+        .collect()
+}
+
+#[test]
+fn test_schemes_by_name_and_config() {
+    assert_eq!(InferenceScheme::default(), InferenceScheme::Gradient);
+    assert_eq!(InferenceScheme::parse("EKF"), Some(InferenceScheme::Ekf));
+    assert_eq!(InferenceScheme::parse("kalman"), None);
+    let names: Vec<&str> = InferenceScheme::ALL.iter().map(|s| s.name()).collect();
+    assert_eq!(names, ["gradient", "ekf"]);
+
+    assert_eq!(DishConfig::default().inference, InferenceScheme::Gradient);
+    let config = DishConfig::from_toml_str("inference = \"ekf\"").unwrap();
+    assert_eq!(config.inference, InferenceScheme::Ekf);
+    assert!(DishConfig::from_toml_str("inference = \"kalman\"").is_err());
+    assert_eq!(
+        DishConfig::from_toml_str(&config.to_toml_string()).unwrap(),
+        config
+    );
+}
+
+#[test]
+fn test_pair_update_is_the_kalman_gain() {
+    let model = GenerativeModel::new();
+    let mut state = beliefs();
+    let (prior_mean, prior_var) = (state.mean.nutrient, state.covariance.nutrient_var);
+    let precision = model.sensory_precision.left;
+    ekf_update(&mut state, &ReceptorObservation::pair((0.9, 0.9)), &model);
+    // Heading 0: the pair's angle terms cancel, leaving a scalar filter
+    let information = 1.0 / prior_var + 2.0 * precision;
+    let expected = prior_mean + 2.0 * precision * (0.9 - prior_mean) / information;
+    assert!((state.mean.nutrient - expected).abs() < 1e-12);
+    assert!((state.covariance.nutrient_var - 1.0 / information).abs() < 1e-12);
+    assert_eq!(state.mean.angle, 0.0);
+}
+
+#[test]
+fn test_no_readings_change_nothing() {
+    let model = GenerativeModel::new();
+    let mut state = beliefs();
+    let before = (state.mean.nutrient, state.covariance.nutrient_var);
+    ekf_update(&mut state, &[], &model);
+    assert_eq!((state.mean.nutrient, state.covariance.nutrient_var), before);
+}
+
+#[test]
+fn test_thermal_update_weighs_the_readings_by_precision() {
+    let mut model = GenerativeModel::new();
+    model.sensory_precision.thermal_left = 10.0;
+    model.sensory_precision.thermal_right = 0.5;
+    let mut state = beliefs();
+    ekf_thermal_update(&mut state, (0.9, 0.1), &model);
+    // The precise left reading wins
+    assert!(state.mean.temperature > 0.5);
+    assert!(state.covariance.temperature_var < 0.25);
+    for _ in 0..200 {
+        ekf_thermal_update(&mut state, (0.9, 0.1), &model);
+    }
+    assert_eq!(state.covariance.temperature_var, EKF_VARIANCE_FLOOR);
+    assert!((state.mean.temperature - (10.0 * 0.9 + 0.5 * 0.1) / 10.5).abs() < 0.01);
+}
+
+#[test]
+fn test_ekf_variance_reaches_its_steady_state() {
+    let model = GenerativeModel::new();
+    let mut state = beliefs();
+    let obs = ReceptorObservation::pair((0.6, 0.6));
+    for _ in 0..100 {
+        InferenceScheme::Ekf.update(&mut state, &obs, (0.5, 0.5), &model, 0.0);
+    }
+    // Growth by γ and the pair's information balance at (1 − 1/γ) / Σπ
+    let steady = (1.0 - 1.0 / UNCERTAINTY_GROWTH) / (2.0 * model.sensory_precision.left);
+    assert!((state.covariance.nutrient_var - steady.max(EKF_VARIANCE_FLOOR)).abs() < 1e-6);
+    assert!((state.mean.nutrient - 0.6).abs() < 1e-4);
+}
+
+#[test]
+fn test_same_readings_both_schemes_converge() {
+    // A step from 0.2 to 0.8, with a little alternating jitter
+    let readings: Vec<f64> = (0..80)
+        .map(|i| {
+            let level = if i < 40 { 0.2 } else { 0.8 };
+            level + if i % 2 == 0 { 0.02 } else { -0.02 }
+        })
+        .collect();
+    let ekf = errors(InferenceScheme::Ekf, &readings);
+    let gradient = errors(InferenceScheme::Gradient, &readings);
+    let worst = |errors: &[f64], from: usize, to: usize| {
+        errors[from..to].iter().copied().fold(0.0, f64::max)
+    };
+    // On the first level the filter settles within a few samples, while a
+    // gradient step of learning rate × Σπ above 1 overshoots the jitter
+    assert!(worst(&ekf, 5, 40) < 0.05, "ekf {}", worst(&ekf, 5, 40));
+    assert!(worst(&gradient, 5, 40) > worst(&ekf, 5, 40));
+    // A settled filter trusts its belief and follows the step more slowly,
+    // but it gets there
+    assert!(ekf[45] > gradient[45]);
+    assert!(ekf[79] < 0.02, "ekf {}", ekf[79]);
+    // And neither scheme blows up
+    assert!(
+        ekf.iter()
+            .chain(&gradient)
+            .all(|e| e.is_finite() && *e <= 1.0)
+    );
+}
+
+#[test]
+fn test_agents_run_the_configured_scheme() {
+    let mut generator = GeneratorConfig::default();
+    generator.dish.inference = InferenceScheme::Ekf;
+    let scenario = Scenario::Generated {
+        generator,
+        spawn: SpawnConfig::default(),
+    };
+    let mut run = scenario.build(7);
+    assert_eq!(run.agent.inference, InferenceScheme::Ekf);
+    for _ in 0..200 {
+        run.agent.sense(&run.dish);
+        run.agent.update_state(&run.dish);
+        run.dish.update();
+    }
+    assert!(run.agent.beliefs.check_finite().is_ok());
+    let var = run.agent.beliefs.covariance.nutrient_var;
+    assert!((EKF_VARIANCE_FLOOR..0.25).contains(&var), "variance {var}");
+}
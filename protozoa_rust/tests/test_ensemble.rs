@@ -0,0 +1,116 @@
+//! Tests for concurrent replicate runs and the aggregate dashboard.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::ensemble::{Ensemble, Stat};
+use protozoa_rust::simulation::params::ENSEMBLE_HISTORY_LEN;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::aggregate::{band_bounds, band_series, draw_aggregate_dashboard};
+#[cfg(feature = "tui")]
+use ratatui::Terminal;
+#[cfg(feature = "tui")]
+use ratatui::backend::TestBackend;
+
+#[test]
+fn test_stat_from_values() {
+    let stat = Stat::from_values(&[1.0, 2.0, 3.0, 4.0]);
+    assert!((stat.mean - 2.5).abs() < 1e-12);
+    assert!((stat.std - 1.25_f64.sqrt()).abs() < 1e-12);
+    assert!((stat.min - 1.0).abs() < 1e-12);
+    assert!((stat.max - 4.0).abs() < 1e-12);
+    assert!((stat.lower() - (2.5 - stat.std)).abs() < 1e-12);
+
+    let empty = Stat::from_values(&[]);
+    assert!(empty.mean.abs() < 1e-12);
+}
+
+#[test]
+fn test_ensemble_steps_all_runs() {
+    let mut ensemble = Ensemble::new(4, &SpawnConfig::default());
+    assert_eq!(ensemble.len(), 4);
+
+    for _ in 0..10 {
+        ensemble.step();
+    }
+
+    assert_eq!(ensemble.tick, 10);
+    assert_eq!(ensemble.history.len(), 10);
+    for run in &ensemble.runs {
+        assert_eq!(run.agent.tick_count, 10);
+    }
+
+    let sample = ensemble.history.back().unwrap();
+    assert_eq!(sample.tick, 10);
+    assert!(sample.energy.min <= sample.energy.mean && sample.energy.mean <= sample.energy.max);
+    assert!(sample.coverage.mean > 0.0);
+}
+
+#[test]
+fn test_ensemble_history_is_bounded() {
+    let mut ensemble = Ensemble::new(2, &SpawnConfig::default());
+    for _ in 0..ENSEMBLE_HISTORY_LEN + 5 {
+        ensemble.step();
+    }
+    assert_eq!(ensemble.history.len(), ENSEMBLE_HISTORY_LEN);
+    assert_eq!(ensemble.history.front().unwrap().tick, 6);
+}
+
+#[test]
+fn test_same_seed_gives_same_ensemble() {
+    let run = |seed: u64| {
+        let mut ensemble =
+            Ensemble::with_dish_config(3, &SpawnConfig::default(), &DishConfig::default(), seed);
+        for _ in 0..200 {
+            ensemble.step();
+        }
+        ensemble
+    };
+    let (first, second) = (run(7), run(7));
+    for (a, b) in first.runs.iter().zip(&second.runs) {
+        assert_eq!((a.agent.x, a.agent.y), (b.agent.x, b.agent.y));
+        assert_eq!(a.agent.energy, b.agent.energy);
+    }
+    assert_eq!(first.history, second.history);
+    // Replicates differ from each other and from another seed
+    assert_ne!(first.runs[0].agent.x, first.runs[1].agent.x);
+    assert_ne!(first.history, run(8).history);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_band_series_and_bounds() {
+    let mut ensemble = Ensemble::new(3, &SpawnConfig::default());
+    for _ in 0..5 {
+        ensemble.step();
+    }
+    let history: Vec<_> = ensemble.history.iter().copied().collect();
+    let series = band_series(&history, |s| s.energy);
+
+    assert_eq!(series.mean.len(), 5);
+    for ((lo, mean), hi) in series.lower.iter().zip(&series.mean).zip(&series.upper) {
+        assert!(lo.1 <= mean.1 && mean.1 <= hi.1);
+    }
+
+    let [y_lo, y_hi] = band_bounds(&series);
+    assert!(y_lo < y_hi);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_aggregate_dashboard_renders() {
+    let mut ensemble = Ensemble::new(2, &SpawnConfig::default());
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+
+    // Empty history must render too
+    terminal
+        .draw(|f| draw_aggregate_dashboard(f, &[], ensemble.len(), &[], None))
+        .unwrap();
+
+    for _ in 0..3 {
+        ensemble.step();
+    }
+    let history: Vec<_> = ensemble.history.iter().copied().collect();
+    terminal
+        .draw(|f| draw_aggregate_dashboard(f, &history, ensemble.len(), &[], None))
+        .unwrap();
+}
@@ -48,7 +48,7 @@ fn test_concentration_never_negative_inside_dish() {
         for y in (0..=50).map(|i| i as f64) {
             let val = dish.get_concentration(x, y);
             assert!(
-                val >= 0.0 && val <= 1.0,
+                (0.0..=1.0).contains(&val),
                 "Concentration at ({x}, {y}) = {val} is out of bounds [0, 1]"
             );
         }
@@ -0,0 +1,169 @@
+//! Tests for the `Environment` trait and the worlds implementing it.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{Environment, PetriDish};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::params::{AMBIENT_TEMPERATURE, NEUTRAL_PH};
+use protozoa_rust::simulation::planning::{Action, AgentState};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+/// Concentration rising linearly toward +x, fading a little every tick.
+struct Ramp {
+    width: f64,
+    height: f64,
+    level: f64,
+}
+
+impl Ramp {
+    fn new() -> Self {
+        Self {
+            width: 80.0,
+            height: 40.0,
+            level: 1.0,
+        }
+    }
+}
+
+impl Environment for Ramp {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            self.level * x / self.width
+        } else {
+            -1.0
+        }
+    }
+
+    fn gradient(&self, x: f64, y: f64) -> (f64, f64) {
+        if self.contains(x, y) {
+            (self.level / self.width, 0.0)
+        } else {
+            (0.0, 0.0)
+        }
+    }
+
+    fn update(&mut self) {
+        self.level *= 0.99;
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+}
+
+/// Runs `ticks` sense/act cycles of `agent` in any world.
+fn run(world: &mut dyn Environment, agent: &mut Protozoa, ticks: usize) {
+    for _ in 0..ticks {
+        world.update();
+        agent.sense(world);
+        agent.update_state(world);
+    }
+}
+
+#[test]
+fn test_agent_lives_in_a_custom_world() {
+    let mut ramp = Ramp::new();
+    let mut agent = Protozoa::with_heading(40.0, 20.0, 0.0);
+    agent.seed_streams(1);
+    agent.sense(&ramp);
+    assert!(
+        (agent.val_l - agent.val_r).abs() < 1e-9,
+        "ramp is flat in y"
+    );
+    assert_eq!(agent.species_l, vec![agent.val_l]);
+    assert_eq!(agent.thermo_l, AMBIENT_TEMPERATURE);
+    assert_eq!(agent.ph_r, NEUTRAL_PH);
+
+    run(&mut ramp, &mut agent, 200);
+    assert!(ramp.level < 1.0, "the world ticked");
+    assert!((0.0..=80.0).contains(&agent.x) && (0.0..=40.0).contains(&agent.y));
+    assert!(agent.energy.is_finite());
+}
+
+#[test]
+fn test_dish_through_the_trait_matches_the_dish() {
+    let dish = PetriDish::generate(8, &GeneratorConfig::default());
+    let world: &dyn Environment = &dish;
+    assert_eq!(world.bounds(), (dish.width, dish.height));
+    for (x, y) in [(12.0, 9.0), (50.0, 25.0), (97.0, 3.0), (-2.0, 10.0)] {
+        assert_eq!(world.get_concentration(x, y), dish.get_concentration(x, y));
+        assert_eq!(world.gradient(x, y), dish.gradient(x, y));
+        assert_eq!(world.get_temperature(x, y), dish.get_temperature(x, y));
+        assert_eq!(world.get_ph(x, y), dish.get_ph(x, y));
+        assert_eq!(world.get_light(x, y), dish.get_light(x, y));
+        assert_eq!(world.is_blocked(x, y), dish.is_blocked(x, y));
+    }
+
+    // An agent driven through `dyn Environment` matches one driven directly
+    let (mut direct, mut boxed) = (dish.clone(), dish);
+    let mut a = Protozoa::with_heading(30.0, 20.0, 0.5);
+    let mut b = Protozoa::with_heading(30.0, 20.0, 0.5);
+    a.seed_streams(4);
+    b.seed_streams(4);
+    for _ in 0..100 {
+        direct.update();
+        a.sense(&direct);
+        a.update_state(&direct);
+    }
+    run(&mut boxed, &mut b, 100);
+    assert_eq!((a.x, a.y, a.energy), (b.x, b.y, b.energy));
+}
+
+#[test]
+fn test_worlds_can_be_swapped_at_runtime() {
+    let mut worlds: Vec<Box<dyn Environment>> = vec![
+        Box::new(Ramp::new()),
+        Box::new(PetriDish::from_preset(
+            EnvironmentPreset::Gradient,
+            100.0,
+            50.0,
+        )),
+    ];
+    for world in &mut worlds {
+        let mut agent = Protozoa::with_heading(20.0, 20.0, 0.0);
+        agent.seed_streams(2);
+        run(world.as_mut(), &mut agent, 50);
+        let (width, height) = world.bounds();
+        assert!(agent.x <= width && agent.y <= height);
+    }
+}
+
+#[test]
+fn test_learned_map_is_a_world_model() {
+    let mut grid: SpatialGrid<20, 10> = SpatialGrid::new(100.0, 50.0);
+    for _ in 0..5 {
+        grid.update(52.0, 27.0, 0.8);
+        grid.update(47.0, 27.0, 0.4);
+    }
+    assert_eq!(Environment::bounds(&grid), (100.0, 50.0));
+    assert!((grid.get_concentration(52.0, 27.0) - grid.expected(52.0, 27.0)).abs() < 1e-12);
+    let (g_x, g_y) = Environment::gradient(&grid, 52.0, 27.0);
+    assert!(g_x > 0.0 && g_y.abs() < 1e-12);
+    assert_eq!(Environment::gradient(&grid, 5.0, 45.0), (0.0, 0.0));
+
+    let before = grid.clone();
+    Environment::update(&mut grid);
+    assert_eq!(grid.total_visits(), before.total_visits());
+}
+
+#[test]
+fn test_rollout_step_runs_in_any_model() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    let priors: SpatialGrid<20, 10> = SpatialGrid::new(100.0, 50.0);
+    let state = AgentState::new(50.0, 25.0, 0.0, 1.0, 0.5);
+
+    // The planner's model (learned priors) and the true dish give different
+    // predictions for the same action
+    let imagined = state.step(Action::Straight, &priors);
+    let actual = state.step(Action::Straight, &dish);
+    let moved_apart = (imagined.x - actual.x).abs() > 1e-6;
+    assert!(moved_apart || (imagined.energy - actual.energy).abs() > 1e-6);
+
+    // Rollouts stay inside the stepped world's bounds
+    let ramp = Ramp::new();
+    let mut edge = AgentState::new(79.5, 39.5, 0.7, 1.0, 0.5);
+    for _ in 0..20 {
+        edge = edge.step(Action::Straight, &ramp);
+        assert!(edge.x <= 80.0 && edge.y <= 40.0);
+    }
+}
@@ -1,6 +1,7 @@
 //! Tests for episodic memory module.
 
-use protozoa_rust::simulation::memory::{EpisodicMemory, Landmark};
+use protozoa_rust::simulation::memory::{EpisodicMemory, Landmark, LandmarkProvenance};
+use protozoa_rust::simulation::params::MAX_LANDMARKS;
 
 #[test]
 fn test_landmark_value_decay() {
@@ -129,3 +130,70 @@ fn test_best_distant_landmark_excludes_current() {
     let lm = best.unwrap();
     assert!((lm.x - 80.0).abs() < 1e-10);
 }
+
+#[test]
+fn test_pinned_landmark_does_not_decay() {
+    let mut lm = Landmark::pinned(10.0, 10.0, 0);
+    assert_eq!(lm.provenance, LandmarkProvenance::User);
+    for _ in 0..100 {
+        lm.decay();
+    }
+    assert!((lm.reliability - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_pin_promotes_nearby_and_survives_eviction() {
+    let mut mem = EpisodicMemory::new();
+    mem.maybe_store(10.0, 10.0, 0.8, 0);
+
+    // Pinning next to a discovered landmark promotes it instead of duplicating
+    assert!(mem.pin(11.0, 10.0, 5));
+    assert_eq!(mem.count(), 1);
+    assert!(mem.iter().next().unwrap().is_pinned());
+
+    // Fill memory with discovered landmarks of higher value
+    for i in 1..=MAX_LANDMARKS {
+        #[allow(clippy::cast_precision_loss)]
+        mem.maybe_store(i as f64 * 10.0 + 5.0, 40.0, 0.99, 10);
+    }
+    assert!(mem.iter().any(Landmark::is_pinned));
+}
+
+#[test]
+fn test_pin_fails_when_all_slots_pinned() {
+    let mut mem = EpisodicMemory::new();
+    for i in 0..MAX_LANDMARKS {
+        #[allow(clippy::cast_precision_loss)]
+        let placed = mem.pin(i as f64 * 10.0, 10.0, 0);
+        assert!(placed);
+    }
+    assert!(!mem.pin(5.0, 40.0, 0));
+
+    // Discovered landmarks cannot evict pins either
+    mem.maybe_store(5.0, 40.0, 1.0, 0);
+    assert!(mem.iter().all(Landmark::is_pinned));
+}
+
+#[test]
+fn test_unpin_only_removes_user_landmarks() {
+    let mut mem = EpisodicMemory::new();
+    mem.maybe_store(10.0, 10.0, 0.9, 0);
+    mem.pin(50.0, 25.0, 0);
+
+    assert!(!mem.unpin(10.0, 10.0));
+    assert!(mem.unpin(51.0, 25.0));
+    assert_eq!(mem.count(), 1);
+    assert!(!mem.iter().next().unwrap().is_pinned());
+}
+
+#[test]
+fn test_goal_navigation_prefers_pinned_landmark() {
+    let mut mem = EpisodicMemory::new();
+    mem.maybe_store(10.0, 10.0, 1.0, 0);
+    mem.pin(80.0, 40.0, 0);
+    mem.update_on_visit(80.0, 40.0, 0.1, 1);
+
+    let best = mem.best_distant_landmark(50.0, 25.0, 5.0).unwrap();
+    assert!(best.is_pinned());
+    assert!((best.x - 80.0).abs() < 1e-12);
+}
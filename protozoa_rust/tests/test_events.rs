@@ -0,0 +1,317 @@
+//! Tests for scheduled environmental events.
+
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::events::{DishEvent, EventSchedule};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::params::{
+    DISH_HEIGHT, DISH_WIDTH, PULSE_RADIUS, SOURCE_DECAY_MAX, SOURCE_INTENSITY_MAX,
+    SOURCE_RADIUS_MAX,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::{Scenario, run_prepared};
+
+#[test]
+fn test_schedule_fires_in_tick_order() {
+    let mut schedule = EventSchedule::new();
+    schedule.schedule(30, DishEvent::Relocation);
+    schedule.schedule(10, DishEvent::Extinction { fraction: 0.5 });
+    schedule.schedule(10, DishEvent::pulse(5.0, 5.0));
+    assert_eq!(schedule.next_tick(), Some(10));
+
+    assert!(schedule.take_due(9).is_empty());
+    let due = schedule.take_due(10);
+    assert_eq!(
+        due,
+        vec![
+            DishEvent::Extinction { fraction: 0.5 },
+            DishEvent::pulse(5.0, 5.0)
+        ]
+    );
+    assert_eq!(schedule.fired(), 2);
+    assert_eq!(schedule.pending().len(), 1);
+
+    // Overdue events still fire
+    assert_eq!(schedule.take_due(100), vec![DishEvent::Relocation]);
+    assert!(schedule.is_empty());
+}
+
+#[test]
+fn test_pulse_appears_on_its_tick_and_fades() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    let base_sources = dish.sources.len();
+    let (x, y) = (20.0, 40.0);
+    let before = dish.get_concentration(x, y);
+    dish.events.schedule(3, DishEvent::pulse(x, y));
+
+    dish.update();
+    dish.update();
+    assert_eq!(dish.sources.len(), base_sources);
+    dish.update();
+    assert_eq!(dish.sources.len(), base_sources + 1);
+    assert!(dish.sources.last().unwrap().transient);
+    assert!(before < 0.5, "pulse site should start lean: {before}");
+    assert!(
+        (dish.get_concentration(x, y) - 1.0).abs() < 1e-12,
+        "saturated"
+    );
+
+    // Preset sources stay frozen while the pulse decays away for good
+    for _ in 0..1000 {
+        dish.update();
+    }
+    assert_eq!(dish.sources.len(), base_sources);
+    assert!((dish.get_concentration(x, y) - before).abs() < 1e-12);
+}
+
+#[test]
+fn test_extinction_removes_fraction_of_sources() {
+    let mut dish = PetriDish::generate(3, &GeneratorConfig::default());
+    let count = dish.sources.len();
+    dish.inject(DishEvent::Extinction { fraction: 0.5 })
+        .unwrap();
+    assert_eq!(
+        dish.sources.len(),
+        count - (count as f64 * 0.5).round() as usize
+    );
+
+    dish.inject(DishEvent::Extinction { fraction: 1.0 })
+        .unwrap();
+    assert!(dish.sources.is_empty());
+    // No respawn from nothing
+    for _ in 0..50 {
+        dish.update();
+    }
+    assert!(dish.sources.is_empty());
+}
+
+#[test]
+fn test_relocation_moves_sources_and_keeps_intensity() {
+    let mut dish = PetriDish::generate(8, &GeneratorConfig::default());
+    let before = dish.sources.clone();
+    dish.inject(DishEvent::Relocation).unwrap();
+
+    assert_eq!(dish.sources.len(), before.len());
+    for (old, new) in before.iter().zip(&dish.sources) {
+        assert!((old.intensity - new.intensity).abs() < 1e-12);
+        assert!((old.radius - new.radius).abs() < 1e-12);
+        assert_eq!(old.species, new.species);
+    }
+    assert!(
+        before
+            .iter()
+            .zip(&dish.sources)
+            .any(|(old, new)| (old.x - new.x).abs() > 1e-9)
+    );
+}
+
+#[test]
+fn test_scheduled_events_are_reproducible() {
+    let run = || {
+        let mut dish = PetriDish::generate(21, &GeneratorConfig::default());
+        dish.events.schedule(5, DishEvent::Relocation);
+        dish.events
+            .schedule(12, DishEvent::Extinction { fraction: 0.3 });
+        for _ in 0..20 {
+            dish.update();
+        }
+        dish.sources
+            .iter()
+            .map(|s| (s.x, s.y, s.intensity))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_spawned_source_is_permanent_and_clamped() {
+    let mut dish = PetriDish::generate(30, &GeneratorConfig::default());
+    let count = dish.sources.len();
+    dish.inject(DishEvent::source(150.0, 20.0)).unwrap();
+    assert_eq!(dish.sources.len(), count + 1);
+    let spawned = dish.sources.last().unwrap();
+    assert!(!spawned.transient);
+    assert_eq!((spawned.x, spawned.y), (DISH_WIDTH, 20.0));
+    assert_eq!(spawned.radius, SOURCE_RADIUS_MAX);
+    assert_eq!(spawned.kernel, dish.config.kernel);
+
+    // Invalid shapes are rejected without touching the dish
+    for event in [
+        DishEvent::SpawnSource {
+            x: 10.0,
+            y: 10.0,
+            radius: 0.0,
+            intensity: 1.0,
+            decay_rate: 0.99,
+            species: 0,
+        },
+        DishEvent::SpawnSource {
+            x: 10.0,
+            y: 10.0,
+            radius: 3.0,
+            intensity: -1.0,
+            decay_rate: 0.99,
+            species: 0,
+        },
+        DishEvent::SpawnSource {
+            x: 10.0,
+            y: 10.0,
+            radius: 3.0,
+            intensity: 1.0,
+            decay_rate: 1.5,
+            species: 0,
+        },
+    ] {
+        assert!(dish.inject(event).is_err(), "{event:?}");
+    }
+    assert_eq!(dish.sources.len(), count + 1);
+}
+
+#[test]
+fn test_source_edits_by_index() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Ring, DISH_WIDTH, DISH_HEIGHT);
+    let before = dish.sources.clone();
+
+    dish.inject(DishEvent::MoveSource {
+        index: 2,
+        x: 30.0,
+        y: -5.0,
+    })
+    .unwrap();
+    assert_eq!((dish.sources[2].x, dish.sources[2].y), (30.0, 0.0));
+    assert_eq!(dish.sources[2].intensity, before[2].intensity);
+
+    dish.inject(DishEvent::SetDecay {
+        index: 1,
+        decay_rate: 0.5,
+    })
+    .unwrap();
+    assert_eq!(dish.sources[1].decay_rate, 0.5);
+
+    dish.inject(DishEvent::RemoveSource { index: 0 }).unwrap();
+    assert_eq!(dish.sources.len(), before.len() - 1);
+    assert_eq!(dish.sources[0].decay_rate, 0.5, "later sources move down");
+
+    // Unknown indices and bad rates leave the dish as it was
+    let edited = dish.sources.clone();
+    let missing = dish.sources.len();
+    assert!(
+        dish.inject(DishEvent::RemoveSource { index: missing })
+            .is_err()
+    );
+    assert!(
+        dish.inject(DishEvent::SetDecay {
+            index: 0,
+            decay_rate: 0.0
+        })
+        .is_err()
+    );
+    assert_eq!(dish.sources, edited);
+}
+
+#[test]
+fn test_event_commands_parse() {
+    assert_eq!(
+        DishEvent::parse("pulse 10 20"),
+        Ok(DishEvent::pulse(10.0, 20.0))
+    );
+    assert_eq!(
+        DishEvent::parse("spawn 10 20"),
+        Ok(DishEvent::source(10.0, 20.0))
+    );
+    assert_eq!(
+        DishEvent::parse("remove 3"),
+        Ok(DishEvent::RemoveSource { index: 3 })
+    );
+    assert_eq!(
+        DishEvent::parse("decay 1 0.9"),
+        Ok(DishEvent::SetDecay {
+            index: 1,
+            decay_rate: 0.9
+        })
+    );
+    assert_eq!(
+        DishEvent::parse("  move 0 5 6 "),
+        Ok(DishEvent::MoveSource {
+            index: 0,
+            x: 5.0,
+            y: 6.0
+        })
+    );
+
+    assert_eq!(
+        DishEvent::parse("pulse 10"),
+        Err("usage: pulse X Y".to_string())
+    );
+    assert_eq!(
+        DishEvent::parse("remove -1"),
+        Err("invalid source index: -1".to_string())
+    );
+    assert_eq!(
+        DishEvent::parse("move 0 x 6"),
+        Err("invalid x: x".to_string())
+    );
+    assert!(DishEvent::parse("flood").is_err());
+    assert!(DishEvent::parse("").is_err());
+}
+
+#[test]
+fn test_scheduled_edits_reach_a_scenario() {
+    let scenario = Scenario::Preset(EnvironmentPreset::Gradient);
+    let mut run = scenario.build(4);
+    let patch = (run.dish.sources[0].x, run.dish.sources[0].y);
+    run.dish.events.schedule(
+        10,
+        DishEvent::MoveSource {
+            index: 0,
+            x: 10.0,
+            y: 10.0,
+        },
+    );
+    run.dish.events.schedule(20, DishEvent::pulse(90.0, 40.0));
+    // A stale edit is skipped rather than stopping the run
+    run.dish
+        .events
+        .schedule(25, DishEvent::RemoveSource { index: 99 });
+
+    let outcome = run_prepared(run, 4, 30);
+    let dish = &outcome.run.dish;
+    assert!(dish.events.is_empty());
+    assert_eq!((dish.sources[0].x, dish.sources[0].y), (10.0, 10.0));
+    assert_ne!((dish.sources[0].x, dish.sources[0].y), patch);
+    let pulse = dish.sources.last().unwrap();
+    assert!(pulse.transient && pulse.radius == PULSE_RADIUS);
+}
+
+#[test]
+fn test_pending_spawns_and_moves_follow_a_resize() {
+    let mut dish = PetriDish::generate(9, &GeneratorConfig::default());
+    dish.events.schedule(5, DishEvent::source(40.0, 20.0));
+    dish.events.schedule(
+        6,
+        DishEvent::MoveSource {
+            index: 0,
+            x: 10.0,
+            y: 10.0,
+        },
+    );
+    dish.resize(DISH_WIDTH * 2.0, DISH_HEIGHT * 2.0);
+    let pending: Vec<DishEvent> = dish.events.pending().iter().map(|e| e.event).collect();
+    assert_eq!(
+        pending,
+        vec![
+            DishEvent::SpawnSource {
+                x: 80.0,
+                y: 40.0,
+                radius: SOURCE_RADIUS_MAX,
+                intensity: SOURCE_INTENSITY_MAX,
+                decay_rate: SOURCE_DECAY_MAX,
+                species: 0,
+            },
+            DishEvent::MoveSource {
+                index: 0,
+                x: 20.0,
+                y: 20.0,
+            },
+        ]
+    );
+}
@@ -0,0 +1,131 @@
+//! Tests for genomes and the generational evolutionary loop.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::evolution::{Evolution, GENE_COUNT, Gene, Genome};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::streams::Stream;
+
+/// A small evolution in the maze, where default agents starve within the cap.
+fn maze_evolution() -> Evolution {
+    let mut evolution = Evolution::new(Scenario::Preset(EnvironmentPreset::Maze));
+    evolution.population = 4;
+    evolution.generations = 3;
+    evolution.ticks = 1500;
+    evolution.seeds = vec![0];
+    evolution.seed = 7;
+    evolution
+}
+
+#[test]
+fn test_genome_round_trips_through_an_agent() {
+    let agent = Protozoa::new(50.0, 25.0);
+    assert_eq!(Genome::of(&agent), Genome::default());
+
+    let genome = Genome {
+        genes: [3.0, 0.8, 0.3, 0.2, 4.0, 7.0],
+    };
+    let mut expressed = Protozoa::new(50.0, 25.0);
+    genome.express(&mut expressed);
+    assert_eq!(Genome::of(&expressed), genome);
+    assert_eq!(expressed.morphology.sensor_dist, 3.0);
+    assert_eq!(expressed.generative_model.prior_precision.nutrient, 4.0);
+    assert_eq!(expressed.generative_model.sensory_precision.right, 7.0);
+    assert_eq!(genome.get(Gene::Saccade), 0.2);
+    assert!(
+        genome
+            .describe()
+            .starts_with("sensor_dist=3.000 sensor_angle=0.800")
+    );
+}
+
+#[test]
+fn test_mutation_stays_within_bounds() {
+    let mut rng = Stream::Mutation.rng(1);
+    let mut unchanged = Genome::default();
+    unchanged.mutate(0.0, 0.5, &mut rng);
+    assert_eq!(unchanged, Genome::default());
+
+    let mut genome = Genome::default();
+    for _ in 0..200 {
+        genome.mutate(1.0, 0.5, &mut rng);
+        for (gene, value) in Gene::ALL.iter().zip(genome.genes) {
+            let (lo, hi) = gene.bounds();
+            assert!((lo..=hi).contains(&value), "{} = {value}", gene.name());
+        }
+    }
+    assert_ne!(genome, Genome::default());
+
+    // Same stream, same mutations
+    let (mut a, mut b) = (Genome::default(), Genome::default());
+    a.mutate(0.5, 0.1, &mut Stream::Mutation.rng(2));
+    b.mutate(0.5, 0.1, &mut Stream::Mutation.rng(2));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_crossover_takes_each_gene_from_a_parent() {
+    let a = Genome::default();
+    let b = Genome {
+        genes: Gene::ALL.map(|gene| gene.bounds().1),
+    };
+    let mut rng = Stream::Mutation.rng(3);
+    let mut from_b = [false; GENE_COUNT];
+    for _ in 0..50 {
+        let child = a.crossover(&b, &mut rng);
+        for (i, value) in child.genes.iter().enumerate() {
+            assert!(*value == a.genes[i] || *value == b.genes[i]);
+            from_b[i] |= *value == b.genes[i];
+        }
+    }
+    assert!(
+        from_b.iter().all(|&taken| taken),
+        "every gene can cross over"
+    );
+    assert_eq!(a.crossover(&a, &mut rng), a);
+}
+
+#[test]
+fn test_lifetime_is_capped_survival_time() {
+    let evolution = maze_evolution();
+    let genome = Genome::default();
+    let (survived, energy) = evolution.lifetime(&genome, 0);
+    assert!(survived < evolution.ticks, "starves in the maze");
+    assert_eq!(energy, 0.0);
+    assert_eq!(evolution.lifetime(&genome, 0), (survived, energy));
+
+    let short = Evolution {
+        ticks: 50,
+        ..maze_evolution()
+    };
+    let (capped, _) = short.lifetime(&genome, 0);
+    assert_eq!(capped, 50);
+    let individual = short.evaluate(&genome);
+    assert_eq!(individual.survival, 50.0);
+}
+
+#[test]
+fn test_evolution_keeps_its_best_and_replays() {
+    let evolution = maze_evolution();
+    let mut seen = Vec::new();
+    let report = evolution.run(|summary| seen.push(summary.generation));
+    assert_eq!(seen, vec![0, 1, 2]);
+    assert_eq!(report.generations.len(), 3);
+
+    // The elite is re-evaluated on the same seeds, so the best never regresses
+    let best: Vec<f64> = report.generations.iter().map(|g| g.best.survival).collect();
+    assert!(best.windows(2).all(|w| w[1] >= w[0]), "{best:?}");
+    for summary in &report.generations {
+        assert!(summary.best.survival >= summary.survival.mean);
+    }
+    assert_eq!(report.best(), Some(&report.generations[2].best));
+    assert_eq!(
+        evolution.run(|_| {}),
+        report,
+        "deterministic under the seed"
+    );
+
+    let csv = report.to_csv();
+    assert!(csv.starts_with("generation,best_survival,mean_survival,std_survival,sensor_dist,"));
+    assert_eq!(csv.lines().count(), 4);
+}
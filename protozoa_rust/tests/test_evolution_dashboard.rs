@@ -0,0 +1,167 @@
+//! Tests for the live evolution dashboard.
+
+#![cfg(feature = "tui")]
+
+use protozoa_rust::simulation::evolution::{Evolution, Gene, GenerationSummary};
+use protozoa_rust::simulation::params::EVOLUTION_HISTOGRAM_BINS;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::ui::evolution::{
+    BoxPlot, ChampionPreview, EvolutionView, draw_evolution_dashboard, histogram,
+};
+use ratatui::{Terminal, backend::TestBackend};
+
+fn evolution() -> Evolution {
+    let mut evolution = Evolution::new(Scenario::Preset(EnvironmentPreset::Gradient));
+    evolution.ticks = 40;
+    evolution.seeds = vec![0, 1];
+    evolution.population = 5;
+    evolution.generations = 3;
+    evolution
+}
+
+fn summaries(evolution: &Evolution) -> Vec<GenerationSummary> {
+    evolution.run(|_| {}).generations
+}
+
+fn screen(view: &EvolutionView) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal
+        .draw(|f| draw_evolution_dashboard(f, view))
+        .unwrap();
+    terminal
+        .backend()
+        .buffer()
+        .content
+        .iter()
+        .map(ratatui::buffer::Cell::symbol)
+        .collect()
+}
+
+#[test]
+fn test_box_plot_quartiles() {
+    assert_eq!(BoxPlot::of(&[]), None);
+    let plot = BoxPlot::of(&[5.0, 1.0, 3.0, 2.0, 4.0]).unwrap();
+    assert_eq!(
+        plot,
+        BoxPlot {
+            min: 1.0,
+            q1: 2.0,
+            median: 3.0,
+            q3: 4.0,
+            max: 5.0
+        }
+    );
+    let even = BoxPlot::of(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+    assert!((even.median - 2.5).abs() < 1e-12);
+    assert!((even.q1 - 1.75).abs() < 1e-12);
+    let single = BoxPlot::of(&[7.0]).unwrap();
+    assert_eq!((single.min, single.median, single.max), (7.0, 7.0, 7.0));
+}
+
+#[test]
+fn test_box_plot_line_marks_the_quartiles() {
+    let plot = BoxPlot::of(&[0.0, 2.5, 5.0, 7.5, 10.0]).unwrap();
+    assert_eq!(plot.line([0.0, 10.0], 11), "|--[=#==]-|");
+    assert_eq!(plot.line([0.0, 20.0], 21).trim_end(), "|--[=#==]-|");
+    assert_eq!(plot.line([0.0, 10.0], 0), "");
+    // A flat generation collapses to one column
+    let flat = BoxPlot::of(&[3.0, 3.0]).unwrap();
+    assert_eq!(flat.line([3.0, 3.0], 4), "#   ");
+}
+
+#[test]
+fn test_histogram_bins_the_range() {
+    assert_eq!(histogram(&[], 3), vec![0, 0, 0]);
+    assert_eq!(histogram(&[0.0, 1.0, 2.0, 3.0, 3.0], 3), vec![1, 1, 3]);
+    assert_eq!(histogram(&[2.0, 2.0], 4), vec![2, 0, 0, 0]);
+    let counts = histogram(&[0.1, 0.5, 0.9, 0.2], EVOLUTION_HISTOGRAM_BINS);
+    assert_eq!(counts.len(), EVOLUTION_HISTOGRAM_BINS);
+    assert_eq!(counts.iter().sum::<u64>(), 4);
+}
+
+#[test]
+fn test_generations_carry_every_fitness() {
+    let evolution = evolution();
+    for summary in summaries(&evolution) {
+        assert_eq!(summary.fitnesses.len(), evolution.population);
+        let max = summary.fitnesses.iter().copied().fold(f64::MIN, f64::max);
+        assert_eq!(max, summary.fitness.max);
+        assert_eq!(summary.fitnesses[0], summary.best.fitness);
+    }
+}
+
+#[test]
+fn test_champion_preview_replays_its_episodes() {
+    let evolution = evolution();
+    let genome = summaries(&evolution).last().unwrap().best.genome;
+    let mut preview = ChampionPreview::new(&evolution, genome);
+    assert_eq!((preview.seed, preview.tick), (0, 0));
+    for _ in 0..evolution.ticks {
+        preview.step(&evolution);
+    }
+    let episode = evolution.episode(&genome, 0);
+    assert_eq!(preview.tick, episode.lifespan);
+    assert_eq!(preview.run.agent.energy, episode.final_energy);
+
+    // At the cap the preview moves on to the next seed, then wraps around
+    preview.step(&evolution);
+    assert_eq!((preview.seed, preview.tick), (1, 0));
+    for _ in 0..=evolution.ticks {
+        preview.step(&evolution);
+    }
+    assert_eq!((preview.seed, preview.tick), (0, 0));
+}
+
+#[test]
+fn test_view_follows_the_champion() {
+    let evolution = evolution();
+    let mut view = EvolutionView::new(&evolution);
+    assert!(view.header().starts_with("Generation 0/3"));
+    let summaries = summaries(&evolution);
+    view.push(summaries[0].clone(), &evolution);
+    assert!(!view.finished);
+    view.step_preview(&evolution, 10);
+    assert_eq!(view.preview.as_ref().unwrap().tick, 10);
+
+    // The same champion keeps its preview running; a new one restarts it
+    let mut same = summaries[1].clone();
+    same.best = summaries[0].best.clone();
+    view.push(same, &evolution);
+    assert_eq!(view.preview.as_ref().unwrap().tick, 10);
+    let mut other = summaries[2].clone();
+    other.best.genome.genes[0] += 1.0;
+    view.push(other.clone(), &evolution);
+    assert_eq!(view.preview.as_ref().unwrap().tick, 0);
+    assert_eq!(view.preview.as_ref().unwrap().genome, other.best.genome);
+    assert!(view.finished);
+    assert!(view.header().starts_with("Generation 3/3 (done)"));
+}
+
+#[test]
+fn test_dashboard_draws_every_panel() {
+    let evolution = evolution();
+    let mut view = EvolutionView::new(&evolution);
+    let empty = screen(&view);
+    assert!(empty.contains("evaluating the founders"));
+    assert!(empty.contains("no champion yet"));
+
+    for summary in summaries(&evolution) {
+        view.push(summary, &evolution);
+    }
+    view.step_preview(&evolution, 5);
+    let text = screen(&view);
+    for title in [
+        " Evolution ",
+        " Fitness ",
+        " Fitness distribution ",
+        " Generation 2 histogram ",
+        " Champion ",
+        " Preview [seed 0 tick 5",
+    ] {
+        assert!(text.contains(title), "{title}");
+    }
+    assert!(text.contains(Gene::ALL[0].name()));
+    assert!(text.contains("fitness: lifespan=1"));
+    assert!(text.contains('O'));
+}
@@ -0,0 +1,83 @@
+//! Tests for headless run export and the analysis notebook.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::export::{
+    CSV_COLUMNS, ExportOptions, NOTEBOOK, RUN_CSV, export_run, notebook, record_csv,
+};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::world::World;
+
+fn run() -> (World, Protozoa) {
+    let world = World::single(PetriDish::generate(7, &GeneratorConfig::default()));
+    let agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    (world, agent)
+}
+
+#[test]
+fn test_csv_has_header_and_one_row_per_tick() {
+    let (mut world, mut agent) = run();
+    let csv = record_csv(&mut world, &mut agent, 25);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], CSV_COLUMNS.join(","));
+    assert_eq!(lines.len(), 26);
+
+    let last: Vec<f64> = lines[25].split(',').map(|v| v.parse().unwrap()).collect();
+    assert_eq!(last.len(), CSV_COLUMNS.len());
+    assert!((last[0] - 25.0).abs() < 1e-12, "tick column counts up");
+    assert!((last[3] - agent.x).abs() < 1e-12);
+    assert!((last[7] - agent.energy).abs() < 1e-12);
+}
+
+#[test]
+fn test_notebook_fills_in_paths_and_plots() {
+    let book = notebook("run.csv", 120.0, 60.0, 500);
+    assert_eq!(book["nbformat"], 4);
+    let cells = book["cells"].as_array().unwrap();
+    let text: Vec<String> = cells
+        .iter()
+        .map(|cell| {
+            cell["source"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|line| line.as_str().unwrap())
+                .collect()
+        })
+        .collect();
+    assert!(text.iter().any(|t| t.contains("RUN_CSV = \"run.csv\"")));
+    assert!(text.iter().any(|t| t.contains("DISH_WIDTH = 120.0")));
+    assert!(text.iter().any(|t| t.contains("run[\"energy\"]")));
+    assert!(text.iter().any(|t| t.contains("run[\"vfe\"]")));
+    assert!(text.iter().any(|t| t.contains("hist2d")));
+    assert!(
+        cells
+            .iter()
+            .filter(|c| c["cell_type"] == "code")
+            .all(|c| c["outputs"].as_array().is_some_and(Vec::is_empty))
+    );
+}
+
+#[test]
+fn test_export_run_writes_requested_files() {
+    let dir = std::env::temp_dir().join(format!("export_{}", std::process::id()));
+    let (mut world, mut agent) = run();
+    let options = ExportOptions {
+        ticks: 10,
+        notebook: false,
+        knowledge: false,
+    };
+    let written = export_run(&dir, &mut world, &mut agent, options).unwrap();
+    assert_eq!(written, vec![dir.join(RUN_CSV)]);
+
+    let options = ExportOptions {
+        notebook: true,
+        ..options
+    };
+    let written = export_run(&dir, &mut world, &mut agent, options).unwrap();
+    assert_eq!(written, vec![dir.join(RUN_CSV), dir.join(NOTEBOOK)]);
+    let text = std::fs::read_to_string(dir.join(NOTEBOOK)).unwrap();
+    let book: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(book["nbformat_minor"], 4);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
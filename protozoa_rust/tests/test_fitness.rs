@@ -0,0 +1,189 @@
+//! Tests for the pluggable fitness of evolution and sweeps.
+
+use protozoa_rust::simulation::evolution::{Evolution, Genome};
+use protozoa_rust::simulation::fitness::{Episode, Fitness, FitnessConfig, Objective};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::{Scenario, run_scenario};
+use protozoa_rust::simulation::sweep::{Sweep, run_sweep};
+
+fn episode() -> Episode {
+    Episode {
+        lifespan: 200,
+        energy_integral: 12.5,
+        final_energy: 0.4,
+        coverage: 0.25,
+        offspring: 3,
+    }
+}
+
+fn evolution() -> Evolution {
+    let mut evolution = Evolution::new(Scenario::Preset(EnvironmentPreset::Gradient));
+    evolution.ticks = 150;
+    evolution.seeds = vec![0, 1];
+    evolution
+}
+
+/// A user-defined two-objective fitness: explore, but not at any cost.
+struct Thrifty;
+
+impl Fitness for Thrifty {
+    fn objectives(&self, episode: &Episode) -> Vec<f64> {
+        vec![episode.coverage, episode.final_energy]
+    }
+
+    fn score(&self, episode: &Episode) -> f64 {
+        episode.coverage * episode.final_energy
+    }
+}
+
+#[test]
+fn test_fitness_specs_parse() {
+    for objective in Objective::ALL {
+        assert_eq!(Objective::parse(objective.name()), Some(objective));
+        assert_eq!(
+            FitnessConfig::parse(objective.name()),
+            Ok(FitnessConfig::only(objective))
+        );
+    }
+    assert_eq!(
+        FitnessConfig::default(),
+        FitnessConfig::only(Objective::Lifespan)
+    );
+
+    let config = FitnessConfig::parse("lifespan=1, Coverage=500").unwrap();
+    assert_eq!(config.weight(Objective::Lifespan), 1.0);
+    assert_eq!(config.weight(Objective::Coverage), 500.0);
+    assert_eq!(config.weight(Objective::Energy), 0.0);
+    assert_eq!(config.describe(), "lifespan=1 coverage=500");
+
+    for bad in [
+        "speed",
+        "speed=1",
+        "lifespan=x",
+        "lifespan=0",
+        "energy=inf",
+        "",
+    ] {
+        assert!(FitnessConfig::parse(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn test_fitness_config_from_toml() {
+    let config = FitnessConfig::from_toml_str("energy = 2.0\noffspring = 10.0\n").unwrap();
+    assert_eq!(config.weight(Objective::Lifespan), 0.0);
+    assert_eq!(config.weight(Objective::Energy), 2.0);
+    assert_eq!(config.weight(Objective::Offspring), 10.0);
+    assert!(FitnessConfig::from_toml_str("").is_err());
+    assert!(FitnessConfig::from_toml_str("speed = 1.0").is_err());
+    assert!(FitnessConfig::from_toml_str("lifespan = 0.0").is_err());
+
+    let path = std::env::temp_dir().join(format!("fitness_{}.toml", std::process::id()));
+    std::fs::write(&path, "coverage = 100.0\n").unwrap();
+    assert_eq!(
+        FitnessConfig::load(&path)
+            .unwrap()
+            .weight(Objective::Coverage),
+        100.0
+    );
+    let _ = std::fs::remove_file(path);
+    assert!(FitnessConfig::load("/nonexistent/fitness.toml").is_err());
+}
+
+#[test]
+fn test_weighted_objectives_and_score() {
+    let episode = episode();
+    assert_eq!(Objective::Lifespan.score(&episode), 200.0);
+    assert_eq!(Objective::Energy.score(&episode), 12.5);
+    assert_eq!(Objective::Coverage.objectives(&episode), vec![0.25]);
+    assert_eq!(Objective::Offspring.score(&episode), 3.0);
+
+    let config = FitnessConfig::parse("lifespan=1,coverage=400,offspring=-10").unwrap();
+    assert_eq!(config.objectives(&episode), vec![200.0, 0.0, 100.0, -30.0]);
+    assert_eq!(config.score(&episode), 270.0);
+    assert_eq!(Thrifty.objectives(&episode), vec![0.25, 0.4]);
+    assert!((Thrifty.score(&episode) - 0.1).abs() < 1e-12);
+}
+
+#[test]
+fn test_episodes_of_evolution_and_scenarios_agree() {
+    let evolution = evolution();
+    let episode = evolution.episode(&Genome::default(), 0);
+    assert_eq!(
+        evolution.lifetime(&Genome::default(), 0),
+        (episode.lifespan, episode.final_energy)
+    );
+    assert_eq!(episode.lifespan, 150);
+    assert!(episode.energy_integral > 0.0);
+    assert!(episode.coverage > 0.0);
+    assert_eq!(episode.offspring, 0);
+
+    let outcome = run_scenario(&evolution.scenario, 0, 150);
+    let traced = Episode::of_outcome(&outcome);
+    assert_eq!(traced.lifespan, episode.lifespan);
+    assert!((traced.energy_integral - episode.energy_integral).abs() < 1e-9);
+    assert_eq!(traced.final_energy, episode.final_energy);
+    assert_eq!(traced.coverage, episode.coverage);
+}
+
+#[test]
+fn test_evolution_ranks_by_its_fitness() {
+    let mut evolution = evolution();
+    let genome = Genome::default();
+    let survival = evolution.evaluate(&genome);
+    assert_eq!(survival.fitness, survival.survival);
+
+    evolution.fitness = FitnessConfig::only(Objective::Coverage);
+    let coverage = evolution.evaluate(&genome);
+    let mean_coverage =
+        (evolution.episode(&genome, 0).coverage + evolution.episode(&genome, 1).coverage) / 2.0;
+    assert!((coverage.fitness - mean_coverage).abs() < 1e-12);
+
+    let custom = evolution.evaluate_by(&genome, &Thrifty);
+    assert_eq!(custom.survival, coverage.survival);
+    let thrifty = (Thrifty.score(&evolution.episode(&genome, 0))
+        + Thrifty.score(&evolution.episode(&genome, 1)))
+        / 2.0;
+    assert!((custom.fitness - thrifty).abs() < 1e-12);
+
+    evolution.population = 4;
+    evolution.generations = 2;
+    let report = evolution.run(|_| {});
+    for summary in &report.generations {
+        assert_eq!(summary.best.fitness, summary.fitness.max);
+    }
+    assert!(
+        report
+            .to_csv()
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with(",best_fitness")
+    );
+}
+
+#[test]
+fn test_sweep_cells_are_scored_by_fitness() {
+    let scenario = Scenario::Preset(EnvironmentPreset::Gradient);
+    let sweep = Sweep::parse_axis(scenario, "sensor_dist=1,3", vec![0, 1], 30).unwrap();
+    let path = std::env::temp_dir().join(format!("sweep_fitness_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let report = run_sweep(&sweep, &path, 2).unwrap();
+    assert!(report.results.iter().all(|r| r.lifespan == 30));
+
+    let lifespans = report.fitness_by_value(&Objective::Lifespan);
+    assert_eq!(lifespans.len(), 2);
+    assert!(lifespans.iter().all(|(_, stat)| stat.mean == 30.0));
+    let energies = report.fitness_by_value(&FitnessConfig::only(Objective::Energy));
+    let first = (report.results[0].energy_integral + report.results[1].energy_integral) / 2.0;
+    assert!((energies[0].1.mean - first).abs() < 1e-12);
+
+    // Resumed cells keep their episode data through the journal
+    let resumed = run_sweep(&sweep, &path, 2).unwrap();
+    assert_eq!(resumed.ran, 0);
+    for (a, b) in resumed.results.iter().zip(&report.results) {
+        assert_eq!(a.lifespan, b.lifespan);
+        assert!((a.energy_integral - b.energy_integral).abs() < 1e-12);
+    }
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,197 @@
+//! Tests for the deterministic fixture environments and their expectations.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::fixtures::{
+    FixtureTrace, LinearGradient, StaticSource, StepChange, run_fixture,
+};
+use protozoa_rust::simulation::inference::array_vfe_gradient;
+
+fn agent(x: f64, y: f64, angle: f64, seed: u64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(x, y, angle);
+    agent.seed_streams(seed);
+    agent
+}
+
+/// Central-difference gradient of `env` at (x, y).
+fn numeric_gradient(env: &impl Environment, x: f64, y: f64) -> (f64, f64) {
+    let h = 1e-5;
+    (
+        (env.get_concentration(x + h, y) - env.get_concentration(x - h, y)) / (2.0 * h),
+        (env.get_concentration(x, y + h) - env.get_concentration(x, y - h)) / (2.0 * h),
+    )
+}
+
+#[test]
+fn test_static_source_field_is_analytic() {
+    let mut source = StaticSource::centered(100.0, 50.0);
+    assert_eq!(source.get_concentration(50.0, 25.0), 1.0);
+    assert_eq!(source.gradient(50.0, 25.0), (0.0, 0.0));
+    assert_eq!(source.get_concentration(-1.0, 25.0), -1.0);
+
+    // One radius out the field is e^(-1/2) of the peak
+    let edge = source.get_concentration(62.0, 25.0);
+    assert!((edge - (-0.5f64).exp()).abs() < 1e-12);
+
+    for &(x, y) in &[(20.0, 10.0), (70.0, 40.0), (51.0, 5.0)] {
+        let (gx, gy) = source.gradient(x, y);
+        let (nx, ny) = numeric_gradient(&source, x, y);
+        assert!((gx - nx).abs() < 1e-8 && (gy - ny).abs() < 1e-8);
+        // Uphill is straight at the center
+        let cross = gx * (25.0 - y) - gy * (50.0 - x);
+        assert!(cross.abs() < 1e-12 && gx * (50.0 - x) + gy * (25.0 - y) > 0.0);
+    }
+
+    // It never changes
+    let before = source;
+    for _ in 0..100 {
+        source.update();
+    }
+    assert_eq!(source, before);
+}
+
+#[test]
+fn test_linear_and_step_fields_are_analytic() {
+    let linear = LinearGradient::across(100.0, 50.0);
+    for &(x, y) in &[(0.0, 0.0), (25.0, 10.0), (80.0, 45.0)] {
+        assert!((linear.get_concentration(x, y) - x / 100.0).abs() < 1e-12);
+        assert_eq!(linear.gradient(x, y), (0.01, 0.0));
+        let (nx, ny) = numeric_gradient(&linear, x.max(1.0), y.max(1.0));
+        assert!((nx - 0.01).abs() < 1e-8 && ny.abs() < 1e-8);
+    }
+
+    let mut step = StepChange::new(0.2, 0.8, 3, 100.0, 50.0);
+    let mut levels = Vec::new();
+    for _ in 0..6 {
+        levels.push(step.get_concentration(10.0, 10.0));
+        assert_eq!(step.level_at(step.tick()), levels[levels.len() - 1]);
+        step.update();
+    }
+    assert_eq!(levels, [0.2, 0.2, 0.2, 0.8, 0.8, 0.8]);
+    assert_eq!(step.tick(), 6);
+    assert_eq!(step.gradient(10.0, 10.0), (0.0, 0.0));
+    assert_eq!(step.get_concentration(10.0, 60.0), -1.0);
+}
+
+#[test]
+fn test_agents_converge_to_a_static_source() {
+    for seed in 0..4 {
+        let mut source = StaticSource::centered(100.0, 50.0);
+        let mut agent = agent(15.0, 10.0 + 8.0 * seed as f64, 0.0, seed);
+        let trace = run_fixture(&mut source, &mut agent, 400);
+        let reached = trace.first_tick_within(source.x, source.y, source.radius);
+        assert!(
+            reached.is_some_and(|tick| tick < 250),
+            "seed {seed} reached the source at {reached:?}"
+        );
+
+        // And once there, stays close
+        let tail = &trace.samples[300..];
+        let mean_distance =
+            tail.iter().map(|s| source.distance(s.x, s.y)).sum::<f64>() / tail.len() as f64;
+        assert!(
+            mean_distance < source.radius,
+            "seed {seed}: {mean_distance}"
+        );
+    }
+}
+
+#[test]
+fn test_agents_climb_a_linear_gradient() {
+    for seed in 0..4 {
+        // Set off facing down the gradient
+        let mut field = LinearGradient::across(100.0, 50.0);
+        let mut agent = agent(20.0, 25.0, 3.0, seed);
+        let trace = run_fixture(&mut field, &mut agent, 300);
+        let last = trace.samples[trace.samples.len() - 1];
+        assert!(last.x > 55.0, "seed {seed} ended at x = {}", last.x);
+        assert!(last.truth > trace.samples[0].truth + 0.3);
+        // What it should have sensed is the field where it sensed
+        for s in &trace.samples {
+            assert!((s.truth - s.sensed_at.0 / 100.0).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_beliefs_follow_a_step_change() {
+    for seed in 0..4 {
+        let mut field = StepChange::new(0.2, 0.8, 100, 100.0, 50.0);
+        let mut agent = agent(50.0, 25.0, 0.0, seed);
+        let trace = run_fixture(&mut field, &mut agent, 250);
+        assert!(trace.samples[..100].iter().all(|s| s.truth == 0.2));
+        assert!(trace.samples[100..].iter().all(|s| s.truth == 0.8));
+
+        // Per tick the descent overshoots, but on average the belief moves
+        // with the field, in the step's direction
+        let before = trace.mean_belief(50..100);
+        let after = trace.mean_belief(150..250);
+        assert!(after - before > 0.25, "seed {seed}: {before} -> {after}");
+    }
+}
+
+#[test]
+fn test_vfe_gradient_vanishes_at_the_analytic_posterior() {
+    // In a uniform field facing along x every receptor predicts the nutrient
+    // belief itself, so the VFE is quadratic in it and its minimum is the
+    // precision-weighted mean of readings and prior
+    let field = StepChange::new(0.3, 0.3, 0, 100.0, 50.0);
+    let mut agent = agent(50.0, 25.0, 0.0, 1);
+    agent.sense(&field);
+    let tick = agent.tick_context();
+    assert!(!tick.chemoreceptors.is_empty());
+
+    let model = &agent.generative_model;
+    let (weighted, total) =
+        tick.chemoreceptors
+            .iter()
+            .fold((0.0, 0.0), |(weighted, total), obs| {
+                let precision = model.sensory_precision.chemoreceptor(obs.bearing);
+                (weighted + precision * obs.value, total + precision)
+            });
+    let prior = model.prior_precision.nutrient;
+    let posterior = (weighted + prior * model.prior_mean.nutrient) / (total + prior);
+    assert!(posterior > 0.3 && posterior < model.prior_mean.nutrient);
+
+    let descent_at = |nutrient: f64| {
+        let mut beliefs = agent.beliefs.clone();
+        beliefs.mean.nutrient = nutrient;
+        beliefs.mean.angle = 0.0;
+        array_vfe_gradient(&tick.chemoreceptors, &beliefs, model).nutrient
+    };
+    assert!(descent_at(posterior).abs() < 1e-9);
+    // Elsewhere the descent direction points back at it, with the curvature
+    // of the quadratic
+    let slope = (descent_at(posterior - 0.1) - descent_at(posterior + 0.1)) / 0.2;
+    assert!((slope - (total + prior)).abs() < 1e-9);
+    assert!(descent_at(posterior - 0.1) > 0.0 && descent_at(posterior + 0.1) < 0.0);
+}
+
+#[test]
+fn test_fixtures_replay_exactly() {
+    let run = |seed: u64| {
+        let mut source = StaticSource::centered(100.0, 50.0);
+        let mut agent = agent(20.0, 15.0, 1.0, seed);
+        run_fixture(&mut source, &mut agent, 120)
+    };
+    assert_eq!(run(5), run(5));
+    assert_ne!(run(5), run(6));
+
+    // A trace is a record of every tick, in order
+    let trace = run(5);
+    assert_eq!(trace.samples.len(), 120);
+    assert!(
+        trace
+            .samples
+            .iter()
+            .enumerate()
+            .all(|(i, s)| s.tick == i as u64)
+    );
+    for pair in trace.samples.windows(2) {
+        assert_eq!(pair[1].sensed_at, (pair[0].x, pair[0].y));
+    }
+    assert_eq!(
+        FixtureTrace::default().first_tick_within(0.0, 0.0, 1e9),
+        None
+    );
+}
@@ -0,0 +1,111 @@
+//! Tests for fluid flow advection in the dish.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::flow::FlowField;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, MAX_SPEED};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::{compute_field_grid, flow_glyph, overlay_flow_glyphs};
+use std::f64::consts::PI;
+
+fn dish_with_flow(strength: f64, direction: f64, vorticity: f64) -> PetriDish {
+    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    dish.flow = FlowField {
+        strength,
+        direction,
+        vorticity,
+        ..FlowField::still(DISH_WIDTH, DISH_HEIGHT)
+    };
+    dish
+}
+
+#[test]
+fn test_still_field_has_zero_velocity() {
+    let flow = FlowField::still(DISH_WIDTH, DISH_HEIGHT);
+    assert!(flow.is_still());
+    let (v_x, v_y) = flow.velocity_at(10.0, 40.0);
+    assert!(v_x.abs() < 1e-12 && v_y.abs() < 1e-12);
+}
+
+#[test]
+fn test_uniform_current_velocity() {
+    let dish = dish_with_flow(0.5, PI / 2.0, 0.0);
+    let (v_x, v_y) = dish.flow_at(20.0, 30.0);
+    assert!(v_x.abs() < 1e-12);
+    assert!((v_y - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_vortex_is_tangential() {
+    let dish = dish_with_flow(0.0, 0.0, 0.01);
+    let (cx, cy) = (DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+
+    // Zero at the center
+    let (v_x, v_y) = dish.flow_at(cx, cy);
+    assert!(v_x.abs() < 1e-12 && v_y.abs() < 1e-12);
+
+    // Perpendicular to the radius elsewhere
+    let (x, y) = (cx + 10.0, cy + 5.0);
+    let (v_x, v_y) = dish.flow_at(x, y);
+    let dot = v_x * (x - cx) + v_y * (y - cy);
+    assert!(dot.abs() < 1e-12);
+}
+
+#[test]
+fn test_sources_are_advected_downstream() {
+    let mut dish = dish_with_flow(2.0, 0.0, 0.0);
+    for source in &mut dish.sources {
+        source.x = 20.0;
+        // Avoid respawn during the test
+        source.intensity = 1.0;
+        source.decay_rate = 1.0;
+    }
+
+    dish.update();
+
+    // Brownian step is at most 0.5, so every source moved right
+    for source in &dish.sources {
+        assert!(source.x > 21.0, "source x = {}", source.x);
+    }
+}
+
+#[test]
+fn test_agent_is_pushed_by_current() {
+    let dish = dish_with_flow(5.0, 0.0, 0.0);
+    let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+
+    agent.sense(&dish);
+    agent.update_state(&dish);
+
+    // Self-propulsion is at most MAX_SPEED, so the current dominates
+    assert!(agent.x >= DISH_WIDTH / 2.0 + 5.0 - MAX_SPEED);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_flow_glyph_directions() {
+    assert_eq!(flow_glyph(1.0, 0.0), '>');
+    assert_eq!(flow_glyph(-1.0, 0.0), '<');
+    // World y grows downward on screen
+    assert_eq!(flow_glyph(0.0, 1.0), 'v');
+    assert_eq!(flow_glyph(0.0, -1.0), '^');
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_overlay_skips_still_field_and_dense_cells() {
+    let still = dish_with_flow(0.0, 0.0, 0.0);
+    let mut grid = compute_field_grid(&still, 20, 40);
+    let before = grid.clone();
+    overlay_flow_glyphs(&mut grid, &still);
+    assert_eq!(grid, before);
+
+    let flowing = dish_with_flow(1.0, 0.0, 0.0);
+    let mut grid: Vec<String> = vec!["@".repeat(40); 20];
+    overlay_flow_glyphs(&mut grid, &flowing);
+    assert!(grid.iter().all(|line| line.chars().all(|c| c == '@')));
+
+    let mut grid: Vec<String> = vec![" ".repeat(40); 20];
+    overlay_flow_glyphs(&mut grid, &flowing);
+    assert!(grid.iter().any(|line| line.contains('>')));
+}
@@ -0,0 +1,226 @@
+//! Tests for long-run memory footprint controls.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::arrival::{ArrivalEvent, ArrivalTarget, EventLog};
+use protozoa_rust::simulation::ensemble::Ensemble;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::footprint::MemoryLimits;
+use protozoa_rust::simulation::lineage::Lineage;
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::params::{
+    DEAD_AGENT_CAPACITY, DISH_HEIGHT, DISH_WIDTH, EVENT_LOG_CAPACITY, LINEAGE_CAPACITY,
+    MCTS_CACHE_CAPACITY, MCTS_CACHE_COMPACT_RATIO,
+};
+use protozoa_rust::simulation::planning::{
+    Action, AgentState, CachedValue, RolloutCache, StateKey,
+};
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+use protozoa_rust::simulation::world::World;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::render::format_bytes;
+
+fn event(travel_ticks: u64) -> ArrivalEvent {
+    ArrivalEvent {
+        target: ArrivalTarget::Landmark,
+        x: 10.0,
+        y: 20.0,
+        tick: travel_ticks,
+        travel_ticks,
+        path_length: 1.0,
+        straight_distance: 1.0,
+    }
+}
+
+#[test]
+fn test_defaults_follow_params() {
+    let limits = MemoryLimits::default();
+    assert_eq!(limits.event_log, EVENT_LOG_CAPACITY);
+    assert_eq!(limits.rollout_cache, MCTS_CACHE_CAPACITY);
+    assert_eq!(limits.lineage, LINEAGE_CAPACITY);
+    assert_eq!(limits.dead_agents, DEAD_AGENT_CAPACITY);
+    assert_eq!(EventLog::new().capacity(), EVENT_LOG_CAPACITY);
+    assert_eq!(RolloutCache::new().capacity(), MCTS_CACHE_CAPACITY);
+}
+
+#[test]
+fn test_event_log_capacity_shrinks_and_keeps_totals() {
+    let mut log = EventLog::with_capacity(5);
+    for i in 1..=5 {
+        log.push(event(i));
+    }
+    log.set_capacity(2);
+    let kept: Vec<u64> = log.iter().map(|e| e.travel_ticks).collect();
+    assert_eq!(kept, vec![4, 5], "oldest events are dropped");
+    assert_eq!(log.count(), 5);
+    assert!((log.mean_travel_ticks().unwrap() - 3.0).abs() < 1e-12);
+
+    log.push(event(6));
+    assert_eq!(log.iter().count(), 2);
+
+    // A zero capacity keeps only the running statistics
+    log.set_capacity(0);
+    log.push(event(7));
+    assert_eq!(log.iter().count(), 0);
+    assert_eq!(log.count(), 7);
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn test_rollout_cache_compacts_weakest_entries() {
+    let priors: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    let capacity = 40;
+    let mut cache = RolloutCache::with_capacity(capacity);
+    let mut keys = Vec::new();
+    for i in 0..=capacity {
+        let x = 2.5 + 5.0 * (i % 20) as f64;
+        let y = 2.5 + 5.0 * (i / 20) as f64;
+        let key = StateKey::new(&AgentState::new(x, y, 0.0, 1.0, 1.0), &priors);
+        keys.push(key);
+        cache.store(
+            key,
+            Action::Straight,
+            CachedValue {
+                pragmatic: 0.0,
+                epistemic: 0.0,
+                weight: 1.0 + i as f64,
+            },
+        );
+    }
+
+    let keep = (capacity as f64 * MCTS_CACHE_COMPACT_RATIO) as usize;
+    assert_eq!(cache.len(), keep, "overflow compacts below the capacity");
+    // The strongest (most recently stored here) entries survive
+    assert!(cache.lookup(keys[capacity], Action::Straight).is_some());
+    assert!(cache.lookup(keys[0], Action::Straight).is_none());
+
+    cache.set_capacity(8);
+    assert_eq!(cache.len(), (8.0 * MCTS_CACHE_COMPACT_RATIO) as usize);
+    cache.clear();
+    assert_eq!(cache.capacity(), 8, "clearing keeps the capacity");
+}
+
+#[test]
+fn test_ensemble_history_follows_limits() {
+    let mut ensemble = Ensemble::new(2, &SpawnConfig::default());
+    for _ in 0..10 {
+        ensemble.step();
+    }
+    let limits = MemoryLimits {
+        event_log: 1,
+        rollout_cache: 16,
+        ensemble_history: 4,
+        ..MemoryLimits::default()
+    };
+    ensemble.set_memory_limits(&limits);
+    assert_eq!(ensemble.history.len(), 4);
+    assert_eq!(ensemble.history.back().unwrap().tick, 10);
+    for _ in 0..10 {
+        ensemble.step();
+    }
+    assert_eq!(ensemble.history.len(), 4);
+    for run in &ensemble.runs {
+        assert_eq!(run.agent.events.capacity(), 1);
+        assert_eq!(run.agent.planner.cache().capacity(), 16);
+    }
+    let usage = ensemble.memory_usage();
+    assert!(usage.history > 0 && usage.fixed > 0);
+    assert_eq!(
+        usage.total(),
+        usage.fixed + usage.event_log + usage.rollout_cache + usage.history + usage.lineage
+    );
+}
+
+#[test]
+fn test_lineage_compacts_the_oldest_dead() {
+    let mut lineage = Lineage::with_capacity(3);
+    for tick in 0..4 {
+        lineage.register(None, tick);
+    }
+    assert_eq!(lineage.len(), 4, "the living are never compacted");
+
+    lineage.record_death(2, 10);
+    let ids = |lineage: &Lineage| lineage.iter().map(|r| r.id).collect::<Vec<_>>();
+    assert_eq!(ids(&lineage), vec![0, 1, 3]);
+    assert!(lineage.get(2).is_none());
+    lineage.record_death(1, 11);
+    lineage.record_death(0, 12);
+    assert_eq!(ids(&lineage), vec![0, 1, 3], "within capacity");
+
+    // IDs keep counting and offspring of the living still find their parent
+    let child = lineage.register(Some(3), 13);
+    assert_eq!((child.id, child.generation), (4, 1));
+    assert_eq!(lineage.born(), 5);
+    assert_eq!(ids(&lineage), vec![1, 3, 4], "oldest dead dropped first");
+
+    lineage.set_capacity(0);
+    assert_eq!(ids(&lineage), vec![3, 4], "only the dead go");
+}
+
+#[test]
+fn test_population_drops_the_earliest_dead() {
+    let mut world = World::single(PetriDish::empty(DISH_WIDTH, DISH_HEIGHT, 5));
+    let mut population = Population::single(Protozoa::new(50.0, 25.0));
+    for _ in 0..5 {
+        population.add(Protozoa::new(50.0, 25.0), Some(0));
+    }
+    population.set_memory_limits(&MemoryLimits {
+        lineage: 4,
+        dead_agents: 2,
+        ..MemoryLimits::default()
+    });
+    assert!(population.select(2));
+    for id in 1..=4 {
+        population.agents[usize::try_from(id).unwrap()].died_at = Some(1);
+        population.lineage.record_death(id, 1);
+    }
+    population.step(&mut world);
+
+    // Agent 2 is selected, so 1 and 3 go and the latest dead (4) stays
+    let ids: Vec<u64> = population.agents.iter().map(|a| a.lineage_id).collect();
+    assert_eq!(ids, vec![0, 2, 4, 5]);
+    assert_eq!(population.selected().lineage_id, 2);
+    assert_eq!(population.alive_count(), 2);
+    assert_eq!(population.lineage.len(), 4);
+    assert!(population.lineage.get(1).is_none());
+
+    let usage = population.memory_usage();
+    assert!(usage.lineage > 0);
+    let agents: usize = population
+        .agents
+        .iter()
+        .map(|a| a.memory_usage().total())
+        .sum();
+    assert_eq!(usage.total(), agents + usage.lineage);
+}
+
+#[test]
+fn test_agent_footprint_stays_bounded() {
+    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    agent.set_memory_limits(&MemoryLimits {
+        rollout_cache: 8,
+        ..MemoryLimits::default()
+    });
+    let mut peak = 0;
+    for _ in 0..1500 {
+        dish.update();
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        peak = peak.max(agent.memory_usage().total());
+        assert!(agent.planner.cache().len() <= 8);
+    }
+    let usage = agent.memory_usage();
+    assert!(usage.fixed > 0);
+    assert!(peak < usage.fixed + 64 * 1024, "peak {peak} bytes");
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(512), "512 B");
+    assert_eq!(format_bytes(42_189), "41.2 KiB");
+    assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "tui")]
+//! Tests for the dashboard's frame-budget guard and its degradation ladder.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{
+    FRAME_BUDGET_DEGRADE_FRAMES, FRAME_BUDGET_RESTORE_FRAMES, FRAME_FIELD_REFRESH, MCTS_MAX_DEPTH,
+    MCTS_MIN_DEPTH, MCTS_MIN_ROLLOUTS,
+};
+use protozoa_rust::simulation::planning::PlanningBudget;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::ui::DishLayer;
+use protozoa_rust::ui::frame_budget::{Degradation, FieldCache, FrameBudget};
+use std::time::Duration;
+
+const BUDGET: Duration = Duration::from_millis(100);
+const SLOW: Duration = Duration::from_millis(250);
+const FAST: Duration = Duration::from_millis(10);
+
+/// Records `frames` frames of `work`, returning how many changed the level.
+fn record(guard: &mut FrameBudget, work: Duration, frames: u32) -> usize {
+    (0..frames).filter(|_| guard.record(work)).count()
+}
+
+#[test]
+fn test_ladder_order_and_labels() {
+    let mut level = Degradation::default();
+    let mut labels = vec![level.label()];
+    while level.next() != level {
+        assert!(level.next() > level);
+        assert_eq!(level.next().previous(), level);
+        level = level.next();
+        labels.push(level.label());
+    }
+    assert_eq!(
+        labels,
+        ["full", "reused field", "nutrient only", "lean planning"]
+    );
+    assert_eq!(Degradation::Full.previous(), Degradation::Full);
+}
+
+#[test]
+fn test_slow_frames_degrade_one_step_at_a_time() {
+    let mut guard = FrameBudget::new(BUDGET);
+    assert_eq!(guard.level, Degradation::Full);
+    assert_eq!(record(&mut guard, SLOW, FRAME_BUDGET_DEGRADE_FRAMES - 1), 0);
+    assert_eq!(guard.level, Degradation::Full);
+    assert!(guard.record(SLOW));
+    assert_eq!(guard.level, Degradation::ReuseField);
+    assert!(guard.mean_frame() > BUDGET);
+
+    // Each further step needs another run of slow frames, down to the floor
+    assert_eq!(record(&mut guard, SLOW, FRAME_BUDGET_DEGRADE_FRAMES), 1);
+    assert_eq!(guard.level, Degradation::NutrientOnly);
+    assert_eq!(
+        record(&mut guard, SLOW, 10 * FRAME_BUDGET_DEGRADE_FRAMES),
+        1
+    );
+    assert_eq!(guard.level, Degradation::LeanPlanning);
+}
+
+#[test]
+fn test_fast_frames_restore_with_hysteresis() {
+    // Slightly slow frames, so the smoothed time settles quickly
+    let mut guard = FrameBudget::new(BUDGET);
+    record(
+        &mut guard,
+        BUDGET.mul_f64(1.2),
+        2 * FRAME_BUDGET_DEGRADE_FRAMES,
+    );
+    assert_eq!(guard.level, Degradation::NutrientOnly);
+
+    // Frames just under budget hold the level rather than restoring it
+    let close = BUDGET.mul_f64(0.9);
+    assert_eq!(
+        record(&mut guard, close, 5 * FRAME_BUDGET_RESTORE_FRAMES),
+        0
+    );
+    assert_eq!(guard.level, Degradation::NutrientOnly);
+
+    // Only a long run of fast frames takes a step back (once the smoothed
+    // time has come down, a few frames in)
+    let changed = record(&mut guard, FAST, FRAME_BUDGET_RESTORE_FRAMES + 5);
+    assert_eq!(changed, 1);
+    assert_eq!(guard.level, Degradation::ReuseField);
+    record(&mut guard, FAST, 10 * FRAME_BUDGET_RESTORE_FRAMES);
+    assert_eq!(guard.level, Degradation::Full);
+}
+
+#[test]
+fn test_a_single_spike_does_not_degrade() {
+    let mut guard = FrameBudget::new(BUDGET);
+    record(&mut guard, FAST, 20);
+    for _ in 0..5 * FRAME_BUDGET_DEGRADE_FRAMES {
+        guard.record(SLOW);
+        record(&mut guard, FAST, 4);
+    }
+    assert_eq!(guard.level, Degradation::Full);
+    assert!(guard.mean_frame() < BUDGET);
+}
+
+#[test]
+fn test_status_names_the_level() {
+    let mut guard = FrameBudget::new(BUDGET);
+    assert_eq!(guard.status(), None);
+    record(&mut guard, SLOW, 2 * FRAME_BUDGET_DEGRADE_FRAMES);
+    assert_eq!(guard.status().as_deref(), Some("lag 2/3: nutrient only"));
+    record(&mut guard, SLOW, FRAME_BUDGET_DEGRADE_FRAMES);
+    assert_eq!(guard.status().as_deref(), Some("lag 3/3: lean planning"));
+}
+
+#[test]
+fn test_levels_reuse_the_field_then_drop_layers() {
+    assert!(!Degradation::Full.reuses_field());
+    assert!(Degradation::LeanPlanning.reuses_field());
+    assert_eq!(
+        Degradation::ReuseField.layer(DishLayer::Gradient),
+        DishLayer::Gradient
+    );
+    assert_eq!(
+        Degradation::NutrientOnly.layer(DishLayer::Gradient),
+        DishLayer::Nutrient
+    );
+
+    let mut cache = FieldCache::default();
+    let key = (DishLayer::Nutrient, 0, (10, 20));
+    let mut computed = 0;
+    let mut frame = |cache: &mut FieldCache, key, reuse| {
+        cache.grid(key, reuse, || {
+            computed += 1;
+            vec![format!("{computed}")]
+        })
+    };
+    // Without reuse every frame recomputes
+    frame(&mut cache, key, false);
+    assert_eq!(frame(&mut cache, key, false), ["2"]);
+    // With it, once per refresh, or when the view changes
+    for _ in 0..FRAME_FIELD_REFRESH - 1 {
+        assert_eq!(frame(&mut cache, key, true), ["2"]);
+    }
+    assert_eq!(frame(&mut cache, key, true), ["3"]);
+    assert_eq!(
+        frame(&mut cache, (DishLayer::Nutrient, 1, (10, 20)), true),
+        ["4"]
+    );
+}
+
+#[test]
+fn test_lean_planning_caps_replans() {
+    assert_eq!(Degradation::NutrientOnly.planning_cap(), None);
+    let cap = Degradation::LeanPlanning.planning_cap().unwrap();
+    assert_eq!(
+        cap,
+        PlanningBudget {
+            depth: MCTS_MIN_DEPTH,
+            rollouts: MCTS_MIN_ROLLOUTS
+        }
+    );
+    let full = PlanningBudget::for_pressure(1.0);
+    assert_eq!(full.capped(None), full);
+    assert_eq!(full.capped(Some(cap)), cap);
+
+    // A starving agent would plan at full depth; capped, it plans lean
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(4);
+    agent.energy = 0.1;
+    agent.planning_cap = Some(cap);
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    assert_eq!(agent.planner.budget(), cap);
+    assert!(agent.planner.best_plan().len() < MCTS_MAX_DEPTH);
+}
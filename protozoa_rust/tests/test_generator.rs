@@ -0,0 +1,105 @@
+//! Tests for seeded procedural dish generation.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::{PetriDish, SpeciesLayout};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+
+/// Comparable fingerprint of every source.
+fn fingerprint(dish: &PetriDish) -> Vec<(f64, f64, f64, f64, f64, usize)> {
+    dish.sources
+        .iter()
+        .map(|s| (s.x, s.y, s.radius, s.intensity, s.decay_rate, s.species))
+        .collect()
+}
+
+fn obstacle_config(count: usize) -> GeneratorConfig {
+    GeneratorConfig {
+        obstacle_count: count,
+        ..GeneratorConfig::default()
+    }
+}
+
+#[test]
+fn test_same_seed_same_dish() {
+    let config = obstacle_config(3);
+    let a = PetriDish::generate(42, &config);
+    let b = PetriDish::generate(42, &config);
+    assert_eq!(fingerprint(&a), fingerprint(&b));
+    assert_eq!(a.thermal, b.thermal);
+    assert_eq!(a.obstacles, b.obstacles);
+
+    let c = PetriDish::generate(43, &config);
+    assert_ne!(fingerprint(&a), fingerprint(&c));
+}
+
+#[test]
+fn test_seeded_dynamics_are_reproducible() {
+    let config = GeneratorConfig::default();
+    let mut a = PetriDish::generate(7, &config);
+    let mut b = PetriDish::generate(7, &config);
+    // Long enough for decay to trigger respawns
+    for _ in 0..2000 {
+        a.update();
+        b.update();
+    }
+    assert_eq!(fingerprint(&a), fingerprint(&b));
+}
+
+#[test]
+fn test_generator_respects_config() {
+    let config = GeneratorConfig {
+        dish: DishConfig {
+            source_count_min: 3,
+            source_count_max: 3,
+            ..DishConfig::sized(80.0, 40.0)
+        },
+        species_count: 2,
+        layout: SpeciesLayout::Segregated,
+        ..GeneratorConfig::default()
+    };
+    let dish = PetriDish::generate(1, &config);
+    assert!((dish.width - 80.0).abs() < 1e-12 && (dish.height - 40.0).abs() < 1e-12);
+    assert_eq!(dish.species_count, 2);
+    assert_eq!(dish.sources.len(), 6);
+    for source in &dish.sources {
+        let band = f64::from(u8::try_from(source.species).unwrap()) * 40.0;
+        assert!(source.x >= band && source.x <= band + 40.0);
+    }
+    assert!(dish.obstacles.is_empty(), "no obstacles by default");
+
+    // Swapped bounds are tolerated
+    let swapped = DishConfig {
+        source_count_min: 4,
+        source_count_max: 2,
+        ..DishConfig::default()
+    };
+    assert_eq!(swapped.source_count_range(), (2, 4));
+}
+
+#[test]
+fn test_obstacles_keep_sources_and_center_free() {
+    let config = obstacle_config(5);
+    for seed in 0..20 {
+        let dish = PetriDish::generate(seed, &config);
+        assert!(!dish.obstacles.is_empty());
+        assert!(!dish.is_blocked(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0));
+        for source in &dish.sources {
+            assert!(!dish.is_blocked(source.x, source.y), "seed {seed}");
+        }
+        for o in &dish.obstacles {
+            let (w, h) = (o.x_max - o.x_min, o.y_max - o.y_min);
+            assert!(w >= config.obstacle_size_min && w <= config.obstacle_size_max);
+            assert!(h >= config.obstacle_size_min && h <= config.obstacle_size_max);
+            assert!(o.x_min >= 0.0 && o.x_max <= DISH_WIDTH);
+            assert!(o.y_min >= 0.0 && o.y_max <= DISH_HEIGHT);
+        }
+    }
+}
+
+#[test]
+fn test_unseeded_dishes_differ() {
+    let a = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let b = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    assert_ne!(fingerprint(&a), fingerprint(&b));
+}
@@ -0,0 +1,134 @@
+//! Tests for the external goal API.
+
+#[cfg(feature = "tui")]
+use crossterm::event::KeyCode;
+use protozoa_rust::simulation::agent::{AgentMode, Protozoa};
+use protozoa_rust::simulation::arrival::ArrivalTarget;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::goal::{Goal, GoalCommand};
+use protozoa_rust::simulation::inference::GenerativeModel;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, GOAL_PRIOR_PRECISION};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::CommandLine;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::render::format_goal_line;
+
+#[test]
+fn test_goal_progress_tracking() {
+    let mut goal = Goal::new(30.0, 0.0, 1.0, (0.0, 0.0), 10);
+    assert!((goal.initial_distance - 30.0).abs() < 1e-12);
+    assert!(goal.progress().abs() < 1e-12);
+
+    goal.track(15.0, 0.0);
+    assert!((goal.progress() - 0.5).abs() < 1e-12);
+
+    // Moving away never reports negative progress
+    goal.track(-10.0, 0.0);
+    assert!(goal.progress().abs() < 1e-12);
+
+    assert_eq!(goal.elapsed(25), 15);
+    assert!((Goal::new(1.0, 1.0, 1.0, (1.0, 1.0), 0).progress() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_set_goal_installs_position_prior() {
+    let mut agent = Protozoa::new(50.0, 25.0);
+    agent.set_goal(10.0, 40.0, 3.0);
+
+    let model = &agent.generative_model;
+    assert!((model.prior_mean.x - 10.0).abs() < 1e-12);
+    assert!((model.prior_mean.y - 40.0).abs() < 1e-12);
+    assert!((model.prior_precision.x - 3.0 * GOAL_PRIOR_PRECISION).abs() < 1e-12);
+    assert!(agent.goal.is_some());
+
+    agent.clear_goal();
+    let defaults = GenerativeModel::new();
+    assert!(agent.goal.is_none());
+    assert!((agent.generative_model.prior_mean.x - defaults.prior_mean.x).abs() < 1e-12);
+    assert!((agent.generative_model.prior_precision.y - defaults.prior_precision.y).abs() < 1e-12);
+}
+
+#[test]
+fn test_agent_makes_progress_toward_goal() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let mut agent = Protozoa::with_heading(20.0, 25.0, 0.0);
+    agent.energy = 1.0;
+    agent.set_goal(80.0, 25.0, 10.0);
+    assert_eq!(agent.current_mode(&dish), AgentMode::GoalNav);
+
+    for _ in 0..150 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        agent.energy = 1.0;
+    }
+
+    // Either still en route with progress, or the goal was reached and completed
+    match agent.goal {
+        Some(goal) => assert!(goal.progress() > 0.5, "progress {}", goal.progress()),
+        None => assert_eq!(
+            agent.events.last().map(|event| event.target),
+            Some(ArrivalTarget::Goal)
+        ),
+    }
+}
+
+#[test]
+fn test_goal_command_parsing() {
+    assert_eq!(
+        GoalCommand::parse("goal 30 20"),
+        Ok(GoalCommand::Set {
+            x: 30.0,
+            y: 20.0,
+            priority: 1.0
+        })
+    );
+    assert_eq!(
+        GoalCommand::parse("  goal 1.5 2 4 "),
+        Ok(GoalCommand::Set {
+            x: 1.5,
+            y: 2.0,
+            priority: 4.0
+        })
+    );
+    assert_eq!(GoalCommand::parse("clear"), Ok(GoalCommand::Clear));
+
+    assert!(GoalCommand::parse("").is_err());
+    assert!(GoalCommand::parse("goal 1").is_err());
+    assert!(GoalCommand::parse("goal x 2").is_err());
+    assert!(GoalCommand::parse("goal 1 2 0").is_err());
+    assert!(GoalCommand::parse("goal 1 2 NaN").is_err());
+    assert!(GoalCommand::parse("jump").is_err());
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_command_line_editing() {
+    let mut line = CommandLine::default();
+    assert!(!line.is_open());
+    assert_eq!(line.handle_key(KeyCode::Char('x')), None);
+
+    line.open();
+    for c in "goal 1 2x".chars() {
+        line.handle_key(KeyCode::Char(c));
+    }
+    line.handle_key(KeyCode::Backspace);
+    assert_eq!(line.display().as_deref(), Some(":goal 1 2_"));
+    assert_eq!(line.handle_key(KeyCode::Enter).as_deref(), Some("goal 1 2"));
+    assert!(!line.is_open());
+
+    line.open();
+    line.handle_key(KeyCode::Esc);
+    assert!(!line.is_open());
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_goal_line_format() {
+    assert_eq!(format_goal_line(None), "Goal: none");
+
+    let mut goal = Goal::new(30.0, 0.0, 1.0, (0.0, 0.0), 0);
+    goal.track(15.0, 0.0);
+    let line = format_goal_line(Some(&goal));
+    assert!(line.contains("50%"), "{line}");
+    assert!(line.contains("d:15.0"), "{line}");
+}
@@ -0,0 +1,180 @@
+//! Tests for the belief-vs-true gradient layer.
+
+use protozoa_rust::simulation::environment::{NutrientSource, Obstacle, PetriDish};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::kernel::Kernel;
+use protozoa_rust::simulation::layout::Layout;
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::gradient::{
+    RegionGradient, compute_gradient_grid, mean_alignment, region_gradients,
+};
+
+/// Dish with a single static blob on the right-hand side.
+fn single_source_dish() -> PetriDish {
+    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    dish.sources = vec![NutrientSource {
+        x: 80.0,
+        y: 25.0,
+        radius: 20.0,
+        intensity: 1.0,
+        decay_rate: 1.0,
+        species: 0,
+        transient: false,
+        kernel: Kernel::Gaussian,
+    }];
+    dish
+}
+
+#[test]
+fn test_mean_gradient_requires_visits() {
+    let mut grid: SpatialGrid<10, 5> = SpatialGrid::new(100.0, 50.0);
+    assert!(grid.mean_gradient(45.0, 25.0).is_none());
+
+    // Increasing concentration along +x across three visited cells
+    grid.update(35.0, 25.0, 0.2);
+    grid.update(45.0, 25.0, 0.4);
+    grid.update(55.0, 25.0, 0.6);
+
+    let (g_x, g_y) = grid.mean_gradient(45.0, 25.0).unwrap();
+    assert!((g_x - 0.02).abs() < 1e-12, "central difference {g_x}");
+    assert!(g_y.abs() < 1e-12);
+
+    // One-sided difference at the right edge of explored territory
+    let (edge_x, _) = grid.mean_gradient(55.0, 25.0).unwrap();
+    assert!((edge_x - 0.02).abs() < 1e-12);
+}
+
+#[test]
+fn test_true_gradient_points_at_source() {
+    let dish = single_source_dish();
+    let (g_x, g_y) = dish.concentration_gradient(50.0, 25.0, 1.0);
+    assert!(g_x > 0.0);
+    assert!(g_y.abs() < 1e-9);
+
+    // Defined at the walls too
+    let (w_x, w_y) = dish.concentration_gradient(0.0, 0.0, 1.0);
+    assert!(w_x.is_finite() && w_y.is_finite());
+}
+
+#[test]
+fn test_exact_gradient_matches_differences() {
+    let dish = PetriDish::generate(3, &GeneratorConfig::default());
+    let h = 1e-5;
+    let mut compared = 0;
+    for i in 1..40 {
+        for j in 1..20 {
+            let (x, y) = (f64::from(i) * 2.5, f64::from(j) * 2.5);
+            let near_wall = [(x - h, y), (x + h, y), (x, y - h), (x, y + h)]
+                .iter()
+                .any(|&(sx, sy)| dish.is_blocked(sx, sy));
+            let level = dish.get_concentration(x, y);
+            if near_wall || level <= 0.0 || level >= 1.0 {
+                continue;
+            }
+            let (e_x, e_y) = dish.gradient(x, y);
+            let (d_x, d_y) = dish.concentration_gradient(x, y, h);
+            assert!((e_x - d_x).abs() < 1e-6, "({x}, {y}): {e_x} vs {d_x}");
+            assert!((e_y - d_y).abs() < 1e-6, "({x}, {y}): {e_y} vs {d_y}");
+            compared += 1;
+        }
+    }
+    assert!(compared > 100);
+}
+
+#[test]
+fn test_exact_gradient_edges() {
+    let mut dish = single_source_dish();
+    let (g_x, g_y) = dish.gradient(50.0, 25.0);
+    assert!(g_x > 0.0 && g_y.abs() < 1e-12);
+
+    assert_eq!(dish.gradient(-1.0, 25.0), (0.0, 0.0));
+    dish.obstacles.push(Obstacle {
+        x_min: 40.0,
+        y_min: 20.0,
+        x_max: 45.0,
+        y_max: 30.0,
+    });
+    assert_eq!(dish.gradient(42.0, 25.0), (0.0, 0.0));
+
+    // No toxic-void step next to the obstacle, unlike the finite difference
+    let (near_x, _) = dish.gradient(46.0, 25.0);
+    assert!(near_x > 0.0 && near_x < 0.05);
+    assert!(dish.concentration_gradient(46.0, 25.0, 1.5).0 > 0.3);
+    dish.sources[0].intensity = 3.0;
+    assert_eq!(dish.gradient(80.0, 20.0), (0.0, 0.0), "saturated");
+}
+
+#[test]
+fn test_nutrient_map_gradient() {
+    let map = Layout::from_ascii("9.").unwrap().nutrient_map(20.0, 10.0);
+    // Level falls from 1 to 0 between the cell centers at x = 5 and x = 15
+    let (g_x, g_y) = map.gradient(10.0, 5.0);
+    assert!((g_x + 0.1).abs() < 1e-12);
+    assert!(g_y.abs() < 1e-12, "single row is flat");
+    assert_eq!(map.gradient(2.0, 5.0), (0.0, 0.0), "flat past the centers");
+    assert_eq!(map.gradient(18.0, 5.0), (0.0, 0.0));
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_alignment_scores() {
+    let aligned = RegionGradient {
+        x: 0.0,
+        y: 0.0,
+        belief: Some((0.1, 0.0)),
+        truth: (0.2, 0.0),
+    };
+    let opposed = RegionGradient {
+        belief: Some((-0.1, 0.0)),
+        ..aligned
+    };
+    let unknown = RegionGradient {
+        belief: None,
+        ..aligned
+    };
+
+    assert!((aligned.alignment().unwrap() - 1.0).abs() < 1e-12);
+    assert!((opposed.alignment().unwrap() + 1.0).abs() < 1e-12);
+    assert!(unknown.alignment().is_none());
+
+    assert!(mean_alignment(&[aligned, opposed, unknown]).unwrap().abs() < 1e-12);
+    assert!(mean_alignment(&[unknown]).is_none());
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_learned_priors_align_with_truth() {
+    let dish = single_source_dish();
+    let mut grid: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    for row in 0..10 {
+        for col in 0..20 {
+            let x = (f64::from(col) + 0.5) * 5.0;
+            let y = (f64::from(row) + 0.5) * 5.0;
+            grid.update(x, y, dish.get_concentration(x, y));
+        }
+    }
+
+    let regions = region_gradients(&dish, &grid);
+    assert_eq!(regions.len(), 200);
+    assert!(mean_alignment(&regions).unwrap() > 0.8);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_gradient_grid_rendering() {
+    let dish = single_source_dish();
+    let grid: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+
+    let lines = compute_gradient_grid(&dish, &grid, 30, 100);
+    assert_eq!(lines.len(), 30);
+    assert!(lines.iter().all(|line| line.chars().count() == 100));
+
+    // Unexplored regions show '?' beliefs next to a true arrow
+    let text = lines.concat();
+    assert_eq!(text.matches('?').count(), 200);
+    assert!(text.contains('>'));
+
+    assert!(compute_gradient_grid(&dish, &grid, 0, 10).is_empty());
+}
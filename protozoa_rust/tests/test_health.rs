@@ -0,0 +1,132 @@
+//! Tests for structural integrity (health), separate from energy.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::environment::{Obstacle, PetriDish};
+use protozoa_rust::simulation::health::{Injury, settle};
+use protozoa_rust::simulation::params::{
+    COLLISION_DAMAGE, HEALTH_REGEN_ENERGY, HEALTH_REGEN_RATE, MAX_SENSOR_DIST, MAX_SPEED,
+    SENSOR_DIST, STRAIN_DAMAGE_RATE, STRAIN_SENSOR_DIST, TOXIN_DAMAGE_RATE,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+fn tick(agent: &mut Protozoa, dish: &PetriDish) {
+    agent.sense(dish);
+    agent.update_state(dish);
+}
+
+#[test]
+fn test_toxins_and_strain_damage_over_time() {
+    let clock = SimClock::default();
+    let clean = Injury::assess((0.5, 0.5), None, SENSOR_DIST, &clock);
+    assert_eq!(clean, Injury::default());
+    assert_eq!(clean.total(), 0.0);
+
+    let half = Injury::assess((-1.0, 0.5), None, SENSOR_DIST, &clock);
+    let full = Injury::assess((-1.0, -1.0), None, SENSOR_DIST, &clock);
+    assert!((half.toxin - clock.rate(TOXIN_DAMAGE_RATE) / 2.0).abs() < 1e-15);
+    assert!((full.toxin - clock.rate(TOXIN_DAMAGE_RATE)).abs() < 1e-15);
+
+    // Strain sets in past the comfortable reach
+    let comfortable = Injury::assess((0.5, 0.5), None, STRAIN_SENSOR_DIST, &clock);
+    assert_eq!(comfortable.strain, 0.0);
+    let stretched = Injury::assess((0.5, 0.5), None, MAX_SENSOR_DIST, &clock);
+    assert!((stretched.strain - clock.rate(STRAIN_DAMAGE_RATE)).abs() < 1e-15);
+    assert!((stretched.total() - stretched.strain).abs() < 1e-15);
+}
+
+#[test]
+fn test_collisions_hurt_in_proportion_to_speed() {
+    let clock = SimClock::default();
+    let hit = |speed: f64| Injury::assess((0.5, 0.5), Some(speed), SENSOR_DIST, &clock).collision;
+    assert_eq!(hit(0.0), 0.0);
+    assert!((hit(MAX_SPEED / 2.0) - COLLISION_DAMAGE / 2.0).abs() < 1e-15);
+    assert!((hit(MAX_SPEED) - COLLISION_DAMAGE).abs() < 1e-15);
+    assert!(
+        (hit(3.0 * MAX_SPEED) - COLLISION_DAMAGE).abs() < 1e-15,
+        "capped"
+    );
+}
+
+#[test]
+fn test_health_regenerates_only_when_fed() {
+    let clock = SimClock::default();
+    let none = Injury::default();
+    let fed = settle(0.5, &none, HEALTH_REGEN_ENERGY, &clock);
+    assert!((fed - (0.5 + clock.rate(HEALTH_REGEN_RATE))).abs() < 1e-15);
+    assert_eq!(settle(0.5, &none, HEALTH_REGEN_ENERGY - 0.01, &clock), 0.5);
+
+    // Kept within [0, 1]
+    assert_eq!(settle(1.0, &none, 1.0, &clock), 1.0);
+    let crushed = Injury {
+        collision: 2.0,
+        ..Injury::default()
+    };
+    assert_eq!(settle(0.5, &crushed, 1.0, &clock), 0.0);
+}
+
+#[test]
+fn test_toxic_void_wears_down_a_fed_agent() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    // Pressed against the left rim, facing out
+    let mut agent = Protozoa::with_heading(0.0, 25.0, std::f64::consts::PI);
+    agent.seed_streams(3);
+    tick(&mut agent, &dish);
+    assert!(agent.val_l < 0.0 && agent.val_r < 0.0);
+    assert!(agent.health < 1.0);
+    assert!(agent.energy > 0.9, "health is not energy");
+}
+
+#[test]
+fn test_damage_kills_a_well_fed_agent() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.morphology.sensor_dist = MAX_SENSOR_DIST;
+    agent.health = 1e-4;
+    tick(&mut agent, &dish);
+    assert!(!agent.is_alive());
+    assert_eq!(agent.died_at, Some(agent.tick_count));
+    assert!(agent.energy > 0.9);
+    assert_eq!(agent.starving_ticks, 0);
+}
+
+#[test]
+fn test_collision_and_recovery_in_a_dish() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    dish.obstacles.push(Obstacle {
+        x_min: 50.5,
+        y_min: 0.0,
+        x_max: 60.0,
+        y_max: 50.0,
+    });
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.cruise_speed = MAX_SPEED;
+    tick(&mut agent, &dish);
+    assert_eq!(agent.x, 50.0, "the wall stops it");
+    assert!(agent.health < 1.0, "and it hurts");
+
+    // Away from harm a fed agent heals
+    let open = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.health = 0.5;
+    tick(&mut agent, &open);
+    assert!(agent.health > 0.5);
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_dashboard_shows_health() {
+    use protozoa_rust::ui::DashboardState;
+    use protozoa_rust::ui::render::format_health_line;
+
+    assert_eq!(format_health_line(0.9), "H: 90%");
+    assert_eq!(format_health_line(1.0), "H:100%");
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::new(50.0, 25.0);
+    agent.health = 0.25;
+    let state = DashboardState::from_agent(&agent, &dish);
+    assert!((state.health - 0.25).abs() < 1e-12);
+}
@@ -0,0 +1,128 @@
+//! Tests for hunger-dependent preference modulation: the nutrient
+//! precision, the planner's drive and the landmark urge follow the energy
+//! deficit continuously.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::arrival::ArrivalTarget;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::hunger::{Drive, Hunger};
+use protozoa_rust::simulation::inference::GenerativeModel;
+use protozoa_rust::simulation::params::{
+    HUNGER_GAIN, HUNGER_PIVOT, INTEROCEPTIVE_SETPOINT, MCTS_URGENT_ENERGY,
+};
+
+fn modulated(gain: f64, energy: f64) -> Hunger {
+    let mut hunger = Hunger::new(gain);
+    hunger.observe(energy, INTEROCEPTIVE_SETPOINT);
+    hunger
+}
+
+#[test]
+fn test_deficit_is_clamped_below_the_setpoint() {
+    assert_eq!(Hunger::deficit(1.0, 1.0), 0.0);
+    assert_eq!(Hunger::deficit(1.4, 1.0), 0.0);
+    assert!((Hunger::deficit(0.25, 1.0) - 0.75).abs() < 1e-12);
+    assert_eq!(Hunger::deficit(-0.5, 1.0), 1.0);
+    assert_eq!(Hunger::deficit(0.5, 0.0), 0.0);
+}
+
+#[test]
+fn test_off_by_default() {
+    assert_eq!(HUNGER_GAIN, 0.0);
+    let hunger = modulated(HUNGER_GAIN, 0.05);
+    assert!(hunger.is_off());
+    assert_eq!(hunger.factor(), 1.0);
+    assert_eq!(hunger.drive(), Drive::default());
+
+    let mut model = GenerativeModel::new();
+    model.observe_energy(0.05);
+    assert_eq!(model.nutrient_precision(), model.prior_precision.nutrient);
+}
+
+#[test]
+fn test_hunger_trades_information_for_food() {
+    let at_pivot = modulated(2.0, INTEROCEPTIVE_SETPOINT * (1.0 - HUNGER_PIVOT));
+    assert!((at_pivot.factor() - 1.0).abs() < 1e-12);
+
+    let hungry = modulated(2.0, 0.1).drive();
+    let sated = modulated(2.0, 0.95).drive();
+    assert!(hungry.pragmatic > 1.0 && hungry.epistemic < 1.0);
+    assert!(sated.pragmatic < 1.0 && sated.epistemic > 1.0);
+    assert!((hungry.pragmatic * hungry.epistemic - 1.0).abs() < 1e-12);
+    // A stronger gain pulls the two further apart
+    assert!(modulated(4.0, 0.1).drive().pragmatic > hungry.pragmatic);
+}
+
+#[test]
+fn test_nutrient_precision_grows_continuously_with_deficit() {
+    let mut model = GenerativeModel::new();
+    model.hunger.gain = 3.0;
+    let precisions: Vec<f64> = (0..=20)
+        .map(|i| {
+            model.observe_energy(1.0 - f64::from(i) * 0.05);
+            model.nutrient_precision()
+        })
+        .collect();
+    assert!(precisions.windows(2).all(|w| w[1] > w[0]));
+    // No jumps between neighboring energies
+    assert!(
+        precisions
+            .windows(2)
+            .all(|w| w[1] / w[0] < (3.0f64 * 0.06).exp())
+    );
+}
+
+#[test]
+fn test_landmark_urge_replaces_the_threshold() {
+    let off = Hunger::default();
+    assert_eq!(off.urge(MCTS_URGENT_ENERGY - 0.01, 1.0), 1.0);
+    assert_eq!(off.urge(MCTS_URGENT_ENERGY, 1.0), 0.0);
+
+    let on = Hunger::new(2.0);
+    assert_eq!(on.urge(1.0, 1.0), 0.0);
+    assert!((on.urge(MCTS_URGENT_ENERGY, 1.0) - 1.0).abs() < 1e-12);
+    assert_eq!(on.urge(0.1, 1.0), 1.0);
+    let half = on.urge(0.65, 1.0);
+    assert!((half - 0.5).abs() < 1e-12, "urge {half}");
+}
+
+#[test]
+fn test_gain_must_be_finite_and_non_negative() {
+    assert!(Hunger::new(0.0).validate().is_ok());
+    assert!(Hunger::new(2.5).validate().is_ok());
+    assert!(Hunger::new(-1.0).validate().is_err());
+    assert!(Hunger::new(f64::NAN).validate().is_err());
+    assert!(Hunger::new(f64::INFINITY).validate().is_err());
+}
+
+#[test]
+fn test_agent_feeds_its_energy_to_the_planner() {
+    let dish = PetriDish::empty(100.0, 50.0, 6);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(6);
+    agent.generative_model.hunger.gain = 2.0;
+    agent.energy = 0.2;
+    for _ in 0..30 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+    let level = Hunger::deficit(agent.energy, INTEROCEPTIVE_SETPOINT);
+    assert!((agent.generative_model.hunger.level - level).abs() < 0.01);
+    assert!(agent.planner.drive().pragmatic > 1.0);
+    assert!(agent.generative_model.check_finite().is_ok());
+}
+
+#[test]
+fn test_modulated_agent_heads_for_food_before_the_threshold() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.energy = 0.5;
+    agent.episodic_memory.maybe_store(35.0, 25.0, 0.75, 0);
+    // Unmodulated, it only navigates below the urgent energy
+    assert!(agent.navigation_target().is_none());
+    agent.generative_model.hunger.gain = 2.0;
+    let (target, x, _) = agent.navigation_target().unwrap();
+    assert_eq!((target, x), (ArrivalTarget::Landmark, 35.0));
+    // A sated agent does not
+    agent.energy = 1.0;
+    assert!(agent.navigation_target().is_none());
+}
@@ -0,0 +1,115 @@
+//! Tests for stable agent identities: IDs, names and creation ticks.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::identity::{label, validate_name};
+use protozoa_rust::simulation::params::AGENT_NAME_MAX_LEN;
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+use protozoa_rust::simulation::world::World;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn swarm(count: usize) -> (World, Population) {
+    let world = World::single(PetriDish::empty(100.0, 50.0, 3));
+    let mut rng = StdRng::seed_from_u64(3);
+    let population = Population::spawn(count, &world, &SpawnConfig::default(), &mut rng);
+    (world, population)
+}
+
+#[test]
+fn test_new_agents_are_anonymous() {
+    let agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    assert_eq!(agent.lineage_id, 0);
+    assert_eq!(agent.name, None);
+    assert_eq!(agent.created_at, 0);
+    assert_eq!(agent.label(), "#0");
+}
+
+#[test]
+fn test_names_are_short_tokens() {
+    assert_eq!(validate_name("alice"), Ok("alice"));
+    assert_eq!(validate_name("run-7_b"), Ok("run-7_b"));
+    assert!(validate_name("").is_err());
+    assert!(validate_name("two words").is_err());
+    assert!(validate_name("a,b").is_err());
+    assert!(validate_name("ümlaut").is_err());
+    assert!(validate_name(&"x".repeat(AGENT_NAME_MAX_LEN)).is_ok());
+    assert!(validate_name(&"x".repeat(AGENT_NAME_MAX_LEN + 1)).is_err());
+}
+
+#[test]
+fn test_labels_show_id_and_name() {
+    assert_eq!(label(3, None), "#3");
+    assert_eq!(label(3, Some("alice")), "#3 alice");
+
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.rename("bob").unwrap();
+    assert_eq!(agent.name.as_deref(), Some("bob"));
+    assert_eq!(agent.label(), "#0 bob");
+    // A bad name leaves the old one in place
+    assert!(agent.rename("no way").is_err());
+    assert_eq!(agent.label(), "#0 bob");
+}
+
+#[test]
+fn test_population_stamps_ids_and_creation_ticks() {
+    let (mut world, mut population) = swarm(3);
+    let ids: Vec<u64> = population.agents.iter().map(|a| a.lineage_id).collect();
+    assert_eq!(ids, [0, 1, 2]);
+    assert!(population.agents.iter().all(|a| a.created_at == 0));
+
+    for _ in 0..5 {
+        population.step(&mut world);
+    }
+    let mut newcomer = Protozoa::with_heading(20.0, 20.0, 0.0);
+    newcomer.name = Some("late".into());
+    let id = population.add(newcomer, Some(1));
+    let added = population.get(id).unwrap();
+    assert_eq!(added.created_at, 5);
+    assert_eq!(added.name.as_deref(), Some("late"));
+    assert_eq!(population.lineage.get(id).unwrap().born, added.created_at);
+    assert!(population.get(42).is_none());
+}
+
+#[test]
+fn test_identities_survive_the_run() {
+    let (mut world, mut population) = swarm(4);
+    population.agents[2].rename("carol").unwrap();
+    for _ in 0..30 {
+        population.step(&mut world);
+    }
+    let carol = population.find_named("carol").unwrap();
+    assert_eq!(carol.lineage_id, 2);
+    assert_eq!(carol.created_at, 0);
+    assert!(population.find_named("dave").is_none());
+}
+
+#[test]
+fn test_select_by_id() {
+    let (_, mut population) = swarm(3);
+    assert_eq!(population.selected().lineage_id, 0);
+    assert!(population.select(2));
+    assert_eq!(population.selected().lineage_id, 2);
+    assert_eq!(population.selected_index(), 2);
+    // An unknown ID keeps the selection
+    assert!(!population.select(7));
+    assert_eq!(population.selected().lineage_id, 2);
+}
+
+#[test]
+fn test_roster_lists_every_agent() {
+    let (mut world, mut population) = swarm(2);
+    population.agents[0].rename("alice").unwrap();
+    population.agents[1].died_at = Some(0);
+    population.step(&mut world);
+    population.add(Protozoa::with_heading(20.0, 20.0, 0.0), Some(0));
+
+    let csv = population.roster_csv();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "id,name,created,dish,alive");
+    assert_eq!(rows[1], "0,alice,0,0,1");
+    assert_eq!(rows[2], "1,,0,0,0");
+    assert_eq!(rows[3], "2,,1,0,1");
+    assert_eq!(rows.len(), population.len() + 1);
+}
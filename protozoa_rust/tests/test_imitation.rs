@@ -0,0 +1,206 @@
+//! Tests for bootstrapping priors from recorded runs.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::export::record_csv;
+use protozoa_rust::simulation::imitation::{Demonstration, DemonstrationStep, HabitMap, Imitation};
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, IMITATION_STRENGTH};
+use protozoa_rust::simulation::provenance::Term;
+use protozoa_rust::simulation::world::World;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+const CSV: &str = "tick,dish,x,y,angle,sense\n\
+                   1,0,10.0,10.0,0.5,0.8\n\
+                   2,1,10.0,10.0,3.0,0.1\n\
+                   3,0,11.0,10.5,0.7,0.6\n";
+
+/// A demonstration swimming at `angle` along the row at `y`.
+fn sweep_row(y: f64, angle: f64, sense: f64) -> Demonstration {
+    let steps = (0..100)
+        .map(|i| DemonstrationStep {
+            x: f64::from(i),
+            y,
+            angle,
+            sense,
+        })
+        .collect();
+    Demonstration {
+        label: "row".to_string(),
+        steps,
+    }
+}
+
+#[test]
+fn test_demonstrations_parse_from_exported_csv() {
+    let demonstration = Demonstration::from_csv("expert.csv", CSV).unwrap();
+    assert_eq!(demonstration.label, "expert.csv");
+    assert_eq!(demonstration.steps.len(), 2, "rows outside dish 0 skipped");
+    assert_eq!(
+        demonstration.steps[1],
+        DemonstrationStep {
+            x: 11.0,
+            y: 10.5,
+            angle: 0.7,
+            sense: 0.6
+        }
+    );
+
+    let no_dish = Demonstration::from_csv("a", "x,y,angle,sense\n1,2,3,0.5\n").unwrap();
+    assert_eq!(no_dish.steps.len(), 1);
+    let missing = Demonstration::from_csv("a", "x,y,sense\n1,2,0.5\n").unwrap_err();
+    assert!(missing.contains("no `angle` column"), "{missing}");
+    let bad = Demonstration::from_csv("a", "x,y,angle,sense\n1,2,NaN,0.5\n").unwrap_err();
+    assert!(bad.contains("line 2"), "{bad}");
+
+    let path = std::env::temp_dir().join(format!("imitation_{}.csv", std::process::id()));
+    std::fs::write(&path, CSV).unwrap();
+    let loaded = Demonstration::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.steps, demonstration.steps);
+    assert!(
+        Demonstration::load(&path)
+            .unwrap_err()
+            .contains("cannot read")
+    );
+}
+
+#[test]
+fn test_recorded_runs_are_demonstrations() {
+    let mut world = World::single(PetriDish::empty(100.0, 50.0, 1));
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(1);
+    let csv = record_csv(&mut world, &mut agent, 50);
+    let demonstration = Demonstration::from_csv("run.csv", &csv).unwrap();
+    assert_eq!(demonstration.steps.len(), 50);
+    let last = demonstration.steps.last().unwrap();
+    assert!((last.x - agent.x).abs() < 1e-3 && (last.y - agent.y).abs() < 1e-3);
+}
+
+#[test]
+fn test_strength_scales_the_bootstrapped_priors() {
+    let mut imitation = Imitation::new(vec![sweep_row(2.0, 0.0, 0.9)]);
+    assert_eq!(imitation.strength, IMITATION_STRENGTH);
+    assert!(imitation.validate().is_ok());
+
+    imitation.strength = 1.0;
+    let full = imitation.spatial_priors(DISH_WIDTH, DISH_HEIGHT);
+    imitation.strength = 0.4;
+    let part = imitation.spatial_priors(DISH_WIDTH, DISH_HEIGHT);
+    assert_eq!(full.total_visits(), 100);
+    // Five readings per cell, of which two are kept
+    assert_eq!(part.total_visits(), 40);
+    assert!((part.expected(2.0, 2.0) - 0.9).abs() < 1e-12);
+    assert!(part.precision(2.0, 2.0) < full.precision(2.0, 2.0));
+    assert_eq!(
+        part.get_cell(2.0, 40.0).visits,
+        0,
+        "unvisited cells stay blank"
+    );
+
+    imitation.strength = 0.0;
+    assert_eq!(
+        imitation
+            .spatial_priors(DISH_WIDTH, DISH_HEIGHT)
+            .total_visits(),
+        0
+    );
+    assert!(imitation.habits(DISH_WIDTH, DISH_HEIGHT).is_empty());
+    for bad in [-0.1, 1.5, f64::NAN] {
+        imitation.strength = bad;
+        assert!(imitation.validate().is_err());
+    }
+}
+
+#[test]
+fn test_habits_follow_consistent_headings() {
+    assert!(HabitMap::default().is_empty());
+    let mut imitation = Imitation::new(vec![sweep_row(2.0, FRAC_PI_2, 0.5)]);
+    imitation.strength = 0.8;
+    let habits = imitation.habits(DISH_WIDTH, DISH_HEIGHT);
+    assert!(!habits.is_empty());
+    assert_eq!(habits.world_dimensions(), (DISH_WIDTH, DISH_HEIGHT));
+    let habit = habits.at(30.0, 2.0);
+    assert!((habit.heading - FRAC_PI_2).abs() < 1e-12);
+    assert!((habit.weight - 0.8).abs() < 1e-12);
+    assert_eq!(habits.at(30.0, 40.0).weight, 0.0);
+
+    // Two demonstrators swimming opposite ways leave no habit
+    imitation
+        .demonstrations
+        .push(sweep_row(2.0, -FRAC_PI_2, 0.5));
+    let habits = imitation.habits(DISH_WIDTH, DISH_HEIGHT);
+    assert!(habits.cells().all(|h| h.weight < 1e-12));
+}
+
+#[test]
+fn test_instill_pools_priors_and_installs_habits() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    assert!(agent.habits.is_empty());
+    assert_eq!(agent.habit_steering(), 0.0);
+    for _ in 0..4 {
+        agent.spatial_priors.update(2.0, 2.0, 0.1);
+    }
+
+    let mut imitation = Imitation::new(vec![sweep_row(2.0, 0.0, 0.9)]);
+    imitation.strength = 1.0;
+    imitation.instill(&mut agent);
+    // 4 own readings of 0.1 and the 5 demonstrated in that cell
+    let cell = agent.spatial_priors.get_cell(2.0, 2.0);
+    assert_eq!(cell.visits, 9);
+    assert!((cell.mean - (4.0 * 0.1 + 5.0 * 0.9) / 9.0).abs() < 1e-12);
+    let expected_m2 = 4.0 * (0.1 - cell.mean).powi(2) + 5.0 * (0.9 - cell.mean).powi(2);
+    assert!((cell.m2 - expected_m2).abs() < 1e-9);
+    assert_eq!(agent.spatial_priors.total_visits(), 104);
+    assert!((agent.habits.at(2.0, 2.0).weight - 1.0).abs() < 1e-12);
+    assert_eq!(agent.habits.at(50.0, 25.0).weight, 0.0);
+
+    // Leaving the dish leaves its habits behind
+    agent.migrate(1, 10.0, 10.0, 100.0, 50.0);
+    assert!(agent.habits.is_empty());
+}
+
+#[test]
+fn test_habit_steering_turns_toward_the_demonstrated_heading() {
+    let dish = PetriDish::empty(100.0, 50.0, 2);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(2);
+    let mut imitation = Imitation::new(vec![sweep_row(25.0, FRAC_PI_2, 0.5)]);
+    imitation.strength = 1.0;
+    imitation.instill(&mut agent);
+    assert!(agent.habit_steering() > 0.0);
+    agent.angle = PI;
+    assert!(agent.habit_steering() < 0.0);
+    agent.angle = FRAC_PI_2;
+    assert!(agent.habit_steering().abs() < 1e-12);
+
+    agent.angle = 0.0;
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    let term = agent.provenance.terms[Term::Habit as usize];
+    assert!(term > 0.0);
+    assert_eq!(Term::Habit.name(), "habit");
+}
+
+#[test]
+fn test_bootstrapped_agents_swim_the_demonstrated_way() {
+    let northward = |strength: f64| {
+        let dish = PetriDish::empty(100.0, 50.0, 3);
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(3);
+        let demonstrations = (0..10)
+            .map(|row| sweep_row(f64::from(row) * 5.0 + 2.5, FRAC_PI_2, 0.5))
+            .collect();
+        let mut imitation = Imitation::new(demonstrations);
+        imitation.strength = strength;
+        imitation.instill(&mut agent);
+        let start = agent.y;
+        for _ in 0..100 {
+            agent.sense(&dish);
+            agent.update_state(&dish);
+        }
+        agent.y - start
+    };
+    let imitating = northward(1.0);
+    let blank = northward(0.0);
+    assert!(imitating > blank + 5.0, "{imitating} vs {blank}");
+}
@@ -0,0 +1,205 @@
+//! Tests for knowledge inheritance (belief initialization of newborns).
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::export::{ExportOptions, KNOWLEDGE, export_run};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::inheritance::{BeliefInit, Knowledge};
+use protozoa_rust::simulation::params::{INHERITANCE_NOISE, MAX_LANDMARKS};
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::world::World;
+use rand::Rng;
+
+/// An agent that has mapped a rich corner and remembers a landmark there.
+fn learned(seed: u64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(seed);
+    for value in [0.9, 0.8, 0.85] {
+        agent.spatial_priors.update(10.0, 10.0, value);
+    }
+    agent.spatial_priors.update(90.0, 40.0, 0.1);
+    agent.episodic_memory.maybe_store(10.0, 10.0, 0.9, 5);
+    agent.beliefs.mean.nutrient = 0.8;
+    agent.beliefs.covariance.nutrient_var = 0.05;
+    agent
+}
+
+fn newborn(seed: u64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(30.0, 30.0, 1.0);
+    agent.seed_streams(seed);
+    agent
+}
+
+#[test]
+fn test_belief_init_parses() {
+    assert!(matches!(BeliefInit::default(), BeliefInit::Blank));
+    assert!(matches!(BeliefInit::parse("blank"), Ok(BeliefInit::Blank)));
+    assert!(matches!(
+        BeliefInit::parse("average"),
+        Ok(BeliefInit::Average)
+    ));
+    assert!(
+        matches!(BeliefInit::parse("parent"), Ok(BeliefInit::Parent { noise }) if noise == INHERITANCE_NOISE)
+    );
+    assert!(
+        matches!(BeliefInit::parse("parent:0.2"), Ok(BeliefInit::Parent { noise }) if noise == 0.2)
+    );
+    assert!(BeliefInit::parse("parent:-1").is_err());
+    assert!(BeliefInit::parse("parent:lots").is_err());
+    assert!(BeliefInit::parse("no/such/knowledge.json").is_err());
+}
+
+#[test]
+fn test_daughters_inherit_from_their_parent() {
+    let mut population = Population::single(learned(1));
+    let blank = population.add(newborn(2), Some(0));
+    let fresh = Protozoa::with_heading(30.0, 30.0, 1.0);
+    let agent = &population.agents[usize::try_from(blank).unwrap()];
+    assert_eq!(agent.spatial_priors.total_visits(), 0, "blank by default");
+    assert_eq!(agent.beliefs.mean.nutrient, fresh.beliefs.mean.nutrient);
+
+    population.belief_init = BeliefInit::Parent { noise: 0.0 };
+    let copy = population.add(newborn(3), Some(0));
+    let daughter = &population.agents[usize::try_from(copy).unwrap()];
+    let parent = &population.agents[0];
+    assert_eq!(
+        daughter.spatial_priors.expected(10.0, 10.0),
+        parent.spatial_priors.expected(10.0, 10.0)
+    );
+    assert_eq!(daughter.spatial_priors.total_visits(), 4);
+    assert_eq!(daughter.episodic_memory.count(), 1);
+    assert_eq!(daughter.beliefs.mean.nutrient, 0.8);
+    assert_eq!(
+        (daughter.x, daughter.y, daughter.beliefs.mean.x),
+        (30.0, 30.0, 30.0)
+    );
+
+    // Noise stays within bounds and leaves unvisited cells alone
+    population.belief_init = BeliefInit::Parent { noise: 0.1 };
+    let noisy = population.add(newborn(4), Some(0));
+    let daughter = &population.agents[usize::try_from(noisy).unwrap()];
+    let parent = &population.agents[0];
+    let shift =
+        daughter.spatial_priors.expected(10.0, 10.0) - parent.spatial_priors.expected(10.0, 10.0);
+    assert!(shift != 0.0 && shift.abs() <= 0.1, "{shift}");
+    assert_eq!(daughter.spatial_priors.expected(50.0, 25.0), 0.5);
+
+    // A founder, or a daughter born in another dish, has nothing to copy
+    let founder = population.add(newborn(5), None);
+    let mut elsewhere = newborn(6);
+    elsewhere.dish_id = 1;
+    let migrant = population.add(elsewhere, Some(0));
+    for id in [founder, migrant] {
+        let agent = &population.agents[usize::try_from(id).unwrap()];
+        assert_eq!(agent.spatial_priors.total_visits(), 0);
+    }
+}
+
+#[test]
+fn test_noise_draws_from_its_own_stream() {
+    let parent = learned(1);
+    let mut a = newborn(7);
+    let mut b = newborn(7);
+    BeliefInit::Parent { noise: 0.1 }.instill(&mut a, Some(&parent), &[]);
+    BeliefInit::Parent { noise: 0.1 }.instill(&mut b, Some(&parent), &[]);
+    assert_eq!(
+        a.spatial_priors.expected(10.0, 10.0),
+        b.spatial_priors.expected(10.0, 10.0),
+        "seeded newborns inherit identically"
+    );
+    // Behavior draws are untouched by the inheritance noise
+    let mut untouched = newborn(7);
+    assert_eq!(
+        a.streams.exploration.random::<u64>(),
+        untouched.streams.exploration.random::<u64>()
+    );
+}
+
+#[test]
+fn test_average_pools_the_living() {
+    let mut other = Protozoa::with_heading(60.0, 25.0, 0.0);
+    other.spatial_priors.update(10.0, 10.0, 0.5);
+    other.episodic_memory.maybe_store(11.0, 10.0, 0.6, 3);
+    other.episodic_memory.maybe_store(80.0, 40.0, 0.7, 3);
+    other.beliefs.mean.nutrient = 0.4;
+    let mut dead = learned(3);
+    dead.died_at = Some(10);
+    dead.spatial_priors.update(10.0, 10.0, 0.0);
+
+    let agents = [learned(1), other, dead];
+    let knowledge = Knowledge::average(agents.iter().take(2)).unwrap();
+    // Visit-weighted mean over all four visits, scaled back to two
+    let cell = knowledge.spatial_priors.get_cell(10.0, 10.0);
+    assert!((cell.mean - (0.9 + 0.8 + 0.85 + 0.5) / 4.0).abs() < 1e-12);
+    assert_eq!(cell.visits, 2);
+    assert!((knowledge.nutrient - 0.6).abs() < 1e-12);
+    assert_eq!(knowledge.episodic_memory.count(), 2, "near landmarks merge");
+    assert!(Knowledge::average([]).is_none());
+
+    let mut population = Population::single(agents[0].clone());
+    population.add(agents[1].clone(), None);
+    population.add(agents[2].clone(), None);
+    population.belief_init = BeliefInit::Average;
+    let id = population.add(newborn(4), None);
+    let child = &population.agents[usize::try_from(id).unwrap()];
+    assert_eq!(
+        child.spatial_priors.expected(10.0, 10.0),
+        cell.mean,
+        "the dead are not averaged"
+    );
+}
+
+#[test]
+fn test_knowledge_round_trips_through_a_file() {
+    let knowledge = Knowledge::of(&learned(1));
+    let text = knowledge.to_json();
+    let loaded = Knowledge::from_json(&text).unwrap();
+    assert_eq!(loaded.to_json(), text);
+    assert_eq!(loaded.nutrient, 0.8);
+    assert_eq!(loaded.episodic_memory.count(), 1);
+
+    assert!(Knowledge::from_json("{").is_err());
+    let short = text.replacen("\"mean\"", "\"mean_\"", 1);
+    assert!(Knowledge::from_json(&short).is_err());
+    let mut crowded: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let landmark = crowded["landmarks"][0].clone();
+    crowded["landmarks"] = vec![landmark; MAX_LANDMARKS + 1].into();
+    assert!(Knowledge::from_json(&crowded.to_string()).is_err());
+
+    // A saved map is stretched to a newborn in a larger dish
+    let mut big = newborn(2);
+    big.spatial_priors.resize(200.0, 100.0);
+    BeliefInit::Saved(Box::new(loaded)).instill(&mut big, None, &[]);
+    assert_eq!(
+        big.spatial_priors.expected(20.0, 20.0),
+        knowledge.spatial_priors.expected(10.0, 10.0)
+    );
+    let landmark = big.episodic_memory.iter().next().unwrap();
+    assert_eq!((landmark.x, landmark.y), (20.0, 20.0));
+}
+
+#[test]
+fn test_export_writes_the_learned_knowledge() {
+    let dir = std::env::temp_dir().join(format!("knowledge_{}", std::process::id()));
+    let mut world = World::single(PetriDish::generate(3, &GeneratorConfig::default()));
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    let options = ExportOptions {
+        ticks: 30,
+        notebook: false,
+        knowledge: true,
+    };
+    let written = export_run(&dir, &mut world, &mut agent, options).unwrap();
+    assert_eq!(written.last(), Some(&dir.join(KNOWLEDGE)));
+
+    let path = dir.join(KNOWLEDGE);
+    let init = BeliefInit::parse(path.to_str().unwrap()).unwrap();
+    let mut founder = newborn(1);
+    init.instill(&mut founder, None, &[]);
+    assert_eq!(
+        founder.spatial_priors.total_visits(),
+        agent.spatial_priors.total_visits()
+    );
+    assert!(founder.spatial_priors.total_visits() > 0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -7,16 +7,22 @@
 
 use protozoa_rust::simulation::agent::Protozoa;
 use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+#[cfg(feature = "tui")]
 use protozoa_rust::ui::DashboardState;
 use std::time::Instant;
 
 const DISH_WIDTH: f64 = 100.0;
 const DISH_HEIGHT: f64 = 50.0;
 
+/// Seed of the simulated dish and agent, so a run cannot starve by chance
+const SEED: u64 = 1;
+
 /// Run simulation for N ticks and return final agent state
 fn run_simulation(ticks: u64) -> Protozoa {
-    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
-    let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    let mut dish = PetriDish::generate(SEED, &GeneratorConfig::default());
+    let mut agent = Protozoa::with_heading(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0, 0.0);
+    agent.seed_streams(SEED);
 
     for _ in 0..ticks {
         dish.update();
@@ -94,8 +100,9 @@ fn test_spatial_priors_updated_during_simulation() {
 #[test]
 fn test_episodic_memory_discovers_landmarks() {
     // Run longer simulation to ensure landmarks are found
-    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
-    let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    let mut dish = PetriDish::generate(SEED, &GeneratorConfig::default());
+    let mut agent = Protozoa::with_heading(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0, 0.0);
+    agent.seed_streams(SEED);
 
     // Run simulation
     for _ in 0..2000 {
@@ -226,8 +233,9 @@ fn test_multiple_agents_independent() {
 
 #[test]
 fn test_full_cognitive_stack_integration() {
-    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
-    let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    let mut dish = PetriDish::generate(SEED, &GeneratorConfig::default());
+    let mut agent = Protozoa::with_heading(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0, 0.0);
+    agent.seed_streams(SEED);
 
     // Run extended simulation
     for tick in 0..1000 {
@@ -259,6 +267,7 @@ fn test_full_cognitive_stack_integration() {
 }
 
 #[test]
+#[cfg(feature = "tui")]
 fn test_dashboard_state_updates_during_simulation() {
     let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
     let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
@@ -275,5 +284,6 @@ fn test_dashboard_state_updates_during_simulation() {
     // Verify state is populated
     assert!(state.energy > 0.0);
     assert!(state.spatial_grid.len() == 200); // 20x10
-    assert!(state.plan_details.len() == 3); // One per action
+    // One per action
+    assert!(state.plan_details.len() == protozoa_rust::simulation::planning::Action::all().len());
 }
@@ -0,0 +1,112 @@
+//! Tests for the interoceptive (energy) channel of the generative model.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::inference::{
+    BeliefState, GenerativeModel, expected_free_energy, interoceptive_efe,
+    interoceptive_free_energy,
+};
+use protozoa_rust::simulation::params::{
+    INTEROCEPTIVE_PRECISION, INTEROCEPTIVE_SETPOINT, TARGET_CONCENTRATION,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+/// Predicted beliefs with the given nutrient mean and variance.
+fn predicted(nutrient: f64, variance: f64) -> BeliefState {
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.0);
+    beliefs.mean.nutrient = nutrient;
+    beliefs.covariance.nutrient_var = variance;
+    beliefs
+}
+
+#[test]
+fn test_model_observes_energy_with_its_own_precision() {
+    let model = GenerativeModel::new();
+    assert_eq!(model.prior_mean.energy, INTEROCEPTIVE_SETPOINT);
+    assert_eq!(
+        model.sensory_precision.interoceptive,
+        INTEROCEPTIVE_PRECISION
+    );
+
+    assert_eq!(
+        interoceptive_free_energy(INTEROCEPTIVE_SETPOINT, &model),
+        0.0
+    );
+    let surprise = interoceptive_free_energy(INTEROCEPTIVE_SETPOINT - 0.5, &model);
+    assert!((surprise - 0.5 * INTEROCEPTIVE_PRECISION * 0.25).abs() < 1e-12);
+}
+
+#[test]
+fn test_pragmatic_weight_grows_smoothly_with_hunger() {
+    let model = GenerativeModel::new();
+    assert_eq!(model.pragmatic_weight(INTEROCEPTIVE_SETPOINT), 1.0);
+    assert_eq!(model.pragmatic_weight(INTEROCEPTIVE_SETPOINT + 0.1), 1.0);
+    let half = model.pragmatic_weight(INTEROCEPTIVE_SETPOINT - 0.5);
+    assert!((half - (1.0 + 0.5 * INTEROCEPTIVE_PRECISION)).abs() < 1e-12);
+
+    // No threshold: every step of hunger weighs a little more
+    let weights: Vec<f64> = (0..=10)
+        .map(|i| model.pragmatic_weight(f64::from(i) / 10.0))
+        .collect();
+    assert!(weights.windows(2).all(|pair| pair[0] > pair[1]));
+
+    // A model that ignores its energy weighs food the same whatever it holds
+    let mut numb = GenerativeModel::new();
+    numb.sensory_precision.interoceptive = 0.0;
+    assert_eq!(numb.pragmatic_weight(0.0), 1.0);
+}
+
+#[test]
+fn test_sated_efe_is_unchanged() {
+    let model = GenerativeModel::new();
+    for (nutrient, variance) in [(0.1, 0.2), (0.5, 0.5), (TARGET_CONCENTRATION, 0.1)] {
+        let beliefs = predicted(nutrient, variance);
+        assert_eq!(
+            interoceptive_efe(&beliefs, &model, INTEROCEPTIVE_SETPOINT),
+            expected_free_energy(&beliefs, &model)
+        );
+    }
+    // At the nutrient target there is no risk to weigh
+    let on_target = predicted(TARGET_CONCENTRATION, 0.3);
+    assert_eq!(
+        interoceptive_efe(&on_target, &model, 0.0),
+        expected_free_energy(&on_target, &model)
+    );
+}
+
+#[test]
+fn test_hunger_trades_certainty_for_food() {
+    let model = GenerativeModel::new();
+    // Promising but ambiguous, or certain but poor
+    let promising = predicted(TARGET_CONCENTRATION, 0.3);
+    let poor = predicted(0.0, 0.1);
+
+    let sated = |beliefs: &BeliefState| interoceptive_efe(beliefs, &model, 1.0);
+    assert!(
+        sated(&poor) < sated(&promising),
+        "a sated agent avoids ambiguity"
+    );
+
+    let hungry = |beliefs: &BeliefState| interoceptive_efe(beliefs, &model, 0.0);
+    assert!(
+        hungry(&promising) < hungry(&poor),
+        "a hungry one goes for food"
+    );
+}
+
+#[test]
+fn test_agent_vfe_includes_the_energy_channel() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    let run = |energy: f64| {
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(8);
+        agent.energy = energy;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        agent.current_vfe
+    };
+    let sated = run(1.0);
+    let hungry = run(0.5);
+    let model = GenerativeModel::new();
+    assert!((hungry - sated - interoceptive_free_energy(0.5, &model)).abs() < 1e-9);
+}
@@ -0,0 +1,102 @@
+//! Tests for configurable source kernel shapes.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::kernel::Kernel;
+use protozoa_rust::simulation::params::{ANNULUS_WIDTH, SOURCE_INDEX_CUTOFF};
+
+const KERNELS: [Kernel; 4] = [
+    Kernel::Gaussian,
+    Kernel::TopHat,
+    Kernel::Exponential,
+    Kernel::Annulus,
+];
+
+fn kernel_dish(kernel: Kernel, seed: u64) -> PetriDish {
+    let config = GeneratorConfig {
+        dish: DishConfig {
+            kernel,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    PetriDish::generate(seed, &config)
+}
+
+#[test]
+fn test_kernel_profiles() {
+    let r = 4.0;
+    let at = |kernel: Kernel, d: f64| kernel.profile(d, 0.0, r);
+    assert!((at(Kernel::Gaussian, 0.0) - 1.0).abs() < 1e-12);
+    assert!((at(Kernel::Gaussian, r) - (-0.5_f64).exp()).abs() < 1e-12);
+    assert!((at(Kernel::TopHat, r) - 1.0).abs() < 1e-12);
+    assert!(at(Kernel::TopHat, r + 1e-9).abs() < 1e-12);
+    assert!((at(Kernel::Exponential, 2.0 * r) - (-2.0_f64).exp()).abs() < 1e-12);
+    assert!((at(Kernel::Annulus, r) - 1.0).abs() < 1e-12, "ridge at r");
+    let w = ANNULUS_WIDTH * r;
+    assert!((at(Kernel::Annulus, 0.0) - (-r * r / (2.0 * w * w)).exp()).abs() < 1e-12);
+    // Radially symmetric
+    for kernel in KERNELS {
+        assert!((kernel.profile(3.0, 4.0, r) - kernel.profile(-5.0, 0.0, r)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_kernel_gradients_match_differences() {
+    let (r, h) = (4.0, 1e-6);
+    for kernel in [Kernel::Gaussian, Kernel::Exponential, Kernel::Annulus] {
+        for &(dx, dy) in &[(1.0, 0.5), (-3.0, 2.0), (6.0, -7.0), (0.2, 0.1)] {
+            let (g_x, g_y) = kernel.gradient(dx, dy, r);
+            let d_x = (kernel.profile(dx + h, dy, r) - kernel.profile(dx - h, dy, r)) / (2.0 * h);
+            let d_y = (kernel.profile(dx, dy + h, r) - kernel.profile(dx, dy - h, r)) / (2.0 * h);
+            assert!((g_x - d_x).abs() < 1e-6, "{kernel:?} at ({dx}, {dy})");
+            assert!((g_y - d_y).abs() < 1e-6, "{kernel:?} at ({dx}, {dy})");
+        }
+        assert_eq!(kernel.gradient(0.0, 0.0, r), (0.0, 0.0));
+    }
+    assert_eq!(Kernel::TopHat.gradient(1.0, 2.0, r), (0.0, 0.0));
+    // Inside the annulus the gradient points outward, toward the ridge
+    assert!(Kernel::Annulus.gradient(1.0, 0.0, r).0 > 0.0);
+    assert!(Kernel::Exponential.gradient(1.0, 0.0, r).0 < 0.0);
+}
+
+#[test]
+fn test_dish_config_selects_kernel() {
+    let dish = kernel_dish(Kernel::Annulus, 4);
+    assert!(!dish.sources.is_empty());
+    assert!(dish.sources.iter().all(|s| s.kernel == Kernel::Annulus));
+    assert!(
+        PetriDish::generate(4, &GeneratorConfig::default())
+            .sources
+            .iter()
+            .all(|s| s.kernel == Kernel::Gaussian)
+    );
+
+    let config = DishConfig::from_toml_str("kernel = \"top_hat\"").unwrap();
+    assert_eq!(config.kernel, Kernel::TopHat);
+    assert!(DishConfig::from_toml_str("kernel = \"cone\"").is_err());
+}
+
+#[test]
+fn test_index_bound_holds_for_every_kernel() {
+    let bound = (-SOURCE_INDEX_CUTOFF.powi(2) / 2.0).exp();
+    for kernel in KERNELS {
+        let dish = kernel_dish(kernel, 6);
+        let index = dish.source_index();
+        let tolerance = dish.sources.len() as f64 * bound + 1e-12;
+        for i in 0..=20 {
+            for j in 0..=10 {
+                let (x, y) = (f64::from(i) * 5.0, f64::from(j) * 5.0);
+                let exact = dish.get_concentration(x, y);
+                let fast = dish.get_concentration_indexed(&index, x, y);
+                assert!(exact - fast <= tolerance, "{kernel:?} ({x}, {y})");
+            }
+        }
+        // Just past the reach the profile is below the bound
+        for source in &dish.sources {
+            let beyond = source.reach() * (1.0 + 1e-9);
+            assert!(kernel.profile(beyond, 0.0, source.radius) <= bound + 1e-12);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Tests for inertial kinematics: momentum, drag and their use in planning.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{Obstacle, PetriDish};
+use protozoa_rust::simulation::kinematics::{Kinematics, Motion};
+use protozoa_rust::simulation::params::{LINEAR_DRAG, MAX_SPEED, ROTATIONAL_DRAG, SENSOR_DIST};
+use protozoa_rust::simulation::planning::Action;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use std::f64::consts::PI;
+
+const REST: Motion = Motion {
+    speed: 0.0,
+    spin: 0.0,
+};
+
+fn dish() -> PetriDish {
+    PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0)
+}
+
+fn agent(kinematics: Kinematics) -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.morphology.kinematics = kinematics;
+    agent
+}
+
+fn tick(agent: &mut Protozoa, dish: &PetriDish) {
+    agent.sense(dish);
+    agent.update_state(dish);
+}
+
+#[test]
+fn test_no_inertia_by_default() {
+    let kinematics = Kinematics::default();
+    assert!(kinematics.is_off());
+    assert!(agent(kinematics).morphology.kinematics.is_off());
+
+    // Commands take effect at once, divided by the viscosity
+    let (motion, turn) = kinematics.advance(REST, PI / 4.0, 1.2, SENSOR_DIST, 2.0, 1.0);
+    assert_eq!(turn, PI / 4.0);
+    assert_eq!(motion.speed, 0.6);
+    assert_eq!(motion.spin, PI / 4.0);
+    assert!(!Kinematics::new(0.5, 0.0).is_off());
+}
+
+#[test]
+fn test_speed_relaxes_exponentially_to_terminal_speed() {
+    let kinematics = Kinematics::new(2.0, 0.0);
+    let mut motion = REST;
+    let mut speeds = Vec::new();
+    for _ in 0..30 {
+        motion = kinematics
+            .advance(motion, 0.0, 1.0, SENSOR_DIST, 1.0, 1.0)
+            .0;
+        speeds.push(motion.speed);
+    }
+    // v(t) = u/η (1 − e^(−k η t / m))
+    for (t, speed) in speeds.iter().enumerate() {
+        let expected = 1.0 - (-LINEAR_DRAG * (t + 1) as f64 / 2.0).exp();
+        assert!((speed - expected).abs() < 1e-12);
+    }
+    assert!(speeds.windows(2).all(|w| w[1] > w[0]));
+    assert!((speeds[29] - 1.0).abs() < 1e-6);
+
+    // A thicker medium lowers the terminal speed
+    let mut motion = REST;
+    for _ in 0..60 {
+        motion = kinematics
+            .advance(motion, 0.0, 1.0, SENSOR_DIST, 2.0, 1.0)
+            .0;
+    }
+    assert!((motion.speed - 0.5).abs() < 1e-6);
+
+    // And a body coasts when the thrust stops
+    let coasting = kinematics
+        .advance(
+            Motion {
+                speed: 1.0,
+                spin: 0.0,
+            },
+            0.0,
+            0.0,
+            SENSOR_DIST,
+            1.0,
+            1.0,
+        )
+        .0;
+    assert!(coasting.speed > 0.5 && coasting.speed < 1.0);
+}
+
+#[test]
+fn test_spin_lags_the_commanded_turn() {
+    let kinematics = Kinematics::new(0.0, 1.0);
+    let (motion, turn) = kinematics.advance(REST, PI / 4.0, 1.0, SENSOR_DIST, 1.0, 1.0);
+    let expected = PI / 4.0 * (1.0 - (-ROTATIONAL_DRAG).exp());
+    assert!((turn - expected).abs() < 1e-12);
+    assert_eq!(motion.spin, turn);
+    // Speed is still instantaneous without mass
+    assert_eq!(motion.speed, 1.0);
+
+    // Held, the turn rate approaches the command; released, it keeps turning
+    let mut motion = motion;
+    for _ in 0..40 {
+        motion = kinematics
+            .advance(motion, PI / 4.0, 1.0, SENSOR_DIST, 1.0, 1.0)
+            .0;
+    }
+    assert!((motion.spin - PI / 4.0).abs() < 1e-6);
+    let (_, turn) = kinematics.advance(motion, 0.0, 1.0, SENSOR_DIST, 1.0, 1.0);
+    assert!(turn > 0.0 && turn < PI / 4.0);
+}
+
+#[test]
+fn test_drag_follows_sensor_length() {
+    assert_eq!(Kinematics::drag(SENSOR_DIST), LINEAR_DRAG);
+    assert_eq!(Kinematics::rotational_drag(SENSOR_DIST), ROTATIONAL_DRAG);
+    assert!((Kinematics::drag(2.0 * SENSOR_DIST) - 2.0 * LINEAR_DRAG).abs() < 1e-12);
+    assert!((Kinematics::rotational_drag(2.0 * SENSOR_DIST) - 4.0 * ROTATIONAL_DRAG).abs() < 1e-12);
+
+    // A bigger body is held back harder: it reaches and sheds speed sooner
+    let kinematics = Kinematics::new(4.0, 4.0);
+    let short = kinematics.advance(REST, PI / 4.0, 1.0, 0.5 * SENSOR_DIST, 1.0, 1.0);
+    let long = kinematics.advance(REST, PI / 4.0, 1.0, 2.0 * SENSOR_DIST, 1.0, 1.0);
+    assert!(long.0.speed > short.0.speed);
+    assert!(long.1 > short.1);
+}
+
+#[test]
+fn test_validate_rejects_bad_bodies() {
+    assert!(Kinematics::default().validate().is_ok());
+    assert!(Kinematics::new(3.0, 0.5).validate().is_ok());
+    assert!(Kinematics::new(-1.0, 0.0).validate().is_err());
+    assert!(Kinematics::new(0.0, -0.1).validate().is_err());
+    assert!(Kinematics::new(f64::NAN, 1.0).validate().is_err());
+    assert!(Kinematics::new(1.0, f64::INFINITY).validate().is_err());
+}
+
+#[test]
+fn test_rollouts_step_the_same_dynamics() {
+    let kinematics = Kinematics::new(3.0, 2.0);
+    let mut agent = agent(kinematics);
+    agent.cruise_speed = 1.0;
+    let state = agent.rollout_state();
+    assert_eq!(state.kinematics, kinematics);
+    assert_eq!(state.motion, agent.motion());
+
+    // From rest a rollout only gets going slowly, as the body would
+    let priors = agent.spatial_priors.clone();
+    let moved = state.step(Action::TurnLeft, &priors);
+    let (motion, turn) = kinematics.advance(REST, PI / 4.0, 1.0, SENSOR_DIST, 1.0, 1.0);
+    assert_eq!(moved.motion, motion);
+    assert!((moved.angle - turn).abs() < 1e-12);
+    assert!((moved.x - (50.0 + motion.speed * turn.cos())).abs() < 1e-12);
+
+    // Without inertia the same rollout turns and moves at once
+    let instant = Protozoa::with_heading(50.0, 25.0, 0.0).rollout_state();
+    let moved = instant.step(Action::TurnLeft, &priors);
+    assert!((moved.angle - PI / 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_agents_carry_momentum_and_obstacles_absorb_it() {
+    let open = dish();
+    let mut light = agent(Kinematics::default());
+    let mut heavy = agent(Kinematics::new(5.0, 5.0));
+    light.cruise_speed = MAX_SPEED;
+    heavy.cruise_speed = MAX_SPEED;
+    tick(&mut light, &open);
+    tick(&mut heavy, &open);
+    // Starting from rest, the heavy body is still picking up speed
+    assert!(heavy.speed < light.speed);
+    assert!((heavy.x - 50.0).abs() < (light.x - 50.0).abs());
+
+    // Running into a wall stops a body with momentum dead
+    let mut walled = dish();
+    walled.obstacles.push(Obstacle {
+        x_min: 50.5,
+        y_min: 0.0,
+        x_max: 60.0,
+        y_max: 50.0,
+    });
+    let mut heavy = agent(Kinematics::new(5.0, 5.0));
+    heavy.speed = MAX_SPEED;
+    heavy.cruise_speed = MAX_SPEED;
+    tick(&mut heavy, &walled);
+    assert_eq!(heavy.x, 50.0);
+    assert_eq!(heavy.speed, 0.0);
+}
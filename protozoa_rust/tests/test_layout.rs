@@ -0,0 +1,134 @@
+//! Tests for environment layouts loaded from ASCII maps and PNG images.
+
+use protozoa_rust::simulation::environment::{Obstacle, PetriDish};
+use protozoa_rust::simulation::layout::{Layout, NutrientMap};
+use protozoa_rust::simulation::spawn::StartPosition;
+
+const ARENA: &str = "\
+##########
+#S.......#
+#...##...#
+#...##..9#
+##########";
+
+#[test]
+fn test_ascii_legend() {
+    let layout = Layout::from_ascii("#.5\n*S\n").unwrap();
+    assert_eq!((layout.cols, layout.rows), (3, 2));
+    assert_eq!(layout.walls, vec![true, false, false, false, false, false]);
+    assert!((layout.nutrient[2] - 5.0 / 9.0).abs() < 1e-12);
+    assert!((layout.nutrient[3] - 1.0).abs() < 1e-12);
+    assert_eq!(layout.start, Some((1, 1)));
+    // Short lines are padded with empty medium
+    assert!(!layout.walls[5] && layout.nutrient[5].abs() < 1e-12);
+
+    let err = Layout::from_ascii("..\n.x").unwrap_err();
+    assert!(err.contains("line 2, column 2"), "{err}");
+    assert!(Layout::from_ascii("").is_err());
+}
+
+#[test]
+fn test_wall_runs_merge_into_rectangles() {
+    let layout = Layout::from_ascii(ARENA).unwrap();
+    let obstacles = layout.obstacles(100.0, 50.0);
+    // Top and bottom walls, two side walls spanning the middle rows, one 2×2 block
+    assert_eq!(obstacles.len(), 5);
+    assert!(obstacles.contains(&Obstacle {
+        x_min: 40.0,
+        y_min: 20.0,
+        x_max: 60.0,
+        y_max: 40.0,
+    }));
+    assert!(obstacles.contains(&Obstacle {
+        x_min: 0.0,
+        y_min: 10.0,
+        x_max: 10.0,
+        y_max: 40.0,
+    }));
+}
+
+#[test]
+fn test_nutrient_map_is_bilinear() {
+    let map = Layout::from_ascii("9.").unwrap().nutrient_map(20.0, 10.0);
+    assert!((map.sample(5.0, 5.0) - 1.0).abs() < 1e-12, "cell center");
+    assert!(
+        (map.sample(10.0, 5.0) - 0.5).abs() < 1e-12,
+        "between centers"
+    );
+    assert!(
+        (map.sample(0.0, 0.0) - 1.0).abs() < 1e-12,
+        "clamped at the edge"
+    );
+    assert!(map.sample(15.0, 5.0).abs() < 1e-12);
+    assert!(NutrientMap::default().sample(1.0, 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_dish_from_layout() {
+    let layout = Layout::from_ascii(ARENA).unwrap();
+    let mut dish = PetriDish::from_layout(&layout, 100.0, 50.0);
+    assert!(dish.sources.is_empty());
+    assert!((dish.get_concentration(85.0, 35.0) - 1.0).abs() < 1e-12);
+    assert!(dish.get_concentration(25.0, 15.0).abs() < 1e-12);
+    assert!(
+        (dish.get_concentration(50.0, 30.0) + 1.0).abs() < 1e-12,
+        "wall"
+    );
+    assert!((dish.get_species_concentration(0, 85.0, 35.0) - 1.0).abs() < 1e-12);
+
+    let before = dish.get_concentration(80.0, 30.0);
+    for _ in 0..20 {
+        dish.update();
+    }
+    assert!((dish.get_concentration(80.0, 30.0) - before).abs() < 1e-12);
+
+    let spawn = layout.spawn_config(100.0, 50.0);
+    assert_eq!(spawn.position, StartPosition::Fixed { x: 15.0, y: 15.0 });
+    let center = Layout::from_ascii("...").unwrap().spawn_config(30.0, 10.0);
+    assert_eq!(center.position, StartPosition::Center);
+}
+
+#[test]
+fn test_load_ascii_file() {
+    let path = std::env::temp_dir().join(format!("layout_{}.txt", std::process::id()));
+    std::fs::write(&path, ARENA).unwrap();
+    let layout = Layout::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!((layout.cols, layout.rows), (10, 5));
+    assert!(Layout::load(&path).unwrap_err().contains("cannot read"));
+}
+
+#[test]
+#[cfg(feature = "png")]
+fn test_png_gray_levels() {
+    let encode = |color: png::ColorType, pixels: &[u8], width: u32| {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, width, 1);
+        encoder.set_color(color);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(pixels).unwrap();
+        writer.finish().unwrap();
+        bytes
+    };
+
+    let gray =
+        Layout::from_png(&encode(png::ColorType::Grayscale, &[0, 127, 254, 255], 4)).unwrap();
+    assert_eq!((gray.cols, gray.rows), (4, 1));
+    assert_eq!(gray.walls, vec![false, false, false, true]);
+    assert!(gray.nutrient[0].abs() < 1e-12);
+    assert!((gray.nutrient[1] - 0.5).abs() < 1e-12);
+    assert!((gray.nutrient[2] - 1.0).abs() < 1e-12);
+
+    // Color pixels by luminance; transparent pixels are empty medium
+    let rgba = [255, 255, 255, 255, 255, 255, 255, 0];
+    let color = Layout::from_png(&encode(png::ColorType::Rgba, &rgba, 2)).unwrap();
+    assert_eq!(color.walls, vec![true, false]);
+    assert!(color.nutrient[1].abs() < 1e-12);
+
+    assert!(
+        Layout::from_png(b"not a png")
+            .unwrap_err()
+            .contains("invalid layout image")
+    );
+}
@@ -0,0 +1,102 @@
+//! Tests for the light field and phototaxis.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::light::LightField;
+use protozoa_rust::simulation::params::{
+    DISH_HEIGHT, DISH_WIDTH, LIGHT_AMBIENT, LIGHT_SENSITIVITY,
+};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::compute_light_grid;
+use std::f64::consts::FRAC_PI_2;
+
+#[test]
+fn test_default_light_field_gradient() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let center = dish.get_light(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    assert!((center - LIGHT_AMBIENT).abs() < 1e-12);
+
+    // Brightness increases monotonically along the light direction (+x)
+    let samples: Vec<f64> = [0.0, 25.0, 50.0, 75.0, 100.0]
+        .iter()
+        .map(|&x| dish.get_light(x, 25.0))
+        .collect();
+    assert!(samples.windows(2).all(|w| w[0] < w[1]));
+    assert!(samples.iter().all(|v| (0.0..=1.0).contains(v)));
+    assert!(dish.get_light(-1.0, 25.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_light_direction_and_clamping() {
+    // Brightening toward +y: uniform along x
+    let field = LightField::directional(100.0, 50.0, FRAC_PI_2, 0.5, 1.0);
+    assert!((field.intensity_at(10.0, 40.0) - field.intensity_at(90.0, 40.0)).abs() < 1e-12);
+    assert!(field.intensity_at(50.0, 50.0) > field.intensity_at(50.0, 0.0));
+
+    let harsh = LightField::directional(100.0, 50.0, 0.0, 0.5, 4.0);
+    assert!((harsh.intensity_at(100.0, 25.0) - 1.0).abs() < 1e-12);
+    assert!(harsh.intensity_at(0.0, 25.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_default_agent_ignores_light() {
+    let agent = Protozoa::new(50.0, 25.0);
+    assert!((agent.morphology.light_sensitivity - LIGHT_SENSITIVITY).abs() < 1e-12);
+}
+
+#[test]
+fn test_phototaxis_steering_sign() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    // Facing +y, the left sensor (angle + sensor_angle) leans toward -x (darker)
+    let mut agent = Protozoa::new(50.0, 25.0);
+    agent.angle = FRAC_PI_2;
+    agent.sense(&dish);
+    assert!(agent.light_l < agent.light_r);
+
+    assert!(agent.phototaxis_steering().abs() < 1e-12);
+    agent.morphology.light_sensitivity = 2.0;
+    let seeking = agent.phototaxis_steering();
+    agent.morphology.light_sensitivity = -2.0;
+    let avoiding = agent.phototaxis_steering();
+    assert!(seeking < 0.0 && avoiding > 0.0);
+    assert!((seeking + avoiding).abs() < 1e-12);
+}
+
+/// Mean x displacement of light-guided agents in a dish without other cues.
+fn mean_drift(sensitivity: f64) -> f64 {
+    let mut dish = PetriDish::empty(DISH_WIDTH, DISH_HEIGHT, 1);
+    dish.thermal.sources.clear();
+    let trials = 20;
+    let mut total = 0.0;
+    for _ in 0..trials {
+        let mut agent = Protozoa::new(50.0, 25.0);
+        agent.morphology.light_sensitivity = sensitivity;
+        for _ in 0..100 {
+            agent.sense(&dish);
+            agent.update_state(&dish);
+        }
+        total += agent.x - 50.0;
+    }
+    total / f64::from(trials)
+}
+
+#[test]
+fn test_positive_and_negative_phototaxis() {
+    let toward = mean_drift(20.0);
+    let away = mean_drift(-20.0);
+    assert!(toward > away, "phototactic {toward} vs photophobic {away}");
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_light_grid_rendering() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let grid = compute_light_grid(&dish, 10, 20);
+    assert_eq!(grid.len(), 10);
+    assert!(grid.iter().all(|line| line.chars().count() == 20));
+    // Left edge is darker than right edge
+    let first = grid[5].chars().next().unwrap();
+    let last = grid[5].chars().last().unwrap();
+    assert_ne!(first, last);
+    assert!(compute_light_grid(&dish, 0, 5).is_empty());
+}
@@ -0,0 +1,171 @@
+//! Tests for likelihood parameter learning: the gain and offsets of the
+//! chemoreceptor observation function follow the prediction errors.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::inference::{BeliefMean, BeliefState, GenerativeModel, Likelihood};
+use protozoa_rust::simulation::params::{
+    LIKELIHOOD_GAIN, LIKELIHOOD_MAX_BIAS, LIKELIHOOD_MAX_GAIN, SENSOR_ANGLE,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+fn belief(nutrient: f64, angle: f64) -> BeliefMean {
+    let mut beliefs = BeliefState::new(50.0, 25.0, angle);
+    beliefs.mean.nutrient = nutrient;
+    beliefs.mean
+}
+
+/// A learning model shown readings from a world with `gain` and offsets
+/// `(bias_l, bias_r)`, over headings sweeping the circle.
+fn learn_from(gain: f64, bias_l: f64, bias_r: f64, steps: usize) -> GenerativeModel {
+    let mut model = GenerativeModel::new();
+    model.likelihood = Likelihood::new(0.01);
+    let lateral = SENSOR_ANGLE.sin();
+    for i in 0..steps {
+        #[allow(clippy::cast_precision_loss)]
+        let angle = i as f64 * 0.37;
+        let mean = belief(0.5, angle);
+        let obs_l = 0.5 + gain * lateral * angle.sin() + bias_l;
+        let obs_r = 0.5 - gain * lateral * angle.sin() + bias_r;
+        let (pred_l, pred_r) = model.observation_function(&mean);
+        model.learn_likelihood((obs_l - pred_l, obs_r - pred_r), angle);
+    }
+    model
+}
+
+#[test]
+fn test_default_is_the_fixed_model() {
+    let likelihood = Likelihood::default();
+    assert!(likelihood.is_off());
+    assert!(likelihood.validate().is_ok());
+    assert_eq!(likelihood.gain, LIKELIHOOD_GAIN);
+    assert!(Likelihood::new(-0.1).validate().is_err());
+    assert!(Likelihood::new(f64::NAN).validate().is_err());
+
+    let model = GenerativeModel::new();
+    let mean = belief(0.4, 0.9);
+    let expected = 0.4 + SENSOR_ANGLE.sin() * 0.2 * 0.9_f64.sin();
+    assert!((model.receptor_prediction(&mean, 1.0) - expected).abs() < 1e-12);
+    let (d_nutrient, d_angle) = model.receptor_jacobian(&mean, -1.0);
+    assert_eq!(d_nutrient, 1.0);
+    assert!((d_angle + SENSOR_ANGLE.sin() * 0.2 * 0.9_f64.cos()).abs() < 1e-12);
+}
+
+#[test]
+fn test_fixed_parameters_never_move() {
+    let mut likelihood = Likelihood::default();
+    likelihood.learn((0.3, -0.3), (5.0, 5.0), 1.0);
+    assert_eq!(likelihood, Likelihood::default());
+    assert_eq!(likelihood.updates, 0);
+}
+
+#[test]
+fn test_gain_learns_a_steeper_gradient() {
+    let model = learn_from(0.5, 0.0, 0.0, 3000);
+    let likelihood = model.likelihood;
+    assert!((likelihood.gain - 0.5).abs() < 0.02, "{likelihood:?}");
+    assert!(likelihood.bias_left.abs() < 0.01 && likelihood.bias_right.abs() < 0.01);
+    assert_eq!(likelihood.updates, 3000);
+    // A flatter gradient pulls the gain down
+    assert!(learn_from(0.05, 0.0, 0.0, 3000).likelihood.gain < 0.1);
+}
+
+#[test]
+fn test_offsets_learn_the_difference_only() {
+    let likelihood = learn_from(LIKELIHOOD_GAIN, 0.08, -0.02, 3000).likelihood;
+    // The shared part of the offsets (0.03) is the nutrient belief's to explain
+    assert!((likelihood.bias_left - 0.05).abs() < 0.01, "{likelihood:?}");
+    assert!(
+        (likelihood.bias_right + 0.05).abs() < 0.01,
+        "{likelihood:?}"
+    );
+    assert!((likelihood.bias_left + likelihood.bias_right).abs() < 1e-12);
+    assert!((likelihood.gain - LIKELIHOOD_GAIN).abs() < 0.02);
+}
+
+#[test]
+fn test_learned_parameters_stay_bounded() {
+    let mut likelihood = Likelihood::new(1.0);
+    for _ in 0..50 {
+        likelihood.learn((5.0, -5.0), (20.0, 20.0), 1.0);
+    }
+    assert_eq!(likelihood.gain, LIKELIHOOD_MAX_GAIN);
+    assert_eq!(likelihood.bias_left, LIKELIHOOD_MAX_BIAS);
+    assert_eq!(likelihood.bias_right, -LIKELIHOOD_MAX_BIAS);
+    for _ in 0..50 {
+        likelihood.learn((-5.0, 5.0), (20.0, 20.0), 1.0);
+    }
+    assert_eq!(likelihood.gain, 0.0);
+    assert_eq!(likelihood.bias_left, -LIKELIHOOD_MAX_BIAS);
+}
+
+#[test]
+fn test_predictions_use_the_learned_parameters() {
+    let mut model = GenerativeModel::new();
+    model.likelihood.gain = 0.4;
+    model.likelihood.bias_left = 0.1;
+    model.likelihood.bias_right = -0.06;
+    let mean = belief(0.5, 0.7);
+    let lateral = SENSOR_ANGLE.sin() * 0.4 * 0.7_f64.sin();
+    let (pred_l, pred_r) = model.observation_function(&mean);
+    assert!((pred_l - (0.5 + lateral + 0.1)).abs() < 1e-12);
+    assert!((pred_r - (0.5 - lateral - 0.06)).abs() < 1e-12);
+    // A receptor facing ahead sees no lateral term and the mean offset
+    assert!((model.receptor_prediction(&mean, 0.0) - 0.52).abs() < 1e-12);
+    let (_, d_angle) = model.receptor_jacobian(&mean, 1.0);
+    assert!((d_angle - SENSOR_ANGLE.sin() * 0.4 * 0.7_f64.cos()).abs() < 1e-12);
+}
+
+#[test]
+fn test_agent_learns_while_it_forages() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Ring, 100.0, 50.0);
+    let make = |rate: f64| {
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(9);
+        agent.generative_model.likelihood = Likelihood::new(rate);
+        agent
+    };
+    let mut fixed = make(0.0);
+    let mut default = Protozoa::with_heading(50.0, 25.0, 0.0);
+    default.seed_streams(9);
+    let mut learning = make(0.005);
+    for _ in 0..100 {
+        for agent in [&mut fixed, &mut default, &mut learning] {
+            agent.sense(&dish);
+            agent.update_state(&dish);
+        }
+    }
+    // Without learning the run is the default one
+    assert_eq!(
+        (fixed.x, fixed.y, fixed.angle),
+        (default.x, default.y, default.angle)
+    );
+    let likelihood = learning.generative_model.likelihood;
+    assert!(likelihood.updates > 0);
+    assert_ne!(likelihood, Likelihood::new(0.005));
+    assert!(learning.check_finite(&learning.tick_context()).is_ok());
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_dashboard_shows_learned_parameters() {
+    use protozoa_rust::ui::DashboardState;
+    use protozoa_rust::ui::render::format_likelihood_line;
+
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::with_heading(10.0, 10.0, 0.0);
+    assert!(
+        DashboardState::from_agent(&agent, &dish)
+            .likelihood
+            .is_none()
+    );
+    agent.generative_model.likelihood = Likelihood {
+        gain: 0.237,
+        bias_left: 0.012,
+        bias_right: -0.012,
+        ..Likelihood::new(0.01)
+    };
+    let state = DashboardState::from_agent(&agent, &dish);
+    let likelihood = state.likelihood.as_ref().unwrap();
+    assert_eq!(format_likelihood_line(likelihood), "k:0.24 β:+0.01/-0.01");
+}
@@ -0,0 +1,153 @@
+//! Tests for starvation death, corpse deposits and lineage tracking.
+
+use protozoa_rust::simulation::AgentMode;
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{Environment, PetriDish};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::lineage::Lineage;
+use protozoa_rust::simulation::params::{CORPSE_INTENSITY, CORPSE_RADIUS, DEATH_STARVATION_TICKS};
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::world::World;
+
+/// Empty dish: nothing to eat anywhere.
+struct Barren;
+
+impl Environment for Barren {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) { 0.0 } else { -1.0 }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+#[test]
+fn test_agent_starves_after_ticks_at_zero_energy() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(1);
+    agent.energy = 0.0;
+    for tick in 1..=DEATH_STARVATION_TICKS {
+        assert!(agent.is_alive(), "alive before tick {tick}");
+        agent.sense(&Barren);
+        agent.update_state(&Barren);
+        assert_eq!(agent.starving_ticks, tick);
+    }
+    assert!(!agent.is_alive());
+    assert_eq!(agent.died_at, Some(DEATH_STARVATION_TICKS));
+    assert!(matches!(agent.current_mode(&Barren), AgentMode::Dead));
+
+    // The dead neither sense nor act
+    let (x, y, val_l) = (agent.x, agent.y, agent.val_l);
+    let dish = PetriDish::generate(3, &GeneratorConfig::default());
+    for _ in 0..20 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+    assert_eq!((agent.x, agent.y, agent.val_l), (x, y, val_l));
+    assert_eq!(agent.speed, 0.0);
+}
+
+#[test]
+fn test_feeding_resets_starvation() {
+    let dish = PetriDish::generate(4, &GeneratorConfig::default());
+    let source = dish.sources[0].clone();
+    let mut agent = Protozoa::with_heading(source.x, source.y, 0.0);
+    agent.seed_streams(2);
+    agent.energy = 0.0;
+    agent.starving_ticks = DEATH_STARVATION_TICKS - 1;
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    assert!(agent.energy > 0.0, "ate at the source");
+    assert_eq!(agent.starving_ticks, 0);
+    assert!(agent.is_alive());
+}
+
+#[test]
+fn test_death_leaves_a_corpse_and_is_recorded() {
+    // Nothing to eat, so a starving agent cannot recover
+    let mut world = World::single(PetriDish::empty(100.0, 50.0, 5));
+    let mut population = Population::single(Protozoa::with_heading(50.0, 25.0, 0.0));
+    population.add(Protozoa::with_heading(20.0, 20.0, 0.0), Some(0));
+    for _ in 0..3 {
+        population.step(&mut world);
+    }
+    let sources = world.dishes[0].sources.len();
+    assert_eq!(sources, 0);
+    let doomed = &mut population.agents[1];
+    (doomed.energy, doomed.starving_ticks) = (0.0, DEATH_STARVATION_TICKS - 1);
+    doomed.reserve.level = 0.0;
+    population.step(&mut world);
+
+    let (x, y) = (population.agents[1].x, population.agents[1].y);
+    assert!(!population.agents[1].is_alive());
+    assert_eq!(population.alive_count(), 1);
+    let corpse = world.dishes[0].sources.last().cloned().unwrap();
+    assert_eq!(world.dishes[0].sources.len(), sources + 1);
+    assert!(corpse.transient && (corpse.x, corpse.y) == (x, y));
+    assert_eq!(
+        (corpse.radius, corpse.intensity),
+        (CORPSE_RADIUS, CORPSE_INTENSITY)
+    );
+    assert_eq!(population.lineage.get(1).unwrap().died, Some(4));
+    assert_eq!(population.lineage.get(0).unwrap().died, None);
+
+    // Corpses are not stepped again
+    let ticks = population.agents[1].tick_count;
+    population.step(&mut world);
+    assert_eq!(population.agents[1].tick_count, ticks);
+    assert_eq!(
+        world.dishes[0].sources.len(),
+        sources + 1,
+        "one corpse only"
+    );
+}
+
+#[test]
+fn test_lineage_rebuilds_family_trees() {
+    let mut lineage = Lineage::default();
+    let founder = lineage.register(None, 0);
+    let other = lineage.register(None, 0);
+    let child = lineage.register(Some(founder.id), 10);
+    let grandchild = lineage.register(Some(child.id), 25);
+    lineage.register(Some(founder.id), 30);
+    assert_eq!((founder.id, other.id, child.id), (0, 1, 2));
+    assert_eq!((child.generation, grandchild.generation), (1, 2));
+    assert_eq!(lineage.ancestry(grandchild.id), vec![3, 2, 0]);
+    assert!(lineage.ancestry(99).is_empty());
+    let children: Vec<u64> = lineage.children(0).map(|r| r.id).collect();
+    assert_eq!(children, vec![2, 4]);
+    assert_eq!(lineage.children(1).count(), 0);
+
+    lineage.record_death(2, 40);
+    lineage.record_death(2, 50);
+    assert_eq!(lineage.get(2).unwrap().died, Some(40), "first death stands");
+    let csv = lineage.to_csv();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "id,parent,generation,born,died");
+    assert_eq!(rows[1], "0,,0,0,");
+    assert_eq!(rows[3], "2,0,1,10,40");
+    assert_eq!(rows.len(), lineage.len() + 1);
+}
+
+#[test]
+fn test_population_assigns_lineage_ids() {
+    let mut population = Population::single(Protozoa::with_heading(10.0, 10.0, 0.0));
+    assert_eq!(population.agents[0].lineage_id, 0);
+    let child = population.add(Protozoa::with_heading(12.0, 10.0, 0.0), Some(0));
+    let grandchild = population.add(Protozoa::with_heading(14.0, 10.0, 0.0), Some(child));
+    assert_eq!(population.agents[2].lineage_id, grandchild);
+    assert_eq!(population.lineage.get(grandchild).unwrap().generation, 2);
+    assert_eq!(population.lineage.len(), 3);
+
+    let orphan = std::panic::catch_unwind(move || {
+        population.add(Protozoa::with_heading(1.0, 1.0, 0.0), Some(42))
+    });
+    assert!(orphan.is_err(), "unknown parents are rejected");
+}
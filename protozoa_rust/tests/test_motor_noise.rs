@@ -0,0 +1,235 @@
+//! Tests for the configurable motor (execution) noise model.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::controller::ControllerKind;
+use protozoa_rust::simulation::ensemble::Run;
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::motor_noise::MotorNoise;
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+use protozoa_rust::simulation::streams::Stream;
+use std::f64::consts::PI;
+
+/// Uniform field: a gradient follower commands no turn at all.
+struct Flat;
+
+impl Environment for Flat {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) { 0.5 } else { -1.0 }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+/// Mean and standard deviation of `values`.
+fn mean_std(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, var.sqrt())
+}
+
+/// Headings of a gradient follower under `noise` over `ticks` ticks.
+fn headings(noise: MotorNoise, seed: u64, ticks: usize) -> Vec<f64> {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(seed);
+    agent.controller = ControllerKind::GradientFollower;
+    agent.motor_noise = noise;
+    (0..ticks)
+        .map(|_| {
+            agent.sense(&Flat);
+            agent.update_state(&Flat);
+            agent.angle
+        })
+        .collect()
+}
+
+#[test]
+fn test_default_is_noiseless() {
+    let noise = MotorNoise::default();
+    assert!(noise.is_off());
+    assert_eq!(DishConfig::default().motor_noise, noise);
+    let mut rng = Stream::Motor.rng(1);
+    assert_eq!(noise.execute(0.3, 1.2, &mut rng), (0.3, 1.2));
+    assert!(headings(noise, 1, 50).iter().all(|&angle| angle == 0.0));
+}
+
+#[test]
+fn test_turns_carry_bias_and_white_noise() {
+    let noise = MotorNoise {
+        turn_std: 0.2,
+        turn_bias: 0.05,
+        ..MotorNoise::default()
+    };
+    let mut rng = Stream::Motor.rng(2);
+    let executed: Vec<(f64, f64)> = (0..20_000)
+        .map(|_| noise.execute(0.1, 1.0, &mut rng))
+        .collect();
+    assert!(executed.iter().all(|&(_, speed)| speed == 1.0));
+    let turns: Vec<f64> = executed.iter().map(|&(turn, _)| turn).collect();
+    let (mean, std) = mean_std(&turns);
+    assert!((mean - 0.15).abs() < 0.01, "mean {mean}");
+    assert!((std - 0.2).abs() < 0.01, "std {std}");
+}
+
+#[test]
+fn test_speed_noise_is_relative_and_never_negative() {
+    let noise = MotorNoise {
+        speed_std: 0.1,
+        ..MotorNoise::default()
+    };
+    let mut rng = Stream::Motor.rng(3);
+    let speeds: Vec<f64> = (0..20_000)
+        .map(|_| noise.execute(0.0, 2.0, &mut rng).1)
+        .collect();
+    let (mean, std) = mean_std(&speeds);
+    assert!((mean - 2.0).abs() < 0.01, "mean {mean}");
+    assert!((std - 0.2).abs() < 0.01, "std {std}");
+
+    // A resting agent stays at rest, and large errors stop at 0
+    assert_eq!(noise.execute(0.0, 0.0, &mut rng).1, 0.0);
+    let wild = MotorNoise {
+        speed_std: 3.0,
+        ..MotorNoise::default()
+    };
+    let speeds: Vec<f64> = (0..1000)
+        .map(|_| wild.execute(0.0, 1.0, &mut rng).1)
+        .collect();
+    assert!(speeds.iter().all(|&speed| speed >= 0.0));
+    assert!(speeds.contains(&0.0));
+}
+
+#[test]
+fn test_noise_is_configured_in_toml() {
+    let config = DishConfig::from_toml_str(
+        "width = 120.0\n[motor_noise]\nturn_std = 0.1\nturn_bias = -0.02\nspeed_std = 0.2\n",
+    )
+    .unwrap();
+    assert_eq!(config.width, 120.0);
+    assert_eq!(
+        config.motor_noise,
+        MotorNoise {
+            turn_std: 0.1,
+            turn_bias: -0.02,
+            speed_std: 0.2,
+        }
+    );
+    assert_eq!(
+        DishConfig::from_toml_str(&config.to_toml_string()),
+        Ok(config)
+    );
+
+    assert!(DishConfig::from_toml_str("[motor_noise]\nsigma = 0.2\n").is_err());
+    assert!(DishConfig::from_toml_str("[motor_noise]\nturn_std = -0.1\n").is_err());
+    assert!(DishConfig::from_toml_str("[motor_noise]\nspeed_std = -0.1\n").is_err());
+    assert!(DishConfig::from_toml_str("[motor_noise]\nturn_bias = nan\n").is_err());
+}
+
+#[test]
+fn test_biased_body_veers_off_its_command() {
+    let noise = MotorNoise {
+        turn_bias: 0.05,
+        ..MotorNoise::default()
+    };
+    let veered = headings(noise, 4, 40);
+    for (tick, angle) in veered.iter().enumerate() {
+        let expected = (0.05 * (tick + 1) as f64).rem_euclid(2.0 * PI);
+        assert!((angle - expected).abs() < 1e-9, "tick {tick}: {angle}");
+    }
+
+    // The command itself is unchanged: the provenance records no turn
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.controller = ControllerKind::GradientFollower;
+    agent.motor_noise = noise;
+    agent.sense(&Flat);
+    agent.update_state(&Flat);
+    assert_eq!(agent.provenance.turn, 0.0);
+    assert_eq!(agent.angle, 0.05);
+}
+
+#[test]
+fn test_agent_motion_is_noisy_and_seeded() {
+    let noise = MotorNoise {
+        turn_std: 0.1,
+        speed_std: 0.2,
+        ..MotorNoise::default()
+    };
+    let noisy = headings(noise, 5, 60);
+    assert_eq!(
+        noisy,
+        headings(noise, 5, 60),
+        "deterministic under the seed"
+    );
+    assert_ne!(noisy, headings(noise, 6, 60));
+    assert!(noisy.iter().any(|&angle| angle != 0.0));
+
+    // Speed noise alone leaves the heading alone but not the distance covered
+    let speed_only = MotorNoise {
+        speed_std: 0.2,
+        ..MotorNoise::default()
+    };
+    assert!(
+        headings(speed_only, 5, 60)
+            .iter()
+            .all(|&angle| angle == 0.0)
+    );
+    let distance = |noise: MotorNoise| {
+        let mut agent = Protozoa::with_heading(20.0, 25.0, 0.0);
+        agent.seed_streams(5);
+        agent.controller = ControllerKind::GradientFollower;
+        agent.motor_noise = noise;
+        for _ in 0..20 {
+            agent.sense(&Flat);
+            agent.update_state(&Flat);
+        }
+        agent.x
+    };
+    assert_ne!(distance(speed_only), distance(MotorNoise::default()));
+}
+
+#[test]
+fn test_scenarios_install_the_dish_motor_noise() {
+    let noise = MotorNoise {
+        turn_std: 0.3,
+        ..MotorNoise::default()
+    };
+    let generator = GeneratorConfig {
+        dish: DishConfig {
+            motor_noise: noise,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    let scenario = Scenario::Generated {
+        generator,
+        spawn: SpawnConfig::default(),
+    };
+    let run = scenario.build(7);
+    assert_eq!(run.agent.motor_noise, noise);
+
+    // Noise draws come from their own stream: the first plan, made from the
+    // same state with the same rollout stream, is unchanged by it
+    let quiet = Scenario::Generated {
+        generator: GeneratorConfig::default(),
+        spawn: SpawnConfig::default(),
+    }
+    .build(7);
+    let first_tick = |mut run: Run| {
+        run.agent.sense(&run.dish);
+        run.agent.update_state(&run.dish);
+        run.agent
+    };
+    let (noisy, quiet) = (first_tick(run), first_tick(quiet));
+    assert_eq!(noisy.planner.best_plan(), quiet.planner.best_plan());
+    assert_ne!(noisy.angle, quiet.angle);
+}
@@ -0,0 +1,177 @@
+//! Tests for the strict numerical mode: finiteness checks per subsystem and
+//! the fault a strict agent halts with.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::export::{ExportOptions, FAULT_DUMP, RUN_CSV, export_run};
+use protozoa_rust::simulation::numerics::{NonFinite, NumericMode, finite, finite_all};
+use protozoa_rust::simulation::pipeline::Phase;
+use protozoa_rust::simulation::world::World;
+
+fn strict_agent() -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(5);
+    agent.numerics = NumericMode::Strict;
+    agent
+}
+
+/// A dish whose flow is broken: it carries the agent to a NaN position.
+fn broken_flow() -> PetriDish {
+    let mut dish = PetriDish::new(100.0, 50.0);
+    dish.flow.strength = f64::NAN;
+    dish
+}
+
+fn tick(agent: &mut Protozoa, dish: &PetriDish) {
+    agent.sense(dish);
+    agent.update_state(dish);
+}
+
+#[test]
+fn test_finite_names_the_first_offender() {
+    assert_eq!(finite(1.5, "energy"), Ok(1.5));
+    let error = finite(f64::NAN, "energy").unwrap_err();
+    assert_eq!(error.context, "energy");
+    assert!(error.value.is_nan());
+    assert_eq!(error.to_string(), "non-finite energy: NaN");
+
+    assert!(finite_all([("x", 1.0), ("y", 2.0)]).is_ok());
+    assert_eq!(
+        finite_all([("x", 1.0), ("y", f64::INFINITY), ("z", f64::NAN)]),
+        Err(NonFinite {
+            context: "y",
+            value: f64::INFINITY
+        })
+    );
+}
+
+#[test]
+fn test_lenient_by_default() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    assert_eq!(agent.numerics, NumericMode::Lenient);
+    assert!(!agent.numerics.is_strict());
+    for _ in 0..20 {
+        tick(&mut agent, &dish);
+    }
+    assert!(agent.fault.is_none());
+    assert!(!agent.is_halted());
+
+    // A lenient agent never halts, whatever its state
+    let mut tick = agent.tick_context();
+    agent.energy = f64::NAN;
+    tick.mean_sense = f64::NAN;
+    assert!(!agent.halt_on_fault("sense", &tick));
+    assert!(agent.check_finite(&tick).is_err());
+}
+
+#[test]
+fn test_subsystems_check_their_own_state() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    assert!(agent.check_finite(&agent.tick_context()).is_ok());
+
+    agent.beliefs.mean.x = f64::NAN;
+    assert_eq!(
+        agent.beliefs.check_finite().unwrap_err().context,
+        "beliefs.mean.x"
+    );
+    agent.morphology.sensor_angle = f64::INFINITY;
+    assert_eq!(
+        agent.morphology.check_finite().unwrap_err().context,
+        "morphology.sensor_angle"
+    );
+    agent.energy = f64::NEG_INFINITY;
+    // The agent's own quantities come before its subsystems
+    assert_eq!(
+        agent
+            .check_finite(&agent.tick_context())
+            .unwrap_err()
+            .context,
+        "energy"
+    );
+    assert!(agent.planner.check_finite().is_ok());
+    assert!(agent.generative_model.check_finite().is_ok());
+}
+
+#[test]
+fn test_strict_agent_halts_at_the_phase_that_broke() {
+    let dish = broken_flow();
+    let mut agent = strict_agent();
+    tick(&mut agent, &dish);
+    let fault = agent.fault.as_deref().expect("halted");
+    assert!(agent.is_halted());
+    assert_eq!(fault.error.context, "x");
+    assert!(fault.error.value.is_nan());
+    // Found right after the phase that produced it, not before
+    assert_eq!(fault.stage, Phase::Locomote.name());
+    assert_eq!(fault.tick, agent.tick_count);
+    assert_eq!(
+        fault.to_string(),
+        "non-finite x: NaN after locomote at tick 1"
+    );
+}
+
+#[test]
+fn test_state_broken_before_the_tick_halts_at_sense() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = strict_agent();
+    agent.beliefs.covariance.x_var = f64::NAN;
+    tick(&mut agent, &dish);
+    let fault = agent.fault.as_deref().expect("halted");
+    assert_eq!(fault.stage, "sense");
+    assert_eq!(fault.error.context, "beliefs.covariance.x_var");
+    // The dump holds the whole agent as it was
+    assert!(fault.dump.starts_with("Protozoa {"));
+    assert!(fault.dump.contains("x_var: NaN"));
+    assert!(fault.dump.contains("numerics: Strict"));
+}
+
+#[test]
+fn test_halted_agent_stays_put() {
+    let dish = broken_flow();
+    let mut agent = strict_agent();
+    tick(&mut agent, &dish);
+    assert!(agent.is_halted());
+    let (angle, energy, ticks) = (agent.angle, agent.energy, agent.tick_count);
+    let fault = agent
+        .fault
+        .as_deref()
+        .map(|f| (f.to_string(), f.dump.clone()));
+    for _ in 0..5 {
+        tick(&mut agent, &dish);
+    }
+    assert_eq!(
+        (agent.angle, agent.energy, agent.tick_count),
+        (angle, energy, ticks)
+    );
+    assert_eq!(
+        agent
+            .fault
+            .as_deref()
+            .map(|f| (f.to_string(), f.dump.clone())),
+        fault
+    );
+}
+
+#[test]
+fn test_export_stops_and_writes_the_dump() {
+    let dir = std::env::temp_dir().join(format!("protozoa_numerics_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut world = World::single(broken_flow());
+    let mut agent = strict_agent();
+    let options = ExportOptions {
+        ticks: 50,
+        notebook: false,
+        knowledge: false,
+    };
+    let error = export_run(&dir, &mut world, &mut agent, options).unwrap_err();
+    assert!(error.starts_with("non-finite x: NaN after locomote"));
+    assert!(error.ends_with(&dir.join(FAULT_DUMP).display().to_string()));
+
+    let dump = std::fs::read_to_string(dir.join(FAULT_DUMP)).unwrap();
+    assert!(dump.starts_with("non-finite x: NaN after locomote at tick 1\n\nProtozoa {"));
+    // The run stops with the agent: the tick it halted on is the last row
+    let csv = std::fs::read_to_string(dir.join(RUN_CSV)).unwrap();
+    assert_eq!(csv.lines().count(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
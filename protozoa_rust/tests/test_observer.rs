@@ -0,0 +1,144 @@
+//! Tests for observer hooks on the dish's source dynamics.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::observer::{SourceEvent, SourceEventLog};
+use protozoa_rust::simulation::world::World;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::render::format_source_events;
+
+/// Seeded dish whose sources deplete within a few ticks.
+fn fast_decay_dish(seed: u64) -> PetriDish {
+    let config = GeneratorConfig {
+        dish: DishConfig {
+            decay_min: 0.5,
+            decay_max: 0.5,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    PetriDish::generate(seed, &config)
+}
+
+#[test]
+fn test_closure_sees_depletion_then_respawn() {
+    let mut dish = fast_decay_dish(5);
+    let mut seen = Vec::new();
+    for _ in 0..20 {
+        dish.update_observed(&mut |tick, event| seen.push((tick, event)));
+    }
+    let (tick, index) = seen
+        .iter()
+        .find_map(|&(tick, e)| match e {
+            SourceEvent::Depleted { index, .. } => Some((tick, index)),
+            _ => None,
+        })
+        .expect("a source depletes");
+    // By day a depleted source is replaced in the same tick
+    assert!(
+        seen.iter().any(|&(t, e)| t == tick
+            && matches!(e, SourceEvent::Respawned { index: i, .. } if i == index))
+    );
+    for (_, event) in &seen {
+        if let SourceEvent::Respawned { index, x, y, .. } = *event {
+            assert!(index < dish.sources.len());
+            assert!((0.0..=dish.width).contains(&x) && (0.0..=dish.height).contains(&y));
+        }
+    }
+}
+
+#[test]
+fn test_edge_crossings_reported() {
+    let mut dish = PetriDish::generate(2, &GeneratorConfig::default());
+    dish.sources.truncate(1);
+    dish.sources[0].x = 0.0;
+    dish.sources[0].intensity = 1.0;
+    let mut log = SourceEventLog::default();
+    for _ in 0..50 {
+        dish.update_observed(&mut log);
+    }
+    let (_, _, crossed) = log.counts();
+    assert!(crossed > 0, "Brownian steps push a source at x = 0 out");
+    assert!(log.iter().all(|(_, e)| match *e {
+        SourceEvent::CrossedEdge { index, x, .. } => index == 0 && x >= 0.0,
+        _ => true,
+    }));
+}
+
+#[test]
+fn test_observing_does_not_change_dynamics() {
+    let mut plain = fast_decay_dish(9);
+    let mut observed = fast_decay_dish(9);
+    let mut log = SourceEventLog::with_capacity(3);
+    for _ in 0..30 {
+        plain.update();
+        observed.update_observed(&mut log);
+    }
+    assert_eq!(plain.sources, observed.sources);
+
+    // Bounded: old events are evicted but still counted
+    let (depleted, respawned, crossed) = log.counts();
+    assert_eq!(log.iter().count(), 3);
+    assert!(depleted + respawned + crossed > 3);
+    let ticks: Vec<u64> = log.iter().map(|(t, _)| *t).collect();
+    assert!(ticks.windows(2).all(|w| w[0] <= w[1]), "oldest first");
+}
+
+#[test]
+fn test_world_routes_events_per_dish() {
+    let mut world = World::single(fast_decay_dish(1));
+    world
+        .dishes
+        .push(PetriDish::generate(2, &GeneratorConfig::default()));
+    let mut logs = vec![SourceEventLog::default()];
+    for _ in 0..20 {
+        world.update_observed(&mut logs);
+    }
+    assert!(logs[0].counts().0 > 0);
+    assert_eq!(
+        world.dishes[1].clock.tick, 20,
+        "unobserved dishes still update"
+    );
+
+    // Any observer type works, e.g. a counting closure per dish
+    let mut counts = [0_usize; 2];
+    let [a, b] = &mut counts;
+    let mut observers: Vec<Box<dyn FnMut(u64, SourceEvent)>> =
+        vec![Box::new(|_, _| *a += 1), Box::new(|_, _| *b += 1)];
+    for _ in 0..20 {
+        for (dish, observer) in world.dishes.iter_mut().zip(&mut observers) {
+            dish.update_observed(observer);
+        }
+    }
+    drop(observers);
+    assert!(counts[0] > 0);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_format_source_events() {
+    let events = [
+        (
+            10,
+            SourceEvent::Depleted {
+                index: 2,
+                species: 0,
+            },
+        ),
+        (
+            10,
+            SourceEvent::Respawned {
+                index: 2,
+                species: 0,
+                x: 40.0,
+                y: 12.5,
+            },
+        ),
+    ];
+    let lines = format_source_events(&events, (4, 3, 1), 1);
+    assert_eq!(lines[0], "Sources: 4 low 3 new 1 edge");
+    assert_eq!(lines.len(), 2, "limited to max_events");
+    assert!(lines[1].contains("#2 new") && lines[1].contains("40"));
+    assert_eq!(format_source_events(&[], (0, 0, 0), 5).len(), 1);
+}
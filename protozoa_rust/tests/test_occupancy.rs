@@ -0,0 +1,120 @@
+//! Tests for the time-spent (occupancy) heatmap layer.
+
+#[cfg(feature = "tui")]
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::occupancy::OccupancyMap;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::DishLayer;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::compute_occupancy_grid;
+
+#[test]
+fn test_record_and_count() {
+    let mut map = OccupancyMap::new(DISH_WIDTH, DISH_HEIGHT);
+    assert_eq!(map.total(), 0);
+    assert_eq!(map.max_count(), 0);
+
+    for _ in 0..5 {
+        map.record(10.2, 20.7);
+    }
+    map.record(80.0, 5.0);
+
+    assert_eq!(map.count_at(10.0, 20.0), 5);
+    assert_eq!(map.count_at(80.5, 5.5), 1);
+    assert_eq!(map.total(), 6);
+    assert_eq!(map.max_count(), 5);
+}
+
+#[test]
+fn test_out_of_range_positions_are_clamped_and_nan_ignored() {
+    let mut map = OccupancyMap::with_resolution(DISH_WIDTH, DISH_HEIGHT, 10, 10);
+    map.record(-5.0, -5.0);
+    map.record(DISH_WIDTH + 5.0, DISH_HEIGHT + 5.0);
+    map.record(f64::NAN, 10.0);
+
+    assert_eq!(map.total(), 2);
+    assert_eq!(map.count_at(0.0, 0.0), 1);
+    assert_eq!(map.count_at(DISH_WIDTH, DISH_HEIGHT), 1);
+}
+
+#[test]
+fn test_log_intensity_scaling() {
+    let mut map = OccupancyMap::new(DISH_WIDTH, DISH_HEIGHT);
+    assert!(map.log_intensity(10.0, 10.0).abs() < 1e-12);
+
+    for _ in 0..99 {
+        map.record(10.0, 10.0);
+    }
+    map.record(50.0, 25.0);
+
+    // Most visited cell is the reference
+    assert!((map.log_intensity(10.0, 10.0) - 1.0).abs() < 1e-12);
+    // A single visit is still clearly visible on a log scale (ln 2 / ln 100 ≈ 0.15)
+    let single = map.log_intensity(50.0, 25.0);
+    assert!(
+        single > 0.1 && single < 0.2,
+        "single visit intensity {single}"
+    );
+    assert!(map.log_intensity(90.0, 40.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_reset_clears_counts() {
+    let mut map = OccupancyMap::new(DISH_WIDTH, DISH_HEIGHT);
+    map.record(10.0, 10.0);
+    map.reset();
+    assert_eq!(map.total(), 0);
+    assert_eq!(map.count_at(10.0, 10.0), 0);
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_occupancy_grid_rendering() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let mut map = OccupancyMap::new(DISH_WIDTH, DISH_HEIGHT);
+    for _ in 0..10 {
+        map.record(0.5, 0.5);
+    }
+
+    let grid = compute_occupancy_grid(&dish, &map, 10, 20);
+    assert_eq!(grid.len(), 10);
+    assert!(grid.iter().all(|line| line.chars().count() == 20));
+    // Visited corner renders at full density, the rest stays blank
+    assert_eq!(grid[0].chars().next(), Some('@'));
+    assert_eq!(grid[9].chars().last(), Some(' '));
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_dish_layer_cycles() {
+    let layer = DishLayer::default();
+    assert_eq!(layer, DishLayer::Nutrient);
+    assert_eq!(layer.next(), DishLayer::Occupancy);
+    assert_eq!(layer.next().next(), DishLayer::Gradient);
+    assert_eq!(layer.next().next().next(), DishLayer::Temperature);
+    assert_eq!(layer.next().next().next().next(), DishLayer::Light);
+    assert_eq!(layer.next().next().next().next().next(), DishLayer::Ph);
+    assert_eq!(
+        layer.next().next().next().next().next().next(),
+        DishLayer::Pheromone
+    );
+    assert_eq!(
+        layer.next().next().next().next().next().next().next(),
+        DishLayer::Quorum
+    );
+    assert_eq!(
+        layer
+            .next()
+            .next()
+            .next()
+            .next()
+            .next()
+            .next()
+            .next()
+            .next(),
+        DishLayer::Density
+    );
+    assert_eq!(DishLayer::Density.next(), DishLayer::Sources);
+    assert_eq!(DishLayer::Sources.next(), DishLayer::Nutrient);
+}
@@ -0,0 +1,159 @@
+//! Tests for the mutation and crossover operators of the evolutionary loop.
+
+use protozoa_rust::simulation::evolution::{Evolution, GENE_COUNT, Gene, Genome};
+use protozoa_rust::simulation::operators::{CrossoverOperator, MutationOperator, StepSize};
+use protozoa_rust::simulation::params::{
+    MUTATION_SCALE, MUTATION_SCALE_MAX, MUTATION_SCALE_MIN, MUTATION_STEP_FACTOR,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::streams::Stream;
+
+/// The genome at the top of every gene's range.
+fn maxed() -> Genome {
+    Genome {
+        genes: Gene::ALL.map(|gene| gene.bounds().1),
+    }
+}
+
+#[test]
+fn test_operators_parse_by_name() {
+    for operator in MutationOperator::ALL {
+        assert_eq!(MutationOperator::parse(operator.name()), Some(operator));
+    }
+    for operator in CrossoverOperator::ALL {
+        assert_eq!(CrossoverOperator::parse(operator.name()), Some(operator));
+    }
+    assert_eq!(
+        MutationOperator::parse("Gaussian"),
+        Some(MutationOperator::Gaussian)
+    );
+    assert_eq!(CrossoverOperator::parse("one-point"), None);
+
+    // The defaults are the genome's own operators
+    let evolution = Evolution::new(Scenario::Preset(EnvironmentPreset::Gradient));
+    assert_eq!(evolution.mutation, MutationOperator::Uniform);
+    assert_eq!(evolution.crossover, CrossoverOperator::Uniform);
+    assert_eq!(evolution.step_size, StepSize::Fixed);
+    let (mut a, mut b) = (Genome::default(), Genome::default());
+    MutationOperator::Uniform.apply(&mut a, 0.5, 0.1, &mut Stream::Mutation.rng(2));
+    b.mutate(0.5, 0.1, &mut Stream::Mutation.rng(2));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_gaussian_mutation_stays_within_bounds() {
+    let mut rng = Stream::Mutation.rng(4);
+    let mut genome = Genome::default();
+    for _ in 0..200 {
+        MutationOperator::Gaussian.apply(&mut genome, 1.0, 0.5, &mut rng);
+        for (gene, value) in Gene::ALL.iter().zip(genome.genes) {
+            let (lo, hi) = gene.bounds();
+            assert!((lo..=hi).contains(&value), "{} = {value}", gene.name());
+        }
+    }
+    assert_ne!(genome, Genome::default());
+
+    // Small steps stay close; most land within one standard deviation
+    let start = Genome::default();
+    let (lo, hi) = Gene::LearningRate.bounds();
+    let sigma = 0.01 * (hi - lo);
+    let within = (0..500)
+        .filter(|_| {
+            let mut genome = start;
+            MutationOperator::Gaussian.apply(&mut genome, 1.0, 0.01, &mut rng);
+            let shift = genome.get(Gene::LearningRate) - start.get(Gene::LearningRate);
+            shift.abs() <= sigma
+        })
+        .count();
+    assert!((280..420).contains(&within), "{within} of 500");
+}
+
+#[test]
+fn test_reset_mutation_restores_defaults() {
+    let mut rng = Stream::Mutation.rng(5);
+    let mut untouched = maxed();
+    MutationOperator::Reset.apply(&mut untouched, 0.0, 0.1, &mut rng);
+    assert_eq!(untouched, maxed());
+
+    let mut genome = maxed();
+    MutationOperator::Reset.apply(&mut genome, 1.0, 0.1, &mut rng);
+    assert_eq!(genome, Genome::default());
+}
+
+#[test]
+fn test_swap_and_blend_crossover() {
+    let (a, b) = (Genome::default(), maxed());
+    let mut rng = Stream::Mutation.rng(6);
+    let mut cuts = [false; GENE_COUNT];
+    for _ in 0..100 {
+        let child = CrossoverOperator::Swap.apply(&a, &b, &mut rng);
+        // A head from the first parent, a non-empty tail from the second
+        let cut = (0..GENE_COUNT)
+            .find(|&i| child.genes[i] != a.genes[i])
+            .expect("the tail swaps");
+        assert!(cut >= 1);
+        assert_eq!(child.genes[cut..], b.genes[cut..]);
+        cuts[cut] = true;
+
+        let blend = CrossoverOperator::Blend.apply(&a, &b, &mut rng);
+        for i in 0..GENE_COUNT {
+            let (lo, hi) = (a.genes[i].min(b.genes[i]), a.genes[i].max(b.genes[i]));
+            assert!((lo..=hi).contains(&blend.genes[i]));
+        }
+    }
+    assert!(cuts[1..].iter().all(|&seen| seen), "every cut point occurs");
+    assert_eq!(CrossoverOperator::Blend.apply(&a, &a, &mut rng), a);
+}
+
+#[test]
+fn test_adaptive_steps_follow_the_success_rule() {
+    assert_eq!(StepSize::Fixed.adapt(0.1, 0.9), 0.1);
+    let grown = StepSize::Adaptive.adapt(0.1, 0.5);
+    assert!((grown - 0.1 * MUTATION_STEP_FACTOR).abs() < 1e-12);
+    let shrunk = StepSize::Adaptive.adapt(0.1, 0.0);
+    assert!((shrunk - 0.1 / MUTATION_STEP_FACTOR).abs() < 1e-12);
+    assert_eq!(
+        StepSize::Adaptive.adapt(MUTATION_SCALE_MAX, 1.0),
+        MUTATION_SCALE_MAX
+    );
+    assert_eq!(
+        StepSize::Adaptive.adapt(MUTATION_SCALE_MIN, 0.0),
+        MUTATION_SCALE_MIN
+    );
+}
+
+#[test]
+fn test_evolution_runs_with_chosen_operators() {
+    let mut evolution = Evolution::new(Scenario::Preset(EnvironmentPreset::Maze));
+    evolution.population = 4;
+    evolution.generations = 3;
+    evolution.ticks = 300;
+    evolution.seeds = vec![0];
+    evolution.seed = 7;
+    let fixed = evolution.run(|_| {});
+    assert!(
+        fixed
+            .generations
+            .iter()
+            .all(|g| g.mutation_scale == MUTATION_SCALE)
+    );
+
+    evolution.mutation = MutationOperator::Gaussian;
+    evolution.crossover = CrossoverOperator::Blend;
+    evolution.step_size = StepSize::Adaptive;
+    let report = evolution.run(|_| {});
+    assert_eq!(report.generations.len(), 3);
+    assert_eq!(report.generations[0].mutation_scale, MUTATION_SCALE);
+    assert!(
+        report.generations[1..]
+            .iter()
+            .all(|g| g.mutation_scale != MUTATION_SCALE),
+        "the scale adapts after the first generation"
+    );
+    assert_eq!(
+        evolution.run(|_| {}),
+        report,
+        "deterministic under the seed"
+    );
+}
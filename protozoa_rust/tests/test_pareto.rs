@@ -0,0 +1,132 @@
+//! Tests for Pareto (NSGA-II) multi-objective evolution.
+
+use protozoa_rust::simulation::evolution::Evolution;
+use protozoa_rust::simulation::fitness::FitnessConfig;
+use protozoa_rust::simulation::pareto::{
+    Selection, crowded_order, crowding_distance, dominates, non_dominated_sort,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+
+fn points() -> Vec<Vec<f64>> {
+    vec![
+        vec![1.0, 5.0],
+        vec![2.0, 4.0],
+        vec![1.5, 3.0],
+        vec![3.0, 1.0],
+        vec![0.5, 0.5],
+        vec![2.0, 4.0],
+    ]
+}
+
+fn pareto_evolution() -> Evolution {
+    let mut evolution = Evolution::new(Scenario::Preset(EnvironmentPreset::Gradient));
+    evolution.ticks = 150;
+    evolution.seeds = vec![0];
+    evolution.population = 6;
+    evolution.generations = 3;
+    evolution.fitness = FitnessConfig::parse("energy=1,coverage=100").unwrap();
+    evolution.selection = Selection::Pareto;
+    evolution
+}
+
+#[test]
+fn test_selection_names_parse() {
+    for selection in Selection::ALL {
+        assert_eq!(Selection::parse(selection.name()), Some(selection));
+    }
+    assert_eq!(Selection::parse("PARETO"), Some(Selection::Pareto));
+    assert_eq!(Selection::parse("nsga"), None);
+    assert_eq!(Selection::default(), Selection::Scalar);
+}
+
+#[test]
+fn test_dominance_and_fronts() {
+    assert!(dominates(&[2.0, 4.0], &[1.5, 3.0]));
+    assert!(dominates(&[2.0, 4.0], &[2.0, 3.0]));
+    assert!(!dominates(&[2.0, 4.0], &[2.0, 4.0]));
+    assert!(!dominates(&[1.0, 5.0], &[2.0, 4.0]));
+
+    let fronts = non_dominated_sort(&points());
+    assert_eq!(fronts, vec![vec![0, 1, 3, 5], vec![2], vec![4]]);
+    assert!(non_dominated_sort(&[]).is_empty());
+}
+
+#[test]
+fn test_crowding_distance_keeps_the_extremes() {
+    let points = points();
+    let distance = crowding_distance(&points, &[0, 1, 3]);
+    assert_eq!(distance[0], f64::INFINITY);
+    assert_eq!(distance[2], f64::INFINITY);
+    // Neighbors span the whole front on both objectives
+    assert!((distance[1] - 2.0).abs() < 1e-12);
+    assert!(crowding_distance(&points, &[2])[0].is_infinite());
+
+    let order = crowded_order(&points);
+    assert_eq!(order.len(), points.len());
+    let ranks: Vec<usize> = order.iter().map(|&(_, rank)| rank).collect();
+    assert_eq!(ranks, vec![0, 0, 0, 0, 1, 2]);
+    assert_eq!(order.last(), Some(&(4, 2)));
+}
+
+#[test]
+fn test_pareto_run_is_deterministic() {
+    let evolution = pareto_evolution();
+    let a = evolution.run(|_| {});
+    let b = evolution.run(|_| {});
+    assert_eq!(a.generations.len(), 3);
+    assert_eq!(a.to_csv(), b.to_csv());
+    assert_eq!(a.front_csv(), b.front_csv());
+}
+
+#[test]
+fn test_pareto_front_is_non_dominated() {
+    let evolution = pareto_evolution();
+    let report = evolution.run(|_| {});
+    for summary in &report.generations {
+        assert!(!summary.front.is_empty());
+        for (i, a) in summary.front.iter().enumerate() {
+            assert_eq!(a.objectives.len(), 4);
+            for b in &summary.front[i + 1..] {
+                assert!(!dominates(&a.objectives, &b.objectives));
+                assert!(!dominates(&b.objectives, &a.objectives));
+                assert_ne!(a.genome, b.genome);
+            }
+        }
+        // The scalar best is never dominated, so an equal point is on the front
+        assert!(
+            summary
+                .front
+                .iter()
+                .any(|i| i.objectives == summary.best.objectives)
+        );
+    }
+}
+
+#[test]
+fn test_front_csv_lists_the_last_front() {
+    let report = pareto_evolution().run(|_| {});
+    let csv = report.front_csv();
+    let mut lines = csv.lines();
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("generation,lifespan,energy,coverage,offspring,survival,fitness,"));
+    let columns = header.split(',').count();
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(rows.len(), report.generations.last().unwrap().front.len());
+    for row in rows {
+        assert_eq!(row.split(',').count(), columns);
+        assert!(row.starts_with("2,"));
+    }
+}
+
+#[test]
+fn test_scalar_selection_is_unchanged() {
+    let mut evolution = pareto_evolution();
+    evolution.selection = Selection::Scalar;
+    let report = evolution.run(|_| {});
+    for summary in &report.generations {
+        assert_eq!(summary.best.fitness, summary.fitness.max);
+        assert!(summary.front.iter().all(|i| i.objectives.len() == 4));
+    }
+    assert_eq!(report.to_csv(), evolution.run(|_| {}).to_csv());
+}
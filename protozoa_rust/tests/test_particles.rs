@@ -0,0 +1,217 @@
+//! Tests for the particle belief over the patch location: weighting,
+//! resampling, the effective sample size and the dashboard panel.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::inference::{Particle, ParticleBelief, PointReading};
+use protozoa_rust::simulation::params::{MAX_PARTICLES, PARTICLE_JITTER};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Particles at `points`, weighted by `weights`.
+fn cloud(points: &[(f64, f64)], weights: &[f64]) -> ParticleBelief {
+    let particles = points
+        .iter()
+        .zip(weights)
+        .map(|(&(x, y), &weight)| Particle { x, y, weight })
+        .collect();
+    ParticleBelief::from_particles(particles, 100.0, 50.0)
+}
+
+/// Readings along y = 20 of a patch at (50, 15). Its mirror image in
+/// the line, (50, 25), explains them just as well.
+fn mirrored_readings() -> Vec<PointReading> {
+    (0..=15)
+        .map(|i| {
+            let x = 35.0 + 2.0 * f64::from(i);
+            let d2 = (x - 50.0).powi(2) + 25.0;
+            PointReading {
+                x,
+                y: 20.0,
+                value: (-d2 / 50.0).exp(),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_uniform_belief_covers_the_dish() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let belief = ParticleBelief::uniform(300, 100.0, 50.0, &mut rng);
+    assert_eq!(belief.len(), 300);
+    assert!(!belief.is_empty());
+    assert_eq!(belief.bounds(), (100.0, 50.0));
+    assert!((belief.effective_sample_size() - 300.0).abs() < 1e-6);
+    let total: f64 = belief.particles().iter().map(|p| p.weight).sum();
+    assert!((total - 1.0).abs() < 1e-12);
+    assert!(
+        belief
+            .particles()
+            .iter()
+            .all(|p| (0.0..=100.0).contains(&p.x) && (0.0..=50.0).contains(&p.y))
+    );
+    // The count is capped, and never empty
+    let mut rng = StdRng::seed_from_u64(1);
+    assert_eq!(
+        ParticleBelief::uniform(MAX_PARTICLES + 1, 100.0, 50.0, &mut rng).len(),
+        MAX_PARTICLES
+    );
+    assert_eq!(ParticleBelief::uniform(0, 100.0, 50.0, &mut rng).len(), 1);
+}
+
+#[test]
+fn test_effective_sample_size() {
+    let points = [(10.0, 10.0), (20.0, 10.0), (30.0, 10.0), (40.0, 10.0)];
+    assert!((cloud(&points, &[1.0; 4]).effective_sample_size() - 4.0).abs() < 1e-12);
+    assert!((cloud(&points, &[1.0, 0.0, 0.0, 0.0]).effective_sample_size() - 1.0).abs() < 1e-12);
+    // Two particles sharing the weight count as two
+    let shared = cloud(&points, &[3.0, 3.0, 0.0, 0.0]);
+    assert!((shared.effective_sample_size() - 2.0).abs() < 1e-12);
+    assert!((shared.particles()[0].weight - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_symmetric_readings_keep_both_patches() {
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut belief = ParticleBelief::uniform(2000, 100.0, 50.0, &mut rng);
+    belief.noise = 0.3;
+    let readings = mirrored_readings();
+    for _ in 0..3 {
+        belief.observe(&readings, &mut rng);
+    }
+    let below = belief.weight_within(50.0, 15.0, 3.0);
+    let above = belief.weight_within(50.0, 25.0, 3.0);
+    assert!(below > 0.3 && above > 0.3, "modes {below:.2} {above:.2}");
+    // A Gaussian summary would put the patch between them, where nothing is
+    let (x, y) = belief.mean();
+    assert!(
+        (x - 50.0).abs() < 1.5 && (y - 20.0).abs() < 2.0,
+        "mean {x} {y}"
+    );
+    assert!(belief.weight_within(x, y, 2.0) < below.min(above) / 3.0);
+    assert!(belief.spread() > 3.0);
+}
+
+#[test]
+fn test_resampling_keeps_the_heavy_particles() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut belief = cloud(
+        &[(10.0, 10.0), (60.0, 30.0), (90.0, 40.0)],
+        &[0.01, 0.98, 0.01],
+    );
+    assert_eq!(belief.resamples(), 0);
+    belief.resample(&mut rng);
+    assert_eq!(belief.resamples(), 1);
+    assert_eq!(belief.len(), 3);
+    // Systematic draws copy the particle holding 98% into every slot,
+    // jittered, and even the weights out
+    for p in belief.particles() {
+        assert!((p.x - 60.0).hypot(p.y - 30.0) < 6.0 * PARTICLE_JITTER);
+        assert!((p.weight - 1.0 / 3.0).abs() < 1e-12);
+    }
+    assert!((belief.effective_sample_size() - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_resampling_waits_for_a_low_sample_size() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut belief = ParticleBelief::uniform(200, 100.0, 50.0, &mut rng);
+    // Readings from outside the dish carry no information
+    let outside = [PointReading {
+        x: -1.0,
+        y: 10.0,
+        value: -1.0,
+    }];
+    assert!(!belief.observe(&outside, &mut rng));
+    assert!((belief.effective_sample_size() - 200.0).abs() < 1e-6);
+
+    // A strong reading leaves few plausible particles, and they are resampled
+    let strong = [PointReading {
+        x: 30.0,
+        y: 30.0,
+        value: 1.0,
+    }];
+    assert!(belief.observe(&strong, &mut rng));
+    assert_eq!(belief.resamples(), 1);
+    assert!((belief.effective_sample_size() - 200.0).abs() < 1e-6);
+    assert!(belief.weight_within(30.0, 30.0, 8.0) > 0.9);
+}
+
+#[test]
+fn test_unexplained_readings_reset_the_weights() {
+    let mut belief = cloud(&[(10.0, 10.0), (90.0, 40.0)], &[0.7, 0.3]);
+    belief.noise = 1e-3;
+    // No particle is anywhere near a patch this strong reading points to
+    belief.reweight(&[PointReading {
+        x: 50.0,
+        y: 25.0,
+        value: 1.0,
+    }]);
+    assert!(belief.check_finite().is_ok());
+    for p in belief.particles() {
+        assert!((p.weight - 0.5).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_agent_tracks_the_patch_without_changing_course() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Ring, 100.0, 50.0);
+    let make = |particles: bool| {
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(6);
+        if particles {
+            agent.track_particles(500, dish.width, dish.height);
+        }
+        agent
+    };
+    assert!(make(false).particles.is_none());
+    let (mut plain, mut tracking) = (make(false), make(true));
+    for _ in 0..60 {
+        for agent in [&mut plain, &mut tracking] {
+            agent.sense(&dish);
+            agent.update_state(&dish);
+        }
+    }
+    // The particles only watch: the run is the same without them
+    assert_eq!(
+        (plain.x, plain.y, plain.angle),
+        (tracking.x, tracking.y, tracking.angle)
+    );
+    let belief = tracking.particles.as_ref().unwrap();
+    assert!(belief.check_finite().is_ok());
+    assert!(belief.effective_sample_size() <= 500.0 + 1e-6);
+    assert!(tracking.check_finite(&tracking.tick_context()).is_ok());
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_dashboard_draws_the_cloud() {
+    use protozoa_rust::ui::DashboardState;
+    use protozoa_rust::ui::render::render_particle_lines;
+
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::with_heading(10.0, 10.0, 0.0);
+    assert!(
+        DashboardState::from_agent(&agent, &dish)
+            .particles
+            .is_none()
+    );
+    agent.particles = Some(cloud(
+        &[(80.0, 40.0), (81.0, 41.0), (20.0, 40.0)],
+        &[0.45, 0.45, 0.1],
+    ));
+    let state = DashboardState::from_agent(&agent, &dish);
+    let cloud = state.particles.as_ref().unwrap();
+    assert_eq!(cloud.points.len(), 3);
+    assert!((cloud.ess - 1.0 / (2.0 * 0.45 * 0.45 + 0.01)).abs() < 1e-9);
+
+    let lines = render_particle_lines(cloud, 10, 5, (state.x, state.y));
+    assert_eq!(lines.len(), 5);
+    assert!(lines.iter().all(|l| l.chars().count() == 10));
+    assert_eq!(lines[1].chars().nth(1), Some('@'));
+    // The heavy pair shares a cell, the light particle is a faint dot
+    assert_eq!(lines[4].chars().nth(8), Some('#'));
+    assert_eq!(lines[4].chars().nth(2), Some('.'));
+    assert!(render_particle_lines(cloud, 0, 5, (0.0, 0.0)).is_empty());
+}
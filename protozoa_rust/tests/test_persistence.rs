@@ -0,0 +1,162 @@
+//! Tests for directional persistence: correlated exploration and the
+//! turning cost on reversals.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::annotation::{MorphologyParam, ParamCommand};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{PERSISTENCE_LENGTH, TURNING_COST};
+use protozoa_rust::simulation::persistence::{
+    correlated_turn, heading_correlation, turning_cost_term,
+};
+use protozoa_rust::simulation::provenance::Term;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Commanded turns of a seeded agent exploring an empty dish.
+fn turns(persistence_length: f64, seed: u64, ticks: usize) -> Vec<f64> {
+    let dish = PetriDish::empty(400.0, 400.0, 1);
+    let mut agent = Protozoa::with_heading(200.0, 200.0, 0.0);
+    agent.seed_streams(seed);
+    agent.morphology.persistence_length = persistence_length;
+    (0..ticks)
+        .map(|_| {
+            agent.sense(&dish);
+            agent.update_state(&dish);
+            agent.provenance.turn
+        })
+        .collect()
+}
+
+/// Total magnitude of the turns that reverse the one before.
+fn reversal_turning(turns: &[f64]) -> f64 {
+    turns
+        .windows(2)
+        .filter(|w| w[0] * w[1] < 0.0)
+        .map(|w| w[1].abs())
+        .sum()
+}
+
+#[test]
+fn test_heading_correlation_decays_along_the_path() {
+    assert_eq!(PERSISTENCE_LENGTH, 0.0);
+    assert_eq!(heading_correlation(1.0, 0.0), 0.0);
+    assert_eq!(heading_correlation(0.0, 20.0), 1.0);
+    assert!((heading_correlation(20.0, 20.0) - (-1.0f64).exp()).abs() < 1e-12);
+    // Faster swimmers cover more of the persistence length per second
+    assert!(heading_correlation(1.5, 20.0) < heading_correlation(0.5, 20.0));
+    assert!(heading_correlation(1.0, 50.0) > heading_correlation(1.0, 5.0));
+}
+
+#[test]
+fn test_exploration_is_an_ar1_process() {
+    // Uncorrelated, the fresh draw passes through unchanged
+    assert_eq!(correlated_turn(0.7, -0.3, 0.0), -0.3);
+    // Fully correlated, the last turn is held
+    assert_eq!(correlated_turn(0.7, -0.3, 1.0), 0.7);
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let rho = 0.9;
+    let mut u = 0.0;
+    let samples: Vec<f64> = (0..20_000)
+        .map(|_| {
+            u = correlated_turn(u, rng.random_range(-1.0..1.0), rho);
+            u
+        })
+        .collect();
+    let n = samples.len() as f64;
+    let variance = samples.iter().map(|u| u * u).sum::<f64>() / n;
+    let lag1 = samples.windows(2).map(|w| w[0] * w[1]).sum::<f64>() / (n - 1.0);
+    // The spread of a single draw, with neighbors correlated by ρ
+    assert!((variance - 1.0 / 3.0).abs() < 0.03, "variance {variance}");
+    assert!(
+        (lag1 / variance - rho).abs() < 0.05,
+        "lag-1 {}",
+        lag1 / variance
+    );
+}
+
+#[test]
+fn test_turning_cost_damps_only_reversals() {
+    let rho = 0.8;
+    let weight = TURNING_COST * rho;
+    // A reversal keeps 1 / (1 + w ρ) of the blended turn
+    let term = turning_cost_term(0.3, -0.2, rho);
+    assert!((0.3 + term - 0.3 / (1.0 + weight)).abs() < 1e-12);
+    assert!(term < 0.0);
+    // Turning on the same way, going straight or starting out is free
+    assert_eq!(turning_cost_term(0.3, 0.2, rho), 0.0);
+    assert_eq!(turning_cost_term(0.0, 0.2, rho), 0.0);
+    assert_eq!(turning_cost_term(0.3, 0.0, rho), 0.0);
+    // And so is everything without persistence
+    assert_eq!(turning_cost_term(0.3, -0.2, 0.0), 0.0);
+}
+
+#[test]
+fn test_no_persistence_by_default() {
+    let dish = PetriDish::empty(400.0, 400.0, 1);
+    let mut agent = Protozoa::with_heading(200.0, 200.0, 0.0);
+    agent.seed_streams(2);
+    assert_eq!(agent.morphology.persistence_length, 0.0);
+    for _ in 0..50 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        assert_eq!(agent.heading_persistence(), 0.0);
+        assert_eq!(agent.provenance.term(Term::Persistence), 0.0);
+        // The exploration term is the fresh draw, scaled
+        assert!(agent.explore_turn.abs() <= 1.0);
+        assert_eq!(agent.last_turn, agent.provenance.turn);
+    }
+}
+
+#[test]
+fn test_persistent_agents_reverse_less() {
+    let (mut jittery, mut persistent) = (0.0, 0.0);
+    for seed in 0..6 {
+        jittery += reversal_turning(&turns(0.0, seed, 300));
+        persistent += reversal_turning(&turns(30.0, seed, 300));
+    }
+    assert!(
+        persistent < 0.6 * jittery,
+        "reversals {persistent} vs {jittery}"
+    );
+}
+
+#[test]
+fn test_persistence_shows_in_the_provenance() {
+    let dish = PetriDish::empty(400.0, 400.0, 1);
+    let mut agent = Protozoa::with_heading(200.0, 200.0, 0.0);
+    agent.seed_streams(4);
+    agent.morphology.persistence_length = 30.0;
+    let mut damped = 0;
+    for _ in 0..200 {
+        let last_turn = agent.last_turn;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        let term = agent.provenance.term(Term::Persistence);
+        if term != 0.0 {
+            damped += 1;
+            // Only a reversal is damped, and never past zero
+            let blended = agent.provenance.turn - term;
+            assert!(blended * last_turn < 0.0);
+            assert!(agent.provenance.turn * blended > 0.0);
+        }
+        assert_eq!(agent.last_turn, agent.provenance.turn);
+        assert!(agent.heading_persistence() > 0.0);
+    }
+    assert!(damped > 0);
+    assert_eq!(Term::Persistence.name(), "persistence");
+}
+
+#[test]
+fn test_persistence_length_is_a_runtime_parameter() {
+    let command = ParamCommand::parse("set persistence 25").unwrap();
+    assert_eq!(command.param, MorphologyParam::Persistence);
+    assert!(ParamCommand::parse("set persistence -1").is_err());
+
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.speed = 1.0;
+    assert_eq!(agent.heading_persistence(), 0.0);
+    command.apply(&mut agent.morphology);
+    assert_eq!(MorphologyParam::Persistence.get(&agent.morphology), 25.0);
+    assert!((agent.heading_persistence() - (-1.0f64 / 25.0).exp()).abs() < 1e-12);
+}
@@ -0,0 +1,132 @@
+//! Tests for the pH field and the pH set-point.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::inference::{GenerativeModel, ph_risk};
+use protozoa_rust::simulation::params::{
+    DISH_HEIGHT, DISH_WIDTH, NEUTRAL_PH, PH_PRIOR_PRECISION, PREFERRED_PH,
+};
+use protozoa_rust::simulation::ph::{PhField, PhSpot};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::compute_ph_grid;
+
+fn acid_spot(x: f64, y: f64) -> PhField {
+    PhField {
+        neutral: NEUTRAL_PH,
+        spots: vec![PhSpot {
+            x,
+            y,
+            radius: 10.0,
+            shift: -0.3,
+        }],
+    }
+}
+
+#[test]
+fn test_ph_field_shape() {
+    let field = acid_spot(50.0, 25.0);
+    assert!((field.ph_at(50.0, 25.0) - 0.2).abs() < 1e-12);
+    assert!(field.ph_at(55.0, 25.0) < field.ph_at(60.0, 25.0));
+    assert!((field.ph_at(0.0, 0.0) - NEUTRAL_PH).abs() < 1e-3);
+
+    let strong = PhField {
+        neutral: 0.9,
+        spots: vec![PhSpot {
+            x: 0.0,
+            y: 0.0,
+            radius: 5.0,
+            shift: 0.5,
+        }],
+    };
+    assert!((strong.ph_at(0.0, 0.0) - 1.0).abs() < 1e-12, "clamped");
+    assert!((PhField::uniform().ph_at(10.0, 10.0) - NEUTRAL_PH).abs() < 1e-12);
+
+    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    dish.ph = field;
+    assert!((dish.get_ph(50.0, 25.0) - 0.2).abs() < 1e-12);
+    assert!((dish.get_ph(-1.0, 25.0) + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_generated_spots_follow_config() {
+    let plain = PetriDish::generate(3, &GeneratorConfig::default());
+    assert!(plain.ph.spots.is_empty());
+
+    let parsed = DishConfig::from_toml_str("ph_spots = 3").unwrap();
+    assert_eq!(parsed.ph_spots, 3);
+    let config = GeneratorConfig {
+        dish: parsed,
+        ..GeneratorConfig::default()
+    };
+    let dish = PetriDish::generate(3, &config);
+    assert_eq!(dish.ph.spots.len(), 3);
+    assert_eq!(dish.ph, PetriDish::generate(3, &config).ph);
+    assert!(dish.ph.spots[0].shift < 0.0 && dish.ph.spots[1].shift > 0.0);
+    for spot in &dish.ph.spots {
+        assert!((0.0..=DISH_WIDTH).contains(&spot.x) && (0.0..=DISH_HEIGHT).contains(&spot.y));
+    }
+}
+
+#[test]
+fn test_ph_risk_minimized_at_setpoint() {
+    let model = GenerativeModel::new();
+    assert!((model.prior_precision.ph - PH_PRIOR_PRECISION).abs() < 1e-12);
+    assert!(ph_risk((0.5, 0.5), 0.5, &model).abs() < 1e-12);
+
+    let off = ph_risk((0.2, 0.2), 0.5, &model);
+    assert!((off - 0.5 * PH_PRIOR_PRECISION * 0.09).abs() < 1e-12);
+    assert!(ph_risk((0.1, 0.1), 0.5, &model) > off);
+    // Shifting the set-point to the local pH removes the penalty
+    assert!(ph_risk((0.2, 0.2), 0.2, &model).abs() < 1e-12);
+
+    let mut lax = GenerativeModel::new();
+    lax.prior_precision.ph = 0.0;
+    assert!(ph_risk((0.2, 0.2), 0.5, &lax).abs() < 1e-12);
+}
+
+#[test]
+fn test_acidic_food_trades_off_against_setpoint() {
+    // The only food patch sits in acidic water
+    let neutral = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    let (x, y) = (neutral.sources[0].x, neutral.sources[0].y);
+    let mut acidic = neutral.clone();
+    acidic.ph = acid_spot(x, y);
+
+    let vfe = |dish: &PetriDish, setpoint: f64| {
+        let mut agent = Protozoa::with_heading(x, y, 0.0);
+        assert!((agent.morphology.preferred_ph - PREFERRED_PH).abs() < 1e-12);
+        agent.morphology.preferred_ph = setpoint;
+        agent.sense(dish);
+        agent.update_state(dish);
+        agent.current_vfe
+    };
+    let fed = vfe(&neutral, PREFERRED_PH);
+    let stressed = vfe(&acidic, PREFERRED_PH);
+    let adapted = vfe(&acidic, acidic.get_ph(x, y));
+    assert!(stressed > fed + 0.01, "{stressed} vs {fed}");
+    assert!(adapted < stressed, "allostatic shift eases the conflict");
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_ph_layer_rendering() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    let flat = compute_ph_grid(&dish, 10, 20);
+    assert_eq!(flat.len(), 10);
+    assert!(
+        flat.iter()
+            .all(|l| l.chars().all(|c| c == flat[0].chars().next().unwrap()))
+    );
+
+    dish.ph = acid_spot(50.0, 25.0);
+    let grid = compute_ph_grid(&dish, 10, 20);
+    let center = grid[5].chars().nth(10).unwrap();
+    let corner = grid[0].chars().next().unwrap();
+    assert_ne!(
+        center, corner,
+        "acidic spot renders sparser than neutral water"
+    );
+}
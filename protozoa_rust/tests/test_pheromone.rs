@@ -0,0 +1,120 @@
+//! Tests for pheromone trail deposition, decay and sensing.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::annotation::{MorphologyParam, ParamCommand};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{PHEROMONE_DECAY, PHEROMONE_FLOOR, PHEROMONE_GAIN};
+use protozoa_rust::simulation::pheromone::PheromoneField;
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::world::World;
+
+#[test]
+fn test_deposit_saturates_and_ignores_outside() {
+    let mut field = PheromoneField::new(100.0, 50.0);
+    assert_eq!(field.level_at(50.0, 25.0), 0.0);
+
+    field.deposit(50.0, 25.0, 0.4);
+    assert!((field.level_at(50.0, 25.0) - 0.4).abs() < 1e-12);
+    field.deposit(50.0, 25.0, 0.9);
+    assert_eq!(field.level_at(50.0, 25.0), 1.0);
+
+    // Outside the dish, non-positive and non-finite amounts leave no trace
+    field.deposit(-1.0, 25.0, 0.5);
+    field.deposit(50.0, 60.0, 0.5);
+    field.deposit(10.0, 10.0, 0.0);
+    field.deposit(10.0, 10.0, -0.5);
+    field.deposit(10.0, 10.0, f64::NAN);
+    assert_eq!(field.level_at(10.0, 10.0), 0.0);
+    assert_eq!(field.level_at(-1.0, 25.0), 0.0);
+    assert!((field.total() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_decay_fades_trails_below_floor_to_zero() {
+    let mut field = PheromoneField::new(100.0, 50.0);
+    field.deposit(20.0, 20.0, 1.0);
+    field.deposit(80.0, 40.0, PHEROMONE_FLOOR * 1.5);
+    field.decay(0.5);
+    assert!((field.level_at(20.0, 20.0) - 0.5).abs() < 1e-12);
+    assert_eq!(field.level_at(80.0, 40.0), 0.0);
+
+    for _ in 0..20 {
+        field.decay(0.5);
+    }
+    assert_eq!(field.total(), 0.0);
+
+    // The dish fades its trails by PHEROMONE_DECAY per second
+    let mut dish = PetriDish::empty(100.0, 50.0, 1);
+    dish.pheromone.deposit(50.0, 25.0, 1.0);
+    dish.update();
+    let expected = dish.clock.factor(PHEROMONE_DECAY);
+    assert!((dish.get_pheromone(50.0, 25.0) - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_agents_lay_no_trail_by_default() {
+    let mut world = World::single(PetriDish::empty(100.0, 50.0, 2));
+    let mut population = Population::single(Protozoa::with_heading(50.0, 25.0, 0.0));
+    population.add(Protozoa::with_heading(20.0, 20.0, 0.0), None);
+    for _ in 0..20 {
+        population.step(&mut world);
+    }
+    assert_eq!(world.dishes[0].pheromone.total(), 0.0);
+    assert_eq!(population.agents[0].trail_steering(), 0.0);
+}
+
+#[test]
+fn test_receptors_steer_toward_or_away_from_trail() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 3);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    // Trail on the agent's left (toward +y)
+    for dx in -5..=5 {
+        for dy in 0..=5 {
+            dish.pheromone
+                .deposit(50.0 + f64::from(dx), 25.0 + f64::from(dy), 1.0);
+        }
+    }
+    agent.sense(&dish);
+    assert!(agent.pheromone_l > agent.pheromone_r);
+    assert_eq!(agent.trail_steering(), 0.0, "insensitive by default");
+
+    agent.morphology.pheromone_sensitivity = 2.0;
+    let toward = agent.trail_steering();
+    let expected = PHEROMONE_GAIN * 2.0 * (agent.pheromone_l - agent.pheromone_r);
+    assert!((toward - expected).abs() < 1e-12);
+    assert!(toward > 0.0, "turns left, toward the trail");
+
+    agent.morphology.pheromone_sensitivity = -2.0;
+    assert!(agent.trail_steering() < 0.0, "turns away from the trail");
+}
+
+#[test]
+fn test_population_lays_trails_that_survive_resize() {
+    let mut world = World::single(PetriDish::empty(100.0, 50.0, 4));
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.morphology.pheromone_deposit = 0.5;
+    let mut population = Population::single(agent);
+    let mut dead = Protozoa::with_heading(20.0, 20.0, 0.0);
+    dead.morphology.pheromone_deposit = 0.5;
+    dead.died_at = Some(0);
+    population.add(dead, None);
+    for _ in 0..10 {
+        population.step(&mut world);
+    }
+    let dish = &mut world.dishes[0];
+    assert!(dish.pheromone.total() > 0.0);
+    assert_eq!(dish.get_pheromone(20.0, 20.0), 0.0, "the dead lay nothing");
+
+    let (x, y) = (population.agents[0].x, population.agents[0].y);
+    let level = dish.get_pheromone(x, y);
+    assert!(level > 0.0);
+    dish.resize(200.0, 100.0);
+    assert!((dish.get_pheromone(x * 2.0, y * 2.0) - level).abs() < 1e-12);
+
+    let deposit = ParamCommand::parse("set deposit 0.2").unwrap();
+    assert_eq!(deposit.param, MorphologyParam::Deposit);
+    assert!(ParamCommand::parse("set deposit -0.1").is_err());
+    let trail = ParamCommand::parse("set trail -1.5").unwrap();
+    assert_eq!(trail.param, MorphologyParam::Trail);
+    assert_eq!(trail.value, -1.5);
+}
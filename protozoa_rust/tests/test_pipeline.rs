@@ -0,0 +1,183 @@
+//! Tests for the composable phases of an agent's tick.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::DEATH_STARVATION_TICKS;
+use protozoa_rust::simulation::pipeline::{Phase, TickPipeline};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+fn dish() -> PetriDish {
+    PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0)
+}
+
+fn agent(seed: u64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(30.0, 20.0, 0.5);
+    agent.seed_streams(seed);
+    agent
+}
+
+/// Poses over `ticks` ticks, each stepped by `step`.
+fn poses(
+    mut agent: Protozoa,
+    dish: &PetriDish,
+    ticks: usize,
+    step: impl Fn(&mut Protozoa, &PetriDish),
+) -> Vec<(f64, f64, f64)> {
+    (0..ticks)
+        .map(|_| {
+            agent.sense(dish);
+            step(&mut agent, dish);
+            (agent.x, agent.y, agent.angle)
+        })
+        .collect()
+}
+
+#[test]
+fn test_default_pipeline_is_the_full_tick() {
+    let pipeline = TickPipeline::default();
+    assert_eq!(pipeline.phases(), Phase::ALL);
+    let names: Vec<&str> = Phase::ALL.iter().map(|p| p.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "infer",
+            "learn_precision",
+            "plan",
+            "act",
+            "remember",
+            "metabolize",
+            "locomote",
+            "regulate"
+        ]
+    );
+    assert_eq!(agent(1).pipeline, pipeline);
+
+    let ablated = pipeline.without(Phase::Plan);
+    assert_eq!(ablated.phases().len(), 7);
+    assert!(!ablated.phases().contains(&Phase::Plan));
+    let reordered = TickPipeline::new(vec![Phase::Act, Phase::Infer, Phase::Act]);
+    assert_eq!(reordered.phases(), [Phase::Act, Phase::Infer, Phase::Act]);
+}
+
+#[test]
+fn test_phases_by_hand_replay_update_state() {
+    let dish = dish();
+    let stepped = poses(agent(2), &dish, 150, |agent, dish| agent.update_state(dish));
+    let by_hand = poses(agent(2), &dish, 150, |agent, dish| {
+        let mut tick = agent.tick_context();
+        agent.infer(dish, &tick);
+        agent.learn_precision(&tick);
+        agent.plan(&mut tick);
+        agent.act(dish, &mut tick);
+        agent.remember(dish, &tick);
+        agent.metabolize(dish, &tick);
+        agent.locomote(dish, &mut tick);
+        agent.regulate(dish, &tick);
+    });
+    assert_eq!(stepped, by_hand);
+
+    let piped = poses(agent(2), &dish, 150, |agent, dish| {
+        TickPipeline::default().run(agent, dish);
+    });
+    assert_eq!(stepped, piped);
+}
+
+#[test]
+fn test_ablating_precision_learning_freezes_precisions() {
+    let dish = dish();
+    let run = |pipeline: TickPipeline| {
+        let mut agent = agent(3);
+        agent.pipeline = pipeline;
+        let start = agent.precision_estimator.precision_left();
+        for _ in 0..100 {
+            agent.sense(&dish);
+            agent.update_state(&dish);
+        }
+        (start, agent.precision_estimator.precision_left())
+    };
+    let (start, learned) = run(TickPipeline::default());
+    assert_ne!(learned, start);
+    let (start, frozen) = run(TickPipeline::default().without(Phase::LearnPrecision));
+    assert_eq!(frozen, start);
+}
+
+#[test]
+fn test_without_a_plan_the_agent_swims_straight_on() {
+    let dish = dish();
+    let mut blind = agent(4);
+    blind.pipeline = TickPipeline::default().without(Phase::Plan);
+    let straight = poses(blind, &dish, 50, |agent, dish| agent.update_state(dish));
+    assert!(straight.iter().all(|&(_, _, angle)| angle == 0.5));
+    let (x, y, _) = straight[straight.len() - 1];
+    assert!(
+        x > 30.0 && y > 20.0,
+        "moved along its heading to ({x}, {y})"
+    );
+
+    let steered = poses(agent(4), &dish, 50, |agent, dish| agent.update_state(dish));
+    assert!(steered.iter().any(|&(_, _, angle)| angle != 0.5));
+}
+
+#[test]
+fn test_ablating_the_body_keeps_it_still_and_fed() {
+    let dish = dish();
+    let mut still = agent(5);
+    still.pipeline = TickPipeline::default().without(Phase::Locomote);
+    let poses = poses(still, &dish, 30, |agent, dish| agent.update_state(dish));
+    assert!(poses.iter().all(|&(x, y, _)| (x, y) == (30.0, 20.0)));
+
+    let mut unfed = agent(5);
+    unfed.energy = 0.6;
+    unfed.pipeline = TickPipeline::default().without(Phase::Metabolize);
+    for _ in 0..30 {
+        unfed.sense(&dish);
+        unfed.update_state(&dish);
+    }
+    assert_eq!(unfed.energy, 0.6);
+    assert_eq!(unfed.tick_count, 30, "remember still keeps the clock");
+}
+
+#[test]
+fn test_reordered_phases_change_what_is_remembered() {
+    let dish = dish();
+    let last_snapshot = |pipeline: TickPipeline| {
+        let mut agent = agent(6);
+        agent.pipeline = pipeline;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        let snapshot = *agent.sensor_history.last().unwrap();
+        ((snapshot.x, snapshot.y), (agent.x, agent.y))
+    };
+    // By default the reading is remembered where it was taken
+    let (remembered, now) = last_snapshot(TickPipeline::default());
+    assert_eq!(remembered, (30.0, 20.0));
+    assert_ne!(now, remembered);
+
+    // Moving before remembering files it under the new position
+    let mut phases = Phase::ALL.to_vec();
+    phases.retain(|&p| p != Phase::Locomote);
+    phases.insert(4, Phase::Locomote);
+    let (remembered, now) = last_snapshot(TickPipeline::new(phases));
+    assert_eq!(remembered, now);
+}
+
+#[test]
+fn test_a_phase_that_kills_ends_the_tick() {
+    // Nothing to eat and nothing banked: the metabolism starves it
+    let mut dish = dish();
+    dish.sources.clear();
+    let mut agent = agent(7);
+    agent.energy = 0.0;
+    agent.reserve.level = 0.0;
+    agent.starving_ticks = DEATH_STARVATION_TICKS - 1;
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    assert!(!agent.is_alive());
+    assert_eq!((agent.x, agent.y), (30.0, 20.0), "no move after dying");
+    assert_eq!(agent.speed, 0.0);
+
+    // A dead agent runs no phase at all
+    let ticks = agent.tick_count;
+    TickPipeline::default().run(&mut agent, &dish);
+    assert_eq!(agent.tick_count, ticks);
+}
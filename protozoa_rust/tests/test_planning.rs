@@ -1,18 +1,32 @@
 //! Tests for planning module components.
 
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
 use protozoa_rust::simulation::memory::SpatialGrid;
-use protozoa_rust::simulation::planning::{Action, AgentState, MCTSPlanner};
+use protozoa_rust::simulation::params::{
+    MCTS_CACHE_DECAY, MCTS_CACHE_MIN_ROLLOUTS, MCTS_CACHE_MIN_WEIGHT, MCTS_DEPTH, MCTS_MIN_DEPTH,
+    MCTS_ROLLOUTS, PLAN_COMMIT_HORIZON,
+};
+use protozoa_rust::simulation::planning::{
+    Action, AgentState, CachedValue, Interruption, MCTSPlanner, PlanExecutor, PlanSignals,
+    RolloutCache, StateKey,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
 
 const DISH_WIDTH: f64 = 100.0;
 const DISH_HEIGHT: f64 = 50.0;
 
 #[test]
-fn test_action_all_returns_three_actions() {
+fn test_action_all_returns_six_actions() {
     let actions = Action::all();
-    assert_eq!(actions.len(), 3);
+    assert_eq!(actions.len(), 6);
     assert!(actions.contains(&Action::TurnLeft));
     assert!(actions.contains(&Action::Straight));
     assert!(actions.contains(&Action::TurnRight));
+    assert!(actions.contains(&Action::Accelerate));
+    assert!(actions.contains(&Action::Decelerate));
+    assert!(actions.contains(&Action::Stop));
+    assert!(Action::TURNS.iter().all(|a| actions.contains(a)));
 }
 
 #[test]
@@ -67,10 +81,7 @@ fn test_multiple_plans_with_different_states() {
         let state = AgentState::new(10.0 + i as f64 * 15.0, 25.0, 0.0, 1.0, 1.0);
         let action = planner.plan(&state, &priors);
 
-        assert!(matches!(
-            action,
-            Action::TurnLeft | Action::Straight | Action::TurnRight
-        ));
+        assert!(Action::all().contains(&action));
     }
 }
 
@@ -183,13 +194,9 @@ fn test_planner_exposes_top_trajectories() {
     planner.plan(&state, &priors);
 
     let details = planner.last_plan_details();
-    assert_eq!(details.len(), 3); // One per action
-    for detail in details {
-        assert!(
-            detail.action == Action::TurnLeft
-                || detail.action == Action::Straight
-                || detail.action == Action::TurnRight
-        );
+    assert_eq!(details.len(), Action::all().len()); // One per action
+    for (detail, action) in details.iter().zip(Action::all()) {
+        assert_eq!(detail.action, action);
     }
 }
 
@@ -213,3 +220,208 @@ fn test_planner_exposes_efe_breakdown() {
         );
     }
 }
+
+#[test]
+fn test_state_key_discretization() {
+    let priors: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    let a = StateKey::new(&AgentState::new(51.0, 26.0, 0.1, 1.0, 0.81), &priors);
+    let b = StateKey::new(&AgentState::new(53.0, 28.0, 0.7, 0.95, 0.99), &priors);
+    // Same 5x5 cell, first octant, speed step, top energy bucket
+    assert_eq!(a, b);
+    assert_eq!((a.col, a.row, a.octant), (10, 5, 0));
+
+    let turned = StateKey::new(&AgentState::new(51.0, 26.0, 3.3, 1.0, 0.81), &priors);
+    assert_eq!(turned.octant, 4);
+    let hungry = StateKey::new(&AgentState::new(51.0, 26.0, 0.1, 1.0, 0.1), &priors);
+    assert_ne!(hungry.energy_bucket, a.energy_bucket);
+    let stopped = StateKey::new(&AgentState::new(51.0, 26.0, 0.1, 0.0, 0.81), &priors);
+    assert_ne!(stopped.speed_step, a.speed_step);
+    let edge = StateKey::new(
+        &AgentState::new(DISH_WIDTH, DISH_HEIGHT, 0.0, 1.0, 1.0),
+        &priors,
+    );
+    assert_eq!((edge.col, edge.row), (19, 9));
+}
+
+#[test]
+fn test_rollout_cache_decay_and_eviction() {
+    let priors: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    let key = StateKey::new(&AgentState::new(50.0, 25.0, 0.0, 1.0, 1.0), &priors);
+    let mut cache = RolloutCache::new();
+    assert!(cache.lookup(key, Action::Straight).is_none());
+
+    #[allow(clippy::cast_precision_loss)]
+    let overfull = 10.0 * MCTS_ROLLOUTS as f64;
+    cache.store(
+        key,
+        Action::Straight,
+        CachedValue {
+            pragmatic: 2.0,
+            epistemic: 1.0,
+            weight: overfull,
+        },
+    );
+    let stored = cache.lookup(key, Action::Straight).unwrap();
+    #[allow(clippy::cast_precision_loss)]
+    let cap = MCTS_ROLLOUTS as f64;
+    assert!((stored.weight - cap).abs() < 1e-12, "weight is capped");
+    assert!((cache.hit_rate() - 0.5).abs() < 1e-12);
+
+    cache.decay();
+    let faded = cache.lookup(key, Action::Straight).unwrap();
+    assert!((faded.weight - cap * MCTS_CACHE_DECAY).abs() < 1e-9);
+    assert!(
+        (faded.pragmatic - 2.0).abs() < 1e-12,
+        "decay keeps the mean"
+    );
+
+    // Entries fade until they fall below the minimum weight
+    while let Some(value) = cache.lookup(key, Action::Straight) {
+        assert!(value.weight >= MCTS_CACHE_MIN_WEIGHT);
+        cache.decay();
+    }
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_replanning_reuses_cached_rollouts() {
+    let priors: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    let state = AgentState::new(50.0, 25.0, 0.0, 1.0, 1.0);
+    let mut planner = MCTSPlanner::new();
+
+    planner.plan(&state, &priors);
+    assert!(
+        planner
+            .last_plan_details()
+            .iter()
+            .all(|d| d.fresh_rollouts == MCTS_ROLLOUTS)
+    );
+    assert_eq!(planner.cache().len(), Action::all().len());
+
+    // A nearly identical state hits the cache and simulates fewer rollouts
+    let nearby = AgentState::new(51.0, 25.5, 0.05, 1.0, 0.98);
+    planner.plan(&nearby, &priors);
+    for detail in planner.last_plan_details() {
+        assert!(detail.fresh_rollouts < MCTS_ROLLOUTS);
+        assert!(detail.fresh_rollouts >= MCTS_CACHE_MIN_ROLLOUTS);
+        assert!(detail.total_efe.is_finite());
+    }
+    assert!(planner.cache().hit_rate() > 0.0);
+
+    // A distant state misses and runs the full budget
+    let far = AgentState::new(10.0, 5.0, 3.0, 1.0, 0.2);
+    planner.plan(&far, &priors);
+    assert!(
+        planner
+            .last_plan_details()
+            .iter()
+            .all(|d| d.fresh_rollouts == MCTS_ROLLOUTS)
+    );
+}
+
+fn calm(vfe: f64) -> PlanSignals {
+    PlanSignals {
+        vfe,
+        hazard: false,
+        arrivals: 0,
+    }
+}
+
+#[test]
+fn test_planner_returns_action_sequence() {
+    let priors: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    let state = AgentState::new(50.0, 25.0, 0.0, 1.0, 1.0);
+    let mut planner = MCTSPlanner::new();
+    let best = planner.plan(&state, &priors);
+    assert_eq!(planner.best_plan().len(), MCTS_DEPTH);
+    assert_eq!(planner.best_plan()[0], best);
+}
+
+#[test]
+fn test_executor_plays_back_committed_plan() {
+    let plan = [Action::TurnLeft, Action::Straight, Action::TurnRight];
+    let mut executor = PlanExecutor::new();
+    executor.commit(&plan, 0);
+    for expected in plan {
+        assert_eq!(executor.check(&calm(1.0)), None);
+        assert_eq!(executor.next_action(), Some(expected));
+    }
+    assert!(!executor.is_committed());
+    assert_eq!(executor.interruptions(), 0);
+
+    // Longer plans are cut to the commitment horizon
+    executor.commit(&[Action::Straight; PLAN_COMMIT_HORIZON + 5], 0);
+    assert_eq!(executor.remaining(), PLAN_COMMIT_HORIZON);
+}
+
+#[test]
+fn test_executor_interruptions() {
+    let plan = [Action::Straight; 5];
+    let mut executor = PlanExecutor::new();
+
+    // Surprise spike against a calm baseline
+    executor.commit(&plan, 0);
+    for _ in 0..3 {
+        assert_eq!(executor.check(&calm(0.5)), None);
+    }
+    assert_eq!(
+        executor.check(&calm(3.0)),
+        Some(Interruption::SurpriseSpike)
+    );
+    assert!(!executor.is_committed());
+
+    executor.commit(&plan, 0);
+    let hazard = PlanSignals {
+        hazard: true,
+        ..calm(0.5)
+    };
+    assert_eq!(executor.check(&hazard), Some(Interruption::Hazard));
+
+    executor.commit(&plan, 2);
+    let arrived = PlanSignals {
+        arrivals: 3,
+        ..calm(0.5)
+    };
+    assert_eq!(executor.check(&arrived), Some(Interruption::GoalReached));
+    assert_eq!(
+        executor.last_interruption(),
+        Some(Interruption::GoalReached)
+    );
+    assert_eq!(executor.interruptions(), 3);
+
+    // Nothing to interrupt without a plan
+    assert_eq!(executor.check(&hazard), None);
+}
+
+#[test]
+fn test_agent_commits_to_plan_between_replans() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    assert_eq!(agent.last_plan_tick, 0);
+    // The plan is as long as the adaptive budget is deep, up to the horizon
+    let committed = agent.planner.budget().depth.min(PLAN_COMMIT_HORIZON);
+    assert_eq!(
+        agent.planner.best_plan().len(),
+        agent.planner.budget().depth
+    );
+    assert_eq!(agent.ticks_until_replan() as usize, committed - 1);
+
+    // Without interruptions the agent replans only when the plan runs out
+    let mut replans = 0;
+    for _ in 0..(3 * PLAN_COMMIT_HORIZON) {
+        let before = agent.last_plan_tick;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        if agent.last_plan_tick != before {
+            replans += 1;
+        }
+    }
+    let interrupted = usize::try_from(agent.plan_executor.interruptions()).unwrap();
+    let shortest = MCTS_MIN_DEPTH.min(PLAN_COMMIT_HORIZON);
+    assert!(
+        replans <= 3 * PLAN_COMMIT_HORIZON / shortest + interrupted,
+        "{replans} replans"
+    );
+}
@@ -0,0 +1,176 @@
+//! Tests for the adaptive MCTS budget.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::params::{
+    MAX_VFE, MCTS_DEPTH, MCTS_MAX_DEPTH, MCTS_MAX_ROLLOUTS, MCTS_MIN_DEPTH, MCTS_MIN_ROLLOUTS,
+    MCTS_RELAXED_ENERGY, MCTS_ROLLOUTS, MCTS_URGENT_ENERGY,
+};
+use protozoa_rust::simulation::planning::{
+    AgentState, MCTSPlanner, PlanningBudget, planning_pressure,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+fn priors() -> SpatialGrid<20, 10> {
+    SpatialGrid::new(100.0, 50.0)
+}
+
+#[test]
+fn test_budget_grows_with_pressure() {
+    assert_eq!(PlanningBudget::default(), PlanningBudget::FIXED);
+    assert_eq!(
+        PlanningBudget::FIXED,
+        PlanningBudget {
+            depth: MCTS_DEPTH,
+            rollouts: MCTS_ROLLOUTS
+        }
+    );
+    assert_eq!(MCTSPlanner::new().budget(), PlanningBudget::FIXED);
+
+    let coast = PlanningBudget::for_pressure(0.0);
+    let crisis = PlanningBudget::for_pressure(1.0);
+    assert_eq!(
+        (coast.depth, coast.rollouts),
+        (MCTS_MIN_DEPTH, MCTS_MIN_ROLLOUTS)
+    );
+    assert_eq!(
+        (crisis.depth, crisis.rollouts),
+        (MCTS_MAX_DEPTH, MCTS_MAX_ROLLOUTS)
+    );
+    assert_eq!(PlanningBudget::for_pressure(-3.0), coast);
+    assert_eq!(PlanningBudget::for_pressure(7.0), crisis);
+    assert_eq!(PlanningBudget::for_pressure(f64::NAN), crisis);
+
+    let mut last = coast;
+    for step in 1..=10 {
+        let budget = PlanningBudget::for_pressure(f64::from(step) / 10.0);
+        assert!(budget.depth >= last.depth && budget.rollouts >= last.rollouts);
+        last = budget;
+    }
+}
+
+#[test]
+fn test_pressure_from_hunger_and_uncertainty() {
+    assert_eq!(planning_pressure(1.0, 0.0), 0.0, "sated and certain");
+    assert_eq!(planning_pressure(MCTS_RELAXED_ENERGY, 0.0), 0.0);
+    assert_eq!(planning_pressure(MCTS_URGENT_ENERGY, 0.0), 1.0);
+    assert_eq!(planning_pressure(0.0, 0.0), 1.0);
+    let halfway = f64::midpoint(MCTS_RELAXED_ENERGY, MCTS_URGENT_ENERGY);
+    assert!((planning_pressure(halfway, 0.0) - 0.5).abs() < 1e-12);
+
+    assert!((planning_pressure(1.0, 0.4 * MAX_VFE) - 0.4).abs() < 1e-12);
+    assert_eq!(planning_pressure(1.0, 3.0 * MAX_VFE), 1.0);
+    // The larger of the two drives the budget
+    assert!((planning_pressure(halfway, 0.2 * MAX_VFE) - 0.5).abs() < 1e-12);
+    assert_eq!(
+        PlanningBudget::adaptive(0.0, 0.0),
+        PlanningBudget::for_pressure(1.0)
+    );
+}
+
+#[test]
+fn test_planner_spends_its_budget() {
+    let state = AgentState::new(50.0, 25.0, 0.0, 1.0, 1.0);
+    let mut planner = MCTSPlanner::new();
+    planner.seed(1);
+    let shallow = PlanningBudget {
+        depth: 4,
+        rollouts: 20,
+    };
+    planner.set_budget(shallow);
+    let best = planner.plan(&state, &priors());
+    assert_eq!(planner.budget(), shallow);
+    assert_eq!(planner.best_plan().len(), 4);
+    assert_eq!(planner.best_plan()[0], best);
+    assert!(
+        planner
+            .last_plan_details()
+            .iter()
+            .all(|d| d.fresh_rollouts == 20 && d.sample_trajectory.len() == 5)
+    );
+}
+
+#[test]
+fn test_cache_is_shared_across_depths() {
+    let state = AgentState::new(50.0, 25.0, 0.0, 1.0, 1.0);
+    let straight_value = |planner: &MCTSPlanner| planner.last_plan_details()[1].total_efe;
+    let cold = |depth: usize| {
+        let mut planner = MCTSPlanner::new();
+        planner.seed(2);
+        planner.set_budget(PlanningBudget {
+            depth,
+            rollouts: 40,
+        });
+        planner.plan(&state, &priors());
+        planner
+    };
+    let cold_shallow = straight_value(&cold(5));
+    let cold_deep = straight_value(&cold(12));
+
+    // A planner warmed up deep reuses its estimates when planning shallow,
+    // rescaled to the shallow depth
+    let mut warm = cold(12);
+    warm.set_budget(PlanningBudget {
+        depth: 5,
+        rollouts: 40,
+    });
+    warm.plan(&state, &priors());
+    assert!(
+        warm.last_plan_details()
+            .iter()
+            .all(|d| d.fresh_rollouts < 40)
+    );
+    let warm_shallow = straight_value(&warm);
+    assert!(
+        (warm_shallow - cold_shallow).abs() < (cold_deep - cold_shallow).abs(),
+        "warm {warm_shallow}, cold {cold_shallow}, deep {cold_deep}"
+    );
+}
+
+#[test]
+fn test_agent_sizes_its_budget_at_each_replan() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+
+    // Starving: the largest budget whatever the surprise
+    let mut hungry = Protozoa::with_heading(50.0, 25.0, 0.0);
+    hungry.seed_streams(4);
+    hungry.energy = 0.1;
+    hungry.sense(&dish);
+    hungry.update_state(&dish);
+    assert_eq!(hungry.planner.budget(), PlanningBudget::for_pressure(1.0));
+    assert_eq!(hungry.planner.best_plan().len(), MCTS_MAX_DEPTH);
+
+    // Sated: sized by the free energy of the tick it planned in
+    let mut sated = Protozoa::with_heading(50.0, 25.0, 0.0);
+    sated.seed_streams(4);
+    sated.sense(&dish);
+    let energy = sated.energy;
+    sated.update_state(&dish);
+    assert_eq!(
+        sated.planner.budget(),
+        PlanningBudget::adaptive(energy, sated.current_vfe)
+    );
+    assert!(sated.planner.budget().depth < MCTS_MAX_DEPTH);
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_mcts_panel_shows_the_budget() {
+    use protozoa_rust::ui::DashboardState;
+    use protozoa_rust::ui::render::format_mcts_summary;
+
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(5);
+    agent.energy = 0.1;
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    let state = DashboardState::from_agent(&agent, &dish);
+    assert_eq!(state.planning_budget, agent.planner.budget());
+
+    let lines = format_mcts_summary(&state.plan_details, state.planning_budget, 3);
+    let fresh = state.plan_details[0].fresh_rollouts;
+    assert!(lines.contains(&format!("Rolls: {fresh}/{MCTS_MAX_ROLLOUTS}")));
+    assert!(lines.contains(&format!("Depth: {MCTS_MAX_DEPTH}")));
+}
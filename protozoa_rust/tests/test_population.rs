@@ -0,0 +1,144 @@
+//! Tests for populations of agents sharing one world.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::Environment;
+#[cfg(feature = "tui")]
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::spawn::{SpawnConfig, StartPosition};
+use protozoa_rust::simulation::world::World;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::{DashboardState, render::draw_dashboard};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+#[cfg(feature = "tui")]
+use ratatui::{Terminal, backend::TestBackend};
+
+fn uniform() -> SpawnConfig {
+    SpawnConfig {
+        position: StartPosition::Uniform,
+        ..SpawnConfig::default()
+    }
+}
+
+fn world(count: usize) -> World {
+    let config = GeneratorConfig {
+        obstacle_count: 0,
+        ..GeneratorConfig::default()
+    };
+    World::chain(count, &config, 9)
+}
+
+#[test]
+fn test_spawn_places_distinct_agents() {
+    let world = world(1);
+    let population = Population::spawn(5, &world, &uniform(), &mut StdRng::seed_from_u64(1));
+    assert_eq!(population.len(), 5);
+    assert_eq!(population.selected_index(), 0);
+    for (i, a) in population.agents.iter().enumerate() {
+        assert!(world.dishes[0].contains(a.x, a.y));
+        for b in &population.agents[i + 1..] {
+            assert!((a.x, a.y) != (b.x, b.y));
+        }
+    }
+    let lone = Population::spawn(0, &world, &uniform(), &mut StdRng::seed_from_u64(1));
+    assert_eq!(lone.len(), 1, "at least one agent");
+    assert!(!lone.is_empty());
+}
+
+#[test]
+fn test_step_updates_every_agent_independently() {
+    let mut world = world(1);
+    let mut population = Population::spawn(3, &world, &uniform(), &mut StdRng::seed_from_u64(2));
+    let mut alone: Vec<Protozoa> = population.agents.clone();
+    for _ in 0..100 {
+        world.update();
+        population.step(&mut world);
+        for agent in &mut alone {
+            world.step_agent(agent);
+        }
+    }
+    for (shared, own) in population.agents.iter().zip(&alone) {
+        assert_eq!(
+            (shared.x, shared.y, shared.energy),
+            (own.x, own.y, own.energy)
+        );
+        assert_eq!(shared.tick_count, 100);
+    }
+}
+
+#[test]
+fn test_selection_cycles_and_targets_commands() {
+    let world = world(1);
+    let mut population = Population::spawn(3, &world, &uniform(), &mut StdRng::seed_from_u64(3));
+    population.select_next();
+    assert_eq!(population.selected_index(), 1);
+    population.selected_mut().set_goal(10.0, 10.0, 1.0);
+    assert!(population.agents[1].goal.is_some());
+    assert!(population.agents[0].goal.is_none() && population.agents[2].goal.is_none());
+    population.select_next();
+    population.select_next();
+    assert_eq!(population.selected_index(), 0, "wraps around");
+    assert_eq!(population.selected().x, population.agents[0].x);
+
+    let mut single = Population::single(Protozoa::with_heading(5.0, 5.0, 0.0));
+    single.select_next();
+    assert_eq!(single.selected_index(), 0);
+}
+
+#[test]
+fn test_agents_spread_over_chained_dishes() {
+    let mut world = world(2);
+    let spawn = SpawnConfig {
+        position: StartPosition::Fixed { x: 95.0, y: 25.0 },
+        ..SpawnConfig::default()
+    };
+    let mut population = Population::spawn(4, &world, &spawn, &mut StdRng::seed_from_u64(4));
+    let mut migrated = false;
+    for _ in 0..300 {
+        world.update();
+        population.step(&mut world);
+        migrated |= population.in_dish(1).next().is_some();
+    }
+    assert!(migrated, "some agent crossed the portal");
+    assert_eq!(
+        population.in_dish(0).count() + population.in_dish(1).count(),
+        4
+    );
+}
+
+#[test]
+fn test_resize_moves_every_agent() {
+    let mut world = world(1);
+    let mut population = Population::spawn(3, &world, &uniform(), &mut StdRng::seed_from_u64(5));
+    let before: Vec<(f64, f64)> = population.agents.iter().map(|a| (a.x, a.y)).collect();
+    world.resize(200.0, 100.0, &mut population.agents);
+    for (agent, (x, y)) in population.agents.iter().zip(before) {
+        assert!((agent.x - 2.0 * x).abs() < 1e-9 && (agent.y - 2.0 * y).abs() < 1e-9);
+        assert_eq!(agent.spatial_priors.world_dimensions(), (200.0, 100.0));
+    }
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_selected_agent_in_sidebar_title() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut state = DashboardState::from_agent(&agent, &dish);
+    assert!(state.agent_label.is_none());
+    state.agent_label = Some("2/5".into());
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal
+        .draw(|f| draw_dashboard(f, vec![" ".repeat(40); 10], &state))
+        .unwrap();
+    let text: String = terminal
+        .backend()
+        .buffer()
+        .content
+        .iter()
+        .map(ratatui::buffer::Cell::symbol)
+        .collect();
+    assert!(text.contains(" Agent 2/5 [Mem "));
+}
@@ -0,0 +1,162 @@
+//! Tests for pursuit predators and the prey's threat-aware inference.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{Environment, PetriDish};
+use protozoa_rust::simulation::inference::{GenerativeModel, threat_risk};
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::params::{
+    PREDATOR_CONTACT_RADIUS, PREDATOR_DRAIN, PREDATOR_SPEED, PREDATOR_TURN_RATE,
+    THREAT_HAZARD_LEVEL,
+};
+use protozoa_rust::simulation::planning::{Action, AgentState, MCTSPlanner};
+use protozoa_rust::simulation::predator::{Predator, hunt, threat_at};
+use protozoa_rust::simulation::streams::Stream;
+
+#[test]
+fn test_pursuit_turns_boundedly_and_closes_in() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    // Prey straight "up" while the predator heads east
+    let mut predator = Predator::new(50.0, 25.0, 0.0);
+    let prey = [(50.0, 45.0)];
+    predator.pursue(&prey, &dish);
+    assert!((predator.angle - PREDATOR_TURN_RATE).abs() < 1e-12);
+
+    let start = (predator.x - prey[0].0).hypot(predator.y - prey[0].1);
+    for _ in 0..30 {
+        predator.pursue(&prey, &dish);
+    }
+    let end = (predator.x - prey[0].0).hypot(predator.y - prey[0].1);
+    assert!(end < start - 10.0, "closed in: {start} -> {end}");
+
+    // Nothing in range: it keeps its heading and swims at its speed
+    let mut idle = Predator::new(50.0, 25.0, 0.3);
+    idle.pursue(&[], &dish);
+    assert!((idle.angle - 0.3).abs() < 1e-12);
+    let moved = (idle.x - 50.0).hypot(idle.y - 25.0);
+    assert!((moved - dish.clock.rate(PREDATOR_SPEED)).abs() < 1e-9);
+}
+
+#[test]
+fn test_threat_scent_peaks_at_predators_and_is_capped() {
+    assert_eq!(threat_at(&[], 50.0, 25.0), 0.0);
+
+    let one = [Predator::new(50.0, 25.0, 0.0)];
+    assert!((threat_at(&one, 50.0, 25.0) - 1.0).abs() < 1e-12);
+    let near = threat_at(&one, 55.0, 25.0);
+    let far = threat_at(&one, 80.0, 25.0);
+    assert!(near > far && far > 0.0);
+
+    let pack = [
+        Predator::new(50.0, 25.0, 0.0),
+        Predator::new(52.0, 25.0, 0.0),
+    ];
+    assert!(threat_at(&pack, 51.0, 25.0) <= 1.0);
+
+    let mut dish = PetriDish::empty(100.0, 50.0, 2);
+    assert_eq!(dish.get_threat(50.0, 25.0), 0.0);
+    dish.predators = one.to_vec();
+    assert!((dish.get_threat(50.0, 25.0) - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_hunting_drains_touched_prey_only() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 3);
+    dish.predators = vec![Predator::new(50.0, 25.0, 0.0)];
+    let mut caught = Protozoa::with_heading(50.0 + PREDATOR_CONTACT_RADIUS / 2.0, 25.0, 0.0);
+    let mut distant = Protozoa::with_heading(90.0, 5.0, 0.0);
+    let mut dead = Protozoa::with_heading(50.0, 25.0, 0.0);
+    dead.died_at = Some(1);
+    for agent in [&mut caught, &mut distant, &mut dead] {
+        agent.energy = 0.5;
+    }
+
+    hunt(&mut dish, [&mut caught, &mut distant, &mut dead]);
+    let drain = dish.clock.rate(PREDATOR_DRAIN);
+    assert!((caught.energy - (0.5 - drain)).abs() < 1e-12);
+    assert_eq!(distant.energy, 0.5);
+    assert_eq!(dead.energy, 0.5);
+    assert_eq!(dish.predators[0].feeding_ticks, 1);
+
+    // Energy bottoms out at zero
+    caught.energy = drain / 2.0;
+    (caught.x, caught.y) = (dish.predators[0].x, dish.predators[0].y);
+    hunt(&mut dish, [&mut caught]);
+    assert_eq!(caught.energy, 0.0);
+}
+
+#[test]
+fn test_prey_senses_and_learns_threat() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 4);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(4);
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    let calm_vfe = agent.current_vfe;
+    assert_eq!((agent.threat_l, agent.threat_r), (0.0, 0.0));
+
+    let model = GenerativeModel::new();
+    assert_eq!(threat_risk((0.0, 0.0), &model), 0.0);
+    assert!(threat_risk((0.8, 0.6), &model) > threat_risk((0.4, 0.3), &model));
+
+    let mut threatened = Protozoa::with_heading(50.0, 25.0, 0.0);
+    threatened.seed_streams(4);
+    dish.predators = vec![Predator::new(52.0, 25.0, 0.0)];
+    threatened.sense(&dish);
+    assert!(f64::midpoint(threatened.threat_l, threatened.threat_r) > THREAT_HAZARD_LEVEL);
+    threatened.update_state(&dish);
+    assert!(threatened.current_vfe > calm_vfe);
+    for _ in 0..50 {
+        threatened.sense(&dish);
+        threatened.update_state(&dish);
+    }
+    let learned = (0..20)
+        .flat_map(|c| (0..10).map(move |r| (f64::from(c) * 5.0, f64::from(r) * 5.0)))
+        .map(|(x, y)| threatened.threat_priors.get_cell(x, y))
+        .filter(|c| c.visits > 0)
+        .map(|c| c.mean)
+        .fold(0.0, f64::max);
+    assert!(learned > 0.0, "threat map learned");
+}
+
+#[test]
+fn test_planning_steers_away_from_learned_threat() {
+    // Poor food everywhere
+    let mut priors: SpatialGrid<20, 10> = SpatialGrid::new(100.0, 50.0);
+    let mut threat: SpatialGrid<20, 10> = SpatialGrid::new(100.0, 50.0);
+    for x in (0..100).step_by(5) {
+        for y in (0..50).step_by(5) {
+            let (x, y) = (f64::from(x), f64::from(y));
+            for _ in 0..10 {
+                priors.update(x, y, 0.1);
+                // Predators were met on the left of an agent heading east
+                if y > 27.0 {
+                    threat.update(x, y, 1.0);
+                }
+            }
+        }
+    }
+    let state = AgentState::new(50.0, 25.0, 0.0, 1.0, 1.0);
+    // Value of turning left over turning right
+    let left_bias = |planner: &MCTSPlanner| {
+        let details = planner.last_plan_details();
+        let value = |action| {
+            details
+                .iter()
+                .find(|d| d.action == action)
+                .unwrap()
+                .total_efe
+        };
+        value(Action::TurnLeft) - value(Action::TurnRight)
+    };
+    for seed in 0..10 {
+        let mut unaware = MCTSPlanner::new();
+        unaware.seed(Stream::Rollouts.seed(seed));
+        unaware.plan(&state, &priors);
+        let mut wary = MCTSPlanner::new();
+        wary.seed(Stream::Rollouts.seed(seed));
+        let action = wary.plan_avoiding(&state, &priors, Some(&threat));
+        // Wary plans turn away from the threat, or hold back
+        assert!(action.angle_delta() <= 0.0, "seed {seed}: {action:?}");
+        assert!(left_bias(&wary) < left_bias(&unaware), "seed {seed}");
+    }
+}
@@ -0,0 +1,149 @@
+//! Tests for the canonical environment presets.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{Obstacle, PetriDish};
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH, MAX_SPEED, PRESET_RING_SOURCES};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::{OBSTACLE_GLYPH, compute_field_grid};
+
+fn preset_dish(preset: EnvironmentPreset) -> PetriDish {
+    PetriDish::from_preset(preset, DISH_WIDTH, DISH_HEIGHT)
+}
+
+#[test]
+fn test_preset_names_round_trip() {
+    for preset in EnvironmentPreset::ALL {
+        assert_eq!(EnvironmentPreset::parse(preset.name()), Some(preset));
+    }
+    assert_eq!(
+        EnvironmentPreset::parse("MAZE"),
+        Some(EnvironmentPreset::Maze)
+    );
+    assert_eq!(EnvironmentPreset::parse("labyrinth"), None);
+}
+
+#[test]
+fn test_presets_are_static_and_reproducible() {
+    for preset in EnvironmentPreset::ALL {
+        let mut dish = preset_dish(preset);
+        let before: Vec<(f64, f64, f64)> = dish
+            .sources
+            .iter()
+            .map(|s| (s.x, s.y, s.intensity))
+            .collect();
+        for _ in 0..50 {
+            dish.update();
+        }
+        let after: Vec<(f64, f64, f64)> = dish
+            .sources
+            .iter()
+            .map(|s| (s.x, s.y, s.intensity))
+            .collect();
+        assert_eq!(before, after, "{preset:?} sources changed");
+
+        let other = preset_dish(preset);
+        assert_eq!(other.obstacles, dish.obstacles);
+        assert_eq!(other.sources.len(), dish.sources.len());
+
+        // Suggested start is deterministic and not inside a wall
+        let spawn = preset.spawn_config(DISH_WIDTH, DISH_HEIGHT);
+        let mut rng = rand::rng();
+        let a = spawn.spawn(&dish, &mut rng);
+        let b = spawn.spawn(&dish, &mut rng);
+        assert!((a.x - b.x).abs() < 1e-12 && (a.y - b.y).abs() < 1e-12);
+        assert!((a.angle - b.angle).abs() < 1e-12);
+        assert!(
+            !dish.is_blocked(a.x, a.y),
+            "{preset:?} spawns inside a wall"
+        );
+        for source in &dish.sources {
+            assert!(!dish.is_blocked(source.x, source.y));
+        }
+    }
+}
+
+#[test]
+fn test_gradient_and_ring_layouts() {
+    let gradient = preset_dish(EnvironmentPreset::Gradient);
+    assert_eq!(gradient.sources.len(), 1);
+    let samples: Vec<f64> = [10.0, 30.0, 50.0, 70.0]
+        .iter()
+        .map(|&x| gradient.get_concentration(x, 25.0))
+        .collect();
+    assert!(samples.windows(2).all(|w| w[0] < w[1]), "{samples:?}");
+
+    let ring = preset_dish(EnvironmentPreset::Ring);
+    assert_eq!(ring.sources.len(), PRESET_RING_SOURCES);
+    let radii: Vec<f64> = ring
+        .sources
+        .iter()
+        .map(|s| (s.x - DISH_WIDTH / 2.0).hypot(s.y - DISH_HEIGHT / 2.0))
+        .collect();
+    assert!(radii.iter().all(|r| (r - radii[0]).abs() < 1e-9));
+    assert!(ring.obstacles.is_empty());
+}
+
+#[test]
+fn test_maze_walls_read_as_toxic_void() {
+    let maze = preset_dish(EnvironmentPreset::Maze);
+    assert_eq!(maze.obstacles.len(), 3);
+    // Every wall is thick enough that one step cannot skip over it
+    assert!(
+        maze.obstacles
+            .iter()
+            .all(|o| o.x_max - o.x_min >= 2.0 * MAX_SPEED)
+    );
+
+    let wall = maze.obstacles[0];
+    let (cx, cy) = (
+        f64::midpoint(wall.x_min, wall.x_max),
+        f64::midpoint(wall.y_min, wall.y_max),
+    );
+    assert!(maze.is_blocked(cx, cy));
+    assert!((maze.get_concentration(cx, cy) + 1.0).abs() < 1e-12);
+    assert!((maze.get_species_concentration(0, cx, cy) + 1.0).abs() < 1e-12);
+
+    let obstacle = Obstacle {
+        x_min: 0.0,
+        y_min: 0.0,
+        x_max: 1.0,
+        y_max: 1.0,
+    };
+    assert!(obstacle.contains(1.0, 0.5));
+    assert!(!obstacle.contains(1.1, 0.5));
+}
+
+#[test]
+fn test_agent_cannot_enter_obstacles() {
+    let dish = preset_dish(EnvironmentPreset::Corridor);
+    // Just below the top wall, repeatedly pushed straight into it
+    let mut agent = Protozoa::with_heading(50.0, 0.35 * DISH_HEIGHT + 1.0, -1.5);
+    for _ in 0..100 {
+        agent.angle = -std::f64::consts::FRAC_PI_2;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        assert!(
+            !dish.is_blocked(agent.x, agent.y),
+            "({}, {})",
+            agent.x,
+            agent.y
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_field_grid_draws_obstacles() {
+    let maze = preset_dish(EnvironmentPreset::Maze);
+    let grid = compute_field_grid(&maze, 25, 100);
+    let walls: usize = grid
+        .iter()
+        .map(|line| line.chars().filter(|&c| c == OBSTACLE_GLYPH).count())
+        .sum();
+    assert!(walls > 0);
+
+    let open = preset_dish(EnvironmentPreset::Ring);
+    let grid = compute_field_grid(&open, 25, 100);
+    assert!(grid.iter().all(|line| !line.contains(OBSTACLE_GLYPH)));
+}
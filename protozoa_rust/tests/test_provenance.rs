@@ -0,0 +1,148 @@
+//! Tests for the per-tick action provenance record.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::controller::{ControllerKind, Decision};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::export::{CSV_COLUMNS, record_csv};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::provenance::{Provenance, TERM_COUNT, Term};
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::world::World;
+
+#[test]
+fn test_terms_rank_by_magnitude() {
+    let names: std::collections::HashSet<&str> = Term::ALL.iter().map(|t| t.name()).collect();
+    assert_eq!(names.len(), TERM_COUNT);
+    for (i, term) in Term::ALL.into_iter().enumerate() {
+        assert_eq!(term as usize, i, "terms are indexed in ALL order");
+    }
+
+    let mut provenance = Provenance::default();
+    assert_eq!(provenance.dominant(), None);
+    provenance.terms[Term::Noise as usize] = -0.3;
+    provenance.terms[Term::Plan as usize] = 0.2;
+    provenance.terms[Term::Efe as usize] = 0.3;
+    assert_eq!(
+        provenance.dominant(),
+        Some((Term::Efe, 0.3)),
+        "earliest on ties"
+    );
+    let ranked: Vec<Term> = provenance.ranked().into_iter().map(|(t, _)| t).collect();
+    assert_eq!(ranked, [Term::Efe, Term::Noise, Term::Plan]);
+    assert_eq!(provenance.term(Term::Goal), 0.0);
+}
+
+#[test]
+fn test_active_inference_records_every_term() {
+    let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(4);
+    run.step();
+    let provenance = run.agent.provenance;
+    assert_eq!(provenance.controller, ControllerKind::ActiveInference);
+    assert!(provenance.replanned, "the first tick plans");
+    assert_eq!(provenance.planned_action, Some(run.agent.planned_action));
+    assert!(provenance.efe_action.is_some());
+    assert_eq!(provenance.term(Term::Policy), 0.0);
+
+    let total: f64 = provenance.terms.iter().sum();
+    assert!(
+        (total - provenance.turn).abs() < 1e-12,
+        "terms add up to the turn"
+    );
+    assert_eq!(provenance.speed, run.agent.speed);
+    assert!(
+        provenance.commanded_speed >= provenance.speed,
+        "drag only slows"
+    );
+
+    // A committed plan is followed, not recomputed, on the next tick
+    run.step();
+    assert!(!run.agent.provenance.replanned);
+}
+
+#[test]
+fn test_baselines_attribute_the_turn_to_their_policy() {
+    for controller in [
+        ControllerKind::RandomWalk,
+        ControllerKind::GradientFollower,
+        ControllerKind::Braitenberg,
+    ] {
+        let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(2);
+        run.agent.controller = controller;
+        for _ in 0..5 {
+            run.step();
+        }
+        let provenance = run.agent.provenance;
+        assert_eq!(provenance.controller, controller);
+        assert_eq!(provenance.efe_action, None);
+        assert_eq!(provenance.term(Term::Policy), provenance.turn);
+        assert_eq!(
+            provenance.ranked().len(),
+            usize::from(provenance.turn != 0.0)
+        );
+    }
+
+    let mut provenance = Provenance::new(ControllerKind::RandomWalk);
+    provenance.settle(
+        Decision {
+            turn: -0.25,
+            speed: 1.0,
+        },
+        0.5,
+    );
+    assert_eq!(provenance.dominant(), Some((Term::Policy, -0.25)));
+    assert_eq!(
+        provenance.to_string(),
+        "random turn -0.250 speed 0.50: policy -0.250"
+    );
+}
+
+#[test]
+fn test_goal_attraction_shows_in_the_record() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::with_heading(20.0, 25.0, 0.0);
+    agent.seed_streams(1);
+    agent.set_goal(20.0, 45.0, 100.0);
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    let goal = agent.provenance.term(Term::Goal);
+    assert!(goal > 0.0, "the goal lies to the left: {goal}");
+    assert_eq!(agent.provenance.term(Term::Landmark), 0.0);
+    assert!(agent.provenance.to_string().starts_with("active turn "));
+}
+
+#[test]
+fn test_export_records_turn_and_driver() {
+    let mut world = World::single(PetriDish::generate(7, &GeneratorConfig::default()));
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let csv = record_csv(&mut world, &mut agent, 10);
+    let column = |name: &str| CSV_COLUMNS.iter().position(|c| *c == name).unwrap();
+    let last: Vec<f64> = csv
+        .lines()
+        .last()
+        .unwrap()
+        .split(',')
+        .map(|v| v.parse().unwrap())
+        .collect();
+    assert_eq!(last[column("turn")], agent.provenance.turn);
+    let driver = agent.provenance.dominant().unwrap().0;
+    assert_eq!(last[column("driver")], f64::from(driver as u8));
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_dashboard_names_the_driver() {
+    use protozoa_rust::ui::DashboardState;
+    use protozoa_rust::ui::render::format_driver;
+
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    assert_eq!(format_driver(&agent.provenance), None);
+    agent.provenance.terms[Term::Plan as usize] = 0.1;
+    let state = DashboardState::from_agent(&agent, &dish);
+    assert_eq!(state.provenance, agent.provenance);
+    assert_eq!(
+        format_driver(&state.provenance).as_deref(),
+        Some("plan +0.10")
+    );
+}
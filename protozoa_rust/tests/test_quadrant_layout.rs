@@ -0,0 +1,178 @@
+#![cfg(feature = "tui")]
+//! Tests for the selectable quadrant dashboard layout and its persistence.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use protozoa_rust::ui::DashboardState;
+use protozoa_rust::ui::layout::{DashboardLayout, LANDMARKS_HEIGHT, LayoutKind, METRICS_HEIGHT};
+use protozoa_rust::ui::render::{compute_quadrant_layout, draw_dashboard, screen_to_world};
+use protozoa_rust::ui::terminal::{ColorMode, RenderOverrides};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use std::path::PathBuf;
+
+/// Column of the first occurrence of `text` on buffer row `y`.
+fn find_on_row(terminal: &Terminal<TestBackend>, y: u16, text: &str) -> Option<u16> {
+    let buffer = terminal.backend().buffer();
+    let row: String = (0..buffer.area.width)
+        .map(|x| {
+            buffer
+                .cell((x, y))
+                .map_or(" ", ratatui::buffer::Cell::symbol)
+        })
+        .collect::<Vec<_>>()
+        .concat();
+    row.find(text)
+        .map(|byte| u16::try_from(row[..byte].chars().count()).unwrap())
+}
+
+#[test]
+fn test_quadrants_hold_field_beliefs_planning_and_memory() {
+    let area = Rect::new(0, 0, 120, 60);
+    let quadrants = compute_quadrant_layout(area);
+    let layout = DashboardLayout::new(LayoutKind::Quadrant, area);
+    assert_eq!(layout, DashboardLayout::quadrants(area));
+    assert_eq!(layout.main, quadrants[0]);
+    assert_eq!(layout.metrics, quadrants[1]);
+    assert!(layout.detail.is_none());
+
+    // Planning over events bottom left, landmarks over spatial memory bottom right
+    assert_eq!(layout.mcts.y, quadrants[2].y);
+    assert_eq!(layout.events.bottom(), quadrants[2].bottom());
+    assert_eq!(layout.mcts.height + layout.events.height, 30);
+    assert_eq!(layout.landmarks.height, LANDMARKS_HEIGHT);
+    assert_eq!(layout.spatial.x, quadrants[3].x);
+    assert_eq!(layout.spatial.bottom(), quadrants[3].bottom());
+
+    assert_eq!(
+        DashboardLayout::new(LayoutKind::Sidebar, area),
+        DashboardLayout::for_area(area)
+    );
+    assert_eq!(
+        layout.metrics.height, 30,
+        "beliefs get more than {METRICS_HEIGHT} rows"
+    );
+}
+
+#[test]
+fn test_field_size_and_clicks_follow_the_layout() {
+    let area = Rect::new(0, 0, 120, 60);
+    let quadrant = DashboardLayout::new(LayoutKind::Quadrant, area);
+    assert_eq!(quadrant.field_size(), (28, 58));
+    let sidebar = DashboardLayout::new(LayoutKind::Sidebar, area);
+    assert_eq!(sidebar.field_size(), (58, 82));
+
+    // Cells outside the top-left field miss, even if the sidebar field has them
+    assert!(
+        quadrant
+            .screen_to_world(0, 0, DISH_WIDTH, DISH_HEIGHT)
+            .is_none()
+    );
+    assert!(
+        quadrant
+            .screen_to_world(70, 10, DISH_WIDTH, DISH_HEIGHT)
+            .is_none()
+    );
+    assert!(
+        quadrant
+            .screen_to_world(10, 40, DISH_WIDTH, DISH_HEIGHT)
+            .is_none()
+    );
+    assert!(
+        sidebar
+            .screen_to_world(10, 40, DISH_WIDTH, DISH_HEIGHT)
+            .is_some()
+    );
+
+    let (x, y) = quadrant
+        .screen_to_world(58, 28, DISH_WIDTH, DISH_HEIGHT)
+        .unwrap();
+    assert!((x - DISH_WIDTH * 57.5 / 58.0).abs() < 1e-9);
+    assert!((y - DISH_HEIGHT * 27.5 / 28.0).abs() < 1e-9);
+    assert_eq!(
+        sidebar.screen_to_world(5, 5, DISH_WIDTH, DISH_HEIGHT),
+        screen_to_world(area, 5, 5, DISH_WIDTH, DISH_HEIGHT)
+    );
+}
+
+#[test]
+fn test_layout_kind_toggles_and_parses() {
+    assert_eq!(LayoutKind::default(), LayoutKind::Sidebar);
+    assert_eq!(LayoutKind::Sidebar.next(), LayoutKind::Quadrant);
+    assert_eq!(LayoutKind::Quadrant.next(), LayoutKind::Sidebar);
+
+    let overrides = RenderOverrides::from_toml_str("layout = \"quadrant\"").unwrap();
+    assert_eq!(overrides.layout, Some(LayoutKind::Quadrant));
+    assert_eq!(RenderOverrides::from_toml_str("").unwrap().layout, None);
+    assert!(RenderOverrides::from_toml_str("layout = \"grid\"").is_err());
+}
+
+#[test]
+fn test_save_layout_keeps_other_keys() {
+    let dir = std::env::temp_dir().join(format!("render_layout_{}", std::process::id()));
+    let path = dir.join("nested").join("render.toml");
+    RenderOverrides::save_layout(&path, LayoutKind::Quadrant).unwrap();
+    assert_eq!(
+        RenderOverrides::load(&path).unwrap(),
+        RenderOverrides {
+            layout: Some(LayoutKind::Quadrant),
+            ..RenderOverrides::default()
+        }
+    );
+
+    std::fs::write(&path, "color = \"mono\"\nlayout = \"quadrant\"\n").unwrap();
+    RenderOverrides::save_layout(&path, LayoutKind::Sidebar).unwrap();
+    let saved = RenderOverrides::load(&path).unwrap();
+    assert_eq!(saved.color, Some(ColorMode::Mono));
+    assert_eq!(saved.layout, Some(LayoutKind::Sidebar));
+
+    // An invalid file is reported, not overwritten
+    std::fs::write(&path, "colour = 1\n").unwrap();
+    assert!(RenderOverrides::save_layout(&path, LayoutKind::Quadrant).is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "colour = 1\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // The per-user path prefers XDG_CONFIG_HOME, then HOME
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value).to_string())
+        }
+    };
+    assert_eq!(
+        RenderOverrides::default_path(env(&[("XDG_CONFIG_HOME", "/xdg"), ("HOME", "/home/u")])),
+        Some(PathBuf::from("/xdg/protozoa_rust/render.toml"))
+    );
+    assert_eq!(
+        RenderOverrides::default_path(env(&[("XDG_CONFIG_HOME", ""), ("HOME", "/home/u")])),
+        Some(PathBuf::from("/home/u/.config/protozoa_rust/render.toml"))
+    );
+    assert_eq!(RenderOverrides::default_path(env(&[])), None);
+}
+
+#[test]
+fn test_quadrant_dashboard_draws_panels_in_their_quadrants() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let mut state = DashboardState::from_agent(&Protozoa::new(50.0, 25.0), &dish);
+    state.layout = LayoutKind::Quadrant;
+    let mut terminal = Terminal::new(TestBackend::new(120, 60)).unwrap();
+    terminal
+        .draw(|f| draw_dashboard(f, vec![".".repeat(58); 28], &state))
+        .unwrap();
+
+    let layout = DashboardLayout::quadrants(Rect::new(0, 0, 120, 60));
+    assert!(find_on_row(&terminal, 0, "Petri Dish").unwrap() < 60);
+    assert!(find_on_row(&terminal, 0, "Agent").unwrap() >= 60);
+    assert_eq!(find_on_row(&terminal, 30, " MCTS Planning "), Some(1));
+    assert!(find_on_row(&terminal, layout.events.y, "Events").unwrap() < 60);
+    assert!(find_on_row(&terminal, 30, "Landmarks").unwrap() >= 60);
+    assert!(find_on_row(&terminal, layout.spatial.y, "Spatial Memory").unwrap() >= 60);
+    assert_eq!(
+        find_on_row(&terminal, 1, "."),
+        Some(1),
+        "field fills the quadrant"
+    );
+}
@@ -0,0 +1,142 @@
+//! Tests for the quorum-sensing signal channel between agents.
+
+use protozoa_rust::simulation::agent::{AgentMode, Protozoa};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::inference::{GenerativeModel, signal_risk};
+use protozoa_rust::simulation::params::{
+    SIGNAL_DECAY, SIGNAL_FLOOR, SIGNAL_RADIUS, SIGNAL_STEERING_GAIN,
+};
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::signal::SignalField;
+use protozoa_rust::simulation::world::World;
+
+/// An agent at (50, 25) whose state reads as exploiting a rich patch.
+fn exploiting_agent() -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    for _ in 0..20 {
+        agent.spatial_priors.update(50.0, 25.0, 0.8);
+    }
+    (agent.val_l, agent.val_r) = (0.8, 0.8);
+    agent.current_vfe = 0.5;
+    agent
+}
+
+#[test]
+fn test_broadcasts_spread_cap_and_fade() {
+    let mut field = SignalField::default();
+    assert_eq!(field.level_at(50.0, 25.0), 0.0);
+    field.emit(50.0, 25.0, 0.5);
+    field.emit(50.0, 25.0, 0.0);
+    field.emit(50.0, 25.0, -1.0);
+    assert_eq!(field.broadcasts.len(), 1);
+
+    assert!((field.level_at(50.0, 25.0) - 0.5).abs() < 1e-12);
+    let one_sigma = field.level_at(50.0 + SIGNAL_RADIUS, 25.0);
+    assert!((one_sigma - 0.5 * (-0.5_f64).exp()).abs() < 1e-12);
+    field.emit(52.0, 25.0, 0.9);
+    assert_eq!(field.level_at(51.0, 25.0), 1.0, "capped at 1");
+
+    field.decay(0.5);
+    assert!((field.broadcasts[0].level - 0.25).abs() < 1e-12);
+    while !field.broadcasts.is_empty() {
+        field.decay(0.5);
+    }
+    assert_eq!(field.level_at(50.0, 25.0), 0.0);
+    field.emit(10.0, 10.0, SIGNAL_FLOOR * 1.5);
+    field.decay(0.5);
+    assert!(field.broadcasts.is_empty(), "dropped below the floor");
+}
+
+#[test]
+fn test_dish_signal_decays_and_follows_resize() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 1);
+    dish.signal.emit(50.0, 25.0, 0.8);
+    assert_eq!(dish.get_signal(-1.0, 25.0), 0.0);
+    assert_eq!(dish.get_signal(50.0, 60.0), 0.0);
+
+    dish.update();
+    let expected = 0.8 * dish.clock.factor(SIGNAL_DECAY);
+    assert!((dish.get_signal(50.0, 25.0) - expected).abs() < 1e-12);
+
+    dish.resize(200.0, 100.0);
+    assert!((dish.get_signal(100.0, 50.0) - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_signal_is_an_observation_weighted_by_precision() {
+    let model = GenerativeModel::new();
+    assert_eq!(model.prior_precision.signal, 0.0);
+    assert_eq!(signal_risk((0.0, 0.0), &model), 0.0);
+
+    let mut weighted = GenerativeModel::new();
+    weighted.prior_precision.signal = 2.0;
+    assert!((signal_risk((0.0, 0.0), &weighted) - 2.0).abs() < 1e-12);
+    assert_eq!(signal_risk((1.0, 1.0), &weighted), 0.0);
+    assert!(signal_risk((0.2, 0.1), &weighted) > signal_risk((0.6, 0.5), &weighted));
+
+    // A broadcast on the agent's left (toward +y)
+    let mut dish = PetriDish::empty(100.0, 50.0, 2);
+    dish.signal.emit(50.0, 40.0, 1.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(2);
+    agent.sense(&dish);
+    assert!(agent.signal_l > agent.signal_r && agent.signal_r > 0.0);
+    assert_eq!(agent.recruitment_steering(), 0.0, "unweighted by default");
+    let mut deaf = agent.clone();
+    deaf.update_state(&dish);
+
+    agent.generative_model.prior_precision.signal = 2.0;
+    let expected = SIGNAL_STEERING_GAIN * 2.0 * (agent.signal_l - agent.signal_r);
+    assert!((agent.recruitment_steering() - expected).abs() < 1e-12);
+    assert!(
+        agent.recruitment_steering() > 0.0,
+        "turns toward the signal"
+    );
+    let mut listening = agent.clone();
+    listening.update_state(&dish);
+    let risk = signal_risk((agent.signal_l, agent.signal_r), &agent.generative_model);
+    assert!((listening.current_vfe - deaf.current_vfe - risk).abs() < 1e-9);
+}
+
+#[test]
+fn test_only_exploiting_emitters_broadcast() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 3);
+    let mut agent = exploiting_agent();
+    assert_eq!(agent.current_mode(&dish), AgentMode::Exploiting);
+    agent.broadcast(&mut dish);
+    assert!(dish.signal.broadcasts.is_empty(), "silent by default");
+
+    agent.morphology.signal_emission = 0.4;
+    agent.broadcast(&mut dish);
+    assert_eq!(dish.signal.broadcasts.len(), 1);
+    let expected = dish.clock.rate(0.4);
+    assert!((dish.signal.broadcasts[0].level - expected).abs() < 1e-12);
+
+    // Exploring (surprised) and dead agents stay silent
+    agent.current_vfe = 5.0;
+    assert_ne!(agent.current_mode(&dish), AgentMode::Exploiting);
+    agent.broadcast(&mut dish);
+    let mut dead = exploiting_agent();
+    dead.morphology.signal_emission = 0.4;
+    dead.died_at = Some(1);
+    dead.broadcast(&mut dish);
+    assert_eq!(dish.signal.broadcasts.len(), 1);
+}
+
+#[test]
+fn test_populations_are_silent_by_default() {
+    let mut world = World::single(PetriDish::generate(4, &GeneratorConfig::default()));
+    let mut population = Population::single(Protozoa::with_heading(50.0, 25.0, 0.0));
+    population.add(Protozoa::with_heading(20.0, 20.0, 0.0), None);
+    for _ in 0..100 {
+        population.step(&mut world);
+    }
+    assert!(world.dishes[0].signal.broadcasts.is_empty());
+    assert!(
+        population
+            .agents
+            .iter()
+            .all(|a| a.signal_l == 0.0 && a.signal_r == 0.0)
+    );
+}
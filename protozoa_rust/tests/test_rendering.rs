@@ -1,14 +1,18 @@
+#![cfg(feature = "tui")]
+
 use protozoa_rust::simulation::agent::{AgentMode, Protozoa};
 use protozoa_rust::simulation::environment::PetriDish;
 use protozoa_rust::simulation::memory::CellPrior;
 use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
-use protozoa_rust::simulation::planning::{Action, ActionDetail};
+use protozoa_rust::simulation::planning::{Action, ActionDetail, PlanningBudget};
 use protozoa_rust::ui::DashboardState;
 use protozoa_rust::ui::LandmarkSnapshot;
+use protozoa_rust::ui::PinCursor;
 use protozoa_rust::ui::field::compute_field_grid;
 use protozoa_rust::ui::render::{
     compute_quadrant_layout, compute_sidebar_layout, format_landmarks_list, format_mcts_summary,
-    format_metrics_overlay, petri_dish_grid_size, render_spatial_grid_lines,
+    format_metrics_overlay, petri_dish_grid_size, render_spatial_grid_lines, screen_to_world,
+    world_to_grid_coords,
 };
 use ratatui::layout::Rect;
 use ratatui::widgets::{Block, Borders};
@@ -153,6 +157,7 @@ fn test_mcts_summary_format() {
             pragmatic_value: -1.2,
             epistemic_value: -1.0,
             sample_trajectory: vec![(50.0, 25.0), (52.0, 27.0)],
+            fresh_rollouts: 50,
         },
         ActionDetail {
             action: Action::Straight,
@@ -160,10 +165,11 @@ fn test_mcts_summary_format() {
             pragmatic_value: -1.8,
             epistemic_value: -1.67,
             sample_trajectory: vec![(50.0, 25.0), (55.0, 25.0)],
+            fresh_rollouts: 50,
         },
     ];
 
-    let lines = format_mcts_summary(&details, 7);
+    let lines = format_mcts_summary(&details, PlanningBudget::FIXED, 7);
 
     // Should have lines for best action, G, Prag, Epis, Rolls, Depth, Replan
     assert!(lines.len() >= 5);
@@ -179,6 +185,7 @@ fn test_mcts_summary_turn_right_arrow() {
             pragmatic_value: 0.5,
             epistemic_value: 0.5,
             sample_trajectory: vec![(50.0, 25.0)],
+            fresh_rollouts: 50,
         },
         ActionDetail {
             action: Action::TurnLeft,
@@ -186,10 +193,11 @@ fn test_mcts_summary_turn_right_arrow() {
             pragmatic_value: 0.1,
             epistemic_value: 0.1,
             sample_trajectory: vec![(50.0, 25.0)],
+            fresh_rollouts: 50,
         },
     ];
 
-    let lines = format_mcts_summary(&details, 4);
+    let lines = format_mcts_summary(&details, PlanningBudget::FIXED, 4);
 
     assert!(lines[0].contains("↘"));
 }
@@ -202,12 +210,14 @@ fn test_landmarks_list_format() {
             y: 8.0,
             reliability: 0.92,
             visit_count: 4,
+            pinned: false,
         },
         LandmarkSnapshot {
             x: 3.0,
             y: 18.0,
             reliability: 0.67,
             visit_count: 2,
+            pinned: false,
         },
     ];
 
@@ -226,6 +236,7 @@ fn test_landmarks_list_reliability_full() {
         y: 8.0,
         reliability: 1.0,
         visit_count: 1,
+        pinned: false,
     }];
 
     let lines = format_landmarks_list(&landmarks, None);
@@ -246,3 +257,46 @@ fn test_dashboard_state_uses_visit_count() {
     assert_eq!(state.landmarks.len(), 1);
     assert_eq!(state.landmarks[0].visit_count, 2);
 }
+
+#[test]
+fn test_landmarks_list_marks_pinned() {
+    let landmarks = vec![LandmarkSnapshot {
+        x: 12.0,
+        y: 8.0,
+        reliability: 1.0,
+        visit_count: 0,
+        pinned: true,
+    }];
+
+    let lines = format_landmarks_list(&landmarks, None);
+
+    assert!(lines[2].starts_with(" 1*"));
+}
+
+#[test]
+fn test_pin_cursor_toggle_and_clamp() {
+    let mut cursor = PinCursor::default();
+    assert!(cursor.position().is_none());
+
+    cursor.toggle(10.0, 20.0);
+    assert_eq!(cursor.position(), Some((10.0, 20.0)));
+
+    cursor.nudge(-100.0, 1.0, DISH_WIDTH, DISH_HEIGHT);
+    assert_eq!(cursor.position(), Some((0.0, 21.0)));
+
+    cursor.toggle(50.0, 50.0);
+    assert!(cursor.position().is_none());
+}
+
+#[test]
+fn test_screen_to_world_round_trip() {
+    let area = Rect::new(0, 0, 120, 40);
+    let (rows, cols) = petri_dish_grid_size(area);
+
+    // Top-left border cell is outside the field
+    assert!(screen_to_world(area, 0, 0, DISH_WIDTH, DISH_HEIGHT).is_none());
+
+    let (x, y) = screen_to_world(area, 5, 5, DISH_WIDTH, DISH_HEIGHT).unwrap();
+    let (r, c) = world_to_grid_coords(x, y, DISH_WIDTH, DISH_HEIGHT, rows, cols);
+    assert_eq!((r, c), (4, 4));
+}
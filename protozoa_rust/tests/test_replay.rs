@@ -0,0 +1,155 @@
+//! Tests for recorded runs and the replay scrubber.
+
+use protozoa_rust::simulation::arrival::ArrivalTarget;
+use protozoa_rust::simulation::ensemble::Run;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::replay::{Recording, ReplayEventKind};
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::scrubber::{Scrubber, draw_scrubber};
+#[cfg(feature = "tui")]
+use ratatui::{Terminal, backend::TestBackend};
+
+fn goal_run(seed: u64) -> Run {
+    let scenario = Scenario::Generated {
+        generator: GeneratorConfig::default(),
+        spawn: SpawnConfig::default(),
+    };
+    let mut run = scenario.build(seed);
+    run.agent.set_goal(20.0, 20.0, 1.0);
+    run
+}
+
+fn same_state(a: &Run, b: &Run) -> bool {
+    (a.agent.x, a.agent.y, a.agent.energy, a.agent.tick_count)
+        == (b.agent.x, b.agent.y, b.agent.energy, b.agent.tick_count)
+}
+
+#[test]
+fn test_frames_match_the_live_run() {
+    let recording = Recording::record(goal_run(1), 300, 50);
+    assert_eq!(recording.len(), 300);
+    assert!(!recording.is_empty());
+    let mut live = goal_run(1);
+    for tick in 0..=300 {
+        if tick % 37 == 0 || tick % 50 == 0 || tick == 300 {
+            assert!(same_state(&recording.frame(tick), &live), "tick {tick}");
+        }
+        live.step();
+    }
+    assert!(
+        same_state(&recording.frame(1000), &recording.frame(300)),
+        "clamped"
+    );
+}
+
+#[test]
+fn test_seek_matches_frames_both_ways() {
+    let recording = Recording::record(goal_run(2), 400, 50);
+    let mut run = recording.frame(0);
+    let mut at = 0;
+    for to in [3, 40, 90, 260, 120, 121, 0, 399, 500] {
+        at = recording.seek(&mut run, at, to);
+        assert_eq!(at, to.min(400));
+        assert!(same_state(&run, &recording.frame(at)), "seek to {to}");
+    }
+}
+
+#[test]
+fn test_events_are_recorded_in_order() {
+    let recording = Recording::record(goal_run(1), 1000, 50);
+    let events = recording.events();
+    assert!(events.windows(2).all(|w| w[0].tick <= w[1].tick));
+    let arrival = events
+        .iter()
+        .find(|e| e.kind == ReplayEventKind::Arrival(ArrivalTarget::Goal))
+        .expect("the agent reaches its goal");
+    assert_eq!(arrival.label(), "arrived at goal");
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e.kind, ReplayEventKind::Source(_)))
+    );
+
+    // The arrival shows in the frame at its tick, not the one before
+    let before = recording.frame(arrival.tick - 1);
+    let after = recording.frame(arrival.tick);
+    assert_eq!(after.agent.events.count(), before.agent.events.count() + 1);
+
+    let next = recording.next_event(arrival.tick - 1).unwrap();
+    assert_eq!(next.tick, arrival.tick);
+    assert!(recording.next_event(arrival.tick).unwrap().tick > arrival.tick);
+    assert!(recording.previous_event(arrival.tick + 1).unwrap().tick <= arrival.tick);
+    assert!(recording.previous_event(events[0].tick).is_none());
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_scrubber_speed_loop_and_end() {
+    let mut scrubber = Scrubber::new(100);
+    assert!(scrubber.playing);
+    assert_eq!(scrubber.speed(), 1.0);
+    assert_eq!(scrubber.advance(), 1);
+
+    scrubber.slower();
+    scrubber.slower();
+    assert_eq!(scrubber.speed(), 0.25);
+    scrubber.slower();
+    assert_eq!(scrubber.speed(), 0.25, "slowest");
+    let ticks: Vec<u64> = (0..4).map(|_| scrubber.advance()).collect();
+    assert_eq!(ticks, [1, 1, 1, 2]);
+
+    // A loop region wraps playback back to its start
+    scrubber.seek(20);
+    scrubber.set_loop_start();
+    scrubber.seek(30);
+    scrubber.set_loop_end();
+    assert_eq!(scrubber.loop_region, Some((20, 30)));
+    for _ in 0..4 {
+        scrubber.faster();
+    }
+    assert_eq!(scrubber.speed(), 4.0);
+    let ticks: Vec<u64> = (0..4).map(|_| scrubber.advance()).collect();
+    assert_eq!(ticks, [20, 24, 28, 20]);
+
+    // Without one, playback pauses at the end and restarts on play
+    scrubber.clear_loop();
+    scrubber.seek(98);
+    scrubber.advance();
+    assert_eq!((scrubber.tick, scrubber.playing), (100, false));
+    scrubber.toggle_play();
+    assert_eq!((scrubber.tick, scrubber.playing), (0, true));
+    scrubber.step_by(-5);
+    assert_eq!((scrubber.tick, scrubber.playing), (0, false));
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_scrubber_timeline_and_status() {
+    let mut scrubber = Scrubber::new(100);
+    scrubber.seek(50);
+    assert_eq!(scrubber.timeline(11, &[90]), "=====|---*-");
+    scrubber.seek(20);
+    scrubber.set_loop_start();
+    scrubber.seek(50);
+    scrubber.set_loop_end();
+    assert_eq!(scrubber.timeline(11, &[]), "==[==|-----");
+    assert_eq!(scrubber.tick_at_column(0, 11), 0);
+    assert_eq!(scrubber.tick_at_column(3, 11), 30);
+    assert_eq!(scrubber.tick_at_column(40, 11), 100);
+
+    let mut terminal = Terminal::new(TestBackend::new(60, 4)).unwrap();
+    terminal
+        .draw(|f| draw_scrubber(f, f.area(), &scrubber, &[90], Some("next: x")))
+        .unwrap();
+    let text: String = terminal
+        .backend()
+        .buffer()
+        .content
+        .iter()
+        .map(ratatui::buffer::Cell::symbol)
+        .collect();
+    assert!(text.contains(" Replay "));
+    assert!(text.contains("PLAY  1x  tick 50/100  loop 20-50  next: x"));
+}
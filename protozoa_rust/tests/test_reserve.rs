@@ -0,0 +1,132 @@
+//! Tests for the slow energy reserve compartment.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{
+    RESERVE_CAPACITY, RESERVE_RELEASE_LEVEL, RESERVE_RELEASE_RATE, RESERVE_STORE_LEVEL,
+    RESERVE_STORE_RATE,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::reserve::EnergyReserve;
+
+/// Ticks an agent survives in an empty dish, starting from `energy`.
+fn ticks_survived(reserve: EnergyReserve, energy: f64) -> u32 {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    dish.sources.clear();
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(4);
+    agent.energy = energy;
+    agent.reserve = reserve;
+    let mut ticks = 0;
+    while agent.is_alive() && ticks < 5000 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        ticks += 1;
+    }
+    ticks
+}
+
+#[test]
+fn test_overflow_is_banked() {
+    let clock = SimClock::default();
+    let mut reserve = EnergyReserve::default();
+    assert_eq!((reserve.level, reserve.capacity), (0.0, RESERVE_CAPACITY));
+
+    let energy = reserve.exchange(1.1, &clock);
+    assert!(energy < 1.0, "surplus above the store level moves in too");
+    assert!((reserve.level + energy - 1.1).abs() < 1e-12, "nothing lost");
+    assert!((reserve.level - (0.1 + clock.rate(RESERVE_STORE_RATE))).abs() < 1e-12);
+}
+
+#[test]
+fn test_surplus_is_stored_slowly() {
+    let clock = SimClock::default();
+    let mut reserve = EnergyReserve::default();
+    let energy = reserve.exchange(0.9, &clock);
+    assert!((energy - (0.9 - clock.rate(RESERVE_STORE_RATE))).abs() < 1e-12);
+
+    // Never drawn below the store level
+    let mut reserve = EnergyReserve::default();
+    let energy = reserve.exchange(RESERVE_STORE_LEVEL + 1e-4, &clock);
+    assert!((energy - RESERVE_STORE_LEVEL).abs() < 1e-12);
+
+    // Between the two levels the compartments are left alone
+    let mut reserve = EnergyReserve {
+        level: 0.2,
+        capacity: RESERVE_CAPACITY,
+    };
+    assert_eq!(reserve.exchange(0.5, &clock), 0.5);
+    assert_eq!(reserve.level, 0.2);
+}
+
+#[test]
+fn test_reserve_refills_energy_in_scarcity() {
+    let clock = SimClock::default();
+    let mut reserve = EnergyReserve {
+        level: 0.2,
+        capacity: RESERVE_CAPACITY,
+    };
+    let energy = reserve.exchange(0.1, &clock);
+    let released = clock.rate(RESERVE_RELEASE_RATE);
+    assert!((energy - (0.1 + released)).abs() < 1e-12);
+    assert!((reserve.level - (0.2 - released)).abs() < 1e-12);
+
+    // A deficit is covered only as far as the reserve reaches
+    let mut reserve = EnergyReserve {
+        level: 0.001,
+        capacity: RESERVE_CAPACITY,
+    };
+    assert_eq!(reserve.exchange(-0.5, &clock), 0.0);
+    assert_eq!(reserve.level, 0.0);
+    assert!(reserve.exchange(RESERVE_RELEASE_LEVEL - 0.1, &clock) < RESERVE_RELEASE_LEVEL);
+}
+
+#[test]
+fn test_capacity_is_respected() {
+    let clock = SimClock::default();
+    let mut reserve = EnergyReserve::default();
+    for _ in 0..1000 {
+        reserve.exchange(1.2, &clock);
+        assert!(reserve.level <= RESERVE_CAPACITY);
+    }
+    assert_eq!(reserve.fraction(), 1.0);
+    assert_eq!(
+        reserve.exchange(1.2, &clock),
+        1.0,
+        "overflow past full is lost"
+    );
+
+    // Without a reserve metabolism is single-compartment
+    let mut none = EnergyReserve::NONE;
+    for energy in [-0.2, 0.1, 0.5, 0.9, 1.3] {
+        assert_eq!(none.exchange(energy, &clock), energy.clamp(0.0, 1.0));
+    }
+    assert_eq!((none.level, none.fraction()), (0.0, 0.0));
+}
+
+#[test]
+fn test_reserve_delays_starvation() {
+    let bare = ticks_survived(EnergyReserve::NONE, 0.3);
+    let full = EnergyReserve {
+        level: RESERVE_CAPACITY,
+        capacity: RESERVE_CAPACITY,
+    };
+    let buffered = ticks_survived(full, 0.3);
+    assert!(bare < 5000, "an empty dish starves the bare agent");
+    assert!(buffered > bare + 100, "{buffered} vs {bare}");
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_dashboard_shows_the_reserve() {
+    use protozoa_rust::ui::DashboardState;
+    use protozoa_rust::ui::render::format_reserve_line;
+
+    assert_eq!(format_reserve_line(0.5), "R: 50%");
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::new(50.0, 25.0);
+    agent.reserve.level = RESERVE_CAPACITY / 4.0;
+    let state = DashboardState::from_agent(&agent, &dish);
+    assert!((state.reserve - 0.25).abs() < 1e-12);
+}
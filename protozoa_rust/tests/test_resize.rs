@@ -0,0 +1,193 @@
+//! Tests for resizing dishes mid-run.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{Obstacle, PetriDish};
+use protozoa_rust::simulation::events::DishEvent;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::occupancy::OccupancyMap;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::world::World;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+#[test]
+fn test_dish_contents_stretch_with_the_dish() {
+    let mut dish = PetriDish::generate(3, &GeneratorConfig::default());
+    dish.obstacles.push(Obstacle {
+        x_min: 10.0,
+        y_min: 5.0,
+        x_max: 20.0,
+        y_max: 15.0,
+    });
+    dish.events.schedule(50, DishEvent::pulse(40.0, 20.0));
+    let before = dish.clone();
+
+    assert_eq!(dish.resize(200.0, 75.0), (2.0, 1.5));
+    assert_eq!((dish.width, dish.height), (200.0, 75.0));
+    assert_eq!((dish.config.width, dish.config.height), (200.0, 75.0));
+    for (old, new) in before.sources.iter().zip(&dish.sources) {
+        assert!((new.x - 2.0 * old.x).abs() < 1e-9);
+        assert!((new.y - 1.5 * old.y).abs() < 1e-9);
+        assert!(
+            (new.radius - old.radius).abs() < 1e-12,
+            "patches keep their size"
+        );
+    }
+    let wall = dish.obstacles.last().unwrap();
+    assert_eq!((wall.x_min, wall.x_max), (20.0, 40.0));
+    assert_eq!((wall.y_min, wall.y_max), (7.5, 22.5));
+    let (old_zone, new_zone) = (&before.viscosity.zones, &dish.viscosity.zones);
+    for (old, new) in old_zone.iter().zip(new_zone) {
+        assert!((new.x - 2.0 * old.x).abs() < 1e-9);
+    }
+    let pulse = dish.events.pending()[0].event;
+    assert_eq!(pulse, DishEvent::pulse(80.0, 30.0));
+    assert!((dish.flow.center_x - 100.0).abs() < 1e-9);
+    let light = |d: &PetriDish, x: f64, y: f64| d.light.intensity_at(x, y);
+    assert!((light(&dish, 160.0, 30.0) - light(&before, 80.0, 20.0)).abs() < 1e-12);
+
+    // Invalid sizes leave the dish as it was
+    let snapshot = dish.clone();
+    assert_eq!(dish.resize(0.0, 10.0), (1.0, 1.0));
+    assert_eq!(dish.sources, snapshot.sources);
+    assert_eq!(dish.width, snapshot.width);
+}
+
+#[test]
+fn test_preset_layout_keeps_its_shape() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Maze, 100.0, 50.0);
+    let gap = (30.0, 40.0);
+    assert!(!dish.is_blocked(gap.0, gap.1));
+    dish.resize(160.0, 90.0);
+    assert!(!dish.is_blocked(gap.0 * 1.6, gap.1 * 1.8), "gap stays open");
+    let food = &dish.sources[0];
+    assert!((food.x - 0.92 * 160.0).abs() < 1e-9);
+    assert!((dish.get_concentration(food.x, food.y) - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn test_spatial_priors_follow_their_region() {
+    let mut grid: SpatialGrid<20, 10> = SpatialGrid::new(100.0, 50.0);
+    for _ in 0..5 {
+        grid.update(12.0, 7.0, 0.9);
+    }
+    let mut map = OccupancyMap::new(100.0, 50.0);
+    map.record(12.0, 7.0);
+
+    grid.resize(300.0, 100.0);
+    map.resize(300.0, 100.0);
+    assert_eq!(grid.world_dimensions(), (300.0, 100.0));
+    assert_eq!(grid.cell_dimensions(), (15.0, 10.0));
+    assert!((grid.expected(36.0, 14.0) - 0.9).abs() < 1e-9);
+    assert_eq!(grid.get_cell(36.0, 14.0).visits, 5);
+    assert_eq!(map.count_at(36.0, 14.0), 1);
+    assert_eq!(grid.total_visits(), 5);
+}
+
+#[test]
+fn test_agent_moves_with_its_dish() {
+    let mut world = World::single(PetriDish::generate(5, &GeneratorConfig::default()));
+    let mut agent = Protozoa::with_heading(40.0, 20.0, 0.0);
+    agent.episodic_memory.pin(10.0, 10.0, 0);
+    agent.set_goal(80.0, 30.0, 1.0);
+
+    world.resize(300.0, 150.0, std::slice::from_mut(&mut agent));
+    assert!((agent.x - 120.0).abs() < 1e-9 && (agent.y - 60.0).abs() < 1e-9);
+    assert!((agent.beliefs.mean.x - 120.0).abs() < 1e-9);
+    let pin = agent.episodic_memory.iter().next().unwrap();
+    assert!((pin.x - 30.0).abs() < 1e-9 && (pin.y - 30.0).abs() < 1e-9);
+    let goal = agent.goal.unwrap();
+    assert!((goal.x - 240.0).abs() < 1e-9 && (goal.y - 90.0).abs() < 1e-9);
+    assert_eq!(agent.spatial_priors.world_dimensions(), (300.0, 150.0));
+
+    // The agent can use the whole enlarged dish
+    (agent.x, agent.y) = (280.0, 140.0);
+    agent.beliefs.sync_position(agent.x, agent.y, agent.angle);
+    for _ in 0..20 {
+        world.update();
+        world.step_agent(&mut agent);
+        assert!(agent.x <= 300.0 && agent.y <= 150.0);
+    }
+    assert!(agent.x > 200.0, "not clamped to the old dish");
+}
+
+#[test]
+fn test_world_resize_moves_portals_and_stored_maps() {
+    let config = GeneratorConfig {
+        obstacle_count: 0,
+        ..GeneratorConfig::default()
+    };
+    let mut world = World::chain(2, &config, 21);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.migrate(1, 10.0, 25.0, 100.0, 50.0);
+    agent.migrate(0, 50.0, 25.0, 100.0, 50.0);
+    let exit = world.portals_in(0).next().copied().unwrap();
+
+    world.resize(200.0, 100.0, std::slice::from_mut(&mut agent));
+    let moved = world.portals_in(0).next().copied().unwrap();
+    assert!((moved.x - 2.0 * exit.x).abs() < 1e-9);
+    assert!((moved.to_y - 2.0 * exit.to_y).abs() < 1e-9);
+    assert!(world.dishes.iter().all(|d| d.width == 200.0));
+    let stored = agent.spatial_priors_in(1).unwrap();
+    assert_eq!(stored.world_dimensions(), (200.0, 100.0));
+}
+
+#[test]
+fn test_first_visit_after_a_resize_maps_the_resized_dish() {
+    let config = GeneratorConfig {
+        obstacle_count: 0,
+        ..GeneratorConfig::default()
+    };
+    let mut world = World::chain(2, &config, 21);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(21);
+    world.resize(150.0, 60.0, std::slice::from_mut(&mut agent));
+    assert!(agent.spatial_priors_in(1).is_none());
+
+    // Swim into the opening to dish 1 from just outside it
+    let portal = world.portals_in(0).next().copied().unwrap();
+    (agent.x, agent.y, agent.angle) = (portal.x - portal.radius - 0.2, portal.y, 0.0);
+    agent.beliefs.sync_position(agent.x, agent.y, agent.angle);
+    let migrated = (0..10).find_map(|_| world.step_agent(&mut agent));
+    assert_eq!(migrated, Some(1));
+    assert_eq!(agent.spatial_priors.world_dimensions(), (150.0, 60.0));
+    assert_eq!(agent.threat_priors.world_dimensions(), (150.0, 60.0));
+    assert_eq!(agent.habits.world_dimensions(), (150.0, 60.0));
+}
+
+#[test]
+fn test_particle_cloud_stretches_with_its_dish() {
+    let mut world = World::single(PetriDish::generate(5, &GeneratorConfig::default()));
+    let mut agent = Protozoa::with_heading(40.0, 20.0, 0.0);
+    agent.track_particles(64, 100.0, 50.0);
+    let before: Vec<(f64, f64)> = agent
+        .particles
+        .as_ref()
+        .unwrap()
+        .particles()
+        .iter()
+        .map(|p| (p.x, p.y))
+        .collect();
+
+    world.resize(200.0, 75.0, std::slice::from_mut(&mut agent));
+    let cloud = agent.particles.as_mut().unwrap();
+    assert_eq!(cloud.bounds(), (200.0, 75.0));
+    for (p, &(x, y)) in cloud.particles().iter().zip(&before) {
+        assert!((p.x - 2.0 * x).abs() < 1e-9 && (p.y - 1.5 * y).abs() < 1e-9);
+    }
+
+    // Resampling jitters within the new bounds, not the old ones
+    let mut rng = StdRng::seed_from_u64(4);
+    cloud.resample(&mut rng);
+    assert!(
+        cloud
+            .particles()
+            .iter()
+            .all(|p| (0.0..=200.0).contains(&p.x) && (0.0..=75.0).contains(&p.y))
+    );
+    assert!(
+        cloud.particles().iter().any(|p| p.x > 100.0),
+        "clamped to the old dish"
+    );
+}
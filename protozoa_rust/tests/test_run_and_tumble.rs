@@ -0,0 +1,119 @@
+//! Tests for the run-and-tumble chemotaxis controller.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::controller::{Controller, ControllerKind, RunAndTumble};
+use protozoa_rust::simulation::params::{BASELINE_SPEED_FRACTION, MAX_SPEED, TUMBLE_BASE_RATE};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::provenance::Term;
+use protozoa_rust::simulation::scenario::Scenario;
+
+/// An agent whose mean reading changed by `temp_gradient` last tick.
+fn sensing(temp_gradient: f64, seed: u64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(seed);
+    agent.temp_gradient = temp_gradient;
+    agent
+}
+
+/// Share of `ticks` decisions that turn for a fixed temporal gradient.
+fn tumble_share(temp_gradient: f64, ticks: u32) -> f64 {
+    let mut agent = sensing(temp_gradient, 8);
+    let tumbles = (0..ticks)
+        .filter(|_| RunAndTumble.decide(&mut agent).turn != 0.0)
+        .count();
+    f64::from(u32::try_from(tumbles).unwrap()) / f64::from(ticks)
+}
+
+/// Mean chemoreceptor reading over `ticks` ticks in the gradient preset.
+fn mean_reading(controller: ControllerKind, seed: u64, ticks: u32) -> f64 {
+    let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(seed);
+    run.agent.controller = controller;
+    let mut total = 0.0;
+    for _ in 0..ticks {
+        run.step();
+        total += f64::midpoint(run.agent.val_l, run.agent.val_r);
+    }
+    total / f64::from(ticks)
+}
+
+#[test]
+fn test_tumble_probability_follows_the_temporal_gradient() {
+    assert_eq!(RunAndTumble::tumble_probability(0.0), TUMBLE_BASE_RATE);
+    let rising = RunAndTumble::tumble_probability(0.01);
+    let falling = RunAndTumble::tumble_probability(-0.01);
+    assert!(rising < TUMBLE_BASE_RATE && falling > TUMBLE_BASE_RATE);
+    assert!((rising * falling - TUMBLE_BASE_RATE.powi(2)).abs() < 1e-12);
+    assert_eq!(
+        RunAndTumble::tumble_probability(-1.0),
+        1.0,
+        "leaving the dish"
+    );
+    assert_eq!(RunAndTumble::tumble_probability(f64::NAN), 1.0);
+}
+
+#[test]
+fn test_runs_straight_and_tumbles_anywhere() {
+    let mut agent = sensing(-0.1, 2);
+    let mut turns = Vec::new();
+    for _ in 0..200 {
+        let decision = RunAndTumble.decide(&mut agent);
+        assert_eq!(decision.speed, BASELINE_SPEED_FRACTION * MAX_SPEED);
+        assert!(decision.turn.abs() <= std::f64::consts::PI);
+        turns.push(decision.turn);
+    }
+    assert!(
+        turns.iter().all(|&t| t != 0.0),
+        "a steep fall always tumbles"
+    );
+    assert!(turns.iter().any(|&t| t > 2.0) && turns.iter().any(|&t| t < -2.0));
+
+    // Sharing the stream, identical agents decide identically
+    let mut a = sensing(0.0, 6);
+    let mut b = sensing(0.0, 6);
+    for _ in 0..50 {
+        assert_eq!(RunAndTumble.decide(&mut a), RunAndTumble.decide(&mut b));
+    }
+}
+
+#[test]
+fn test_rising_readings_lengthen_runs() {
+    let flat = tumble_share(0.0, 2000);
+    assert!((flat - TUMBLE_BASE_RATE).abs() < 0.03, "{flat}");
+    assert!(tumble_share(0.01, 2000) < flat);
+    assert!(tumble_share(-0.01, 2000) > flat);
+}
+
+#[test]
+fn test_selectable_like_any_controller() {
+    assert_eq!(
+        ControllerKind::parse("tumble"),
+        Some(ControllerKind::RunAndTumble)
+    );
+    assert_eq!(ControllerKind::RunAndTumble.name(), RunAndTumble.name());
+    assert!(ControllerKind::ALL.contains(&ControllerKind::RunAndTumble));
+
+    let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(3);
+    run.agent.controller = ControllerKind::RunAndTumble;
+    for _ in 0..20 {
+        let before = run.agent.angle;
+        run.step();
+        let provenance = run.agent.provenance;
+        assert_eq!(provenance.controller, ControllerKind::RunAndTumble);
+        assert_eq!(provenance.term(Term::Policy), provenance.turn);
+        if provenance.turn == 0.0 {
+            assert_eq!(run.agent.angle, before, "runs keep their heading");
+        }
+    }
+    assert!(run.agent.planner.last_plan_details().is_empty());
+}
+
+#[test]
+fn test_run_and_tumble_outclimbs_random_walk() {
+    let tumbling: f64 = (0..3)
+        .map(|seed| mean_reading(ControllerKind::RunAndTumble, seed, 400))
+        .sum();
+    let wandering: f64 = (0..3)
+        .map(|seed| mean_reading(ControllerKind::RandomWalk, seed, 400))
+        .sum();
+    assert!(tumbling > wandering, "{tumbling} vs {wandering}");
+}
@@ -0,0 +1,130 @@
+//! Tests for saccades: one-tick sensor sweeps chosen for their information.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::annotation::{MorphologyParam, ParamCommand};
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::inference::{BeliefState, GenerativeModel, saccade_efe};
+use protozoa_rust::simulation::params::{INTAKE_RATE, SACCADE_COST, SENSOR_ANGLE};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Ramp along x; an agent heading along y sees it only across its sensors.
+struct Slope;
+
+impl Environment for Slope {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            0.2 + 0.6 * x / 100.0
+        } else {
+            -1.0
+        }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.006, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+fn beliefs(nutrient_var: f64) -> BeliefState {
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.0);
+    beliefs.covariance.nutrient_var = nutrient_var;
+    beliefs
+}
+
+#[test]
+fn test_saccade_efe_weighs_information_against_cost() {
+    let model = GenerativeModel::new();
+    let cost = model.prior_precision.nutrient * SACCADE_COST / INTAKE_RATE;
+    let efe = |var: f64, sweep: f64| {
+        saccade_efe(&beliefs(var), &model, SENSOR_ANGLE, sweep, SACCADE_COST)
+    };
+
+    assert!(
+        (efe(0.1, 0.0) - cost).abs() < 1e-12,
+        "no sweep, no information"
+    );
+    assert!(efe(0.2, 1.0) < 0.0, "uncertain beliefs: worth sweeping");
+    assert!(efe(0.001, 1.0) > 0.0, "settled beliefs: not worth it");
+    assert!(efe(0.2, 1.0) < efe(0.2, 0.5), "wider sweeps inform more");
+    // Beyond side-by-side sensors the baseline shrinks no further
+    assert_eq!(efe(0.2, FRAC_PI_2 - SENSOR_ANGLE), efe(0.2, PI));
+}
+
+#[test]
+fn test_no_saccades_by_default() {
+    let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(1);
+    assert_eq!(run.agent.morphology.saccade_sweep, 0.0);
+    for _ in 0..300 {
+        run.step();
+        assert!(!run.agent.saccade && !run.agent.saccade_planned);
+    }
+}
+
+#[test]
+fn test_saccade_widens_one_sample() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, FRAC_PI_2);
+    agent.morphology.saccade_sweep = 0.8;
+    agent.sense(&Slope);
+    let narrow = agent.val_r - agent.val_l;
+    assert!(!agent.saccade && narrow > 0.0);
+
+    agent.saccade_planned = true;
+    agent.sense(&Slope);
+    assert!(agent.saccade && !agent.saccade_planned);
+    let swept = agent.val_r - agent.val_l;
+    let expected = narrow * (SENSOR_ANGLE + 0.8).sin() / SENSOR_ANGLE.sin();
+    assert!((swept - expected).abs() < 1e-9, "{swept} vs {expected}");
+
+    agent.sense(&Slope);
+    assert!(!agent.saccade);
+    assert!(
+        (agent.val_r - agent.val_l - narrow).abs() < 1e-12,
+        "spread restored"
+    );
+}
+
+#[test]
+fn test_saccade_costs_energy() {
+    let drain = |saccade: bool| {
+        let mut agent = Protozoa::with_heading(30.0, 25.0, 0.0);
+        agent.seed_streams(2);
+        agent.energy = 0.5;
+        agent.saccade_planned = saccade;
+        agent.sense(&Slope);
+        agent.update_state(&Slope);
+        0.5 - agent.energy
+    };
+    let (still, swept) = (drain(false), drain(true));
+    // With no sweep set the readings match, so the difference is the price
+    assert!(
+        (swept - still - SACCADE_COST).abs() < 1e-9,
+        "{still} {swept}"
+    );
+}
+
+#[test]
+fn test_agent_saccades_while_uncertain() {
+    let command = ParamCommand::parse("set saccade 1.0").unwrap();
+    assert_eq!(command.param, MorphologyParam::Saccade);
+    assert!(ParamCommand::parse("set saccade -0.5").is_err());
+
+    let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(3);
+    command.apply(&mut run.agent.morphology);
+    let (mut early, mut late) = (0, 0);
+    for tick in 0..600 {
+        run.step();
+        if run.agent.saccade {
+            if tick < 300 { early += 1 } else { late += 1 }
+        }
+    }
+    // Sweeps pay off while the nutrient belief is vague, then stop
+    assert!(early > 10, "{early}");
+    assert_eq!(late, 0);
+}
@@ -0,0 +1,179 @@
+//! Tests for active sensing scans: sampling the gradient at several orientations.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::inference::{GenerativeModel, scan_efe};
+use protozoa_rust::simulation::params::{
+    BEARING_DIFFUSION, BEARING_MAX_VAR, INTAKE_RATE, SCAN_COST, SCAN_PRIOR_AMPLITUDE, SENSOR_ANGLE,
+};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::provenance::Term;
+use protozoa_rust::simulation::scan::{BearingBelief, fit_bearing, scan_bearings};
+use protozoa_rust::simulation::scenario::Scenario;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+/// Ramp along x; its gradient points along bearing 0.
+struct Slope;
+
+impl Environment for Slope {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            0.2 + 0.6 * x / 100.0
+        } else {
+            -1.0
+        }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.006, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+/// Readings `0.5 + amplitude·cos(ψ − bearing)` at the scan's bearings.
+fn harmonic(bearing: f64, amplitude: f64) -> Vec<(f64, f64)> {
+    scan_bearings(0.3, SENSOR_ANGLE, 4)
+        .into_iter()
+        .map(|psi| (psi, 0.5 + amplitude * (psi - bearing).cos()))
+        .collect()
+}
+
+#[test]
+fn test_scan_bearings_turn_the_pair_around_the_body() {
+    assert!(scan_bearings(0.0, SENSOR_ANGLE, 0).is_empty());
+    assert!(
+        scan_bearings(0.0, SENSOR_ANGLE, 2).is_empty(),
+        "too few to fit"
+    );
+    let bearings = scan_bearings(1.0, 0.4, 4);
+    assert_eq!(bearings.len(), 8);
+    for (k, pair) in bearings.chunks(2).enumerate() {
+        let turn = 1.0 + TAU * k as f64 / 4.0;
+        assert!((pair[0] - (turn + 0.4)).abs() < 1e-12);
+        assert!((pair[1] - (turn - 0.4)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_fit_recovers_bearing_and_amplitude() {
+    for bearing in [0.0, 1.0, 4.0] {
+        let (fitted, amplitude) = fit_bearing(&harmonic(bearing, 0.2)).unwrap();
+        assert!((fitted - bearing).abs() < 1e-9, "{fitted} vs {bearing}");
+        assert!((amplitude - 0.2).abs() < 1e-9);
+    }
+    assert_eq!(fit_bearing(&harmonic(1.0, 0.0)), None, "flat field");
+    assert_eq!(fit_bearing(&[]), None);
+}
+
+#[test]
+fn test_bearing_belief_drifts_and_sharpens() {
+    let mut belief = BearingBelief::default();
+    assert_eq!(belief.var, BEARING_MAX_VAR);
+    assert_eq!(belief.amplitude, SCAN_PRIOR_AMPLITUDE);
+    assert_eq!(belief.confidence(), 0.0);
+
+    belief.observe(&harmonic(2.0, 0.2), 0.001);
+    assert!(belief.heading_error(2.0).abs() < 0.01);
+    assert!(belief.var < 0.01 * BEARING_MAX_VAR);
+    assert!((belief.amplitude - 0.2).abs() < 1e-9);
+    let settled = belief.var;
+
+    // A flat scan tells nothing; time blurs the bearing again, up to the cap
+    belief.observe(&harmonic(0.0, 0.0), 0.001);
+    assert_eq!(belief.var, settled);
+    let clock = SimClock::default();
+    belief.drift(&clock);
+    assert!((belief.var - settled - clock.rate(BEARING_DIFFUSION)).abs() < 1e-12);
+    for _ in 0..1000 {
+        belief.drift(&clock);
+    }
+    assert_eq!(belief.var, BEARING_MAX_VAR);
+}
+
+#[test]
+fn test_scan_efe_values_vague_bearings() {
+    let model = GenerativeModel::new();
+    let cost = model.prior_precision.nutrient * SCAN_COST / INTAKE_RATE;
+    let efe = |var: f64| scan_efe(var, SCAN_PRIOR_AMPLITUDE, 8, &model, SCAN_COST);
+
+    assert!(
+        (scan_efe(BEARING_MAX_VAR, 0.2, 0, &model, SCAN_COST) - cost).abs() < 1e-12,
+        "no samples, no information"
+    );
+    assert!(efe(BEARING_MAX_VAR) < 0.0, "vague bearing: worth scanning");
+    assert!(efe(0.05) > 0.0, "settled bearing: not worth it");
+    assert!(efe(2.0) < efe(1.0), "more uncertainty, more value");
+}
+
+#[test]
+fn test_scan_finds_the_slope_and_costs_energy() {
+    let drain = |scan: bool| {
+        let mut agent = Protozoa::with_heading(30.0, 25.0, FRAC_PI_2);
+        agent.seed_streams(2);
+        agent.energy = 0.5;
+        agent.morphology.scan_orientations = 4;
+        agent.scan_planned = scan;
+        agent.sense(&Slope);
+        assert_eq!(agent.scan, scan);
+        assert!(!agent.scan_planned);
+        agent.update_state(&Slope);
+        (agent, 0.5)
+    };
+    let (still, _) = drain(false);
+    assert!(still.scan_samples.is_empty());
+    assert_eq!(still.bearing.confidence(), 0.0);
+
+    let (scanned, _) = drain(true);
+    assert_eq!(scanned.scan_samples.len(), 8);
+    assert!(
+        scanned.bearing.heading_error(0.0).abs() < 0.05,
+        "up the ramp"
+    );
+    assert!(scanned.bearing.confidence() > 0.0);
+    // Readings left of a heading along y are equal, so the difference is the price
+    assert!((still.energy - scanned.energy - SCAN_COST).abs() < 1e-9);
+    assert!(scanned.provenance.term(Term::Scan) != 0.0);
+}
+
+#[test]
+fn test_no_scans_by_default() {
+    let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(1);
+    assert_eq!(run.agent.morphology.scan_orientations, 0);
+    for _ in 0..300 {
+        run.step();
+        assert!(!run.agent.scan && !run.agent.scan_planned);
+        assert_eq!(run.agent.provenance.term(Term::Scan), 0.0);
+    }
+}
+
+#[test]
+fn test_agent_scans_while_its_bearing_is_vague() {
+    let scans = |reach: f64| {
+        let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(3);
+        run.agent.morphology.scan_orientations = 4;
+        run.agent.morphology.sensor_dist = reach;
+        let (mut scans, mut worst) = (0, 0.0_f64);
+        for tick in 0..600 {
+            run.step();
+            scans += u32::from(run.agent.scan);
+            if tick >= 10 {
+                worst = worst.max(run.agent.bearing.var);
+            }
+        }
+        (scans, worst)
+    };
+    // A long reach reads the gradient well: after the first scans the bearing
+    // stays in check, and scans stop while it is settled
+    let (long, worst) = scans(20.0);
+    assert!((10..500).contains(&long), "{long}");
+    assert!(worst < BEARING_MAX_VAR / 2.0, "{worst}");
+    // The default reach barely resolves the gradient, so scans soon stop paying
+    let (short, _) = scans(2.0);
+    assert!(short < 5, "{short}");
+}
@@ -0,0 +1,74 @@
+//! Tests for the scenario harness and its behavioral assertions.
+
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::{
+    Region, Scenario, assert_energy_above_after, assert_reaches_region, run_scenario,
+};
+use protozoa_rust::simulation::spawn::SpawnConfig;
+
+#[test]
+fn test_agent_finds_gradient_patch() {
+    let scenario = Scenario::Preset(EnvironmentPreset::Gradient);
+    for seed in 0..3 {
+        let outcome = run_scenario(&scenario, seed, 500);
+        let patch = Region::around_source(&outcome.run.dish.sources[0]);
+        assert_reaches_region(&outcome, &patch, 500);
+        assert_energy_above_after(&outcome, 300, 0.5);
+    }
+}
+
+#[test]
+fn test_scenarios_replay_by_seed() {
+    let scenario = Scenario::Generated {
+        generator: GeneratorConfig::default(),
+        spawn: SpawnConfig::default(),
+    };
+    let a = run_scenario(&scenario, 5, 80);
+    let b = run_scenario(&scenario, 5, 80);
+    let c = run_scenario(&scenario, 6, 80);
+    assert_eq!(a.trace, b.trace);
+    assert_eq!(a.metrics, b.metrics);
+    assert_ne!(a.trace, c.trace);
+}
+
+#[test]
+fn test_trace_and_metrics_agree() {
+    let outcome = run_scenario(&Scenario::Preset(EnvironmentPreset::Ring), 1, 120);
+    assert_eq!(outcome.trace.len(), 121);
+    assert_eq!(outcome.trace[0].tick, 0);
+    assert_eq!(outcome.at(120), outcome.trace.last());
+    assert_eq!(outcome.at(500), outcome.trace.last());
+    assert_eq!(outcome.run.agent.tick_count, 120);
+
+    let min = outcome
+        .trace
+        .iter()
+        .map(|p| p.energy)
+        .fold(f64::INFINITY, f64::min);
+    assert!((outcome.metrics.min_energy - min).abs() < 1e-12);
+    assert!((outcome.metrics.final_energy - outcome.run.agent.energy).abs() < 1e-12);
+    let path: f64 = outcome
+        .trace
+        .windows(2)
+        .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+        .sum();
+    assert!((outcome.metrics.distance - path).abs() < 1e-9);
+    assert!(outcome.metrics.coverage > 0.0);
+}
+
+#[test]
+#[should_panic(expected = "did not reach")]
+fn test_reach_assertion_reports_failure() {
+    // The maze food is far beyond what 50 ticks of swimming can cover
+    let outcome = run_scenario(&Scenario::Preset(EnvironmentPreset::Maze), 1, 50);
+    let food = Region::around_source(&outcome.run.dish.sources[0]);
+    assert_reaches_region(&outcome, &food, 50);
+}
+
+#[test]
+#[should_panic(expected = "ended before tick 100")]
+fn test_energy_assertion_needs_the_tick() {
+    let outcome = run_scenario(&Scenario::Preset(EnvironmentPreset::Gradient), 1, 20);
+    assert_energy_above_after(&outcome, 100, 0.0);
+}
@@ -0,0 +1,154 @@
+//! Tests for the seasonal nutrient regimes.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::cycle::{Season, SeasonCycle};
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::export::{CSV_COLUMNS, record_csv};
+use protozoa_rust::simulation::observer::{SourceEvent, SourceEventLog};
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::replay::{ReplayEvent, ReplayEventKind};
+use protozoa_rust::simulation::world::World;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::render::format_source_events;
+
+/// The gradient preset with seasons of `length` seconds and the given depth.
+fn seasonal_dish(length: u64, depth: f64) -> PetriDish {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    dish.config.season_length = length;
+    dish.config.season_depth = depth;
+    dish
+}
+
+#[test]
+fn test_seasons_alternate_rich_and_poor() {
+    let cycle = SeasonCycle {
+        length: 100,
+        depth: 0.7,
+    };
+    assert_eq!(cycle.season(0.0), Season::Rich);
+    assert_eq!(cycle.season(99.9), Season::Rich);
+    assert_eq!(cycle.season(100.0), Season::Poor);
+    assert_eq!(cycle.season(199.0), Season::Poor);
+    assert_eq!(cycle.season(200.0), Season::Rich);
+    assert!((cycle.level(50.0) - 1.0).abs() < 1e-12);
+    assert!((cycle.level(150.0) - 0.3).abs() < 1e-12);
+
+    let off = SeasonCycle {
+        length: 100,
+        depth: 0.0,
+    };
+    assert!(off.is_off());
+    assert_eq!(off.season(150.0), Season::Rich);
+    assert!((off.level(150.0) - 1.0).abs() < 1e-12);
+    assert_eq!((Season::Rich.code(), Season::Poor.code()), (0, 1));
+    assert_eq!(Season::default(), Season::Rich);
+}
+
+#[test]
+fn test_poor_season_scales_supply_with_daylight() {
+    let mut dish = seasonal_dish(200, 0.5);
+    dish.config.day_night_period = 400;
+    dish.config.day_night_amplitude = 0.4;
+    let source = dish.sources[0].clone();
+    let rich = dish.get_concentration(source.x, source.y);
+    assert!((dish.supply() - 1.0).abs() < 1e-12);
+
+    for _ in 0..200 {
+        dish.update();
+    }
+    // Poor season at midnight: 0.5 × 0.6
+    assert_eq!(dish.season(), Season::Poor);
+    assert!((dish.supply() - 0.3).abs() < 1e-9);
+    let poor = dish.get_concentration(source.x, source.y);
+    assert!((poor - 0.3 * rich).abs() < 1e-9, "{poor} vs {rich}");
+    let (g_x, g_y) = dish.gradient(source.x + 5.0, source.y);
+    assert!(g_x.hypot(g_y) > 0.0);
+}
+
+#[test]
+fn test_season_boundaries_are_logged() {
+    let mut dish = seasonal_dish(100, 0.5);
+    let mut log = SourceEventLog::default();
+    for _ in 0..250 {
+        dish.update_observed(&mut log);
+    }
+    let changes: Vec<(u64, SourceEvent)> = log
+        .iter()
+        .filter(|(_, e)| matches!(e, SourceEvent::SeasonChanged { .. }))
+        .copied()
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                100,
+                SourceEvent::SeasonChanged {
+                    season: Season::Poor
+                }
+            ),
+            (
+                200,
+                SourceEvent::SeasonChanged {
+                    season: Season::Rich
+                }
+            ),
+        ]
+    );
+    assert_eq!(log.season_changes(), 2);
+    assert_eq!(log.counts(), (0, 0, 0));
+
+    // Without seasons nothing is reported
+    let mut calm = seasonal_dish(100, 0.0);
+    let mut quiet = SourceEventLog::default();
+    for _ in 0..250 {
+        calm.update_observed(&mut quiet);
+    }
+    assert_eq!(quiet.season_changes(), 0);
+}
+
+#[test]
+fn test_season_config_keys() {
+    let config = DishConfig::from_toml_str("season_length = 3000\nseason_depth = 0.6\n").unwrap();
+    assert_eq!(config.seasons().length, 3000);
+    assert!((config.seasons().depth - 0.6).abs() < 1e-12);
+    assert!(DishConfig::default().seasons().is_off());
+
+    assert!(DishConfig::from_toml_str("season_depth = 1.5").is_err());
+    assert!(DishConfig::from_toml_str("season_depth = -0.1").is_err());
+    assert!(DishConfig::from_toml_str("season_length = 0").is_err());
+}
+
+#[test]
+fn test_season_is_exported_and_labeled() {
+    let mut world = World::single(seasonal_dish(10, 0.5));
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(1);
+    let csv = record_csv(&mut world, &mut agent, 25);
+    let column = CSV_COLUMNS.iter().position(|c| *c == "season").unwrap();
+    let seasons: Vec<&str> = csv
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').nth(column).unwrap())
+        .collect();
+    // Ticks 1-9 rich, 10-19 poor, 20-25 rich
+    assert_eq!(seasons[8], "0");
+    assert_eq!(seasons[9], "1");
+    assert_eq!(seasons[18], "1");
+    assert_eq!(seasons[19], "0");
+
+    let change = SourceEvent::SeasonChanged {
+        season: Season::Poor,
+    };
+    let event = ReplayEvent {
+        tick: 10,
+        kind: ReplayEventKind::Source(change),
+    };
+    assert_eq!(event.label(), "poor season");
+    #[cfg(feature = "tui")]
+    {
+        let lines = format_source_events(&[(10, change)], (0, 0, 0), 1);
+        assert_eq!(lines[1], "    10t poor season");
+    }
+}
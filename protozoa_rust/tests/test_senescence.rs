@@ -0,0 +1,140 @@
+//! Tests for aging and senescence.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::annotation::{MorphologyParam, ParamCommand};
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::params::{MAX_SPEED, SENESCENCE_FLOOR};
+use protozoa_rust::simulation::senescence::Senescence;
+
+/// Concentration rising left to right, so a reading tells how far ahead
+/// the sensors reach.
+struct Ramp;
+
+impl Environment for Ramp {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) { x / 100.0 } else { -1.0 }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.01, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+fn agent(senescence: Senescence, age: f64) -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(1);
+    agent.morphology.senescence = senescence;
+    agent.age = age;
+    agent
+}
+
+#[test]
+fn test_default_agents_never_age() {
+    let senescence = Senescence::default();
+    assert!(senescence.is_off());
+    assert!(senescence.validate().is_ok());
+    for age in [0.0, 1e3, 1e6] {
+        assert_eq!(senescence.vigor(age), 1.0);
+    }
+    let agent = agent(senescence, 1e6);
+    assert_eq!(agent.vigor(), 1.0);
+    assert_eq!(agent.sensor_reach(), agent.morphology.sensor_dist);
+}
+
+#[test]
+fn test_vigor_declines_past_onset_to_the_floor() {
+    let senescence = Senescence::new(100.0, 0.01);
+    assert!(!senescence.is_off());
+    assert_eq!(senescence.floor, SENESCENCE_FLOOR);
+    assert_eq!(senescence.vigor(0.0), 1.0);
+    assert_eq!(senescence.vigor(100.0), 1.0);
+    let expected = SENESCENCE_FLOOR + (1.0 - SENESCENCE_FLOOR) * (-1.0_f64).exp();
+    assert!((senescence.vigor(200.0) - expected).abs() < 1e-12);
+
+    let mut previous = 1.0;
+    for age in (150..2000).step_by(50) {
+        let vigor = senescence.vigor(f64::from(age));
+        assert!(vigor < previous && vigor > SENESCENCE_FLOOR);
+        previous = vigor;
+    }
+    assert!((senescence.vigor(1e6) - SENESCENCE_FLOOR).abs() < 1e-12);
+}
+
+#[test]
+fn test_invalid_senescence_is_rejected() {
+    for bad in [
+        Senescence::new(-1.0, 0.01),
+        Senescence::new(100.0, -0.01),
+        Senescence::new(f64::INFINITY, 0.01),
+        Senescence::new(100.0, f64::NAN),
+        Senescence {
+            floor: 1.5,
+            ..Senescence::new(100.0, 0.01)
+        },
+    ] {
+        assert!(bad.validate().is_err(), "{bad:?}");
+    }
+    assert!(Senescence::new(0.0, 0.0).validate().is_ok());
+}
+
+#[test]
+fn test_age_counts_simulated_seconds() {
+    let mut agent = agent(Senescence::new(50.0, 0.01), 0.0);
+    let dt = Ramp.clock().dt();
+    for _ in 0..120 {
+        agent.sense(&Ramp);
+        agent.update_state(&Ramp);
+    }
+    assert!(agent.is_alive());
+    assert!((agent.age - 120.0 * dt).abs() < 1e-9);
+    assert!(agent.vigor() < 1.0);
+    assert!((agent.vigor() - agent.morphology.senescence.vigor(agent.age)).abs() < 1e-12);
+}
+
+#[test]
+fn test_old_agents_are_slower() {
+    let senescence = Senescence::new(0.0, 0.1);
+    let mut young = agent(senescence, 0.0);
+    let mut old = agent(senescence, 1e4);
+    for agent in [&mut young, &mut old] {
+        agent.cruise_speed = MAX_SPEED;
+        agent.sense(&Ramp);
+        agent.update_state(&Ramp);
+    }
+    assert!(young.speed > MAX_SPEED * SENESCENCE_FLOOR);
+    assert!(old.speed <= MAX_SPEED * SENESCENCE_FLOOR + 1e-9);
+}
+
+#[test]
+fn test_old_agents_sense_close_by() {
+    let senescence = Senescence::new(0.0, 0.1);
+    let mut young = agent(senescence, 0.0);
+    let mut old = agent(senescence, 1e4);
+    young.morphology.sensor_dist = 20.0;
+    old.morphology.sensor_dist = 20.0;
+    assert!((old.sensor_reach() - 20.0 * SENESCENCE_FLOOR).abs() < 1e-9);
+    young.sense(&Ramp);
+    old.sense(&Ramp);
+    // Both sensors point ahead, up the ramp; the old ones reach less far
+    assert!(young.val_l > old.val_l && young.val_r > old.val_r);
+    assert!(old.val_l > 0.5 && old.val_r > 0.5);
+}
+
+#[test]
+fn test_aging_rate_is_a_runtime_parameter() {
+    let command = ParamCommand::parse("set aging 0.002").unwrap();
+    assert_eq!(command.param, MorphologyParam::Aging);
+    assert!(ParamCommand::parse("set aging -0.1").is_err());
+
+    let mut agent = agent(Senescence::default(), 1e4);
+    assert_eq!(agent.vigor(), 1.0);
+    command.apply(&mut agent.morphology);
+    assert_eq!(MorphologyParam::Aging.get(&agent.morphology), 0.002);
+    assert!(agent.vigor() < 1.0);
+}
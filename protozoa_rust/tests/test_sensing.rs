@@ -0,0 +1,159 @@
+//! Tests for the `Sensor` abstraction: the built-in sensors, the suite that
+//! composes them on the agent, and the chemoreceptor array as a sensor.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{Environment, Obstacle, PetriDish};
+use protozoa_rust::simulation::fixtures::LinearGradient;
+use protozoa_rust::simulation::params::{MAX_SUITE_SENSORS, SECONDS_PER_TICK, TOUCH_REACH};
+use protozoa_rust::simulation::sensing::{
+    ChemoreceptorPair, Pose, Sensor, SensorKind, SensorSuite, TemporalDerivative, TouchSensor,
+};
+use protozoa_rust::simulation::sensors::SensorArray;
+
+/// Facing east at (50, 25) with the pair's tips at ±45° and 10 units out.
+fn pose(x: f64, tick: u64) -> Pose {
+    Pose {
+        x,
+        y: 25.0,
+        angle: 0.0,
+        spread: std::f64::consts::FRAC_PI_4,
+        reach: 10.0,
+        tick,
+    }
+}
+
+fn sample(sensor: &mut impl Sensor, pose: &Pose, dish: &LinearGradient) -> Vec<f64> {
+    let mut out = Vec::new();
+    sensor.sample(pose, dish, &mut out);
+    assert_eq!(out.len(), sensor.channels());
+    out
+}
+
+#[test]
+fn test_pair_reads_both_tips() {
+    let dish = LinearGradient::across(100.0, 50.0);
+    let pose = pose(50.0, 0);
+    let (x_l, y_l) = pose.point(1.0, 1.0);
+    let (x_r, _) = pose.point(-1.0, 1.0);
+    assert!(y_l > 25.0);
+    assert!((x_l - x_r).abs() < 1e-12);
+
+    let reading = sample(&mut ChemoreceptorPair::default(), &pose, &dish);
+    assert_eq!(reading, [dish.sensed_concentration(x_l, y_l); 2]);
+    // Longer tips reach further up the gradient
+    let far = sample(&mut ChemoreceptorPair { reach: 2.0 }, &pose, &dish);
+    assert!(far[0] > reading[0]);
+}
+
+#[test]
+fn test_array_is_a_sensor() {
+    let dish = LinearGradient::across(100.0, 50.0);
+    let pose = pose(50.0, 0);
+    let mut array = SensorArray::parse("1:1,0:1.5,-1:1").unwrap();
+    assert_eq!(array.name(), "array");
+    let reading = sample(&mut array, &pose, &dish);
+    let (x, y) = pose.point(0.0, 1.5);
+    assert_eq!(reading[1], dish.sensed_concentration(x, y));
+    // The pair array reads as the pair sensor does
+    let pair = sample(&mut SensorArray::pair(), &pose, &dish);
+    assert_eq!(
+        pair,
+        sample(&mut ChemoreceptorPair::default(), &pose, &dish)
+    );
+}
+
+#[test]
+fn test_temporal_derivative_reads_change_over_time() {
+    let dish = LinearGradient::across(100.0, 50.0);
+    let mut sensor = TemporalDerivative::default();
+    // Nothing to compare the first sample with
+    assert_eq!(sample(&mut sensor, &pose(50.0, 0), &dish), [0.0]);
+    assert_eq!(sensor.last(), Some((0.5, 0)));
+    // Swimming up the gradient by 1 unit per tick
+    let rate = sample(&mut sensor, &pose(51.0, 1), &dish)[0];
+    assert!((rate - 0.01 / SECONDS_PER_TICK).abs() < 1e-9);
+    // A sample two ticks later spreads the change over both
+    let rate = sample(&mut sensor, &pose(53.0, 3), &dish)[0];
+    assert!((rate - 0.01 / SECONDS_PER_TICK).abs() < 1e-9);
+    // And swimming down it reads negative
+    assert!(sample(&mut sensor, &pose(50.0, 4), &dish)[0] < 0.0);
+}
+
+#[test]
+fn test_touch_feels_obstacles_and_walls() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut touch = TouchSensor::default();
+    assert_eq!(touch.reach, TOUCH_REACH);
+    let mut out = Vec::new();
+    touch.sample(&pose(50.0, 0), &dish, &mut out);
+    assert_eq!(out, [0.0, 0.0]);
+
+    // A wall only on the left feeler's side
+    dish.obstacles.push(Obstacle {
+        x_min: 52.0,
+        y_min: 27.0,
+        x_max: 56.0,
+        y_max: 31.0,
+    });
+    out.clear();
+    touch.sample(&pose(50.0, 0), &dish, &mut out);
+    assert_eq!(out, [1.0, 0.0]);
+    // The dish edge counts as contact
+    out.clear();
+    touch.sample(&pose(97.0, 0), &dish, &mut out);
+    assert_eq!(out, [1.0, 1.0]);
+}
+
+#[test]
+fn test_suite_parses_names() {
+    let suite = SensorSuite::parse("temporal, Touch").unwrap();
+    let names: Vec<&str> = suite.sensors().iter().map(Sensor::name).collect();
+    assert_eq!(names, ["temporal", "touch"]);
+    assert_eq!(SensorKind::parse("PAIR").unwrap().channels(), 2);
+    let error = SensorSuite::parse("temporal,sonar").unwrap_err();
+    assert_eq!(
+        error,
+        "unknown sensor: sonar (expected pair, temporal, touch)"
+    );
+    let crowded = vec![SensorKind::ALL[0]; MAX_SUITE_SENSORS + 1];
+    assert!(SensorSuite::new(crowded).is_err());
+    assert!(SensorSuite::default().is_empty());
+}
+
+#[test]
+fn test_suite_readings_by_sensor() {
+    let dish = LinearGradient::across(100.0, 50.0);
+    let mut suite = SensorSuite::parse("pair,temporal,touch").unwrap();
+    assert_eq!(suite.reading("touch"), Some(&[][..]));
+    suite.sense(&pose(50.0, 0), &dish);
+    suite.sense(&pose(52.0, 1), &dish);
+    assert_eq!(suite.readings().len(), 5);
+    assert_eq!(suite.reading("pair").unwrap().len(), 2);
+    assert!(suite.reading("temporal").unwrap()[0] > 0.0);
+    assert_eq!(suite.reading("touch"), Some(&[0.0, 0.0][..]));
+    assert_eq!(suite.reading("sonar"), None);
+    assert!(suite.check_finite().is_ok());
+}
+
+#[test]
+fn test_agent_samples_its_suite() {
+    let dish = LinearGradient::across(200.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(4);
+    // Nothing besides the array by default
+    agent.sense(&dish);
+    assert!(agent.sensor_suite.readings().is_empty());
+
+    agent.sensor_suite = SensorSuite::parse("pair,temporal").unwrap();
+    for _ in 0..10 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+    agent.sense(&dish);
+    // Noiseless, the suite's pair reads what the array's receptors do
+    let pair = agent.sensor_suite.reading("pair").unwrap();
+    assert_eq!(pair, [agent.val_l, agent.val_r]);
+    let temporal = agent.sensor_suite.reading("temporal").unwrap()[0];
+    assert!(temporal.is_finite());
+    assert!(agent.check_finite(&agent.tick_context()).is_ok());
+}
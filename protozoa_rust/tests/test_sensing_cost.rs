@@ -0,0 +1,148 @@
+//! Tests for energy-proportional sensing and the sensing-rate choice.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::annotation::{MorphologyParam, ParamCommand};
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::inference::{BeliefState, GenerativeModel, sensing_efe};
+use protozoa_rust::simulation::params::SENSING_INTERVALS;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+
+/// Gentle ramp along x, so readings change as the agent swims.
+struct Slope;
+
+impl Environment for Slope {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) {
+            0.2 + 0.6 * x / 100.0
+        } else {
+            -1.0
+        }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.006, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+fn best_interval(beliefs: &BeliefState, model: &GenerativeModel, cost: f64) -> u32 {
+    SENSING_INTERVALS
+        .into_iter()
+        .min_by(|a, b| {
+            let efe = |k: u32| sensing_efe(beliefs, model, k, cost);
+            efe(*a).total_cmp(&efe(*b))
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_sensing_efe_trades_ambiguity_for_energy() {
+    let model = GenerativeModel::new();
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.0);
+    beliefs.covariance.nutrient_var = 0.05;
+    assert_eq!(best_interval(&beliefs, &model, 0.0), 1, "free sensing");
+    assert_eq!(best_interval(&beliefs, &model, 1.0), 4, "costly sensing");
+
+    // Growing uncertainty makes each skipped sample dearer
+    let sparse = sensing_efe(&beliefs, &model, 4, 0.0);
+    assert!(sparse > sensing_efe(&beliefs, &model, 1, 0.0));
+    assert!(best_interval(&beliefs, &model, 0.005) > 1);
+    beliefs.covariance.nutrient_var = 0.5;
+    assert!(sensing_efe(&beliefs, &model, 4, 0.0) > sparse);
+    assert_eq!(best_interval(&beliefs, &model, 0.005), 1);
+}
+
+#[test]
+fn test_free_sensing_samples_every_tick() {
+    let mut agent = Protozoa::with_heading(30.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    for _ in 0..100 {
+        agent.sense(&Slope);
+        assert!(agent.sampled);
+        agent.update_state(&Slope);
+        assert_eq!(agent.sensing_interval, 1);
+    }
+    assert_eq!(agent.spatial_priors.total_visits(), 100);
+}
+
+#[test]
+fn test_each_sample_costs_energy_by_sensor_distance() {
+    let drain = |cost: f64, dist: f64| {
+        let mut agent = Protozoa::with_heading(30.0, 25.0, 0.0);
+        agent.seed_streams(3);
+        agent.morphology.sensing_cost = cost;
+        agent.morphology.sensor_dist = dist;
+        agent.energy = 0.5;
+        agent.sense(&Slope);
+        agent.update_state(&Slope);
+        0.5 - agent.energy
+    };
+    let free = drain(0.0, 4.0);
+    assert!((drain(0.01, 4.0) - free - 0.04).abs() < 1e-12);
+    assert!((drain(0.01, 2.0) - drain(0.0, 2.0) - 0.02).abs() < 1e-12);
+}
+
+#[test]
+fn test_readings_are_held_between_samples() {
+    let mut agent = Protozoa::with_heading(20.0, 25.0, 0.0);
+    agent.seed_streams(5);
+    agent.morphology.sensing_cost = 1.0;
+    agent.sense(&Slope);
+    agent.update_state(&Slope);
+    assert_eq!(agent.sensing_interval, 4);
+    let (held, visits) = (agent.val_l, agent.spatial_priors.total_visits());
+    let var = agent.beliefs.covariance.nutrient_var;
+    for _ in 0..3 {
+        let x = agent.x;
+        agent.sense(&Slope);
+        assert!(!agent.sampled);
+        assert_eq!(agent.val_l, held);
+        agent.update_state(&Slope);
+        assert!(agent.x != x, "still swimming");
+    }
+    assert_eq!(agent.spatial_priors.total_visits(), visits);
+    assert!(agent.beliefs.covariance.nutrient_var > var, "beliefs drift");
+    agent.sense(&Slope);
+    assert!(agent.sampled);
+    assert!(agent.val_l != held);
+}
+
+#[test]
+fn test_costlier_sensing_samples_less_often() {
+    let command = ParamCommand::parse("set sensing_cost 0.05").unwrap();
+    assert_eq!(command.param, MorphologyParam::SensingCost);
+    assert!(ParamCommand::parse("set sensing_cost -0.1").is_err());
+
+    let mean_interval = |cost: f64| {
+        let mut total: f64 = 0.0;
+        for seed in 0..4 {
+            let mut run = Scenario::Preset(EnvironmentPreset::Gradient).build(seed);
+            ParamCommand {
+                param: MorphologyParam::SensingCost,
+                value: cost,
+            }
+            .apply(&mut run.agent.morphology);
+            run.agent.morphology.sensor_dist = 8.0;
+            let mut samples: f64 = 0.0;
+            for _ in 0..400 {
+                run.step();
+                if run.agent.sampled {
+                    samples += 1.0;
+                }
+            }
+            total += 400.0 / samples;
+        }
+        total / 4.0
+    };
+    let free = mean_interval(0.0);
+    let cheap = mean_interval(0.002);
+    let dear = mean_interval(0.05);
+    assert!((free - 1.0).abs() < 1e-12);
+    assert!(cheap > free && dear > cheap, "{free} {cheap} {dear}");
+}
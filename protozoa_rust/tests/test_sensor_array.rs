@@ -0,0 +1,174 @@
+//! Tests for configurable chemoreceptor arrays.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::calibration::SensorCalibration;
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::inference::{
+    BeliefState, GenerativeModel, ReceptorObservation, array_free_energy, array_vfe_gradient,
+    variational_free_energy, vfe_gradient,
+};
+use protozoa_rust::simulation::params::{MAX_CHEMORECEPTORS, SENSOR_DIST};
+use protozoa_rust::simulation::sensors::{Chemoreceptor, SensorArray, side_means};
+
+/// Ramp along y; an agent heading along x reads it across its receptors.
+struct Ramp;
+
+impl Environment for Ramp {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) { y / 50.0 } else { -1.0 }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.0, 0.02)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+fn agent_with(sensors: SensorArray) -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(5);
+    agent.morphology.sensors = sensors;
+    agent
+}
+
+#[test]
+fn test_arrays_parse_and_validate() {
+    let pair = SensorArray::default();
+    assert_eq!(pair, SensorArray::pair());
+    assert_eq!(
+        pair.receptors(),
+        [Chemoreceptor::new(1.0, 1.0), Chemoreceptor::new(-1.0, 1.0)]
+    );
+    assert_eq!(SensorArray::parse("2").unwrap(), pair);
+
+    let fan = SensorArray::fan(5).unwrap();
+    let bearings: Vec<f64> = fan.receptors().iter().map(|r| r.bearing).collect();
+    assert_eq!(bearings, [1.0, 0.5, 0.0, -0.5, -1.0]);
+    assert_eq!(SensorArray::fan(1).unwrap().receptors()[0].bearing, 0.0);
+
+    let custom = SensorArray::parse("1:1, 0:1.5 ,-1:1").unwrap();
+    assert_eq!(custom.receptors()[1], Chemoreceptor::new(0.0, 1.5));
+
+    assert!(SensorArray::fan(0).is_err());
+    assert!(SensorArray::fan(MAX_CHEMORECEPTORS + 1).is_err());
+    assert!(SensorArray::parse("1:0").is_err(), "reach must be positive");
+    assert!(SensorArray::parse("1").is_ok());
+    assert!(SensorArray::parse("left:1").is_err());
+    assert!(SensorArray::parse("1;1").is_err());
+}
+
+#[test]
+fn test_array_model_generalizes_the_pair() {
+    let model = GenerativeModel::new();
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.7);
+    beliefs.mean.nutrient = 0.4;
+    let pair = ReceptorObservation::pair((0.5, 0.3));
+
+    assert_eq!(
+        model.observation_function(&beliefs.mean),
+        (
+            model.receptor_prediction(&beliefs.mean, 1.0),
+            model.receptor_prediction(&beliefs.mean, -1.0)
+        )
+    );
+    assert_eq!(
+        array_free_energy(&pair, &beliefs, &model),
+        variational_free_energy((0.5, 0.3), &beliefs, &model)
+    );
+    let array = array_vfe_gradient(&pair, &beliefs, &model);
+    let classic = vfe_gradient((0.5, 0.3), &beliefs, &model);
+    assert_eq!(array.nutrient, classic.nutrient);
+    assert_eq!(array.angle, classic.angle);
+
+    // Each Jacobian row matches a finite difference of its prediction
+    for bearing in [1.0, 0.5, 0.0, -0.25, -1.0] {
+        let (d_nutrient, d_angle) = model.receptor_jacobian(&beliefs.mean, bearing);
+        let mut shifted = beliefs.mean;
+        shifted.angle += 1e-6;
+        let numeric = (model.receptor_prediction(&shifted, bearing)
+            - model.receptor_prediction(&beliefs.mean, bearing))
+            / 1e-6;
+        assert!((d_angle - numeric).abs() < 1e-6, "bearing {bearing}");
+        assert_eq!(d_nutrient, 1.0);
+    }
+    assert_eq!(model.receptor_jacobian(&beliefs.mean, 0.0).1, 0.0);
+}
+
+#[test]
+fn test_more_receptors_pull_beliefs_harder() {
+    let model = GenerativeModel::new();
+    let beliefs = BeliefState::new(50.0, 25.0, 0.0);
+    let reading = |bearing| ReceptorObservation {
+        value: 0.9,
+        bearing,
+    };
+    let two = array_vfe_gradient(&[reading(1.0), reading(-1.0)], &beliefs, &model);
+    let five: Vec<ReceptorObservation> = [1.0, 0.5, 0.0, -0.5, -1.0]
+        .into_iter()
+        .map(reading)
+        .collect();
+    let five = array_vfe_gradient(&five, &beliefs, &model);
+    assert!(two.nutrient > 0.0);
+    assert!(five.nutrient > two.nutrient, "more evidence, bigger step");
+    // With no readings only the priors are left
+    assert!(
+        array_free_energy(&[], &beliefs, &model)
+            < array_free_energy(&[reading(0.0)], &beliefs, &model)
+    );
+}
+
+#[test]
+fn test_array_sensing_reads_every_receptor() {
+    // The pair reads exactly its two channels
+    let mut pair = agent_with(SensorArray::pair());
+    pair.sense(&Ramp);
+    assert_eq!(pair.readings.len(), 2);
+    assert_eq!(
+        (pair.readings[0].value, pair.readings[1].value),
+        (pair.val_l, pair.val_r)
+    );
+    assert!(pair.val_l > pair.val_r, "the ramp rises to the left");
+
+    let mut fan = agent_with(SensorArray::fan(5).unwrap());
+    fan.sense(&Ramp);
+    let values: Vec<f64> = fan.readings.iter().map(|r| r.value).collect();
+    assert_eq!(values.len(), 5);
+    assert!(values.windows(2).all(|w| w[0] > w[1]), "{values:?}");
+    assert!(
+        (values[2] - 0.5).abs() < 1e-12,
+        "center reads straight ahead"
+    );
+    assert_eq!(side_means(&fan.readings), (fan.val_l, fan.val_r));
+    assert!((fan.val_l - (values[0] + values[1] + values[2]) / 3.0).abs() < 1e-12);
+
+    // Crossed wiring mirrors the side each receptor reports to
+    let mut crossed = agent_with(SensorArray::fan(3).unwrap());
+    crossed.calibration = SensorCalibration::parse("swap").unwrap();
+    crossed.sense(&Ramp);
+    assert_eq!(crossed.readings[0].bearing, -1.0);
+    assert!(crossed.val_l < crossed.val_r);
+}
+
+#[test]
+fn test_sample_cost_scales_with_reach() {
+    let pair = agent_with(SensorArray::pair());
+    let cost = pair.morphology.sensing_cost * SENSOR_DIST;
+    assert_eq!(pair.morphology.sample_cost(), cost);
+    let fan = agent_with(SensorArray::fan(5).unwrap());
+    assert!((fan.morphology.sample_cost() - 2.5 * cost).abs() < 1e-12);
+    let long = agent_with(SensorArray::parse("1:2,-1:2").unwrap());
+    assert!((long.morphology.sample_cost() - 2.0 * cost).abs() < 1e-12);
+
+    // A fanned agent runs its whole loop on the array
+    let mut agent = agent_with(SensorArray::fan(4).unwrap());
+    for _ in 0..50 {
+        agent.sense(&Ramp);
+        agent.update_state(&Ramp);
+    }
+    assert!(agent.current_vfe.is_finite() && agent.believed_nutrient().is_finite());
+}
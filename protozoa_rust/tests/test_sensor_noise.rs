@@ -0,0 +1,183 @@
+//! Tests for the configurable chemoreceptor noise model.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::Environment;
+use protozoa_rust::simulation::inference::PrecisionEstimator;
+use protozoa_rust::simulation::params::MAX_SENSORY_PRECISION;
+use protozoa_rust::simulation::sensor_noise::{ChannelNoise, SensorNoise};
+use protozoa_rust::simulation::streams::Stream;
+
+/// Uniform field: every reading error is sensor noise.
+struct Flat;
+
+impl Environment for Flat {
+    fn get_concentration(&self, x: f64, y: f64) -> f64 {
+        if self.contains(x, y) { 0.5 } else { -1.0 }
+    }
+
+    fn gradient(&self, _x: f64, _y: f64) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    fn update(&mut self) {}
+
+    fn bounds(&self) -> (f64, f64) {
+        (100.0, 50.0)
+    }
+}
+
+fn white(std: f64) -> ChannelNoise {
+    ChannelNoise {
+        std,
+        ..ChannelNoise::default()
+    }
+}
+
+/// Precision a `PrecisionEstimator` learns from the reading errors of an
+/// agent sensing the flat field (its variance estimate averaged over ticks
+/// 500..3000).
+fn learned_precision(noise: SensorNoise) -> (f64, f64) {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.sensor_noise = noise;
+    let mut estimator = PrecisionEstimator::new();
+    let (mut left, mut right) = (0.0, 0.0);
+    for tick in 0..3000 {
+        agent.sense(&Flat);
+        estimator.update(agent.val_l - 0.5, agent.val_r - 0.5);
+        if tick >= 500 {
+            left += 1.0 / estimator.precision_left();
+            right += 1.0 / estimator.precision_right();
+        }
+    }
+    (2500.0 / left, 2500.0 / right)
+}
+
+#[test]
+fn test_default_is_noiseless() {
+    let noise = SensorNoise::default();
+    assert!(noise.is_off());
+    assert_eq!(DishConfig::default().sensor_noise, noise);
+    assert_eq!(Protozoa::new(50.0, 25.0).sensor_noise, noise);
+    let mut rng = Stream::Sensing.rng(0);
+    assert_eq!(noise.read(0.4, (0.0, 0.0), &mut rng), (0.4, 0.4));
+    assert_eq!(noise.read(-1.0, (0.0, 0.0), &mut rng), (-1.0, -1.0));
+    let clock = SimClock::new(1.0);
+    assert_eq!(noise.drift((0.1, -0.1), &clock, &mut rng), (0.1, -0.1));
+}
+
+#[test]
+fn test_readings_carry_bias_and_white_noise() {
+    let channel = ChannelNoise {
+        std: 0.1,
+        bias: 0.05,
+        drift: 0.0,
+    };
+    let mut rng = Stream::Sensing.rng(1);
+    let reads: Vec<f64> = (0..20_000)
+        .map(|_| channel.read(0.5, 0.02, &mut rng))
+        .collect();
+    let mean = reads.iter().sum::<f64>() / 20_000.0;
+    let var = reads.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 20_000.0;
+    assert!((mean - 0.57).abs() < 0.005, "mean {mean}");
+    assert!((var.sqrt() - 0.1).abs() < 0.005, "std {}", var.sqrt());
+
+    // Noise never fakes the toxic void, and the void stays visible
+    let loud = white(1.0);
+    assert!((0..1000).all(|_| loud.read(0.0, 0.0, &mut rng) >= 0.0));
+    assert_eq!(loud.read(-1.0, 0.0, &mut rng), -1.0);
+}
+
+#[test]
+fn test_drift_is_a_random_walk_in_sim_time() {
+    let channel = ChannelNoise {
+        drift: 0.2,
+        ..ChannelNoise::default()
+    };
+    let clock = SimClock::new(0.5);
+    let mut rng = Stream::Sensing.rng(2);
+    // 400 ticks of 0.5 s: variance 0.2² × 200 s = 8 per walk
+    let ends: Vec<f64> = (0..2000)
+        .map(|_| (0..400).fold(0.0, |offset, _| channel.drift(offset, &clock, &mut rng)))
+        .collect();
+    let var = ends.iter().map(|e| e * e).sum::<f64>() / 2000.0;
+    assert!((var - 8.0).abs() < 0.8, "variance {var}");
+}
+
+#[test]
+fn test_noise_is_configured_in_toml() {
+    let config = DishConfig::from_toml_str(
+        "width = 120.0\n[sensor_noise.left]\nstd = 0.2\ndrift = 0.01\n[sensor_noise.right]\nbias = -0.1\n",
+    )
+    .unwrap();
+    assert_eq!(config.width, 120.0);
+    assert_eq!(config.sensor_noise.left.std, 0.2);
+    assert_eq!(config.sensor_noise.left.drift, 0.01);
+    assert_eq!(config.sensor_noise.right.bias, -0.1);
+    assert_eq!(config.sensor_noise.right.std, 0.0);
+    assert_eq!(
+        DishConfig::from_toml_str(&config.to_toml_string()),
+        Ok(config)
+    );
+
+    assert!(DishConfig::from_toml_str("[sensor_noise.left]\nsigma = 0.2\n").is_err());
+    assert!(DishConfig::from_toml_str("[sensor_noise.right]\nstd = -0.2\n").is_err());
+    assert!(DishConfig::from_toml_str("[sensor_noise.left]\ndrift = -1.0\n").is_err());
+}
+
+#[test]
+fn test_agent_readings_are_noisy_and_seeded() {
+    let run = |noise: SensorNoise, seed: u64| {
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(seed);
+        agent.sensor_noise = noise;
+        (0..20)
+            .map(|_| {
+                agent.sense(&Flat);
+                (agent.val_l, agent.val_r)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert!(
+        run(SensorNoise::default(), 0)
+            .iter()
+            .all(|&reading| reading == (0.5, 0.5))
+    );
+    let noise = SensorNoise {
+        left: white(0.1),
+        right: ChannelNoise::default(),
+    };
+    let noisy = run(noise, 4);
+    assert_eq!(noisy, run(noise, 4), "deterministic under the seed");
+    assert_ne!(noisy, run(noise, 5));
+    assert!(noisy.iter().all(|&(_, right)| right == 0.5));
+    assert!(noisy.iter().any(|&(left, _)| left != 0.5));
+}
+
+#[test]
+fn test_precision_converges_to_inverse_noise_variance() {
+    // Readings stop at 0 and 1, which trims a little of the noise at 0.5 ± 2σ
+    let std = 0.25;
+    let truth = 1.0 / (std * std);
+    let (left, right) = learned_precision(SensorNoise::symmetric(white(std)));
+    for learned in [left, right] {
+        assert!(
+            (learned - truth).abs() < 0.1 * truth,
+            "learned {learned}, true {truth}"
+        );
+    }
+
+    // Unequal channels are told apart
+    let (sharp, blurred) = learned_precision(SensorNoise {
+        left: white(std),
+        right: white(2.0 * std),
+    });
+    assert!((sharp - truth).abs() < 0.1 * truth);
+    assert!(blurred < 0.5 * sharp, "{sharp} vs {blurred}");
+
+    // Without noise the errors vanish and precision saturates
+    let (exact, _) = learned_precision(SensorNoise::default());
+    assert!((exact - MAX_SENSORY_PRECISION).abs() < 1e-9);
+}
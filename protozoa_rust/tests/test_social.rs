@@ -0,0 +1,207 @@
+//! Tests for agent-to-agent observation and social preferences.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::inference::{GenerativeModel, social_risk};
+use protozoa_rust::simulation::params::{SOCIAL_RADIUS, SOCIAL_TARGET_DENSITY};
+use protozoa_rust::simulation::population::Population;
+use protozoa_rust::simulation::provenance::Term;
+use protozoa_rust::simulation::social::{SocialMode, SocialObservation, SocialPreference, observe};
+use protozoa_rust::simulation::world::World;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Mean distance between every pair of agents.
+fn mean_spacing(agents: &[Protozoa]) -> f64 {
+    let mut total = 0.0;
+    let mut pairs = 0.0;
+    for (i, a) in agents.iter().enumerate() {
+        for b in &agents[i + 1..] {
+            total += (a.x - b.x).hypot(a.y - b.y);
+            pairs += 1.0;
+        }
+    }
+    total / pairs
+}
+
+#[test]
+fn test_social_modes_parse_and_default_is_off() {
+    for mode in SocialMode::ALL {
+        assert_eq!(SocialMode::parse(mode.name()), Some(mode));
+        assert!(!mode.preference().is_off());
+    }
+    assert_eq!(SocialMode::parse("School"), Some(SocialMode::School));
+    assert_eq!(SocialMode::parse("flock"), None);
+    assert_eq!(SocialMode::Avoid.preference().density, 0.0);
+    assert_eq!(
+        SocialMode::Aggregate.preference().density,
+        SOCIAL_TARGET_DENSITY
+    );
+    assert_eq!(SocialMode::Aggregate.preference().alignment, 0.0);
+    assert!(SocialMode::School.preference().alignment > 0.0);
+
+    let agent = Protozoa::new(50.0, 25.0);
+    assert!(agent.generative_model.social.is_off());
+    assert_eq!(agent.social, SocialObservation::default());
+    assert_eq!(agent.social_steering(), 0.0);
+}
+
+#[test]
+fn test_observation_sides_density_and_heading() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut left = Protozoa::with_heading(50.0, 25.0 + SOCIAL_RADIUS / 2.0, FRAC_PI_2);
+    let mut right = Protozoa::with_heading(50.0, 25.0 - SOCIAL_RADIUS / 4.0, FRAC_PI_2);
+    let mut far = Protozoa::with_heading(50.0 + SOCIAL_RADIUS * 2.0, 25.0, PI);
+    let mut dead = Protozoa::with_heading(51.0, 25.0, PI);
+    dead.died_at = Some(1);
+    observe(
+        &dish,
+        [&mut agent, &mut left, &mut right, &mut far, &mut dead],
+    );
+
+    let social = agent.social;
+    assert_eq!(social.neighbors, 2, "out of range and dead agents unseen");
+    assert!((social.left - 0.5).abs() < 1e-12);
+    assert!((social.right - 0.75).abs() < 1e-12, "closer weighs more");
+    assert!((social.density() - 1.25).abs() < 1e-12);
+    assert!((social.heading.unwrap() - FRAC_PI_2).abs() < 1e-12);
+
+    assert_eq!(left.social.neighbors, 2);
+    assert_eq!(right.social.neighbors, 2);
+    assert_eq!(far.social, SocialObservation::default());
+    assert_eq!(dead.social, SocialObservation::default());
+
+    // Opposite headings cancel out
+    let mut a = Protozoa::with_heading(20.0, 20.0, 0.0);
+    let mut b = Protozoa::with_heading(21.0, 20.0, 0.0);
+    let mut c = Protozoa::with_heading(19.0, 20.0, PI);
+    observe(&dish, [&mut a, &mut b, &mut c]);
+    assert_eq!(a.social.heading, None);
+    assert_eq!(a.social.neighbors, 2);
+
+    // A coincident agent is on neither side: it counts half on each
+    let mut d = Protozoa::with_heading(80.0, 30.0, 1.0);
+    let mut e = Protozoa::with_heading(80.0, 30.0, 2.0);
+    observe(&dish, [&mut d, &mut e]);
+    assert_eq!((d.social.left, d.social.right), (0.5, 0.5));
+    assert_eq!(d.social.heading, Some(2.0));
+}
+
+#[test]
+fn test_population_step_refreshes_observations() {
+    let mut world = World::single(PetriDish::empty(100.0, 50.0, 2));
+    let mut population = Population::single(Protozoa::with_heading(50.0, 25.0, 0.0));
+    population.agents[0].seed_streams(0);
+    population.step(&mut world);
+    assert_eq!(population.agents[0].social.neighbors, 0);
+
+    let mut other = Protozoa::with_heading(52.0, 25.0, 0.0);
+    other.seed_streams(1);
+    population.add(other, None);
+    population.step(&mut world);
+    for agent in &population.agents {
+        assert_eq!(agent.social.neighbors, 1);
+        assert!(agent.social.density() > 0.0);
+    }
+
+    population.agents[1].died_at = Some(population.tick);
+    population.step(&mut world);
+    assert_eq!(population.agents[0].social.neighbors, 0);
+}
+
+#[test]
+fn test_social_risk_measures_the_preference() {
+    let mut model = GenerativeModel::new();
+    let crowded = SocialObservation {
+        left: 1.5,
+        right: 1.5,
+        heading: Some(PI),
+        neighbors: 4,
+    };
+    assert_eq!(social_risk(&crowded, 0.0, &model), 0.0, "off by default");
+
+    model.social = SocialMode::Avoid.preference();
+    let precision = model.social.precision;
+    assert!((social_risk(&crowded, 0.0, &model) - 0.5 * precision * 9.0).abs() < 1e-12);
+    assert_eq!(social_risk(&SocialObservation::default(), 0.0, &model), 0.0);
+
+    model.social = SocialPreference {
+        density: 3.0,
+        precision: 1.0,
+        alignment: 2.0,
+    };
+    assert!(social_risk(&crowded, PI, &model).abs() < 1e-12, "aligned");
+    assert!((social_risk(&crowded, 0.0, &model) - 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_social_steering_follows_the_preference() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.social = SocialObservation {
+        left: 1.0,
+        right: 0.0,
+        heading: Some(-FRAC_PI_2),
+        neighbors: 1,
+    };
+    assert_eq!(agent.social_steering(), 0.0);
+
+    agent.generative_model.social = SocialMode::Aggregate.preference();
+    assert!(agent.social_steering() > 0.0, "toward the company");
+    agent.generative_model.social = SocialMode::Avoid.preference();
+    assert!(agent.social_steering() < 0.0, "away from it");
+
+    // Schooling also turns toward the neighbors' heading
+    agent.generative_model.social = SocialPreference {
+        alignment: 1.0,
+        ..SocialPreference::default()
+    };
+    assert!(agent.social_steering() < 0.0);
+    agent.social.heading = Some(0.0);
+    assert!(agent.social_steering().abs() < 1e-12);
+}
+
+#[test]
+fn test_social_term_is_recorded_in_provenance() {
+    let dish = PetriDish::empty(100.0, 50.0, 3);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.generative_model.social = SocialMode::Avoid.preference();
+    agent.social = SocialObservation {
+        left: 2.0,
+        right: 0.0,
+        heading: None,
+        neighbors: 3,
+    };
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    let term = agent.provenance.terms[Term::Social as usize];
+    assert!((term - agent.social_steering()).abs() < 1e-12);
+    assert!(term < 0.0);
+    assert_eq!(Term::Social.name(), "social");
+}
+
+#[test]
+fn test_avoiding_agents_spread_out_more_than_aggregating_ones() {
+    let spacing = |mode: SocialMode| {
+        let mut world = World::single(PetriDish::empty(100.0, 50.0, 4));
+        let mut population = Population::single(Protozoa::with_heading(50.0, 25.0, 0.0));
+        for i in 1..6 {
+            let angle = f64::from(i);
+            population.add(
+                Protozoa::with_heading(50.0 + angle.cos(), 25.0 + angle.sin(), angle),
+                None,
+            );
+        }
+        for (i, agent) in (0..).zip(&mut population.agents) {
+            agent.seed_streams(i);
+            agent.generative_model.social = mode.preference();
+        }
+        for _ in 0..200 {
+            population.step(&mut world);
+        }
+        mean_spacing(&population.agents)
+    };
+    let avoiding = spacing(SocialMode::Avoid);
+    let aggregating = spacing(SocialMode::Aggregate);
+    assert!(avoiding > aggregating, "{avoiding} vs {aggregating}");
+}
@@ -0,0 +1,107 @@
+//! Tests for the spatial index over nutrient sources.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::{NutrientSource, Obstacle, PetriDish};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::kernel::Kernel;
+use protozoa_rust::simulation::params::{SOURCE_INDEX_CELL, SOURCE_INDEX_CUTOFF};
+use protozoa_rust::simulation::source_index::SourceIndex;
+
+fn source(x: f64, y: f64, radius: f64) -> NutrientSource {
+    NutrientSource {
+        x,
+        y,
+        radius,
+        intensity: 1.0,
+        decay_rate: 1.0,
+        species: 0,
+        transient: false,
+        kernel: Kernel::Gaussian,
+    }
+}
+
+fn crowded_dish(seed: u64) -> PetriDish {
+    let config = GeneratorConfig {
+        dish: DishConfig {
+            width: 400.0,
+            height: 200.0,
+            source_count_min: 60,
+            source_count_max: 60,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    PetriDish::generate(seed, &config)
+}
+
+#[test]
+fn test_indexed_matches_brute_force() {
+    for dish in [
+        PetriDish::generate(3, &GeneratorConfig::default()),
+        crowded_dish(5),
+    ] {
+        let index = dish.source_index();
+        // Cutoff error bound: every source may lose exp(-cutoff²/2) of its peak
+        let tolerance = dish.sources.len() as f64 * (-SOURCE_INDEX_CUTOFF.powi(2) / 2.0).exp();
+        for i in 0..=40 {
+            for j in 0..=20 {
+                let (x, y) = (
+                    f64::from(i) * dish.width / 40.0,
+                    f64::from(j) * dish.height / 20.0,
+                );
+                let exact = dish.get_concentration(x, y);
+                let fast = dish.get_concentration_indexed(&index, x, y);
+                assert!(fast <= exact + 1e-12);
+                assert!(exact - fast <= tolerance, "({x}, {y}): {exact} vs {fast}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_sources_bucketed_by_reach() {
+    let sources = vec![source(5.0, 5.0, 1.0), source(95.0, 45.0, 1.0)];
+    let index = SourceIndex::build(&sources, 100.0, 50.0);
+    assert_eq!(index.candidates(4.0, 4.0), 1);
+    assert_eq!(index.candidates(50.0, 25.0), 0, "far from both");
+    assert_eq!(index.candidates(99.0, 49.0), 1);
+
+    // A wide source reaches every cell within its cutoff
+    let wide = vec![source(50.0, 25.0, 4.0)];
+    let index = SourceIndex::build(&wide, 100.0, 50.0);
+    let reach = SOURCE_INDEX_CUTOFF * 4.0;
+    assert_eq!(index.candidates(50.0 - reach + 0.1, 25.0), 1);
+    assert_eq!(index.candidates(50.0 - reach - SOURCE_INDEX_CELL, 25.0), 0);
+    assert_eq!(index.near(&wide, 50.0 + reach + 0.1, 25.0).count(), 0);
+    assert_eq!(index.near(&wide, 50.0 + reach - 0.1, 25.0).count(), 1);
+}
+
+#[test]
+fn test_indexed_query_semantics() {
+    let mut dish = PetriDish::new(100.0, 50.0);
+    dish.sources = vec![source(50.0, 25.0, 5.0)];
+    dish.obstacles.push(Obstacle {
+        x_min: 0.0,
+        y_min: 0.0,
+        x_max: 10.0,
+        y_max: 10.0,
+    });
+    let index = dish.source_index();
+    assert!((dish.get_concentration_indexed(&index, -1.0, 25.0) + 1.0).abs() < 1e-12);
+    assert!((dish.get_concentration_indexed(&index, 5.0, 5.0) + 1.0).abs() < 1e-12);
+    assert!(
+        (dish.get_concentration_indexed(&index, 50.0, 25.0) - dish.get_concentration(50.0, 25.0))
+            .abs()
+            < 1e-12
+    );
+
+    // A stale index skips sources that no longer exist instead of panicking
+    dish.sources.clear();
+    assert!(dish.get_concentration_indexed(&index, 50.0, 25.0).abs() < 1e-12);
+    assert!(
+        SourceIndex::default()
+            .near(&dish.sources, 1.0, 1.0)
+            .next()
+            .is_none()
+    );
+}
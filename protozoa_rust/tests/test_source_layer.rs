@@ -0,0 +1,111 @@
+//! Tests for the per-source dish layer (centers and radius rings).
+#![cfg(feature = "tui")]
+
+use protozoa_rust::simulation::environment::{NutrientSource, Obstacle, PetriDish};
+use protozoa_rust::simulation::kernel::Kernel;
+use protozoa_rust::ui::DishLayer;
+use protozoa_rust::ui::field::{
+    OBSTACLE_GLYPH, SOURCE_RING_GLYPH, compute_field_grid, overlay_sources, source_center_glyph,
+};
+
+fn source(x: f64, y: f64, radius: f64, intensity: f64) -> NutrientSource {
+    NutrientSource {
+        x,
+        y,
+        radius,
+        intensity,
+        decay_rate: 1.0,
+        species: 0,
+        transient: false,
+        kernel: Kernel::Gaussian,
+    }
+}
+
+/// A 100 x 50 dish drawn one world unit per column and row.
+fn dish_with(sources: Vec<NutrientSource>) -> PetriDish {
+    let mut dish = PetriDish::new(100.0, 50.0);
+    dish.sources = sources;
+    dish
+}
+
+fn glyph(grid: &[String], row: usize, col: usize) -> char {
+    grid[row].chars().nth(col).unwrap()
+}
+
+#[test]
+fn test_center_brightness_follows_intensity() {
+    assert_eq!(source_center_glyph(0.0), '1', "a spent source still shows");
+    assert_eq!(source_center_glyph(0.5), '5');
+    assert_eq!(source_center_glyph(1.0), '9');
+    assert_eq!(source_center_glyph(2.0), '9', "pulses saturate");
+    assert_eq!(source_center_glyph(f64::NAN), '1');
+}
+
+#[test]
+fn test_sources_are_marked_with_center_and_ring() {
+    let dish = dish_with(vec![source(50.5, 25.5, 8.0, 0.7)]);
+    let mut grid = vec![" ".repeat(100); 50];
+    overlay_sources(&mut grid, &dish);
+    assert_eq!(glyph(&grid, 25, 50), '6');
+    for (row, col) in [(25, 58), (25, 42), (33, 50), (17, 50)] {
+        assert_eq!(glyph(&grid, row, col), SOURCE_RING_GLYPH, "({row}, {col})");
+    }
+    // Inside and outside the ring the grid is untouched
+    assert_eq!(glyph(&grid, 25, 54), ' ');
+    assert_eq!(glyph(&grid, 25, 62), ' ');
+    assert!(grid.iter().all(|line| line.chars().count() == 100));
+}
+
+#[test]
+fn test_ring_grows_with_the_spread() {
+    let ring_cols = |radius: f64| {
+        let dish = dish_with(vec![source(50.5, 25.5, radius, 1.0)]);
+        let mut grid = vec![" ".repeat(100); 50];
+        overlay_sources(&mut grid, &dish);
+        grid[25]
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c == SOURCE_RING_GLYPH)
+            .map(|(col, _)| col)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ring_cols(5.0), vec![45, 55]);
+    assert_eq!(ring_cols(15.0), vec![35, 65]);
+}
+
+#[test]
+fn test_overlay_keeps_obstacles_and_clips_to_the_grid() {
+    let mut dish = dish_with(vec![source(2.0, 2.0, 10.0, 1.0)]);
+    dish.obstacles.push(Obstacle {
+        x_min: 10.0,
+        y_min: 0.0,
+        x_max: 14.0,
+        y_max: 50.0,
+    });
+    let mut grid = compute_field_grid(&dish, 50, 100);
+    overlay_sources(&mut grid, &dish);
+    assert_eq!(
+        glyph(&grid, 2, 12),
+        OBSTACLE_GLYPH,
+        "the ring passes behind"
+    );
+    assert_eq!(glyph(&grid, 2, 2), '9');
+    assert!(grid.iter().all(|line| line.chars().count() == 100));
+
+    let mut empty: Vec<String> = Vec::new();
+    overlay_sources(&mut empty, &dish);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_sources_layer_is_in_the_cycle() {
+    assert_eq!(DishLayer::Sources.label(), "Sources");
+    let mut layer = DishLayer::default();
+    let mut seen = Vec::new();
+    for _ in 0..10 {
+        layer = layer.next();
+        seen.push(layer);
+    }
+    assert!(seen.contains(&DishLayer::Sources));
+    assert_eq!(DishLayer::Sources.for_swarm(1000), DishLayer::Sources);
+}
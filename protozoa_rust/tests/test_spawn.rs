@@ -0,0 +1,168 @@
+//! Tests for configurable agent start distributions.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::{Environment, PetriDish};
+use protozoa_rust::simulation::events::DishEvent;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use protozoa_rust::simulation::planning::{Action, AgentState};
+use protozoa_rust::simulation::spawn::{SpawnConfig, StartOrientation, StartPosition};
+use std::f64::consts::PI;
+
+#[test]
+fn test_default_spawn_matches_historical_start() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let agent = SpawnConfig::default().spawn(&dish, &mut rand::rng());
+
+    assert!((agent.x - DISH_WIDTH / 2.0).abs() < 1e-10);
+    assert!((agent.y - DISH_HEIGHT / 2.0).abs() < 1e-10);
+    assert!((agent.energy - 1.0).abs() < 1e-10);
+    assert!(agent.angle >= 0.0 && agent.angle < 2.0 * PI);
+}
+
+#[test]
+fn test_fixed_position_and_orientation() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let config = SpawnConfig {
+        position: StartPosition::Fixed { x: 12.0, y: 34.0 },
+        orientation: StartOrientation::Fixed(-PI / 2.0),
+        ..SpawnConfig::default()
+    };
+    let agent = config.spawn(&dish, &mut rand::rng());
+
+    assert!((agent.x - 12.0).abs() < 1e-10);
+    assert!((agent.y - 34.0).abs() < 1e-10);
+    // Heading is normalized into [0, 2π)
+    assert!((agent.angle - 1.5 * PI).abs() < 1e-10);
+    assert!((agent.beliefs.mean.angle - agent.angle).abs() < 1e-10);
+}
+
+#[test]
+fn test_uniform_positions_stay_inside_dish() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let config = SpawnConfig {
+        position: StartPosition::Uniform,
+        ..SpawnConfig::default()
+    };
+    let mut rng = rand::rng();
+
+    for _ in 0..200 {
+        let (x, y) = config.sample_position(&dish, &mut rng);
+        assert!((0.0..=DISH_WIDTH).contains(&x));
+        assert!((0.0..=DISH_HEIGHT).contains(&y));
+    }
+}
+
+#[test]
+fn test_ring_positions_lie_on_circle() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let config = SpawnConfig {
+        position: StartPosition::Ring { radius: 10.0 },
+        ..SpawnConfig::default()
+    };
+    let mut rng = rand::rng();
+
+    for _ in 0..50 {
+        let (x, y) = config.sample_position(&dish, &mut rng);
+        let r = (x - DISH_WIDTH / 2.0).hypot(y - DISH_HEIGHT / 2.0);
+        assert!((r - 10.0).abs() < 1e-9, "radius {r}");
+    }
+}
+
+#[test]
+fn test_near_source_within_distance() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let config = SpawnConfig {
+        position: StartPosition::NearSource { max_dist: 3.0 },
+        ..SpawnConfig::default()
+    };
+    let mut rng = rand::rng();
+
+    for _ in 0..50 {
+        let (x, y) = config.sample_position(&dish, &mut rng);
+        let nearest = dish
+            .sources
+            .iter()
+            .map(|s| (s.x - x).hypot(s.y - y))
+            .fold(f64::INFINITY, f64::min);
+        assert!(nearest <= 3.0 + 1e-9, "nearest source at {nearest}");
+    }
+}
+
+#[test]
+fn test_far_from_sources_respects_min_distance_when_feasible() {
+    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    // Single source in a corner leaves plenty of room
+    dish.sources.truncate(1);
+    dish.inject(DishEvent::MoveSource {
+        index: 0,
+        x: 10.0,
+        y: 10.0,
+    })
+    .unwrap();
+
+    let config = SpawnConfig {
+        position: StartPosition::FarFromSources { min_dist: 30.0 },
+        ..SpawnConfig::default()
+    };
+    let mut rng = rand::rng();
+
+    for _ in 0..50 {
+        let (x, y) = config.sample_position(&dish, &mut rng);
+        assert!((x - 10.0).hypot(y - 10.0) >= 30.0);
+    }
+}
+
+#[test]
+fn test_toward_center_orientation() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let config = SpawnConfig {
+        position: StartPosition::Fixed {
+            x: 10.0,
+            y: DISH_HEIGHT / 2.0,
+        },
+        orientation: StartOrientation::TowardCenter,
+        ..SpawnConfig::default()
+    };
+    let agent = config.spawn(&dish, &mut rand::rng());
+
+    // Left of center, so facing +x
+    assert!(agent.angle.abs() < 1e-10);
+}
+
+#[test]
+fn test_energy_range_sampled_and_clamped() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let config = SpawnConfig {
+        energy_min: 0.3,
+        energy_max: 0.6,
+        ..SpawnConfig::default()
+    };
+    let mut rng = rand::rng();
+
+    for _ in 0..50 {
+        let agent = config.spawn(&dish, &mut rng);
+        assert!((0.3..=0.6).contains(&agent.energy));
+    }
+
+    let out_of_range = SpawnConfig {
+        energy_min: 1.5,
+        energy_max: 2.0,
+        ..SpawnConfig::default()
+    };
+    assert!((out_of_range.sample_energy(&mut rng) - 1.0).abs() < 1e-10);
+}
+
+#[test]
+fn test_maps_cover_a_config_sized_dish() {
+    let dish = PetriDish::from_config(&DishConfig::sized(200.0, 100.0), 1);
+    let agent = SpawnConfig::default().spawn(&dish, &mut rand::rng());
+
+    assert!((agent.x - 100.0).abs() < 1e-10 && (agent.y - 50.0).abs() < 1e-10);
+    assert_eq!(agent.spatial_priors.bounds(), (200.0, 100.0));
+    assert_eq!(agent.threat_priors.world_dimensions(), (200.0, 100.0));
+    assert_eq!(agent.habits.world_dimensions(), (200.0, 100.0));
+    // Rollouts in the far quarter of the dish are not clamped back
+    let state = AgentState::new(150.0, 75.0, 0.0, 1.0, 1.0);
+    let next = state.step(Action::Straight, &agent.spatial_priors);
+    assert!(next.x > 150.0 && (next.y - 75.0).abs() < 1e-9);
+}
@@ -0,0 +1,115 @@
+//! Tests for multiple nutrient species with distinct preferences.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{PetriDish, SpeciesLayout};
+use protozoa_rust::simulation::inference::{GenerativeModel, species_risk};
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+
+#[test]
+fn test_default_dish_has_single_species() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    assert_eq!(dish.species_count, 1);
+    assert!(dish.sources.iter().all(|s| s.species == 0));
+
+    // Single-species concentration equals the total
+    let (x, y) = (DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    let total = dish.get_concentration(x, y);
+    let species = dish.get_species_concentration(0, x, y);
+    assert!((total - species).abs() < 1e-12);
+}
+
+#[test]
+fn test_every_species_gets_sources() {
+    let dish = PetriDish::with_species(DISH_WIDTH, DISH_HEIGHT, 3, SpeciesLayout::Mixed, 1);
+    assert_eq!(dish.species_count, 3);
+    for k in 0..3 {
+        assert!(dish.sources.iter().any(|s| s.species == k));
+    }
+    assert_eq!(dish.species_concentrations(10.0, 10.0).len(), 3);
+    assert!(
+        dish.species_concentrations(-1.0, 10.0)
+            .iter()
+            .all(|&c| (c + 1.0).abs() < 1e-12)
+    );
+}
+
+#[test]
+fn test_segregated_layout_is_anti_correlated_and_survives_respawn() {
+    let mut dish =
+        PetriDish::with_species(DISH_WIDTH, DISH_HEIGHT, 2, SpeciesLayout::Segregated, 1);
+    let half = DISH_WIDTH / 2.0;
+
+    for source in &dish.sources {
+        if source.species == 0 {
+            assert!(source.x <= half);
+        } else {
+            assert!(source.x >= half);
+        }
+    }
+
+    // Force every source to respawn and check bands still hold
+    for source in &mut dish.sources {
+        source.intensity = 0.0;
+    }
+    dish.flow.strength = 0.0;
+    dish.flow.vorticity = 0.0;
+    dish.update();
+
+    for source in &dish.sources {
+        if source.species == 0 {
+            assert!(source.x <= half);
+        } else {
+            assert!(source.x >= half);
+        }
+    }
+}
+
+#[test]
+fn test_agent_senses_each_species() {
+    let dish = PetriDish::with_species(DISH_WIDTH, DISH_HEIGHT, 2, SpeciesLayout::Mixed, 1);
+    let mut agent = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    agent.sense(&dish);
+
+    assert_eq!(agent.species_l.len(), 2);
+    assert_eq!(agent.species_r.len(), 2);
+    for &v in agent.species_l.iter().chain(&agent.species_r) {
+        assert!((-1.0..=1.0).contains(&v));
+    }
+}
+
+#[test]
+fn test_species_risk() {
+    let model = GenerativeModel::with_species_targets(&[0.8, 0.2]);
+    assert_eq!(model.species_preferences.len(), 2);
+
+    // On target: no risk
+    let at_target = species_risk(&[0.8, 0.2], &[0.8, 0.2], &model);
+    assert!(at_target.abs() < 1e-12);
+
+    // Trading one species against the other costs risk on both
+    let off_target = species_risk(&[0.2, 0.8], &[0.2, 0.8], &model);
+    assert!(off_target > at_target);
+
+    // No preferences: no species contribution
+    let plain = GenerativeModel::new();
+    assert!(species_risk(&[0.0], &[0.0], &plain).abs() < 1e-12);
+}
+
+#[test]
+fn test_species_preferences_raise_vfe_when_unsatisfied() {
+    let dish = PetriDish::with_species(DISH_WIDTH, DISH_HEIGHT, 2, SpeciesLayout::Mixed, 1);
+
+    let mut plain = Protozoa::new(DISH_WIDTH / 2.0, DISH_HEIGHT / 2.0);
+    let mut picky = plain.clone();
+    picky.generative_model = GenerativeModel::with_species_targets(&[1.0, 1.0]);
+
+    for agent in [&mut plain, &mut picky] {
+        agent.sense(&dish);
+        agent.species_l = vec![0.0, 0.0];
+        agent.species_r = vec![0.0, 0.0];
+        agent.update_state(&dish);
+        assert!(agent.current_vfe.is_finite());
+    }
+
+    assert!(picky.current_vfe > plain.current_vfe);
+}
@@ -0,0 +1,128 @@
+//! Tests for speed as part of the action space.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::params::{INITIAL_CRUISE_SPEED, MAX_SPEED, SPEED_STEP};
+use protozoa_rust::simulation::planning::{Action, AgentState, MCTSPlanner};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+
+fn priors() -> SpatialGrid<20, 10> {
+    SpatialGrid::new(100.0, 50.0)
+}
+
+/// Details of `action` from the planner's last cycle.
+fn detail(planner: &MCTSPlanner, action: Action) -> (f64, f64) {
+    let detail = planner
+        .last_plan_details()
+        .iter()
+        .find(|d| d.action == action)
+        .unwrap();
+    (detail.pragmatic_value, detail.epistemic_value)
+}
+
+#[test]
+fn test_speed_actions_keep_the_heading() {
+    let state = AgentState::new(50.0, 25.0, 1.0, 0.6, 0.5);
+    let faster = state.step(Action::Accelerate, &priors());
+    assert_eq!(faster.angle, 1.0);
+    assert!((faster.speed - (0.6 + SPEED_STEP)).abs() < 1e-12);
+    let slower = state.step(Action::Decelerate, &priors());
+    assert!((slower.speed - (0.6 - SPEED_STEP)).abs() < 1e-12);
+
+    // A stopped agent stays put, turning on the spot, until it accelerates
+    let stopped = state.step(Action::Stop, &priors());
+    let turned = stopped.step(Action::TurnLeft, &priors());
+    assert_eq!((turned.x, turned.y, turned.speed), (state.x, state.y, 0.0));
+    assert!(turned.angle > stopped.angle);
+    assert!(turned.step(Action::Accelerate, &priors()).x != state.x);
+
+    let flat_out = AgentState::new(50.0, 25.0, 0.0, MAX_SPEED, 0.5);
+    assert_eq!(
+        flat_out.step(Action::Accelerate, &priors()).speed,
+        MAX_SPEED
+    );
+    assert!(Action::TURNS.iter().all(|a| a.next_speed(0.6) == 0.6));
+}
+
+#[test]
+fn test_stopping_saves_energy() {
+    let state = AgentState::new(50.0, 25.0, 0.0, MAX_SPEED, 0.5);
+    let moving = state.step(Action::Straight, &priors());
+    let stopped = state.step(Action::Stop, &priors());
+    assert!(stopped.energy > moving.energy);
+}
+
+#[test]
+fn test_moving_on_buys_information() {
+    // Unexplored everywhere: lingering in one cell learns about one cell
+    let state = AgentState::new(52.5, 27.5, 0.0, MAX_SPEED, 1.0);
+    let mut planner = MCTSPlanner::new();
+    planner.seed(1);
+    planner.plan(&state, &priors());
+    let (_, stop) = detail(&planner, Action::Stop);
+    let (_, go) = detail(&planner, Action::Accelerate);
+    assert!(go > stop, "{go} vs {stop}");
+}
+
+#[test]
+fn test_known_rich_ground_is_not_worth_the_swim() {
+    // Everywhere mapped and equally rich: moving only costs energy
+    let mut rich = priors();
+    for x in (0..100).step_by(5) {
+        for y in (0..50).step_by(5) {
+            for _ in 0..20 {
+                rich.update(f64::from(x), f64::from(y), 0.9);
+            }
+        }
+    }
+    let state = AgentState::new(52.5, 27.5, 0.0, 0.6, 0.5);
+    let mut planner = MCTSPlanner::new();
+    planner.seed(2);
+    planner.plan(&state, &rich);
+    let (stop, _) = detail(&planner, Action::Stop);
+    let (go, _) = detail(&planner, Action::Accelerate);
+    assert!(stop > go, "{stop} vs {go}");
+}
+
+#[test]
+fn test_agent_cruises_at_the_planned_speed() {
+    let dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+    let mut agent = Protozoa::with_heading(30.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    assert_eq!(agent.cruise_speed, INITIAL_CRUISE_SPEED);
+    let mut speeds = Vec::new();
+    for _ in 0..100 {
+        let cruise = agent.cruise_speed;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        assert_eq!(
+            agent.cruise_speed,
+            agent.planned_action.next_speed(cruise),
+            "plan step {:?}",
+            agent.planned_action
+        );
+        speeds.push(agent.cruise_speed);
+    }
+    // Speed is chosen, not pinned: the agent both speeds up and slows down
+    assert!(speeds.iter().any(|&s| s > INITIAL_CRUISE_SPEED));
+    assert!(speeds.iter().any(|&s| s < INITIAL_CRUISE_SPEED));
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_mcts_panel_names_speed_actions() {
+    use protozoa_rust::simulation::planning::{ActionDetail, PlanningBudget};
+    use protozoa_rust::ui::render::format_mcts_summary;
+
+    let stop = ActionDetail {
+        action: Action::Stop,
+        total_efe: 1.0,
+        pragmatic_value: 1.0,
+        epistemic_value: 0.0,
+        sample_trajectory: Vec::new(),
+        fresh_rollouts: 10,
+    };
+    let lines = format_mcts_summary(&[stop], PlanningBudget::FIXED, 0);
+    assert_eq!(lines[0], "Best: → (0)");
+}
@@ -0,0 +1,172 @@
+//! Tests for the time-to-starvation forecast.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::{NutrientSource, PetriDish};
+use protozoa_rust::simulation::kernel::Kernel;
+use protozoa_rust::simulation::params::{
+    MCTS_MAX_DEPTH, STARVATION_RELAXED_TICKS, STARVATION_SMOOTHING, STARVATION_URGENT_TICKS,
+};
+use protozoa_rust::simulation::planning::PlanningBudget;
+use protozoa_rust::simulation::reserve::EnergyReserve;
+use protozoa_rust::simulation::starvation::StarvationForecast;
+
+fn tick(agent: &mut Protozoa, dish: &PetriDish) {
+    agent.sense(dish);
+    agent.update_state(dish);
+}
+
+/// A forecast that has settled on `net` and `cost` per tick.
+fn settled(net: f64, cost: f64) -> StarvationForecast {
+    let mut forecast = StarvationForecast::default();
+    for _ in 0..10 {
+        forecast.observe(net, cost);
+    }
+    forecast
+}
+
+#[test]
+fn test_ticks_left_divide_the_store_by_the_rates() {
+    let none = StarvationForecast::default();
+    assert_eq!(none.ticks_left(1.0), None, "no estimate before a tick");
+    assert_eq!(none.ticks_left_fasting(1.0), None);
+
+    let declining = settled(-0.01, 0.02);
+    assert!((declining.ticks_left(1.0).unwrap() - 100.0).abs() < 1e-9);
+    assert!((declining.ticks_left_fasting(1.0).unwrap() - 50.0).abs() < 1e-9);
+    assert_eq!(declining.ticks_left(-0.5), Some(0.0));
+
+    // Breaking even or gaining never starves; fasting still would
+    let fed = settled(0.01, 0.02);
+    assert_eq!(fed.ticks_left(1.0), None);
+    assert_eq!(settled(0.0, 0.02).ticks_left(1.0), None);
+    assert!((fed.ticks_left_fasting(1.0).unwrap() - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_rates_are_smoothed() {
+    // A running mean while warming up
+    let mut forecast = StarvationForecast::default();
+    forecast.observe(-0.02, 0.02);
+    forecast.observe(0.0, 0.04);
+    assert!((forecast.net_rate + 0.01).abs() < 1e-12);
+    assert!((forecast.cost_rate - 0.03).abs() < 1e-12);
+
+    // Then a moving average: one good tick barely moves it
+    let mut forecast = settled(-0.01, 0.01);
+    for _ in 0..100 {
+        forecast.observe(-0.01, 0.01);
+    }
+    forecast.observe(0.09, 0.01);
+    assert!((forecast.net_rate - (-0.01 + STARVATION_SMOOTHING * 0.1)).abs() < 1e-12);
+    assert!(forecast.ticks_left(1.0).is_some());
+}
+
+#[test]
+fn test_urgency_ramps_between_the_horizons() {
+    let urgency = |ticks: f64| settled(-1.0 / ticks, 0.01).urgency(1.0);
+    assert_eq!(urgency(STARVATION_RELAXED_TICKS * 2.0), 0.0);
+    assert!(urgency(STARVATION_RELAXED_TICKS).abs() < 1e-9);
+    let midway = f64::midpoint(STARVATION_RELAXED_TICKS, STARVATION_URGENT_TICKS);
+    assert!((urgency(midway) - 0.5).abs() < 1e-9);
+    assert_eq!(urgency(STARVATION_URGENT_TICKS / 2.0), 1.0);
+    assert_eq!(
+        settled(0.01, 0.01).urgency(1.0),
+        0.0,
+        "gaining is never urgent"
+    );
+}
+
+#[test]
+fn test_forecast_tracks_a_starving_agent() {
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(6);
+    agent.energy = 0.4;
+    agent.reserve = EnergyReserve::NONE;
+    assert_eq!(agent.starvation_forecast(), (None, None));
+    for _ in 0..40 {
+        tick(&mut agent, &dish);
+    }
+    let (ticks, fasting) = agent.starvation_forecast();
+    let (ticks, fasting) = (ticks.expect("declining"), fasting.expect("spending"));
+    // Nothing to eat: the two estimates agree
+    assert!(
+        (ticks - fasting).abs() < 1e-6 * ticks,
+        "{ticks} vs {fasting}"
+    );
+
+    let mut elapsed = 0.0;
+    while agent.energy > 0.0 && elapsed < 10_000.0 {
+        tick(&mut agent, &dish);
+        elapsed += 1.0;
+    }
+    assert!(
+        elapsed > ticks * 0.5 && elapsed < ticks * 2.0,
+        "ran out after {elapsed} ticks, forecast {ticks}"
+    );
+}
+
+#[test]
+fn test_feeding_agent_is_not_starving() {
+    let mut dish = PetriDish::empty(100.0, 50.0, 1);
+    dish.sources.push(NutrientSource {
+        x: 50.0,
+        y: 25.0,
+        radius: 15.0,
+        intensity: 1.0,
+        decay_rate: 1.0,
+        species: 0,
+        transient: false,
+        kernel: Kernel::Gaussian,
+    });
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(6);
+    agent.energy = 0.5;
+    for _ in 0..30 {
+        tick(&mut agent, &dish);
+    }
+    let (ticks, fasting) = agent.starvation_forecast();
+    assert_eq!(ticks, None, "energy is rising");
+    assert!(fasting.is_some());
+    assert_eq!(agent.starvation.urgency(agent.stored_energy()), 0.0);
+}
+
+#[test]
+fn test_imminent_starvation_raises_the_planning_budget() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut sated = Protozoa::with_heading(50.0, 25.0, 0.0);
+    sated.seed_streams(4);
+    let mut doomed = sated.clone();
+    doomed.starvation = settled(-0.05, 0.05);
+
+    tick(&mut sated, &dish);
+    tick(&mut doomed, &dish);
+    assert!(sated.planner.budget().depth < MCTS_MAX_DEPTH);
+    assert_eq!(doomed.planner.budget(), PlanningBudget::for_pressure(1.0));
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_agent_panel_shows_the_countdown() {
+    use protozoa_rust::ui::DashboardState;
+    use protozoa_rust::ui::render::format_starvation_line;
+
+    assert_eq!(
+        format_starvation_line(Some(340.6), Some(120.0)),
+        "T-340 (120)"
+    );
+    assert_eq!(
+        format_starvation_line(None, Some(4500.0)),
+        "T-\u{221E} (4k)"
+    );
+    assert_eq!(format_starvation_line(None, None), "T-\u{221E} (\u{221E})");
+
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::new(50.0, 25.0);
+    agent.starvation = settled(-0.01, 0.02);
+    agent.energy = 0.5;
+    agent.reserve.level = 0.5;
+    let state = DashboardState::from_agent(&agent, &dish);
+    assert!((state.starvation_ticks.unwrap() - 100.0).abs() < 1e-9);
+    assert!((state.fasting_ticks.unwrap() - 50.0).abs() < 1e-9);
+}
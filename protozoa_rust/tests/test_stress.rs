@@ -0,0 +1,181 @@
+//! Tests for the stress response and the panic that emerges from it.
+
+use protozoa_rust::simulation::agent::{AgentMode, Protozoa};
+use protozoa_rust::simulation::clock::SimClock;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{
+    PANIC_THRESHOLD, STRESS_HALF_VFE, STRESS_PANIC_LEVEL, STRESS_RECOVERY_TIME, STRESS_RISE_TIME,
+};
+use protozoa_rust::simulation::provenance::Term;
+use protozoa_rust::simulation::stress::StressResponse;
+
+#[test]
+fn test_stress_is_off_by_default() {
+    let response = StressResponse::default();
+    assert!(response.is_off());
+    assert!(response.validate().is_ok());
+    assert_eq!(response.precision_factor(0.9), 1.0);
+    assert_eq!(response.noise_factor(0.9), 1.0);
+
+    let dish = PetriDish::empty(100.0, 50.0, 1);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(1);
+    agent.energy = 0.05;
+    for _ in 0..50 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+    assert_eq!(agent.stress, 0.0, "no stress kept");
+    assert_eq!(agent.stress_precision(), 1.0);
+
+    // Without a stress response panic is the falling-reading threshold
+    agent.temp_gradient = PANIC_THRESHOLD * 2.0;
+    assert!(agent.is_panicking());
+    agent.temp_gradient = 0.0;
+    assert!(!agent.is_panicking());
+}
+
+#[test]
+fn test_stress_settings_are_validated() {
+    let response = StressResponse::new(0.5, 2.0);
+    assert_eq!(response.rise_time, STRESS_RISE_TIME);
+    assert_eq!(response.recovery_time, STRESS_RECOVERY_TIME);
+    assert!(!response.is_off());
+    assert!(response.validate().is_ok());
+    assert!((response.precision_factor(0.5) - 0.75).abs() < 1e-12);
+    assert!((response.noise_factor(0.5) - 2.0).abs() < 1e-12);
+
+    let slow = StressResponse {
+        rise_time: 0.0,
+        ..response
+    };
+    for bad in [
+        StressResponse::new(1.5, 1.0),
+        StressResponse::new(-0.1, 1.0),
+        StressResponse::new(0.5, -1.0),
+        StressResponse::new(0.5, f64::INFINITY),
+        slow,
+    ] {
+        assert!(bad.validate().is_err(), "{bad:?}");
+    }
+}
+
+#[test]
+fn test_sustained_surprise_builds_stress_that_recovers_slowly() {
+    assert_eq!(StressResponse::drive(0.0), 0.0);
+    assert_eq!(StressResponse::drive(-1.0), 0.0);
+    assert!((StressResponse::drive(STRESS_HALF_VFE) - 0.5).abs() < 1e-12);
+    assert!(StressResponse::drive(100.0) < 1.0);
+
+    let response = StressResponse::new(0.5, 1.0);
+    let clock = SimClock::default();
+    // One surprising tick does little
+    let spike = response.step(0.0, 30.0, &clock);
+    assert!(spike > 0.0 && spike < 0.1);
+
+    let mut stress = 0.0;
+    let mut rise_ticks = 0;
+    while stress < STRESS_PANIC_LEVEL {
+        stress = response.step(stress, 30.0, &clock);
+        rise_ticks += 1;
+    }
+    assert!(rise_ticks > 5, "builds up over time");
+    let mut recovery_ticks = 0;
+    while stress >= STRESS_PANIC_LEVEL * 0.5 {
+        stress = response.step(stress, 0.0, &clock);
+        recovery_ticks += 1;
+    }
+    assert!(
+        recovery_ticks > rise_ticks,
+        "{recovery_ticks} vs {rise_ticks}"
+    );
+    assert!(stress > 0.0);
+
+    // The relaxation is exact: one second at rise time τ covers 1 - e^(-1/τ)
+    let target = StressResponse::drive(30.0);
+    let expected = target * (1.0 - (-1.0 / STRESS_RISE_TIME).exp());
+    assert!((spike - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_stress_scales_learned_sensory_precision() {
+    let dish = PetriDish::empty(100.0, 50.0, 2);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(2);
+    agent.morphology.stress = StressResponse::new(0.6, 0.0);
+    agent.stress = 0.8;
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    let factor = agent.stress_precision();
+    assert!(factor < 1.0);
+    assert!((factor - (1.0 - 0.6 * agent.stress)).abs() < 1e-12);
+    let model = &agent.generative_model.sensory_precision;
+    let learned = agent.precision_estimator.precision_left();
+    assert!((model.left - learned * factor).abs() < 1e-9);
+    let thermal = agent.thermal_precision_estimator.precision_left();
+    assert!((model.thermal_left - thermal * factor).abs() < 1e-9);
+}
+
+#[test]
+fn test_panic_emerges_from_stress() {
+    let dish = PetriDish::empty(100.0, 50.0, 3);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.morphology.stress = StressResponse::new(0.5, 1.0);
+
+    // A falling reading alone no longer panics a stress-prone agent
+    agent.temp_gradient = PANIC_THRESHOLD * 2.0;
+    assert!(!agent.is_panicking());
+    agent.stress = STRESS_PANIC_LEVEL;
+    agent.temp_gradient = 0.0;
+    assert!(agent.is_panicking());
+    assert_eq!(agent.current_mode(&dish), AgentMode::Panicking);
+
+    // A hungry agent that minds its hunger, in an empty dish, is surprised
+    // until it panics
+    let mut hungry = Protozoa::with_heading(50.0, 25.0, 0.0);
+    hungry.seed_streams(4);
+    hungry.morphology.stress = StressResponse::new(0.5, 1.0);
+    hungry.generative_model.sensory_precision.interoceptive = 10.0;
+    hungry.energy = 0.1;
+    let mut panicked = false;
+    for _ in 0..300 {
+        hungry.sense(&dish);
+        hungry.update_state(&dish);
+        if hungry.is_panicking() {
+            panicked = hungry.provenance.terms[Term::Panic as usize] != 0.0;
+            break;
+        }
+    }
+    assert!(panicked, "stress {}", hungry.stress);
+}
+
+#[test]
+fn test_stress_amplifies_heading_noise() {
+    let noise = |gain: f64| {
+        let dish = PetriDish::empty(100.0, 50.0, 5);
+        let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+        agent.seed_streams(5);
+        agent.morphology.stress = StressResponse::new(0.1, gain);
+        agent.stress = 0.4;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        (agent.provenance.terms[Term::Noise as usize], agent.stress)
+    };
+    let (calm, stress) = noise(0.0);
+    let (amplified, _) = noise(2.0);
+    assert!(calm != 0.0);
+    assert!((amplified - calm * (1.0 + 2.0 * stress)).abs() < 1e-12);
+}
+
+#[test]
+fn test_stress_recovers_once_things_calm_down() {
+    let response = StressResponse::new(0.5, 1.0);
+    let clock = SimClock::default();
+    let mut stress = 0.9;
+    for _ in 0..(5.0 * STRESS_RECOVERY_TIME) as usize {
+        stress = response.step(stress, 0.0, &clock);
+    }
+    assert!(stress < 0.01);
+    assert!(response.precision_factor(stress) > 0.99);
+}
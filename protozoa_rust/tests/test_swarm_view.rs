@@ -0,0 +1,121 @@
+//! Tests for the swarm density view of large populations.
+
+use protozoa_rust::simulation::agent::Protozoa;
+#[cfg(feature = "tui")]
+use protozoa_rust::simulation::environment::{Obstacle, PetriDish};
+#[cfg(feature = "tui")]
+use protozoa_rust::simulation::params::SWARM_VIEW_THRESHOLD;
+use protozoa_rust::simulation::population::Population;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::DishLayer;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::{OBSTACLE_GLYPH, compute_density_grid};
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_nutrient_view_switches_for_a_swarm() {
+    let nutrient = DishLayer::Nutrient;
+    assert_eq!(
+        nutrient.for_swarm(SWARM_VIEW_THRESHOLD),
+        DishLayer::Nutrient
+    );
+    assert_eq!(
+        nutrient.for_swarm(SWARM_VIEW_THRESHOLD + 1),
+        DishLayer::Density
+    );
+    assert_eq!(DishLayer::Density.label(), "Agent density");
+
+    // A layer picked by hand is kept
+    for layer in [
+        DishLayer::Occupancy,
+        DishLayer::Pheromone,
+        DishLayer::Density,
+    ] {
+        assert_eq!(layer.for_swarm(1000), layer);
+    }
+    assert_eq!(DishLayer::Density.for_swarm(0), DishLayer::Density);
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_density_grid_scales_to_the_crowd() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut positions = vec![(5.0, 5.0); 9];
+    positions.push((95.0, 45.0));
+    let grid = compute_density_grid(&dish, &positions, 5, 10);
+    assert_eq!(grid.len(), 5);
+    assert!(grid.iter().all(|line| line.chars().count() == 10));
+    assert_eq!(grid[0].chars().next(), Some('@'), "the densest cell");
+    assert_eq!(
+        grid[4].chars().last(),
+        Some('.'),
+        "a lone agent still shows"
+    );
+    let blank = grid.concat().chars().filter(|&c| c == ' ').count();
+    assert_eq!(blank, 48);
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_density_grid_edges() {
+    let dish = PetriDish::new(100.0, 50.0);
+    assert!(compute_density_grid(&dish, &[], 0, 10).is_empty());
+    let empty = compute_density_grid(&dish, &[], 2, 4);
+    assert_eq!(empty, vec!["    ".to_string(); 2]);
+
+    // Positions on or past the rim land in the border cells
+    let grid = compute_density_grid(&dish, &[(100.0, 50.0), (-1.0, -1.0)], 2, 4);
+    assert_eq!(grid, vec!["@   ".to_string(), "   @".to_string()]);
+
+    let mut walled = PetriDish::new(100.0, 50.0);
+    walled.obstacles.push(Obstacle {
+        x_min: 0.0,
+        y_min: 0.0,
+        x_max: 30.0,
+        y_max: 30.0,
+    });
+    let grid = compute_density_grid(&walled, &[], 2, 4);
+    assert_eq!(grid[0].chars().next(), Some(OBSTACLE_GLYPH));
+}
+
+#[test]
+fn test_positions_in_lists_the_living_of_a_dish() {
+    let mut population = Population::single(Protozoa::with_heading(10.0, 20.0, 0.0));
+    let mut dead = Protozoa::with_heading(30.0, 20.0, 0.0);
+    dead.died_at = Some(5);
+    population.add(dead, None);
+    let mut elsewhere = Protozoa::with_heading(50.0, 20.0, 0.0);
+    elsewhere.dish_id = 1;
+    population.add(elsewhere, None);
+
+    assert_eq!(population.positions_in(0), vec![(10.0, 20.0)]);
+    assert_eq!(population.positions_in(1), vec![(50.0, 20.0)]);
+    assert!(population.positions_in(2).is_empty());
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_spawned_swarm_is_drawn_as_density() {
+    use protozoa_rust::simulation::spawn::{SpawnConfig, StartPosition};
+    use protozoa_rust::simulation::world::World;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    let world = World::single(PetriDish::new(100.0, 50.0));
+    let mut rng = StdRng::seed_from_u64(4);
+    let uniform = SpawnConfig {
+        position: StartPosition::Uniform,
+        ..SpawnConfig::default()
+    };
+    let population = Population::spawn(200, &world, &uniform, &mut rng);
+    let swarm = population.positions_in(0);
+    assert_eq!(
+        DishLayer::default().for_swarm(swarm.len()),
+        DishLayer::Density
+    );
+
+    let grid = compute_density_grid(&world.dishes[0], &swarm, 10, 20);
+    let occupied = grid.concat().chars().filter(|&c| c != ' ').count();
+    assert!(occupied > 50, "a spread swarm fills many cells: {occupied}");
+    assert!(grid.concat().contains('@'));
+}
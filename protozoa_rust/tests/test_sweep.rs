@@ -0,0 +1,104 @@
+//! Tests for parallel, resumable parameter sweeps.
+
+use protozoa_rust::simulation::annotation::MorphologyParam;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::scenario::Scenario;
+use protozoa_rust::simulation::sweep::{Sweep, run_sweep};
+use std::path::PathBuf;
+
+fn sweep(ticks: u64) -> Sweep {
+    let scenario = Scenario::Preset(EnvironmentPreset::Gradient);
+    Sweep::parse_axis(scenario, "sensor_dist=1,3", vec![0, 1, 2], ticks).unwrap()
+}
+
+fn journal(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sweep_{name}_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn test_axis_parsing_and_cell_order() {
+    let sweep = sweep(10);
+    assert_eq!(sweep.param, MorphologyParam::SensorDist);
+    assert_eq!(sweep.values, vec![1.0, 3.0]);
+    let cells = sweep.cells();
+    assert_eq!(cells.len(), 6);
+    let order: Vec<(f64, u64)> = cells.iter().map(|c| (c.command.value, c.seed)).collect();
+    assert_eq!(order[..4], [(1.0, 0), (1.0, 1), (1.0, 2), (3.0, 0)]);
+    assert!(cells.iter().enumerate().all(|(i, c)| c.index == i));
+
+    let scenario = Scenario::Preset(EnvironmentPreset::Ring);
+    assert!(Sweep::parse_axis(scenario, "sensor_dist", vec![0], 10).is_err());
+    assert!(Sweep::parse_axis(scenario, "tail_length=1", vec![0], 10).is_err());
+    assert!(Sweep::parse_axis(scenario, "ph=0.5,2", vec![0], 10).is_err());
+}
+
+#[test]
+fn test_workers_match_serial_run() {
+    let sweep = sweep(40);
+    let (serial, parallel) = (journal("serial"), journal("parallel"));
+    let one = run_sweep(&sweep, &serial, 1).unwrap();
+    let many = run_sweep(&sweep, &parallel, 4).unwrap();
+    assert_eq!((one.ran, one.resumed), (6, 0));
+    assert_eq!(one.results, many.results);
+    assert_eq!(
+        std::fs::read_to_string(&parallel).unwrap().lines().count(),
+        6
+    );
+
+    // Each cell is the seeded scenario with the value applied
+    let cell = sweep.cells()[4];
+    assert_eq!(sweep.run_cell(&cell), one.results[4]);
+    assert!((one.results[4].value - 3.0).abs() < 1e-12);
+    let _ = std::fs::remove_file(serial);
+    let _ = std::fs::remove_file(parallel);
+}
+
+#[test]
+fn test_interrupted_sweep_resumes() {
+    let sweep = sweep(40);
+    let path = journal("resume");
+    let full = run_sweep(&sweep, &path, 2).unwrap();
+
+    // Keep two finished cells and a torn third line, as after a crash
+    let text = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    let torn = format!("{}\n{}\n{}", lines[0], lines[1], &lines[2][..10]);
+    std::fs::write(&path, torn).unwrap();
+
+    let resumed = run_sweep(&sweep, &path, 3).unwrap();
+    assert_eq!((resumed.resumed, resumed.ran), (2, 4));
+    assert_eq!(resumed.results, full.results);
+    let journaled = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(journaled.lines().count(), 6);
+    assert!(journaled.ends_with('\n'));
+
+    // Nothing left to do on a finished journal
+    let again = run_sweep(&sweep, &path, 3).unwrap();
+    assert_eq!((again.resumed, again.ran), (6, 0));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_journal_of_another_sweep_is_rejected() {
+    let path = journal("mismatch");
+    run_sweep(&sweep(20), &path, 2).unwrap();
+    let err = run_sweep(&sweep(30), &path, 2).unwrap_err();
+    assert!(err.contains("different sweep"), "{err}");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_final_energy_grouped_by_value() {
+    let path = journal("groups");
+    let report = run_sweep(&sweep(20), &path, 2).unwrap();
+    let groups = report.final_energy_by_value();
+    assert_eq!(groups.len(), 2);
+    assert!((groups[0].0 - 1.0).abs() < 1e-12);
+    assert!((groups[1].0 - 3.0).abs() < 1e-12);
+    let first: Vec<f64> = report.results[..3].iter().map(|r| r.final_energy).collect();
+    let mean = first.iter().sum::<f64>() / 3.0;
+    assert!((groups[0].1.mean - mean).abs() < 1e-12);
+    let _ = std::fs::remove_file(path);
+}
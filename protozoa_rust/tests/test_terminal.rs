@@ -0,0 +1,136 @@
+//! Tests for terminal capability detection and renderer selection.
+#![cfg(feature = "tui")]
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use protozoa_rust::ui::DashboardState;
+use protozoa_rust::ui::render::draw_dashboard;
+use protozoa_rust::ui::terminal::{
+    ColorMode, GlyphSet, GraphicsProtocol, RenderConfig, RenderOverrides, TerminalCaps,
+    adapt_buffer, ascii_fallback, reduce_color,
+};
+use ratatui::style::Color;
+use ratatui::{Terminal, backend::TestBackend};
+
+fn caps(vars: &[(&str, &str)]) -> TerminalCaps {
+    TerminalCaps::from_env(|name| {
+        vars.iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| (*v).to_string())
+    })
+}
+
+#[test]
+fn test_detection_from_environment() {
+    let modern = caps(&[
+        ("TERM", "xterm-256color"),
+        ("COLORTERM", "truecolor"),
+        ("LANG", "en_US.UTF-8"),
+    ]);
+    assert!(modern.color && modern.truecolor && modern.unicode && modern.mouse);
+    assert_eq!(modern.graphics, GraphicsProtocol::None);
+
+    let basic = caps(&[("TERM", "xterm"), ("LC_ALL", "C"), ("LANG", "en_US.UTF-8")]);
+    assert!(basic.color && !basic.truecolor);
+    assert!(!basic.unicode, "LC_ALL takes precedence over LANG");
+
+    assert_eq!(caps(&[("TERM", "dumb")]), TerminalCaps::default());
+    assert_eq!(caps(&[]), TerminalCaps::default());
+    let console = caps(&[("TERM", "linux"), ("LANG", "C.UTF-8")]);
+    assert!(console.color && !console.unicode && !console.mouse);
+    assert!(!caps(&[("TERM", "xterm"), ("NO_COLOR", "1")]).color);
+
+    assert_eq!(
+        caps(&[("TERM", "xterm-kitty")]).graphics,
+        GraphicsProtocol::Kitty
+    );
+    assert_eq!(caps(&[("TERM", "foot")]).graphics, GraphicsProtocol::Sixel);
+    let windows = caps(&[("WT_SESSION", "abc")]);
+    assert!(windows.truecolor && windows.unicode && windows.mouse);
+}
+
+#[test]
+fn test_config_follows_caps_and_overrides() {
+    let plain = RenderConfig::from_caps(caps(&[("TERM", "xterm")]));
+    assert_eq!(plain.glyphs, GlyphSet::Ascii);
+    assert_eq!(plain.color, ColorMode::Ansi16);
+    assert!(plain.mouse);
+    assert_eq!(
+        RenderConfig::from_caps(TerminalCaps::default()).color,
+        ColorMode::Mono
+    );
+
+    let overrides = RenderOverrides::from_toml_str("glyphs = \"unicode\"\nmouse = false").unwrap();
+    let forced = plain.with_overrides(overrides);
+    assert_eq!(forced.glyphs, GlyphSet::Unicode);
+    assert_eq!(forced.color, ColorMode::Ansi16, "unset keys keep detection");
+    assert!(!forced.mouse);
+
+    assert!(RenderOverrides::from_toml_str("colour = \"mono\"").is_err());
+    assert!(RenderOverrides::from_toml_str("color = \"sepia\"").is_err());
+}
+
+#[test]
+fn test_load_render_config_file() {
+    let path = std::env::temp_dir().join(format!("render_{}.toml", std::process::id()));
+    std::fs::write(&path, "color = \"mono\"\n").unwrap();
+    let overrides = RenderOverrides::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(overrides.color, Some(ColorMode::Mono));
+    assert!(
+        RenderOverrides::load(&path)
+            .unwrap_err()
+            .contains("cannot read")
+    );
+}
+
+#[test]
+fn test_color_reduction() {
+    let rgb = Color::Rgb(250, 10, 10);
+    assert_eq!(reduce_color(rgb, ColorMode::TrueColor), rgb);
+    assert_eq!(reduce_color(rgb, ColorMode::Ansi16), Color::LightRed);
+    assert_eq!(
+        reduce_color(Color::Indexed(21), ColorMode::Ansi16),
+        Color::Blue
+    );
+    assert_eq!(
+        reduce_color(Color::Indexed(244), ColorMode::Ansi16),
+        Color::DarkGray
+    );
+    assert_eq!(reduce_color(Color::Green, ColorMode::Ansi16), Color::Green);
+    assert_eq!(reduce_color(Color::Green, ColorMode::Mono), Color::Reset);
+}
+
+#[test]
+fn test_ascii_frames() {
+    assert_eq!(ascii_fallback('↗'), '/');
+    assert_eq!(ascii_fallback('█'), '#');
+    assert_eq!(ascii_fallback('\u{28FF}'), '.');
+    assert_eq!(ascii_fallback('λ'), '?');
+
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let state = DashboardState::from_agent(&agent, &dish);
+    let config = RenderConfig {
+        glyphs: GlyphSet::Ascii,
+        color: ColorMode::Mono,
+        mouse: false,
+    };
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal
+        .draw(|f| {
+            draw_dashboard(f, vec!["█".repeat(40); 10], &state);
+            adapt_buffer(f.buffer_mut(), config);
+        })
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    assert!(buffer.content.iter().all(|cell| cell.symbol().is_ascii()));
+    assert!(
+        buffer
+            .content
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+    );
+    assert!(buffer.content.iter().any(|cell| cell.symbol() == "#"));
+}
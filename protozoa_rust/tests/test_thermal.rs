@@ -0,0 +1,112 @@
+//! Tests for the temperature field and thermal inference.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::inference::{
+    BeliefState, GenerativeModel, thermal_free_energy, thermal_gradient,
+};
+use protozoa_rust::simulation::params::{
+    AMBIENT_TEMPERATURE, DISH_HEIGHT, DISH_WIDTH, PREFERRED_TEMPERATURE, THERMAL_SOURCE_COUNT,
+};
+use protozoa_rust::simulation::thermal::{ThermalField, ThermalSource};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::compute_temperature_grid;
+
+fn hot_spot_dish() -> PetriDish {
+    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    dish.thermal = ThermalField {
+        ambient: 0.2,
+        sources: vec![ThermalSource {
+            x: 70.0,
+            y: 25.0,
+            radius: 15.0,
+            intensity: 0.6,
+        }],
+    };
+    dish
+}
+
+#[test]
+fn test_default_dish_has_thermal_sources() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    assert_eq!(dish.thermal.sources.len(), THERMAL_SOURCE_COUNT);
+    assert!((dish.thermal.ambient - AMBIENT_TEMPERATURE).abs() < 1e-12);
+
+    for x in [0.0, 25.0, 50.0, 75.0, 100.0] {
+        let t = dish.get_temperature(x, 25.0);
+        assert!((0.0..=1.0).contains(&t));
+    }
+    assert!((dish.get_temperature(-1.0, 25.0) + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_temperature_field_shape() {
+    let dish = hot_spot_dish();
+    assert!((dish.get_temperature(70.0, 25.0) - 0.8).abs() < 1e-12);
+    assert!(dish.get_temperature(50.0, 25.0) < dish.get_temperature(60.0, 25.0));
+
+    let uniform = ThermalField::uniform();
+    assert!((uniform.temperature_at(10.0, 10.0) - AMBIENT_TEMPERATURE).abs() < 1e-12);
+}
+
+#[test]
+fn test_thermal_free_energy_minimized_at_preference() {
+    let model = GenerativeModel::new();
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.0);
+
+    beliefs.mean.temperature = PREFERRED_TEMPERATURE;
+    let at_target = thermal_free_energy(
+        (PREFERRED_TEMPERATURE, PREFERRED_TEMPERATURE),
+        &beliefs,
+        &model,
+    );
+    assert!(at_target.abs() < 1e-12);
+
+    let too_hot = thermal_free_energy((0.9, 0.9), &beliefs, &model);
+    assert!(too_hot > at_target);
+}
+
+#[test]
+fn test_thermal_precision_weights_belief_update() {
+    let mut beliefs = BeliefState::new(50.0, 25.0, 0.0);
+    beliefs.mean.temperature = PREFERRED_TEMPERATURE;
+
+    let mut trusting = GenerativeModel::new();
+    trusting.update_thermal_precision(10.0, 10.0);
+    let mut doubtful = GenerativeModel::new();
+    doubtful.update_thermal_precision(0.5, 0.5);
+
+    // The same hot reading pulls the belief harder under high sensory precision
+    let strong = thermal_gradient((0.9, 0.9), &beliefs, &trusting);
+    let weak = thermal_gradient((0.9, 0.9), &beliefs, &doubtful);
+    assert!(strong > weak && weak > 0.0);
+}
+
+#[test]
+fn test_agent_senses_and_learns_temperature() {
+    let dish = hot_spot_dish();
+    let mut agent = Protozoa::new(70.0, 25.0);
+
+    for _ in 0..20 {
+        agent.x = 70.0;
+        agent.y = 25.0;
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+
+    assert!(agent.thermo_l > 0.7 && agent.thermo_r > 0.7);
+    // Belief moves from the neutral prior toward the hot readings
+    assert!(agent.beliefs.mean.temperature > PREFERRED_TEMPERATURE);
+    assert!(agent.thermal_precision_estimator.count() >= 20);
+    assert!(agent.current_vfe.is_finite());
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_temperature_grid_rendering() {
+    let dish = hot_spot_dish();
+    let grid = compute_temperature_grid(&dish, 10, 20);
+    assert_eq!(grid.len(), 10);
+    assert!(grid.iter().all(|line| line.chars().count() == 20));
+    assert!(compute_temperature_grid(&dish, 0, 5).is_empty());
+}
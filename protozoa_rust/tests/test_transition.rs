@@ -0,0 +1,179 @@
+//! Tests for transition model learning: the speed gain, turn gain and
+//! drift of the agent's own motion follow its proprioceived moves.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::flow::FlowField;
+use protozoa_rust::simulation::inference::{MotionSample, TransitionModel};
+use protozoa_rust::simulation::memory::SpatialGrid;
+use protozoa_rust::simulation::params::{
+    DISH_HEIGHT, DISH_WIDTH, TRANSITION_MAX_DRIFT, TRANSITION_MAX_GAIN,
+};
+use protozoa_rust::simulation::planning::{Action, AgentState};
+use std::f64::consts::PI;
+
+/// The move a body with `speed_gain`, `turn_gain` and `drift` makes on a
+/// command to turn `turn` at `speed` from `heading`.
+fn body_move(
+    (speed_gain, turn_gain, drift): (f64, f64, (f64, f64)),
+    turn: f64,
+    speed: f64,
+    heading: f64,
+) -> MotionSample {
+    let new_heading = (heading + turn_gain * turn).rem_euclid(2.0 * PI);
+    MotionSample {
+        turn,
+        speed,
+        heading,
+        new_heading,
+        dx: speed_gain * speed * new_heading.cos() + drift.0,
+        dy: speed_gain * speed * new_heading.sin() + drift.1,
+    }
+}
+
+fn dish_with_current(strength: f64, direction: f64) -> PetriDish {
+    let mut dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    dish.flow = FlowField {
+        strength,
+        direction,
+        ..FlowField::still(DISH_WIDTH, DISH_HEIGHT)
+    };
+    dish
+}
+
+#[test]
+fn test_default_is_the_commanded_motion() {
+    let model = TransitionModel::default();
+    assert!(model.is_off());
+    assert!(model.validate().is_ok());
+    assert!(TransitionModel::new(-1.0).validate().is_err());
+    assert!(TransitionModel::new(f64::INFINITY).validate().is_err());
+    assert_eq!(model.turn(0.3), 0.3);
+    let (dx, dy) = model.displacement(1.2, 0.4);
+    assert_eq!((dx, dy), (1.2 * 0.4_f64.cos(), 1.2 * 0.4_f64.sin()));
+}
+
+#[test]
+fn test_fixed_model_never_moves() {
+    let mut model = TransitionModel::default();
+    model.learn(&body_move((0.5, 0.5, (0.2, 0.0)), 0.4, 1.0, 0.0));
+    assert_eq!(model, TransitionModel::default());
+}
+
+#[test]
+fn test_learns_a_sluggish_drifting_body() {
+    let body = (0.6, 0.7, (0.1, -0.05));
+    let mut model = TransitionModel::new(0.05);
+    let mut heading = 0.0;
+    for i in 0..4000 {
+        #[allow(clippy::cast_precision_loss)]
+        let phase = i as f64;
+        // Turning steadily left, the body heads every way over the run
+        let turn = 0.3 + 0.4 * (phase * 0.91).sin();
+        let speed = 0.75 + 0.5 * (phase * 0.37).cos();
+        let sample = body_move(body, turn, speed, heading);
+        model.learn(&sample);
+        heading = sample.new_heading;
+    }
+    assert!((model.speed_gain - 0.6).abs() < 0.02, "{model:?}");
+    assert!((model.turn_gain - 0.7).abs() < 0.02, "{model:?}");
+    assert!((model.drift_x - 0.1).abs() < 0.02, "{model:?}");
+    assert!((model.drift_y + 0.05).abs() < 0.02, "{model:?}");
+    assert_eq!(model.updates, 4000);
+}
+
+#[test]
+fn test_heading_errors_wrap() {
+    // Turning left across 0 is a small turn, not nearly a full circle
+    let mut model = TransitionModel::new(0.5);
+    let sample = MotionSample {
+        turn: 0.2,
+        speed: 0.0,
+        heading: 2.0 * PI - 0.1,
+        new_heading: 0.1,
+        dx: 0.0,
+        dy: 0.0,
+    };
+    model.learn(&sample);
+    assert!((model.turn_gain - 1.0).abs() < 1e-9, "{model:?}");
+}
+
+#[test]
+fn test_learned_model_stays_bounded() {
+    let mut model = TransitionModel::new(1.0);
+    for _ in 0..50 {
+        model.learn(&body_move((10.0, 10.0, (5.0, 5.0)), 0.3, 1.0, 0.0));
+    }
+    assert_eq!(model.speed_gain, TRANSITION_MAX_GAIN);
+    assert_eq!(model.turn_gain, TRANSITION_MAX_GAIN);
+    assert!(model.drift_x.hypot(model.drift_y) <= TRANSITION_MAX_DRIFT + 1e-12);
+    // A body that turns and swims backwards gets no negative gains
+    let mut model = TransitionModel::new(1.0);
+    for _ in 0..50 {
+        model.learn(&body_move((-1.0, -1.0, (0.0, 0.0)), 0.3, 1.0, 0.0));
+    }
+    assert_eq!(model.speed_gain, 0.0);
+    assert_eq!(model.turn_gain, 0.0);
+}
+
+#[test]
+fn test_rollouts_move_as_the_model_predicts() {
+    let priors: SpatialGrid<20, 10> = SpatialGrid::new(DISH_WIDTH, DISH_HEIGHT);
+    let state = AgentState::new(50.0, 25.0, 0.0, 1.0, 1.0);
+    // The default model is the plain rollout
+    let plain = state.step(Action::TurnLeft, &priors);
+    let same = state
+        .with_transition(TransitionModel::new(0.0))
+        .step(Action::TurnLeft, &priors);
+    assert_eq!(
+        (plain.x, plain.y, plain.angle),
+        (same.x, same.y, same.angle)
+    );
+
+    let learned = TransitionModel {
+        speed_gain: 0.5,
+        turn_gain: 0.5,
+        drift_x: 0.0,
+        drift_y: 0.3,
+        ..TransitionModel::new(0.05)
+    };
+    let next = state
+        .with_transition(learned)
+        .step(Action::TurnLeft, &priors);
+    assert!((next.angle - plain.angle / 2.0).abs() < 1e-12);
+    let (dx, dy) = learned.displacement(plain.motion.speed, next.angle);
+    assert!((next.x - 50.0 - dx).abs() < 1e-12 && (next.y - 25.0 - dy).abs() < 1e-12);
+}
+
+#[test]
+fn test_agent_learns_the_current() {
+    let dish = dish_with_current(0.2, PI / 2.0);
+    let make = |rate: f64| {
+        let mut agent = Protozoa::with_heading(50.0, 10.0, 0.0);
+        agent.seed_streams(5);
+        agent.generative_model.transition = TransitionModel::new(rate);
+        agent
+    };
+    let mut fixed = make(0.0);
+    let mut default = Protozoa::with_heading(50.0, 10.0, 0.0);
+    default.seed_streams(5);
+    let mut learning = make(0.05);
+    for _ in 0..80 {
+        for agent in [&mut fixed, &mut default, &mut learning] {
+            agent.sense(&dish);
+            agent.update_state(&dish);
+        }
+    }
+    // Without learning the run is the default one
+    assert_eq!(
+        (fixed.x, fixed.y, fixed.angle),
+        (default.x, default.y, default.angle)
+    );
+    let model = learning.generative_model.transition;
+    assert!(model.updates > 0);
+    assert!(model.drift_y > 0.1, "{model:?}");
+    assert!(model.drift_x.abs() < model.drift_y, "{model:?}");
+    // The planner's rollouts start from the learned model
+    assert_eq!(learning.rollout_state().transition, model);
+    assert!(learning.check_finite(&learning.tick_context()).is_ok());
+}
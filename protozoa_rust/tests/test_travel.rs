@@ -0,0 +1,165 @@
+//! Tests for energy-aware landmark selection in goal navigation.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::arrival::ArrivalTarget;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::memory::{EpisodicMemory, Landmark, TravelModel};
+use protozoa_rust::simulation::params::{
+    BASE_METABOLIC_COST, INITIAL_CRUISE_SPEED, LANDMARK_MIN_ARRIVAL, MAX_SPEED,
+    SPEED_METABOLIC_COST,
+};
+use protozoa_rust::simulation::reserve::EnergyReserve;
+
+/// Trips costing 0.002 energy per unit of distance.
+fn travel(stored: f64) -> TravelModel {
+    TravelModel {
+        stored,
+        cost_per_distance: 0.002,
+    }
+}
+
+/// A rich landmark 90 units east and a modest one 15 units west of (50, 25).
+fn rich_far_modest_near() -> EpisodicMemory {
+    let mut memory = EpisodicMemory::new();
+    memory.maybe_store(140.0, 25.0, 1.0, 0);
+    memory.maybe_store(35.0, 25.0, 0.75, 0);
+    memory
+}
+
+/// A starving agent at (50, 25) with no reserve.
+fn starving() -> Protozoa {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(3);
+    agent.energy = 0.1;
+    agent.reserve = EnergyReserve::NONE;
+    agent
+}
+
+#[test]
+fn test_arrival_probability_falls_with_distance() {
+    let model = travel(0.2);
+    assert!((model.cost(50.0) - 0.1).abs() < 1e-12);
+    assert!(model.arrival_probability(10.0) > 0.95);
+    // Even odds where the trip costs everything stored
+    assert!((model.arrival_probability(100.0) - 0.5).abs() < 1e-12);
+    assert!(model.arrival_probability(200.0) < 0.3);
+
+    let probabilities: Vec<f64> = (1..30)
+        .map(|i| model.arrival_probability(f64::from(i) * 10.0))
+        .collect();
+    assert!(probabilities.windows(2).all(|w| w[1] < w[0]));
+    assert!(travel(0.4).arrival_probability(150.0) > model.arrival_probability(150.0));
+}
+
+#[test]
+fn test_utility_is_value_at_arrival_less_cost() {
+    let model = travel(0.2);
+    let landmark = Landmark::new(80.0, 25.0, 0.9, 0);
+    let expected = model.arrival_probability(30.0) * 0.9 - model.cost(30.0);
+    assert!((model.utility(&landmark, 50.0, 25.0) - expected).abs() < 1e-12);
+    // The same landmark is worth less from farther away
+    assert!(model.utility(&landmark, 0.0, 25.0) < model.utility(&landmark, 50.0, 25.0));
+}
+
+#[test]
+fn test_starving_agents_prefer_what_they_can_reach() {
+    let memory = rich_far_modest_near();
+    // Distance-agnostic ordering chases the rich landmark
+    let best = memory.best_distant_landmark(50.0, 25.0, 5.0).unwrap();
+    assert_eq!(best.x, 140.0);
+
+    // Starving, the far trip is likely fatal: the modest one is chosen
+    let best = memory
+        .best_reachable_landmark(50.0, 25.0, 5.0, &travel(0.1))
+        .unwrap();
+    assert_eq!(best.x, 35.0);
+
+    // With energy to spare the rich landmark is worth the trip again
+    let best = memory
+        .best_reachable_landmark(50.0, 25.0, 5.0, &travel(1.0))
+        .unwrap();
+    assert_eq!(best.x, 140.0);
+}
+
+#[test]
+fn test_unreachable_landmarks_are_not_chosen() {
+    let mut memory = EpisodicMemory::new();
+    memory.maybe_store(140.0, 25.0, 1.0, 0);
+    let model = travel(0.1);
+    assert!(model.arrival_probability(90.0) < LANDMARK_MIN_ARRIVAL);
+    assert!(
+        memory
+            .best_reachable_landmark(50.0, 25.0, 5.0, &model)
+            .is_none()
+    );
+    // Nor is the one the agent is already at
+    assert!(
+        memory
+            .best_reachable_landmark(138.0, 25.0, 5.0, &model)
+            .is_none()
+    );
+    assert!(
+        memory
+            .best_reachable_landmark(120.0, 25.0, 5.0, &model)
+            .is_some()
+    );
+}
+
+#[test]
+fn test_reachable_pins_keep_priority() {
+    let mut memory = rich_far_modest_near();
+    memory.pin(70.0, 25.0, 0);
+    memory.pin(190.0, 25.0, 0);
+    // The near pin beats the landmarks, the far pin is out of reach
+    let best = memory
+        .best_reachable_landmark(50.0, 25.0, 5.0, &travel(0.1))
+        .unwrap();
+    assert!(best.is_pinned());
+    assert_eq!(best.x, 70.0);
+
+    let mut far_pin_only = rich_far_modest_near();
+    far_pin_only.pin(190.0, 25.0, 0);
+    let best = far_pin_only
+        .best_reachable_landmark(50.0, 25.0, 5.0, &travel(0.1))
+        .unwrap();
+    assert!(!best.is_pinned());
+}
+
+#[test]
+fn test_agent_travel_model_follows_its_metabolism() {
+    let mut agent = starving();
+    agent.reserve.level = 0.05;
+    agent.cruise_speed = 0.0;
+    let model = agent.travel_model();
+    assert!((model.stored - 0.15).abs() < 1e-12);
+    // A resting agent still plans the trip at the initial cruise speed
+    let per_second = BASE_METABOLIC_COST + SPEED_METABOLIC_COST * INITIAL_CRUISE_SPEED / MAX_SPEED;
+    assert!((model.cost_per_distance - per_second / INITIAL_CRUISE_SPEED).abs() < 1e-12);
+
+    // Costly samples make every unit of distance dearer
+    agent.morphology.sensing_cost = 0.001;
+    assert!(agent.travel_model().cost_per_distance > model.cost_per_distance);
+}
+
+#[test]
+fn test_starving_agent_heads_for_the_reachable_landmark() {
+    let dish = PetriDish::empty(200.0, 50.0, 1);
+    let mut agent = starving();
+    agent.episodic_memory = rich_far_modest_near();
+    let (target, x, _) = agent.navigation_target().unwrap();
+    assert_eq!((target, x), (ArrivalTarget::Landmark, 35.0));
+    for _ in 0..40 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+    }
+    assert!(agent.x < 45.0, "swam west to x = {}", agent.x);
+
+    // With only the unreachable landmark left, it rests instead of chasing it
+    let mut agent = starving();
+    agent.morphology.dormancy_threshold = 0.2;
+    agent.episodic_memory.maybe_store(140.0, 25.0, 1.0, 0);
+    assert!(agent.navigation_target().is_none());
+    agent.sense(&dish);
+    agent.update_state(&dish);
+    assert!(agent.is_dormant());
+}
@@ -0,0 +1,130 @@
+//! Tests for structured sensory turbulence.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::streams::Stream;
+use protozoa_rust::simulation::turbulence::Turbulence;
+
+fn turbulence(amplitude: f64, seed: u64) -> Turbulence {
+    Turbulence {
+        amplitude,
+        seed,
+        ..Turbulence::default()
+    }
+}
+
+/// Mean absolute difference between samples `dx` apart along a row.
+#[allow(clippy::cast_precision_loss)]
+fn mean_step(noise: &Turbulence, dx: f64) -> f64 {
+    let n = 400;
+    (0..n)
+        .map(|i| {
+            let x = f64::from(i) * 0.37;
+            (noise.sample(x + dx, 13.0, 2.0) - noise.sample(x, 13.0, 2.0)).abs()
+        })
+        .sum::<f64>()
+        / f64::from(n)
+}
+
+#[test]
+fn test_off_by_default() {
+    let dish = PetriDish::generate(4, &GeneratorConfig::default());
+    assert!(dish.turbulence.is_off());
+    for (x, y) in [(10.0, 10.0), (50.0, 25.0), (93.0, 41.0), (-1.0, 5.0)] {
+        assert_eq!(
+            dish.sensed_concentration(x, y),
+            dish.get_concentration(x, y)
+        );
+    }
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.sense(&dish);
+    let (x_l, y_l) = (
+        50.0 + agent.morphology.sensor_dist * agent.morphology.sensor_angle.cos(),
+        25.0 + agent.morphology.sensor_dist * agent.morphology.sensor_angle.sin(),
+    );
+    assert_eq!(agent.val_l, dish.get_concentration(x_l, y_l));
+}
+
+#[test]
+fn test_noise_is_bounded_and_spatially_structured() {
+    let noise = turbulence(0.2, 9);
+    let samples: Vec<f64> = (0..2000)
+        .map(|i| {
+            let i = f64::from(i);
+            noise.sample(i * 0.73, (i * 0.31) % 50.0, i * 0.05)
+        })
+        .collect();
+    assert!(samples.iter().all(|v| v.abs() <= 0.2));
+    let mean = samples.iter().sum::<f64>() / 2000.0;
+    let spread = (samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 2000.0).sqrt();
+    assert!(mean.abs() < 0.02, "centered: {mean}");
+    assert!(spread > 0.02, "not flat: {spread}");
+
+    // Nearby readings are correlated, distant ones are not
+    let near = mean_step(&noise, 0.5);
+    let far = mean_step(&noise, 40.0);
+    assert!(near < 0.3 * far, "near {near}, far {far}");
+}
+
+#[test]
+fn test_noise_drifts_in_time_and_follows_the_seed() {
+    let noise = turbulence(0.2, 9);
+    let at = |t: f64| noise.sample(21.3, 7.9, t);
+    assert_eq!(at(3.0), turbulence(0.2, 9).sample(21.3, 7.9, 3.0));
+    assert!((at(3.0) - at(3.1)).abs() < 0.02, "smooth in time");
+    let later: Vec<f64> = (1..20).map(|k| at(3.0 + f64::from(k) * 7.0)).collect();
+    assert!(later.iter().any(|v| (v - at(3.0)).abs() > 0.02), "drifts");
+    let other = turbulence(0.2, 10);
+    assert!((0..20).any(|i| {
+        let x = f64::from(i) * 3.1;
+        (noise.sample(x, 5.0, 1.0) - other.sample(x, 5.0, 1.0)).abs() > 1e-6
+    }));
+    assert_eq!(turbulence(0.0, 9).sample(21.3, 7.9, 3.0), 0.0);
+}
+
+#[test]
+fn test_config_keys_and_validation() {
+    let config = DishConfig::from_toml_str(
+        "turbulence_amplitude = 0.1\nturbulence_scale = 5.0\nturbulence_period = 2.0",
+    )
+    .unwrap();
+    let generator = GeneratorConfig {
+        dish: config,
+        ..GeneratorConfig::default()
+    };
+    let dish = PetriDish::generate(6, &generator);
+    assert_eq!(
+        dish.turbulence,
+        config.turbulence(Stream::Turbulence.seed(6))
+    );
+    assert!((dish.turbulence.scale - 5.0).abs() < 1e-12);
+    let sensed = dish.sensed_concentration(30.0, 20.0);
+    assert!((0.0..=1.0).contains(&sensed));
+    assert!((sensed - dish.get_concentration(30.0, 20.0)).abs() <= 0.1);
+
+    assert!(DishConfig::from_toml_str("turbulence_amplitude = -0.1").is_err());
+    assert!(DishConfig::from_toml_str("turbulence_scale = 0.0").is_err());
+    assert!(DishConfig::from_toml_str("turbulence_period = -1.0").is_err());
+}
+
+#[test]
+fn test_turbulence_lowers_learned_precision() {
+    let precision_after = |amplitude: f64| {
+        let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, 100.0, 50.0);
+        dish.turbulence = turbulence(amplitude, 3);
+        let mut agent = Protozoa::with_heading(15.0, 25.0, 0.0);
+        agent.seed_streams(3);
+        for _ in 0..300 {
+            dish.update();
+            agent.sense(&dish);
+            agent.update_state(&dish);
+        }
+        agent.precision_estimator.precision_left() + agent.precision_estimator.precision_right()
+    };
+    let calm = precision_after(0.0);
+    let turbulent = precision_after(0.15);
+    assert!(turbulent < calm, "calm {calm}, turbulent {turbulent}");
+}
@@ -0,0 +1,83 @@
+//! Tests for the batched environment API.
+
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::spawn::SpawnConfig;
+use protozoa_rust::simulation::vec_env::{OBS_DIM, OBS_FIELDS, VecEnv};
+
+fn batch(n: u64) -> VecEnv {
+    let seeds: Vec<u64> = (0..n).collect();
+    VecEnv::new(&seeds, &GeneratorConfig::default(), &SpawnConfig::default())
+}
+
+#[test]
+fn test_contiguous_layout() {
+    let env = batch(4);
+    assert_eq!(env.len(), 4);
+    assert_eq!(OBS_FIELDS.len(), OBS_DIM);
+    assert_eq!(env.observations().len(), 4 * OBS_DIM);
+    assert_eq!(env.rewards().len(), 4);
+    assert_eq!(env.dones(), &[false; 4]);
+
+    for (i, run) in env.runs().iter().enumerate() {
+        let row = env.observation(i);
+        assert_eq!(row, &env.observations()[i * OBS_DIM..(i + 1) * OBS_DIM]);
+        assert!((row[0] - run.agent.x).abs() < 1e-12);
+        assert!((row[4] - run.agent.energy).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_step_updates_every_environment() {
+    let mut env = batch(6);
+    env.step_n(20);
+    assert_eq!(env.tick, 20);
+    for (i, run) in env.runs().iter().enumerate() {
+        assert_eq!(run.agent.tick_count, 20);
+        let row = env.observation(i);
+        assert!((row[0] - run.agent.x).abs() < 1e-12);
+        assert!((row[1] - run.agent.y).abs() < 1e-12);
+        assert!((row[7] - run.agent.current_vfe).abs() < 1e-12);
+        assert!(row.iter().all(|v| v.is_finite()));
+    }
+}
+
+#[test]
+fn test_rewards_are_energy_changes() {
+    let mut env = batch(3);
+    let before: Vec<f64> = env.runs().iter().map(|r| r.agent.energy).collect();
+    env.step();
+    for ((run, reward), energy) in env.runs().iter().zip(env.rewards()).zip(before) {
+        assert!((reward - (run.agent.energy - energy)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_done_environments_freeze_until_reset() {
+    // No food: the agent starves, which ends (and freezes) its environment
+    let barren = GeneratorConfig {
+        dish: DishConfig {
+            source_count_min: 0,
+            source_count_max: 0,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    let mut env = VecEnv::new(&[1], &barren, &SpawnConfig::default());
+    let thermal = env.runs()[0].dish.thermal.clone();
+    while !env.dones()[0] {
+        env.step();
+        assert!(env.tick < 10_000, "agent never exhausted");
+    }
+    let frozen_tick = env.runs()[0].agent.tick_count;
+    env.step();
+    assert_eq!(env.runs()[0].agent.tick_count, frozen_tick);
+    assert!(env.rewards()[0].abs() < 1e-12);
+
+    // Reset regenerates the same dish from its seed with a fresh agent
+    env.reset(0);
+    assert!(!env.dones()[0]);
+    assert_eq!(env.runs()[0].agent.tick_count, 0);
+    assert_eq!(env.runs()[0].dish.thermal, thermal);
+    assert!((env.observation(0)[4] - 1.0).abs() < 1e-12);
+}
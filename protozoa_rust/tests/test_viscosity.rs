@@ -0,0 +1,102 @@
+//! Tests for viscosity zones.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::config::DishConfig;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::generator::GeneratorConfig;
+use protozoa_rust::simulation::params::{DISH_HEIGHT, DISH_WIDTH};
+use protozoa_rust::simulation::preset::EnvironmentPreset;
+use protozoa_rust::simulation::viscosity::{ViscosityField, ViscosityZone};
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::field::{VISCOSITY_GLYPH, compute_field_grid};
+
+fn zone(x: f64, y: f64, excess: f64) -> ViscosityField {
+    ViscosityField {
+        zones: vec![ViscosityZone {
+            x,
+            y,
+            radius: 5.0,
+            excess,
+        }],
+    }
+}
+
+#[test]
+fn test_viscosity_field_shape() {
+    let field = zone(50.0, 25.0, 3.0);
+    assert!((field.viscosity_at(50.0, 25.0) - 4.0).abs() < 1e-12);
+    assert!((field.viscosity_at(5.0, 5.0) - 1.0).abs() < 1e-9);
+    assert!(field.viscosity_at(53.0, 25.0) > field.viscosity_at(56.0, 25.0));
+    assert!((ViscosityField::uniform().viscosity_at(50.0, 25.0) - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_generated_zones_follow_config() {
+    let plain = PetriDish::generate(4, &GeneratorConfig::default());
+    assert!(plain.viscosity.zones.is_empty());
+
+    let config = GeneratorConfig {
+        dish: DishConfig {
+            viscosity_zones: 3,
+            ..DishConfig::default()
+        },
+        ..GeneratorConfig::default()
+    };
+    let dish = PetriDish::generate(4, &config);
+    assert_eq!(dish.viscosity.zones.len(), 3);
+    assert_eq!(dish.viscosity, PetriDish::generate(4, &config).viscosity);
+    for z in &dish.viscosity.zones {
+        assert!(z.x >= 0.0 && z.x <= DISH_WIDTH && z.y >= 0.0 && z.y <= DISH_HEIGHT);
+        assert!((dish.get_viscosity(z.x, z.y) - 1.0 - z.excess).abs() < z.excess);
+    }
+
+    let parsed = DishConfig::from_toml_str("viscosity_zones = 2").unwrap();
+    assert_eq!(parsed.viscosity_zones, 2);
+}
+
+#[test]
+fn test_viscosity_slows_agent_and_costs_energy() {
+    let plain = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    let (x, y) = (30.0, 25.0);
+    let mut thick = plain.clone();
+    thick.viscosity = zone(x, y, 3.0);
+
+    // Start hungry so intake cannot saturate energy at 1.0
+    let mut free_agent = Protozoa::new(x, y);
+    free_agent.energy = 0.5;
+    let mut slow_agent = free_agent.clone();
+    free_agent.sense(&plain);
+    slow_agent.sense(&thick);
+    free_agent.update_state(&plain);
+    slow_agent.update_state(&thick);
+
+    assert!(
+        (slow_agent.speed - free_agent.speed / 4.0).abs() < 1e-9,
+        "{} vs {}",
+        slow_agent.speed,
+        free_agent.speed
+    );
+    let moved = |a: &Protozoa| (a.x - x).hypot(a.y - y);
+    assert!(moved(&slow_agent) < moved(&free_agent));
+    assert!(slow_agent.energy < free_agent.energy, "drag costs energy");
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_viscosity_texture_rendering() {
+    let mut dish = PetriDish::from_preset(EnvironmentPreset::Gradient, DISH_WIDTH, DISH_HEIGHT);
+    dish.sources[0].x = 90.0;
+    dish.sources[0].radius = 5.0;
+    let plain = compute_field_grid(&dish, 25, 50);
+    assert!(!plain.iter().any(|l| l.contains(VISCOSITY_GLYPH)));
+
+    // A zone in the empty half shows up as texture on empty cells only
+    dish.viscosity = zone(20.0, 25.0, 3.0);
+    let textured = compute_field_grid(&dish, 25, 50);
+    assert!(textured[12].contains(VISCOSITY_GLYPH));
+    for (a, b) in plain.iter().zip(&textured) {
+        for (p, t) in a.chars().zip(b.chars()) {
+            assert!(p == t || (p == ' ' && t == VISCOSITY_GLYPH));
+        }
+    }
+}
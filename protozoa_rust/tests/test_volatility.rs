@@ -0,0 +1,150 @@
+//! Tests for volatility estimation: the third level over the precision
+//! estimates that scales learning rates and uncertainty growth.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::environment::PetriDish;
+use protozoa_rust::simulation::inference::{GenerativeModel, PrecisionEstimator, Volatility};
+use protozoa_rust::simulation::params::{UNCERTAINTY_GROWTH, VOLATILITY_MAX_FACTOR};
+use protozoa_rust::simulation::streams::standard_normal;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Noisy readings of a level that holds still.
+fn stationary(rng: &mut StdRng, n: usize) -> Vec<f64> {
+    (0..n).map(|_| 0.5 + 0.05 * standard_normal(rng)).collect()
+}
+
+/// Noisy readings of a level on a random walk.
+fn drifting(rng: &mut StdRng, n: usize) -> Vec<f64> {
+    let mut level = 0.5;
+    (0..n)
+        .map(|_| {
+            level += 0.1 * standard_normal(rng);
+            level + 0.05 * standard_normal(rng)
+        })
+        .collect()
+}
+
+fn estimate(readings: &[f64]) -> Volatility {
+    let mut volatility = Volatility::new(1.0);
+    for &reading in readings {
+        volatility.observe(reading);
+    }
+    volatility
+}
+
+#[test]
+fn test_off_by_default() {
+    let mut volatility = Volatility::default();
+    assert!(volatility.is_off());
+    volatility.observe(0.9);
+    assert_eq!(volatility.samples, 0);
+    assert_eq!(volatility.factor(), 1.0);
+    assert_eq!(volatility.growth(UNCERTAINTY_GROWTH), UNCERTAINTY_GROWTH);
+
+    let model = GenerativeModel::default();
+    assert_eq!(model.learning_rate_scale(), 1.0);
+    assert_eq!(model.uncertainty_growth(), UNCERTAINTY_GROWTH);
+}
+
+#[test]
+fn test_couplings_are_validated() {
+    assert!(Volatility::new(0.0).validate().is_ok());
+    assert!(Volatility::new(1.5).validate().is_ok());
+    assert!(Volatility::new(-1.0).validate().is_err());
+    assert!(Volatility::new(f64::NAN).validate().is_err());
+    assert!(Volatility::new(f64::INFINITY).validate().is_err());
+}
+
+#[test]
+fn test_a_calm_world_slows_learning() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let volatility = estimate(&stationary(&mut rng, 400));
+    assert_eq!(volatility.samples, 400);
+    assert!(
+        volatility.log_volatility < -1.0,
+        "μ₃ {}",
+        volatility.log_volatility
+    );
+    assert!(volatility.factor() < 1.0);
+    assert!(volatility.factor() >= 1.0 / VOLATILITY_MAX_FACTOR);
+    // The level tracks the reading
+    assert!((volatility.level_mean - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_a_drifting_world_speeds_learning() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let volatility = estimate(&drifting(&mut rng, 400));
+    assert!(
+        volatility.log_volatility > 0.5,
+        "μ₃ {}",
+        volatility.log_volatility
+    );
+    assert!(volatility.factor() > 1.0);
+    assert!(volatility.factor() <= VOLATILITY_MAX_FACTOR);
+    // Variance grows faster between samples, by the same factor over 1
+    let growth = volatility.growth(UNCERTAINTY_GROWTH);
+    assert!((growth - 1.0 - (UNCERTAINTY_GROWTH - 1.0) * volatility.factor()).abs() < 1e-12);
+    assert!(growth > UNCERTAINTY_GROWTH);
+}
+
+#[test]
+fn test_volatility_follows_a_change_of_regime() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut readings = stationary(&mut rng, 200);
+    readings.extend(drifting(&mut rng, 100));
+    let calm = estimate(&readings[..200]);
+    let after = estimate(&readings);
+    assert!(calm.log_volatility < 0.0);
+    assert!(
+        after.log_volatility > calm.log_volatility + 1.0,
+        "μ₃ {} -> {}",
+        calm.log_volatility,
+        after.log_volatility
+    );
+    assert!(after.factor() > calm.factor());
+}
+
+#[test]
+fn test_precision_rate_scales() {
+    let (mut fixed, mut scaled, mut fast) = (
+        PrecisionEstimator::new(),
+        PrecisionEstimator::new(),
+        PrecisionEstimator::new(),
+    );
+    for _ in 0..10 {
+        fixed.update(0.02, 0.03);
+        scaled.update_scaled(0.02, 0.03, 1.0);
+        fast.update_scaled(0.02, 0.03, 2.0);
+    }
+    // Unscaled is the plain update, bit for bit
+    assert_eq!(fixed.precision_left(), scaled.precision_left());
+    assert_eq!(fixed.precision_right(), scaled.precision_right());
+    // A faster rate forgets the initial uncertainty sooner
+    assert!(fast.precision_left() > fixed.precision_left());
+    // The rate never passes 1
+    let mut capped = PrecisionEstimator::new();
+    capped.update_scaled(0.5, 0.5, 1e6);
+    assert!((capped.precision_left() - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_agents_estimate_volatility_on_each_sample() {
+    let dish = PetriDish::new(100.0, 50.0);
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.seed_streams(9);
+    agent.generative_model.volatility = Volatility::new(1.0);
+    let mut sampled = 0;
+    for _ in 0..80 {
+        agent.sense(&dish);
+        agent.update_state(&dish);
+        sampled += u64::from(agent.sampled);
+    }
+    let volatility = agent.generative_model.volatility;
+    assert_eq!(volatility.samples, sampled);
+    assert!(agent.generative_model.check_finite().is_ok());
+    let scale = agent.generative_model.learning_rate_scale();
+    assert!((1.0 / VOLATILITY_MAX_FACTOR..=VOLATILITY_MAX_FACTOR).contains(&scale));
+    assert_eq!(scale, volatility.factor());
+}
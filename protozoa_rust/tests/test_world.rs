@@ -0,0 +1,183 @@
+//! Tests for multi-dish worlds linked by portals.
+
+use protozoa_rust::simulation::agent::Protozoa;
+use protozoa_rust::simulation::controller::ControllerKind;
+use protozoa_rust::simulation::environment::{Obstacle, PetriDish};
+use protozoa_rust::simulation::generator::GeneratorConfig;
+#[cfg(feature = "tui")]
+use protozoa_rust::simulation::params::DISH_HEIGHT;
+use protozoa_rust::simulation::params::{DISH_WIDTH, PORTAL_MARGIN};
+use protozoa_rust::simulation::world::World;
+#[cfg(feature = "tui")]
+use protozoa_rust::ui::{DashboardState, render::draw_dashboard};
+#[cfg(feature = "tui")]
+use ratatui::{Terminal, backend::TestBackend};
+
+/// Two seeded obstacle-free dishes, so agent runs replay exactly.
+fn two_dishes() -> World {
+    let config = GeneratorConfig {
+        obstacle_count: 0,
+        ..GeneratorConfig::default()
+    };
+    let mut world = World::single(PetriDish::generate(11, &config));
+    world.dishes.push(PetriDish::generate(12, &config));
+    world
+}
+
+#[test]
+fn test_connect_makes_two_way_channel() {
+    let mut world = two_dishes();
+    world.dishes[1].obstacles.push(Obstacle {
+        x_min: 5.0,
+        y_min: 5.0,
+        x_max: 15.0,
+        y_max: 15.0,
+    });
+    world.connect((0, 90.0, 25.0), (1, 10.0, 10.0), 3.0);
+
+    assert_eq!(world.portals.len(), 2);
+    assert!(world.dishes[1].obstacles.is_empty(), "opening kept clear");
+    let out = world.portal_at(0, 91.0, 26.0).unwrap();
+    assert_eq!((out.to, out.to_x, out.to_y), (1, 10.0, 10.0));
+    let back = world.portal_at(1, 10.0, 10.0).unwrap();
+    assert_eq!((back.to, back.to_x, back.to_y), (0, 90.0, 25.0));
+    assert!(world.portal_at(0, 80.0, 25.0).is_none());
+    assert!(
+        world.portal_at(1, 90.0, 25.0).is_none(),
+        "openings are per dish"
+    );
+}
+
+#[test]
+fn test_chain_layout() {
+    let config = GeneratorConfig::default();
+    let world = World::chain(3, &config, 40);
+    assert_eq!(world.len(), 3);
+    assert_eq!(world.portals.len(), 4);
+    assert_eq!(
+        world.dishes[2].sources,
+        PetriDish::generate(42, &config).sources
+    );
+
+    // Right edge of dish 0 leads to the left edge of dish 1
+    let portal = world.portals_in(0).next().unwrap();
+    assert!((portal.x - (DISH_WIDTH - PORTAL_MARGIN)).abs() < 1e-12);
+    assert_eq!(portal.to, 1);
+    assert!((portal.to_x - PORTAL_MARGIN).abs() < 1e-12);
+    assert_eq!(world.portals_in(1).count(), 2);
+    assert_eq!(World::chain(0, &config, 1).len(), 1);
+}
+
+#[test]
+fn test_agent_swims_through_portal() {
+    let mut world = two_dishes();
+    world.connect((0, 50.0, 25.0), (1, 20.0, 20.0), 15.0);
+    let mut agent = Protozoa::with_heading(50.0, 5.0, std::f64::consts::FRAC_PI_2);
+    agent.seed_streams(1);
+
+    let mut migrated = None;
+    for _ in 0..500 {
+        world.update();
+        migrated = world.step_agent(&mut agent);
+        if migrated.is_some() {
+            break;
+        }
+    }
+    assert_eq!(migrated, Some(1));
+    assert_eq!(agent.dish_id, 1);
+    assert_eq!((agent.x, agent.y), (20.0, 20.0));
+    assert!((agent.beliefs.mean.x - 20.0).abs() < 1e-12);
+
+    // Landing on the return portal does not bounce the agent back
+    world.update();
+    assert_eq!(world.step_agent(&mut agent), None);
+    assert_eq!(agent.dish_id, 1);
+}
+
+#[test]
+fn test_memories_are_kept_per_dish() {
+    let mut agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    agent.episodic_memory.pin(30.0, 30.0, 0);
+    agent.set_goal(80.0, 40.0, 1.0);
+
+    agent.migrate(1, 10.0, 10.0, 100.0, 50.0);
+    assert_eq!(agent.episodic_memory.count(), 0, "fresh map in a new dish");
+    assert!(agent.goal.is_none());
+    assert_eq!(agent.dish_memories.len(), 1);
+    assert_eq!(agent.dish_memories[0].dish_id, 0);
+    assert_eq!(agent.dish_memories[0].episodic_memory.count(), 1);
+    assert!(agent.spatial_priors_in(0).is_some());
+    assert!(agent.spatial_priors_in(2).is_none());
+
+    agent.episodic_memory.pin(70.0, 20.0, 1);
+    agent.migrate(0, 90.0, 25.0, 100.0, 50.0);
+    assert_eq!(agent.dish_id, 0);
+    let pinned: Vec<_> = agent.episodic_memory.iter().map(|l| (l.x, l.y)).collect();
+    assert_eq!(pinned, vec![(30.0, 30.0)]);
+    assert_eq!(agent.dish_memories[0].dish_id, 1);
+    assert_eq!((agent.x, agent.y), (90.0, 25.0));
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn test_dish_label_in_panel_title() {
+    let dish = PetriDish::new(DISH_WIDTH, DISH_HEIGHT);
+    let agent = Protozoa::with_heading(50.0, 25.0, 0.0);
+    let mut state = DashboardState::from_agent(&agent, &dish);
+    assert!(state.dish_label.is_none());
+    state.dish_label = Some("Dish 2/3".into());
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal
+        .draw(|f| draw_dashboard(f, vec![" ".repeat(40); 10], &state))
+        .unwrap();
+    let text: String = terminal
+        .backend()
+        .buffer()
+        .content
+        .iter()
+        .map(ratatui::buffer::Cell::symbol)
+        .collect();
+    assert!(text.contains("[Dish 2/3]"));
+}
+
+/// Steps `agent` from just outside the opening to the next dish of a chain
+/// until it swims through; returns the dish it lands in.
+fn swim_through(world: &World, agent: &mut Protozoa) -> Option<usize> {
+    let portal = world
+        .portals_in(agent.dish_id)
+        .find(|p| p.to == agent.dish_id + 1)
+        .copied()
+        .unwrap();
+    (agent.x, agent.y) = (portal.x - portal.radius - 0.2, portal.y);
+    agent.angle = 0.0;
+    agent.beliefs.sync_position(agent.x, agent.y, agent.angle);
+    (0..10).find_map(|_| world.step_agent(agent))
+}
+
+#[test]
+fn test_unvisited_dishes_are_mapped_at_their_resized_size() {
+    let config = GeneratorConfig {
+        obstacle_count: 0,
+        ..GeneratorConfig::default()
+    };
+    let mut world = World::chain(3, &config, 31);
+    // Run and tumble swims straight into the opening between tumbles
+    let mut agent = Protozoa::builder()
+        .heading(0.0)
+        .seed(31)
+        .controller(ControllerKind::RunAndTumble)
+        .build();
+    world.resize(240.0, 80.0, std::slice::from_mut(&mut agent));
+
+    for dish in [1, 2] {
+        assert_eq!(swim_through(&world, &mut agent), Some(dish));
+        assert_eq!(agent.spatial_priors.world_dimensions(), (240.0, 80.0));
+        let (width, height) = agent.spatial_priors.world_dimensions();
+        assert!(agent.x <= width && agent.y <= height);
+    }
+    assert_eq!(
+        agent.spatial_priors_in(1).unwrap().world_dimensions(),
+        (240.0, 80.0)
+    );
+}